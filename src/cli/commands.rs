@@ -0,0 +1,694 @@
+//! Command implementations.
+//!
+//! Commands that touch the library or LLMs start [`Services`] headless;
+//! dice, campaign and backup commands open only what they need, so they
+//! work while the TUI holds the library database.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::mpsc;
+use walkdir::WalkDir;
+
+use super::{
+    AskArgs, BackupCommand, CampaignCommand, CliError, Command, IngestArgs, Output, Result,
+    RollArgs, SearchArgs,
+};
+use crate::config::AppConfig;
+use crate::core::backup::{self, ArchiveOptions, ArchiveSource};
+use crate::core::campaign::dice::{DiceNotation, DiceRoller};
+use crate::core::campaign::random_table::{match_tables_by_name, RandomTableEngine};
+use crate::core::llm::router::{ChatMessage, ChatRequest};
+use crate::core::storage::embedding_index::{BackfillJob, ReembedJob};
+use crate::core::storage::rag::{build_system_prompt, format_context, RagConfig, RagSource};
+use crate::core::storage::search::{
+    fulltext_search, hybrid_search_reranked, HybridSearchConfig, SearchResult,
+};
+use crate::core::storage::SurrealStorage;
+use crate::database::{CampaignBundle, CampaignExportOps, CampaignOps, Database};
+use crate::ingestion::kreuzberg_extractor::DocumentExtractor;
+use crate::tui::events::AppEvent;
+use crate::tui::ingestion::{ingest_file, ImportPlan, ImportTarget};
+use crate::tui::services::{open_credentials, Services};
+
+pub(super) async fn dispatch(command: Command, config: &AppConfig, out: Output) -> Result<()> {
+    match command {
+        Command::Ingest(args) => ingest(args, config, out).await,
+        Command::Search(args) => search(args, config, out).await,
+        Command::Ask(args) => ask(args, config, out).await,
+        Command::Roll(args) => roll(args, config, out).await,
+        Command::Campaign(command) => campaign(command, config, out).await,
+        Command::Backup(command) => backup(command, config, out).await,
+        Command::Reindex => reindex(config, out).await,
+    }
+}
+
+/// Start services without the TUI. The receiver must be kept alive so
+/// progress events have somewhere to go.
+async fn open_services(
+    config: &AppConfig,
+) -> Result<(Services, mpsc::UnboundedReceiver<AppEvent>)> {
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let services = Services::init_headless(config, event_tx)
+        .await
+        .map_err(|e| CliError::Failed(format!("Failed to initialize services: {e}")))?;
+    Ok((services, event_rx))
+}
+
+fn preview(text: &str, max_chars: usize) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() > max_chars {
+        let cut: String = flat.chars().take(max_chars).collect();
+        format!("{cut}…")
+    } else {
+        flat
+    }
+}
+
+// ============================================================================
+// Ingest
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct IngestedDocument {
+    path: PathBuf,
+    library_item_id: Option<String>,
+    /// "new", "updated" or "duplicate"; absent on failure
+    outcome: Option<&'static str>,
+    chunks: usize,
+    error: Option<String>,
+}
+
+/// Expand `paths` into the supported documents they name or contain.
+fn collect_documents(paths: &[PathBuf], recursive: bool) -> Result<Vec<PathBuf>> {
+    let mut documents = Vec::new();
+    for path in paths {
+        if path.is_file() {
+            if !DocumentExtractor::is_supported(path) {
+                return Err(CliError::Usage(format!(
+                    "Unsupported file format: {}",
+                    path.display()
+                )));
+            }
+            documents.push(path.clone());
+        } else if path.is_dir() {
+            let depth = if recursive { usize::MAX } else { 1 };
+            let mut found: Vec<PathBuf> = WalkDir::new(path)
+                .max_depth(depth)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_file())
+                .map(|entry| entry.into_path())
+                .filter(|p| DocumentExtractor::is_supported(p))
+                .collect();
+            found.sort();
+            documents.extend(found);
+        } else {
+            return Err(CliError::NotFound(format!(
+                "No such file or folder: {}",
+                path.display()
+            )));
+        }
+    }
+    Ok(documents)
+}
+
+async fn ingest(args: IngestArgs, config: &AppConfig, out: Output) -> Result<()> {
+    let documents = collect_documents(&args.paths, args.recursive)?;
+    if documents.is_empty() {
+        return Err(CliError::NotFound(
+            "No supported documents found".to_string(),
+        ));
+    }
+    if args.title.is_some() && documents.len() > 1 {
+        return Err(CliError::Usage(
+            "--title only applies to a single document".to_string(),
+        ));
+    }
+    if args.update.is_some() && documents.len() > 1 {
+        return Err(CliError::Usage(
+            "--update only applies to a single document".to_string(),
+        ));
+    }
+    let target = match args.update {
+        Some(ref id) => ImportTarget::Update(id.clone()),
+        None if args.new => ImportTarget::New,
+        None => ImportTarget::Auto,
+    };
+
+    let (services, _events) = open_services(config).await?;
+    if services.embedding_provider.is_none() {
+        out.status("No embedding provider available; chunks are stored without vectors");
+    }
+
+    let total = documents.len();
+    let mut results = Vec::with_capacity(total);
+    for (i, path) in documents.into_iter().enumerate() {
+        out.status(&format!("[{}/{total}] {}", i + 1, path.display()));
+        let outcome = ingest_file(
+            &path,
+            args.title.as_deref(),
+            &args.content_type,
+            target.clone(),
+            services.storage.clone(),
+            services.random_tables.clone(),
+            services.embedding_provider.clone(),
+            services.event_tx.clone(),
+        )
+        .await;
+
+        // A file that looks like a new version of another item is not
+        // ingested until the user says which it is
+        let outcome = match outcome {
+            Ok((
+                ImportPlan::Similar {
+                    library_item_id,
+                    title,
+                    reason,
+                },
+                _,
+            )) => Err(format!(
+                "looks like a new version of \"{title}\" ({reason}); \
+                 rerun with --update {library_item_id} or --new"
+            )),
+            other => other,
+        };
+
+        results.push(match outcome {
+            Ok((plan, chunks)) => {
+                let outcome = match plan {
+                    ImportPlan::New { .. } => "new",
+                    ImportPlan::Update { .. } => "updated",
+                    ImportPlan::Relinked { ref title, .. } => {
+                        out.status(&format!("  moved file of \"{title}\"; re-linked"));
+                        "relinked"
+                    }
+                    ImportPlan::Similar { .. } => "similar",
+                    ImportPlan::Duplicate { ref title, .. } => {
+                        out.status(&format!("  already in the library as \"{title}\""));
+                        "duplicate"
+                    }
+                };
+                IngestedDocument {
+                    path,
+                    library_item_id: Some(plan.library_item_id().to_string()),
+                    outcome: Some(outcome),
+                    chunks,
+                    error: None,
+                }
+            }
+            Err(error) => {
+                out.status(&format!("  failed: {error}"));
+                IngestedDocument {
+                    path,
+                    library_item_id: None,
+                    outcome: None,
+                    chunks: 0,
+                    error: Some(error),
+                }
+            }
+        });
+    }
+
+    out.emit(&results, |results| {
+        for doc in results.iter().filter(|d| d.error.is_none()) {
+            println!(
+                "{}\t{} chunks\t{}",
+                doc.path.display(),
+                doc.chunks,
+                doc.outcome.unwrap_or_default()
+            );
+        }
+    })?;
+
+    let failed = results.iter().filter(|d| d.error.is_some()).count();
+    if failed > 0 {
+        return Err(CliError::Failed(format!(
+            "{failed} of {total} documents failed"
+        )));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Search / Ask
+// ============================================================================
+
+/// Hybrid search when the query can be embedded, keyword search otherwise.
+async fn search_library(
+    services: &Services,
+    query: &str,
+    limit: usize,
+    out: Output,
+) -> Result<Vec<SearchResult>> {
+    let db = services.storage.db();
+    let embedded = match services.embedding_provider {
+        Some(ref provider) => match provider.embed(query).await {
+            Ok(embedding) => Some((provider, embedding)),
+            Err(e) => {
+                log::warn!("Query embedding failed, using keyword search: {e}");
+                out.status(&format!(
+                    "Embedding failed ({e}); using keyword search only"
+                ));
+                None
+            }
+        },
+        None => None,
+    };
+    let results = match embedded {
+        Some((provider, embedding)) => {
+            let config = HybridSearchConfig::default()
+                .with_limit(limit)
+                .with_query_model(provider.model_id());
+            hybrid_search_reranked(
+                db,
+                query,
+                embedding,
+                &config,
+                None,
+                services.reranker.as_deref(),
+            )
+            .await
+        }
+        None => fulltext_search(db, query, limit, None).await,
+    };
+    results.map_err(|e| CliError::Failed(format!("Search failed: {e}")))
+}
+
+async fn search(args: SearchArgs, config: &AppConfig, out: Output) -> Result<()> {
+    let (services, _events) = open_services(config).await?;
+    let results = search_library(&services, &args.query, args.limit, out).await?;
+
+    out.emit(&results, |results| {
+        if results.is_empty() {
+            println!("No results");
+        }
+        for (i, r) in results.iter().enumerate() {
+            let page = r.page_number.map(|p| format!(" p.{p}")).unwrap_or_default();
+            println!("{}. {}{page} ({:.3})", i + 1, r.source, r.score);
+            println!("   {}", preview(&r.content, 160));
+        }
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct Answer {
+    answer: String,
+    provider: String,
+    model: String,
+    sources: Vec<RagSource>,
+}
+
+async fn ask(args: AskArgs, config: &AppConfig, out: Output) -> Result<()> {
+    let (services, _events) = open_services(config).await?;
+    if services.llm.provider_ids().is_empty() {
+        return Err(CliError::Unavailable(
+            "No LLM provider configured (add one in the TUI's Settings view)".to_string(),
+        ));
+    }
+
+    let mut sources = Vec::new();
+    let mut system_prompt = None;
+    if !args.no_context {
+        let rag_config = RagConfig::default();
+        let results = search_library(
+            &services,
+            &args.question,
+            rag_config.search_config.limit,
+            out,
+        )
+        .await?;
+        let formatted = format_context(&results, &rag_config);
+        if formatted.total_bytes > 0 {
+            system_prompt = Some(build_system_prompt(
+                &formatted.text,
+                rag_config.system_prompt_template.as_deref(),
+            ));
+            sources = formatted.sources;
+        }
+    }
+
+    let mut request = ChatRequest::new(vec![ChatMessage::user(args.question)]);
+    if let Some(prompt) = system_prompt {
+        request = request.with_system(prompt);
+    }
+    if let Some(provider) = args.provider {
+        request = request.with_provider(provider);
+    }
+
+    let response = services
+        .llm
+        .chat(request)
+        .await
+        .map_err(|e| CliError::Failed(format!("LLM request failed: {e}")))?;
+
+    let answer = Answer {
+        answer: response.content,
+        provider: response.provider,
+        model: response.model,
+        sources,
+    };
+    out.emit(&answer, |a| {
+        println!("{}", a.answer);
+        if !a.sources.is_empty() {
+            println!();
+            for s in &a.sources {
+                let page = s.page.map(|p| format!(" p.{p}")).unwrap_or_default();
+                println!("  [{}{page}]", s.title);
+            }
+        }
+    })
+}
+
+// ============================================================================
+// Roll
+// ============================================================================
+
+async fn roll(args: RollArgs, config: &AppConfig, out: Output) -> Result<()> {
+    let times = args.times.max(1);
+
+    if let Some(query) = args.expression.strip_prefix('@') {
+        let database = Database::new(&config.data_dir()).await?;
+        let engine = RandomTableEngine::new(Arc::new(database.pool().clone()));
+        let tables = engine
+            .list_tables(None)
+            .await
+            .map_err(|e| CliError::Failed(format!("Failed to load tables: {e}")))?;
+        let table = match_tables_by_name(&tables, query.trim())
+            .first()
+            .map(|t| (*t).clone())
+            .ok_or_else(|| CliError::NotFound(format!("No table matching '{}'", query.trim())))?;
+
+        let mut results = Vec::new();
+        for _ in 0..times {
+            results.push(
+                engine
+                    .quick_roll(&table.id)
+                    .await
+                    .map_err(|e| CliError::Failed(format!("Roll failed: {e}")))?,
+            );
+        }
+        return out.emit(&results, |results| {
+            for r in results {
+                println!("{} ({}): {}", r.table_name, r.roll.total, r.final_text);
+            }
+        });
+    }
+
+    let notation = DiceNotation::parse(&args.expression)
+        .map_err(|e| CliError::Usage(format!("Invalid dice notation: {e}")))?;
+    let roller = DiceRoller::new();
+    let results: Vec<_> = (0..times).map(|_| roller.roll(&notation)).collect();
+    out.emit(&results, |results| {
+        for r in results {
+            println!("{r}");
+        }
+    })
+}
+
+// ============================================================================
+// Campaigns
+// ============================================================================
+
+async fn campaign(command: CampaignCommand, config: &AppConfig, out: Output) -> Result<()> {
+    let database = Database::new(&config.data_dir()).await?;
+
+    match command {
+        CampaignCommand::List => {
+            let campaigns = database.list_campaigns().await?;
+            out.emit(&campaigns, |campaigns| {
+                for c in campaigns {
+                    println!("{}\t{}\t{}", c.id, c.name, c.system);
+                }
+            })
+        }
+        CampaignCommand::Export {
+            campaign_id,
+            output,
+        } => {
+            let bundle = database
+                .export_campaign_bundle(&campaign_id)
+                .await?
+                .ok_or_else(|| CliError::NotFound(format!("Campaign not found: {campaign_id}")))?;
+            let json = serde_json::to_string_pretty(&bundle)?;
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, json)?;
+                    let summary = serde_json::json!({
+                        "campaign_id": campaign_id,
+                        "path": path,
+                    });
+                    out.emit(&summary, |_| {
+                        println!("Exported {} to {}", bundle.campaign.name, path.display())
+                    })
+                }
+                // The export is JSON already; print it as-is either way
+                None => {
+                    println!("{json}");
+                    Ok(())
+                }
+            }
+        }
+        CampaignCommand::Import { file } => {
+            let json = std::fs::read_to_string(&file)?;
+            let bundle: CampaignBundle = serde_json::from_str(&json)?;
+            if database.get_campaign(&bundle.campaign.id).await?.is_some() {
+                return Err(CliError::Failed(format!(
+                    "Campaign {} already exists",
+                    bundle.campaign.id
+                )));
+            }
+            database.import_campaign_bundle(&bundle).await?;
+
+            let summary = serde_json::json!({
+                "campaign_id": bundle.campaign.id,
+                "sessions": bundle.sessions.len(),
+                "npcs": bundle.npcs.len(),
+                "locations": bundle.locations.len(),
+            });
+            out.emit(&summary, |_| {
+                println!("Imported {} ({})", bundle.campaign.name, bundle.campaign.id)
+            })
+        }
+    }
+}
+
+// ============================================================================
+// Backups
+// ============================================================================
+
+/// An archive given by path, or by file name inside the backup directory.
+fn resolve_archive(archive: &Path, config: &AppConfig) -> PathBuf {
+    if archive.exists() {
+        archive.to_path_buf()
+    } else {
+        config.backup_dir().join(archive)
+    }
+}
+
+fn backup_passphrase(config: &AppConfig) -> Result<Option<String>> {
+    let credentials =
+        open_credentials(config).map_err(|e| CliError::Failed(format!("Credential vault: {e}")))?;
+    Ok(backup::resolve_passphrase(&credentials))
+}
+
+async fn backup(command: BackupCommand, config: &AppConfig, out: Output) -> Result<()> {
+    let config = config.clone();
+    let sources = ArchiveSource::defaults(&config.data_dir(), &AppConfig::config_path());
+
+    match command {
+        BackupCommand::Create { description } => {
+            let passphrase = if config.backup.encrypt {
+                Some(backup_passphrase(&config)?.ok_or_else(|| {
+                    CliError::Unavailable(format!(
+                        "Encrypted backups need a passphrase (keyring or {})",
+                        backup::PASSPHRASE_ENV
+                    ))
+                })?)
+            } else {
+                None
+            };
+            let options = ArchiveOptions {
+                description: description.or_else(|| Some("Command-line backup".to_string())),
+                passphrase,
+            };
+            // The export needs the database open, so this fails while the app runs
+            let storage = SurrealStorage::new(config.data_dir().join("surrealdb"))
+                .await
+                .map_err(|e| {
+                    CliError::Unavailable(format!(
+                        "Cannot open SurrealDB ({e}); close the app or back up from it"
+                    ))
+                })?;
+            let database = Database::new(&config.data_dir()).await?;
+            let info = backup::backup_live(
+                storage.clone_db(),
+                database.pool().clone(),
+                sources,
+                config.backup_dir(),
+                config.backup.keep,
+                options,
+            )
+            .await?;
+
+            out.emit(&info, |info| println!("{}", info.path.display()))
+        }
+        BackupCommand::List => {
+            let archives = backup::list_archives(&config.backup_dir())?;
+            out.emit(&archives, |archives| {
+                for a in archives {
+                    let lock = if a.encrypted { " (encrypted)" } else { "" };
+                    println!(
+                        "{}\t{}\t{} bytes{lock}",
+                        a.filename,
+                        a.created_at.format("%Y-%m-%d %H:%M"),
+                        a.size_bytes
+                    );
+                }
+            })
+        }
+        BackupCommand::Verify { archive } => {
+            let archive = resolve_archive(&archive, &config);
+            let passphrase = backup_passphrase(&config)?;
+            let manifest = tokio::task::spawn_blocking(move || {
+                backup::verify_archive(&archive, passphrase.as_deref())
+            })
+            .await
+            .map_err(|e| CliError::Failed(e.to_string()))??;
+
+            out.emit(&manifest, |m| {
+                println!(
+                    "OK: {} files, schema v{}, created {}",
+                    m.entries.len(),
+                    m.schema_version,
+                    m.created_at.format("%Y-%m-%d %H:%M")
+                )
+            })
+        }
+        BackupCommand::Restore { archive, yes } => {
+            if !yes {
+                return Err(CliError::Usage(
+                    "Restoring replaces all current data; pass --yes to confirm".to_string(),
+                ));
+            }
+            let archive = resolve_archive(&archive, &config);
+            let passphrase = backup_passphrase(&config)?;
+            let targets =
+                ArchiveSource::restore_targets(&config.data_dir(), &AppConfig::config_path());
+            let manifest = backup::restore_live(archive, targets, passphrase).await?;
+
+            out.emit(&manifest, |m| {
+                println!(
+                    "Restored {} files; previous data kept as *.pre-restore",
+                    m.entries.len()
+                )
+            })
+        }
+    }
+}
+
+// ============================================================================
+// Reindex
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct ReindexSummary {
+    model: String,
+    /// "backfill" when the index already served the model, else "reembed"
+    mode: &'static str,
+    processed: usize,
+    failed: usize,
+    total: usize,
+}
+
+async fn reindex(config: &AppConfig, out: Output) -> Result<()> {
+    let (services, _events) = open_services(config).await?;
+    let provider = services
+        .embedding_provider
+        .clone()
+        .ok_or_else(|| CliError::Unavailable("No embedding provider available".to_string()))?;
+
+    let model = provider.model_id();
+    let backfill = BackfillJob::new(services.storage.clone_db(), provider.clone())
+        .with_batch_size(config.embedding.batch_size)
+        .with_requests_per_minute(config.embedding.backfill_requests_per_minute);
+    let index_ready = backfill
+        .is_ready()
+        .await
+        .map_err(|e| CliError::Failed(format!("Failed to read embedding index: {e}")))?;
+
+    // Same model: only embed the chunks that lack a vector
+    let summary = if index_ready {
+        out.status(&format!("Embedding chunks without vectors with {model}"));
+        let progress = backfill
+            .run(|p| out.status(&format!("  {}/{} chunks", p.embedded + p.failed, p.total)))
+            .await
+            .map_err(|e| CliError::Failed(format!("Backfill stopped: {e} (run again to resume)")))?;
+        ReindexSummary {
+            model,
+            mode: "backfill",
+            processed: progress.embedded,
+            failed: progress.failed,
+            total: progress.total,
+        }
+    } else {
+        out.status(&format!("Re-embedding library with {model}"));
+        let job = ReembedJob::new(services.storage.clone_db(), provider);
+        let progress = job
+            .run(|p| out.status(&format!("  {}/{} chunks", p.processed, p.total)))
+            .await
+            .map_err(|e| {
+                CliError::Failed(format!("Re-embedding stopped: {e} (run again to resume)"))
+            })?;
+        ReindexSummary {
+            model,
+            mode: "reembed",
+            processed: progress.processed,
+            failed: 0,
+            total: progress.total,
+        }
+    };
+
+    out.emit(&summary, |s| match s.mode {
+        "backfill" if s.failed > 0 => println!(
+            "Embedded {} of {} chunks with {} ({} failed; run again to retry)",
+            s.processed, s.total, s.model, s.failed
+        ),
+        "backfill" => println!("Embedded {} chunks with {}", s.processed, s.model),
+        _ => println!("Re-embedded {} chunks with {}", s.total, s.model),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_collect_documents() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("a.md"), "# A").unwrap();
+        std::fs::write(dir.path().join("b.exe"), "").unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("sub").join("c.txt"), "c").unwrap();
+
+        let flat = collect_documents(&[dir.path().to_path_buf()], false).unwrap();
+        assert_eq!(flat, vec![dir.path().join("a.md")]);
+
+        let deep = collect_documents(&[dir.path().to_path_buf()], true).unwrap();
+        assert_eq!(deep.len(), 2);
+
+        let unsupported = collect_documents(&[dir.path().join("b.exe")], false);
+        assert!(matches!(unsupported, Err(CliError::Usage(_))));
+        let missing = collect_documents(&[dir.path().join("nope")], false);
+        assert!(matches!(missing, Err(CliError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_preview_truncates() {
+        assert_eq!(preview("a  b\nc", 10), "a b c");
+        assert_eq!(preview("abcdef", 3), "abc…");
+    }
+}
@@ -0,0 +1,299 @@
+//! Headless command-line interface.
+//!
+//! `ttttrps <command>` runs a single task against the same services as the
+//! TUI and exits, so ingestion, search, dice and backups can be scripted or
+//! run from cron. Without a command the TUI starts as usual.
+//!
+//! `--json` prints results as JSON on stdout; errors always go to stderr.
+//! Exit codes: 0 success, 1 failure, 2 invalid usage, 3 not found,
+//! 4 a required service (LLM, embeddings, passphrase) is unavailable.
+
+mod commands;
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::config::AppConfig;
+use crate::core::backup::ArchiveError;
+
+// ============================================================================
+// Arguments
+// ============================================================================
+
+/// AI-powered TTRPG assistant. Starts the TUI when no command is given.
+#[derive(Debug, Parser)]
+#[command(name = "ttttrps", version, about)]
+pub struct Cli {
+    /// Print results as JSON.
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Ingest documents or folders of documents into the library.
+    Ingest(IngestArgs),
+    /// Search the library.
+    Search(SearchArgs),
+    /// Ask a question, answered with context from the library.
+    Ask(AskArgs),
+    /// Roll dice notation, or `@name` to roll on a random table.
+    Roll(RollArgs),
+    /// Export, import or list campaigns.
+    #[command(subcommand)]
+    Campaign(CampaignCommand),
+    /// Create, list, verify or restore backup archives.
+    #[command(subcommand)]
+    Backup(BackupCommand),
+    /// Embed chunks that lack a vector from the active embedding model.
+    Reindex,
+}
+
+#[derive(Debug, Args)]
+pub struct IngestArgs {
+    /// Files or folders to ingest.
+    #[arg(required = true)]
+    pub paths: Vec<PathBuf>,
+    /// Content type of the documents.
+    #[arg(
+        long,
+        default_value = "rules",
+        value_parser = ["rules", "fiction", "session_notes", "homebrew"]
+    )]
+    pub content_type: String,
+    /// Title for the document (single file only; defaults to the file name).
+    #[arg(long)]
+    pub title: Option<String>,
+    /// Re-ingest this library item from the document (single file only),
+    /// e.g. when an errata PDF replaces a book under a new file name.
+    #[arg(long, value_name = "ID", conflicts_with = "new")]
+    pub update: Option<String>,
+    /// Add the documents as new library items even if one looks like an
+    /// earlier version of them.
+    #[arg(long)]
+    pub new: bool,
+    /// Descend into subfolders.
+    #[arg(short, long)]
+    pub recursive: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SearchArgs {
+    pub query: String,
+    /// Maximum number of results.
+    #[arg(short, long, default_value_t = 10)]
+    pub limit: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct AskArgs {
+    pub question: String,
+    /// LLM provider to use (defaults to the router's choice).
+    #[arg(long)]
+    pub provider: Option<String>,
+    /// Answer without retrieving library context.
+    #[arg(long)]
+    pub no_context: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RollArgs {
+    /// Dice notation (e.g. `2d6+3`, `4d6kh3`) or `@table name`.
+    pub expression: String,
+    /// Number of times to roll.
+    #[arg(short = 'n', long, default_value_t = 1)]
+    pub times: u32,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CampaignCommand {
+    /// List campaigns.
+    List,
+    /// Export a campaign with its sessions, notes, NPCs and locations.
+    Export {
+        campaign_id: String,
+        /// Write to this file instead of stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import a campaign exported with `campaign export`.
+    Import { file: PathBuf },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BackupCommand {
+    /// Archive all application data now, rotating old archives.
+    Create {
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// List archives in the backup directory.
+    List,
+    /// Check an archive's checksums without restoring it.
+    Verify { archive: PathBuf },
+    /// Replace application data with an archive's contents.
+    Restore {
+        archive: PathBuf,
+        /// Confirm replacing the current data.
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("{0}")]
+    Usage(String),
+
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Unavailable(String),
+
+    #[error("{0}")]
+    Failed(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Archive(#[from] ArchiveError),
+}
+
+pub type Result<T> = std::result::Result<T, CliError>;
+
+impl CliError {
+    /// Process exit code for this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Usage(_) => 2,
+            Self::NotFound(_) | Self::Archive(ArchiveError::NotFound(_)) => 3,
+            Self::Unavailable(_)
+            | Self::Archive(ArchiveError::PassphraseRequired | ArchiveError::InUse) => 4,
+            _ => 1,
+        }
+    }
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+/// Writes command results as text or JSON.
+#[derive(Debug, Clone, Copy)]
+pub struct Output {
+    json: bool,
+}
+
+impl Output {
+    pub fn new(json: bool) -> Self {
+        Self { json }
+    }
+
+    /// Print `value` as JSON, or call `text` to print it for humans.
+    pub fn emit<T: Serialize>(&self, value: &T, text: impl FnOnce(&T)) -> Result<()> {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(value)?);
+        } else {
+            text(value);
+        }
+        Ok(())
+    }
+
+    /// Progress or status line on stderr (text mode only).
+    pub fn status(&self, message: &str) {
+        if !self.json {
+            eprintln!("{message}");
+        }
+    }
+
+    fn error(&self, error: &CliError) {
+        if self.json {
+            let body = serde_json::json!({
+                "error": error.to_string(),
+                "exit_code": error.exit_code(),
+            });
+            eprintln!("{body}");
+        } else {
+            eprintln!("Error: {error}");
+        }
+    }
+}
+
+// ============================================================================
+// Entry Point
+// ============================================================================
+
+/// Run `command` and return the process exit code.
+pub async fn run(command: Command, json: bool, config: &AppConfig) -> i32 {
+    let out = Output::new(json);
+    match commands::dispatch(command, config, out).await {
+        Ok(()) => 0,
+        Err(e) => {
+            log::error!("Command failed: {e}");
+            out.error(&e);
+            e.exit_code()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_subcommands() {
+        let cli = Cli::try_parse_from(["ttttrps", "--json", "roll", "2d6", "-n", "3"]).unwrap();
+        assert!(cli.json);
+        match cli.command {
+            Some(Command::Roll(args)) => {
+                assert_eq!(args.expression, "2d6");
+                assert_eq!(args.times, 3);
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["ttttrps", "campaign", "export", "abc", "-o", "x.json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Campaign(CampaignCommand::Export { ref campaign_id, .. })) if campaign_id == "abc"
+        ));
+
+        assert!(Cli::try_parse_from(["ttttrps"]).unwrap().command.is_none());
+        assert!(
+            Cli::try_parse_from(["ttttrps", "ingest", "--content-type", "poetry", "x.pdf"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(CliError::Usage("x".into()).exit_code(), 2);
+        assert_eq!(CliError::NotFound("x".into()).exit_code(), 3);
+        assert_eq!(CliError::Unavailable("x".into()).exit_code(), 4);
+        assert_eq!(
+            CliError::Archive(ArchiveError::PassphraseRequired).exit_code(),
+            4
+        );
+        assert_eq!(CliError::Archive(ArchiveError::InUse).exit_code(), 4);
+        assert_eq!(CliError::Failed("x".into()).exit_code(), 1);
+    }
+}
@@ -4,7 +4,9 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::core::llm::providers::ProviderConfig;
+use crate::core::npc_memory::NpcMemoryConfig;
 use crate::core::search::embeddings::EmbeddingConfig;
+use crate::core::search::rerank::RerankerConfig;
 use crate::core::voice::types::VoiceConfig;
 
 /// Top-level application configuration.
@@ -16,8 +18,13 @@ pub struct AppConfig {
     pub llm: LlmConfig,
     pub voice: VoiceConfig,
     pub embedding: EmbeddingConfig,
+    pub rerank: RerankerConfig,
     pub budget: BudgetConfig,
     pub transcription: TranscriptionConfig,
+    pub backup: BackupConfig,
+    pub credentials: CredentialsConfig,
+    pub npc_memory: NpcMemoryConfig,
+    pub watch: WatchConfig,
 }
 
 /// Budget enforcement configuration.
@@ -34,6 +41,94 @@ impl Default for BudgetConfig {
     }
 }
 
+/// Scheduled backup configuration.
+///
+/// Encrypted backups read their passphrase from the keyring
+/// (`backup_passphrase`) or `TTTTRPS_BACKUP_PASSPHRASE`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Create archives automatically while the app is running.
+    pub enabled: bool,
+    /// Hours between scheduled archives.
+    pub interval_hours: u64,
+    /// Number of archives to keep; older ones are deleted.
+    pub keep: usize,
+    /// Encrypt archives with the configured passphrase.
+    pub encrypt: bool,
+    /// Override the archive directory (default: `<data_dir>/backups`).
+    pub dir: Option<PathBuf>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: 24,
+            keep: 7,
+            encrypt: false,
+            dir: None,
+        }
+    }
+}
+
+/// Watched library folders.
+///
+/// Supported documents under these folders are ingested when they appear or
+/// change, and their library items are marked as missing when the file goes
+/// away. A `.ttttrps.toml` in a folder sets the content type and game system
+/// for everything below it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    /// Folders to watch (empty disables watching).
+    pub folders: Vec<PathBuf>,
+    /// Seconds between folder scans.
+    pub interval_secs: u64,
+    /// Content type for files not covered by a folder config.
+    pub default_content_type: String,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            folders: Vec::new(),
+            interval_secs: 60,
+            default_content_type: "rules".to_string(),
+        }
+    }
+}
+
+/// Secret storage configuration.
+///
+/// The vault backend keeps API keys and OAuth tokens in an encrypted file
+/// for machines without a Secret Service; it is unlocked at startup or from
+/// `TTTTRPS_VAULT_PASSPHRASE`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CredentialsConfig {
+    /// Secret backend: "keyring" or "vault".
+    pub backend: String,
+    /// Override the vault file (default: `<data_dir>/credentials.vault`).
+    pub vault_path: Option<PathBuf>,
+}
+
+impl Default for CredentialsConfig {
+    fn default() -> Self {
+        Self {
+            backend: "keyring".to_string(),
+            vault_path: None,
+        }
+    }
+}
+
+impl CredentialsConfig {
+    /// Whether the encrypted vault backend is selected.
+    pub fn uses_vault(&self) -> bool {
+        self.backend.eq_ignore_ascii_case("vault")
+    }
+}
+
 /// Transcription provider configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -89,8 +184,13 @@ impl Default for AppConfig {
             llm: LlmConfig::default(),
             voice: VoiceConfig::default(),
             embedding: EmbeddingConfig::default(),
+            rerank: RerankerConfig::default(),
             budget: BudgetConfig::default(),
             transcription: TranscriptionConfig::default(),
+            backup: BackupConfig::default(),
+            credentials: CredentialsConfig::default(),
+            npc_memory: NpcMemoryConfig::default(),
+            watch: WatchConfig::default(),
         }
     }
 }
@@ -152,6 +252,22 @@ impl AppConfig {
             })
     }
 
+    /// Resolved backup archive directory (override or `<data_dir>/backups`).
+    pub fn backup_dir(&self) -> PathBuf {
+        self.backup
+            .dir
+            .clone()
+            .unwrap_or_else(|| self.data_dir().join("backups"))
+    }
+
+    /// Resolved credential vault file (override or `<data_dir>/credentials.vault`).
+    pub fn vault_path(&self) -> PathBuf {
+        self.credentials
+            .vault_path
+            .clone()
+            .unwrap_or_else(|| self.data_dir().join("credentials.vault"))
+    }
+
     /// Save configuration to `~/.config/ttttrps/config.toml`.
     pub fn save(&self) -> Result<(), String> {
         let config_path = Self::config_path();
@@ -167,7 +283,8 @@ impl AppConfig {
         Ok(())
     }
 
-    fn config_path() -> PathBuf {
+    /// Location of the config file.
+    pub fn config_path() -> PathBuf {
         dirs::config_dir()
             .map(|d| d.join("ttttrps").join("config.toml"))
             .unwrap_or_else(|| PathBuf::from("config.toml"))
@@ -216,6 +333,41 @@ mod tests {
         assert_eq!(deserialized.tui.tick_rate_ms, config.tui.tick_rate_ms);
     }
 
+    #[test]
+    fn test_backup_dir_default() {
+        let mut config = AppConfig::default();
+        config.data.data_dir = Some(PathBuf::from("/tmp/custom"));
+        assert_eq!(config.backup_dir(), PathBuf::from("/tmp/custom/backups"));
+        assert!(!config.backup.enabled);
+        assert_eq!(config.backup.keep, 7);
+    }
+
+    #[test]
+    fn test_credentials_default_keyring() {
+        let mut config = AppConfig::default();
+        assert!(!config.credentials.uses_vault());
+        config.data.data_dir = Some(PathBuf::from("/tmp/custom"));
+        assert_eq!(
+            config.vault_path(),
+            PathBuf::from("/tmp/custom/credentials.vault")
+        );
+
+        let parsed: AppConfig = toml::from_str("[credentials]\nbackend = \"vault\"\n").unwrap();
+        assert!(parsed.credentials.uses_vault());
+    }
+
+    #[test]
+    fn test_watch_config_default_disabled() {
+        let config = AppConfig::default();
+        assert!(config.watch.folders.is_empty());
+        assert_eq!(config.watch.default_content_type, "rules");
+
+        let parsed: AppConfig =
+            toml::from_str("[watch]\nfolders = [\"/srv/rulebooks\"]\n").unwrap();
+        assert_eq!(parsed.watch.folders, vec![PathBuf::from("/srv/rulebooks")]);
+        assert_eq!(parsed.watch.interval_secs, 60);
+    }
+
     #[test]
     fn test_llm_config_default_empty() {
         let config = AppConfig::default();
@@ -122,6 +122,17 @@ pub enum ArchetypeError {
         missing_ids: Vec<String>,
     },
 
+    /// A pack's declared dependency is missing, out of order or the wrong version.
+    #[error("Setting pack '{pack_id}' dependency on '{dependency}' not met: {reason}")]
+    SettingPackDependencyError {
+        /// The pack whose dependency failed
+        pack_id: String,
+        /// The required pack ID
+        dependency: String,
+        /// Why the dependency is not met
+        reason: String,
+    },
+
     // =========================================================================
     // Vocabulary Bank Errors
     // =========================================================================
@@ -22,7 +22,7 @@
 //! println!("Loaded {} archetypes from YAML assets", all.len());
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
@@ -30,6 +30,7 @@ use lru::LruCache;
 use tokio::sync::RwLock;
 
 use super::error::{ArchetypeError, Result};
+use super::pack_stack::{merge_pack_stack, validate_pack_stack};
 use super::registry::{ArchetypeEvent, CacheStats};
 use super::resolution::{ResolutionQuery, ResolvedArchetype};
 use super::setting_pack::{SettingPack, SettingPackSummary};
@@ -63,8 +64,9 @@ pub struct InMemoryArchetypeRegistry {
     /// Loaded setting packs (not necessarily active).
     setting_packs: Arc<RwLock<HashMap<String, SettingPack>>>,
 
-    /// Active setting pack per campaign (campaign_id → pack_id).
-    active_packs: Arc<RwLock<HashMap<String, String>>>,
+    /// Active setting pack stack per campaign (campaign_id → [pack_id]),
+    /// lowest precedence first.
+    active_packs: Arc<RwLock<HashMap<String, Vec<String>>>>,
 
     /// Resolution cache with LRU eviction.
     cache: Arc<RwLock<LruCache<String, ResolvedArchetype>>>,
@@ -324,72 +326,109 @@ impl InMemoryArchetypeRegistry {
         packs.values().map(SettingPackSummary::from).collect()
     }
 
-    /// Activate a setting pack for a campaign.
+    /// Activate a setting pack for a campaign, replacing its pack stack.
     pub async fn activate_setting_pack(
         &self,
         pack_id: &str,
         campaign_id: &str,
     ) -> Result<()> {
-        // Verify pack exists
-        let pack = {
-            let packs = self.setting_packs.read().await;
-            packs
-                .get(pack_id)
-                .cloned()
-                .ok_or_else(|| ArchetypeError::SettingPackNotFound(pack_id.to_string()))?
-        };
-
-        // Validate all referenced archetypes exist
-        let archetypes = self.archetypes.read().await;
-        let missing: Vec<String> = pack
-            .archetype_overrides
-            .keys()
-            .filter(|id| !archetypes.contains_key(*id))
-            .cloned()
-            .collect();
+        self.activate_setting_pack_stack(&[pack_id], campaign_id)
+            .await
+    }
 
-        if !missing.is_empty() {
-            return Err(ArchetypeError::SettingPackReferenceError {
-                pack_id: pack_id.to_string(),
-                missing_ids: missing,
-            });
+    /// Replace a campaign's setting pack stack.
+    ///
+    /// Packs are listed from lowest to highest precedence; when several
+    /// define the same field the highest wins (see [`super::pack_stack`]).
+    /// The whole stack is validated before anything changes. An empty stack
+    /// deactivates the campaign.
+    pub async fn activate_setting_pack_stack(
+        &self,
+        pack_ids: &[&str],
+        campaign_id: &str,
+    ) -> Result<()> {
+        if pack_ids.is_empty() {
+            return self.deactivate_setting_pack(campaign_id).await;
         }
-        drop(archetypes);
 
-        // Deactivate any existing pack for this campaign
-        self.deactivate_setting_pack(campaign_id).await?;
+        let packs = {
+            let packs = self.setting_packs.read().await;
+            pack_ids
+                .iter()
+                .map(|id| {
+                    packs
+                        .get(*id)
+                        .cloned()
+                        .ok_or_else(|| ArchetypeError::SettingPackNotFound(id.to_string()))
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
 
-        // Activate new pack
-        {
+        // Validate dependencies and archetype references across the stack
+        let existing: HashSet<String> = self.archetypes.read().await.keys().cloned().collect();
+        validate_pack_stack(&packs, &existing)?;
+
+        let stack: Vec<String> = pack_ids.iter().map(|id| id.to_string()).collect();
+        let old_stack = {
             let mut active = self.active_packs.write().await;
-            active.insert(campaign_id.to_string(), pack_id.to_string());
-        }
+            active
+                .insert(campaign_id.to_string(), stack.clone())
+                .unwrap_or_default()
+        };
 
         self.invalidate_cache_for_campaign(campaign_id).await;
 
-        self.emit_event(ArchetypeEvent::SettingPackActivated {
-            pack_id: pack_id.to_string(),
-            campaign_id: campaign_id.to_string(),
-        })
-        .await;
+        for pack_id in old_stack.iter().filter(|id| !stack.contains(id)) {
+            self.emit_event(ArchetypeEvent::SettingPackDeactivated {
+                pack_id: pack_id.clone(),
+                campaign_id: campaign_id.to_string(),
+            })
+            .await;
+        }
+        for pack_id in stack.iter().filter(|id| !old_stack.contains(id)) {
+            self.emit_event(ArchetypeEvent::SettingPackActivated {
+                pack_id: pack_id.clone(),
+                campaign_id: campaign_id.to_string(),
+            })
+            .await;
+        }
 
         log::info!(
-            "Activated setting pack '{}' for campaign '{}'",
-            pack_id,
+            "Activated setting packs [{}] for campaign '{}'",
+            stack.join(", "),
             campaign_id
         );
+
         Ok(())
     }
 
-    /// Deactivate the setting pack for a campaign.
+    /// Add a setting pack to the top of a campaign's stack.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`activate_setting_pack_stack`](Self::activate_setting_pack_stack);
+    /// in particular `ArchetypeError::SettingPackInvalid` if the pack is
+    /// already active.
+    pub async fn push_setting_pack(&self, pack_id: &str, campaign_id: &str) -> Result<()> {
+        let mut stack = self.get_active_pack_stack(campaign_id).await;
+        stack.push(pack_id.to_string());
+
+        let refs: Vec<&str> = stack.iter().map(String::as_str).collect();
+        self.activate_setting_pack_stack(&refs, campaign_id).await
+    }
+
+    /// Deactivate all setting packs for a campaign.
     pub async fn deactivate_setting_pack(&self, campaign_id: &str) -> Result<()> {
-        let old_pack = {
+        let old_stack = {
             let mut active = self.active_packs.write().await;
-            active.remove(campaign_id)
+            active.remove(campaign_id).unwrap_or_default()
         };
 
-        if let Some(pack_id) = old_pack {
+        if !old_stack.is_empty() {
             self.invalidate_cache_for_campaign(campaign_id).await;
+        }
+
+        for pack_id in old_stack.into_iter().rev() {
             self.emit_event(ArchetypeEvent::SettingPackDeactivated {
                 pack_id,
                 campaign_id: campaign_id.to_string(),
@@ -400,19 +439,27 @@ impl InMemoryArchetypeRegistry {
         Ok(())
     }
 
-    /// Get the active setting pack for a campaign.
+    /// Get the effective setting pack for a campaign: its pack stack merged
+    /// into one pack.
     pub async fn get_active_setting_pack(&self, campaign_id: &str) -> Option<SettingPack> {
-        let active = self.active_packs.read().await;
-        let pack_id = active.get(campaign_id)?;
-
-        let packs = self.setting_packs.read().await;
-        packs.get(pack_id).cloned()
+        let stack = self.get_active_pack_stack(campaign_id).await;
+        let layers: Vec<SettingPack> = {
+            let packs = self.setting_packs.read().await;
+            stack.iter().filter_map(|id| packs.get(id).cloned()).collect()
+        };
+        merge_pack_stack(&layers).map(|merged| merged.pack)
     }
 
-    /// Get the active setting pack ID for a campaign.
+    /// Get the top (highest precedence) active setting pack ID for a campaign.
     pub async fn get_active_pack_id(&self, campaign_id: &str) -> Option<String> {
         let active = self.active_packs.read().await;
-        active.get(campaign_id).cloned()
+        active.get(campaign_id).and_then(|stack| stack.last()).cloned()
+    }
+
+    /// Get a campaign's active setting pack IDs, lowest precedence first.
+    pub async fn get_active_pack_stack(&self, campaign_id: &str) -> Vec<String> {
+        let active = self.active_packs.read().await;
+        active.get(campaign_id).cloned().unwrap_or_default()
     }
 
     // ========================================================================
@@ -515,8 +562,8 @@ impl InMemoryArchetypeRegistry {
         self.setting_packs.clone()
     }
 
-    /// Get a reference to the active packs map for the resolver.
-    pub(crate) fn active_packs(&self) -> Arc<RwLock<HashMap<String, String>>> {
+    /// Get a reference to the active pack stacks for the resolver.
+    pub(crate) fn active_packs(&self) -> Arc<RwLock<HashMap<String, Vec<String>>>> {
         self.active_packs.clone()
     }
 }
@@ -617,6 +664,43 @@ mod tests {
         assert!(!packs.is_empty(), "should load setting packs from YAML");
     }
 
+    #[tokio::test]
+    async fn test_setting_pack_stack() {
+        use crate::core::archetype::setting_pack::ArchetypeOverride;
+
+        let registry = InMemoryArchetypeRegistry::empty();
+        registry
+            .register(Archetype::new("dwarf", "Dwarf", ArchetypeCategory::Race))
+            .await
+            .unwrap();
+        for (id, name) in [("generic", "Hill Dwarf"), ("realms", "Shield Dwarf")] {
+            let dwarf = ArchetypeOverride::new().with_display_name(name);
+            let pack =
+                SettingPack::new(id, id, "dnd5e", "1.0.0").with_archetype_override("dwarf", dwarf);
+            registry.register_setting_pack(pack).await.unwrap();
+        }
+
+        registry.push_setting_pack("generic", "c1").await.unwrap();
+        registry.push_setting_pack("realms", "c1").await.unwrap();
+        assert_eq!(
+            registry.get_active_pack_stack("c1").await,
+            vec!["generic".to_string(), "realms".to_string()]
+        );
+        assert_eq!(registry.get_active_pack_id("c1").await.as_deref(), Some("realms"));
+
+        let merged = registry.get_active_setting_pack("c1").await.unwrap();
+        assert_eq!(
+            merged.archetype_overrides["dwarf"].display_name.as_deref(),
+            Some("Shield Dwarf")
+        );
+
+        // A pack can only appear once in a stack
+        assert!(registry.push_setting_pack("generic", "c1").await.is_err());
+
+        registry.deactivate_setting_pack("c1").await.unwrap();
+        assert!(registry.get_active_setting_pack("c1").await.is_none());
+    }
+
     #[tokio::test]
     async fn test_cache_operations() {
         let registry = InMemoryArchetypeRegistry::new().await;
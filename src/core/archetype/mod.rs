@@ -102,6 +102,7 @@
 //! - [`error`]: Error types for all archetype operations
 //! - [`types`]: Core data models (Archetype, ArchetypeCategory, etc.)
 //! - [`setting_pack`]: Setting pack types for content customization
+//! - [`pack_stack`]: Layered setting pack merging and conflict reports
 //! - [`resolution`]: Query and result types for archetype resolution
 //! - [`meilisearch`]: Meilisearch index configuration and management
 
@@ -113,6 +114,7 @@ pub mod cache;
 pub mod error;
 pub mod integration;
 pub mod memory_registry;
+pub mod pack_stack;
 pub mod search;
 pub mod registry;
 pub mod resolution;
@@ -175,6 +177,9 @@ pub use setting_pack::{
     ArchetypeOverride,
     VocabularyBankOverride,
 
+    // Dependency types
+    PackDependency,
+
     // Vocabulary types
     PhraseDefinition,
     VocabularyBankDefinition,
@@ -185,7 +190,9 @@ pub use setting_pack::{
     // Helper functions
     compare_semver,
     is_valid_semver,
+    is_valid_version_req,
     parse_semver,
+    version_satisfies,
 };
 
 // ============================================================================
@@ -217,6 +224,10 @@ pub use resolver::ArchetypeResolver;
 
 pub use setting_pack_loader::{SettingPackEvent, SettingPackLoader};
 
+pub use pack_stack::{
+    merge_pack_stack, validate_pack_stack, MergedSettingPack, PackConflict, PackConflictReport,
+};
+
 // ============================================================================
 // Re-exports: Cache Types
 // ============================================================================
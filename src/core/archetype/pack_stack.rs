@@ -0,0 +1,572 @@
+//! Layered setting pack stacks.
+//!
+//! A campaign can activate several setting packs at once, for example a
+//! generic fantasy base, a published setting on top of it and homebrew
+//! overrides above that. Packs are ordered from lowest to highest precedence:
+//! when two packs define the same field, the higher pack wins.
+//!
+//! # Precedence Rules
+//!
+//! - **Archetype overrides** merge field by field. Scalar fields (display
+//!   name, description, vocabulary bank, naming cultures, stat tendencies,
+//!   affinity replacement) take the highest pack's value; affinity additions
+//!   merge by trait ID; additional tags and nullified fields are unioned.
+//! - **Vocabulary overrides** merge phrase additions by category (a phrase
+//!   text keeps the highest pack's definition) and union phrase removals.
+//! - **Custom archetypes, vocabulary banks and naming cultures** are replaced
+//!   whole by ID.
+//!
+//! Every field set by more than one pack is listed in the
+//! [`PackConflictReport`] with the pack that won it.
+//!
+//! # Validation
+//!
+//! [`validate_pack_stack`] checks that each pack appears once, that its
+//! declared dependencies sit below it with a matching version, and that its
+//! archetype overrides refer to base archetypes or to custom archetypes
+//! defined by itself or a lower pack.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::error::{ArchetypeError, Result};
+use super::setting_pack::{ArchetypeOverride, SettingPack, VocabularyBankOverride};
+
+// ============================================================================
+// Conflict Report
+// ============================================================================
+
+/// A field defined by more than one pack in a stack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackConflict {
+    /// Dotted path of the field, e.g. `archetypeOverrides.dwarf.displayName`.
+    pub field: String,
+
+    /// Version key (`{pack_id}@{version}`) of the pack whose value is used.
+    pub winner: String,
+
+    /// Version keys of the lower packs whose values were shadowed, lowest first.
+    pub overridden: Vec<String>,
+}
+
+/// Which pack won each contested field of a pack stack.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackConflictReport {
+    /// Version keys of the stack, lowest precedence first.
+    pub layers: Vec<String>,
+
+    /// Contested fields, sorted by field path.
+    pub conflicts: Vec<PackConflict>,
+}
+
+impl PackConflictReport {
+    /// Check if no field was defined by more than one pack.
+    pub fn is_empty(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Get the version key of the pack that won a field, if it was contested.
+    pub fn winner(&self, field: &str) -> Option<&str> {
+        self.conflicts
+            .iter()
+            .find(|c| c.field == field)
+            .map(|c| c.winner.as_str())
+    }
+}
+
+/// A pack stack flattened into a single effective setting pack.
+#[derive(Debug, Clone)]
+pub struct MergedSettingPack {
+    /// The effective pack. Identity fields (ID, version, game system) are
+    /// those of the top pack; content is merged from every layer.
+    pub pack: SettingPack,
+
+    /// Fields defined by more than one layer and the pack that won each.
+    pub report: PackConflictReport,
+}
+
+/// Records which layers set each field, in stack order.
+#[derive(Default)]
+struct FieldTracker {
+    setters: BTreeMap<String, Vec<String>>,
+}
+
+impl FieldTracker {
+    fn record(&mut self, field: String, layer: &str) {
+        self.setters
+            .entry(field)
+            .or_default()
+            .push(layer.to_string());
+    }
+
+    fn into_conflicts(self) -> Vec<PackConflict> {
+        self.setters
+            .into_iter()
+            .filter(|(_, setters)| setters.len() > 1)
+            .map(|(field, mut setters)| {
+                let winner = setters.pop().unwrap_or_default();
+                PackConflict {
+                    field,
+                    winner,
+                    overridden: setters,
+                }
+            })
+            .collect()
+    }
+}
+
+fn layer_key(pack: &SettingPack) -> String {
+    format!("{}@{}", pack.id, pack.version)
+}
+
+/// Iterate a map in key order so merges are deterministic.
+fn sorted<V>(map: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+// ============================================================================
+// Merging
+// ============================================================================
+
+/// Flatten a pack stack (lowest precedence first) into one effective pack.
+///
+/// # Returns
+///
+/// `None` if the stack is empty.
+pub fn merge_pack_stack(layers: &[SettingPack]) -> Option<MergedSettingPack> {
+    let top = layers.last()?;
+
+    let mut merged = SettingPack {
+        name: layers
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" + "),
+        dependencies: Vec::new(),
+        archetype_overrides: HashMap::new(),
+        custom_archetypes: Vec::new(),
+        vocabulary_overrides: HashMap::new(),
+        vocabulary_banks: Vec::new(),
+        naming_cultures: Vec::new(),
+        tags: Vec::new(),
+        ..top.clone()
+    };
+    let mut tracker = FieldTracker::default();
+
+    for layer in layers {
+        let key = layer_key(layer);
+
+        for (base_id, override_def) in sorted(&layer.archetype_overrides) {
+            let target = merged
+                .archetype_overrides
+                .entry(base_id.clone())
+                .or_default();
+            let path = format!("archetypeOverrides.{}", base_id);
+            merge_archetype_override(target, override_def, &path, &key, &mut tracker);
+        }
+
+        for archetype in &layer.custom_archetypes {
+            let id = archetype.id.as_str();
+            tracker.record(format!("customArchetypes.{}", id), &key);
+            match merged
+                .custom_archetypes
+                .iter_mut()
+                .find(|a| a.id.as_str() == id)
+            {
+                Some(existing) => *existing = archetype.clone(),
+                None => merged.custom_archetypes.push(archetype.clone()),
+            }
+        }
+
+        for (bank_id, override_def) in sorted(&layer.vocabulary_overrides) {
+            let target = merged
+                .vocabulary_overrides
+                .entry(bank_id.clone())
+                .or_default();
+            let path = format!("vocabularyOverrides.{}", bank_id);
+            merge_vocabulary_override(target, override_def, &path, &key, &mut tracker);
+        }
+
+        for bank in &layer.vocabulary_banks {
+            tracker.record(format!("vocabularyBanks.{}", bank.id), &key);
+            match merged.vocabulary_banks.iter_mut().find(|b| b.id == bank.id) {
+                Some(existing) => *existing = bank.clone(),
+                None => merged.vocabulary_banks.push(bank.clone()),
+            }
+        }
+
+        for culture in &layer.naming_cultures {
+            tracker.record(format!("namingCultures.{}", culture.culture_id), &key);
+            match merged
+                .naming_cultures
+                .iter_mut()
+                .find(|c| c.culture_id == culture.culture_id)
+            {
+                Some(existing) => *existing = culture.clone(),
+                None => merged.naming_cultures.push(culture.clone()),
+            }
+        }
+
+        for tag in &layer.tags {
+            if !merged.tags.contains(tag) {
+                merged.tags.push(tag.clone());
+            }
+        }
+    }
+
+    Some(MergedSettingPack {
+        pack: merged,
+        report: PackConflictReport {
+            layers: layers.iter().map(layer_key).collect(),
+            conflicts: tracker.into_conflicts(),
+        },
+    })
+}
+
+/// Overlay one layer's archetype override onto the merged override.
+fn merge_archetype_override(
+    target: &mut ArchetypeOverride,
+    layer: &ArchetypeOverride,
+    path: &str,
+    key: &str,
+    tracker: &mut FieldTracker,
+) {
+    if let Some(ref name) = layer.display_name {
+        target.display_name = Some(name.clone());
+        tracker.record(format!("{}.displayName", path), key);
+    }
+    if let Some(ref description) = layer.description {
+        target.description = Some(description.clone());
+        tracker.record(format!("{}.description", path), key);
+    }
+    if let Some(ref replacement) = layer.personality_affinity_replacement {
+        target.personality_affinity_replacement = Some(replacement.clone());
+        tracker.record(format!("{}.personalityAffinityReplacement", path), key);
+    }
+    if let Some(ref bank_id) = layer.vocabulary_bank_id {
+        target.vocabulary_bank_id = Some(bank_id.clone());
+        tracker.record(format!("{}.vocabularyBankId", path), key);
+    }
+    if let Some(ref cultures) = layer.naming_cultures {
+        target.naming_cultures = Some(cultures.clone());
+        tracker.record(format!("{}.namingCultures", path), key);
+    }
+    if let Some(ref stats) = layer.stat_tendencies {
+        target.stat_tendencies = Some(stats.clone());
+        tracker.record(format!("{}.statTendencies", path), key);
+    }
+
+    for affinity in &layer.personality_affinity_additions {
+        tracker.record(
+            format!(
+                "{}.personalityAffinityAdditions.{}",
+                path, affinity.trait_id
+            ),
+            key,
+        );
+        match target
+            .personality_affinity_additions
+            .iter_mut()
+            .find(|a| a.trait_id == affinity.trait_id)
+        {
+            Some(existing) => *existing = affinity.clone(),
+            None => target.personality_affinity_additions.push(affinity.clone()),
+        }
+    }
+
+    for tag in &layer.additional_tags {
+        if !target.additional_tags.contains(tag) {
+            target.additional_tags.push(tag.clone());
+        }
+    }
+    for field in &layer.nullified_fields {
+        if !target.nullified_fields.contains(field) {
+            target.nullified_fields.push(field.clone());
+        }
+    }
+}
+
+/// Overlay one layer's vocabulary bank override onto the merged override.
+fn merge_vocabulary_override(
+    target: &mut VocabularyBankOverride,
+    layer: &VocabularyBankOverride,
+    path: &str,
+    key: &str,
+    tracker: &mut FieldTracker,
+) {
+    if let Some(ref name) = layer.display_name {
+        target.display_name = Some(name.clone());
+        tracker.record(format!("{}.displayName", path), key);
+    }
+    if let Some(ref description) = layer.description {
+        target.description = Some(description.clone());
+        tracker.record(format!("{}.description", path), key);
+    }
+
+    for (category, phrases) in sorted(&layer.phrase_additions) {
+        let merged = target.phrase_additions.entry(category.clone()).or_default();
+        for phrase in phrases {
+            tracker.record(
+                format!("{}.phraseAdditions.{}.{}", path, category, phrase.text),
+                key,
+            );
+            match merged.iter_mut().find(|p| p.text == phrase.text) {
+                Some(existing) => *existing = phrase.clone(),
+                None => merged.push(phrase.clone()),
+            }
+        }
+    }
+
+    for (category, texts) in sorted(&layer.phrase_removals) {
+        let merged = target.phrase_removals.entry(category.clone()).or_default();
+        for text in texts {
+            if !merged.contains(text) {
+                merged.push(text.clone());
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Check that a pack stack (lowest precedence first) can be activated.
+///
+/// # Errors
+///
+/// - `ArchetypeError::SettingPackInvalid` if a pack appears more than once
+/// - `ArchetypeError::SettingPackDependencyError` if a dependency is not
+///   below its dependent or its version does not match
+/// - `ArchetypeError::SettingPackReferenceError` if an override refers to
+///   an archetype that neither exists nor is defined lower in the stack
+pub fn validate_pack_stack(
+    layers: &[SettingPack],
+    existing_archetypes: &HashSet<String>,
+) -> Result<()> {
+    let mut available: HashSet<&str> = existing_archetypes.iter().map(String::as_str).collect();
+
+    for (index, pack) in layers.iter().enumerate() {
+        if layers[..index].iter().any(|p| p.id == pack.id) {
+            return Err(ArchetypeError::SettingPackInvalid {
+                pack_id: pack.id.clone(),
+                reason: "Pack appears more than once in the stack".to_string(),
+            });
+        }
+
+        for dependency in &pack.dependencies {
+            let dependency_error = |reason: String| ArchetypeError::SettingPackDependencyError {
+                pack_id: pack.id.clone(),
+                dependency: dependency.pack_id.clone(),
+                reason,
+            };
+
+            match layers[..index].iter().find(|p| p.id == dependency.pack_id) {
+                Some(below) if !dependency.is_satisfied_by(&below.version) => {
+                    return Err(dependency_error(format!(
+                        "requires version '{}', found {}",
+                        dependency.version, below.version
+                    )));
+                }
+                Some(_) => {}
+                None if layers[index + 1..]
+                    .iter()
+                    .any(|p| p.id == dependency.pack_id) =>
+                {
+                    return Err(dependency_error(
+                        "must be below it in the stack".to_string(),
+                    ));
+                }
+                None => {
+                    return Err(dependency_error("not active".to_string()));
+                }
+            }
+        }
+
+        available.extend(pack.custom_archetypes.iter().map(|a| a.id.as_str()));
+
+        let mut missing: Vec<String> = pack
+            .archetype_overrides
+            .keys()
+            .filter(|id| !available.contains(id.as_str()))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            missing.sort();
+            return Err(ArchetypeError::SettingPackReferenceError {
+                pack_id: pack.id.clone(),
+                missing_ids: missing,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::archetype::setting_pack::{CustomNamingCulture, PhraseDefinition};
+    use crate::core::archetype::types::PersonalityAffinity;
+
+    fn base() -> HashSet<String> {
+        ["dwarf".to_string(), "elf".to_string()]
+            .into_iter()
+            .collect()
+    }
+
+    fn stack() -> Vec<SettingPack> {
+        let generic = SettingPack::new("generic_fantasy", "Generic Fantasy", "dnd5e", "1.2.0")
+            .with_archetype_override(
+                "dwarf",
+                ArchetypeOverride::new()
+                    .with_display_name("Mountain Dwarf")
+                    .with_description("Stout folk of the hills")
+                    .with_affinity_additions(vec![
+                        PersonalityAffinity::new("stubborn", 0.6),
+                        PersonalityAffinity::new("greedy", 0.3),
+                    ]),
+            )
+            .with_naming_culture(CustomNamingCulture::new("dwarvish", "Dwarvish"));
+
+        let realms = SettingPack::new("forgotten_realms", "Forgotten Realms", "dnd5e", "2.0.0")
+            .with_dependency("generic_fantasy", "^1.0")
+            .with_archetype_override(
+                "dwarf",
+                ArchetypeOverride::new()
+                    .with_display_name("Shield Dwarf")
+                    .with_affinity_additions(vec![PersonalityAffinity::new("stubborn", 0.9)]),
+            )
+            .with_naming_culture(CustomNamingCulture::new("dwarvish", "Dwarvish (Realms)"));
+
+        let mut homebrew = SettingPack::new("homebrew", "Homebrew", "dnd5e", "0.1.0")
+            .with_dependency("forgotten_realms", ">=2.0.0, <3.0.0")
+            .with_archetype_override("elf", ArchetypeOverride::new().with_display_name("Sun Elf"));
+        homebrew.vocabulary_overrides.insert(
+            "tavern".to_string(),
+            VocabularyBankOverride::new()
+                .add_phrases("greeting", vec![PhraseDefinition::new("Well met!")]),
+        );
+
+        vec![generic, realms, homebrew]
+    }
+
+    #[test]
+    fn test_merge_precedence_and_report() {
+        let merged = merge_pack_stack(&stack()).unwrap();
+        let pack = &merged.pack;
+
+        assert_eq!(pack.id, "homebrew");
+        assert_eq!(pack.name, "Generic Fantasy + Forgotten Realms + Homebrew");
+
+        let dwarf = &pack.archetype_overrides["dwarf"];
+        assert_eq!(dwarf.display_name.as_deref(), Some("Shield Dwarf"));
+        // Unopposed lower fields survive
+        assert_eq!(
+            dwarf.description.as_deref(),
+            Some("Stout folk of the hills")
+        );
+        let stubborn = dwarf
+            .personality_affinity_additions
+            .iter()
+            .find(|a| a.trait_id == "stubborn")
+            .unwrap();
+        assert_eq!(stubborn.weight, 0.9);
+        assert_eq!(dwarf.personality_affinity_additions.len(), 2);
+
+        assert_eq!(pack.naming_cultures.len(), 1);
+        assert_eq!(pack.naming_cultures[0].display_name, "Dwarvish (Realms)");
+        assert!(pack.vocabulary_overrides.contains_key("tavern"));
+
+        let report = &merged.report;
+        assert_eq!(
+            report.layers,
+            vec![
+                "generic_fantasy@1.2.0",
+                "forgotten_realms@2.0.0",
+                "homebrew@0.1.0"
+            ]
+        );
+        assert_eq!(
+            report.winner("archetypeOverrides.dwarf.displayName"),
+            Some("forgotten_realms@2.0.0")
+        );
+        assert_eq!(
+            report.winner("namingCultures.dwarvish"),
+            Some("forgotten_realms@2.0.0")
+        );
+        assert!(report
+            .winner("archetypeOverrides.dwarf.personalityAffinityAdditions.stubborn")
+            .is_some());
+        assert!(report
+            .winner("archetypeOverrides.elf.displayName")
+            .is_none());
+        assert_eq!(report.conflicts.len(), 3);
+
+        let fields: Vec<&str> = report.conflicts.iter().map(|c| c.field.as_str()).collect();
+        let mut sorted_fields = fields.clone();
+        sorted_fields.sort();
+        assert_eq!(fields, sorted_fields);
+
+        assert!(merge_pack_stack(&[]).is_none());
+    }
+
+    #[test]
+    fn test_validate_stack_dependencies() {
+        let layers = stack();
+        assert!(validate_pack_stack(&layers, &base()).is_ok());
+
+        // Dependency above its dependent
+        let reordered = vec![layers[1].clone(), layers[0].clone()];
+        match validate_pack_stack(&reordered, &base()).unwrap_err() {
+            ArchetypeError::SettingPackDependencyError { reason, .. } => {
+                assert!(reason.contains("below"));
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // Missing dependency
+        assert!(matches!(
+            validate_pack_stack(&layers[1..], &base()),
+            Err(ArchetypeError::SettingPackDependencyError { .. })
+        ));
+
+        // Version outside the required range
+        let mut old_generic = layers[0].clone();
+        old_generic.version = "0.9.0".to_string();
+        let err = validate_pack_stack(&[old_generic, layers[1].clone()], &base()).unwrap_err();
+        assert!(err.to_string().contains("^1.0"));
+
+        // Duplicate pack
+        let duplicated = vec![layers[0].clone(), layers[0].clone()];
+        assert!(matches!(
+            validate_pack_stack(&duplicated, &base()),
+            Err(ArchetypeError::SettingPackInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_stack_references() {
+        let layers = stack();
+        let only_dwarf: HashSet<String> = ["dwarf".to_string()].into_iter().collect();
+        match validate_pack_stack(&layers, &only_dwarf).unwrap_err() {
+            ArchetypeError::SettingPackReferenceError {
+                pack_id,
+                missing_ids,
+            } => {
+                assert_eq!(pack_id, "homebrew");
+                assert_eq!(missing_ids, vec!["elf"]);
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+}
@@ -42,7 +42,7 @@
 //! let all = registry.list(None).await;
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
@@ -57,6 +57,7 @@ use crate::core::wilysearch::error::Error as WilySearchError;
 
 use super::error::{ArchetypeError, Result};
 
+use super::pack_stack::{merge_pack_stack, validate_pack_stack};
 use super::resolution::{ResolutionQuery, ResolvedArchetype};
 use super::setting_pack::{SettingPack, SettingPackSummary};
 use super::types::{Archetype, ArchetypeCategory, ArchetypeId, ArchetypeSummary};
@@ -124,7 +125,7 @@ pub enum ArchetypeEvent {
 ///     +-------------------+-------------------+
 ///     |                   |                   |
 /// archetypes         setting_packs       active_packs
-/// (HashMap)          (HashMap)           (HashMap)
+/// (HashMap)          (HashMap)           (campaign->stack)
 ///     |                   |                   |
 ///     +-------------------+-------------------+
 ///                         |
@@ -147,10 +148,10 @@ pub struct ArchetypeRegistry {
     /// Packs are loaded and validated before activation.
     setting_packs: Arc<RwLock<HashMap<String, SettingPack>>>,
 
-    /// Active setting pack per campaign.
+    /// Active setting pack stack per campaign.
     ///
-    /// Maps campaign_id -> pack_id.
-    active_packs: Arc<RwLock<HashMap<String, String>>>,
+    /// Maps campaign_id -> [pack_id], lowest precedence first; never empty.
+    active_packs: Arc<RwLock<HashMap<String, Vec<String>>>>,
 
     /// Resolution cache with LRU eviction.
     ///
@@ -536,7 +537,7 @@ impl ArchetypeRegistry {
         packs.values().map(SettingPackSummary::from).collect()
     }
 
-    /// Activate a setting pack for a campaign.
+    /// Activate a setting pack for a campaign, replacing its pack stack.
     ///
     /// # Arguments
     ///
@@ -552,76 +553,121 @@ impl ArchetypeRegistry {
         pack_id: &str,
         campaign_id: &str,
     ) -> Result<()> {
-        // Verify pack exists
-        let pack = {
-            let packs = self.setting_packs.read().await;
-            packs
-                .get(pack_id)
-                .cloned()
-                .ok_or_else(|| ArchetypeError::SettingPackNotFound(pack_id.to_string()))?
-        };
-
-        // Validate all referenced archetypes exist
-        let archetypes = self.archetypes.read().await;
-        let missing: Vec<String> = pack
-            .archetype_overrides
-            .keys()
-            .filter(|id| !archetypes.contains_key(*id))
-            .cloned()
-            .collect();
+        self.activate_setting_pack_stack(&[pack_id], campaign_id)
+            .await
+    }
 
-        if !missing.is_empty() {
-            return Err(ArchetypeError::SettingPackReferenceError {
-                pack_id: pack_id.to_string(),
-                missing_ids: missing,
-            });
+    /// Replace a campaign's setting pack stack.
+    ///
+    /// Packs are listed from lowest to highest precedence; when several
+    /// define the same field the highest wins (see [`super::pack_stack`]).
+    /// The whole stack is validated before anything changes. An empty stack
+    /// deactivates the campaign.
+    ///
+    /// # Arguments
+    ///
+    /// * `pack_ids` - IDs of the setting packs, lowest precedence first
+    /// * `campaign_id` - ID of the campaign to activate for
+    ///
+    /// # Errors
+    ///
+    /// - `ArchetypeError::SettingPackNotFound` if a pack doesn't exist
+    /// - `ArchetypeError::SettingPackInvalid` if a pack appears twice
+    /// - `ArchetypeError::SettingPackDependencyError` if a dependency is not met
+    /// - `ArchetypeError::SettingPackReferenceError` if a pack references missing archetypes
+    pub async fn activate_setting_pack_stack(
+        &self,
+        pack_ids: &[&str],
+        campaign_id: &str,
+    ) -> Result<()> {
+        if pack_ids.is_empty() {
+            return self.deactivate_setting_pack(campaign_id).await;
         }
-        drop(archetypes);
 
-        // Deactivate any existing pack for this campaign
-        self.deactivate_setting_pack(campaign_id).await?;
+        let packs = {
+            let packs = self.setting_packs.read().await;
+            pack_ids
+                .iter()
+                .map(|id| {
+                    packs
+                        .get(*id)
+                        .cloned()
+                        .ok_or_else(|| ArchetypeError::SettingPackNotFound(id.to_string()))
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
 
-        // Activate new pack
-        {
+        // Validate dependencies and archetype references across the stack
+        let existing: HashSet<String> = self.archetypes.read().await.keys().cloned().collect();
+        validate_pack_stack(&packs, &existing)?;
+
+        let stack: Vec<String> = pack_ids.iter().map(|id| id.to_string()).collect();
+        let old_stack = {
             let mut active = self.active_packs.write().await;
-            active.insert(campaign_id.to_string(), pack_id.to_string());
-        }
+            active
+                .insert(campaign_id.to_string(), stack.clone())
+                .unwrap_or_default()
+        };
 
         // Invalidate cache for this campaign
         self.invalidate_cache_for_campaign(campaign_id).await;
 
-        // Emit event
-        self.emit_event(ArchetypeEvent::SettingPackActivated {
-            pack_id: pack_id.to_string(),
-            campaign_id: campaign_id.to_string(),
-        })
-        .await;
+        for pack_id in old_stack.iter().filter(|id| !stack.contains(id)) {
+            self.emit_event(ArchetypeEvent::SettingPackDeactivated {
+                pack_id: pack_id.clone(),
+                campaign_id: campaign_id.to_string(),
+            })
+            .await;
+        }
+        for pack_id in stack.iter().filter(|id| !old_stack.contains(id)) {
+            self.emit_event(ArchetypeEvent::SettingPackActivated {
+                pack_id: pack_id.clone(),
+                campaign_id: campaign_id.to_string(),
+            })
+            .await;
+        }
 
         log::info!(
-            "Activated setting pack '{}' for campaign '{}'",
-            pack_id,
+            "Activated setting packs [{}] for campaign '{}'",
+            stack.join(", "),
             campaign_id
         );
 
         Ok(())
     }
 
-    /// Deactivate the setting pack for a campaign.
+    /// Add a setting pack to the top of a campaign's stack.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`activate_setting_pack_stack`](Self::activate_setting_pack_stack);
+    /// in particular `ArchetypeError::SettingPackInvalid` if the pack is
+    /// already active.
+    pub async fn push_setting_pack(&self, pack_id: &str, campaign_id: &str) -> Result<()> {
+        let mut stack = self.get_active_pack_stack(campaign_id).await;
+        stack.push(pack_id.to_string());
+
+        let refs: Vec<&str> = stack.iter().map(String::as_str).collect();
+        self.activate_setting_pack_stack(&refs, campaign_id).await
+    }
+
+    /// Deactivate all setting packs for a campaign.
     ///
     /// # Arguments
     ///
-    /// * `campaign_id` - ID of the campaign to deactivate pack for
+    /// * `campaign_id` - ID of the campaign to deactivate packs for
     pub async fn deactivate_setting_pack(&self, campaign_id: &str) -> Result<()> {
-        let old_pack = {
+        let old_stack = {
             let mut active = self.active_packs.write().await;
-            active.remove(campaign_id)
+            active.remove(campaign_id).unwrap_or_default()
         };
 
-        if let Some(pack_id) = old_pack {
+        if !old_stack.is_empty() {
             // Invalidate cache for this campaign
             self.invalidate_cache_for_campaign(campaign_id).await;
+        }
 
-            // Emit event
+        for pack_id in old_stack.into_iter().rev() {
             self.emit_event(ArchetypeEvent::SettingPackDeactivated {
                 pack_id,
                 campaign_id: campaign_id.to_string(),
@@ -632,19 +678,27 @@ impl ArchetypeRegistry {
         Ok(())
     }
 
-    /// Get the active setting pack for a campaign.
+    /// Get the effective setting pack for a campaign: its pack stack merged
+    /// into one pack.
     pub async fn get_active_setting_pack(&self, campaign_id: &str) -> Option<SettingPack> {
-        let active = self.active_packs.read().await;
-        let pack_id = active.get(campaign_id)?;
-
-        let packs = self.setting_packs.read().await;
-        packs.get(pack_id).cloned()
+        let stack = self.get_active_pack_stack(campaign_id).await;
+        let layers: Vec<SettingPack> = {
+            let packs = self.setting_packs.read().await;
+            stack.iter().filter_map(|id| packs.get(id).cloned()).collect()
+        };
+        merge_pack_stack(&layers).map(|merged| merged.pack)
     }
 
-    /// Get the active setting pack ID for a campaign.
+    /// Get the top (highest precedence) active setting pack ID for a campaign.
     pub async fn get_active_pack_id(&self, campaign_id: &str) -> Option<String> {
         let active = self.active_packs.read().await;
-        active.get(campaign_id).cloned()
+        active.get(campaign_id).and_then(|stack| stack.last()).cloned()
+    }
+
+    /// Get a campaign's active setting pack IDs, lowest precedence first.
+    pub async fn get_active_pack_stack(&self, campaign_id: &str) -> Vec<String> {
+        let active = self.active_packs.read().await;
+        active.get(campaign_id).cloned().unwrap_or_default()
     }
 
     // ========================================================================
@@ -811,8 +865,8 @@ impl ArchetypeRegistry {
         self.setting_packs.clone()
     }
 
-    /// Get a reference to the active packs map for the resolver.
-    pub(crate) fn active_packs(&self) -> Arc<RwLock<HashMap<String, String>>> {
+    /// Get a reference to the active pack stacks for the resolver.
+    pub(crate) fn active_packs(&self) -> Arc<RwLock<HashMap<String, Vec<String>>>> {
         self.active_packs.clone()
     }
 }
@@ -12,7 +12,8 @@
 //! 1. Base Role (e.g., "merchant" archetype)
 //! 2. Race (e.g., "dwarf" archetype)
 //! 3. Class (e.g., "fighter" archetype)
-//! 4. Setting Pack Override (e.g., "forgotten_realms" overrides)
+//! 4. Setting Pack Override (e.g., "forgotten_realms" overrides, or the
+//!    campaign's pack stack merged with the top pack winning)
 //! 5. Direct Archetype ID (explicit archetype takes final precedence)
 //! ```
 //!
@@ -55,6 +56,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use super::error::{ArchetypeError, Result};
+use super::pack_stack::merge_pack_stack;
 use super::resolution::{ResolutionMetadata, ResolutionQuery, ResolvedArchetype};
 use super::setting_pack::{ArchetypeOverride, SettingPack};
 use super::types::{Archetype, ArchetypeCategory, PersonalityAffinity};
@@ -93,8 +95,8 @@ pub struct ArchetypeResolver {
     /// Reference to loaded setting packs.
     setting_packs: Arc<RwLock<HashMap<String, SettingPack>>>,
 
-    /// Reference to active setting pack per campaign.
-    active_packs: Arc<RwLock<HashMap<String, String>>>,
+    /// Reference to the active setting pack stack per campaign.
+    active_packs: Arc<RwLock<HashMap<String, Vec<String>>>>,
 }
 
 impl ArchetypeResolver {
@@ -104,11 +106,11 @@ impl ArchetypeResolver {
     ///
     /// * `archetypes` - Arc reference to the archetypes map
     /// * `setting_packs` - Arc reference to the setting packs map
-    /// * `active_packs` - Arc reference to the active packs map
+    /// * `active_packs` - Arc reference to the active pack stacks
     pub fn new(
         archetypes: Arc<RwLock<HashMap<String, Archetype>>>,
         setting_packs: Arc<RwLock<HashMap<String, SettingPack>>>,
-        active_packs: Arc<RwLock<HashMap<String, String>>>,
+        active_packs: Arc<RwLock<HashMap<String, Vec<String>>>>,
     ) -> Self {
         Self {
             archetypes,
@@ -175,10 +177,10 @@ impl ArchetypeResolver {
         }
 
         // Layer 4: Setting Pack Override
-        let setting_id = self.determine_setting_id(query).await;
-        if let Some(ref setting) = setting_id {
-            layers_checked.push(format!("setting:{}", setting));
-            if let Some(overrides) = self.get_setting_overrides(setting, query).await {
+        let setting_stack = self.determine_setting_stack(query).await;
+        if !setting_stack.is_empty() {
+            layers_checked.push(format!("setting:{}", setting_stack.join("+")));
+            if let Some(overrides) = self.get_setting_overrides(&setting_stack, query).await {
                 resolved =
                     self.apply_setting_overrides(resolved, overrides, &mut merge_count)?;
                 self.check_merge_limit(merge_count)?;
@@ -289,34 +291,42 @@ impl ArchetypeResolver {
             .cloned()
     }
 
-    /// Determine the setting ID to use for resolution.
+    /// Determine the setting packs to use for resolution, lowest precedence
+    /// first.
     ///
     /// Priority:
-    /// 1. Explicit setting in query
-    /// 2. Active pack for campaign (if campaign_id specified)
-    async fn determine_setting_id(&self, query: &ResolutionQuery) -> Option<String> {
+    /// 1. Explicit setting in query (that pack alone)
+    /// 2. Active pack stack for campaign (if campaign_id specified)
+    async fn determine_setting_stack(&self, query: &ResolutionQuery) -> Vec<String> {
         // Explicit setting takes precedence
         if let Some(ref setting) = query.setting {
-            return Some(setting.clone());
+            return vec![setting.clone()];
         }
 
-        // Check for active campaign pack
+        // Check for active campaign packs
         if let Some(ref campaign_id) = query.campaign_id {
             let active = self.active_packs.read().await;
-            return active.get(campaign_id).cloned();
+            return active.get(campaign_id).cloned().unwrap_or_default();
         }
 
-        None
+        Vec::new()
     }
 
     /// Get setting pack overrides applicable to the query.
+    ///
+    /// The packs in `stack` (lowest precedence first) are merged before the
+    /// overrides are looked up, so a higher pack wins each field it sets.
+    /// Packs that are not registered are skipped.
     pub async fn get_setting_overrides(
         &self,
-        setting_id: &str,
+        stack: &[String],
         query: &ResolutionQuery,
     ) -> Option<Vec<ArchetypeOverride>> {
-        let packs = self.setting_packs.read().await;
-        let pack = packs.get(setting_id)?;
+        let layers: Vec<SettingPack> = {
+            let packs = self.setting_packs.read().await;
+            stack.iter().filter_map(|id| packs.get(id).cloned()).collect()
+        };
+        let pack = merge_pack_stack(&layers)?.pack;
 
         let mut overrides = Vec::new();
 
@@ -1205,6 +1215,55 @@ mod tests {
         assert!(metadata.layers_checked.contains(&"race:dwarf".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_resolve_through_campaign_pack_stack() {
+        let mut archetypes = HashMap::new();
+        archetypes.insert(
+            "dwarf".to_string(),
+            Archetype::new("dwarf", "Dwarf", ArchetypeCategory::Race),
+        );
+
+        let base = SettingPack::new("generic", "Generic Fantasy", "dnd5e", "1.0.0")
+            .with_archetype_override(
+                "dwarf",
+                ArchetypeOverride::new()
+                    .with_display_name("Hill Dwarf")
+                    .with_vocabulary_bank("generic_dwarvish"),
+            );
+        let top = SettingPack::new("realms", "Realms", "dnd5e", "1.0.0")
+            .with_archetype_override(
+                "dwarf",
+                ArchetypeOverride::new().with_display_name("Shield Dwarf"),
+            );
+        let packs = HashMap::from([
+            ("generic".to_string(), base),
+            ("realms".to_string(), top),
+        ]);
+        let active = HashMap::from([(
+            "campaign_1".to_string(),
+            vec!["generic".to_string(), "realms".to_string()],
+        )]);
+
+        let resolver = ArchetypeResolver::new(
+            Arc::new(RwLock::new(archetypes)),
+            Arc::new(RwLock::new(packs)),
+            Arc::new(RwLock::new(active)),
+        );
+
+        let query = ResolutionQuery::new()
+            .with_race("dwarf")
+            .with_campaign("campaign_1");
+        let result = resolver.resolve(&query).await.unwrap();
+
+        // The top pack wins the display name; the base pack's bank survives
+        assert_eq!(result.display_name.unwrap().as_ref(), "Shield Dwarf");
+        assert_eq!(result.vocabulary_bank_id.as_deref(), Some("generic_dwarvish"));
+        let metadata = result.resolution_metadata.unwrap();
+        assert!(metadata
+            .layers_checked
+            .contains(&"setting:generic+realms".to_string()));
+    }
+
     #[tokio::test]
     async fn test_resolve_not_found() {
         let resolver = create_resolver_with_archetypes(vec![]);
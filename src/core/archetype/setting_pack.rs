@@ -30,6 +30,10 @@
 //! gameSystem: "dnd5e"
 //! version: "1.0.0"
 //!
+//! dependencies:
+//!   - packId: "generic_fantasy"
+//!     version: "^1.0"
+//!
 //! archetypeOverrides:
 //!   dwarf:
 //!     displayName: "Shield Dwarf"
@@ -74,6 +78,7 @@ use super::types::{
 /// - `description`: Optional detailed description
 /// - `game_system`: Target game system (e.g., "dnd5e", "pathfinder2e")
 /// - `version`: Semantic version for tracking updates
+/// - `dependencies`: Packs that must be active beneath this one
 /// - `archetype_overrides`: Modifications to base archetypes
 /// - `custom_archetypes`: New archetypes scoped to this setting
 /// - `vocabulary_overrides`: Modifications to vocabulary banks
@@ -108,6 +113,13 @@ pub struct SettingPack {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 
+    /// Packs this pack builds on, with version requirements.
+    ///
+    /// Each dependency must sit below this pack in a campaign's pack stack
+    /// so that this pack's content takes precedence over it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<PackDependency>,
+
     /// Archetype overrides keyed by base archetype ID.
     ///
     /// Each entry modifies the corresponding base archetype when
@@ -170,6 +182,7 @@ impl SettingPack {
             version: version.into(),
             author: None,
             url: None,
+            dependencies: Vec::new(),
             archetype_overrides: HashMap::new(),
             custom_archetypes: Vec::new(),
             vocabulary_overrides: HashMap::new(),
@@ -193,6 +206,16 @@ impl SettingPack {
         self
     }
 
+    /// Builder method to add a dependency on another pack.
+    pub fn with_dependency(
+        mut self,
+        pack_id: impl Into<String>,
+        version: impl Into<String>,
+    ) -> Self {
+        self.dependencies.push(PackDependency::new(pack_id, version));
+        self
+    }
+
     /// Builder method to add archetype overrides.
     pub fn with_archetype_override(
         mut self,
@@ -230,6 +253,7 @@ impl SettingPack {
     /// - Version must be valid semantic version (MAJOR.MINOR.PATCH)
     /// - All custom archetypes must be valid
     /// - All naming cultures must have valid IDs
+    /// - Dependencies must name another pack with a valid version requirement
     pub fn validate(&self) -> Result<()> {
         // Validate required fields
         if self.id.is_empty() {
@@ -261,6 +285,25 @@ impl SettingPack {
             });
         }
 
+        // Validate dependencies
+        for dependency in &self.dependencies {
+            if dependency.pack_id.is_empty() || dependency.pack_id == self.id {
+                return Err(ArchetypeError::SettingPackInvalid {
+                    pack_id: self.id.clone(),
+                    reason: format!("Invalid dependency on '{}'", dependency.pack_id),
+                });
+            }
+            if !is_valid_version_req(&dependency.version) {
+                return Err(ArchetypeError::SettingPackInvalid {
+                    pack_id: self.id.clone(),
+                    reason: format!(
+                        "Invalid version requirement '{}' for dependency '{}'",
+                        dependency.version, dependency.pack_id
+                    ),
+                });
+            }
+        }
+
         // Validate custom archetypes
         for archetype in &self.custom_archetypes {
             archetype.validate().map_err(|e| ArchetypeError::SettingPackInvalid {
@@ -336,6 +379,49 @@ impl SettingPackStats {
     }
 }
 
+// ============================================================================
+// PackDependency - Required packs beneath this one
+// ============================================================================
+
+/// A dependency of one setting pack on another.
+///
+/// # Examples
+///
+/// ```yaml
+/// dependencies:
+///   - packId: "generic_fantasy"
+///     version: "^1.2"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackDependency {
+    /// ID of the required pack.
+    pub pack_id: String,
+
+    /// Version requirement (see [`version_satisfies`]); `*` accepts any version.
+    #[serde(default = "any_version")]
+    pub version: String,
+}
+
+fn any_version() -> String {
+    "*".to_string()
+}
+
+impl PackDependency {
+    /// Create a dependency on `pack_id` matching the `version` requirement.
+    pub fn new(pack_id: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            pack_id: pack_id.into(),
+            version: version.into(),
+        }
+    }
+
+    /// Check whether `version` of the required pack satisfies this dependency.
+    pub fn is_satisfied_by(&self, version: &str) -> bool {
+        version_satisfies(version, &self.version).unwrap_or(false)
+    }
+}
+
 // ============================================================================
 // ArchetypeOverride - Modifications to base archetypes
 // ============================================================================
@@ -1034,6 +1120,102 @@ pub fn compare_semver(a: &str, b: &str) -> Option<std::cmp::Ordering> {
     Some((a_maj, a_min, a_pat).cmp(&(b_maj, b_min, b_pat)))
 }
 
+/// Parse a possibly partial version (`1`, `1.2`, `1.2.3`) into its
+/// components, padding missing parts with zero, plus the number of parts given.
+fn parse_partial_semver(version: &str) -> Option<((u32, u32, u32), usize)> {
+    let parts: Vec<u32> = version
+        .split('.')
+        .map(|p| {
+            if !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()) {
+                p.parse().ok()
+            } else {
+                None
+            }
+        })
+        .collect::<Option<_>>()?;
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+
+    let get = |i: usize| parts.get(i).copied().unwrap_or(0);
+    Some(((get(0), get(1), get(2)), parts.len()))
+}
+
+/// Check a single comparator such as `>=1.2.0`, `^1.2` or `~1.2.3`.
+fn comparator_matches(version: (u32, u32, u32), comparator: &str) -> Option<bool> {
+    let comparator = comparator.trim();
+    if comparator == "*" {
+        return Some(true);
+    }
+
+    let (op, rest) = [">=", "<=", ">", "<", "=", "^", "~"]
+        .iter()
+        .find_map(|op| comparator.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("=", comparator));
+    let (req, given) = parse_partial_semver(rest.trim())?;
+
+    Some(match op {
+        ">=" => version >= req,
+        "<=" => version <= req,
+        ">" => version > req,
+        "<" => version < req,
+        // `^1.2.3` allows changes that keep the leftmost non-zero component
+        "^" => {
+            let upper = if req.0 > 0 || given == 1 {
+                (req.0 + 1, 0, 0)
+            } else if req.1 > 0 || given == 2 {
+                (0, req.1 + 1, 0)
+            } else {
+                (0, 0, req.2 + 1)
+            };
+            version >= req && version < upper
+        }
+        // `~1.2.3` allows patch changes; `~1` allows minor changes
+        "~" => {
+            let upper = if given == 1 {
+                (req.0 + 1, 0, 0)
+            } else {
+                (req.0, req.1 + 1, 0)
+            };
+            version >= req && version < upper
+        }
+        // Exact match on the components given (`=1.2` matches any 1.2.x)
+        _ => match given {
+            1 => version.0 == req.0,
+            2 => (version.0, version.1) == (req.0, req.1),
+            _ => version == req,
+        },
+    })
+}
+
+/// Check whether a version satisfies a requirement.
+///
+/// Requirements are comma-separated comparators that must all match:
+/// `*`, `1.2.3` / `=1.2`, `>=1.0.0`, `<2.0.0`, `^1.2` (compatible, same
+/// leftmost non-zero component) and `~1.2.3` (patch updates only).
+///
+/// # Returns
+///
+/// `None` if the version or the requirement is malformed.
+pub fn version_satisfies(version: &str, requirement: &str) -> Option<bool> {
+    let version = parse_semver(version)?;
+    let requirement = requirement.trim();
+    if requirement.is_empty() {
+        return Some(true);
+    }
+
+    let mut satisfied = true;
+    for comparator in requirement.split(',') {
+        satisfied &= comparator_matches(version, comparator)?;
+    }
+    Some(satisfied)
+}
+
+/// Check whether a version requirement is well formed.
+pub fn is_valid_version_req(requirement: &str) -> bool {
+    version_satisfies("0.0.0", requirement).is_some()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1286,6 +1468,58 @@ mod tests {
         assert_eq!(compare_semver("invalid", "1.0.0"), None);
     }
 
+    #[test]
+    fn test_version_satisfies() {
+        assert_eq!(version_satisfies("1.4.2", "*"), Some(true));
+        assert_eq!(version_satisfies("1.4.2", "1.4.2"), Some(true));
+        assert_eq!(version_satisfies("1.4.2", "=1.4"), Some(true));
+        assert_eq!(version_satisfies("1.5.0", "=1.4"), Some(false));
+
+        assert_eq!(version_satisfies("1.9.0", "^1.2"), Some(true));
+        assert_eq!(version_satisfies("2.0.0", "^1.2"), Some(false));
+        assert_eq!(version_satisfies("1.1.0", "^1.2"), Some(false));
+        assert_eq!(version_satisfies("0.2.5", "^0.2.1"), Some(true));
+        assert_eq!(version_satisfies("0.3.0", "^0.2.1"), Some(false));
+
+        assert_eq!(version_satisfies("1.2.9", "~1.2.3"), Some(true));
+        assert_eq!(version_satisfies("1.3.0", "~1.2.3"), Some(false));
+
+        assert_eq!(version_satisfies("1.5.0", ">=1.0.0, <2.0.0"), Some(true));
+        assert_eq!(version_satisfies("2.0.0", ">=1.0.0, <2.0.0"), Some(false));
+
+        assert_eq!(version_satisfies("1.0.0", ">=one"), None);
+        assert_eq!(version_satisfies("latest", "*"), None);
+        assert!(!is_valid_version_req("^1.x"));
+        assert!(is_valid_version_req(">=1.0, <3"));
+    }
+
+    #[test]
+    fn test_validate_dependencies() {
+        let pack = SettingPack::new("fr", "Forgotten Realms", "dnd5e", "1.0.0")
+            .with_dependency("generic_fantasy", "^1.0");
+        assert!(pack.validate().is_ok());
+
+        let pack = SettingPack::new("fr", "Forgotten Realms", "dnd5e", "1.0.0")
+            .with_dependency("generic_fantasy", "newest");
+        assert!(pack.validate().is_err());
+
+        let pack = SettingPack::new("fr", "Forgotten Realms", "dnd5e", "1.0.0")
+            .with_dependency("fr", "*");
+        assert!(pack.validate().is_err());
+
+        let yaml = r#"
+id: "homebrew"
+name: "Homebrew"
+gameSystem: "dnd5e"
+version: "0.1.0"
+dependencies:
+  - packId: "forgotten_realms"
+"#;
+        let pack: SettingPack = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(pack.dependencies[0].version, "*");
+        assert!(pack.dependencies[0].is_satisfied_by("3.1.0"));
+    }
+
     // -------------------------------------------------------------------------
     // Serialization tests
     // -------------------------------------------------------------------------
@@ -4,7 +4,7 @@
 //!
 //! - **Loading**: Parse and validate setting packs from YAML/JSON files
 //! - **Validation**: Ensure required fields and semantic version format
-//! - **Activation**: Enable a pack for a specific campaign
+//! - **Activation**: Enable a pack, or an ordered stack of packs, for a campaign
 //! - **Deactivation**: Remove a pack from a campaign
 //! - **Version Management**: Store and retrieve multiple versions of packs
 //!
@@ -29,6 +29,14 @@
 //! **Key Principle**: Loading a setting pack does not affect any campaigns
 //! until explicitly activated.
 //!
+//! # Pack Stacks
+//!
+//! Each campaign has an ordered stack of active packs, lowest precedence
+//! first (e.g. `generic_fantasy`, `forgotten_realms`, then homebrew). The top
+//! pack wins any field defined by several packs; [`SettingPackLoader::resolve_active`]
+//! returns the merged pack and a report of which pack won each contested
+//! field. See [`super::pack_stack`] for the precedence rules.
+//!
 //! # Thread Safety (CRITICAL-ARCH-002)
 //!
 //! All mutable state is protected by `tokio::sync::RwLock` for async-safe
@@ -52,6 +60,14 @@
 //!     println!("Active pack: {}", active_pack_id);
 //! }
 //!
+//! // Layer homebrew overrides on top and see which pack won each field
+//! loader.push_pack("homebrew", "campaign_123", &existing).await?;
+//! if let Some(merged) = loader.resolve_active("campaign_123").await {
+//!     for conflict in &merged.report.conflicts {
+//!         println!("{} <- {}", conflict.field, conflict.winner);
+//!     }
+//! }
+//!
 //! // Deactivate
 //! loader.deactivate("campaign_123").await?;
 //! ```
@@ -63,6 +79,9 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use super::error::{ArchetypeError, Result};
+use super::pack_stack::{
+    merge_pack_stack, validate_pack_stack, MergedSettingPack, PackConflictReport,
+};
 use super::setting_pack::{compare_semver, SettingPack, SettingPackSummary};
 
 // ============================================================================
@@ -146,7 +165,7 @@ fn parse_version_key(key: &str) -> Option<(&str, &str)> {
 ///     ┌───────────────────┼───────────────────┐
 ///     │                   │                   │
 /// loaded_packs       active_packs       version_index
-/// (versioned)        (campaign->pack)   (pack->versions)
+/// (versioned)        (campaign->stack)  (pack->versions)
 /// ```
 ///
 /// # Thread Safety
@@ -158,10 +177,10 @@ pub struct SettingPackLoader {
     /// This allows storing multiple versions of the same pack.
     loaded_packs: Arc<RwLock<HashMap<String, SettingPack>>>,
 
-    /// Active pack per campaign: `campaign_id -> pack_version_key`.
+    /// Active pack stack per campaign: `campaign_id -> [pack_version_key]`.
     ///
-    /// Only one pack can be active per campaign at a time.
-    active_packs: Arc<RwLock<HashMap<String, String>>>,
+    /// Ordered from lowest to highest precedence; never empty.
+    active_packs: Arc<RwLock<HashMap<String, Vec<String>>>>,
 
     /// Version index: `pack_id -> Vec<version>` (sorted by semver).
     ///
@@ -376,15 +395,16 @@ impl SettingPackLoader {
     // Activation Operations (TASK-ARCH-051)
     // ========================================================================
 
-    /// Activate a setting pack for a campaign.
+    /// Activate a setting pack as the only pack for a campaign.
     ///
     /// This method:
     /// 1. Verifies the pack is loaded
-    /// 2. Validates all archetype references exist in the registry
-    /// 3. Deactivates any previously active pack for this campaign
-    /// 4. Activates the new pack
-    /// 5. Invalidates cache for the campaign
-    /// 6. Emits `SETTING_PACK_ACTIVATED` event
+    /// 2. Validates its dependencies and archetype references
+    /// 3. Replaces the campaign's pack stack with this pack
+    /// 4. Emits `Deactivated` for replaced packs and `Activated` for the new one
+    ///
+    /// A pack with dependencies cannot be activated alone; use
+    /// [`activate_stack`](Self::activate_stack) or [`push_pack`](Self::push_pack).
     ///
     /// # Arguments
     ///
@@ -395,6 +415,7 @@ impl SettingPackLoader {
     /// # Errors
     ///
     /// - `ArchetypeError::SettingPackNotFound` if pack is not loaded
+    /// - `ArchetypeError::SettingPackDependencyError` if the pack has dependencies
     /// - `ArchetypeError::SettingPackReferenceError` if pack references missing archetypes
     ///
     /// # Example
@@ -414,120 +435,171 @@ impl SettingPackLoader {
         campaign_id: &str,
         existing_archetypes: &std::collections::HashSet<String>,
     ) -> Result<()> {
-        // If pack_id contains @, use it as-is; otherwise get latest version
-        let vkey = if pack_id.contains('@') {
-            pack_id.to_string()
-        } else {
-            self.get_latest_version_key(pack_id).await?
-        };
+        self.activate_stack(&[pack_id], campaign_id, existing_archetypes)
+            .await
+    }
 
-        // Verify pack is loaded and get it for validation
-        let pack = {
-            let loaded = self.loaded_packs.read().await;
-            loaded
-                .get(&vkey)
-                .cloned()
-                .ok_or_else(|| ArchetypeError::SettingPackNotFound(vkey.clone()))?
-        };
+    /// Activate a specific version of a setting pack.
+    ///
+    /// # Arguments
+    ///
+    /// * `pack_id` - ID of the pack
+    /// * `version` - Specific version to activate
+    /// * `campaign_id` - ID of the campaign
+    /// * `existing_archetypes` - Set of archetype IDs that exist
+    pub async fn activate_version(
+        &self,
+        pack_id: &str,
+        version: &str,
+        campaign_id: &str,
+        existing_archetypes: &std::collections::HashSet<String>,
+    ) -> Result<()> {
+        let vkey = version_key(pack_id, version);
+        self.activate(&vkey, campaign_id, existing_archetypes).await
+    }
 
-        // Validate all referenced archetypes exist
-        let missing: Vec<String> = pack
-            .archetype_overrides
-            .keys()
-            .filter(|id| !existing_archetypes.contains(*id))
-            .cloned()
-            .collect();
+    /// Replace a campaign's pack stack.
+    ///
+    /// Packs are listed from lowest to highest precedence. Each entry is a
+    /// pack ID (latest version) or a version key (`{pack_id}@{version}`).
+    /// The whole stack is validated before anything changes: each pack may
+    /// appear once, declared dependencies must sit below their dependent with
+    /// a matching version, and archetype overrides must refer to existing
+    /// archetypes or custom archetypes from the same or a lower pack.
+    ///
+    /// An empty stack deactivates the campaign.
+    ///
+    /// # Errors
+    ///
+    /// - `ArchetypeError::SettingPackNotFound` if a pack is not loaded
+    /// - `ArchetypeError::SettingPackInvalid` if a pack appears twice
+    /// - `ArchetypeError::SettingPackDependencyError` if a dependency is not met
+    /// - `ArchetypeError::SettingPackReferenceError` if a pack references missing archetypes
+    pub async fn activate_stack(
+        &self,
+        pack_ids: &[&str],
+        campaign_id: &str,
+        existing_archetypes: &std::collections::HashSet<String>,
+    ) -> Result<()> {
+        if pack_ids.is_empty() {
+            return self.deactivate(campaign_id).await;
+        }
 
-        if !missing.is_empty() {
-            return Err(ArchetypeError::SettingPackReferenceError {
-                pack_id: pack.id.clone(),
-                missing_ids: missing,
-            });
+        let mut vkeys = Vec::with_capacity(pack_ids.len());
+        for pack_id in pack_ids {
+            // If pack_id contains @, use it as-is; otherwise get latest version
+            let vkey = if pack_id.contains('@') {
+                pack_id.to_string()
+            } else {
+                self.get_latest_version_key(pack_id).await?
+            };
+            vkeys.push(vkey);
         }
 
-        // Deactivate any existing pack for this campaign
-        let old_pack = {
-            let active = self.active_packs.read().await;
-            active.get(campaign_id).cloned()
+        let packs = self.packs_for_keys(&vkeys).await?;
+        validate_pack_stack(&packs, existing_archetypes)?;
+
+        let old_vkeys = {
+            let mut active = self.active_packs.write().await;
+            active
+                .insert(campaign_id.to_string(), vkeys.clone())
+                .unwrap_or_default()
         };
 
-        if let Some(old_vkey) = old_pack {
-            self.deactivate_internal(campaign_id, &old_vkey).await?;
+        for old in old_vkeys.iter().filter(|k| !vkeys.contains(k)) {
+            self.deactivate_internal(campaign_id, old).await?;
         }
 
-        // Activate new pack
-        {
-            let mut active = self.active_packs.write().await;
-            active.insert(campaign_id.to_string(), vkey.clone());
-        }
+        for (vkey, pack) in vkeys.iter().zip(&packs) {
+            if old_vkeys.contains(vkey) {
+                continue;
+            }
 
-        // Emit event
-        self.emit_event(SettingPackEvent::Activated {
-            pack_id: pack.id.clone(),
-            campaign_id: campaign_id.to_string(),
-        })
-        .await;
+            self.emit_event(SettingPackEvent::Activated {
+                pack_id: pack.id.clone(),
+                campaign_id: campaign_id.to_string(),
+            })
+            .await;
 
-        log::info!(
-            "Activated setting pack '{}' for campaign '{}'",
-            vkey,
-            campaign_id
-        );
+            log::info!(
+                "Activated setting pack '{}' for campaign '{}'",
+                vkey,
+                campaign_id
+            );
+        }
 
         Ok(())
     }
 
-    /// Activate a specific version of a setting pack.
+    /// Add a pack to the top of a campaign's stack, above all active packs.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `pack_id` - ID of the pack
-    /// * `version` - Specific version to activate
-    /// * `campaign_id` - ID of the campaign
-    /// * `existing_archetypes` - Set of archetype IDs that exist
-    pub async fn activate_version(
+    /// Same as [`activate_stack`](Self::activate_stack); in particular
+    /// `ArchetypeError::SettingPackInvalid` if the pack is already active.
+    pub async fn push_pack(
         &self,
         pack_id: &str,
-        version: &str,
         campaign_id: &str,
         existing_archetypes: &std::collections::HashSet<String>,
     ) -> Result<()> {
-        let vkey = version_key(pack_id, version);
-        self.activate(&vkey, campaign_id, existing_archetypes).await
+        let mut stack = self.get_active_stack(campaign_id).await;
+        stack.push(pack_id.to_string());
+
+        let refs: Vec<&str> = stack.iter().map(String::as_str).collect();
+        self.activate_stack(&refs, campaign_id, existing_archetypes)
+            .await
+    }
+
+    /// Remove one pack (any version) from a campaign's stack.
+    ///
+    /// The remaining stack is revalidated, so a pack that others depend on
+    /// cannot be removed while they are active.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the pack was in the stack.
+    pub async fn remove_pack(
+        &self,
+        pack_id: &str,
+        campaign_id: &str,
+        existing_archetypes: &std::collections::HashSet<String>,
+    ) -> Result<bool> {
+        let stack = self.get_active_stack(campaign_id).await;
+        let remaining: Vec<&str> = stack
+            .iter()
+            .map(String::as_str)
+            .filter(|vkey| parse_version_key(vkey).map(|(pid, _)| pid) != Some(pack_id))
+            .collect();
+
+        if remaining.len() == stack.len() {
+            return Ok(false);
+        }
+
+        self.activate_stack(&remaining, campaign_id, existing_archetypes)
+            .await?;
+        Ok(true)
     }
 
-    /// Deactivate the setting pack for a campaign.
+    /// Deactivate all setting packs for a campaign.
     ///
-    /// This removes the pack from the campaign without deleting the loaded pack.
+    /// This removes the packs from the campaign without deleting the loaded packs.
     ///
     /// # Arguments
     ///
-    /// * `campaign_id` - ID of the campaign to deactivate pack for
+    /// * `campaign_id` - ID of the campaign to deactivate packs for
     ///
     /// # Returns
     ///
     /// `Ok(())` even if no pack was active (idempotent).
     pub async fn deactivate(&self, campaign_id: &str) -> Result<()> {
-        let old_vkey = {
+        let old_vkeys = {
             let mut active = self.active_packs.write().await;
-            active.remove(campaign_id)
+            active.remove(campaign_id).unwrap_or_default()
         };
 
-        if let Some(vkey) = old_vkey {
-            // Extract pack_id from version key
-            if let Some((pack_id, _)) = parse_version_key(&vkey) {
-                self.emit_event(SettingPackEvent::Deactivated {
-                    pack_id: pack_id.to_string(),
-                    campaign_id: campaign_id.to_string(),
-                })
-                .await;
-
-                log::info!(
-                    "Deactivated setting pack '{}' for campaign '{}'",
-                    vkey,
-                    campaign_id
-                );
-            }
+        for vkey in old_vkeys.iter().rev() {
+            self.deactivate_internal(campaign_id, vkey).await?;
         }
 
         Ok(())
@@ -552,7 +624,7 @@ impl SettingPackLoader {
         Ok(())
     }
 
-    /// Get the active setting pack ID for a campaign.
+    /// Get the top (highest precedence) active pack ID for a campaign.
     ///
     /// # Arguments
     ///
@@ -563,10 +635,19 @@ impl SettingPackLoader {
     /// `Some(version_key)` if a pack is active, `None` otherwise.
     pub async fn get_active(&self, campaign_id: &str) -> Option<String> {
         let active = self.active_packs.read().await;
-        active.get(campaign_id).cloned()
+        active.get(campaign_id).and_then(|stack| stack.last()).cloned()
     }
 
-    /// Get the active setting pack for a campaign.
+    /// Get a campaign's active pack stack as version keys, lowest precedence first.
+    pub async fn get_active_stack(&self, campaign_id: &str) -> Vec<String> {
+        let active = self.active_packs.read().await;
+        active.get(campaign_id).cloned().unwrap_or_default()
+    }
+
+    /// Get the top (highest precedence) active setting pack for a campaign.
+    ///
+    /// Use [`resolve_active`](Self::resolve_active) for the content of the
+    /// whole stack.
     ///
     /// # Arguments
     ///
@@ -581,6 +662,26 @@ impl SettingPackLoader {
         loaded.get(&vkey).cloned()
     }
 
+    /// Merge a campaign's active stack into one effective setting pack.
+    ///
+    /// # Returns
+    ///
+    /// The merged pack with its conflict report, or `None` if no pack is
+    /// active. Packs unloaded since activation are skipped.
+    pub async fn resolve_active(&self, campaign_id: &str) -> Option<MergedSettingPack> {
+        let stack = self.get_active_stack(campaign_id).await;
+        let layers: Vec<SettingPack> = {
+            let loaded = self.loaded_packs.read().await;
+            stack.iter().filter_map(|k| loaded.get(k).cloned()).collect()
+        };
+        merge_pack_stack(&layers)
+    }
+
+    /// Report which pack won each field defined by more than one active pack.
+    pub async fn conflict_report(&self, campaign_id: &str) -> Option<PackConflictReport> {
+        self.resolve_active(campaign_id).await.map(|m| m.report)
+    }
+
     /// Check if a pack is active for any campaign.
     ///
     /// # Arguments
@@ -589,18 +690,30 @@ impl SettingPackLoader {
     ///
     /// # Returns
     ///
-    /// List of campaign IDs where this pack (any version) is active.
+    /// List of campaign IDs where this pack (any version) is in the active stack.
     pub async fn get_campaigns_using_pack(&self, pack_id: &str) -> Vec<String> {
         let active = self.active_packs.read().await;
         active
             .iter()
-            .filter_map(|(campaign_id, vkey)| {
-                if let Some((pid, _)) = parse_version_key(vkey) {
-                    if pid == pack_id {
-                        return Some(campaign_id.clone());
-                    }
-                }
-                None
+            .filter(|(_, stack)| {
+                stack
+                    .iter()
+                    .any(|vkey| parse_version_key(vkey).is_some_and(|(pid, _)| pid == pack_id))
+            })
+            .map(|(campaign_id, _)| campaign_id.clone())
+            .collect()
+    }
+
+    /// Look up loaded packs for version keys, in order.
+    async fn packs_for_keys(&self, vkeys: &[String]) -> Result<Vec<SettingPack>> {
+        let loaded = self.loaded_packs.read().await;
+        vkeys
+            .iter()
+            .map(|vkey| {
+                loaded
+                    .get(vkey)
+                    .cloned()
+                    .ok_or_else(|| ArchetypeError::SettingPackNotFound(vkey.clone()))
             })
             .collect()
     }
@@ -1205,6 +1318,113 @@ version: "1.0.0"
         assert!(campaigns.contains(&"campaign_2".to_string()));
     }
 
+    // -------------------------------------------------------------------------
+    // Pack stack tests
+    // -------------------------------------------------------------------------
+
+    async fn load_layered_packs(loader: &SettingPackLoader) {
+        let base = create_test_pack("generic_fantasy", "1.2.0").with_archetype_override(
+            "dwarf",
+            super::super::setting_pack::ArchetypeOverride::new()
+                .with_display_name("Mountain Dwarf"),
+        );
+        let realms = create_test_pack("forgotten_realms", "1.0.0")
+            .with_dependency("generic_fantasy", "^1.0")
+            .with_archetype_override(
+                "dwarf",
+                super::super::setting_pack::ArchetypeOverride::new()
+                    .with_display_name("Shield Dwarf"),
+            );
+        loader.load_pack(base).await.unwrap();
+        loader.load_pack(realms).await.unwrap();
+        loader
+            .load_pack(create_test_pack("homebrew", "0.1.0"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_activate_stack_and_resolve() {
+        let loader = SettingPackLoader::new();
+        load_layered_packs(&loader).await;
+        let existing: HashSet<String> = ["dwarf".to_string()].into_iter().collect();
+
+        loader
+            .activate_stack(&["generic_fantasy", "forgotten_realms"], "campaign_1", &existing)
+            .await
+            .unwrap();
+        loader.push_pack("homebrew", "campaign_1", &existing).await.unwrap();
+
+        assert_eq!(
+            loader.get_active_stack("campaign_1").await,
+            vec!["generic_fantasy@1.2.0", "forgotten_realms@1.0.0", "homebrew@0.1.0"]
+        );
+        assert_eq!(
+            loader.get_active("campaign_1").await,
+            Some("homebrew@0.1.0".to_string())
+        );
+
+        let merged = loader.resolve_active("campaign_1").await.unwrap();
+        assert_eq!(
+            merged.pack.archetype_overrides["dwarf"].display_name.as_deref(),
+            Some("Shield Dwarf")
+        );
+        let report = loader.conflict_report("campaign_1").await.unwrap();
+        assert_eq!(
+            report.winner("archetypeOverrides.dwarf.displayName"),
+            Some("forgotten_realms@1.0.0")
+        );
+
+        let campaigns = loader.get_campaigns_using_pack("generic_fantasy").await;
+        assert_eq!(campaigns, vec!["campaign_1".to_string()]);
+
+        // Pushing an active pack again is rejected
+        assert!(loader.push_pack("homebrew", "campaign_1", &existing).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stack_dependencies_checked() {
+        let loader = SettingPackLoader::new();
+        load_layered_packs(&loader).await;
+        let existing: HashSet<String> = ["dwarf".to_string()].into_iter().collect();
+
+        // Dependency not active
+        let result = loader.activate("forgotten_realms", "campaign_1", &existing).await;
+        assert!(matches!(
+            result,
+            Err(ArchetypeError::SettingPackDependencyError { .. })
+        ));
+        assert!(loader.get_active("campaign_1").await.is_none());
+
+        // A failed activation leaves the existing stack alone
+        loader
+            .activate_stack(&["generic_fantasy", "forgotten_realms"], "campaign_1", &existing)
+            .await
+            .unwrap();
+        let result = loader
+            .remove_pack("generic_fantasy", "campaign_1", &existing)
+            .await;
+        assert!(matches!(
+            result,
+            Err(ArchetypeError::SettingPackDependencyError { .. })
+        ));
+        assert_eq!(loader.get_active_stack("campaign_1").await.len(), 2);
+
+        // Removing the dependent first is fine
+        assert!(loader
+            .remove_pack("forgotten_realms", "campaign_1", &existing)
+            .await
+            .unwrap());
+        assert_eq!(
+            loader.get_active_stack("campaign_1").await,
+            vec!["generic_fantasy@1.2.0"]
+        );
+        assert!(!loader
+            .remove_pack("forgotten_realms", "campaign_1", &existing)
+            .await
+            .unwrap());
+    }
+
     // -------------------------------------------------------------------------
     // Version management tests (TASK-ARCH-052)
     // -------------------------------------------------------------------------
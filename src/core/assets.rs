@@ -1,7 +1,8 @@
 //! Compile-time bundled asset loader for TTRPG content.
 //!
-//! Bundles 35 YAML files (archetypes, vocabulary, setting packs) and 2 TOML
-//! config files into the binary via `include_str!`. Total ~50KB.
+//! Bundles 44 YAML files (archetypes, vocabulary, setting packs, calendars,
+//! game system definitions) and 2 TOML config files into the binary via
+//! `include_str!`. Total ~75KB.
 //!
 //! # Usage
 //!
@@ -11,6 +12,8 @@
 //! let archetypes = AssetLoader::load_archetypes();
 //! let vocab_banks = AssetLoader::load_vocabulary_banks();
 //! let setting_packs = AssetLoader::load_setting_packs();
+//! let calendars = AssetLoader::load_calendars();
+//! let systems = AssetLoader::load_system_definitions();
 //! let synonyms = AssetLoader::load_synonyms().unwrap();
 //! let config = AssetLoader::load_preprocessing_config().unwrap();
 //! ```
@@ -19,6 +22,8 @@ use tracing::{debug, warn};
 
 use super::archetype::setting_pack::{SettingPack, VocabularyBankDefinition};
 use super::archetype::types::Archetype;
+use super::campaign::calendar::Calendar;
+use super::character_gen::definition::SystemDefinition;
 use super::preprocess::config::PreprocessConfig;
 use super::preprocess::synonyms::SynonymMap;
 
@@ -88,6 +93,25 @@ const STYLE_SCHOLARLY: &str = include_str!("../../assets/vocabulary/styles/schol
 const SETTING_GENERIC_FANTASY: &str =
     include_str!("../../assets/setting_packs/generic_fantasy.yaml");
 
+// ============================================================================
+// Compile-time bundled YAML: Calendars (3)
+// ============================================================================
+
+const CALENDAR_GREGORIAN: &str = include_str!("../../assets/calendars/gregorian.yaml");
+const CALENDAR_HARPTOS: &str = include_str!("../../assets/calendars/harptos.yaml");
+const CALENDAR_GOLARION: &str = include_str!("../../assets/calendars/golarion.yaml");
+
+// ============================================================================
+// Compile-time bundled YAML: Game System Definitions (6)
+// ============================================================================
+
+const SYSTEM_BITD: &str = include_str!("../../assets/systems/bitd.yaml");
+const SYSTEM_CYPHER: &str = include_str!("../../assets/systems/cypher.yaml");
+const SYSTEM_MOTHERSHIP: &str = include_str!("../../assets/systems/mothership.yaml");
+const SYSTEM_SAVAGE_WORLDS: &str = include_str!("../../assets/systems/savage_worlds.yaml");
+const SYSTEM_TRAVELLER: &str = include_str!("../../assets/systems/traveller.yaml");
+const SYSTEM_YEAR_ZERO: &str = include_str!("../../assets/systems/year_zero.yaml");
+
 // ============================================================================
 // Compile-time bundled TOML: Config (2)
 // ============================================================================
@@ -151,6 +175,23 @@ const SETTING_PACK_SOURCES: &[(&str, &str)] = &[
     ("generic_fantasy", SETTING_GENERIC_FANTASY),
 ];
 
+/// All preset calendar YAML sources.
+const CALENDAR_SOURCES: &[(&str, &str)] = &[
+    ("gregorian", CALENDAR_GREGORIAN),
+    ("harptos", CALENDAR_HARPTOS),
+    ("golarion", CALENDAR_GOLARION),
+];
+
+/// All bundled game system definition YAML sources.
+const SYSTEM_SOURCES: &[(&str, &str)] = &[
+    ("bitd", SYSTEM_BITD),
+    ("cypher", SYSTEM_CYPHER),
+    ("mothership", SYSTEM_MOTHERSHIP),
+    ("savage_worlds", SYSTEM_SAVAGE_WORLDS),
+    ("traveller", SYSTEM_TRAVELLER),
+    ("year_zero", SYSTEM_YEAR_ZERO),
+];
+
 // ============================================================================
 // AssetLoader
 // ============================================================================
@@ -228,6 +269,49 @@ impl AssetLoader {
         packs
     }
 
+    /// Load the preset calendars (Gregorian, Harptos, Golarion) from bundled YAML.
+    ///
+    /// Returns calendars that parse and validate. Invalid files are logged and skipped.
+    pub fn load_calendars() -> Vec<Calendar> {
+        let mut calendars = Vec::with_capacity(CALENDAR_SOURCES.len());
+
+        for (label, yaml) in CALENDAR_SOURCES {
+            match Calendar::from_yaml_str(yaml) {
+                Ok(calendar) => {
+                    debug!(name = %calendar.name, months = calendar.months.len(), "loaded calendar");
+                    calendars.push(calendar);
+                }
+                Err(e) => {
+                    warn!(file = label, error = %e, "failed to load calendar YAML");
+                }
+            }
+        }
+
+        calendars
+    }
+
+    /// Load the bundled game system definitions for the data-driven character
+    /// generator.
+    ///
+    /// Returns definitions that parse and validate. Invalid files are logged and skipped.
+    pub fn load_system_definitions() -> Vec<SystemDefinition> {
+        let mut definitions = Vec::with_capacity(SYSTEM_SOURCES.len());
+
+        for (label, yaml) in SYSTEM_SOURCES {
+            match SystemDefinition::from_yaml_str(yaml) {
+                Ok(definition) => {
+                    debug!(id = %definition.id, classes = definition.classes.len(), "loaded system definition");
+                    definitions.push(definition);
+                }
+                Err(e) => {
+                    warn!(file = label, error = %e, "failed to load system definition YAML");
+                }
+            }
+        }
+
+        definitions
+    }
+
     /// Load the TTRPG synonym map from bundled TOML.
     ///
     /// Contains 80+ synonym groups for TTRPG terminology.
@@ -262,9 +346,19 @@ impl AssetLoader {
     /// Count of bundled setting pack files.
     pub const SETTING_PACK_COUNT: usize = SETTING_PACK_SOURCES.len();
 
+    /// Count of bundled calendar files.
+    pub const CALENDAR_COUNT: usize = CALENDAR_SOURCES.len();
+
+    /// Count of bundled game system definition files.
+    pub const SYSTEM_COUNT: usize = SYSTEM_SOURCES.len();
+
     /// Total count of all bundled asset files (YAML + TOML).
-    pub const TOTAL_ASSET_COUNT: usize =
-        Self::ARCHETYPE_COUNT + Self::VOCABULARY_COUNT + Self::SETTING_PACK_COUNT + 2;
+    pub const TOTAL_ASSET_COUNT: usize = Self::ARCHETYPE_COUNT
+        + Self::VOCABULARY_COUNT
+        + Self::SETTING_PACK_COUNT
+        + Self::CALENDAR_COUNT
+        + Self::SYSTEM_COUNT
+        + 2;
 }
 
 // ============================================================================
@@ -392,6 +486,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_load_system_definitions() {
+        let definitions = AssetLoader::load_system_definitions();
+        assert_eq!(
+            definitions.len(),
+            AssetLoader::SYSTEM_COUNT,
+            "all {} system definition files should parse and validate",
+            AssetLoader::SYSTEM_COUNT
+        );
+    }
+
     #[test]
     fn test_load_synonyms() {
         let synonyms = AssetLoader::load_synonyms().expect("synonyms TOML should parse");
@@ -418,7 +523,9 @@ mod tests {
         assert_eq!(AssetLoader::ARCHETYPE_COUNT, 21);
         assert_eq!(AssetLoader::VOCABULARY_COUNT, 13);
         assert_eq!(AssetLoader::SETTING_PACK_COUNT, 1);
-        assert_eq!(AssetLoader::TOTAL_ASSET_COUNT, 37);
+        assert_eq!(AssetLoader::CALENDAR_COUNT, 3);
+        assert_eq!(AssetLoader::SYSTEM_COUNT, 6);
+        assert_eq!(AssetLoader::TOTAL_ASSET_COUNT, 46);
     }
 
     #[test]
@@ -0,0 +1,480 @@
+//! Assistant Agent
+//!
+//! Runs the tool-calling loop: ask the model, execute any requested tool
+//! calls (gating state changes behind the GM's approval), feed the results
+//! back, and repeat until the model answers in plain text.
+//!
+//! [`AssistantAgent::run_streaming`] streams every round, so replies that
+//! need no tools arrive token by token just like plain chat.
+
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use thiserror::Error;
+
+use super::tools::{ToolContext, ToolRegistry};
+use crate::core::llm::router::{ChatMessage, ChatRequest, ChatResponse, LLMError, LLMRouter};
+
+/// Text and tool calls produced by one model round.
+struct Round {
+    content: String,
+    tool_calls: Vec<Value>,
+}
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum AgentError {
+    #[error("LLM error: {0}")]
+    Llm(#[from] LLMError),
+
+    #[error("Assistant did not finish within {0} tool steps")]
+    TooManySteps(usize),
+}
+
+pub type AgentResult<T> = Result<T, AgentError>;
+
+// ============================================================================
+// Tool Calls + Traces
+// ============================================================================
+
+/// A tool call requested by the model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: Value,
+}
+
+impl ToolCall {
+    /// Parse an OpenAI-shaped tool call (`{id, function: {name, arguments}}`).
+    ///
+    /// `arguments` may be a JSON-encoded string (OpenAI) or an object.
+    pub fn parse(raw: &Value) -> Option<Self> {
+        let function = raw.get("function")?;
+        let name = function.get("name")?.as_str()?.to_string();
+        let arguments = match function.get("arguments") {
+            Some(Value::String(s)) if s.trim().is_empty() => Value::Object(Default::default()),
+            Some(Value::String(s)) => serde_json::from_str(s).unwrap_or(Value::Null),
+            Some(other) => other.clone(),
+            None => Value::Object(Default::default()),
+        };
+        let id = raw
+            .get("id")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("call_{name}"));
+        Some(Self {
+            id,
+            name,
+            arguments,
+        })
+    }
+
+    pub fn parse_all(raw: &[Value]) -> Vec<Self> {
+        raw.iter().filter_map(Self::parse).collect()
+    }
+}
+
+/// What happened to a tool call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolOutcome {
+    Success(String),
+    Failed(String),
+    Declined,
+    UnknownTool,
+}
+
+/// Record of one executed tool call, shown to the GM in the chat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolTrace {
+    pub tool: String,
+    pub summary: String,
+    pub outcome: ToolOutcome,
+}
+
+impl ToolTrace {
+    /// Text sent back to the model as the tool result
+    fn result_text(&self) -> String {
+        match &self.outcome {
+            ToolOutcome::Success(text) => text.clone(),
+            ToolOutcome::Failed(err) => format!("Error: {err}"),
+            ToolOutcome::Declined => "The GM declined this action.".to_string(),
+            ToolOutcome::UnknownTool => format!("Error: unknown tool '{}'", self.tool),
+        }
+    }
+}
+
+impl fmt::Display for ToolTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.outcome {
+            ToolOutcome::Success(text) => write!(f, "{} → {}", self.summary, text),
+            ToolOutcome::Failed(err) => write!(f, "{} failed: {}", self.summary, err),
+            ToolOutcome::Declined => write!(f, "{} (declined)", self.summary),
+            ToolOutcome::UnknownTool => write!(f, "unknown tool '{}'", self.tool),
+        }
+    }
+}
+
+// ============================================================================
+// Approval
+// ============================================================================
+
+/// Decides whether a state-changing tool call may run.
+#[async_trait]
+pub trait ToolApproval: Send + Sync {
+    async fn approve(&self, call: &ToolCall, summary: &str) -> bool;
+}
+
+/// Approves every call (headless use and tests).
+pub struct AutoApprove;
+
+#[async_trait]
+impl ToolApproval for AutoApprove {
+    async fn approve(&self, _call: &ToolCall, _summary: &str) -> bool {
+        true
+    }
+}
+
+// ============================================================================
+// Agent
+// ============================================================================
+
+/// Tool-calling GM assistant.
+pub struct AssistantAgent {
+    router: LLMRouter,
+    registry: Arc<ToolRegistry>,
+    context: ToolContext,
+    max_steps: usize,
+}
+
+impl AssistantAgent {
+    /// Model round-trips allowed before giving up
+    pub const DEFAULT_MAX_STEPS: usize = 6;
+
+    pub fn new(router: LLMRouter, registry: Arc<ToolRegistry>, context: ToolContext) -> Self {
+        Self {
+            router,
+            registry,
+            context,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+        }
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Run the request to completion, executing tool calls along the way.
+    ///
+    /// `on_trace` is called after each tool call so the UI can show it.
+    pub async fn run(
+        &self,
+        mut request: ChatRequest,
+        approval: &dyn ToolApproval,
+        mut on_trace: impl FnMut(&ToolTrace) + Send,
+    ) -> AgentResult<ChatResponse> {
+        request.tools = Some(self.registry.definitions());
+
+        for _ in 0..self.max_steps {
+            let response = self.router.chat(request.clone()).await?;
+            let raw_calls = match response.tool_calls {
+                Some(ref calls) if !calls.is_empty() => calls.clone(),
+                _ => return Ok(response),
+            };
+
+            request
+                .messages
+                .push(ChatMessage::assistant_with_tool_calls(
+                    response.content.clone(),
+                    raw_calls.clone(),
+                ));
+
+            for call in ToolCall::parse_all(&raw_calls) {
+                let trace = self.execute(&call, approval).await;
+                on_trace(&trace);
+                request.messages.push(ChatMessage::tool_result(
+                    call.id.clone(),
+                    trace.result_text(),
+                ));
+            }
+        }
+
+        Err(AgentError::TooManySteps(self.max_steps))
+    }
+
+    /// Like [`run`](Self::run), but streams each model round, passing text to
+    /// `on_token` as it arrives. Returns the text of the final answer.
+    ///
+    /// Providers that cannot stream are asked without streaming and their
+    /// reply is passed to `on_token` in one piece.
+    pub async fn run_streaming(
+        &self,
+        mut request: ChatRequest,
+        approval: &dyn ToolApproval,
+        mut on_trace: impl FnMut(&ToolTrace) + Send,
+        mut on_token: impl FnMut(&str) + Send,
+    ) -> AgentResult<String> {
+        request.tools = Some(self.registry.definitions());
+
+        for _ in 0..self.max_steps {
+            let round = self.stream_round(&request, &mut on_token).await?;
+            if round.tool_calls.is_empty() {
+                return Ok(round.content);
+            }
+
+            request
+                .messages
+                .push(ChatMessage::assistant_with_tool_calls(
+                    round.content,
+                    round.tool_calls.clone(),
+                ));
+
+            for call in ToolCall::parse_all(&round.tool_calls) {
+                let trace = self.execute(&call, approval).await;
+                on_trace(&trace);
+                request.messages.push(ChatMessage::tool_result(
+                    call.id.clone(),
+                    trace.result_text(),
+                ));
+            }
+        }
+
+        Err(AgentError::TooManySteps(self.max_steps))
+    }
+
+    async fn stream_round(
+        &self,
+        request: &ChatRequest,
+        on_token: &mut (impl FnMut(&str) + Send),
+    ) -> AgentResult<Round> {
+        let mut rx = match self.router.stream_chat(request.clone()).await {
+            Ok(rx) => rx,
+            Err(LLMError::StreamingNotSupported(_)) => {
+                let response = self.router.chat(request.clone()).await?;
+                if !response.content.is_empty() {
+                    on_token(&response.content);
+                }
+                return Ok(Round {
+                    content: response.content,
+                    tool_calls: response.tool_calls.unwrap_or_default(),
+                });
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut round = Round {
+            content: String::new(),
+            tool_calls: Vec::new(),
+        };
+        while let Some(chunk) = rx.recv().await {
+            let chunk = chunk?;
+            if !chunk.content.is_empty() {
+                on_token(&chunk.content);
+                round.content.push_str(&chunk.content);
+            }
+            if chunk.is_final {
+                round.tool_calls = chunk.tool_calls.unwrap_or_default();
+                break;
+            }
+        }
+        Ok(round)
+    }
+
+    async fn execute(&self, call: &ToolCall, approval: &dyn ToolApproval) -> ToolTrace {
+        let Some(tool) = self.registry.get(&call.name) else {
+            return ToolTrace {
+                tool: call.name.clone(),
+                summary: call.name.clone(),
+                outcome: ToolOutcome::UnknownTool,
+            };
+        };
+
+        let summary = tool.describe(&call.arguments);
+        let outcome = if tool.mutates_state() && !approval.approve(call, &summary).await {
+            ToolOutcome::Declined
+        } else {
+            match tool.invoke(call.arguments.clone(), &self.context).await {
+                Ok(text) => ToolOutcome::Success(text),
+                Err(e) => ToolOutcome::Failed(e.to_string()),
+            }
+        };
+
+        ToolTrace {
+            tool: call.name.clone(),
+            summary,
+            outcome,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::campaign::random_table::RandomTableEngine;
+    use crate::core::llm::cost::ProviderPricing;
+    use crate::core::llm::router::{ChatChunk, LLMProvider};
+    use crate::core::session_manager::SessionManager;
+    use crate::core::storage::surrealdb::SurrealStorage;
+    use serde_json::json;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+    use tokio::sync::mpsc;
+
+    /// Streams scripted replies, one per request, and records the requests.
+    struct ScriptedProvider {
+        replies: Mutex<VecDeque<(&'static str, Option<Vec<Value>>)>>,
+        requests: Arc<Mutex<Vec<ChatRequest>>>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for ScriptedProvider {
+        fn id(&self) -> &str {
+            "scripted"
+        }
+
+        fn name(&self) -> &str {
+            "Scripted"
+        }
+
+        fn model(&self) -> &str {
+            "scripted-model"
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+
+        fn pricing(&self) -> Option<ProviderPricing> {
+            None
+        }
+
+        async fn chat(&self, _request: ChatRequest) -> crate::core::llm::router::Result<ChatResponse> {
+            Err(LLMError::InvalidResponse("scripted provider only streams".into()))
+        }
+
+        async fn stream_chat(
+            &self,
+            request: ChatRequest,
+        ) -> crate::core::llm::router::Result<mpsc::Receiver<crate::core::llm::router::Result<ChatChunk>>> {
+            self.requests.lock().unwrap().push(request);
+            let (content, tool_calls) = self.replies.lock().unwrap().pop_front().unwrap();
+            let chunk = |content: &str, is_final: bool, tool_calls: Option<Vec<Value>>| ChatChunk {
+                stream_id: "s".into(),
+                content: content.into(),
+                provider: "scripted".into(),
+                model: "scripted-model".into(),
+                is_final,
+                finish_reason: None,
+                usage: None,
+                index: 0,
+                tool_calls,
+            };
+
+            let (tx, rx) = mpsc::channel(4);
+            tx.send(Ok(chunk(content, false, None))).await.unwrap();
+            tx.send(Ok(chunk("", true, tool_calls))).await.unwrap();
+            Ok(rx)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_streaming_streams_tokens_and_runs_tools() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let provider = ScriptedProvider {
+            replies: Mutex::new(VecDeque::from([
+                (
+                    "Rolling.",
+                    Some(vec![json!({
+                        "id": "call_1",
+                        "type": "function",
+                        "function": { "name": "roll_dice", "arguments": "{\"notation\":\"2d1+5\"}" }
+                    })]),
+                ),
+                ("You rolled 7.", None),
+            ])),
+            requests: requests.clone(),
+        };
+        let mut router = LLMRouter::with_defaults();
+        router.add_provider(Arc::new(provider)).await;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let pool = sqlx::sqlite::SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+        let context = ToolContext {
+            session: Arc::new(SessionManager::new()),
+            storage: SurrealStorage::new(dir.path().to_path_buf()).await.unwrap(),
+            embedding_provider: None,
+            reranker: None,
+            random_tables: Arc::new(RandomTableEngine::new(Arc::new(pool))),
+        };
+        let agent = AssistantAgent::new(router, Arc::new(ToolRegistry::with_defaults()), context);
+
+        let mut tokens = Vec::new();
+        let mut traces = Vec::new();
+        let answer = agent
+            .run_streaming(
+                ChatRequest::new(vec![ChatMessage::user("Roll 2d1+5")]),
+                &AutoApprove,
+                |trace| traces.push(trace.clone()),
+                |token| tokens.push(token.to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(answer, "You rolled 7.");
+        assert_eq!(tokens, vec!["Rolling.", "You rolled 7."]);
+        assert_eq!(traces.len(), 1);
+        assert!(matches!(traces[0].outcome, ToolOutcome::Success(_)));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].tools.is_some());
+        let last = requests[1].messages.last().unwrap();
+        assert_eq!(last.tool_call_id.as_deref(), Some("call_1"));
+    }
+
+    #[test]
+    fn test_parse_tool_call_string_arguments() {
+        let raw = json!({
+            "id": "call_1",
+            "type": "function",
+            "function": { "name": "roll_dice", "arguments": "{\"notation\":\"2d6\"}" }
+        });
+        let call = ToolCall::parse(&raw).unwrap();
+        assert_eq!(call.id, "call_1");
+        assert_eq!(call.name, "roll_dice");
+        assert_eq!(call.arguments, json!({"notation": "2d6"}));
+    }
+
+    #[test]
+    fn test_parse_tool_call_object_and_empty_arguments() {
+        let calls = ToolCall::parse_all(&[
+            json!({"id": "a", "function": {"name": "create_session_note", "arguments": {"title": "x"}}}),
+            json!({"id": "b", "function": {"name": "search_rules", "arguments": ""}}),
+            json!({"id": "c"}),
+        ]);
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].arguments, json!({"title": "x"}));
+        assert_eq!(calls[1].arguments, json!({}));
+    }
+
+    #[test]
+    fn test_trace_result_text() {
+        let trace = ToolTrace {
+            tool: "damage_combatant".into(),
+            summary: "deal 5 damage to Goblin".into(),
+            outcome: ToolOutcome::Declined,
+        };
+        assert!(trace.result_text().contains("declined"));
+        assert_eq!(trace.to_string(), "deal 5 damage to Goblin (declined)");
+    }
+}
@@ -0,0 +1,14 @@
+//! GM Assistant
+//!
+//! Tool-calling assistant for the chat view. The model can roll dice, search
+//! the rules, roll on random tables, and — with the GM's approval — change
+//! combat and session state.
+
+pub mod agent;
+pub mod tools;
+
+pub use agent::{
+    AgentError, AgentResult, AssistantAgent, AutoApprove, ToolApproval, ToolCall, ToolOutcome,
+    ToolTrace,
+};
+pub use tools::{AssistantTool, ToolContext, ToolError, ToolRegistry, ToolResult};
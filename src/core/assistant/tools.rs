@@ -0,0 +1,697 @@
+//! Assistant Tools
+//!
+//! Typed tools the GM assistant can call. Each tool deserializes its own
+//! argument struct from the model's JSON and returns a plain-text result
+//! that is fed back to the model.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::core::campaign::dice::{DiceNotation, DiceRoller};
+use crate::core::campaign::random_table::RandomTableEngine;
+use crate::core::search::embeddings::EmbeddingProvider;
+use crate::core::search::rerank::Reranker;
+use crate::core::session::notes::SessionNote;
+use crate::core::session_manager::{Combatant, CombatantType, GameSession, SessionManager};
+use crate::core::storage::search::{
+    fulltext_search, hybrid_search_reranked, HybridSearchConfig, SearchResult,
+};
+use crate::core::storage::surrealdb::SurrealStorage;
+
+/// Maximum characters of each search hit returned to the model
+const SEARCH_SNIPPET_CHARS: usize = 600;
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum ToolError {
+    #[error("Invalid arguments: {0}")]
+    InvalidArguments(String),
+
+    #[error("No active game session")]
+    NoActiveSession,
+
+    #[error("No combatant named '{0}'")]
+    CombatantNotFound(String),
+
+    #[error("{0}")]
+    Failed(String),
+}
+
+pub type ToolResult<T> = Result<T, ToolError>;
+
+// ============================================================================
+// Tool Trait + Context
+// ============================================================================
+
+/// Backend handles available to tools.
+#[derive(Clone)]
+pub struct ToolContext {
+    pub session: Arc<SessionManager>,
+    pub storage: SurrealStorage,
+    pub embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
+    pub reranker: Option<Arc<dyn Reranker>>,
+    pub random_tables: Arc<RandomTableEngine>,
+}
+
+impl ToolContext {
+    fn active_session(&self) -> ToolResult<GameSession> {
+        self.session
+            .latest_active_session()
+            .ok_or(ToolError::NoActiveSession)
+    }
+
+    /// Resolve a combatant by name (exact, then prefix, case-insensitive).
+    fn find_combatant(&self, session_id: &str, name: &str) -> ToolResult<Combatant> {
+        let combat = self
+            .session
+            .get_combat(session_id)
+            .ok_or_else(|| ToolError::Failed("No combat is running".into()))?;
+        let needle = name.to_lowercase();
+        combat
+            .combatants
+            .iter()
+            .find(|c| c.name.to_lowercase() == needle)
+            .or_else(|| {
+                combat
+                    .combatants
+                    .iter()
+                    .find(|c| c.name.to_lowercase().starts_with(&needle))
+            })
+            .cloned()
+            .ok_or_else(|| ToolError::CombatantNotFound(name.to_string()))
+    }
+}
+
+/// A tool the assistant can call.
+#[async_trait]
+pub trait AssistantTool: Send + Sync {
+    /// Function name exposed to the model
+    fn name(&self) -> &'static str;
+
+    /// What the tool does, for the model
+    fn description(&self) -> &'static str;
+
+    /// JSON schema of the arguments object
+    fn parameters(&self) -> Value;
+
+    /// Whether the tool changes game state and needs the GM's approval
+    fn mutates_state(&self) -> bool {
+        false
+    }
+
+    /// Short human-readable form of a call, for the trace and approval prompt
+    fn describe(&self, args: &Value) -> String {
+        format!("{} {}", self.name(), args)
+    }
+
+    async fn invoke(&self, args: Value, ctx: &ToolContext) -> ToolResult<String>;
+}
+
+fn parse_args<T: DeserializeOwned>(args: Value) -> ToolResult<T> {
+    serde_json::from_value(args).map_err(|e| ToolError::InvalidArguments(e.to_string()))
+}
+
+fn arg_str<'a>(args: &'a Value, key: &str) -> &'a str {
+    args.get(key).and_then(Value::as_str).unwrap_or("?")
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Named set of tools offered to the model.
+#[derive(Default)]
+pub struct ToolRegistry {
+    tools: BTreeMap<&'static str, Arc<dyn AssistantTool>>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with every built-in GM tool.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(RollDiceTool);
+        registry.register(SearchRulesTool);
+        registry.register(RollTableTool);
+        registry.register(AddCombatantTool);
+        registry.register(DamageCombatantTool);
+        registry.register(CreateNoteTool);
+        registry
+    }
+
+    pub fn register(&mut self, tool: impl AssistantTool + 'static) {
+        self.tools.insert(tool.name(), Arc::new(tool));
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn AssistantTool>> {
+        self.tools.get(name).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Tool definitions in the OpenAI function-calling format used by `ChatRequest::tools`.
+    pub fn definitions(&self) -> Vec<Value> {
+        self.tools
+            .values()
+            .map(|tool| {
+                json!({
+                    "type": "function",
+                    "function": {
+                        "name": tool.name(),
+                        "description": tool.description(),
+                        "parameters": tool.parameters(),
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// Dice
+// ============================================================================
+
+pub struct RollDiceTool;
+
+#[derive(Deserialize)]
+struct RollDiceArgs {
+    notation: String,
+}
+
+#[async_trait]
+impl AssistantTool for RollDiceTool {
+    fn name(&self) -> &'static str {
+        "roll_dice"
+    }
+
+    fn description(&self) -> &'static str {
+        "Roll dice using standard notation, e.g. 1d20+5, 4d6kh3, 2d20kl1, 8d10>=7, 4dF."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "notation": { "type": "string", "description": "Dice expression" }
+            },
+            "required": ["notation"]
+        })
+    }
+
+    fn describe(&self, args: &Value) -> String {
+        format!("roll {}", arg_str(args, "notation"))
+    }
+
+    async fn invoke(&self, args: Value, _ctx: &ToolContext) -> ToolResult<String> {
+        let args: RollDiceArgs = parse_args(args)?;
+        let notation = DiceNotation::parse(args.notation.trim())
+            .map_err(|e| ToolError::InvalidArguments(e.to_string()))?;
+        Ok(DiceRoller::new().roll(&notation).to_string())
+    }
+}
+
+// ============================================================================
+// Rules Search
+// ============================================================================
+
+pub struct SearchRulesTool;
+
+#[derive(Deserialize)]
+struct SearchRulesArgs {
+    query: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[async_trait]
+impl AssistantTool for SearchRulesTool {
+    fn name(&self) -> &'static str {
+        "search_rules"
+    }
+
+    fn description(&self) -> &'static str {
+        "Search the ingested rulebooks and sourcebooks. Returns matching passages with source and page."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string", "description": "What to look up" },
+                "limit": { "type": "integer", "minimum": 1, "maximum": 8 }
+            },
+            "required": ["query"]
+        })
+    }
+
+    fn describe(&self, args: &Value) -> String {
+        format!("search \"{}\"", arg_str(args, "query"))
+    }
+
+    async fn invoke(&self, args: Value, ctx: &ToolContext) -> ToolResult<String> {
+        let args: SearchRulesArgs = parse_args(args)?;
+        let limit = args.limit.unwrap_or(4).clamp(1, 8);
+        let db = ctx.storage.db();
+
+        let embedding = match ctx.embedding_provider {
+            Some(ref provider) => provider
+                .embed(&args.query)
+                .await
+                .ok()
+                .map(|embedding| (embedding, provider.model_id())),
+            None => None,
+        };
+        let results = match embedding {
+            Some((embedding, model)) => {
+                let config = HybridSearchConfig::for_rules()
+                    .with_limit(limit)
+                    .with_query_model(model);
+                hybrid_search_reranked(
+                    db,
+                    &args.query,
+                    embedding,
+                    &config,
+                    None,
+                    ctx.reranker.as_deref(),
+                )
+                .await
+            }
+            None => fulltext_search(db, &args.query, limit, None).await,
+        }
+        .map_err(|e| ToolError::Failed(e.to_string()))?;
+
+        Ok(format_search_results(&results, limit))
+    }
+}
+
+fn format_search_results(results: &[SearchResult], limit: usize) -> String {
+    if results.is_empty() {
+        return "No matching passages found.".to_string();
+    }
+    results
+        .iter()
+        .take(limit)
+        .map(|r| {
+            let source = if r.source.is_empty() {
+                "unknown"
+            } else {
+                r.source.as_str()
+            };
+            let page = r
+                .page_number
+                .map(|p| format!(" p. {p}"))
+                .unwrap_or_default();
+            let snippet: String = r.content.chars().take(SEARCH_SNIPPET_CHARS).collect();
+            format!("[{source}{page}]\n{snippet}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// ============================================================================
+// Random Tables
+// ============================================================================
+
+pub struct RollTableTool;
+
+#[derive(Deserialize)]
+struct RollTableArgs {
+    table: String,
+}
+
+#[async_trait]
+impl AssistantTool for RollTableTool {
+    fn name(&self) -> &'static str {
+        "roll_random_table"
+    }
+
+    fn description(&self) -> &'static str {
+        "Roll on a random table imported from the library (encounters, treasure, trinkets...). \
+         Matches the table by name."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "table": { "type": "string", "description": "Table name or part of it" }
+            },
+            "required": ["table"]
+        })
+    }
+
+    fn describe(&self, args: &Value) -> String {
+        format!("roll on table \"{}\"", arg_str(args, "table"))
+    }
+
+    async fn invoke(&self, args: Value, ctx: &ToolContext) -> ToolResult<String> {
+        let args: RollTableArgs = parse_args(args)?;
+        let tables = ctx
+            .random_tables
+            .list_imported_tables()
+            .await
+            .map_err(|e| ToolError::Failed(e.to_string()))?;
+
+        let needle = args.table.to_lowercase();
+        let table = tables
+            .iter()
+            .find(|t| t.name.to_lowercase() == needle)
+            .or_else(|| {
+                tables
+                    .iter()
+                    .find(|t| t.name.to_lowercase().contains(&needle))
+            })
+            .ok_or_else(|| {
+                ToolError::Failed(format!("No random table matching '{}'", args.table))
+            })?;
+
+        let rolled = ctx
+            .random_tables
+            .quick_roll(&table.id)
+            .await
+            .map_err(|e| ToolError::Failed(e.to_string()))?;
+        Ok(format!(
+            "{} ({} = {}): {}",
+            rolled.table_name, table.dice_notation, rolled.roll.total, rolled.final_text
+        ))
+    }
+}
+
+// ============================================================================
+// Combat
+// ============================================================================
+
+pub struct AddCombatantTool;
+
+#[derive(Deserialize)]
+struct AddCombatantArgs {
+    name: String,
+    #[serde(default)]
+    initiative: Option<i32>,
+    #[serde(default)]
+    hit_points: Option<i32>,
+    #[serde(default)]
+    armor_class: Option<i32>,
+    #[serde(default)]
+    kind: Option<String>,
+}
+
+fn parse_combatant_type(kind: Option<&str>) -> CombatantType {
+    match kind.map(str::to_lowercase).as_deref() {
+        Some("player") | Some("pc") => CombatantType::Player,
+        Some("npc") => CombatantType::NPC,
+        Some("ally") => CombatantType::Ally,
+        Some("environment") | Some("hazard") => CombatantType::Environment,
+        _ => CombatantType::Monster,
+    }
+}
+
+#[async_trait]
+impl AssistantTool for AddCombatantTool {
+    fn name(&self) -> &'static str {
+        "add_combatant"
+    }
+
+    fn description(&self) -> &'static str {
+        "Add a combatant to the current session's combat, starting combat if needed. \
+         Initiative is rolled (d20) when omitted."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "initiative": { "type": "integer" },
+                "hit_points": { "type": "integer", "minimum": 1 },
+                "armor_class": { "type": "integer" },
+                "kind": { "type": "string", "enum": ["monster", "npc", "player", "ally", "environment"] }
+            },
+            "required": ["name"]
+        })
+    }
+
+    fn mutates_state(&self) -> bool {
+        true
+    }
+
+    fn describe(&self, args: &Value) -> String {
+        let mut text = format!("add {} to combat", arg_str(args, "name"));
+        if let Some(hp) = args.get("hit_points").and_then(Value::as_i64) {
+            text.push_str(&format!(" ({hp} HP)"));
+        }
+        text
+    }
+
+    async fn invoke(&self, args: Value, ctx: &ToolContext) -> ToolResult<String> {
+        let args: AddCombatantArgs = parse_args(args)?;
+        let session = ctx.active_session()?;
+        if ctx.session.get_combat(&session.id).is_none() {
+            ctx.session
+                .start_combat(&session.id)
+                .map_err(|e| ToolError::Failed(e.to_string()))?;
+        }
+
+        let initiative = match args.initiative {
+            Some(init) => init,
+            None => {
+                DiceRoller::new()
+                    .quick_roll("1d20")
+                    .map_err(|e| ToolError::Failed(e.to_string()))?
+                    .total
+            }
+        };
+        let mut combatant = Combatant::new(
+            args.name.clone(),
+            initiative,
+            parse_combatant_type(args.kind.as_deref()),
+        );
+        combatant.max_hp = args.hit_points;
+        combatant.current_hp = args.hit_points;
+        combatant.armor_class = args.armor_class;
+
+        ctx.session
+            .add_combatant(&session.id, combatant)
+            .map_err(|e| ToolError::Failed(e.to_string()))?;
+        Ok(format!(
+            "Added {} to combat at initiative {initiative}",
+            args.name
+        ))
+    }
+}
+
+pub struct DamageCombatantTool;
+
+#[derive(Deserialize)]
+struct DamageCombatantArgs {
+    name: String,
+    amount: i32,
+}
+
+#[async_trait]
+impl AssistantTool for DamageCombatantTool {
+    fn name(&self) -> &'static str {
+        "damage_combatant"
+    }
+
+    fn description(&self) -> &'static str {
+        "Apply damage to a combatant in the current combat. A negative amount heals."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "amount": { "type": "integer" }
+            },
+            "required": ["name", "amount"]
+        })
+    }
+
+    fn mutates_state(&self) -> bool {
+        true
+    }
+
+    fn describe(&self, args: &Value) -> String {
+        let amount = args.get("amount").and_then(Value::as_i64).unwrap_or(0);
+        if amount < 0 {
+            format!("heal {} for {}", arg_str(args, "name"), -amount)
+        } else {
+            format!("deal {amount} damage to {}", arg_str(args, "name"))
+        }
+    }
+
+    async fn invoke(&self, args: Value, ctx: &ToolContext) -> ToolResult<String> {
+        let args: DamageCombatantArgs = parse_args(args)?;
+        let session = ctx.active_session()?;
+        let combatant = ctx.find_combatant(&session.id, &args.name)?;
+
+        let hp = if args.amount < 0 {
+            ctx.session
+                .heal_combatant(&session.id, &combatant.id, -args.amount)
+        } else {
+            ctx.session
+                .damage_combatant(&session.id, &combatant.id, args.amount)
+        }
+        .map_err(|e| ToolError::Failed(e.to_string()))?;
+
+        let max = combatant
+            .max_hp
+            .map(|m| format!("/{m}"))
+            .unwrap_or_default();
+        Ok(format!("{} is at {hp}{max} HP", combatant.name))
+    }
+}
+
+// ============================================================================
+// Notes
+// ============================================================================
+
+pub struct CreateNoteTool;
+
+#[derive(Deserialize)]
+struct CreateNoteArgs {
+    title: String,
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[async_trait]
+impl AssistantTool for CreateNoteTool {
+    fn name(&self) -> &'static str {
+        "create_session_note"
+    }
+
+    fn description(&self) -> &'static str {
+        "Save a note to the current game session (plot hooks, rulings, NPC details...)."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string" },
+                "content": { "type": "string" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["title", "content"]
+        })
+    }
+
+    fn mutates_state(&self) -> bool {
+        true
+    }
+
+    fn describe(&self, args: &Value) -> String {
+        format!("create note \"{}\"", arg_str(args, "title"))
+    }
+
+    async fn invoke(&self, args: Value, ctx: &ToolContext) -> ToolResult<String> {
+        let args: CreateNoteArgs = parse_args(args)?;
+        let session = ctx.active_session()?;
+
+        let mut note =
+            SessionNote::new(&session.id, &session.campaign_id, &args.title, args.content)
+                .with_tags(args.tags);
+        note.author = "assistant".to_string();
+
+        ctx.session
+            .create_note(note)
+            .map_err(|e| ToolError::Failed(e.to_string()))?;
+        Ok(format!("Created note \"{}\"", args.title))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_definitions() {
+        let registry = ToolRegistry::with_defaults();
+        assert_eq!(registry.len(), 6);
+
+        let defs = registry.definitions();
+        let names: Vec<&str> = defs
+            .iter()
+            .filter_map(|d| d["function"]["name"].as_str())
+            .collect();
+        assert!(names.contains(&"roll_dice"));
+        assert!(names.contains(&"damage_combatant"));
+        assert!(defs.iter().all(|d| d["type"] == "function"));
+    }
+
+    #[test]
+    fn test_mutating_tools() {
+        let registry = ToolRegistry::with_defaults();
+        let all = [
+            "roll_dice",
+            "search_rules",
+            "roll_random_table",
+            "add_combatant",
+            "damage_combatant",
+            "create_session_note",
+        ];
+        let mutating: Vec<&str> = all
+            .into_iter()
+            .filter(|name| registry.get(name).unwrap().mutates_state())
+            .collect();
+        assert_eq!(
+            mutating,
+            vec!["add_combatant", "damage_combatant", "create_session_note"]
+        );
+    }
+
+    #[test]
+    fn test_describe_calls() {
+        assert_eq!(
+            DamageCombatantTool.describe(&json!({"name": "Goblin 2", "amount": -4})),
+            "heal Goblin 2 for 4"
+        );
+        assert_eq!(
+            AddCombatantTool.describe(&json!({"name": "Ogre", "hit_points": 59})),
+            "add Ogre to combat (59 HP)"
+        );
+        assert_eq!(
+            RollDiceTool.describe(&json!({"notation": "2d6+3"})),
+            "roll 2d6+3"
+        );
+    }
+
+    #[test]
+    fn test_parse_args_rejects_missing_fields() {
+        let err = parse_args::<DamageCombatantArgs>(json!({"name": "Goblin"})).unwrap_err();
+        assert!(matches!(err, ToolError::InvalidArguments(_)));
+    }
+
+    #[test]
+    fn test_parse_combatant_type() {
+        assert_eq!(parse_combatant_type(Some("PC")), CombatantType::Player);
+        assert_eq!(parse_combatant_type(Some("ally")), CombatantType::Ally);
+        assert_eq!(parse_combatant_type(None), CombatantType::Monster);
+    }
+}
@@ -0,0 +1,1311 @@
+//! Unified Application Backup
+//!
+//! Snapshots every on-disk store (SQLite, SurrealDB, session journal,
+//! calendars, setting packs, game system definitions, personality templates,
+//! NPC generation data and the config file) into a single zstd-compressed tar archive.
+//!
+//! Neither database is copied file by file while it is open. SurrealDB is
+//! archived as a SurrealQL export taken through the open database handle,
+//! and SQLite as a `VACUUM INTO` copy taken through the connection pool
+//! ([`backup_live`]); copying the RocksDB directory races compaction, and
+//! copying the SQLite file and its WAL separately can pair a database with
+//! a log from a different moment. A restore rebuilds the SurrealDB directory
+//! from the export with [`import_surrealdb`].
+//!
+//! Archive layout:
+//! - `manifest.json` — always the first entry; app/schema versions plus a
+//!   blake3 checksum for every file
+//! - `data/<source>/...` — the snapshotted files, keyed by source name
+//!
+//! Archives can optionally be encrypted with a passphrase. The key is derived
+//! with Argon2id and the compressed stream is sealed in 1 MiB AES-256-GCM
+//! segments, so neither side has to hold the whole archive in memory.
+//!
+//! Restores verify every checksum in a staging directory before touching live
+//! data and refuse archives written for a different SQLite schema version.
+//! They must run while the application is closed; [`restore_live`] refuses
+//! while another process holds the SurrealDB lock.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use surrealdb::engine::local::{Db, RocksDb};
+use surrealdb::Surreal;
+use thiserror::Error;
+use walkdir::WalkDir;
+
+use crate::core::credentials::CredentialManager;
+use crate::database::SCHEMA_VERSION;
+
+/// Version of the archive layout itself (not the app or database schema).
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// Keyring entry holding the passphrase used for scheduled encrypted backups.
+pub const PASSPHRASE_SECRET_KEY: &str = "backup_passphrase";
+
+/// Environment variable consulted when the keyring has no passphrase.
+pub const PASSPHRASE_ENV: &str = "TTTTRPS_BACKUP_PASSPHRASE";
+
+/// Archive name of the SurrealDB export. The export is also handed over
+/// under this name in the data directory while a backup or restore runs.
+pub const SURREAL_EXPORT_NAME: &str = "surrealdb.surql";
+
+/// Archive name of the SQLite database. Backups write a `VACUUM INTO` copy
+/// to [`SQLITE_SNAPSHOT_FILE`] in the data directory and archive that.
+pub const SQLITE_SNAPSHOT_NAME: &str = "sqlite/ttrpg_assistant.db";
+
+/// Data directory file holding the SQLite copy while a backup runs.
+pub const SQLITE_SNAPSHOT_FILE: &str = "ttrpg_assistant.db.snapshot";
+
+const SQLITE_DB_FILE: &str = "ttrpg_assistant.db";
+const SURREAL_DIR_NAME: &str = "surrealdb";
+
+const ARCHIVE_PREFIX: &str = "ttrpg_archive_";
+const ARCHIVE_EXT: &str = ".tar.zst";
+const ENCRYPTED_EXT: &str = ".tar.zst.enc";
+const MANIFEST_NAME: &str = "manifest.json";
+const DATA_PREFIX: &str = "data/";
+const ZSTD_LEVEL: i32 = 3;
+
+const MAGIC: &[u8; 8] = b"TTBKENC1";
+const SALT_LEN: usize = 16;
+const NONCE_PREFIX_LEN: usize = 7;
+const SEGMENT_SIZE: usize = 1024 * 1024;
+const TAG_LEN: usize = 16;
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Manifest error: {0}")]
+    Manifest(#[from] serde_json::Error),
+
+    #[error("Archive not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid archive: {0}")]
+    InvalidArchive(String),
+
+    #[error("Archive is encrypted; a passphrase is required")]
+    PassphraseRequired,
+
+    #[error("Wrong passphrase or corrupted archive")]
+    Decryption,
+
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+
+    #[error("Checksum mismatch for {0}")]
+    ChecksumMismatch(String),
+
+    #[error("Snapshot failed: {0}")]
+    Snapshot(String),
+
+    #[error("Archive format v{0} is newer than this build supports")]
+    UnsupportedFormat(u32),
+
+    #[error("Archive was written for schema v{archive}, this build uses v{current}")]
+    SchemaMismatch { archive: i32, current: i32 },
+
+    #[error("The application is running; close it before restoring")]
+    InUse,
+}
+
+pub type Result<T> = std::result::Result<T, ArchiveError>;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A file or directory that belongs in the archive.
+///
+/// `name` is the path under `data/` in the archive; `path` is where the
+/// source lives on this machine. Missing sources are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveSource {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl ArchiveSource {
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+        }
+    }
+
+    /// Every store the application writes, given the resolved data directory
+    /// and config file path. Both databases are listed by their snapshot
+    /// files, which [`backup_live`] writes before archiving.
+    pub fn defaults(data_dir: &Path, config_path: &Path) -> Vec<Self> {
+        let mut sources = vec![
+            Self::new(SQLITE_SNAPSHOT_NAME, data_dir.join(SQLITE_SNAPSHOT_FILE)),
+            Self::new(SURREAL_EXPORT_NAME, data_dir.join(SURREAL_EXPORT_NAME)),
+            Self::new("sessions.journal", data_dir.join("sessions.journal")),
+            Self::new("calendars", data_dir.join("calendars")),
+            Self::new("setting_packs", data_dir.join("setting_packs")),
+            Self::new("systems", data_dir.join("systems")),
+            Self::new("config/config.toml", config_path),
+        ];
+        if let Some(dir) = dirs::data_local_dir() {
+            sources.push(Self::new(
+                "templates",
+                dir.join("ttrpg-assistant").join("templates"),
+            ));
+        }
+        if let Some(dir) = dirs::data_dir() {
+            sources.push(Self::new(
+                "npc_gen",
+                dir.join("ttrpg-assistant").join("npc_gen"),
+            ));
+        }
+        sources
+    }
+
+    /// Everything a restore replaces: the [`defaults`](Self::defaults), with
+    /// the SQLite snapshot going to the live database file, plus the SQLite
+    /// WAL and shared-memory files and the SurrealDB directory.
+    ///
+    /// The WAL files are only moved aside, so a stale log is never replayed
+    /// into the restored database; the SurrealDB directory is rebuilt from
+    /// the export. Older archives that carry the WAL files or the directory
+    /// itself restore them as is.
+    pub fn restore_targets(data_dir: &Path, config_path: &Path) -> Vec<Self> {
+        let mut sources = Self::defaults(data_dir, config_path);
+        for source in &mut sources {
+            if source.name == SQLITE_SNAPSHOT_NAME {
+                source.path = data_dir.join(SQLITE_DB_FILE);
+            }
+        }
+        for suffix in ["-wal", "-shm"] {
+            sources.push(Self::new(
+                format!("{SQLITE_SNAPSHOT_NAME}{suffix}"),
+                data_dir.join(format!("{SQLITE_DB_FILE}{suffix}")),
+            ));
+        }
+        sources.push(Self::new(SURREAL_DIR_NAME, data_dir.join(SURREAL_DIR_NAME)));
+        sources
+    }
+}
+
+/// Describes the contents of an archive; stored as its first entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub schema_version: i32,
+    pub created_at: DateTime<Utc>,
+    pub description: Option<String>,
+    pub encrypted: bool,
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// One archived file, relative to `data/`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub size_bytes: u64,
+    pub blake3: String,
+}
+
+/// An archive found in the backup directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveInfo {
+    pub filename: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+    pub encrypted: bool,
+}
+
+/// Options for [`create_archive`].
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveOptions {
+    pub description: Option<String>,
+    /// Encrypt the archive with this passphrase.
+    pub passphrase: Option<String>,
+}
+
+// ============================================================================
+// Create
+// ============================================================================
+
+/// Snapshot `sources` into a new archive inside `backup_dir`.
+pub fn create_archive(
+    sources: &[ArchiveSource],
+    backup_dir: &Path,
+    options: &ArchiveOptions,
+) -> Result<ArchiveInfo> {
+    create_with_schema(sources, backup_dir, options, SCHEMA_VERSION)
+}
+
+fn create_with_schema(
+    sources: &[ArchiveSource],
+    backup_dir: &Path,
+    options: &ArchiveOptions,
+    schema_version: i32,
+) -> Result<ArchiveInfo> {
+    fs::create_dir_all(backup_dir)?;
+    let staging = Staging::new(backup_dir)?;
+
+    // Copy first so the manifest, checksums and tar all see the same bytes
+    let snapshot = staging.path().join("data");
+    for source in sources {
+        snapshot_source(source, &snapshot.join(&source.name))?;
+    }
+
+    let created_at = Utc::now();
+    let manifest = ArchiveManifest {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version,
+        created_at,
+        description: options.description.clone(),
+        encrypted: options.passphrase.is_some(),
+        entries: hash_tree(&snapshot)?,
+    };
+
+    let ext = if manifest.encrypted {
+        ENCRYPTED_EXT
+    } else {
+        ARCHIVE_EXT
+    };
+    let path = unique_archive_path(backup_dir, &created_at, ext);
+    let partial = path.with_extension("partial");
+
+    let file = BufWriter::new(File::create(&partial)?);
+    let written = match &options.passphrase {
+        Some(passphrase) => {
+            let writer = EncryptWriter::new(file, passphrase)?;
+            write_tar(writer, &manifest, &snapshot).and_then(|w| Ok(w.finish()?))
+        }
+        None => write_tar(file, &manifest, &snapshot),
+    };
+    let mut file = match written {
+        Ok(file) => file,
+        Err(e) => {
+            fs::remove_file(&partial).ok();
+            return Err(e);
+        }
+    };
+    file.flush()?;
+    drop(file);
+    fs::rename(&partial, &path)?;
+
+    let info = ArchiveInfo {
+        filename: file_name(&path),
+        size_bytes: fs::metadata(&path)?.len(),
+        path,
+        created_at,
+        encrypted: manifest.encrypted,
+    };
+
+    tracing::info!(
+        archive = %info.path.display(),
+        files = manifest.entries.len(),
+        size_bytes = info.size_bytes,
+        encrypted = info.encrypted,
+        "Backup archive created"
+    );
+
+    Ok(info)
+}
+
+/// Copy a file or directory tree into the staging area.
+///
+/// Any file that cannot be read fails the backup: an archive missing part of
+/// a store would restore into a broken one.
+fn snapshot_source(source: &ArchiveSource, dest: &Path) -> Result<()> {
+    if !source.path.exists() {
+        tracing::debug!(source = %source.name, "Backup source missing, skipping");
+        return Ok(());
+    }
+
+    if source.path.is_file() {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&source.path, dest)?;
+        return Ok(());
+    }
+
+    for entry in WalkDir::new(&source.path) {
+        let entry = entry.map_err(io::Error::from)?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(rel) = entry.path().strip_prefix(&source.path) else {
+            continue;
+        };
+        let target = dest.join(rel);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(entry.path(), &target)?;
+    }
+    Ok(())
+}
+
+/// Checksum every file under `root`, sorted by relative path.
+fn hash_tree(root: &Path) -> Result<Vec<ManifestEntry>> {
+    let mut entries = Vec::new();
+    if !root.exists() {
+        return Ok(entries);
+    }
+    for entry in WalkDir::new(root).sort_by_file_name() {
+        let entry = entry.map_err(io::Error::from)?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(rel) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        entries.push(ManifestEntry {
+            path: archive_path(rel),
+            size_bytes: entry.metadata().map_err(io::Error::from)?.len(),
+            blake3: hash_file(entry.path())?,
+        });
+    }
+    Ok(entries)
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// Forward-slash relative path, independent of the host OS.
+fn archive_path(rel: &Path) -> String {
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn write_tar<W: Write>(writer: W, manifest: &ArchiveManifest, snapshot: &Path) -> Result<W> {
+    let mut encoder = zstd::Encoder::new(writer, ZSTD_LEVEL)?;
+    {
+        let mut builder = tar::Builder::new(&mut encoder);
+
+        let json = serde_json::to_vec_pretty(manifest)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(json.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(manifest.created_at.timestamp().max(0) as u64);
+        header.set_cksum();
+        builder.append_data(&mut header, MANIFEST_NAME, json.as_slice())?;
+
+        for entry in &manifest.entries {
+            builder.append_path_with_name(
+                snapshot.join(&entry.path),
+                format!("{DATA_PREFIX}{}", entry.path),
+            )?;
+        }
+        builder.finish()?;
+    }
+    Ok(encoder.finish()?)
+}
+
+fn unique_archive_path(dir: &Path, created_at: &DateTime<Utc>, ext: &str) -> PathBuf {
+    let stamp = created_at.format("%Y%m%d_%H%M%S");
+    let mut path = dir.join(format!("{ARCHIVE_PREFIX}{stamp}{ext}"));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{ARCHIVE_PREFIX}{stamp}_{n}{ext}"));
+        n += 1;
+    }
+    path
+}
+
+// ============================================================================
+// Verify & Restore
+// ============================================================================
+
+/// Extract an archive to a scratch directory and check every checksum,
+/// without touching live data.
+pub fn verify_archive(archive: &Path, passphrase: Option<&str>) -> Result<ArchiveManifest> {
+    let staging = Staging::new(scratch_parent(archive))?;
+    extract_verified(archive, passphrase, staging.path(), SCHEMA_VERSION)
+}
+
+/// Replace the live stores with the contents of `archive`.
+///
+/// Every existing source is moved aside to `<path>.pre-restore` first, so a
+/// source absent from the archive is absent afterwards too — the result is
+/// exactly the snapshot. Nothing is moved until all checksums pass.
+pub fn restore_archive(
+    archive: &Path,
+    sources: &[ArchiveSource],
+    passphrase: Option<&str>,
+) -> Result<ArchiveManifest> {
+    restore_with_schema(archive, sources, passphrase, SCHEMA_VERSION)
+}
+
+fn restore_with_schema(
+    archive: &Path,
+    sources: &[ArchiveSource],
+    passphrase: Option<&str>,
+    schema_version: i32,
+) -> Result<ArchiveManifest> {
+    let staging = Staging::new(scratch_parent(archive))?;
+    let manifest = extract_verified(archive, passphrase, staging.path(), schema_version)?;
+    let extracted = staging.path().join("data");
+
+    for source in sources {
+        if source.path.exists() {
+            let aside = pre_restore_path(&source.path);
+            remove_path(&aside)?;
+            move_path(&source.path, &aside)?;
+        }
+        let staged = extracted.join(&source.name);
+        if staged.exists() {
+            if let Some(parent) = source.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            move_path(&staged, &source.path)?;
+        }
+    }
+
+    tracing::info!(
+        archive = %archive.display(),
+        files = manifest.entries.len(),
+        "Backup archive restored"
+    );
+
+    Ok(manifest)
+}
+
+/// Unpack into `staging/data`, refusing incompatible manifests before any
+/// file is written and verifying checksums after.
+fn extract_verified(
+    archive: &Path,
+    passphrase: Option<&str>,
+    staging: &Path,
+    schema_version: i32,
+) -> Result<ArchiveManifest> {
+    if !archive.exists() {
+        return Err(ArchiveError::NotFound(archive.display().to_string()));
+    }
+
+    let compressed = open_payload(archive, passphrase, staging)?;
+    let decoder = zstd::Decoder::new(File::open(&compressed)?)?;
+    let mut tar = tar::Archive::new(decoder);
+    let mut entries = tar.entries()?;
+
+    let mut first = entries
+        .next()
+        .ok_or_else(|| ArchiveError::InvalidArchive("archive is empty".to_string()))??;
+    if first.path()? != Path::new(MANIFEST_NAME) {
+        return Err(ArchiveError::InvalidArchive(
+            "manifest is not the first entry".to_string(),
+        ));
+    }
+    let mut json = Vec::new();
+    first.read_to_end(&mut json)?;
+    let manifest: ArchiveManifest = serde_json::from_slice(&json)?;
+    check_compatible(&manifest, schema_version)?;
+
+    for entry in entries {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        if !path.starts_with(DATA_PREFIX.trim_end_matches('/')) {
+            return Err(ArchiveError::InvalidArchive(format!(
+                "unexpected entry {}",
+                path.display()
+            )));
+        }
+        if !entry.unpack_in(staging)? {
+            return Err(ArchiveError::InvalidArchive(format!(
+                "unsafe entry path {}",
+                path.display()
+            )));
+        }
+    }
+
+    let extracted = staging.join("data");
+    for entry in &manifest.entries {
+        let path = extracted.join(&entry.path);
+        if !path.is_file() || hash_file(&path)? != entry.blake3 {
+            return Err(ArchiveError::ChecksumMismatch(entry.path.clone()));
+        }
+    }
+
+    Ok(manifest)
+}
+
+fn check_compatible(manifest: &ArchiveManifest, schema_version: i32) -> Result<()> {
+    if manifest.format_version > ARCHIVE_FORMAT_VERSION {
+        return Err(ArchiveError::UnsupportedFormat(manifest.format_version));
+    }
+    if manifest.schema_version != schema_version {
+        return Err(ArchiveError::SchemaMismatch {
+            archive: manifest.schema_version,
+            current: schema_version,
+        });
+    }
+    Ok(())
+}
+
+/// Path of the zstd stream: the archive itself, or a decrypted copy.
+fn open_payload(archive: &Path, passphrase: Option<&str>, staging: &Path) -> Result<PathBuf> {
+    let mut reader = BufReader::new(File::open(archive)?);
+    let mut magic = [0u8; 8];
+    let is_encrypted = match reader.read_exact(&mut magic) {
+        Ok(()) => &magic == MAGIC,
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => false,
+        Err(e) => return Err(e.into()),
+    };
+    if !is_encrypted {
+        return Ok(archive.to_path_buf());
+    }
+
+    let passphrase = passphrase.ok_or(ArchiveError::PassphraseRequired)?;
+    let mut salt = [0u8; SALT_LEN];
+    let mut prefix = [0u8; NONCE_PREFIX_LEN];
+    reader.read_exact(&mut salt)?;
+    reader.read_exact(&mut prefix)?;
+    let cipher = derive_cipher(passphrase, &salt)?;
+
+    let decrypted = staging.join("payload.tar.zst");
+    let mut out = BufWriter::new(File::create(&decrypted)?);
+    let mut buf = vec![0u8; SEGMENT_SIZE + TAG_LEN];
+    let mut counter = 0u32;
+    loop {
+        let n = read_full(&mut reader, &mut buf)?;
+        // Only the final segment is shorter than a full one
+        let last = n < buf.len();
+        let nonce = segment_nonce(&prefix, counter, last);
+        let plain = cipher
+            .decrypt(Nonce::from_slice(&nonce), &buf[..n])
+            .map_err(|_| ArchiveError::Decryption)?;
+        out.write_all(&plain)?;
+        if last {
+            break;
+        }
+        counter = counter
+            .checked_add(1)
+            .ok_or_else(|| ArchiveError::InvalidArchive("too many segments".to_string()))?;
+    }
+    out.flush()?;
+    Ok(decrypted)
+}
+
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+fn pre_restore_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".pre-restore");
+    path.with_file_name(name)
+}
+
+/// Rename, falling back to copy + delete across filesystems.
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        for entry in WalkDir::new(from).into_iter().filter_map(|e| e.ok()) {
+            let Ok(rel) = entry.path().strip_prefix(from) else {
+                continue;
+            };
+            let target = to.join(rel);
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&target)?;
+            } else {
+                fs::copy(entry.path(), &target)?;
+            }
+        }
+    } else {
+        fs::copy(from, to)?;
+    }
+    remove_path(from)
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+fn scratch_parent(archive: &Path) -> &Path {
+    archive.parent().unwrap_or_else(|| Path::new("."))
+}
+
+// ============================================================================
+// SurrealDB
+// ============================================================================
+
+/// Write a SurrealQL export of the open database to `path`.
+///
+/// The export reads through the live handle, so it is a consistent snapshot
+/// even while the application keeps writing.
+pub async fn export_surrealdb(db: &Surreal<Db>, path: &Path) -> Result<()> {
+    let partial = path.with_extension("surql.partial");
+    if let Err(e) = db.export(partial.clone()).await {
+        fs::remove_file(&partial).ok();
+        return Err(ArchiveError::Snapshot(format!(
+            "SurrealDB export failed: {e}"
+        )));
+    }
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+/// Rebuild the SurrealDB directory `db_dir` from an export written by
+/// [`export_surrealdb`]. `db_dir` should not exist yet; the application
+/// must be closed.
+pub async fn import_surrealdb(db_dir: &Path, export: &Path) -> Result<()> {
+    let failed =
+        |e: surrealdb::Error| ArchiveError::Snapshot(format!("SurrealDB import failed: {e}"));
+    let db = Surreal::new::<RocksDb>(db_dir.to_path_buf())
+        .await
+        .map_err(failed)?;
+    db.use_ns("ttrpg").use_db("main").await.map_err(failed)?;
+    db.import(export).await.map_err(failed)?;
+
+    tracing::info!(path = %db_dir.display(), "SurrealDB rebuilt from backup export");
+    Ok(())
+}
+
+/// Fail with [`ArchiveError::InUse`] while another process has the SurrealDB
+/// directory `db_dir` open.
+///
+/// RocksDB holds an exclusive lock on the directory for as long as the
+/// application runs, so trying to open it is the check. The probe handle is
+/// dropped before returning. Errors other than the lock are left for the
+/// restore to replace.
+pub async fn ensure_closed(db_dir: &Path) -> Result<()> {
+    if !db_dir.exists() {
+        return Ok(());
+    }
+    match Surreal::new::<RocksDb>(db_dir.to_path_buf()).await {
+        Ok(_) => Ok(()),
+        Err(e) if e.to_string().to_lowercase().contains("lock") => {
+            tracing::debug!(path = %db_dir.display(), "SurrealDB is locked: {e}");
+            Err(ArchiveError::InUse)
+        }
+        Err(e) => {
+            tracing::warn!(
+                path = %db_dir.display(),
+                "SurrealDB did not open, restoring anyway: {e}"
+            );
+            Ok(())
+        }
+    }
+}
+
+// ============================================================================
+// SQLite
+// ============================================================================
+
+/// Write a copy of the open SQLite database to `path` with `VACUUM INTO`.
+///
+/// The copy is made by a pooled connection inside one read transaction, so
+/// it includes everything committed to the WAL and needs no `-wal` or
+/// `-shm` file beside it.
+pub async fn snapshot_sqlite(pool: &SqlitePool, path: &Path) -> Result<()> {
+    let partial = path.with_extension("partial");
+    // VACUUM INTO refuses to overwrite
+    fs::remove_file(&partial).ok();
+    let result = sqlx::query("VACUUM INTO ?")
+        .bind(partial.to_string_lossy().to_string())
+        .execute(pool)
+        .await;
+    if let Err(e) = result {
+        fs::remove_file(&partial).ok();
+        return Err(ArchiveError::Snapshot(format!(
+            "SQLite snapshot failed: {e}"
+        )));
+    }
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+// ============================================================================
+// Live Backup & Restore
+// ============================================================================
+
+/// Snapshot SQLite through `sqlite` to the [`SQLITE_SNAPSHOT_NAME`] source
+/// and export SurrealDB through `db` to the [`SURREAL_EXPORT_NAME`] source,
+/// then [`backup_and_rotate`]. Both snapshot files are removed afterwards.
+pub async fn backup_live(
+    db: Arc<Surreal<Db>>,
+    sqlite: SqlitePool,
+    sources: Vec<ArchiveSource>,
+    backup_dir: PathBuf,
+    keep: usize,
+    options: ArchiveOptions,
+) -> Result<ArchiveInfo> {
+    let source_path = |name: &str| {
+        sources
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| s.path.clone())
+    };
+    let snapshot = source_path(SQLITE_SNAPSHOT_NAME);
+    let export = source_path(SURREAL_EXPORT_NAME);
+    let snapshots: Vec<PathBuf> = snapshot.iter().chain(&export).cloned().collect();
+
+    let result = async move {
+        if let Some(path) = &snapshot {
+            snapshot_sqlite(&sqlite, path).await?;
+        }
+        if let Some(path) = &export {
+            export_surrealdb(&db, path).await?;
+        }
+        tokio::task::spawn_blocking(move || {
+            backup_and_rotate(&sources, &backup_dir, keep, &options)
+        })
+        .await
+        .map_err(|e| ArchiveError::Snapshot(format!("Backup task failed: {e}")))?
+    }
+    .await;
+
+    for path in snapshots {
+        fs::remove_file(path).ok();
+    }
+    result
+}
+
+/// [`restore_archive`] into `sources` (normally
+/// [`ArchiveSource::restore_targets`]) once [`ensure_closed`] passes, then
+/// rebuild the SurrealDB directory from the restored export.
+pub async fn restore_live(
+    archive: PathBuf,
+    sources: Vec<ArchiveSource>,
+    passphrase: Option<String>,
+) -> Result<ArchiveManifest> {
+    let source_path = |name: &str| {
+        sources
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| s.path.clone())
+    };
+    let db_dir = source_path(SURREAL_DIR_NAME);
+    let export = source_path(SURREAL_EXPORT_NAME);
+    if let Some(ref dir) = db_dir {
+        ensure_closed(dir).await?;
+    }
+
+    let manifest = tokio::task::spawn_blocking(move || {
+        restore_archive(&archive, &sources, passphrase.as_deref())
+    })
+    .await
+    .map_err(|e| ArchiveError::Snapshot(format!("Restore task failed: {e}")))??;
+
+    if let (Some(dir), Some(export)) = (db_dir, export) {
+        if export.exists() {
+            import_surrealdb(&dir, &export).await?;
+            fs::remove_file(&export)?;
+        }
+    }
+    Ok(manifest)
+}
+
+// ============================================================================
+// Listing & Rotation
+// ============================================================================
+
+/// All archives in `backup_dir`, newest first.
+pub fn list_archives(backup_dir: &Path) -> Result<Vec<ArchiveInfo>> {
+    if !backup_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut archives = Vec::new();
+    for entry in fs::read_dir(backup_dir)?.flatten() {
+        let path = entry.path();
+        let filename = file_name(&path);
+        let Some(created_at) = archive_timestamp(&filename) else {
+            continue;
+        };
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        archives.push(ArchiveInfo {
+            encrypted: filename.ends_with(ENCRYPTED_EXT),
+            filename,
+            path,
+            size_bytes: metadata.len(),
+            created_at,
+        });
+    }
+
+    // Timestamped names sort chronologically; same-second suffixes sort after
+    archives.sort_by(|a, b| b.filename.cmp(&a.filename));
+    Ok(archives)
+}
+
+/// Delete all but the newest `keep` archives. Returns the removed paths.
+pub fn rotate_archives(backup_dir: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    for archive in list_archives(backup_dir)?.into_iter().skip(keep) {
+        fs::remove_file(&archive.path)?;
+        removed.push(archive.path);
+    }
+    if !removed.is_empty() {
+        tracing::info!(count = removed.len(), "Rotated old backup archives");
+    }
+    Ok(removed)
+}
+
+/// Parse `ttrpg_archive_20250101_120000[_n].tar.zst[.enc]`.
+fn archive_timestamp(filename: &str) -> Option<DateTime<Utc>> {
+    let stem = filename
+        .strip_suffix(ENCRYPTED_EXT)
+        .or_else(|| filename.strip_suffix(ARCHIVE_EXT))?
+        .strip_prefix(ARCHIVE_PREFIX)?;
+    let stamp = stem.get(..15)?;
+    NaiveDateTime::parse_from_str(stamp, "%Y%m%d_%H%M%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Scheduling
+// ============================================================================
+
+/// Create an archive and prune old ones in one step.
+pub fn backup_and_rotate(
+    sources: &[ArchiveSource],
+    backup_dir: &Path,
+    keep: usize,
+    options: &ArchiveOptions,
+) -> Result<ArchiveInfo> {
+    let info = create_archive(sources, backup_dir, options)?;
+    rotate_archives(backup_dir, keep.max(1))?;
+    Ok(info)
+}
+
+/// Passphrase for unattended encrypted backups: keyring first, then
+/// [`PASSPHRASE_ENV`].
+pub fn resolve_passphrase(credentials: &CredentialManager) -> Option<String> {
+    credentials
+        .get_secret(PASSPHRASE_SECRET_KEY)
+        .ok()
+        .or_else(|| std::env::var(PASSPHRASE_ENV).ok())
+        .filter(|p| !p.is_empty())
+}
+
+/// Run [`backup_live`] whenever the newest archive is older than
+/// `interval`, for as long as the runtime lives.
+pub fn spawn_scheduler(
+    db: Arc<Surreal<Db>>,
+    sqlite: SqlitePool,
+    sources: Vec<ArchiveSource>,
+    backup_dir: PathBuf,
+    interval: Duration,
+    keep: usize,
+    passphrase: Option<String>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let newest = list_archives(&backup_dir)
+                .ok()
+                .and_then(|archives| archives.first().map(|a| a.created_at));
+            let step = chrono::Duration::from_std(interval).unwrap_or(chrono::Duration::zero());
+            let wait = newest
+                .and_then(|at| (at + step - Utc::now()).to_std().ok())
+                .unwrap_or(Duration::ZERO);
+            tokio::time::sleep(wait).await;
+
+            let options = ArchiveOptions {
+                description: Some("Scheduled backup".to_string()),
+                passphrase: passphrase.clone(),
+            };
+            let result = backup_live(
+                db.clone(),
+                sqlite.clone(),
+                sources.clone(),
+                backup_dir.clone(),
+                keep,
+                options,
+            )
+            .await;
+
+            match result {
+                Ok(info) => {
+                    tracing::info!(archive = %info.filename, "Scheduled backup complete")
+                }
+                Err(e) => tracing::warn!("Scheduled backup failed: {e}"),
+            }
+            // Avoid a tight loop if archives keep failing to appear
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    })
+}
+
+// ============================================================================
+// Encryption
+// ============================================================================
+
+fn derive_cipher(passphrase: &str, salt: &[u8]) -> Result<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| ArchiveError::Encryption(e.to_string()))?;
+    Aes256Gcm::new_from_slice(&key).map_err(|e| ArchiveError::Encryption(e.to_string()))
+}
+
+/// Nonce = random prefix ‖ big-endian segment counter ‖ last-segment flag,
+/// so segments cannot be reordered, dropped or truncated undetected.
+fn segment_nonce(prefix: &[u8; NONCE_PREFIX_LEN], counter: u32, last: bool) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..NONCE_PREFIX_LEN].copy_from_slice(prefix);
+    nonce[NONCE_PREFIX_LEN..11].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = last as u8;
+    nonce
+}
+
+/// Buffers plaintext and seals it in fixed-size segments.
+///
+/// Full segments are only emitted once a whole segment is buffered, so the
+/// final segment written by [`EncryptWriter::finish`] is always short — that
+/// is how the reader recognises it.
+struct EncryptWriter<W: Write> {
+    inner: W,
+    cipher: Aes256Gcm,
+    prefix: [u8; NONCE_PREFIX_LEN],
+    counter: u32,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> EncryptWriter<W> {
+    fn new(mut inner: W, passphrase: &str) -> Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut prefix = [0u8; NONCE_PREFIX_LEN];
+        let mut rng = rand::thread_rng();
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut prefix);
+
+        let cipher = derive_cipher(passphrase, &salt)?;
+        inner.write_all(MAGIC)?;
+        inner.write_all(&salt)?;
+        inner.write_all(&prefix)?;
+
+        Ok(Self {
+            inner,
+            cipher,
+            prefix,
+            counter: 0,
+            buf: Vec::with_capacity(SEGMENT_SIZE),
+        })
+    }
+
+    fn seal(&mut self, segment: &[u8], last: bool) -> io::Result<()> {
+        let nonce = segment_nonce(&self.prefix, self.counter, last);
+        let sealed = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), segment)
+            .map_err(|_| io::Error::other("segment encryption failed"))?;
+        self.inner.write_all(&sealed)?;
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or_else(|| io::Error::other("archive too large"))?;
+        Ok(())
+    }
+
+    fn finish(mut self) -> io::Result<W> {
+        let rest = std::mem::take(&mut self.buf);
+        self.seal(&rest, true)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncryptWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        while self.buf.len() >= SEGMENT_SIZE {
+            let segment: Vec<u8> = self.buf.drain(..SEGMENT_SIZE).collect();
+            self.seal(&segment, false)?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// ============================================================================
+// Staging
+// ============================================================================
+
+/// Scratch directory removed on drop.
+struct Staging(PathBuf);
+
+impl Staging {
+    fn new(parent: &Path) -> Result<Self> {
+        let path = parent.join(format!(".staging-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fixture(root: &Path) -> Vec<ArchiveSource> {
+        let live = root.join("live");
+        fs::create_dir_all(live.join("surrealdb/sub")).unwrap();
+        fs::write(live.join("app.db"), b"sqlite bytes").unwrap();
+        fs::write(live.join("surrealdb/000001.sst"), b"rocks").unwrap();
+        // Incompressible, so encrypted archives span several segments
+        let mut noise = vec![0u8; SEGMENT_SIZE + SEGMENT_SIZE / 2];
+        rand::thread_rng().fill_bytes(&mut noise);
+        fs::write(live.join("surrealdb/sub/MANIFEST"), noise).unwrap();
+        vec![
+            ArchiveSource::new("sqlite/app.db", live.join("app.db")),
+            ArchiveSource::new("surrealdb", live.join("surrealdb")),
+            ArchiveSource::new("missing", live.join("missing")),
+        ]
+    }
+
+    #[test]
+    fn test_archive_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let sources = fixture(temp.path());
+        let backups = temp.path().join("backups");
+
+        let info = create_archive(&sources, &backups, &ArchiveOptions::default()).unwrap();
+        assert!(!info.encrypted);
+        assert!(info.filename.ends_with(ARCHIVE_EXT));
+
+        fs::write(&sources[0].path, b"changed").unwrap();
+        fs::remove_file(sources[1].path.join("000001.sst")).unwrap();
+
+        let manifest = restore_archive(&info.path, &sources, None).unwrap();
+        assert_eq!(manifest.entries.len(), 3);
+        assert_eq!(manifest.schema_version, SCHEMA_VERSION);
+        assert_eq!(fs::read(&sources[0].path).unwrap(), b"sqlite bytes");
+        assert_eq!(
+            fs::read(sources[1].path.join("000001.sst")).unwrap(),
+            b"rocks"
+        );
+        assert_eq!(
+            fs::read(pre_restore_path(&sources[0].path)).unwrap(),
+            b"changed"
+        );
+    }
+
+    #[test]
+    fn test_encrypted_archive() {
+        let temp = TempDir::new().unwrap();
+        let sources = fixture(temp.path());
+        let backups = temp.path().join("backups");
+        let options = ArchiveOptions {
+            description: Some("secret".to_string()),
+            passphrase: Some("hunter2".to_string()),
+        };
+
+        let info = create_archive(&sources, &backups, &options).unwrap();
+        assert!(info.encrypted);
+        assert!(matches!(
+            verify_archive(&info.path, None),
+            Err(ArchiveError::PassphraseRequired)
+        ));
+        assert!(matches!(
+            verify_archive(&info.path, Some("wrong")),
+            Err(ArchiveError::Decryption)
+        ));
+
+        let manifest = verify_archive(&info.path, Some("hunter2")).unwrap();
+        assert!(manifest.encrypted);
+        assert_eq!(manifest.description.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn test_restore_refuses_schema_mismatch() {
+        let temp = TempDir::new().unwrap();
+        let sources = fixture(temp.path());
+        let backups = temp.path().join("backups");
+
+        let info = create_with_schema(&sources, &backups, &ArchiveOptions::default(), 1).unwrap();
+        fs::write(&sources[0].path, b"current").unwrap();
+
+        let err = restore_archive(&info.path, &sources, None).unwrap_err();
+        assert!(matches!(
+            err,
+            ArchiveError::SchemaMismatch { archive: 1, .. }
+        ));
+        // Live data untouched
+        assert_eq!(fs::read(&sources[0].path).unwrap(), b"current");
+    }
+
+    #[test]
+    fn test_rotation_keeps_newest() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        for name in [
+            "ttrpg_archive_20250101_000000.tar.zst",
+            "ttrpg_archive_20250102_000000.tar.zst.enc",
+            "ttrpg_archive_20250103_000000.tar.zst",
+            "ttrpg_backup_20250101_000000.db",
+        ] {
+            fs::write(dir.join(name), b"x").unwrap();
+        }
+
+        let removed = rotate_archives(dir, 2).unwrap();
+        assert_eq!(
+            removed,
+            vec![dir.join("ttrpg_archive_20250101_000000.tar.zst")]
+        );
+
+        let remaining = list_archives(dir).unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(
+            remaining[0].filename,
+            "ttrpg_archive_20250103_000000.tar.zst"
+        );
+        assert!(remaining[1].encrypted);
+        assert!(dir.join("ttrpg_backup_20250101_000000.db").exists());
+    }
+
+    #[tokio::test]
+    async fn test_surrealdb_export_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let db = Surreal::new::<RocksDb>(temp.path().join("live"))
+            .await
+            .unwrap();
+        db.use_ns("ttrpg").use_db("main").await.unwrap();
+        db.query("CREATE note:one SET text = 'goblin ambush'")
+            .await
+            .unwrap();
+
+        let export = temp.path().join(SURREAL_EXPORT_NAME);
+        export_surrealdb(&db, &export).await.unwrap();
+        assert!(fs::read_to_string(&export)
+            .unwrap()
+            .contains("goblin ambush"));
+        assert!(!export.with_extension("surql.partial").exists());
+
+        import_surrealdb(&temp.path().join("restored"), &export)
+            .await
+            .unwrap();
+        assert!(temp.path().join("restored").is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_snapshot_includes_wal() {
+        let temp = TempDir::new().unwrap();
+        let options = sqlx::sqlite::SqliteConnectOptions::new()
+            .filename(temp.path().join("live.db"))
+            .create_if_missing(true)
+            .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal);
+        let pool = SqlitePool::connect_with(options).await.unwrap();
+        sqlx::query("CREATE TABLE notes (text TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO notes VALUES ('goblin ambush')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let snapshot = temp.path().join(SQLITE_SNAPSHOT_FILE);
+        snapshot_sqlite(&pool, &snapshot).await.unwrap();
+        // Taken twice, as the scheduler does: the partial copy never lingers
+        snapshot_sqlite(&pool, &snapshot).await.unwrap();
+        assert!(!snapshot.with_extension("partial").exists());
+
+        let copy =
+            SqlitePool::connect_with(sqlx::sqlite::SqliteConnectOptions::new().filename(&snapshot))
+                .await
+                .unwrap();
+        let (text,): (String,) = sqlx::query_as("SELECT text FROM notes")
+            .fetch_one(&copy)
+            .await
+            .unwrap();
+        assert_eq!(text, "goblin ambush");
+    }
+
+    #[tokio::test]
+    async fn test_restore_refuses_while_database_is_open() {
+        let temp = TempDir::new().unwrap();
+        let db_dir = temp.path().join(SURREAL_DIR_NAME);
+        let db = Surreal::new::<RocksDb>(db_dir.clone()).await.unwrap();
+
+        assert!(matches!(
+            ensure_closed(&db_dir).await,
+            Err(ArchiveError::InUse)
+        ));
+        drop(db);
+        assert!(ensure_closed(&temp.path().join("absent")).await.is_ok());
+    }
+
+    #[test]
+    fn test_restore_targets_replace_live_sqlite_files() {
+        let data_dir = Path::new("/data");
+        let targets = ArchiveSource::restore_targets(data_dir, Path::new("/config.toml"));
+        let path_of = |name: &str| {
+            targets
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.path.clone())
+        };
+
+        assert_eq!(
+            path_of(SQLITE_SNAPSHOT_NAME),
+            Some(data_dir.join("ttrpg_assistant.db"))
+        );
+        assert_eq!(
+            path_of("sqlite/ttrpg_assistant.db-wal"),
+            Some(data_dir.join("ttrpg_assistant.db-wal"))
+        );
+        assert!(
+            !ArchiveSource::defaults(data_dir, Path::new("/config.toml"))
+                .iter()
+                .any(|s| s.name.ends_with("-shm"))
+        );
+    }
+}
@@ -0,0 +1,883 @@
+//! Fantasy Calendar Engine
+//!
+//! User-definable calendars for in-game dates: month names and lengths,
+//! intercalary days, leap rules, weekdays, eras, moons, seasons and holidays.
+//! Presets (Gregorian, Harptos, Golarion) are bundled as YAML assets; extra
+//! calendars are loaded from `<data_dir>/calendars/*.yaml`.
+//!
+//! Intercalary days (festivals that belong to no month) are modelled as
+//! `intercalary` months, so an `InGameDate`'s `month` is a 1-based index into
+//! `Calendar::months` including them. Leap-only days use `days: 0` plus
+//! `leap_days`, e.g. Harptos' Shieldmeet.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+use super::world_state::InGameDate;
+use crate::core::assets::AssetLoader;
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Error, Debug)]
+pub enum CalendarError {
+    #[error("Invalid calendar definition: {0}")]
+    InvalidDefinition(String),
+
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, CalendarError>;
+
+/// Calendar used when a campaign doesn't pick one
+pub const DEFAULT_CALENDAR: &str = "Gregorian";
+
+// ============================================================================
+// Calendar Definition
+// ============================================================================
+
+/// A month, or an intercalary festival that sits between months
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Month {
+    pub name: String,
+    /// Days in a common year (0 for days that only exist in leap years)
+    pub days: u8,
+    /// Extra days in leap years
+    #[serde(default)]
+    pub leap_days: u8,
+    /// Intercalary days fall outside the week cycle
+    #[serde(default)]
+    pub intercalary: bool,
+    /// Alternative name (e.g. "Deepwinter" for Hammer)
+    #[serde(default)]
+    pub alias: Option<String>,
+}
+
+impl Month {
+    /// Length of this month in a common or leap year
+    pub fn length(&self, leap: bool) -> u8 {
+        if leap {
+            self.days + self.leap_days
+        } else {
+            self.days
+        }
+    }
+}
+
+/// Leap-year rule: every `every` years, except every `except_every` years,
+/// unless every `unless_every` years (the Gregorian rule is 4/100/400).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LeapRule {
+    pub every: u32,
+    #[serde(default)]
+    pub except_every: Option<u32>,
+    #[serde(default)]
+    pub unless_every: Option<u32>,
+    /// Subtracted from the year before testing divisibility
+    #[serde(default)]
+    pub offset: i32,
+}
+
+impl LeapRule {
+    pub fn is_leap(&self, year: i32) -> bool {
+        let y = year as i64 - self.offset as i64;
+        let divides = |n: u32| y.rem_euclid(n as i64) == 0;
+        if !divides(self.every) {
+            return false;
+        }
+        match self.except_every {
+            Some(except) if divides(except) => self.unless_every.is_some_and(divides),
+            _ => true,
+        }
+    }
+
+    /// Signed leap-year count relative to year 0, such that
+    /// `leaps_before(b) - leaps_before(a)` counts the leap years in `[a, b)`.
+    fn leaps_before(&self, year: i32) -> i64 {
+        let y = year as i64 - self.offset as i64;
+        // Multiples of n in [0, y), negated for y < 0: ceil(y / n)
+        let multiples = |n: u32| -(-y).div_euclid(n as i64);
+        let mut count = multiples(self.every);
+        if let Some(except) = self.except_every {
+            count -= multiples(except);
+            if let Some(unless) = self.unless_every {
+                count += multiples(unless);
+            }
+        }
+        count
+    }
+
+    /// Average share of years that are leap years
+    fn density(&self) -> f64 {
+        let mut density = 1.0 / self.every as f64;
+        if let Some(except) = self.except_every {
+            density -= 1.0 / except as f64;
+            if let Some(unless) = self.unless_every {
+                density += 1.0 / unless as f64;
+            }
+        }
+        density
+    }
+}
+
+/// A named era; the last listed era whose start is on or before a year applies
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Era {
+    pub name: String,
+    #[serde(default)]
+    pub abbreviation: Option<String>,
+    /// First year of the era; `None` covers all earlier years
+    #[serde(default)]
+    pub start_year: Option<i32>,
+}
+
+impl Era {
+    /// Abbreviation if set, otherwise the full name
+    pub fn short_name(&self) -> &str {
+        self.abbreviation.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// A moon with a fixed synodic cycle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Moon {
+    pub name: String,
+    /// Days from new moon to new moon
+    pub cycle_days: f64,
+    /// Day number (days since 1/1/1) of a known new moon
+    #[serde(default)]
+    pub new_moon_offset: f64,
+}
+
+/// A season, starting on a month/day each year and lasting until the next
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Season {
+    pub name: String,
+    /// Month name (or alias)
+    pub month: String,
+    pub day: u8,
+}
+
+/// A holiday recurring on the same month/day every year
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Holiday {
+    pub name: String,
+    /// Month name (or alias)
+    pub month: String,
+    pub day: u8,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Phase of a moon, in eighths of its cycle
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MoonPhase {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    const ALL: [MoonPhase; 8] = [
+        MoonPhase::New,
+        MoonPhase::WaxingCrescent,
+        MoonPhase::FirstQuarter,
+        MoonPhase::WaxingGibbous,
+        MoonPhase::Full,
+        MoonPhase::WaningGibbous,
+        MoonPhase::LastQuarter,
+        MoonPhase::WaningCrescent,
+    ];
+
+    /// Phase for a position in the cycle (0.0 = new, 0.5 = full)
+    pub fn from_fraction(fraction: f64) -> Self {
+        let eighth = (fraction.rem_euclid(1.0) * 8.0).round() as usize % 8;
+        Self::ALL[eighth]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MoonPhase::New => "New",
+            MoonPhase::WaxingCrescent => "Waxing Crescent",
+            MoonPhase::FirstQuarter => "First Quarter",
+            MoonPhase::WaxingGibbous => "Waxing Gibbous",
+            MoonPhase::Full => "Full",
+            MoonPhase::WaningGibbous => "Waning Gibbous",
+            MoonPhase::LastQuarter => "Last Quarter",
+            MoonPhase::WaningCrescent => "Waning Crescent",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            MoonPhase::New => "🌑",
+            MoonPhase::WaxingCrescent => "🌒",
+            MoonPhase::FirstQuarter => "🌓",
+            MoonPhase::WaxingGibbous => "🌔",
+            MoonPhase::Full => "🌕",
+            MoonPhase::WaningGibbous => "🌖",
+            MoonPhase::LastQuarter => "🌗",
+            MoonPhase::WaningCrescent => "🌘",
+        }
+    }
+}
+
+/// A complete calendar system
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Calendar {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Months and intercalary days in year order
+    pub months: Vec<Month>,
+    #[serde(default)]
+    pub leap_year: Option<LeapRule>,
+    #[serde(default)]
+    pub weekdays: Vec<String>,
+    /// Index into `weekdays` of the first day of year 1
+    #[serde(default)]
+    pub epoch_weekday: usize,
+    #[serde(default)]
+    pub eras: Vec<Era>,
+    #[serde(default)]
+    pub moons: Vec<Moon>,
+    #[serde(default)]
+    pub seasons: Vec<Season>,
+    #[serde(default)]
+    pub holidays: Vec<Holiday>,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self::preset(DEFAULT_CALENDAR).expect("bundled Gregorian calendar must parse")
+    }
+}
+
+impl Calendar {
+    // ========================================================================
+    // Loading
+    // ========================================================================
+
+    /// Parse and validate a calendar from YAML
+    pub fn from_yaml_str(yaml: &str) -> Result<Self> {
+        let calendar: Calendar =
+            serde_yaml::from_str(yaml).map_err(|e| CalendarError::ParseError(e.to_string()))?;
+        calendar.validate()?;
+        Ok(calendar)
+    }
+
+    /// All bundled preset calendars
+    pub fn presets() -> Vec<Calendar> {
+        AssetLoader::load_calendars()
+    }
+
+    /// Look up a bundled preset by name (case-insensitive)
+    pub fn preset(name: &str) -> Option<Calendar> {
+        Self::presets()
+            .into_iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Load user calendars from `*.yaml`/`*.yml` files in `dir`.
+    ///
+    /// Invalid files are logged and skipped; a missing directory yields none.
+    pub fn load_dir(dir: &Path) -> Vec<Calendar> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case("yaml") || e.eq_ignore_ascii_case("yml"))
+            })
+            .collect();
+        paths.sort();
+
+        let mut calendars = Vec::new();
+        for path in paths {
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(CalendarError::from)
+                .and_then(|yaml| Self::from_yaml_str(&yaml));
+            match parsed {
+                Ok(calendar) => calendars.push(calendar),
+                Err(e) => log::warn!("Skipping calendar {}: {e}", path.display()),
+            }
+        }
+        calendars
+    }
+
+    /// Presets followed by user calendars from `<data_dir>/calendars`.
+    ///
+    /// A user calendar with a preset's name replaces that preset.
+    pub fn available(data_dir: &Path) -> Vec<Calendar> {
+        let mut calendars = Self::presets();
+        for custom in Self::load_dir(&data_dir.join("calendars")) {
+            match calendars
+                .iter_mut()
+                .find(|c| c.name.eq_ignore_ascii_case(&custom.name))
+            {
+                Some(existing) => *existing = custom,
+                None => calendars.push(custom),
+            }
+        }
+        calendars
+    }
+
+    /// Check the definition is internally consistent
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: String| Err(CalendarError::InvalidDefinition(format!("{}: {msg}", self.name)));
+
+        if self.name.trim().is_empty() {
+            return Err(CalendarError::InvalidDefinition("calendar name is empty".to_string()));
+        }
+        if self.months.is_empty() {
+            return invalid("no months defined".to_string());
+        }
+        if self.months.len() > u8::MAX as usize {
+            return invalid("too many months".to_string());
+        }
+        for (i, month) in self.months.iter().enumerate() {
+            if month.name.trim().is_empty() {
+                return invalid(format!("month {} has no name", i + 1));
+            }
+            if month.days == 0 && month.leap_days == 0 {
+                return invalid(format!("{} has no days", month.name));
+            }
+            if month.days.checked_add(month.leap_days).is_none() {
+                return invalid(format!("{} is too long", month.name));
+            }
+            if self.months[..i]
+                .iter()
+                .any(|m| m.name.eq_ignore_ascii_case(&month.name))
+            {
+                return invalid(format!("duplicate month {}", month.name));
+            }
+        }
+        if self.common_year_length() == 0 {
+            return invalid("common years have no days".to_string());
+        }
+
+        match &self.leap_year {
+            Some(rule) => {
+                if rule.every == 0 {
+                    return invalid("leap rule 'every' must be positive".to_string());
+                }
+                if let Some(except) = rule.except_every {
+                    if except == 0 || except % rule.every != 0 {
+                        return invalid("'except_every' must be a multiple of 'every'".to_string());
+                    }
+                    if let Some(unless) = rule.unless_every {
+                        if unless == 0 || unless % except != 0 {
+                            return invalid(
+                                "'unless_every' must be a multiple of 'except_every'".to_string(),
+                            );
+                        }
+                    }
+                } else if rule.unless_every.is_some() {
+                    return invalid("'unless_every' requires 'except_every'".to_string());
+                }
+            }
+            None => {
+                if self.months.iter().any(|m| m.leap_days > 0) {
+                    return invalid("leap days defined without a leap rule".to_string());
+                }
+            }
+        }
+
+        if !self.weekdays.is_empty() && self.epoch_weekday >= self.weekdays.len() {
+            return invalid("'epoch_weekday' is out of range".to_string());
+        }
+        for moon in &self.moons {
+            if !moon.cycle_days.is_finite() || moon.cycle_days <= 0.0 {
+                return invalid(format!("moon {} needs a positive cycle", moon.name));
+            }
+        }
+        for (label, month, day) in self
+            .seasons
+            .iter()
+            .map(|s| (&s.name, &s.month, s.day))
+            .chain(self.holidays.iter().map(|h| (&h.name, &h.month, h.day)))
+        {
+            let Some(index) = self.month_index(month) else {
+                return invalid(format!("{label} refers to unknown month {month}"));
+            };
+            let max = self.months[index as usize - 1].length(true);
+            if day == 0 || day > max {
+                return invalid(format!("{label} falls on day {day} of {month}, which has {max} days"));
+            }
+        }
+        Ok(())
+    }
+
+    // ========================================================================
+    // Structure
+    // ========================================================================
+
+    pub fn is_leap_year(&self, year: i32) -> bool {
+        self.leap_year.as_ref().is_some_and(|r| r.is_leap(year))
+    }
+
+    /// Month by 1-based index
+    pub fn month(&self, month: u8) -> Option<&Month> {
+        (month as usize)
+            .checked_sub(1)
+            .and_then(|i| self.months.get(i))
+    }
+
+    /// Display name for a 1-based month index
+    pub fn month_name(&self, month: u8) -> String {
+        self.month(month)
+            .map(|m| m.name.clone())
+            .unwrap_or_else(|| format!("Month {month}"))
+    }
+
+    /// 1-based index of a month by name or alias (case-insensitive)
+    pub fn month_index(&self, name: &str) -> Option<u8> {
+        let name = name.trim();
+        self.months
+            .iter()
+            .position(|m| {
+                m.name.eq_ignore_ascii_case(name)
+                    || m.alias.as_deref().is_some_and(|a| a.eq_ignore_ascii_case(name))
+            })
+            .map(|i| i as u8 + 1)
+    }
+
+    /// Days in a month of a given year (0 for an absent leap day)
+    pub fn month_length(&self, year: i32, month: u8) -> u8 {
+        self.month(month)
+            .map(|m| m.length(self.is_leap_year(year)))
+            .unwrap_or(0)
+    }
+
+    pub fn year_length(&self, year: i32) -> u32 {
+        let leap = self.is_leap_year(year);
+        self.months.iter().map(|m| m.length(leap) as u32).sum()
+    }
+
+    fn common_year_length(&self) -> u32 {
+        self.months.iter().map(|m| m.days as u32).sum()
+    }
+
+    /// Days before `year` begins, counting only months matching `include`
+    fn days_before_year(&self, year: i32, include: impl Fn(&Month) -> bool) -> i64 {
+        let (common, extra) = self
+            .months
+            .iter()
+            .filter(|m| include(m))
+            .fold((0i64, 0i64), |(c, e), m| (c + m.days as i64, e + m.leap_days as i64));
+        let leaps = self
+            .leap_year
+            .as_ref()
+            .map(|r| r.leaps_before(year) - r.leaps_before(1))
+            .unwrap_or(0);
+        (year as i64 - 1) * common + extra * leaps
+    }
+
+    /// Days before `month` within `year`, counting only months matching `include`
+    fn days_before_month(&self, year: i32, month: u8, include: impl Fn(&Month) -> bool) -> i64 {
+        let leap = self.is_leap_year(year);
+        self.months
+            .iter()
+            .take(month.saturating_sub(1) as usize)
+            .filter(|m| include(m))
+            .map(|m| m.length(leap) as i64)
+            .sum()
+    }
+
+    // ========================================================================
+    // Date Arithmetic
+    // ========================================================================
+
+    /// Check a date exists in this calendar
+    pub fn validate_date(&self, date: &InGameDate) -> Result<()> {
+        let Some(month) = self.month(date.month) else {
+            return Err(CalendarError::InvalidDate(format!(
+                "{} has no month {}",
+                self.name, date.month
+            )));
+        };
+        let length = self.month_length(date.year, date.month);
+        if date.day == 0 || date.day > length {
+            return Err(CalendarError::InvalidDate(if length == 0 {
+                format!("{} does not occur in {}", month.name, date.year)
+            } else {
+                format!("{} has {} days, not {}", month.name, length, date.day)
+            }));
+        }
+        Ok(())
+    }
+
+    /// Days since the first day of year 1 (negative before it)
+    pub fn day_number(&self, date: &InGameDate) -> Result<i64> {
+        self.validate_date(date)?;
+        Ok(self.days_before_year(date.year, |_| true)
+            + self.days_before_month(date.year, date.month, |_| true)
+            + date.day as i64
+            - 1)
+    }
+
+    /// Date for a day number; the era is filled from the calendar's eras
+    pub fn date_from_day_number(&self, day_number: i64) -> InGameDate {
+        let mean_year = self.common_year_length() as f64
+            + self.leap_year.as_ref().map(|r| r.density()).unwrap_or(0.0)
+                * self.months.iter().map(|m| m.leap_days as f64).sum::<f64>();
+        let guess = 1 + (day_number as f64 / mean_year).floor() as i64;
+        let mut year = guess.clamp(i32::MIN as i64 + 1, i32::MAX as i64 - 1) as i32;
+
+        // The estimate is off by at most a year or so; walk to the exact one
+        while self.days_before_year(year, |_| true) > day_number {
+            year -= 1;
+        }
+        while self.days_before_year(year + 1, |_| true) <= day_number {
+            year += 1;
+        }
+
+        let mut remaining = day_number - self.days_before_year(year, |_| true);
+        let mut month = 1u8;
+        for index in 1..=self.months.len() as u8 {
+            let length = self.month_length(year, index) as i64;
+            if remaining < length {
+                month = index;
+                break;
+            }
+            remaining -= length;
+        }
+
+        InGameDate {
+            year,
+            month,
+            day: remaining as u8 + 1,
+            era: self.era_for_year(year).map(|e| e.name.clone()),
+            calendar: self.name.clone(),
+            time: None,
+        }
+    }
+
+    /// Move a date by a number of days (negative goes back), keeping its time of day
+    pub fn add_days(&self, date: &InGameDate, days: i64) -> Result<InGameDate> {
+        let mut next = self.date_from_day_number(self.day_number(date)? + days);
+        if next.era.is_none() {
+            next.era = date.era.clone();
+        }
+        next.time = date.time.clone();
+        Ok(next)
+    }
+
+    /// Signed number of days from `from` to `to`
+    pub fn days_between(&self, from: &InGameDate, to: &InGameDate) -> Result<i64> {
+        Ok(self.day_number(to)? - self.day_number(from)?)
+    }
+
+    /// Pull a date into range after a calendar change, keeping year/month/day
+    /// where they still exist
+    pub fn clamp_date(&self, date: &InGameDate) -> InGameDate {
+        let mut clamped = date.clone();
+        clamped.calendar = self.name.clone();
+        clamped.month = date.month.clamp(1, self.months.len() as u8);
+        // Skip a leap-only month in a common year
+        while self.month_length(clamped.year, clamped.month) == 0 {
+            clamped.month = clamped.month % self.months.len() as u8 + 1;
+        }
+        clamped.day = date
+            .day
+            .clamp(1, self.month_length(clamped.year, clamped.month));
+        clamped
+    }
+
+    // ========================================================================
+    // Date Details
+    // ========================================================================
+
+    /// Weekday index, or `None` for intercalary days or calendars without weeks
+    pub fn weekday_index(&self, date: &InGameDate) -> Option<usize> {
+        if self.weekdays.is_empty() || self.validate_date(date).is_err() {
+            return None;
+        }
+        if self.month(date.month)?.intercalary {
+            return None;
+        }
+        let regular = |m: &Month| !m.intercalary;
+        let days = self.days_before_year(date.year, regular)
+            + self.days_before_month(date.year, date.month, regular)
+            + date.day as i64
+            - 1;
+        Some((self.epoch_weekday as i64 + days).rem_euclid(self.weekdays.len() as i64) as usize)
+    }
+
+    pub fn weekday(&self, date: &InGameDate) -> Option<&str> {
+        self.weekday_index(date)
+            .map(|i| self.weekdays[i].as_str())
+    }
+
+    /// The era a year belongs to
+    pub fn era_for_year(&self, year: i32) -> Option<&Era> {
+        self.eras
+            .iter()
+            .rev()
+            .find(|e| e.start_year.is_none_or(|start| start <= year))
+    }
+
+    pub fn moon_phase(&self, moon: &Moon, date: &InGameDate) -> Result<MoonPhase> {
+        let age = self.day_number(date)? as f64 - moon.new_moon_offset;
+        Ok(MoonPhase::from_fraction(age / moon.cycle_days))
+    }
+
+    /// Phase of every moon on a date
+    pub fn moon_phases(&self, date: &InGameDate) -> Vec<(&Moon, MoonPhase)> {
+        self.moons
+            .iter()
+            .filter_map(|m| self.moon_phase(m, date).ok().map(|p| (m, p)))
+            .collect()
+    }
+
+    /// Season in effect on a date; seasons wrap around the new year
+    pub fn season(&self, date: &InGameDate) -> Option<&Season> {
+        let ordinal = |month: u8, day: u8| self.days_before_month(date.year, month, |_| true) + day as i64;
+        let today = ordinal(date.month, date.day);
+        let starts: Vec<(i64, &Season)> = self
+            .seasons
+            .iter()
+            .filter_map(|s| self.month_index(&s.month).map(|m| (ordinal(m, s.day), s)))
+            .collect();
+        starts
+            .iter()
+            .filter(|(start, _)| *start <= today)
+            .max_by_key(|(start, _)| *start)
+            .or_else(|| starts.iter().max_by_key(|(start, _)| *start))
+            .map(|(_, s)| *s)
+    }
+
+    /// Holidays falling on a date
+    pub fn holidays_on(&self, date: &InGameDate) -> Vec<&Holiday> {
+        self.holidays
+            .iter()
+            .filter(|h| h.day == date.day && self.month_index(&h.month) == Some(date.month))
+            .collect()
+    }
+
+    /// Format a date, e.g. "15 Mirtul 1492 DR" or "Midsummer 1492 DR"
+    pub fn format_date(&self, date: &InGameDate) -> String {
+        let month = self.month_name(date.month);
+        let single_day = self
+            .month(date.month)
+            .is_some_and(|m| m.intercalary && m.length(true) <= 1);
+        let mut out = if single_day {
+            format!("{} {}", month, date.year)
+        } else {
+            format!("{} {} {}", date.day, month, date.year)
+        };
+        if let Some(era) = self
+            .era_for_year(date.year)
+            .map(|e| e.short_name().to_string())
+            .or_else(|| date.era.clone())
+        {
+            out.push(' ');
+            out.push_str(&era);
+        }
+        if let Some(ref time) = date.time {
+            out.push_str(&format!(" - {}:{:02}", time.hour, time.minute));
+        }
+        out
+    }
+
+    /// A date in this calendar, with its era filled in
+    pub fn date(&self, year: i32, month: u8, day: u8) -> Result<InGameDate> {
+        let date = InGameDate {
+            year,
+            month,
+            day,
+            era: self.era_for_year(year).map(|e| e.name.clone()),
+            calendar: self.name.clone(),
+            time: None,
+        };
+        self.validate_date(&date)?;
+        Ok(date)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gregorian() -> Calendar {
+        Calendar::preset("Gregorian").unwrap()
+    }
+
+    fn harptos() -> Calendar {
+        Calendar::preset("harptos").unwrap()
+    }
+
+    #[test]
+    fn test_presets_validate() {
+        let presets = Calendar::presets();
+        assert_eq!(presets.len(), AssetLoader::CALENDAR_COUNT);
+        for calendar in &presets {
+            calendar.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_gregorian_leap_years() {
+        let cal = gregorian();
+        assert!(cal.is_leap_year(2000));
+        assert!(cal.is_leap_year(2024));
+        assert!(!cal.is_leap_year(1900));
+        assert!(!cal.is_leap_year(2023));
+        assert!(cal.is_leap_year(0));
+        assert!(cal.is_leap_year(-4));
+        assert_eq!(cal.year_length(2024), 366);
+        assert_eq!(cal.month_length(2023, 2), 28);
+    }
+
+    #[test]
+    fn test_gregorian_matches_real_dates() {
+        let cal = gregorian();
+        // 1 January 2000 is day 730120 of the proleptic Gregorian calendar
+        let date = cal.date(2000, 1, 1).unwrap();
+        assert_eq!(cal.day_number(&date).unwrap(), 730_119);
+        assert_eq!(cal.weekday(&date), Some("Saturday"));
+        assert_eq!(cal.weekday(&cal.date(2024, 2, 29).unwrap()), Some("Thursday"));
+
+        let next = cal.add_days(&cal.date(2023, 12, 31).unwrap(), 60).unwrap();
+        assert_eq!((next.year, next.month, next.day), (2024, 2, 29));
+        assert_eq!(cal.format_date(&next), "29 February 2024 CE");
+    }
+
+    #[test]
+    fn test_day_number_roundtrip() {
+        for cal in Calendar::presets() {
+            for n in [-800_000i64, -366, -1, 0, 1, 364, 365, 1_460, 550_000, 737_000] {
+                let date = cal.date_from_day_number(n);
+                assert_eq!(cal.day_number(&date).unwrap(), n, "{} day {n}", cal.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_harptos_festivals_and_shieldmeet() {
+        let cal = harptos();
+        assert_eq!(cal.year_length(1491), 365);
+        assert_eq!(cal.year_length(1492), 366);
+
+        // Flamerule 30 is followed by Midsummer, then Shieldmeet in leap years
+        let flamerule = cal.month_index("Flamerule").unwrap();
+        let last = cal.date(1492, flamerule, 30).unwrap();
+        let midsummer = cal.add_days(&last, 1).unwrap();
+        assert_eq!(cal.format_date(&midsummer), "Midsummer 1492 DR");
+        assert_eq!(cal.weekday(&midsummer), None);
+        let shieldmeet = cal.add_days(&last, 2).unwrap();
+        assert_eq!(cal.month_name(shieldmeet.month), "Shieldmeet");
+        let eleasis = cal.add_days(&last, 3).unwrap();
+        assert_eq!(cal.format_date(&eleasis), "1 Eleasis 1492 DR");
+
+        let last_1491 = cal.date(1491, flamerule, 30).unwrap();
+        assert_eq!(cal.add_days(&last_1491, 2).unwrap().month, eleasis.month);
+        assert!(cal.date(1491, shieldmeet.month, 1).is_err());
+    }
+
+    #[test]
+    fn test_harptos_months_start_on_first_day() {
+        let cal = harptos();
+        for year in [1358, 1372, 1491, 1492] {
+            for (i, month) in cal.months.iter().enumerate() {
+                if !month.intercalary {
+                    let date = cal.date(year, i as u8 + 1, 1).unwrap();
+                    assert_eq!(cal.weekday(&date), Some("First-day"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_month_lookup_by_alias() {
+        let cal = harptos();
+        assert_eq!(cal.month_index("deepwinter"), Some(1));
+        assert_eq!(cal.month_index("Hammer"), Some(1));
+        assert_eq!(cal.month_index("Nowhere"), None);
+    }
+
+    #[test]
+    fn test_seasons_wrap_and_holidays() {
+        let cal = harptos();
+        let hammer = cal.date(1492, 1, 10).unwrap();
+        assert_eq!(cal.season(&hammer).unwrap().name, "Winter");
+        let ches = cal.month_index("Ches").unwrap();
+        let equinox = cal.date(1492, ches, 19).unwrap();
+        assert_eq!(cal.season(&equinox).unwrap().name, "Spring");
+        assert_eq!(cal.holidays_on(&equinox)[0].name, "Spring Equinox");
+        assert!(cal.holidays_on(&hammer).is_empty());
+    }
+
+    #[test]
+    fn test_moon_phases_cycle() {
+        let cal = gregorian();
+        let moon = &cal.moons[0];
+        // Known full moon: 21 January 2000
+        let full = cal.date(2000, 1, 21).unwrap();
+        assert_eq!(cal.moon_phase(moon, &full).unwrap(), MoonPhase::Full);
+        let new = cal.date(2000, 1, 6).unwrap();
+        assert_eq!(cal.moon_phase(moon, &new).unwrap(), MoonPhase::New);
+        assert_eq!(MoonPhase::from_fraction(0.25), MoonPhase::FirstQuarter);
+        assert_eq!(MoonPhase::from_fraction(0.99), MoonPhase::New);
+    }
+
+    #[test]
+    fn test_invalid_dates_and_clamping() {
+        let cal = gregorian();
+        assert!(cal.date(2023, 2, 29).is_err());
+        assert!(cal.date(2023, 13, 1).is_err());
+
+        let harptos = harptos();
+        let mut date = harptos.date(1492, 3, 30).unwrap();
+        date.month = 13;
+        let clamped = cal.clamp_date(&date);
+        assert_eq!((clamped.month, clamped.day), (12, 30));
+        assert_eq!(clamped.calendar, "Gregorian");
+    }
+
+    #[test]
+    fn test_custom_calendar_validation() {
+        let yaml = r#"
+name: Tiny
+months:
+  - { name: Sow, days: 10 }
+  - { name: Reap, days: 10, leap_days: 2 }
+leap_year: { every: 3 }
+weekdays: [Work, Rest]
+holidays:
+  - { name: Harvest Home, month: Reap, day: 12 }
+"#;
+        let cal = Calendar::from_yaml_str(yaml).unwrap();
+        assert_eq!(cal.year_length(3), 22);
+        assert_eq!(cal.year_length(4), 20);
+
+        let broken = yaml.replace("leap_year: { every: 3 }\n", "");
+        assert!(matches!(
+            Calendar::from_yaml_str(&broken),
+            Err(CalendarError::InvalidDefinition(_))
+        ));
+        let bad_holiday = yaml.replace("day: 12", "day: 13");
+        assert!(Calendar::from_yaml_str(&bad_holiday).is_err());
+    }
+}
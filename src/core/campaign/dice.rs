@@ -2,12 +2,20 @@
 //!
 //! Phase 8 of the Campaign Generation Overhaul.
 //!
-//! Parses standard TTRPG dice notation including:
+//! Parses TTRPG dice expressions into a small AST and rolls them with a
+//! per-die breakdown. Supported syntax:
 //! - Standard dice: d4, d6, d8, d10, d12, d20, d100
 //! - Compound dice: 2d6, 3d8+5, 4d6-2
+//! - Multi-term expressions: 1d8+2d6+5, (1d6+2)*2, 1d10/2
 //! - Percentile: d100, d%
 //! - D66 tables: d66 (read as tens/ones)
-//! - Fudge/Fate dice: dF (future extension)
+//! - Keep/drop: 4d6kh3, 2d20kl1, 4d6dl1, 5d10dh2 (`k3` is shorthand for `kh3`)
+//! - Exploding dice: 3d6!, 5d10!>=9 (explode on max face unless a compare point is given)
+//! - Success pools: 8d10>=7, 6d6=6, 8d10>=7f1 (successes minus failures)
+//! - Fudge/Fate dice: 4dF
+//!
+//! Multiplication and division only accept a constant right-hand side, so
+//! every expression has well-defined bounds for random table coverage.
 //!
 //! ## Examples
 //!
@@ -16,12 +24,15 @@
 //!
 //! let notation = DiceNotation::parse("2d6+3")?;
 //! assert_eq!(notation.count, 2);
-//! assert_eq!(notation.sides, 6);
+//! assert_eq!(notation.sides(), 6);
 //! assert_eq!(notation.modifier, 3);
 //!
 //! let roller = DiceRoller::new();
 //! let result = roller.roll(&notation);
 //! assert!(result.total >= 5 && result.total <= 15);
+//!
+//! let stats = roller.quick_roll("4d6kh3")?;
+//! assert_eq!(stats.rolls.iter().filter(|r| r.kept).count(), 3);
 //! ```
 
 use rand::Rng;
@@ -42,14 +53,23 @@ pub enum DiceError {
     #[error("Invalid dice count: must be between 1 and {max}, got {got}")]
     InvalidCount { max: u32, got: u32 },
 
-    #[error("Invalid dice sides: must be greater than 0, got {0}")]
-    InvalidSides(u32),
+    #[error("Invalid dice sides: must be between 1 and {max}, got {got}")]
+    InvalidSides { max: u32, got: u32 },
 
     #[error("Modifier overflow: result would exceed i32 bounds")]
     ModifierOverflow,
 
     #[error("Empty notation")]
     EmptyNotation,
+
+    #[error("Division by zero in dice expression")]
+    DivisionByZero,
+
+    #[error("Exploding dice would never stop: {0}")]
+    InfiniteExplosion(String),
+
+    #[error("Dice expression too complex: at most {max} dice terms allowed")]
+    TooManyTerms { max: usize },
 }
 
 /// Result type for dice operations
@@ -71,6 +91,8 @@ pub enum DiceType {
     D100,
     /// D66 - roll d6 twice, read as tens and ones (11-66)
     D66,
+    /// Fudge/Fate die with faces -1, 0, +1
+    Fudge,
     /// Custom sided die
     Custom(u32),
 }
@@ -87,6 +109,7 @@ impl DiceType {
             DiceType::D20 => 20,
             DiceType::D100 => 100,
             DiceType::D66 => 66, // Special handling required
+            DiceType::Fudge => 3,
             DiceType::Custom(sides) => *sides,
         }
     }
@@ -119,35 +142,865 @@ impl DiceType {
                 | DiceType::D100
         )
     }
+
+    /// Lowest face value of a single die
+    pub fn min_face(&self) -> i32 {
+        match self {
+            DiceType::Fudge => -1,
+            DiceType::D66 => 11,
+            _ => 1,
+        }
+    }
+
+    /// Highest face value of a single die
+    pub fn max_face(&self) -> i32 {
+        match self {
+            DiceType::Fudge => 1,
+            other => other.sides() as i32,
+        }
+    }
 }
 
 impl fmt::Display for DiceType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             DiceType::D66 => write!(f, "d66"),
+            DiceType::Fudge => write!(f, "dF"),
             DiceType::Custom(n) => write!(f, "d{}", n),
             other => write!(f, "d{}", other.sides()),
         }
     }
 }
 
-/// Parsed dice notation with count, sides, and modifier
+// ============================================================================
+// Expression AST
+// ============================================================================
+
+/// Comparison operator used by compare points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareOp {
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            CompareOp::Equal => "=",
+            CompareOp::Greater => ">",
+            CompareOp::GreaterOrEqual => ">=",
+            CompareOp::Less => "<",
+            CompareOp::LessOrEqual => "<=",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// A comparison against a die face, e.g. `>=7` in `8d10>=7`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComparePoint {
+    pub op: CompareOp,
+    pub value: i32,
+}
+
+impl ComparePoint {
+    pub fn new(op: CompareOp, value: i32) -> Self {
+        Self { op, value }
+    }
+
+    /// Check whether a die face satisfies this comparison
+    pub fn matches(&self, face: i32) -> bool {
+        match self.op {
+            CompareOp::Equal => face == self.value,
+            CompareOp::Greater => face > self.value,
+            CompareOp::GreaterOrEqual => face >= self.value,
+            CompareOp::Less => face < self.value,
+            CompareOp::LessOrEqual => face <= self.value,
+        }
+    }
+
+    /// Count how many faces in `min..=max` satisfy this comparison
+    pub fn matching_faces(&self, min: i32, max: i32) -> u64 {
+        let (min, max, value) = (min as i64, max as i64, self.value as i64);
+        let count_from = |lo: i64| (max - lo.max(min) + 1).max(0);
+        let count_to = |hi: i64| (hi.min(max) - min + 1).max(0);
+        let count = match self.op {
+            CompareOp::Equal => (min..=max).contains(&value) as i64,
+            CompareOp::Greater => count_from(value + 1),
+            CompareOp::GreaterOrEqual => count_from(value),
+            CompareOp::Less => count_to(value - 1),
+            CompareOp::LessOrEqual => count_to(value),
+        };
+        count as u64
+    }
+}
+
+impl fmt::Display for ComparePoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.op, self.value)
+    }
+}
+
+/// Keep or drop rule applied to the dice of a term after rolling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeepDrop {
+    KeepHighest(u32),
+    KeepLowest(u32),
+    DropHighest(u32),
+    DropLowest(u32),
+}
+
+impl KeepDrop {
+    /// Number of dice kept out of `rolled` dice
+    pub fn kept_count(&self, rolled: u32) -> u32 {
+        match *self {
+            KeepDrop::KeepHighest(n) | KeepDrop::KeepLowest(n) => n.min(rolled),
+            KeepDrop::DropHighest(n) | KeepDrop::DropLowest(n) => rolled.saturating_sub(n),
+        }
+    }
+
+    /// Mark dropped dice in place. Ties are broken by roll order.
+    fn apply(&self, rolls: &mut [SingleRoll]) {
+        let rolled = rolls.len() as u32;
+        let to_drop = (rolled - self.kept_count(rolled)) as usize;
+        // Dropping always removes from one end of the sorted pool
+        let drop_lowest = matches!(self, KeepDrop::KeepHighest(_) | KeepDrop::DropLowest(_));
+
+        let mut order: Vec<usize> = (0..rolls.len()).collect();
+        if drop_lowest {
+            order.sort_by_key(|&i| rolls[i].value);
+        } else {
+            order.sort_by_key(|&i| std::cmp::Reverse(rolls[i].value));
+        }
+        for &i in order.iter().take(to_drop) {
+            rolls[i].kept = false;
+        }
+    }
+}
+
+impl fmt::Display for KeepDrop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeepDrop::KeepHighest(n) => write!(f, "kh{}", n),
+            KeepDrop::KeepLowest(n) => write!(f, "kl{}", n),
+            KeepDrop::DropHighest(n) => write!(f, "dh{}", n),
+            KeepDrop::DropLowest(n) => write!(f, "dl{}", n),
+        }
+    }
+}
+
+/// A single `NdX` term with its modifiers, e.g. `4d6kh3` or `8d10!10>=8`
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct DiceNotation {
-    /// Number of dice to roll
+pub struct DiceTerm {
+    /// Number of dice rolled before explosions
     pub count: u32,
     /// Type of die
+    pub die: DiceType,
+    /// Keep/drop rule applied after rolling (and exploding)
+    pub keep_drop: Option<KeepDrop>,
+    /// Faces that trigger an extra die
+    pub explode: Option<ComparePoint>,
+    /// Faces counted as successes; turns the term into a dice pool
+    pub success: Option<ComparePoint>,
+    /// Faces subtracted as failures from a dice pool
+    pub failure: Option<ComparePoint>,
+}
+
+impl DiceTerm {
+    /// Plain `NdX` term with no modifiers
+    pub fn new(count: u32, die: DiceType) -> Self {
+        Self {
+            count,
+            die,
+            keep_drop: None,
+            explode: None,
+            success: None,
+            failure: None,
+        }
+    }
+
+    /// Whether this term counts successes instead of summing faces
+    pub fn is_pool(&self) -> bool {
+        self.success.is_some()
+    }
+
+    /// Chance that a single die triggers an explosion
+    fn explode_chance(&self) -> f64 {
+        self.explode.map_or(0.0, |cmp| {
+            cmp.matching_faces(self.die.min_face(), self.die.max_face()) as f64
+                / self.die.sides() as f64
+        })
+    }
+
+    /// Most dice this term can roll, counting capped explosions
+    fn max_rolled(&self) -> u32 {
+        if self.explode.is_some() {
+            self.count + DiceRoller::MAX_EXPLOSIONS
+        } else {
+            self.count
+        }
+    }
+
+    fn kept_of(&self, rolled: u32) -> u32 {
+        self.keep_drop.map_or(rolled, |kd| kd.kept_count(rolled))
+    }
+
+    /// Lowest and highest possible value of this term
+    pub fn bounds(&self) -> (i32, i32) {
+        if matches!(self.die, DiceType::D66) {
+            return (11, 66);
+        }
+        let max_kept = self.kept_of(self.max_rolled()) as i32;
+        if self.is_pool() {
+            let min = if self.failure.is_some() { -max_kept } else { 0 };
+            return (min, max_kept);
+        }
+        let min_kept = self.kept_of(self.count) as i32;
+        if matches!(self.die, DiceType::Fudge) {
+            return (-min_kept, min_kept);
+        }
+        (
+            min_kept.saturating_mul(self.die.min_face()),
+            max_kept.saturating_mul(self.die.max_face()),
+        )
+    }
+
+    /// Expected value of this term.
+    ///
+    /// Exact for plain and keep/drop dice; keep/drop combined with
+    /// exploding dice or pools is approximated from the kept fraction.
+    pub fn average(&self) -> f64 {
+        let sides = self.die.sides() as f64;
+        match self.die {
+            // 36 outcomes 11..=16, 21..=26, ... 61..=66 sum to 1386
+            DiceType::D66 => return 38.5,
+            DiceType::Fudge => return 0.0,
+            _ => {}
+        }
+
+        // Expected number of rolls per starting die (geometric series of explosions)
+        let chain = 1.0 / (1.0 - self.explode_chance());
+        let kept = self.kept_of(self.count) as f64;
+
+        if let Some(success) = self.success {
+            let faces = |cmp: ComparePoint| {
+                cmp.matching_faces(1, self.die.max_face()) as f64 / sides
+            };
+            let per_die = faces(success) - self.failure.map_or(0.0, faces);
+            return per_die * chain * kept;
+        }
+
+        let mean_face = (1.0 + sides) / 2.0;
+        match self.keep_drop {
+            Some(kd) if self.explode.is_none() => {
+                order_statistic_sum(self.count, self.die.sides(), kd)
+                    .unwrap_or(kept * mean_face)
+            }
+            _ => kept * mean_face * chain,
+        }
+    }
+}
+
+/// Expected sum of the dice kept by `kd` out of `count` dice with `sides` faces.
+///
+/// Uses `E[X(i)] = sum over v of P(X(i) >= v)` for each kept order statistic.
+/// Returns `None` when the computation would be too expensive.
+fn order_statistic_sum(count: u32, sides: u32, kd: KeepDrop) -> Option<f64> {
+    const MAX_WORK: u64 = 1_000_000;
+    if count as u64 * sides as u64 > MAX_WORK {
+        return None;
+    }
+
+    let n = count as usize;
+    let kept = kd.kept_count(count) as usize;
+    // Ascending order statistics are 1-indexed; kept positions are a contiguous run
+    let positions = match kd {
+        KeepDrop::KeepHighest(_) | KeepDrop::DropLowest(_) => (n - kept + 1)..=n,
+        KeepDrop::KeepLowest(_) | KeepDrop::DropHighest(_) => 1..=kept,
+    };
+
+    let mut total = 0.0;
+    for v in 1..=sides {
+        // Probability that a single die shows at least v
+        let q = (sides - v + 1) as f64 / sides as f64;
+        // at_least[j] = P(at least j dice show >= v)
+        let mut at_least = vec![0.0; n + 2];
+        if q >= 1.0 {
+            at_least[..=n].iter_mut().for_each(|p| *p = 1.0);
+        } else {
+            let mut pmf = (1.0 - q).powi(n as i32);
+            let mut pmfs = Vec::with_capacity(n + 1);
+            for j in 0..=n {
+                pmfs.push(pmf);
+                pmf *= (n - j) as f64 / (j + 1) as f64 * q / (1.0 - q);
+            }
+            for j in (0..=n).rev() {
+                at_least[j] = at_least[j + 1] + pmfs[j];
+            }
+        }
+        // X(i) >= v iff at least n - i + 1 dice show >= v
+        for i in positions.clone() {
+            total += at_least[n - i + 1];
+        }
+    }
+    Some(total)
+}
+
+impl fmt::Display for DiceTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.count, self.die)?;
+        if let Some(kd) = self.keep_drop {
+            write!(f, "{}", kd)?;
+        }
+        if let Some(explode) = self.explode {
+            write!(f, "!{}", explode)?;
+        }
+        if let Some(success) = self.success {
+            write!(f, "{}", success)?;
+        }
+        if let Some(failure) = self.failure {
+            write!(f, "f{}", failure)?;
+        }
+        Ok(())
+    }
+}
+
+/// Arithmetic operator in a dice expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    /// Multiply by a constant
+    Multiply,
+    /// Divide by a non-zero constant, rounding toward zero
+    Divide,
+}
+
+impl BinaryOp {
+    fn apply(&self, left: i32, right: i32) -> i32 {
+        match self {
+            BinaryOp::Add => left.saturating_add(right),
+            BinaryOp::Subtract => left.saturating_sub(right),
+            BinaryOp::Multiply => left.saturating_mul(right),
+            BinaryOp::Divide => left.checked_div(right).unwrap_or(0),
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            BinaryOp::Add => '+',
+            BinaryOp::Subtract => '-',
+            BinaryOp::Multiply => '*',
+            BinaryOp::Divide => '/',
+        }
+    }
+}
+
+/// Parsed dice expression
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiceExpr {
+    Constant(i32),
+    Dice(DiceTerm),
+    Negate(Box<DiceExpr>),
+    /// Parenthesised sub-expression, kept so display round-trips
+    Group(Box<DiceExpr>),
+    Binary {
+        op: BinaryOp,
+        left: Box<DiceExpr>,
+        right: Box<DiceExpr>,
+    },
+}
+
+impl DiceExpr {
+    /// Visit every dice term in left-to-right order
+    pub fn terms(&self) -> Vec<&DiceTerm> {
+        let mut terms = Vec::new();
+        self.collect_terms(&mut terms);
+        terms
+    }
+
+    fn collect_terms<'a>(&'a self, out: &mut Vec<&'a DiceTerm>) {
+        match self {
+            DiceExpr::Constant(_) => {}
+            DiceExpr::Dice(term) => out.push(term),
+            DiceExpr::Negate(inner) | DiceExpr::Group(inner) => inner.collect_terms(out),
+            DiceExpr::Binary { left, right, .. } => {
+                left.collect_terms(out);
+                right.collect_terms(out);
+            }
+        }
+    }
+
+    /// Lowest and highest possible value of the expression
+    pub fn bounds(&self) -> (i32, i32) {
+        match self {
+            DiceExpr::Constant(c) => (*c, *c),
+            DiceExpr::Dice(term) => term.bounds(),
+            DiceExpr::Group(inner) => inner.bounds(),
+            DiceExpr::Negate(inner) => {
+                let (min, max) = inner.bounds();
+                (max.saturating_neg(), min.saturating_neg())
+            }
+            DiceExpr::Binary { op, left, right } => {
+                let (lmin, lmax) = left.bounds();
+                let (rmin, rmax) = right.bounds();
+                match op {
+                    BinaryOp::Add => (lmin.saturating_add(rmin), lmax.saturating_add(rmax)),
+                    BinaryOp::Subtract => (lmin.saturating_sub(rmax), lmax.saturating_sub(rmin)),
+                    // Right-hand side is always a constant, so both ends are monotonic
+                    BinaryOp::Multiply | BinaryOp::Divide => {
+                        let a = op.apply(lmin, rmin);
+                        let b = op.apply(lmax, rmax);
+                        (a.min(b), a.max(b))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Expected value of the expression
+    pub fn average(&self) -> f64 {
+        match self {
+            DiceExpr::Constant(c) => *c as f64,
+            DiceExpr::Dice(term) => term.average(),
+            DiceExpr::Group(inner) => inner.average(),
+            DiceExpr::Negate(inner) => -inner.average(),
+            DiceExpr::Binary { op, left, right } => {
+                let (l, r) = (left.average(), right.average());
+                match op {
+                    BinaryOp::Add => l + r,
+                    BinaryOp::Subtract => l - r,
+                    BinaryOp::Multiply => l * r,
+                    BinaryOp::Divide => l / r,
+                }
+            }
+        }
+    }
+
+    /// Sum of constants added or subtracted at the top level (the `+5` in `1d8+2d6+5`)
+    fn flat_modifier(&self, sign: i32) -> i32 {
+        match self {
+            DiceExpr::Constant(c) => c.saturating_mul(sign),
+            DiceExpr::Group(inner) => inner.flat_modifier(sign),
+            DiceExpr::Negate(inner) => inner.flat_modifier(-sign),
+            DiceExpr::Binary { op: BinaryOp::Add, left, right } => left
+                .flat_modifier(sign)
+                .saturating_add(right.flat_modifier(sign)),
+            DiceExpr::Binary { op: BinaryOp::Subtract, left, right } => left
+                .flat_modifier(sign)
+                .saturating_add(right.flat_modifier(-sign)),
+            DiceExpr::Dice(_) | DiceExpr::Binary { .. } => 0,
+        }
+    }
+
+    /// Write the expression, replacing each dice term with the output of `term_fmt`
+    fn write_with(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        term_fmt: &mut dyn FnMut(&mut fmt::Formatter<'_>, &DiceTerm) -> fmt::Result,
+    ) -> fmt::Result {
+        match self {
+            DiceExpr::Constant(c) => write!(f, "{}", c),
+            DiceExpr::Dice(term) => term_fmt(f, term),
+            DiceExpr::Negate(inner) => {
+                write!(f, "-")?;
+                inner.write_with(f, term_fmt)
+            }
+            DiceExpr::Group(inner) => {
+                write!(f, "(")?;
+                inner.write_with(f, term_fmt)?;
+                write!(f, ")")
+            }
+            DiceExpr::Binary { op, left, right } => {
+                left.write_with(f, term_fmt)?;
+                write!(f, "{}", op.symbol())?;
+                right.write_with(f, term_fmt)
+            }
+        }
+    }
+}
+
+impl fmt::Display for DiceExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_with(f, &mut |f, term| write!(f, "{}", term))
+    }
+}
+
+// ============================================================================
+// Expression Parser
+// ============================================================================
+
+/// Recursive-descent parser over the normalised (lowercase, trimmed) notation.
+///
+/// ```text
+/// expr    := product (('+' | '-') product)*
+/// product := unary (('*' | '/') number)*
+/// unary   := '-' unary | atom
+/// atom    := '(' expr ')' | dice | number
+/// dice    := number? 'd' (number | 'f') modifier*
+/// modifier:= ('k' | 'kh' | 'kl' | 'dh' | 'dl') number?
+///          | '!' compare?
+///          | compare ('f' compare)?
+/// compare := ('=' | '>' | '>=' | '<' | '<=')? number
+/// ```
+struct ExprParser<'a> {
+    source: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+    terms: usize,
+}
+
+impl<'a> ExprParser<'a> {
+    const MAX_DEPTH: usize = 16;
+
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            bytes: source.as_bytes(),
+            pos: 0,
+            depth: 0,
+            terms: 0,
+        }
+    }
+
+    fn parse(mut self) -> DiceResult<DiceExpr> {
+        let expr = self.parse_expr()?;
+        if self.pos < self.bytes.len() {
+            return Err(self.unexpected());
+        }
+        if self.terms == 0 {
+            return Err(DiceError::InvalidNotation(format!(
+                "{}: expression contains no dice",
+                self.source
+            )));
+        }
+        Ok(expr)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.bytes.get(self.pos + offset).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn unexpected(&self) -> DiceError {
+        let found = match self.source[self.pos..].chars().next() {
+            Some(c) => format!("unexpected '{}' at position {}", c, self.pos + 1),
+            None => "unexpected end of expression".to_string(),
+        };
+        DiceError::InvalidNotation(format!("{}: {}", self.source, found))
+    }
+
+    fn invalid(&self, reason: &str) -> DiceError {
+        DiceError::InvalidNotation(format!("{}: {}", self.source, reason))
+    }
+
+    fn parse_expr(&mut self) -> DiceResult<DiceExpr> {
+        let mut left = self.parse_product()?;
+        loop {
+            let op = match self.peek() {
+                Some(b'+') => BinaryOp::Add,
+                Some(b'-') => BinaryOp::Subtract,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.parse_product()?;
+            left = DiceExpr::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+    }
+
+    fn parse_product(&mut self) -> DiceResult<DiceExpr> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(b'*') => BinaryOp::Multiply,
+                Some(b'/') => BinaryOp::Divide,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            if !self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                return Err(self.invalid("multiplication and division require a constant"));
+            }
+            let value = self.parse_constant()?;
+            if op == BinaryOp::Divide && value == 0 {
+                return Err(DiceError::DivisionByZero);
+            }
+            left = DiceExpr::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(DiceExpr::Constant(value)),
+            };
+        }
+    }
+
+    fn parse_unary(&mut self) -> DiceResult<DiceExpr> {
+        if self.eat(b'-') {
+            self.enter()?;
+            let inner = self.parse_unary()?;
+            self.depth -= 1;
+            return Ok(DiceExpr::Negate(Box::new(inner)));
+        }
+        self.parse_atom()
+    }
+
+    fn parse_atom(&mut self) -> DiceResult<DiceExpr> {
+        match self.peek() {
+            Some(b'(') => {
+                self.pos += 1;
+                self.enter()?;
+                let inner = self.parse_expr()?;
+                self.depth -= 1;
+                if !self.eat(b')') {
+                    return Err(self.unexpected());
+                }
+                Ok(DiceExpr::Group(Box::new(inner)))
+            }
+            Some(b'd') => self.parse_dice(1),
+            Some(b) if b.is_ascii_digit() => {
+                let start = self.pos;
+                let value = self.parse_number()?;
+                if self.peek() == Some(b'd') {
+                    return self.parse_dice(value);
+                }
+                i32::try_from(value)
+                    .map(DiceExpr::Constant)
+                    .map_err(|_| DiceError::InvalidNotation(self.source[start..self.pos].to_string()))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn enter(&mut self) -> DiceResult<()> {
+        self.depth += 1;
+        if self.depth > Self::MAX_DEPTH {
+            return Err(self.invalid("expression nested too deeply"));
+        }
+        Ok(())
+    }
+
+    fn parse_number(&mut self) -> DiceResult<u32> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(self.unexpected());
+        }
+        self.source[start..self.pos]
+            .parse()
+            .map_err(|_| DiceError::InvalidNotation(self.source.to_string()))
+    }
+
+    fn parse_constant(&mut self) -> DiceResult<i32> {
+        let value = self.parse_number()?;
+        i32::try_from(value).map_err(|_| DiceError::ModifierOverflow)
+    }
+
+    fn parse_dice(&mut self, count: u32) -> DiceResult<DiceExpr> {
+        // Consume the 'd'
+        self.pos += 1;
+
+        if count == 0 || count > DiceNotation::MAX_DICE_COUNT {
+            return Err(DiceError::InvalidCount {
+                max: DiceNotation::MAX_DICE_COUNT,
+                got: count,
+            });
+        }
+
+        self.terms += 1;
+        if self.terms > DiceNotation::MAX_TERMS {
+            return Err(DiceError::TooManyTerms {
+                max: DiceNotation::MAX_TERMS,
+            });
+        }
+
+        let die = if self.eat(b'f') {
+            DiceType::Fudge
+        } else {
+            let sides = self.parse_number()?;
+            if sides == 0 || sides > DiceNotation::MAX_DICE_SIDES {
+                return Err(DiceError::InvalidSides {
+                    max: DiceNotation::MAX_DICE_SIDES,
+                    got: sides,
+                });
+            }
+            DiceType::from_sides(sides)
+        };
+
+        let mut term = DiceTerm::new(count, die);
+        self.parse_modifiers(&mut term)?;
+        self.validate_term(&term)?;
+        Ok(DiceExpr::Dice(term))
+    }
+
+    fn parse_modifiers(&mut self, term: &mut DiceTerm) -> DiceResult<()> {
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(b'k'), next) => {
+                    self.pos += 1;
+                    let keep_lowest = next == Some(b'l');
+                    if matches!(next, Some(b'h') | Some(b'l')) {
+                        self.pos += 1;
+                    }
+                    let n = self.parse_optional_count()?;
+                    let kd = if keep_lowest {
+                        KeepDrop::KeepLowest(n)
+                    } else {
+                        KeepDrop::KeepHighest(n)
+                    };
+                    self.set_keep_drop(term, kd)?;
+                }
+                (Some(b'd'), Some(next @ (b'h' | b'l'))) => {
+                    self.pos += 2;
+                    let n = self.parse_optional_count()?;
+                    let kd = if next == b'h' {
+                        KeepDrop::DropHighest(n)
+                    } else {
+                        KeepDrop::DropLowest(n)
+                    };
+                    self.set_keep_drop(term, kd)?;
+                }
+                (Some(b'!'), _) => {
+                    self.pos += 1;
+                    if term.explode.is_some() {
+                        return Err(self.invalid("dice can only explode once per term"));
+                    }
+                    let default = ComparePoint::new(CompareOp::Equal, term.die.max_face());
+                    term.explode = Some(self.parse_compare()?.unwrap_or(default));
+                }
+                (Some(b'=' | b'>' | b'<'), _) => {
+                    if term.success.is_some() {
+                        return Err(self.invalid("only one success condition per term"));
+                    }
+                    term.success = self.parse_compare()?;
+                }
+                (Some(b'f'), _) if term.success.is_some() && term.failure.is_none() => {
+                    self.pos += 1;
+                    match self.parse_compare()? {
+                        Some(cmp) => term.failure = Some(cmp),
+                        None => return Err(self.unexpected()),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn parse_optional_count(&mut self) -> DiceResult<u32> {
+        if self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.parse_number()
+        } else {
+            Ok(1)
+        }
+    }
+
+    /// Parse a compare point; a bare number means `=`. Returns `None` if absent.
+    fn parse_compare(&mut self) -> DiceResult<Option<ComparePoint>> {
+        let op = match (self.peek(), self.peek_at(1)) {
+            (Some(b'>'), Some(b'=')) => Some((CompareOp::GreaterOrEqual, 2)),
+            (Some(b'<'), Some(b'=')) => Some((CompareOp::LessOrEqual, 2)),
+            (Some(b'>'), _) => Some((CompareOp::Greater, 1)),
+            (Some(b'<'), _) => Some((CompareOp::Less, 1)),
+            (Some(b'='), _) => Some((CompareOp::Equal, 1)),
+            _ => None,
+        };
+        let op = match op {
+            Some((op, width)) => {
+                self.pos += width;
+                op
+            }
+            None if self.peek().is_some_and(|b| b.is_ascii_digit()) => CompareOp::Equal,
+            None => return Ok(None),
+        };
+        let value = self.parse_constant()?;
+        Ok(Some(ComparePoint::new(op, value)))
+    }
+
+    fn set_keep_drop(&self, term: &mut DiceTerm, kd: KeepDrop) -> DiceResult<()> {
+        if term.keep_drop.is_some() {
+            return Err(self.invalid("only one keep/drop rule per term"));
+        }
+        term.keep_drop = Some(kd);
+        Ok(())
+    }
+
+    fn validate_term(&self, term: &DiceTerm) -> DiceResult<()> {
+        let has_modifiers = term.keep_drop.is_some()
+            || term.explode.is_some()
+            || term.success.is_some();
+        match term.die {
+            DiceType::D66 if term.count != 1 || has_modifiers => {
+                Err(self.invalid("d66 is read as tens/ones and takes no count or modifiers"))
+            }
+            DiceType::Fudge if term.explode.is_some() || term.success.is_some() => {
+                Err(self.invalid("Fudge dice cannot explode or count successes"))
+            }
+            _ => {
+                if let Some(explode) = term.explode {
+                    let faces = explode.matching_faces(1, term.die.max_face());
+                    if faces >= term.die.sides() as u64 {
+                        return Err(DiceError::InfiniteExplosion(format!("{}", term)));
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Dice Notation
+// ============================================================================
+
+/// Parsed dice notation.
+///
+/// `count`, `dice_type` and `modifier` summarise the expression for callers
+/// that only care about simple `NdX+M` rolls: they describe the first dice
+/// term and the flat constant added at the top level. The full expression
+/// lives in `expr`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiceNotation {
+    /// Number of dice in the first dice term
+    pub count: u32,
+    /// Type of die in the first dice term
     pub dice_type: DiceType,
-    /// Modifier to add/subtract after rolling
+    /// Flat modifier added/subtracted at the top level
     pub modifier: i32,
-    /// Original notation string
+    /// Original notation string (normalised to lowercase)
     pub original: String,
+    /// Parsed expression tree
+    pub expr: DiceExpr,
 }
 
 impl DiceNotation {
-    /// Maximum number of dice allowed in a single roll
+    /// Maximum number of dice allowed in a single term
     pub const MAX_DICE_COUNT: u32 = 100;
 
+    /// Maximum number of sides on a single die
+    pub const MAX_DICE_SIDES: u32 = 1_000_000;
+
+    /// Maximum number of dice terms in one expression
+    pub const MAX_TERMS: usize = 20;
+
     /// Create a new dice notation
     pub fn new(count: u32, dice_type: DiceType, modifier: i32) -> DiceResult<Self> {
         if count == 0 || count > Self::MAX_DICE_COUNT {
@@ -165,11 +1018,25 @@ impl DiceNotation {
             format!("{}{}{}", count, dice_type, modifier)
         };
 
+        let dice = DiceExpr::Dice(DiceTerm::new(count, dice_type));
+        let expr = if modifier == 0 {
+            dice
+        } else {
+            let magnitude =
+                i32::try_from(modifier.unsigned_abs()).map_err(|_| DiceError::ModifierOverflow)?;
+            DiceExpr::Binary {
+                op: if modifier > 0 { BinaryOp::Add } else { BinaryOp::Subtract },
+                left: Box::new(dice),
+                right: Box::new(DiceExpr::Constant(magnitude)),
+            }
+        };
+
         Ok(Self {
             count,
             dice_type,
             modifier,
             original: notation,
+            expr,
         })
     }
 
@@ -182,6 +1049,11 @@ impl DiceNotation {
     /// - "d20-2" -> 1d20-2
     /// - "d%" or "d100" -> 1d100
     /// - "d66" -> d66 (special handling)
+    /// - "4d6kh3", "2d20kl1", "4d6dl1" -> keep/drop
+    /// - "3d6!", "5d10!>=9" -> exploding dice
+    /// - "8d10>=7", "8d10>=7f1" -> success pools
+    /// - "4dF" -> Fudge/Fate dice
+    /// - "1d8+2d6+5", "(1d6+2)*2" -> multi-term expressions
     pub fn parse(notation: &str) -> DiceResult<Self> {
         let notation = notation.trim().to_lowercase();
 
@@ -192,110 +1064,54 @@ impl DiceNotation {
         // Handle d% as d100
         let notation = notation.replace("d%", "d100");
 
-        // Find the 'd' separator
-        let d_pos = notation
-            .find('d')
-            .ok_or_else(|| DiceError::InvalidNotation(notation.clone()))?;
-
-        // Parse count (default to 1 if not specified)
-        let count_str = &notation[..d_pos];
-        let count: u32 = if count_str.is_empty() {
-            1
-        } else {
-            count_str
-                .parse()
-                .map_err(|_| DiceError::InvalidNotation(notation.clone()))?
-        };
-
-        if count == 0 || count > Self::MAX_DICE_COUNT {
-            return Err(DiceError::InvalidCount {
-                max: Self::MAX_DICE_COUNT,
-                got: count,
-            });
-        }
-
-        // Parse sides and modifier
-        let rest = &notation[d_pos + 1..];
-
-        // Find modifier position
-        let (sides_str, modifier) = if let Some(pos) = rest.find('+') {
-            let sides = &rest[..pos];
-            let mod_str = &rest[pos + 1..];
-            let modifier: i32 = mod_str
-                .parse()
-                .map_err(|_| DiceError::InvalidNotation(notation.clone()))?;
-            (sides, modifier)
-        } else if let Some(pos) = rest.rfind('-') {
-            // Use rfind to handle negative modifier correctly
-            if pos == 0 {
-                // No modifier, just sides
-                (rest, 0)
-            } else {
-                let sides = &rest[..pos];
-                let mod_str = &rest[pos..]; // Include the minus sign
-                let modifier: i32 = mod_str
-                    .parse()
-                    .map_err(|_| DiceError::InvalidNotation(notation.clone()))?;
-                (sides, modifier)
-            }
-        } else {
-            (rest, 0)
-        };
-
-        let sides: u32 = sides_str
-            .parse()
-            .map_err(|_| DiceError::InvalidNotation(notation.clone()))?;
-
-        if sides == 0 {
-            return Err(DiceError::InvalidSides(sides));
-        }
-
-        let dice_type = DiceType::from_sides(sides);
+        let expr = ExprParser::new(&notation).parse()?;
+        let first = expr.terms()[0].clone();
+        let modifier = expr.flat_modifier(1);
 
         Ok(Self {
-            count,
-            dice_type,
+            count: first.count,
+            dice_type: first.die,
             modifier,
             original: notation,
+            expr,
         })
     }
 
     /// Get the minimum possible result
     pub fn min_result(&self) -> i32 {
-        if matches!(self.dice_type, DiceType::D66) {
-            11 + self.modifier
-        } else {
-            (self.count as i32) + self.modifier
-        }
+        self.expr.bounds().0
     }
 
-    /// Get the maximum possible result
+    /// Get the maximum possible result.
+    ///
+    /// Exploding dice are capped at [`DiceRoller::MAX_EXPLOSIONS`] extra dice per term.
     pub fn max_result(&self) -> i32 {
-        if matches!(self.dice_type, DiceType::D66) {
-            66 + self.modifier
-        } else {
-            (self.count as i32 * self.dice_type.sides() as i32) + self.modifier
-        }
+        self.expr.bounds().1
     }
 
     /// Get the average expected result
     pub fn average_result(&self) -> f64 {
-        if matches!(self.dice_type, DiceType::D66) {
-            // D66 yields 36 specific outcomes: 11,12,13,14,15,16, 21,22,...,26, ..., 61,62,63,64,65,66
-            // Many intermediate values (17,18,19,20,27,28,...) are impossible.
-            // Mean = (sum of all 36 outcomes) / 36 = 1386 / 36 = 38.5
-            // Note: min_result()=11 and max_result()=66, but the distribution is not uniform over [11,66].
-            38.5 + self.modifier as f64
-        } else {
-            let die_average = (1.0 + self.dice_type.sides() as f64) / 2.0;
-            (self.count as f64 * die_average) + self.modifier as f64
-        }
+        // D66 yields 36 specific outcomes: 11,12,13,14,15,16, 21,22,...,26, ..., 61,62,63,64,65,66
+        // Many intermediate values (17,18,19,20,27,28,...) are impossible.
+        // Mean = (sum of all 36 outcomes) / 36 = 1386 / 36 = 38.5
+        // Note: min_result()=11 and max_result()=66, but the distribution is not uniform over [11,66].
+        self.expr.average()
     }
 
-    /// Get number of sides
+    /// Get number of sides of the first dice term
     pub fn sides(&self) -> u32 {
         self.dice_type.sides()
     }
+
+    /// All dice terms in the expression, left to right
+    pub fn terms(&self) -> Vec<&DiceTerm> {
+        self.expr.terms()
+    }
+
+    /// Whether the expression counts successes (e.g. `8d10>=7`)
+    pub fn is_pool(&self) -> bool {
+        self.terms().iter().any(|t| t.is_pool())
+    }
 }
 
 impl fmt::Display for DiceNotation {
@@ -316,13 +1132,88 @@ impl std::str::FromStr for DiceNotation {
 // Roll Result Types
 // ============================================================================
 
+fn default_kept() -> bool {
+    true
+}
+
 /// Result of a single die roll
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SingleRoll {
     /// The die type that was rolled
     pub die: DiceType,
-    /// The result of the roll
-    pub value: u32,
+    /// The face rolled (-1..=1 for Fudge dice)
+    pub value: i32,
+    /// Whether the die counts towards the total (false if dropped by keep/drop)
+    #[serde(default = "default_kept")]
+    pub kept: bool,
+    /// Whether this die triggered an extra roll
+    #[serde(default)]
+    pub exploded: bool,
+    /// Counted as a success in a dice pool
+    #[serde(default)]
+    pub success: bool,
+    /// Counted as a failure in a dice pool
+    #[serde(default)]
+    pub failure: bool,
+}
+
+impl SingleRoll {
+    /// A kept die showing `value`
+    pub fn new(die: DiceType, value: i32) -> Self {
+        Self {
+            die,
+            value,
+            kept: true,
+            exploded: false,
+            success: false,
+            failure: false,
+        }
+    }
+}
+
+impl fmt::Display for SingleRoll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let face = match (self.die, self.value) {
+            (DiceType::Fudge, 1) => "+".to_string(),
+            (DiceType::Fudge, -1) => "-".to_string(),
+            (_, value) => value.to_string(),
+        };
+        let marker = if self.exploded { "!" } else { "" };
+        let outcome = if self.success {
+            "✓"
+        } else if self.failure {
+            "✗"
+        } else {
+            ""
+        };
+        if self.kept {
+            write!(f, "{}{}{}", face, marker, outcome)
+        } else {
+            write!(f, "~{}{}~", face, marker)
+        }
+    }
+}
+
+/// Breakdown of one dice term within a roll
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermRoll {
+    /// The term that was rolled
+    pub term: DiceTerm,
+    /// Every die rolled for this term, including dropped and exploded dice
+    pub rolls: Vec<SingleRoll>,
+    /// Value the term contributed to the expression
+    pub value: i32,
+    /// Successes counted, for dice pools
+    pub successes: Option<u32>,
+    /// Failures counted, for dice pools with a failure condition
+    pub failures: Option<u32>,
+}
+
+impl fmt::Display for TermRoll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dice: Vec<String> = self.rolls.iter().map(|r| r.to_string()).collect();
+        write!(f, "[{}]", dice.join(", "))
+    }
 }
 
 /// Complete result of a dice roll
@@ -330,12 +1221,18 @@ pub struct SingleRoll {
 pub struct RollResult {
     /// The notation that was rolled
     pub notation: DiceNotation,
-    /// Individual die results
+    /// Individual die results across all terms, in roll order
     pub rolls: Vec<SingleRoll>,
-    /// Sum of all dice (before modifier)
+    /// Per-term breakdown, in the order terms appear in the expression
+    #[serde(default)]
+    pub terms: Vec<TermRoll>,
+    /// Sum of all dice terms (before constants and arithmetic)
     pub subtotal: i32,
     /// Final total (after modifier)
     pub total: i32,
+    /// Net successes (successes minus failures) if the roll contains a dice pool
+    #[serde(default)]
+    pub successes: Option<i32>,
     /// For d66: the tens digit
     pub d66_tens: Option<u32>,
     /// For d66: the ones digit
@@ -343,51 +1240,107 @@ pub struct RollResult {
 }
 
 impl RollResult {
-    /// Check if this roll is a natural maximum (all dice showing max)
+    /// Build a result for a predetermined value, e.g. a GM-forced table roll
+    pub fn forced(notation: &DiceNotation, value: i32) -> Self {
+        let is_d66 = matches!(notation.dice_type, DiceType::D66);
+        let rolls = vec![SingleRoll::new(notation.dice_type, value)];
+        let term = TermRoll {
+            term: DiceTerm::new(notation.count, notation.dice_type),
+            rolls: rolls.clone(),
+            value,
+            successes: None,
+            failures: None,
+        };
+
+        Self {
+            notation: notation.clone(),
+            rolls,
+            terms: vec![term],
+            subtotal: value,
+            total: value,
+            successes: None,
+            d66_tens: is_d66.then_some((value / 10) as u32),
+            d66_ones: is_d66.then_some((value % 10) as u32),
+        }
+    }
+
+    /// Check if this roll is a natural maximum (all kept dice showing max)
     pub fn is_natural_max(&self) -> bool {
         self.rolls
             .iter()
-            .all(|r| r.value == r.die.sides())
+            .filter(|r| r.kept)
+            .all(|r| r.value == r.die.max_face())
     }
 
-    /// Check if this roll is a natural minimum (all dice showing 1)
+    /// Check if this roll is a natural minimum (all kept dice showing their lowest face)
     pub fn is_natural_min(&self) -> bool {
-        self.rolls.iter().all(|r| r.value == 1)
+        self.rolls
+            .iter()
+            .filter(|r| r.kept)
+            .all(|r| r.value == r.die.min_face())
+    }
+
+    /// The face of the single kept d20, if this is a d20 check (`d20+5`, `2d20kh1`)
+    fn natural_d20(&self) -> Option<i32> {
+        if self.successes.is_some() {
+            return None;
+        }
+        let mut kept = self.rolls.iter().filter(|r| r.kept);
+        match (kept.next(), kept.next()) {
+            (Some(roll), None) if matches!(roll.die, DiceType::D20) => Some(roll.value),
+            _ => None,
+        }
     }
 
     /// Check if this is a critical (for d20 rolls)
     pub fn is_critical(&self) -> bool {
-        self.notation.count == 1
-            && matches!(self.notation.dice_type, DiceType::D20)
-            && self.subtotal == 20
+        self.natural_d20() == Some(20)
     }
 
     /// Check if this is a critical failure (for d20 rolls)
     pub fn is_critical_fail(&self) -> bool {
-        self.notation.count == 1
-            && matches!(self.notation.dice_type, DiceType::D20)
-            && self.subtotal == 1
+        self.natural_d20() == Some(1)
+    }
+
+    /// Expression with each dice term replaced by its rolled dice, e.g. `[4]+[3, 5]+5`
+    pub fn breakdown(&self) -> String {
+        struct Breakdown<'a>(&'a RollResult);
+
+        impl fmt::Display for Breakdown<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut terms = self.0.terms.iter();
+                self.0.notation.expr.write_with(f, &mut |f, term| match terms.next() {
+                    Some(rolled) => write!(f, "{}", rolled),
+                    None => write!(f, "{}", term),
+                })
+            }
+        }
+
+        Breakdown(self).to_string()
     }
 }
 
 impl fmt::Display for RollResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let (Some(tens), Some(ones)) = (self.d66_tens, self.d66_ones) {
-            write!(f, "d66: {}{} ({})", tens, ones, self.total)
-        } else {
-            let rolls_str: Vec<String> = self.rolls.iter().map(|r| r.value.to_string()).collect();
-            if self.notation.modifier == 0 {
-                write!(f, "{}: [{}] = {}", self.notation, rolls_str.join(", "), self.total)
-            } else {
-                write!(
-                    f,
-                    "{}: [{}] ({}) = {}",
-                    self.notation,
-                    rolls_str.join(", "),
-                    self.subtotal,
-                    self.total
-                )
+            return write!(f, "d66: {}{} ({})", tens, ones, self.total);
+        }
+
+        let total = match self.successes {
+            Some(_) if self.total == 1 => "1 success".to_string(),
+            Some(_) => format!("{} successes", self.total),
+            None => self.total.to_string(),
+        };
+
+        match (self.terms.as_slice(), &self.notation.expr) {
+            ([term], DiceExpr::Dice(_)) => write!(f, "{}: {} = {}", self.notation, term, total),
+            // Simple NdX+M keeps the subtotal visible
+            ([term], DiceExpr::Binary { op: BinaryOp::Add | BinaryOp::Subtract, left, right })
+                if matches!(**left, DiceExpr::Dice(_)) && matches!(**right, DiceExpr::Constant(_)) =>
+            {
+                write!(f, "{}: {} ({}) = {}", self.notation, term, self.subtotal, total)
             }
+            _ => write!(f, "{}: {} = {}", self.notation, self.breakdown(), total),
         }
     }
 }
@@ -402,6 +1355,9 @@ pub struct DiceRoller {
 }
 
 impl DiceRoller {
+    /// Maximum extra dice a single term may gain from explosions
+    pub const MAX_EXPLOSIONS: u32 = 100;
+
     /// Create a new dice roller
     pub fn new() -> Self {
         Self {}
@@ -415,63 +1371,129 @@ impl DiceRoller {
 
     /// Roll dice with a specific RNG (useful for testing)
     pub fn roll_with_rng<R: Rng>(&self, notation: &DiceNotation, rng: &mut R) -> RollResult {
-        if matches!(notation.dice_type, DiceType::D66) {
-            self.roll_d66_with_rng(notation, rng)
-        } else {
-            self.roll_standard_with_rng(notation, rng)
-        }
-    }
+        let mut terms = Vec::new();
+        let total = self.eval_with_rng(&notation.expr, rng, &mut terms);
 
-    /// Roll standard dice
-    fn roll_standard_with_rng<R: Rng>(&self, notation: &DiceNotation, rng: &mut R) -> RollResult {
-        let sides = notation.dice_type.sides();
-        let mut rolls = Vec::with_capacity(notation.count as usize);
-        let mut subtotal: i32 = 0;
-
-        for _ in 0..notation.count {
-            let value = rng.gen_range(1..=sides);
-            subtotal += value as i32;
-            rolls.push(SingleRoll {
-                die: notation.dice_type,
-                value,
-            });
-        }
+        let rolls = terms.iter().flat_map(|t| t.rolls.iter().cloned()).collect();
+        let subtotal = terms.iter().fold(0i32, |acc, t| acc.saturating_add(t.value));
+        let successes = terms
+            .iter()
+            .filter(|t| t.successes.is_some())
+            .map(|t| t.value)
+            .reduce(i32::saturating_add);
 
-        let total = subtotal + notation.modifier;
+        let d66 = terms
+            .iter()
+            .find(|t| matches!(t.term.die, DiceType::D66))
+            .map(|t| (t.rolls[0].value as u32, t.rolls[1].value as u32));
 
         RollResult {
             notation: notation.clone(),
             rolls,
+            terms,
             subtotal,
             total,
-            d66_tens: None,
-            d66_ones: None,
+            successes,
+            d66_tens: d66.map(|(tens, _)| tens),
+            d66_ones: d66.map(|(_, ones)| ones),
+        }
+    }
+
+    /// Evaluate an expression, collecting the breakdown of each dice term
+    fn eval_with_rng<R: Rng>(&self, expr: &DiceExpr, rng: &mut R, terms: &mut Vec<TermRoll>) -> i32 {
+        match expr {
+            DiceExpr::Constant(c) => *c,
+            DiceExpr::Dice(term) => {
+                let rolled = self.roll_term_with_rng(term, rng);
+                let value = rolled.value;
+                terms.push(rolled);
+                value
+            }
+            DiceExpr::Group(inner) => self.eval_with_rng(inner, rng, terms),
+            DiceExpr::Negate(inner) => self.eval_with_rng(inner, rng, terms).saturating_neg(),
+            DiceExpr::Binary { op, left, right } => {
+                let l = self.eval_with_rng(left, rng, terms);
+                let r = self.eval_with_rng(right, rng, terms);
+                op.apply(l, r)
+            }
+        }
+    }
+
+    /// Roll a single dice term, applying explosions, keep/drop and pool counting
+    fn roll_term_with_rng<R: Rng>(&self, term: &DiceTerm, rng: &mut R) -> TermRoll {
+        if matches!(term.die, DiceType::D66) {
+            return self.roll_d66_with_rng(term, rng);
+        }
+
+        let mut rolls = Vec::with_capacity(term.count as usize);
+        let mut explosions = 0;
+        for _ in 0..term.count {
+            loop {
+                let mut roll = SingleRoll::new(term.die, Self::roll_face(term.die, rng));
+                roll.exploded = explosions < Self::MAX_EXPLOSIONS
+                    && term.explode.is_some_and(|cmp| cmp.matches(roll.value));
+                let exploded = roll.exploded;
+                rolls.push(roll);
+                if !exploded {
+                    break;
+                }
+                explosions += 1;
+            }
+        }
+
+        if let Some(kd) = term.keep_drop {
+            kd.apply(&mut rolls);
+        }
+
+        let (value, successes, failures) = match term.success {
+            Some(success) => {
+                let mut successes = 0u32;
+                let mut failures = 0u32;
+                for roll in rolls.iter_mut().filter(|r| r.kept) {
+                    roll.success = success.matches(roll.value);
+                    roll.failure = term.failure.is_some_and(|cmp| cmp.matches(roll.value));
+                    successes += roll.success as u32;
+                    failures += roll.failure as u32;
+                }
+                let net = successes as i32 - failures as i32;
+                (net, Some(successes), term.failure.map(|_| failures))
+            }
+            None => {
+                let sum = rolls
+                    .iter()
+                    .filter(|r| r.kept)
+                    .fold(0i32, |acc, r| acc.saturating_add(r.value));
+                (sum, None, None)
+            }
+        };
+
+        TermRoll {
+            term: term.clone(),
+            rolls,
+            value,
+            successes,
+            failures,
+        }
+    }
+
+    fn roll_face<R: Rng>(die: DiceType, rng: &mut R) -> i32 {
+        match die {
+            DiceType::Fudge => rng.gen_range(-1..=1),
+            other => rng.gen_range(1..=other.sides()) as i32,
         }
     }
 
     /// Roll d66 (two d6, read as tens/ones)
-    fn roll_d66_with_rng<R: Rng>(&self, notation: &DiceNotation, rng: &mut R) -> RollResult {
+    fn roll_d66_with_rng<R: Rng>(&self, term: &DiceTerm, rng: &mut R) -> TermRoll {
         let tens = rng.gen_range(1..=6);
         let ones = rng.gen_range(1..=6);
-        let subtotal = (tens * 10 + ones) as i32;
-        let total = subtotal + notation.modifier;
 
-        RollResult {
-            notation: notation.clone(),
-            rolls: vec![
-                SingleRoll {
-                    die: DiceType::D6,
-                    value: tens,
-                },
-                SingleRoll {
-                    die: DiceType::D6,
-                    value: ones,
-                },
-            ],
-            subtotal,
-            total,
-            d66_tens: Some(tens),
-            d66_ones: Some(ones),
+        TermRoll {
+            term: term.clone(),
+            rolls: vec![SingleRoll::new(DiceType::D6, tens), SingleRoll::new(DiceType::D6, ones)],
+            value: tens * 10 + ones,
+            successes: None,
+            failures: None,
         }
     }
 
@@ -682,12 +1704,11 @@ mod tests {
         let notation = DiceNotation::new(1, DiceType::D20, 0).unwrap();
         let result = RollResult {
             notation: notation.clone(),
-            rolls: vec![SingleRoll {
-                die: DiceType::D20,
-                value: 20,
-            }],
+            rolls: vec![SingleRoll::new(DiceType::D20, 20)],
+            terms: Vec::new(),
             subtotal: 20,
             total: 20,
+            successes: None,
             d66_tens: None,
             d66_ones: None,
         };
@@ -747,4 +1768,241 @@ mod tests {
             assert!(value >= 1 && value <= 10);
         }
     }
+
+    // ------------------------------------------------------------------------
+    // Expression language
+    // ------------------------------------------------------------------------
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn roll_many(notation: &str) -> Vec<RollResult> {
+        let roller = DiceRoller::new();
+        let parsed = DiceNotation::parse(notation).unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        (0..200).map(|_| roller.roll_with_rng(&parsed, &mut rng)).collect()
+    }
+
+    #[test]
+    fn test_parse_multi_term() {
+        let notation = DiceNotation::parse("1d8+2d6+5").unwrap();
+        assert_eq!(notation.terms().len(), 2);
+        assert_eq!(notation.count, 1);
+        assert_eq!(notation.dice_type, DiceType::D8);
+        assert_eq!(notation.modifier, 5);
+        assert_eq!(notation.min_result(), 8);
+        assert_eq!(notation.max_result(), 25);
+        assert!((notation.average_result() - 16.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_keep_drop() {
+        let kh = DiceNotation::parse("4d6kh3").unwrap();
+        assert_eq!(kh.terms()[0].keep_drop, Some(KeepDrop::KeepHighest(3)));
+        assert_eq!(kh.min_result(), 3);
+        assert_eq!(kh.max_result(), 18);
+
+        let kl = DiceNotation::parse("2d20kl1").unwrap();
+        assert_eq!(kl.terms()[0].keep_drop, Some(KeepDrop::KeepLowest(1)));
+
+        let shorthand = DiceNotation::parse("2d20k").unwrap();
+        assert_eq!(shorthand.terms()[0].keep_drop, Some(KeepDrop::KeepHighest(1)));
+
+        let dl = DiceNotation::parse("4d6dl1").unwrap();
+        assert_eq!(dl.terms()[0].keep_drop, Some(KeepDrop::DropLowest(1)));
+
+        assert!(DiceNotation::parse("4d6kh3dl1").is_err());
+    }
+
+    #[test]
+    fn test_keep_drop_averages() {
+        // 4d6 drop lowest averages ~12.24
+        let stats = DiceNotation::parse("4d6kh3").unwrap();
+        assert!((stats.average_result() - 12.2446).abs() < 0.001);
+
+        // Advantage on a d20 averages 13.825
+        let adv = DiceNotation::parse("2d20kh1").unwrap();
+        assert!((adv.average_result() - 13.825).abs() < 0.001);
+
+        let dis = DiceNotation::parse("2d20kl1").unwrap();
+        assert!((dis.average_result() - 7.175).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_roll_keep_highest() {
+        for result in roll_many("4d6kh3") {
+            assert_eq!(result.rolls.len(), 4);
+            let kept: Vec<i32> = result.rolls.iter().filter(|r| r.kept).map(|r| r.value).collect();
+            let dropped: Vec<i32> = result.rolls.iter().filter(|r| !r.kept).map(|r| r.value).collect();
+            assert_eq!(kept.len(), 3);
+            assert!(kept.iter().all(|k| *k >= dropped[0]));
+            assert_eq!(result.total, kept.iter().sum::<i32>());
+        }
+    }
+
+    #[test]
+    fn test_roll_exploding() {
+        let notation = DiceNotation::parse("3d6!").unwrap();
+        assert_eq!(
+            notation.terms()[0].explode,
+            Some(ComparePoint::new(CompareOp::Equal, 6))
+        );
+
+        for result in roll_many("3d6!") {
+            let exploded = result.rolls.iter().filter(|r| r.exploded).count();
+            assert_eq!(result.rolls.len(), 3 + exploded);
+            assert!(result.rolls.iter().filter(|r| r.exploded).all(|r| r.value == 6));
+            assert_eq!(result.total, result.rolls.iter().map(|r| r.value).sum::<i32>());
+        }
+    }
+
+    #[test]
+    fn test_exploding_with_compare_point() {
+        let notation = DiceNotation::parse("5d10!>=9").unwrap();
+        assert_eq!(
+            notation.terms()[0].explode,
+            Some(ComparePoint::new(CompareOp::GreaterOrEqual, 9))
+        );
+        assert!(matches!(
+            DiceNotation::parse("3d6!>=1"),
+            Err(DiceError::InfiniteExplosion(_))
+        ));
+        assert!(DiceNotation::parse("1d1!").is_err());
+    }
+
+    #[test]
+    fn test_roll_success_pool() {
+        let notation = DiceNotation::parse("8d10>=7").unwrap();
+        assert!(notation.is_pool());
+        assert_eq!(notation.min_result(), 0);
+        assert_eq!(notation.max_result(), 8);
+        assert!((notation.average_result() - 3.2).abs() < 0.001);
+
+        for result in roll_many("8d10>=7") {
+            let successes = result.rolls.iter().filter(|r| r.value >= 7).count() as i32;
+            assert_eq!(result.total, successes);
+            assert_eq!(result.successes, Some(successes));
+            assert!(!result.is_critical());
+        }
+    }
+
+    #[test]
+    fn test_roll_success_pool_with_failures() {
+        let notation = DiceNotation::parse("8d10>=7f1").unwrap();
+        assert_eq!(
+            notation.terms()[0].failure,
+            Some(ComparePoint::new(CompareOp::Equal, 1))
+        );
+        assert_eq!(notation.min_result(), -8);
+
+        for result in roll_many("8d10>=7f1") {
+            let successes = result.rolls.iter().filter(|r| r.value >= 7).count() as i32;
+            let failures = result.rolls.iter().filter(|r| r.value == 1).count() as i32;
+            assert_eq!(result.total, successes - failures);
+        }
+    }
+
+    #[test]
+    fn test_exploding_pool_uses_explicit_explode_face() {
+        let notation = DiceNotation::parse("8d10!10>=8").unwrap();
+        let term = notation.terms()[0];
+        assert_eq!(term.explode, Some(ComparePoint::new(CompareOp::Equal, 10)));
+        assert_eq!(term.success, Some(ComparePoint::new(CompareOp::GreaterOrEqual, 8)));
+    }
+
+    #[test]
+    fn test_roll_fudge() {
+        let notation = DiceNotation::parse("4dF").unwrap();
+        assert_eq!(notation.dice_type, DiceType::Fudge);
+        assert_eq!(notation.min_result(), -4);
+        assert_eq!(notation.max_result(), 4);
+        assert_eq!(notation.average_result(), 0.0);
+
+        for result in roll_many("4dF+2") {
+            assert_eq!(result.rolls.len(), 4);
+            assert!(result.rolls.iter().all(|r| (-1..=1).contains(&r.value)));
+            assert!(result.total >= -2 && result.total <= 6);
+        }
+        assert!(DiceNotation::parse("4dF!").is_err());
+    }
+
+    #[test]
+    fn test_roll_multi_term_breakdown() {
+        for result in roll_many("1d8+2d6+5") {
+            assert_eq!(result.terms.len(), 2);
+            assert_eq!(result.terms[0].rolls.len(), 1);
+            assert_eq!(result.terms[1].rolls.len(), 2);
+            assert_eq!(result.total, result.subtotal + 5);
+            assert!(result.total >= 8 && result.total <= 25);
+        }
+
+        let result = &roll_many("1d8+2d6+5")[0];
+        let display = result.to_string();
+        assert!(display.starts_with("1d8+2d6+5: ["));
+        assert!(display.ends_with(&format!("= {}", result.total)));
+    }
+
+    #[test]
+    fn test_parentheses_and_multiplication() {
+        let notation = DiceNotation::parse("(1d6+2)*2").unwrap();
+        assert_eq!(notation.min_result(), 6);
+        assert_eq!(notation.max_result(), 16);
+        assert_eq!(notation.modifier, 0);
+
+        let halved = DiceNotation::parse("4d6/2").unwrap();
+        assert_eq!(halved.min_result(), 2);
+        assert_eq!(halved.max_result(), 12);
+
+        assert!(matches!(DiceNotation::parse("1d6/0"), Err(DiceError::DivisionByZero)));
+        assert!(DiceNotation::parse("1d6*1d4").is_err());
+    }
+
+    #[test]
+    fn test_negative_terms() {
+        let notation = DiceNotation::parse("1d20-1d4").unwrap();
+        assert_eq!(notation.min_result(), -3);
+        assert_eq!(notation.max_result(), 19);
+    }
+
+    #[test]
+    fn test_advantage_critical() {
+        for result in roll_many("2d20kh1+5") {
+            let kept = result.rolls.iter().find(|r| r.kept).unwrap();
+            assert_eq!(result.is_critical(), kept.value == 20);
+            assert_eq!(result.is_critical_fail(), kept.value == 1);
+        }
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(DiceNotation::parse("2d6+").is_err());
+        assert!(DiceNotation::parse("(2d6").is_err());
+        assert!(DiceNotation::parse("5+3").is_err());
+        assert!(DiceNotation::parse("2d66").is_err());
+        assert!(DiceNotation::parse("d0").is_err());
+        let too_many = vec!["d6"; DiceNotation::MAX_TERMS + 1].join("+");
+        assert!(matches!(
+            DiceNotation::parse(&too_many),
+            Err(DiceError::TooManyTerms { .. })
+        ));
+    }
+
+    #[test]
+    fn test_display_marks_dropped_dice() {
+        let roll = SingleRoll {
+            kept: false,
+            ..SingleRoll::new(DiceType::D6, 2)
+        };
+        assert_eq!(roll.to_string(), "~2~");
+        assert_eq!(SingleRoll::new(DiceType::Fudge, 1).to_string(), "+");
+    }
+
+    #[test]
+    fn test_forced_roll() {
+        let notation = DiceNotation::parse("d66").unwrap();
+        let result = RollResult::forced(&notation, 35);
+        assert_eq!(result.total, 35);
+        assert_eq!(result.d66_tens, Some(3));
+        assert_eq!(result.d66_ones, Some(5));
+    }
 }
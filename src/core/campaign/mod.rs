@@ -1,10 +1,12 @@
 //! Campaign Management Module
 //!
-//! Provides campaign versioning, world state tracking, entity relationship management,
-//! and campaign generation features (arcs, phases, milestones, session planning).
+//! Provides campaign versioning, world state tracking, fantasy calendars, entity
+//! relationship management, and campaign generation features (arcs, phases,
+//! milestones, session planning).
 
 pub mod versioning;
 pub mod world_state;
+pub mod calendar;
 pub mod relationships;
 
 // Campaign Generation modules (TASK-CAMP-001 through TASK-CAMP-017)
@@ -45,9 +47,13 @@ pub use world_state::{
     WorldState, WorldEvent, WorldEventType, LocationState, NpcRelationshipState,
     InGameDate, WorldStateManager,
 };
+pub use calendar::{
+    Calendar, CalendarError, Month, LeapRule, Era, Moon, MoonPhase, Season, Holiday,
+    DEFAULT_CALENDAR,
+};
 pub use relationships::{
     EntityRelationship, RelationshipType, EntityType, RelationshipStrength,
-    RelationshipManager, EntityGraph, GraphNode, GraphEdge,
+    RelationshipManager, EntityGraph, GraphNode, GraphEdge, GraphFilter,
 };
 
 // Campaign Generation re-exports
@@ -160,12 +166,14 @@ pub use cheat_sheet::{
 // Random Tables & Session Recaps re-exports (Phase 8 - Campaign Generation Overhaul)
 pub use dice::{
     DiceNotation, DiceType, DiceRoller, DiceError, DiceResult,
-    RollResult, SingleRoll,
+    RollResult, SingleRoll, TermRoll,
+    DiceExpr, DiceTerm, BinaryOp, KeepDrop, ComparePoint, CompareOp,
 };
 pub use random_table::{
     RandomTableEngine, RandomTableError, RandomTableResult,
     RandomTable, TableEntry, TableRollResult,
     CreateTableRequest, TableEntryInput, RollRequest,
+    TableProvenance, TableImportReport, collect_random_tables,
 };
 pub use recap::{
     RecapGenerator, RecapError, RecapResult,
@@ -7,6 +7,7 @@
 //! - Roll resolution with probability weighting
 //! - Nested/cascading table support
 //! - Roll history tracking
+//! - Importing tables detected during document ingestion
 //!
 //! ## Example
 //!
@@ -30,6 +31,7 @@
 //! let result = engine.roll_on_table(&table.id, None).await?;
 //! ```
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use sqlx::sqlite::SqlitePool;
 use serde::{Deserialize, Serialize};
@@ -41,6 +43,14 @@ use crate::database::{
     RandomTableRecord, RandomTableEntryRecord, RollHistoryRecord,
     RandomTableType, TableResultType,
 };
+use crate::ingestion::ttrpg::{RandomTableData, RandomTableParser};
+
+/// Tag prefix recording the source book of an imported table
+pub const SOURCE_TAG_PREFIX: &str = "source:";
+/// Tag prefix recording the page an imported table was found on
+pub const PAGE_TAG_PREFIX: &str = "page:";
+/// Tag prefix recording the library item an imported table came from
+pub const LIBRARY_TAG_PREFIX: &str = "library:";
 
 // ============================================================================
 // Error Types
@@ -246,6 +256,176 @@ impl RandomTable {
 
         Ok(())
     }
+
+    /// Source book this table was imported from, if any
+    pub fn source(&self) -> Option<&str> {
+        self.tag_value(SOURCE_TAG_PREFIX)
+    }
+
+    /// Page number this table was imported from, if any
+    pub fn page_number(&self) -> Option<u32> {
+        self.tag_value(PAGE_TAG_PREFIX).and_then(|p| p.parse().ok())
+    }
+
+    fn tag_value(&self, prefix: &str) -> Option<&str> {
+        self.tags.iter().find_map(|t| t.strip_prefix(prefix))
+    }
+}
+
+// ============================================================================
+// Ingestion Import
+// ============================================================================
+
+/// Where an imported table came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableProvenance {
+    pub source: String,
+    pub page_number: Option<u32>,
+    pub library_item_id: Option<String>,
+}
+
+impl TableProvenance {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            page_number: None,
+            library_item_id: None,
+        }
+    }
+
+    pub fn with_page(mut self, page_number: u32) -> Self {
+        self.page_number = Some(page_number);
+        self
+    }
+
+    pub fn with_library_item(mut self, library_item_id: impl Into<String>) -> Self {
+        self.library_item_id = Some(library_item_id.into());
+        self
+    }
+
+    /// Tags stored on the table so source, page and library item survive the round trip
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags = vec![format!("{SOURCE_TAG_PREFIX}{}", self.source)];
+        if let Some(page) = self.page_number {
+            tags.push(format!("{PAGE_TAG_PREFIX}{page}"));
+        }
+        if let Some(ref id) = self.library_item_id {
+            tags.push(format!("{LIBRARY_TAG_PREFIX}{id}"));
+        }
+        tags
+    }
+}
+
+impl CreateTableRequest {
+    /// Build a request from a table detected in ingested text
+    pub fn from_parsed(table: &RandomTableData, provenance: &TableProvenance) -> Self {
+        let location = match provenance.page_number {
+            Some(page) => format!("{}, p. {page}", provenance.source),
+            None => provenance.source.clone(),
+        };
+        let name = table
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("{} table ({location})", table.dice_notation));
+
+        Self {
+            name,
+            description: Some(format!("From {location}")),
+            dice_notation: table.dice_notation.clone(),
+            tags: provenance.tags(),
+            entries: table
+                .entries
+                .iter()
+                .map(|e| TableEntryInput {
+                    range_start: e.roll_min as i32,
+                    range_end: e.roll_max as i32,
+                    result_text: e.result.clone(),
+                    weight: None,
+                    nested_table_id: None,
+                    metadata: None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Check dice notation and range coverage without touching the database
+    pub fn validate(&self) -> RandomTableResult<()> {
+        let table = RandomTable {
+            id: String::new(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            dice_notation: self.dice_notation.clone(),
+            table_type: self.table_type,
+            category: self.category.clone(),
+            tags: self.tags.clone(),
+            campaign_id: self.campaign_id.clone(),
+            entries: self
+                .entries
+                .iter()
+                .map(|e| TableEntry {
+                    id: String::new(),
+                    range_start: e.range_start,
+                    range_end: e.range_end,
+                    result_text: e.result_text.clone(),
+                    result_type: TableResultType::default(),
+                    nested_table_id: e.nested_table_id.clone(),
+                    weight: e.weight.unwrap_or(1.0),
+                })
+                .collect(),
+            is_system: self.is_system,
+            is_nested: false,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        table.validate_coverage()
+    }
+}
+
+/// Detect random tables in extracted pages, keeping the page each was found on
+pub fn collect_random_tables(pages: &[(u32, String)]) -> Vec<(u32, RandomTableData)> {
+    let parser = RandomTableParser::new();
+    pages
+        .iter()
+        .flat_map(|(page, text)| {
+            parser
+                .find_tables(text)
+                .into_iter()
+                .map(move |table| (*page, table))
+        })
+        .collect()
+}
+
+/// Tables whose name contains `query` (case-insensitive), exact and prefix
+/// matches first
+pub fn match_tables_by_name<'a>(tables: &'a [RandomTable], query: &str) -> Vec<&'a RandomTable> {
+    let query = query.to_lowercase();
+    let mut matches: Vec<(u8, &RandomTable)> = tables
+        .iter()
+        .filter_map(|t| {
+            let name = t.name.to_lowercase();
+            if name == query {
+                Some((0, t))
+            } else if name.starts_with(&query) {
+                Some((1, t))
+            } else if name.contains(&query) {
+                Some((2, t))
+            } else {
+                None
+            }
+        })
+        .collect();
+    matches.sort_by_key(|(rank, _)| *rank);
+    matches.into_iter().map(|(_, t)| t).collect()
+}
+
+/// Outcome of importing the tables found in one document
+#[derive(Debug, Clone, Default)]
+pub struct TableImportReport {
+    /// Tables stored and now rollable
+    pub imported: Vec<RandomTable>,
+    /// Tables rejected, with the reason (usually incomplete coverage)
+    pub skipped: Vec<(String, String)>,
 }
 
 // ============================================================================
@@ -631,6 +811,119 @@ impl RandomTableEngine {
         Ok(())
     }
 
+    // ========================================================================
+    // Ingestion Import
+    // ========================================================================
+
+    /// Import tables detected in a library item, replacing any earlier import
+    ///
+    /// Tables whose ranges do not cover the full die are skipped rather than
+    /// stored, since rolling on them could miss every entry.
+    pub async fn import_library_tables(
+        &self,
+        library_item_id: &str,
+        source: &str,
+        tables: Vec<(u32, RandomTableData)>,
+    ) -> RandomTableResult<TableImportReport> {
+        self.delete_library_tables(library_item_id).await?;
+
+        let mut report = TableImportReport::default();
+        for (page, table) in tables {
+            let provenance = TableProvenance::new(source)
+                .with_page(page)
+                .with_library_item(library_item_id);
+            let request = CreateTableRequest::from_parsed(&table, &provenance);
+
+            if let Err(e) = request.validate() {
+                debug!(name = %request.name, page, error = %e, "Skipping imported table");
+                report.skipped.push((request.name, e.to_string()));
+                continue;
+            }
+
+            report.imported.push(self.create_table(request).await?);
+        }
+
+        info!(
+            library_item_id,
+            imported = report.imported.len(),
+            skipped = report.skipped.len(),
+            "Imported random tables"
+        );
+        Ok(report)
+    }
+
+    /// Delete all tables imported from a library item
+    pub async fn delete_library_tables(&self, library_item_id: &str) -> RandomTableResult<u64> {
+        let tag = format!("{LIBRARY_TAG_PREFIX}{library_item_id}");
+        let result = sqlx::query(
+            r#"
+            DELETE FROM random_tables
+            WHERE is_system = 0
+              AND EXISTS (SELECT 1 FROM json_each(random_tables.tags) WHERE value = ?)
+            "#
+        )
+        .bind(tag)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// List every table imported from ingested documents
+    ///
+    /// Tables and their entries are loaded with one query each, rather than
+    /// one entry query per table.
+    pub async fn list_imported_tables(&self) -> RandomTableResult<Vec<RandomTable>> {
+        const IMPORTED: &str =
+            "EXISTS (SELECT 1 FROM json_each(t.tags) WHERE instr(value, ?) = 1)";
+
+        let records: Vec<RandomTableRecord> = sqlx::query_as(&format!(
+            "SELECT t.* FROM random_tables t WHERE {IMPORTED} ORDER BY t.name"
+        ))
+        .bind(SOURCE_TAG_PREFIX)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let entry_records: Vec<RandomTableEntryRecord> = sqlx::query_as(&format!(
+            "SELECT e.* FROM random_table_entries e \
+             JOIN random_tables t ON t.id = e.table_id \
+             WHERE {IMPORTED} ORDER BY e.table_id, e.display_order"
+        ))
+        .bind(SOURCE_TAG_PREFIX)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let mut entries: HashMap<String, Vec<TableEntry>> = HashMap::new();
+        for entry in entry_records {
+            entries
+                .entry(entry.table_id.clone())
+                .or_default()
+                .push(TableEntry::from(entry));
+        }
+
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let table_type = record.table_type_enum().unwrap_or_default();
+                let tags = record.tags_vec();
+                RandomTable {
+                    entries: entries.remove(&record.id).unwrap_or_default(),
+                    id: record.id,
+                    name: record.name,
+                    description: record.description,
+                    dice_notation: record.dice_notation,
+                    table_type,
+                    category: record.category,
+                    tags,
+                    campaign_id: record.campaign_id,
+                    is_system: record.is_system != 0,
+                    is_nested: record.is_nested != 0,
+                    created_at: record.created_at,
+                    updated_at: record.updated_at,
+                }
+            })
+            .collect())
+    }
+
     // ========================================================================
     // Rolling Operations
     // ========================================================================
@@ -679,25 +972,7 @@ impl RandomTableEngine {
         // Roll the dice
         let roll = if let Some(forced) = forced_roll {
             // Create a fake roll result with the forced value
-            super::dice::RollResult {
-                notation: notation.clone(),
-                rolls: vec![super::dice::SingleRoll {
-                    die: notation.dice_type,
-                    value: forced as u32,
-                }],
-                subtotal: forced,
-                total: forced,
-                d66_tens: if matches!(notation.dice_type, super::dice::DiceType::D66) {
-                    Some((forced / 10) as u32)
-                } else {
-                    None
-                },
-                d66_ones: if matches!(notation.dice_type, super::dice::DiceType::D66) {
-                    Some((forced % 10) as u32)
-                } else {
-                    None
-                },
-            }
+            RollResult::forced(&notation, forced)
         } else {
             self.roller.roll(&notation)
         };
@@ -944,6 +1219,98 @@ mod tests {
         assert!(!request.is_system);
     }
 
+    fn forest_table() -> RandomTableData {
+        RandomTableParser::new()
+            .parse("Forest Encounters\nd6 Encounter\n1-2 Wolves\n3-5 Bandits\n6 Owlbear")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_request_from_parsed_table() {
+        let provenance = TableProvenance::new("Dungeon Master's Guide")
+            .with_page(87)
+            .with_library_item("dmg");
+        let request = CreateTableRequest::from_parsed(&forest_table(), &provenance);
+
+        assert_eq!(request.name, "Forest Encounters");
+        assert_eq!(request.dice_notation, "d6");
+        assert_eq!(request.description.as_deref(), Some("From Dungeon Master's Guide, p. 87"));
+        assert_eq!(request.entries.len(), 3);
+        assert_eq!(request.entries[1].range_start, 3);
+        assert_eq!(request.entries[1].range_end, 5);
+        assert!(request.validate().is_ok());
+
+        let table = RandomTable {
+            id: "t".to_string(),
+            name: request.name.clone(),
+            description: None,
+            dice_notation: request.dice_notation.clone(),
+            table_type: RandomTableType::Standard,
+            category: None,
+            tags: request.tags.clone(),
+            campaign_id: None,
+            entries: Vec::new(),
+            is_system: false,
+            is_nested: false,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        assert_eq!(table.source(), Some("Dungeon Master's Guide"));
+        assert_eq!(table.page_number(), Some(87));
+    }
+
+    #[test]
+    fn test_validate_rejects_gaps() {
+        let mut parsed = forest_table();
+        parsed.entries.remove(1);
+        let request = CreateTableRequest::from_parsed(&parsed, &TableProvenance::new("DMG"));
+        assert!(matches!(
+            request.validate(),
+            Err(RandomTableError::GapsInCoverage { start: 3, end: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_collect_random_tables_keeps_pages() {
+        let pages = vec![
+            (3, "No tables here.".to_string()),
+            (4, "Forest Encounters\nd6 Encounter\n1-2 Wolves\n3-5 Bandits\n6 Owlbear".to_string()),
+        ];
+        let found = collect_random_tables(&pages);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, 4);
+        assert_eq!(found[0].1.title.as_deref(), Some("Forest Encounters"));
+    }
+
+    #[tokio::test]
+    async fn test_library_tables_match_exact_tag() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect(":memory:")
+            .await
+            .unwrap();
+        crate::database::run_migrations(&pool).await.unwrap();
+        let engine = RandomTableEngine::new(Arc::new(pool));
+
+        // `_` is a LIKE wildcard, so "dmg_1" must not match "dmgx1"
+        for id in ["dmg_1", "dmgx1"] {
+            engine
+                .import_library_tables(id, "Dungeon Master's Guide", vec![(87, forest_table())])
+                .await
+                .unwrap();
+        }
+
+        let imported = engine.list_imported_tables().await.unwrap();
+        assert_eq!(imported.len(), 2);
+        assert!(imported.iter().all(|t| t.entries.len() == 3));
+        assert_eq!(imported[0].entries[1].range_start, 3);
+
+        assert_eq!(engine.delete_library_tables("dmg_1").await.unwrap(), 1);
+        let remaining = engine.list_imported_tables().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining[0].tags.contains(&format!("{LIBRARY_TAG_PREFIX}dmgx1")));
+    }
+
     #[test]
     fn test_roll_request() {
         let request = RollRequest {
@@ -10,6 +10,8 @@ use std::sync::RwLock;
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::database::EntityRelationshipRecord;
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -86,6 +88,46 @@ impl std::fmt::Display for EntityType {
     }
 }
 
+impl EntityType {
+    /// Entity types that can be picked without naming a custom one
+    pub const ALL: [EntityType; 9] = [
+        Self::PC,
+        Self::NPC,
+        Self::Location,
+        Self::Faction,
+        Self::Item,
+        Self::Event,
+        Self::Quest,
+        Self::Deity,
+        Self::Creature,
+    ];
+
+    /// Storage key (`npc`, `location`, ...), as used by the `entity_relationships` table
+    pub fn key(&self) -> String {
+        match self {
+            Self::Custom(s) => s.clone(),
+            other => other.to_string().to_lowercase(),
+        }
+    }
+
+    /// Parse a storage key; unknown keys become `Custom`
+    pub fn from_key(key: &str) -> Self {
+        match key.to_lowercase().as_str() {
+            // The SQLite layer calls player characters "character"
+            "pc" | "character" => Self::PC,
+            "npc" => Self::NPC,
+            "location" => Self::Location,
+            "faction" => Self::Faction,
+            "item" => Self::Item,
+            "event" => Self::Event,
+            "quest" => Self::Quest,
+            "deity" => Self::Deity,
+            "creature" => Self::Creature,
+            _ => Self::Custom(key.to_string()),
+        }
+    }
+}
+
 // ============================================================================
 // Relationship Types
 // ============================================================================
@@ -213,6 +255,57 @@ impl std::fmt::Display for RelationshipType {
 }
 
 impl RelationshipType {
+    /// All built-in relationship types, in declaration order
+    pub const ALL: [RelationshipType; 30] = [
+        Self::Ally,
+        Self::Enemy,
+        Self::Romantic,
+        Self::Family,
+        Self::Mentor,
+        Self::Acquaintance,
+        Self::Employee,
+        Self::BusinessPartner,
+        Self::Patron,
+        Self::Teacher,
+        Self::Protector,
+        Self::MemberOf,
+        Self::LeaderOf,
+        Self::AlliedWith,
+        Self::AtWarWith,
+        Self::VassalOf,
+        Self::LocatedAt,
+        Self::ConnectedTo,
+        Self::PartOf,
+        Self::Controls,
+        Self::Owns,
+        Self::Seeks,
+        Self::Created,
+        Self::Destroyed,
+        Self::QuestGiver,
+        Self::QuestTarget,
+        Self::RelatedTo,
+        Self::Worships,
+        Self::BlessedBy,
+        Self::CursedBy,
+    ];
+
+    /// Storage key (`ally`, `member_of`, ...); custom types keep their name
+    pub fn key(&self) -> String {
+        match self {
+            Self::Custom(s) => s.clone(),
+            other => other.to_string().to_lowercase().replace(' ', "_"),
+        }
+    }
+
+    /// Parse a storage key; unknown keys become `Custom`
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .iter()
+            .find(|t| t.key() == key)
+            .cloned()
+            .unwrap_or_else(|| Self::Custom(key.to_string()))
+    }
+
     /// Check if this relationship type is bidirectional
     pub fn is_bidirectional(&self) -> bool {
         matches!(
@@ -290,6 +383,17 @@ impl RelationshipStrength {
             Self::Custom(v) => *v,
         }
     }
+
+    /// Inverse of `value`, preferring the named levels
+    pub fn from_value(value: u8) -> Self {
+        match value {
+            25 => Self::Weak,
+            50 => Self::Moderate,
+            75 => Self::Strong,
+            100 => Self::Unbreakable,
+            v => Self::Custom(v.min(100)),
+        }
+    }
 }
 
 // ============================================================================
@@ -393,6 +497,86 @@ impl EntityRelationship {
         self
     }
 
+    /// Convert to a database record. Fields the table has no column for
+    /// (names, visibility, in-game dates, tags) are kept in `metadata`.
+    pub fn to_record(&self) -> EntityRelationshipRecord {
+        let extras = RecordExtras {
+            source_name: self.source_name.clone(),
+            target_name: self.target_name.clone(),
+            is_active: self.is_active,
+            is_known: self.is_known,
+            started_at: self.started_at.clone(),
+            ended_at: self.ended_at.clone(),
+            tags: self.tags.clone(),
+            metadata: self.metadata.clone(),
+        };
+
+        EntityRelationshipRecord {
+            id: self.id.clone(),
+            campaign_id: self.campaign_id.clone(),
+            source_entity_type: self.source_type.key(),
+            source_entity_id: self.source_id.clone(),
+            target_entity_type: self.target_type.key(),
+            target_entity_id: self.target_id.clone(),
+            relationship_type: self.relationship_type.key(),
+            description: (!self.description.is_empty()).then(|| self.description.clone()),
+            strength: self.strength.value() as f64 / 100.0,
+            bidirectional: self.relationship_type.is_bidirectional(),
+            metadata: serde_json::to_string(&extras).ok(),
+            created_at: self.created_at.to_rfc3339(),
+            updated_at: self.updated_at.to_rfc3339(),
+        }
+    }
+
+    /// Create from a database record. Records written by other code paths
+    /// (without our metadata) fall back to using entity IDs as names.
+    pub fn from_record(record: &EntityRelationshipRecord) -> Self {
+        let extras: Option<RecordExtras> = record
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str(m).ok());
+        let parse_time = |s: &str| {
+            DateTime::parse_from_rfc3339(s)
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now())
+        };
+        let strength = (record.strength.clamp(0.0, 1.0) * 100.0).round() as u8;
+
+        let mut relationship = Self {
+            id: record.id.clone(),
+            campaign_id: record.campaign_id.clone(),
+            source_id: record.source_entity_id.clone(),
+            source_type: EntityType::from_key(&record.source_entity_type),
+            source_name: record.source_entity_id.clone(),
+            target_id: record.target_entity_id.clone(),
+            target_type: EntityType::from_key(&record.target_entity_type),
+            target_name: record.target_entity_id.clone(),
+            relationship_type: RelationshipType::from_key(&record.relationship_type),
+            strength: RelationshipStrength::from_value(strength),
+            is_active: true,
+            is_known: true,
+            description: record.description.clone().unwrap_or_default(),
+            started_at: None,
+            ended_at: None,
+            tags: vec![],
+            metadata: HashMap::new(),
+            created_at: parse_time(&record.created_at),
+            updated_at: parse_time(&record.updated_at),
+        };
+
+        if let Some(extras) = extras {
+            relationship.source_name = extras.source_name;
+            relationship.target_name = extras.target_name;
+            relationship.is_active = extras.is_active;
+            relationship.is_known = extras.is_known;
+            relationship.started_at = extras.started_at;
+            relationship.ended_at = extras.ended_at;
+            relationship.tags = extras.tags;
+            relationship.metadata = extras.metadata;
+        }
+        relationship
+    }
+
     /// Create the inverse relationship (if applicable)
     pub fn create_inverse(&self) -> Option<Self> {
         self.relationship_type.inverse().map(|inv_type| {
@@ -417,6 +601,23 @@ impl EntityRelationship {
     }
 }
 
+/// Relationship fields stored in the record's `metadata` column
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordExtras {
+    source_name: String,
+    target_name: String,
+    is_active: bool,
+    is_known: bool,
+    #[serde(default)]
+    started_at: Option<String>,
+    #[serde(default)]
+    ended_at: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    metadata: HashMap<String, serde_json::Value>,
+}
+
 /// Summary of a relationship (for listing)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RelationshipSummary {
@@ -489,6 +690,8 @@ pub struct GraphEdge {
     pub bidirectional: bool,
     /// Is this relationship active?
     pub is_active: bool,
+    /// Is this relationship known to the players?
+    pub is_known: bool,
     /// Color hint for visualization
     pub color: String,
 }
@@ -504,6 +707,32 @@ pub struct EntityGraph {
     pub stats: GraphStats,
 }
 
+/// Which relationships go into a graph built by
+/// [`RelationshipManager::get_filtered_graph`]
+#[derive(Debug, Clone, Default)]
+pub struct GraphFilter {
+    /// Center the graph on this entity (ego graph); `None` for the whole campaign
+    pub focus: Option<String>,
+    /// Maximum hops from the focal entity (ignored without a focus)
+    pub depth: usize,
+    /// Only include these relationship types (empty = all)
+    pub relationship_types: Vec<RelationshipType>,
+    /// Drop secret relationships, for player-facing display
+    pub hide_secret: bool,
+    /// Include relationships that are no longer active
+    pub include_inactive: bool,
+}
+
+impl GraphFilter {
+    /// Whether a relationship passes the filter (ignores `focus`/`depth`)
+    pub fn matches(&self, relationship: &EntityRelationship) -> bool {
+        (self.include_inactive || relationship.is_active)
+            && (!self.hide_secret || relationship.is_known)
+            && (self.relationship_types.is_empty()
+                || self.relationship_types.contains(&relationship.relationship_type))
+    }
+}
+
 /// Statistics about an entity graph
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GraphStats {
@@ -654,6 +883,15 @@ impl RelationshipManager {
         self.relationships.write().unwrap().remove(campaign_id);
     }
 
+    /// Replace a campaign's relationships wholesale (e.g. after loading them
+    /// from the database). No validation or inverse creation is done.
+    pub fn replace_relationships(&self, campaign_id: &str, relationships: Vec<EntityRelationship>) {
+        self.relationships
+            .write()
+            .unwrap()
+            .insert(campaign_id.to_string(), relationships);
+    }
+
     // ========================================================================
     // Query Operations
     // ========================================================================
@@ -777,188 +1015,217 @@ impl RelationshipManager {
     /// Generate an entity graph for visualization
     pub fn get_entity_graph(&self, campaign_id: &str, include_inactive: bool) -> EntityGraph {
         let rels = self.relationships.read().unwrap();
-        let campaign_rels = match rels.get(campaign_id) {
-            Some(r) => r,
-            None => {
-                return EntityGraph {
-                    nodes: vec![],
-                    edges: vec![],
-                    stats: GraphStats::default(),
+        match rels.get(campaign_id) {
+            Some(campaign_rels) => build_graph(
+                campaign_rels
+                    .iter()
+                    .filter(|r| include_inactive || r.is_active),
+            ),
+            None => EntityGraph::empty(),
+        }
+    }
+
+    /// Get a subgraph centered on an entity (ego graph)
+    pub fn get_ego_graph(&self, campaign_id: &str, entity_id: &str, depth: usize) -> EntityGraph {
+        ego_subgraph(self.get_entity_graph(campaign_id, false), entity_id, depth)
+    }
+
+    /// Build a graph from the relationships matching `filter`. Filtering
+    /// happens before the ego-graph walk, so hidden relationships never
+    /// connect the focal entity to anything.
+    pub fn get_filtered_graph(&self, campaign_id: &str, filter: &GraphFilter) -> EntityGraph {
+        let graph = {
+            let rels = self.relationships.read().unwrap();
+            match rels.get(campaign_id) {
+                Some(campaign_rels) => {
+                    build_graph(campaign_rels.iter().filter(|r| filter.matches(r)))
                 }
+                None => return EntityGraph::empty(),
             }
         };
 
-        // Collect unique entities
-        let mut entity_map: HashMap<String, GraphNode> = HashMap::new();
-        let mut connection_counts: HashMap<String, usize> = HashMap::new();
+        match filter.focus {
+            Some(ref entity_id) => ego_subgraph(graph, entity_id, filter.depth),
+            None => graph,
+        }
+    }
 
-        for rel in campaign_rels {
-            if !include_inactive && !rel.is_active {
-                continue;
-            }
+    /// Get relationship count for a campaign
+    pub fn relationship_count(&self, campaign_id: &str) -> usize {
+        self.relationships
+            .read()
+            .unwrap()
+            .get(campaign_id)
+            .map(|r| r.len())
+            .unwrap_or(0)
+    }
+}
 
-            // Source entity
-            *connection_counts.entry(rel.source_id.clone()).or_insert(0) += 1;
-            entity_map.entry(rel.source_id.clone()).or_insert_with(|| GraphNode {
-                id: rel.source_id.clone(),
-                name: rel.source_name.clone(),
-                entity_type: rel.source_type.clone(),
-                color: entity_type_color(&rel.source_type),
-                connection_count: 0,
-                is_hub: false,
-                data: HashMap::new(),
-            });
-
-            // Target entity
-            *connection_counts.entry(rel.target_id.clone()).or_insert(0) += 1;
-            entity_map.entry(rel.target_id.clone()).or_insert_with(|| GraphNode {
-                id: rel.target_id.clone(),
-                name: rel.target_name.clone(),
-                entity_type: rel.target_type.clone(),
-                color: entity_type_color(&rel.target_type),
-                connection_count: 0,
-                is_hub: false,
-                data: HashMap::new(),
-            });
+// ============================================================================
+// Graph Building
+// ============================================================================
+
+impl EntityGraph {
+    fn empty() -> Self {
+        Self {
+            nodes: vec![],
+            edges: vec![],
+            stats: GraphStats::default(),
         }
+    }
+}
 
-        // Update connection counts and identify hubs
-        let avg_connections = if entity_map.is_empty() {
-            0.0
-        } else {
-            connection_counts.values().sum::<usize>() as f64 / entity_map.len() as f64
-        };
+/// Build a graph (nodes, edges, stats) from a set of relationships
+fn build_graph<'a>(rels: impl Iterator<Item = &'a EntityRelationship> + Clone) -> EntityGraph {
+    // Collect unique entities
+    let mut entity_map: HashMap<String, GraphNode> = HashMap::new();
+    let mut connection_counts: HashMap<String, usize> = HashMap::new();
+
+    for rel in rels.clone() {
+        // Source entity
+        *connection_counts.entry(rel.source_id.clone()).or_insert(0) += 1;
+        entity_map.entry(rel.source_id.clone()).or_insert_with(|| GraphNode {
+            id: rel.source_id.clone(),
+            name: rel.source_name.clone(),
+            entity_type: rel.source_type.clone(),
+            color: entity_type_color(&rel.source_type),
+            connection_count: 0,
+            is_hub: false,
+            data: HashMap::new(),
+        });
+
+        // Target entity
+        *connection_counts.entry(rel.target_id.clone()).or_insert(0) += 1;
+        entity_map.entry(rel.target_id.clone()).or_insert_with(|| GraphNode {
+            id: rel.target_id.clone(),
+            name: rel.target_name.clone(),
+            entity_type: rel.target_type.clone(),
+            color: entity_type_color(&rel.target_type),
+            connection_count: 0,
+            is_hub: false,
+            data: HashMap::new(),
+        });
+    }
 
-        let mut nodes: Vec<GraphNode> = entity_map
-            .into_iter()
-            .map(|(id, mut node)| {
-                let count = *connection_counts.get(&id).unwrap_or(&0);
-                node.connection_count = count;
-                node.is_hub = count as f64 > avg_connections * 2.0;
-                node
-            })
-            .collect();
+    // Update connection counts and identify hubs
+    let avg_connections = if entity_map.is_empty() {
+        0.0
+    } else {
+        connection_counts.values().sum::<usize>() as f64 / entity_map.len() as f64
+    };
+
+    let mut nodes: Vec<GraphNode> = entity_map
+        .into_iter()
+        .map(|(id, mut node)| {
+            let count = *connection_counts.get(&id).unwrap_or(&0);
+            node.connection_count = count;
+            node.is_hub = count as f64 > avg_connections * 2.0;
+            node
+        })
+        .collect();
 
-        // Create edges
-        let edges: Vec<GraphEdge> = campaign_rels
-            .iter()
-            .filter(|r| include_inactive || r.is_active)
-            .map(|r| GraphEdge {
-                id: r.id.clone(),
-                source: r.source_id.clone(),
-                target: r.target_id.clone(),
-                label: r.relationship_type.to_string(),
-                strength: r.strength.value(),
-                bidirectional: r.relationship_type.is_bidirectional(),
-                is_active: r.is_active,
-                color: relationship_type_color(&r.relationship_type),
-            })
-            .collect();
+    // Create edges
+    let edges: Vec<GraphEdge> = rels
+        .map(|r| GraphEdge {
+            id: r.id.clone(),
+            source: r.source_id.clone(),
+            target: r.target_id.clone(),
+            label: r.relationship_type.to_string(),
+            strength: r.strength.value(),
+            bidirectional: r.relationship_type.is_bidirectional(),
+            is_active: r.is_active,
+            is_known: r.is_known,
+            color: relationship_type_color(&r.relationship_type),
+        })
+        .collect();
 
-        // Calculate stats
-        let mut entity_type_counts: HashMap<String, usize> = HashMap::new();
-        let mut relationship_type_counts: HashMap<String, usize> = HashMap::new();
+    // Calculate stats
+    let mut entity_type_counts: HashMap<String, usize> = HashMap::new();
+    let mut relationship_type_counts: HashMap<String, usize> = HashMap::new();
 
-        for node in &nodes {
-            *entity_type_counts
-                .entry(node.entity_type.to_string())
-                .or_insert(0) += 1;
-        }
+    for node in &nodes {
+        *entity_type_counts
+            .entry(node.entity_type.to_string())
+            .or_insert(0) += 1;
+    }
 
-        for edge in &edges {
-            *relationship_type_counts
-                .entry(edge.label.clone())
-                .or_insert(0) += 1;
-        }
+    for edge in &edges {
+        *relationship_type_counts
+            .entry(edge.label.clone())
+            .or_insert(0) += 1;
+    }
 
-        // Sort nodes by connection count for "most connected"
-        nodes.sort_by(|a, b| b.connection_count.cmp(&a.connection_count));
-        let most_connected: Vec<(String, usize)> = nodes
-            .iter()
-            .take(5)
-            .map(|n| (n.name.clone(), n.connection_count))
-            .collect();
-
-        let node_count = nodes.len();
-        let edge_count = edges.len();
-
-        EntityGraph {
-            nodes,
-            edges,
-            stats: GraphStats {
-                node_count,
-                edge_count,
-                entity_type_counts,
-                relationship_type_counts,
-                most_connected_entities: most_connected,
-            },
-        }
+    // Sort nodes by connection count for "most connected"
+    nodes.sort_by(|a, b| b.connection_count.cmp(&a.connection_count));
+    let most_connected: Vec<(String, usize)> = nodes
+        .iter()
+        .take(5)
+        .map(|n| (n.name.clone(), n.connection_count))
+        .collect();
+
+    let node_count = nodes.len();
+    let edge_count = edges.len();
+
+    EntityGraph {
+        nodes,
+        edges,
+        stats: GraphStats {
+            node_count,
+            edge_count,
+            entity_type_counts,
+            relationship_type_counts,
+            most_connected_entities: most_connected,
+        },
     }
+}
 
-    /// Get a subgraph centered on an entity (ego graph)
-    pub fn get_ego_graph(&self, campaign_id: &str, entity_id: &str, depth: usize) -> EntityGraph {
-        let full_graph = self.get_entity_graph(campaign_id, false);
-
-        if depth == 0 {
-            return EntityGraph {
-                nodes: vec![],
-                edges: vec![],
-                stats: GraphStats::default(),
-            };
-        }
+/// Restrict a graph to the entities within `depth` hops of `entity_id`
+fn ego_subgraph(graph: EntityGraph, entity_id: &str, depth: usize) -> EntityGraph {
+    if depth == 0 {
+        return EntityGraph::empty();
+    }
 
-        // BFS to find entities within depth
-        let mut visited: HashSet<String> = HashSet::new();
-        let mut current_level: HashSet<String> = HashSet::new();
-        current_level.insert(entity_id.to_string());
-        visited.insert(entity_id.to_string());
-
-        for _ in 0..depth {
-            let mut next_level: HashSet<String> = HashSet::new();
-            for edge in &full_graph.edges {
-                if current_level.contains(&edge.source) && !visited.contains(&edge.target) {
-                    next_level.insert(edge.target.clone());
-                }
-                if current_level.contains(&edge.target) && !visited.contains(&edge.source) {
-                    next_level.insert(edge.source.clone());
-                }
+    // BFS to find entities within depth
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut current_level: HashSet<String> = HashSet::new();
+    current_level.insert(entity_id.to_string());
+    visited.insert(entity_id.to_string());
+
+    for _ in 0..depth {
+        let mut next_level: HashSet<String> = HashSet::new();
+        for edge in &graph.edges {
+            if current_level.contains(&edge.source) && !visited.contains(&edge.target) {
+                next_level.insert(edge.target.clone());
+            }
+            if current_level.contains(&edge.target) && !visited.contains(&edge.source) {
+                next_level.insert(edge.source.clone());
             }
-            visited.extend(next_level.iter().cloned());
-            current_level = next_level;
-        }
-
-        // Filter graph to visited nodes
-        let nodes: Vec<GraphNode> = full_graph
-            .nodes
-            .into_iter()
-            .filter(|n| visited.contains(&n.id))
-            .collect();
-
-        let edges: Vec<GraphEdge> = full_graph
-            .edges
-            .into_iter()
-            .filter(|e| visited.contains(&e.source) && visited.contains(&e.target))
-            .collect();
-
-        EntityGraph {
-            stats: GraphStats {
-                node_count: nodes.len(),
-                edge_count: edges.len(),
-                ..Default::default()
-            },
-            nodes,
-            edges,
         }
+        visited.extend(next_level.iter().cloned());
+        current_level = next_level;
     }
 
-    /// Get relationship count for a campaign
-    pub fn relationship_count(&self, campaign_id: &str) -> usize {
-        self.relationships
-            .read()
-            .unwrap()
-            .get(campaign_id)
-            .map(|r| r.len())
-            .unwrap_or(0)
+    // Filter graph to visited nodes
+    let nodes: Vec<GraphNode> = graph
+        .nodes
+        .into_iter()
+        .filter(|n| visited.contains(&n.id))
+        .collect();
+
+    let edges: Vec<GraphEdge> = graph
+        .edges
+        .into_iter()
+        .filter(|e| visited.contains(&e.source) && visited.contains(&e.target))
+        .collect();
+
+    EntityGraph {
+        stats: GraphStats {
+            node_count: nodes.len(),
+            edge_count: edges.len(),
+            ..Default::default()
+        },
+        nodes,
+        edges,
     }
 }
 
@@ -1109,4 +1376,103 @@ mod tests {
         let ally_inverse = RelationshipType::Ally.inverse();
         assert_eq!(ally_inverse, Some(RelationshipType::Ally));
     }
+
+    #[test]
+    fn test_type_keys_roundtrip() {
+        for t in RelationshipType::ALL {
+            assert_eq!(RelationshipType::from_key(&t.key()), t);
+        }
+        assert_eq!(RelationshipType::MemberOf.key(), "member_of");
+        assert_eq!(
+            RelationshipType::from_key("sworn_rival"),
+            RelationshipType::Custom("sworn_rival".to_string())
+        );
+
+        for t in EntityType::ALL {
+            assert_eq!(EntityType::from_key(&t.key()), t);
+        }
+        assert_eq!(EntityType::from_key("character"), EntityType::PC);
+    }
+
+    #[test]
+    fn test_record_roundtrip() {
+        let rel = EntityRelationship::new(
+            "camp-1",
+            "npc-1",
+            EntityType::NPC,
+            "Alice",
+            "fac-1",
+            EntityType::Faction,
+            "Zhentarim",
+            RelationshipType::MemberOf,
+        )
+        .with_strength(RelationshipStrength::Strong)
+        .with_description("Sworn in last winter")
+        .as_secret();
+
+        let record = rel.to_record();
+        assert_eq!(record.relationship_type, "member_of");
+        assert_eq!(record.target_entity_type, "faction");
+        assert!((record.strength - 0.75).abs() < f64::EPSILON);
+
+        let restored = EntityRelationship::from_record(&record);
+        assert_eq!(restored.id, rel.id);
+        assert_eq!(restored.target_name, "Zhentarim");
+        assert_eq!(restored.relationship_type, RelationshipType::MemberOf);
+        assert_eq!(restored.strength, RelationshipStrength::Strong);
+        assert_eq!(restored.description, "Sworn in last winter");
+        assert!(!restored.is_known);
+
+        // Records without our metadata still load
+        let mut bare = record.clone();
+        bare.metadata = None;
+        let restored = EntityRelationship::from_record(&bare);
+        assert_eq!(restored.target_name, "fac-1");
+        assert!(restored.is_known);
+    }
+
+    #[test]
+    fn test_filtered_graph() {
+        let manager = RelationshipManager::default();
+        let rel = |source: &str, target: &str, rel_type: RelationshipType| {
+            EntityRelationship::new(
+                "camp-1",
+                source,
+                EntityType::NPC,
+                source,
+                target,
+                EntityType::NPC,
+                target,
+                rel_type,
+            )
+        };
+        manager.create_relationship(rel("a", "b", RelationshipType::Ally)).unwrap();
+        manager
+            .create_relationship(rel("b", "c", RelationshipType::Enemy).as_secret())
+            .unwrap();
+        manager.create_relationship(rel("c", "d", RelationshipType::Ally)).unwrap();
+
+        let everything = manager.get_filtered_graph("camp-1", &GraphFilter::default());
+        assert_eq!(everything.nodes.len(), 4);
+        assert!(everything.edges.iter().any(|e| !e.is_known));
+
+        // The secret edge is the only path from a to c and d
+        let players = GraphFilter {
+            focus: Some("a".to_string()),
+            depth: 3,
+            hide_secret: true,
+            ..Default::default()
+        };
+        let graph = manager.get_filtered_graph("camp-1", &players);
+        assert_eq!(graph.nodes.len(), 2);
+        assert!(graph.edges.iter().all(|e| e.is_known));
+
+        let allies = GraphFilter {
+            relationship_types: vec![RelationshipType::Ally],
+            ..Default::default()
+        };
+        let graph = manager.get_filtered_graph("camp-1", &allies);
+        assert_eq!(graph.edges.len(), 2);
+        assert!(graph.edges.iter().all(|e| e.label == "Ally"));
+    }
 }
@@ -10,6 +10,8 @@ use std::sync::RwLock;
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::calendar::{self, Calendar, CalendarError, Era, Month, DEFAULT_CALENDAR};
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -33,6 +35,9 @@ pub enum WorldStateError {
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error("Calendar error: {0}")]
+    Calendar(#[from] CalendarError),
 }
 
 pub type Result<T> = std::result::Result<T, WorldStateError>;
@@ -46,7 +51,8 @@ pub type Result<T> = std::result::Result<T, WorldStateError>;
 pub struct InGameDate {
     /// Year (can be negative for ancient history)
     pub year: i32,
-    /// Month (1-12 or custom calendar)
+    /// Month, as a 1-based index into the calendar's months (including
+    /// intercalary days)
     pub month: u8,
     /// Day of month
     pub day: u8,
@@ -74,7 +80,7 @@ impl Default for InGameDate {
             month: 1,
             day: 1,
             era: None,
-            calendar: "Standard".to_string(),
+            calendar: DEFAULT_CALENDAR.to_string(),
             time: None,
         }
     }
@@ -113,34 +119,15 @@ impl InGameDate {
         }
     }
 
-    /// Advance by days
-    pub fn advance_days(&mut self, days: i32) {
-        // Simple implementation - doesn't handle month lengths
-        let total_days = self.day as i32 + days;
-        if total_days > 30 {
-            self.month += (total_days / 30) as u8;
-            self.day = (total_days % 30) as u8;
-            if self.day == 0 {
-                self.day = 30;
-                self.month -= 1;
-            }
-        } else if total_days <= 0 {
-            // Handle negative days
-            self.month = self.month.saturating_sub(1);
-            if self.month == 0 {
-                self.month = 12;
-                self.year -= 1;
-            }
-            self.day = (30 + total_days) as u8;
-        } else {
-            self.day = total_days as u8;
-        }
+    /// Advance by days (negative goes back) using the given calendar
+    pub fn advance_days(&mut self, calendar: &Calendar, days: i64) -> calendar::Result<()> {
+        *self = calendar.add_days(self, days)?;
+        Ok(())
+    }
 
-        // Handle month overflow
-        while self.month > 12 {
-            self.month -= 12;
-            self.year += 1;
-        }
+    /// Whether two dates fall on the same day, ignoring era and time
+    pub fn same_day(&self, other: &InGameDate) -> bool {
+        (self.year, self.month, self.day) == (other.year, other.month, other.day)
     }
 }
 
@@ -436,6 +423,7 @@ pub struct InteractionRecord {
 
 /// Complete world state for a campaign
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "StoredWorldState")]
 pub struct WorldState {
     /// Campaign identifier
     pub campaign_id: String,
@@ -451,51 +439,116 @@ pub struct WorldState {
     pub custom_fields: HashMap<String, serde_json::Value>,
     /// Last real-world update time
     pub updated_at: DateTime<Utc>,
-    /// Calendar configuration
-    pub calendar_config: CalendarConfig,
+    /// Calendar the campaign's dates are reckoned in
+    pub calendar: Calendar,
 }
 
-/// Configuration for the in-game calendar
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CalendarConfig {
-    pub name: String,
-    pub months_per_year: u8,
-    pub days_per_month: Vec<u8>,
-    pub month_names: Vec<String>,
-    pub week_days: Vec<String>,
-    pub eras: Vec<String>,
+/// `WorldState` as stored. World state saved before campaigns had a
+/// [`Calendar`] carries a `calendar_config` instead of a `calendar`.
+#[derive(Deserialize)]
+struct StoredWorldState {
+    campaign_id: String,
+    current_date: InGameDate,
+    events: Vec<WorldEvent>,
+    locations: HashMap<String, LocationState>,
+    npc_relationships: Vec<NpcRelationshipState>,
+    custom_fields: HashMap<String, serde_json::Value>,
+    updated_at: DateTime<Utc>,
+    #[serde(default)]
+    calendar: Option<Calendar>,
+    #[serde(default)]
+    calendar_config: Option<LegacyCalendarConfig>,
 }
 
-impl Default for CalendarConfig {
-    fn default() -> Self {
+impl From<StoredWorldState> for WorldState {
+    fn from(stored: StoredWorldState) -> Self {
+        let mut current_date = stored.current_date;
+        let calendar = match (stored.calendar, stored.calendar_config) {
+            (Some(calendar), _) => calendar,
+            (None, Some(config)) => {
+                let calendar = config.into_calendar();
+                current_date = calendar.clamp_date(&current_date);
+                calendar
+            }
+            (None, None) => Calendar::default(),
+        };
         Self {
-            name: "Standard".to_string(),
-            months_per_year: 12,
-            days_per_month: vec![30; 12], // Simple 30-day months
-            month_names: vec![
-                "January".to_string(),
-                "February".to_string(),
-                "March".to_string(),
-                "April".to_string(),
-                "May".to_string(),
-                "June".to_string(),
-                "July".to_string(),
-                "August".to_string(),
-                "September".to_string(),
-                "October".to_string(),
-                "November".to_string(),
-                "December".to_string(),
-            ],
-            week_days: vec![
-                "Sunday".to_string(),
-                "Monday".to_string(),
-                "Tuesday".to_string(),
-                "Wednesday".to_string(),
-                "Thursday".to_string(),
-                "Friday".to_string(),
-                "Saturday".to_string(),
-            ],
-            eras: vec!["Common Era".to_string()],
+            campaign_id: stored.campaign_id,
+            current_date,
+            events: stored.events,
+            locations: stored.locations,
+            npc_relationships: stored.npc_relationships,
+            custom_fields: stored.custom_fields,
+            updated_at: stored.updated_at,
+            calendar,
+        }
+    }
+}
+
+/// The calendar settings world state stored before [`Calendar`] existed
+#[derive(Debug, Deserialize)]
+struct LegacyCalendarConfig {
+    name: String,
+    #[serde(default)]
+    days_per_month: Vec<u8>,
+    #[serde(default)]
+    month_names: Vec<String>,
+    #[serde(default)]
+    week_days: Vec<String>,
+    #[serde(default)]
+    eras: Vec<String>,
+}
+
+impl LegacyCalendarConfig {
+    /// The preset of the same name, else a plain calendar with the same
+    /// months so stored dates keep their meaning. Settings that don't make
+    /// a valid calendar fall back to the default one.
+    fn into_calendar(self) -> Calendar {
+        if let Some(preset) = Calendar::preset(&self.name) {
+            return preset;
+        }
+
+        let months = self
+            .month_names
+            .into_iter()
+            .zip(self.days_per_month)
+            .map(|(name, days)| Month {
+                name,
+                days,
+                leap_days: 0,
+                intercalary: false,
+                alias: None,
+            })
+            .collect();
+        let calendar = Calendar {
+            name: self.name,
+            description: None,
+            months,
+            leap_year: None,
+            weekdays: self.week_days,
+            epoch_weekday: 0,
+            eras: self
+                .eras
+                .into_iter()
+                .map(|name| Era {
+                    name,
+                    abbreviation: None,
+                    start_year: None,
+                })
+                .collect(),
+            moons: Vec::new(),
+            seasons: Vec::new(),
+            holidays: Vec::new(),
+        };
+        match calendar.validate() {
+            Ok(()) => calendar,
+            Err(e) => {
+                log::warn!(
+                    "Old calendar '{}' is not usable ({e}), using the default",
+                    calendar.name
+                );
+                Calendar::default()
+            }
         }
     }
 }
@@ -510,9 +563,42 @@ impl WorldState {
             npc_relationships: vec![],
             custom_fields: HashMap::new(),
             updated_at: Utc::now(),
-            calendar_config: CalendarConfig::default(),
+            calendar: Calendar::default(),
         }
     }
+
+    /// Advance the current date by days
+    pub fn advance_date(&mut self, days: i64) -> Result<InGameDate> {
+        self.current_date.advance_days(&self.calendar, days)?;
+        self.updated_at = Utc::now();
+        Ok(self.current_date.clone())
+    }
+
+    /// Set the current date, rejecting dates the calendar doesn't have
+    pub fn set_current_date(&mut self, date: InGameDate) -> Result<()> {
+        self.calendar.validate_date(&date)?;
+        self.current_date = InGameDate {
+            calendar: self.calendar.name.clone(),
+            ..date
+        };
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Switch calendars, pulling the current date into range
+    pub fn set_calendar(&mut self, calendar: Calendar) {
+        self.current_date = calendar.clamp_date(&self.current_date);
+        self.calendar = calendar;
+        self.updated_at = Utc::now();
+    }
+
+    /// Events on a given day
+    pub fn events_on(&self, date: &InGameDate) -> Vec<&WorldEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.in_game_date.same_day(date))
+            .collect()
+    }
 }
 
 // ============================================================================
@@ -591,20 +677,16 @@ impl WorldStateManager {
         let state = states
             .get_mut(campaign_id)
             .ok_or_else(|| WorldStateError::CampaignNotFound(campaign_id.to_string()))?;
-        state.current_date = date;
-        state.updated_at = Utc::now();
-        Ok(())
+        state.set_current_date(date)
     }
 
     /// Advance the current date by days
-    pub fn advance_date(&self, campaign_id: &str, days: i32) -> Result<InGameDate> {
+    pub fn advance_date(&self, campaign_id: &str, days: i64) -> Result<InGameDate> {
         let mut states = self.states.write().unwrap();
         let state = states
             .get_mut(campaign_id)
             .ok_or_else(|| WorldStateError::CampaignNotFound(campaign_id.to_string()))?;
-        state.current_date.advance_days(days);
-        state.updated_at = Utc::now();
-        Ok(state.current_date.clone())
+        state.advance_date(days)
     }
 
     /// Get current date
@@ -634,6 +716,16 @@ impl WorldStateManager {
         Ok(event)
     }
 
+    /// Events on a given in-game day
+    pub fn events_on(&self, campaign_id: &str, date: &InGameDate) -> Vec<WorldEvent> {
+        self.states
+            .read()
+            .unwrap()
+            .get(campaign_id)
+            .map(|s| s.events_on(date).into_iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     /// Get event by ID
     pub fn get_event(&self, campaign_id: &str, event_id: &str) -> Option<WorldEvent> {
         self.states
@@ -891,28 +983,28 @@ impl WorldStateManager {
     }
 
     // ========================================================================
-    // Calendar Configuration
+    // Calendar
     // ========================================================================
 
-    /// Set calendar configuration
-    pub fn set_calendar_config(&self, campaign_id: &str, config: CalendarConfig) -> Result<()> {
+    /// Set the campaign's calendar
+    pub fn set_calendar(&self, campaign_id: &str, calendar: Calendar) -> Result<()> {
+        calendar.validate()?;
         let mut states = self.states.write().unwrap();
         let state = states
             .get_mut(campaign_id)
             .ok_or_else(|| WorldStateError::CampaignNotFound(campaign_id.to_string()))?;
 
-        state.calendar_config = config;
-        state.updated_at = Utc::now();
+        state.set_calendar(calendar);
         Ok(())
     }
 
-    /// Get calendar configuration
-    pub fn get_calendar_config(&self, campaign_id: &str) -> Option<CalendarConfig> {
+    /// Get the campaign's calendar
+    pub fn get_calendar(&self, campaign_id: &str) -> Option<Calendar> {
         self.states
             .read()
             .unwrap()
             .get(campaign_id)
-            .map(|s| s.calendar_config.clone())
+            .map(|s| s.calendar.clone())
     }
 }
 
@@ -929,9 +1021,121 @@ mod tests {
         let mut date = InGameDate::new(1492, 6, 15);
         assert_eq!(date.display(), "15/6/1492");
 
-        date.advance_days(20);
+        let calendar = Calendar::default();
+        date.advance_days(&calendar, 20).unwrap();
         assert_eq!(date.day, 5);
         assert_eq!(date.month, 7);
+
+        // Month lengths are honoured: July has 31 days
+        date.advance_days(&calendar, 27).unwrap();
+        assert_eq!((date.month, date.day), (8, 1));
+    }
+
+    #[test]
+    fn test_calendar_aware_dates() {
+        let manager = WorldStateManager::new();
+        manager.initialize("camp-1");
+
+        let harptos = Calendar::preset("Harptos").unwrap();
+        let flamerule = harptos.month_index("Flamerule").unwrap();
+        manager.set_calendar("camp-1", harptos.clone()).unwrap();
+        manager
+            .set_current_date("camp-1", harptos.date(1492, flamerule, 30).unwrap())
+            .unwrap();
+
+        // Midsummer, then Shieldmeet, then 1 Eleasis
+        let date = manager.advance_date("camp-1", 3).unwrap();
+        assert_eq!(harptos.format_date(&date), "1 Eleasis 1492 DR");
+        assert_eq!(date.calendar, "Harptos");
+
+        let bad = InGameDate::new(1492, 40, 1);
+        assert!(matches!(
+            manager.set_current_date("camp-1", bad),
+            Err(WorldStateError::Calendar(_))
+        ));
+    }
+
+    /// World state JSON as saved before campaigns had a `Calendar`
+    fn legacy_world_state(calendar_config: serde_json::Value) -> String {
+        serde_json::json!({
+            "campaign_id": "camp-1",
+            "current_date": {
+                "year": 1492, "month": 2, "day": 30,
+                "era": null, "calendar": "Standard", "time": null
+            },
+            "events": [],
+            "locations": {},
+            "npc_relationships": [],
+            "custom_fields": {},
+            "updated_at": "2025-01-01T00:00:00Z",
+            "calendar_config": calendar_config
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_legacy_calendar_config_roundtrip() {
+        let days = vec![30u8; 12];
+        // A config naming a preset becomes that preset
+        let json = legacy_world_state(serde_json::json!({
+            "name": "Harptos",
+            "months_per_year": 12,
+            "days_per_month": days,
+            "month_names": [],
+            "week_days": [],
+            "eras": ["Dale Reckoning"]
+        }));
+        let state: WorldState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.calendar, Calendar::preset("Harptos").unwrap());
+        assert_eq!(state.current_date.calendar, "Harptos");
+
+        let saved = serde_json::to_string(&state).unwrap();
+        assert!(!saved.contains("calendar_config"));
+        let reloaded: WorldState = serde_json::from_str(&saved).unwrap();
+        assert_eq!(reloaded.calendar, state.calendar);
+        assert_eq!(reloaded.current_date, state.current_date);
+
+        // The old default (twelve 30-day months) keeps its months, so a
+        // stored 30th of February is still a real date
+        let months: Vec<String> = (1..=12).map(|m| format!("Month {m}")).collect();
+        let json = legacy_world_state(serde_json::json!({
+            "name": "Standard",
+            "months_per_year": 12,
+            "days_per_month": days,
+            "month_names": months,
+            "week_days": ["Sunday", "Monday"],
+            "eras": ["Common Era"]
+        }));
+        let state: WorldState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.calendar.name, "Standard");
+        assert_eq!(state.calendar.year_length(1492), 360);
+        assert_eq!((state.current_date.month, state.current_date.day), (2, 30));
+        assert!(state.calendar.validate_date(&state.current_date).is_ok());
+
+        // Neither field: the default calendar
+        let json = legacy_world_state(serde_json::Value::Null);
+        let state: WorldState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.calendar, Calendar::default());
+    }
+
+    #[test]
+    fn test_events_on_day() {
+        let manager = WorldStateManager::new();
+        manager.initialize("camp-1");
+        let day = InGameDate::new(1492, 6, 15);
+        manager
+            .add_event("camp-1", WorldEvent::new("camp-1", "Coronation", "", day.clone()))
+            .unwrap();
+        manager
+            .add_event(
+                "camp-1",
+                WorldEvent::new("camp-1", "Storm", "", InGameDate::new(1492, 6, 16)),
+            )
+            .unwrap();
+
+        let events = manager.events_on("camp-1", &day);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title, "Coronation");
     }
 
     #[test]
@@ -0,0 +1,722 @@
+//! Character Advancement
+//!
+//! Progresses a generated [`Character`] after creation. Each system advances
+//! in its own way:
+//! - **Level up** (D&D 5e, Pathfinder 2e): class features, hit points and the
+//!   level's choices (ability increases, feats, skill increases)
+//! - **Experience spend** (World of Darkness): award experience, then buy dots
+//! - **Milestones** (Fate Core): minor, significant and major milestones
+//! - **Development** (Call of Cthulhu): improvement checks for skills used in play
+//!
+//! Generators opt in through [`SystemGenerator::advancement`](super::SystemGenerator::advancement).
+//! A preview lists the choice slots for the next advancement; the caller picks
+//! options and sends them back as [`AdvancementChoice`]s. Every applied
+//! advancement is appended to [`Character::advancement_history`].
+
+use super::{AttributeValue, Character, CharacterGenError, CharacterTrait, Result, TraitType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ============================================================================
+// Advancement Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AdvancementKind {
+    LevelUp,
+    ExperienceSpend,
+    Milestone,
+    Development,
+}
+
+impl AdvancementKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AdvancementKind::LevelUp => "Level Up",
+            AdvancementKind::ExperienceSpend => "Experience Spend",
+            AdvancementKind::Milestone => "Milestone",
+            AdvancementKind::Development => "Development",
+        }
+    }
+}
+
+/// Fate Core milestone size
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Milestone {
+    #[default]
+    Minor,
+    Significant,
+    Major,
+}
+
+impl Milestone {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Milestone::Minor => "Minor",
+            Milestone::Significant => "Significant",
+            Milestone::Major => "Major",
+        }
+    }
+
+    /// Cycle to the next milestone size
+    pub fn next(self) -> Self {
+        match self {
+            Milestone::Minor => Milestone::Significant,
+            Milestone::Significant => Milestone::Major,
+            Milestone::Major => Milestone::Minor,
+        }
+    }
+
+    /// Cycle to the previous milestone size
+    pub fn prev(self) -> Self {
+        match self {
+            Milestone::Minor => Milestone::Major,
+            Milestone::Significant => Milestone::Minor,
+            Milestone::Major => Milestone::Significant,
+        }
+    }
+}
+
+/// A single choice made while advancing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AdvancementChoice {
+    IncreaseAttribute {
+        name: String,
+        amount: i32,
+    },
+    IncreaseSkill {
+        name: String,
+        amount: i32,
+    },
+    GainTrait {
+        name: String,
+        trait_type: TraitType,
+        description: String,
+    },
+    ReplaceTrait {
+        old: String,
+        name: String,
+        trait_type: TraitType,
+        description: String,
+    },
+    SwapSkills {
+        first: String,
+        second: String,
+    },
+}
+
+impl AdvancementChoice {
+    pub fn describe(&self) -> String {
+        match self {
+            AdvancementChoice::IncreaseSkill { name, amount: 0 } => {
+                format!("{name} improvement check")
+            }
+            AdvancementChoice::IncreaseAttribute { name, amount }
+            | AdvancementChoice::IncreaseSkill { name, amount } => format!("{name} {amount:+}"),
+            AdvancementChoice::GainTrait { name, .. } => format!("Gained {name}"),
+            AdvancementChoice::ReplaceTrait { old, name, .. } => {
+                format!("Replaced {old} with {name}")
+            }
+            AdvancementChoice::SwapSkills { first, second } => {
+                format!("Swapped {first} and {second}")
+            }
+        }
+    }
+
+    /// The slot kind and option each part of this choice occupies
+    fn slot_kinds(&self) -> Vec<(ChoiceKind, &str)> {
+        match self {
+            AdvancementChoice::IncreaseAttribute { name, amount } => {
+                vec![(ChoiceKind::Attribute { amount: *amount }, name.as_str())]
+            }
+            AdvancementChoice::IncreaseSkill { name, amount } => {
+                vec![(ChoiceKind::Skill { amount: *amount }, name.as_str())]
+            }
+            AdvancementChoice::GainTrait {
+                name, trait_type, ..
+            } => {
+                vec![(
+                    ChoiceKind::Trait {
+                        trait_type: trait_type.clone(),
+                    },
+                    name.as_str(),
+                )]
+            }
+            AdvancementChoice::ReplaceTrait {
+                old,
+                name,
+                trait_type,
+                ..
+            } => vec![
+                (ChoiceKind::ReplaceTrait, old.as_str()),
+                (
+                    ChoiceKind::Trait {
+                        trait_type: trait_type.clone(),
+                    },
+                    name.as_str(),
+                ),
+            ],
+            AdvancementChoice::SwapSkills { first, second } => {
+                vec![
+                    (ChoiceKind::SwapSkill, first.as_str()),
+                    (ChoiceKind::SwapSkill, second.as_str()),
+                ]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdvancementRequest {
+    /// Experience awarded before spending (experience-based systems)
+    pub experience_award: u32,
+    /// Milestone size (milestone-based systems)
+    pub milestone: Option<Milestone>,
+    /// Roll hit points instead of taking the average
+    pub roll_hit_points: bool,
+    pub choices: Vec<AdvancementChoice>,
+}
+
+/// An applied advancement, stored on the character
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvancementRecord {
+    pub kind: AdvancementKind,
+    #[serde(default)]
+    pub milestone: Option<Milestone>,
+    pub from_level: u32,
+    pub to_level: u32,
+    #[serde(default)]
+    pub experience_awarded: u32,
+    #[serde(default)]
+    pub experience_spent: u32,
+    #[serde(default)]
+    pub hit_points_gained: Option<i32>,
+    #[serde(default)]
+    pub choices: Vec<AdvancementChoice>,
+    /// Human-readable summary of every change made
+    pub changes: Vec<String>,
+    pub advanced_at: DateTime<Utc>,
+}
+
+impl AdvancementRecord {
+    pub fn new(kind: AdvancementKind, character: &Character, request: &AdvancementRequest) -> Self {
+        Self {
+            kind,
+            milestone: request.milestone,
+            from_level: character.level,
+            to_level: character.level,
+            experience_awarded: 0,
+            experience_spent: 0,
+            hit_points_gained: None,
+            choices: request.choices.clone(),
+            changes: vec![],
+            advanced_at: Utc::now(),
+        }
+    }
+
+    /// One-line summary for history listings
+    pub fn summary(&self) -> String {
+        let mut summary = match (self.kind, self.milestone) {
+            (AdvancementKind::LevelUp, _) => {
+                format!("Level {} → {}", self.from_level, self.to_level)
+            }
+            (AdvancementKind::Milestone, Some(milestone)) => {
+                format!("{} milestone", milestone.label())
+            }
+            (kind, _) => kind.label().to_string(),
+        };
+        if self.experience_spent > 0 {
+            summary.push_str(&format!(" ({} XP)", self.experience_spent));
+        }
+        summary
+    }
+}
+
+/// What kind of option a choice slot offers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChoiceKind {
+    Attribute {
+        amount: i32,
+    },
+    /// Raise a skill; an amount of 0 means the system rolls the increase
+    Skill {
+        amount: i32,
+    },
+    Trait {
+        trait_type: TraitType,
+    },
+    /// Trait to give up; replaced by the pick in the next `Trait` slot
+    ReplaceTrait,
+    /// Skill whose rating is swapped with the next `SwapSkill` slot's pick
+    SwapSkill,
+}
+
+/// One decision offered by a preview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChoiceSlot {
+    pub label: String,
+    pub kind: ChoiceKind,
+    pub options: Vec<String>,
+    /// Description for each option (parallel to `options`, may be empty)
+    pub details: Vec<String>,
+    pub required: bool,
+    /// Experience cost if this slot is taken
+    pub cost: u32,
+}
+
+impl ChoiceSlot {
+    pub fn new(label: impl Into<String>, kind: ChoiceKind, options: Vec<String>) -> Self {
+        Self {
+            label: label.into(),
+            kind,
+            options,
+            details: vec![],
+            required: false,
+            cost: 0,
+        }
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+
+    pub fn with_cost(mut self, cost: u32) -> Self {
+        self.cost = cost;
+        self
+    }
+
+    fn offers(&self, kind: &ChoiceKind, option: &str) -> bool {
+        &self.kind == kind && self.options.iter().any(|o| o.eq_ignore_ascii_case(option))
+    }
+}
+
+/// The next advancement available to a character
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvancementPreview {
+    pub kind: AdvancementKind,
+    /// Level after advancing, for level-based systems
+    pub next_level: Option<u32>,
+    /// Experience available to spend, including the requested award
+    pub experience: u32,
+    /// Features gained or improved automatically
+    pub features: Vec<CharacterTrait>,
+    /// Hit point gain, e.g. "d10 +2 (average 8)"
+    pub hit_points: Option<String>,
+    /// Die rolled for hit points when the player opts to roll
+    pub hit_die: Option<i32>,
+    pub slots: Vec<ChoiceSlot>,
+    /// Rules reminders for the player
+    pub notes: Vec<String>,
+}
+
+impl AdvancementPreview {
+    pub fn new(kind: AdvancementKind) -> Self {
+        Self {
+            kind,
+            next_level: None,
+            experience: 0,
+            features: vec![],
+            hit_points: None,
+            hit_die: None,
+            slots: vec![],
+            notes: vec![],
+        }
+    }
+
+    /// Turn one pick per slot (an index into its options) into choices.
+    /// Consecutive `SwapSkill` picks form one swap and a `ReplaceTrait` pick
+    /// is paired with the next `Trait` pick.
+    pub fn choices(&self, picks: &[Option<usize>]) -> Vec<AdvancementChoice> {
+        let mut choices = vec![];
+        let mut pending_swap: Option<String> = None;
+        let mut pending_replace: Option<String> = None;
+
+        for (slot, pick) in self.slots.iter().zip(picks) {
+            let Some(index) = *pick else { continue };
+            let Some(option) = slot.options.get(index).cloned() else {
+                continue;
+            };
+            let detail = slot.details.get(index).cloned().unwrap_or_default();
+
+            match &slot.kind {
+                ChoiceKind::Attribute { amount } => {
+                    choices.push(AdvancementChoice::IncreaseAttribute {
+                        name: option,
+                        amount: *amount,
+                    })
+                }
+                ChoiceKind::Skill { amount } => choices.push(AdvancementChoice::IncreaseSkill {
+                    name: option,
+                    amount: *amount,
+                }),
+                ChoiceKind::Trait { trait_type } => match pending_replace.take() {
+                    Some(old) => choices.push(AdvancementChoice::ReplaceTrait {
+                        old,
+                        name: option,
+                        trait_type: trait_type.clone(),
+                        description: detail,
+                    }),
+                    None => choices.push(AdvancementChoice::GainTrait {
+                        name: option,
+                        trait_type: trait_type.clone(),
+                        description: detail,
+                    }),
+                },
+                ChoiceKind::ReplaceTrait => pending_replace = Some(option),
+                ChoiceKind::SwapSkill => match pending_swap.take() {
+                    Some(first) => choices.push(AdvancementChoice::SwapSkills {
+                        first,
+                        second: option,
+                    }),
+                    None => pending_swap = Some(option),
+                },
+            }
+        }
+
+        choices
+    }
+
+    /// Total experience cost of the picked slots
+    pub fn cost(&self, picks: &[Option<usize>]) -> u32 {
+        self.slots
+            .iter()
+            .zip(picks)
+            .filter(|(_, pick)| pick.is_some())
+            .map(|(slot, _)| slot.cost)
+            .sum()
+    }
+}
+
+// ============================================================================
+// Advancement Trait
+// ============================================================================
+
+/// System-specific advancement rules
+pub trait Advancement {
+    fn advancement_kind(&self) -> AdvancementKind;
+
+    /// Describe the next advancement without changing the character
+    fn preview_advancement(
+        &self,
+        character: &Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementPreview>;
+
+    /// Apply an advancement and append it to the character's history.
+    /// The character is left untouched if the request is invalid.
+    fn advance(
+        &self,
+        character: &mut Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementRecord>;
+}
+
+/// Run `apply` on a copy of `character`, keeping the changes and recording
+/// the advancement only if it succeeds
+pub(crate) fn apply_advancement<F>(character: &mut Character, apply: F) -> Result<AdvancementRecord>
+where
+    F: FnOnce(&mut Character) -> Result<AdvancementRecord>,
+{
+    let mut advanced = character.clone();
+    let record = apply(&mut advanced)?;
+    advanced.advancement_history.push(record.clone());
+    *character = advanced;
+    Ok(record)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+pub(crate) fn advancement_error(message: impl Into<String>) -> CharacterGenError {
+    CharacterGenError::AdvancementError(message.into())
+}
+
+/// Fail unless the character belongs to the advancing system
+pub(crate) fn check_system(character: &Character, system: &super::GameSystem) -> Result<()> {
+    if &character.system == system {
+        Ok(())
+    } else {
+        Err(advancement_error(format!(
+            "{} is a {} character, not {}",
+            character.name,
+            character.system.display_name(),
+            system.display_name()
+        )))
+    }
+}
+
+/// Match each choice to an unused preview slot offering it, returning the
+/// slot index per choice. Fails if a choice isn't offered or a required
+/// slot is left empty.
+pub(crate) fn match_choices(
+    preview: &AdvancementPreview,
+    choices: &[AdvancementChoice],
+) -> Result<Vec<usize>> {
+    let mut used = vec![false; preview.slots.len()];
+    let mut matched = Vec::with_capacity(choices.len());
+
+    for choice in choices {
+        let mut first = None;
+        for (kind, option) in choice.slot_kinds() {
+            let index = (0..preview.slots.len())
+                .find(|&i| !used[i] && preview.slots[i].offers(&kind, option))
+                .ok_or_else(|| {
+                    advancement_error(format!("{} is not an available choice", choice.describe()))
+                })?;
+            used[index] = true;
+            first.get_or_insert(index);
+        }
+        matched.extend(first);
+    }
+
+    if let Some((_, slot)) = preview
+        .slots
+        .iter()
+        .enumerate()
+        .find(|(i, slot)| slot.required && !used[*i])
+    {
+        return Err(advancement_error(format!("{} must be chosen", slot.label)));
+    }
+    Ok(matched)
+}
+
+/// Find a map key case-insensitively
+pub(crate) fn find_key<V>(map: &HashMap<String, V>, name: &str) -> Option<String> {
+    map.keys()
+        .find(|key| key.eq_ignore_ascii_case(name))
+        .cloned()
+}
+
+pub(crate) fn has_trait(character: &Character, name: &str) -> bool {
+    character
+        .traits
+        .iter()
+        .any(|t| t.name.eq_ignore_ascii_case(name))
+}
+
+/// Sorted keys of a map, for stable option lists
+pub(crate) fn sorted_keys<V>(map: &HashMap<String, V>) -> Vec<String> {
+    let mut keys: Vec<String> = map.keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+/// Features the character lacks, or has with a different effect
+pub(crate) fn new_features(
+    character: &Character,
+    features: Vec<CharacterTrait>,
+) -> Vec<CharacterTrait> {
+    features
+        .into_iter()
+        .filter(|feature| {
+            !character.traits.iter().any(|t| {
+                t.name == feature.name
+                    && t.trait_type == feature.trait_type
+                    && t.mechanical_effect == feature.mechanical_effect
+            })
+        })
+        .collect()
+}
+
+/// Add new features and update improved ones, describing each change
+pub(crate) fn merge_features(
+    character: &mut Character,
+    features: Vec<CharacterTrait>,
+) -> Vec<String> {
+    let mut changes = vec![];
+    for feature in new_features(character, features) {
+        let existing = character
+            .traits
+            .iter_mut()
+            .find(|t| t.name == feature.name && t.trait_type == feature.trait_type);
+        match existing {
+            Some(existing) => {
+                changes.push(format!(
+                    "{} improved: {}",
+                    feature.name,
+                    feature
+                        .mechanical_effect
+                        .as_deref()
+                        .unwrap_or(&feature.description)
+                ));
+                *existing = feature;
+            }
+            None => {
+                changes.push(format!("Gained {}", feature.name));
+                character.traits.push(feature);
+            }
+        }
+    }
+    changes
+}
+
+/// Raise an attribute by `amount` up to `max`, rebuilding its modifier with
+/// `rebuild` (e.g. [`AttributeValue::new`] for d20 systems)
+pub(crate) fn raise_attribute(
+    character: &mut Character,
+    name: &str,
+    amount: i32,
+    max: i32,
+    rebuild: fn(i32) -> AttributeValue,
+) -> Result<String> {
+    let key = find_key(&character.attributes, name)
+        .ok_or_else(|| advancement_error(format!("Unknown attribute: {name}")))?;
+    let value = character.attributes.get_mut(&key).expect("key was found");
+    let raised = value.base + amount;
+    if raised > max {
+        return Err(advancement_error(format!("{key} cannot exceed {max}")));
+    }
+    let temp_bonus = value.temp_bonus;
+    let before = value.base;
+    *value = AttributeValue {
+        temp_bonus,
+        ..rebuild(raised)
+    };
+    Ok(format!("{key} {before} → {raised}"))
+}
+
+/// Raise a skill by `amount` up to `max`
+pub(crate) fn raise_skill(
+    character: &mut Character,
+    name: &str,
+    amount: i32,
+    max: i32,
+) -> Result<String> {
+    let key = find_key(&character.skills, name)
+        .ok_or_else(|| advancement_error(format!("Unknown skill: {name}")))?;
+    let value = character.skills.get_mut(&key).expect("key was found");
+    let raised = *value + amount;
+    if raised > max {
+        return Err(advancement_error(format!("{key} cannot exceed {max}")));
+    }
+    let before = std::mem::replace(value, raised);
+    Ok(format!("{key} {before} → {raised}"))
+}
+
+/// Add a trait the character doesn't have yet
+pub(crate) fn gain_trait(
+    character: &mut Character,
+    name: &str,
+    trait_type: TraitType,
+    description: &str,
+    mechanical_effect: Option<String>,
+) -> Result<String> {
+    if has_trait(character, name) {
+        return Err(advancement_error(format!(
+            "{} already has {name}",
+            character.name
+        )));
+    }
+    character.traits.push(CharacterTrait {
+        name: name.to_string(),
+        trait_type,
+        description: description.to_string(),
+        mechanical_effect,
+    });
+    Ok(format!("Gained {name}"))
+}
+
+/// Read a `Key: value` line from character notes
+pub(crate) fn note_value(notes: &str, key: &str) -> Option<i32> {
+    notes.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if !name.trim().eq_ignore_ascii_case(key) {
+            return None;
+        }
+        value
+            .split_whitespace()
+            .next()?
+            .trim_start_matches('+')
+            .parse()
+            .ok()
+    })
+}
+
+/// Write a `Key: value` line into character notes, replacing an existing one
+pub(crate) fn set_note_value(notes: &mut String, key: &str, value: impl std::fmt::Display) {
+    let line = format!("{key}: {value}");
+    let mut replaced = false;
+    let mut lines: Vec<String> = notes
+        .lines()
+        .map(|existing| match existing.split_once(':') {
+            Some((name, _)) if !replaced && name.trim().eq_ignore_ascii_case(key) => {
+                replaced = true;
+                line.clone()
+            }
+            _ => existing.to_string(),
+        })
+        .collect();
+    if !replaced {
+        lines.push(line);
+    }
+    *notes = lines.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_values_round_trip() {
+        let mut notes = "Health: 7\nWillpower: 4".to_string();
+        assert_eq!(note_value(&notes, "health"), Some(7));
+        assert_eq!(note_value(&notes, "Virtue"), None);
+
+        set_note_value(&mut notes, "Health", 8);
+        set_note_value(&mut notes, "Proficiency Bonus", "+2");
+        assert_eq!(notes, "Health: 8\nWillpower: 4\nProficiency Bonus: +2");
+        assert_eq!(note_value(&notes, "Proficiency Bonus"), Some(2));
+    }
+
+    #[test]
+    fn test_preview_pairs_swap_and_replace_slots() {
+        let mut preview = AdvancementPreview::new(AdvancementKind::Milestone);
+        let skills = vec!["Fight".to_string(), "Notice".to_string()];
+        preview.slots = vec![
+            ChoiceSlot::new("Swap", ChoiceKind::SwapSkill, skills.clone()),
+            ChoiceSlot::new("With", ChoiceKind::SwapSkill, skills),
+            ChoiceSlot::new(
+                "Replace",
+                ChoiceKind::ReplaceTrait,
+                vec!["Old Stunt".to_string()],
+            ),
+            ChoiceSlot::new(
+                "New",
+                ChoiceKind::Trait {
+                    trait_type: TraitType::Stunt,
+                },
+                vec!["New Stunt".to_string()],
+            )
+            .with_details(vec!["Does things".to_string()]),
+        ];
+
+        let choices = preview.choices(&[Some(0), Some(1), Some(0), Some(0)]);
+        assert_eq!(
+            choices,
+            vec![
+                AdvancementChoice::SwapSkills {
+                    first: "Fight".to_string(),
+                    second: "Notice".to_string()
+                },
+                AdvancementChoice::ReplaceTrait {
+                    old: "Old Stunt".to_string(),
+                    name: "New Stunt".to_string(),
+                    trait_type: TraitType::Stunt,
+                    description: "Does things".to_string(),
+                },
+            ]
+        );
+
+        // Without a replacement the stunt is simply gained
+        let choices = preview.choices(&[None, None, None, Some(0)]);
+        assert!(matches!(choices[..], [AdvancementChoice::GainTrait { .. }]));
+    }
+}
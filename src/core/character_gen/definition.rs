@@ -0,0 +1,823 @@
+//! Data-Driven System Definitions
+//!
+//! Describes a game system's character sheet declaratively in YAML or TOML:
+//! attributes and how they are generated, skills, selectable options
+//! (ancestries, classes/playbooks, backgrounds), starting kits and derived
+//! stats computed from formulas. [`DataDrivenGenerator`] turns a definition
+//! into a [`SystemGenerator`](super::SystemGenerator).
+//!
+//! Bundled definitions live in `assets/systems/`. Files in
+//! `<data_dir>/systems/` are read at runtime and replace a bundled or
+//! built-in system with the same ID, so systems can be added or tweaked
+//! without recompiling.
+//!
+//! # Example
+//!
+//! ```yaml
+//! id: mothership
+//! name: Mothership
+//! aliases: ["mothership 1e"]
+//! name_style: modern
+//! attributes:
+//!   - name: Strength
+//!   - name: Combat
+//! generation:
+//!   - method: roll
+//!     dice: "2d10+25"
+//! skills:
+//!   - name: Athletics
+//! classes:
+//!   - name: Marine
+//!     attributes: { Combat: 10 }
+//!     derived: { Fear Save: 20 }
+//!     kit: marine
+//! kits:
+//!   marine:
+//!     - name: Combat Shotgun
+//!       category: Weapon
+//! derived:
+//!   - name: Fear Save
+//!     formula: "2d10 + 10"
+//!   - name: Max Health
+//!     formula: "1d10 + 10"
+//! ```
+//!
+//! # Formulas
+//!
+//! Skill bases, derived stats and `attribute_modifier` are integer
+//! expressions with `+ - * /` (floor division), parentheses, dice (`2d6`),
+//! `min(..)` / `max(..)` and names. Names are matched case-insensitively with
+//! runs of other characters replaced by `_` (`Fear Save` is `fear_save`).
+//! Available names are attributes (`str`) and their modifiers (`str_mod`),
+//! `level`, skills, and derived stats listed earlier. `attribute_modifier`
+//! sees only `value`.
+//!
+//! [`DataDrivenGenerator`]: super::systems::DataDrivenGenerator
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{CharacterGenError, EquipmentCategory, Result, TraitType};
+use crate::core::campaign::dice::DiceNotation;
+
+// ============================================================================
+// Definition Types
+// ============================================================================
+
+/// A complete game system definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemDefinition {
+    /// Identifier, matching the game detector's ID where one exists (e.g. `bitd`).
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Other names accepted when selecting the system.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Highest level; systems without levels leave this unset.
+    #[serde(default)]
+    pub max_level: Option<u32>,
+    #[serde(default)]
+    pub name_style: NameStyle,
+    /// Names to pick from instead of `name_style`.
+    #[serde(default)]
+    pub names: Vec<String>,
+    pub attributes: Vec<AttributeDef>,
+    /// Formula of `value` giving an attribute's modifier; none means 0.
+    #[serde(default)]
+    pub attribute_modifier: Option<String>,
+    /// Generation methods; the first is the default.
+    pub generation: Vec<GenerationMethod>,
+    #[serde(default)]
+    pub skills: Vec<SkillDef>,
+    #[serde(default)]
+    pub races: Vec<OptionDef>,
+    #[serde(default)]
+    pub classes: Vec<OptionDef>,
+    #[serde(default)]
+    pub backgrounds: Vec<OptionDef>,
+    /// Traits every character receives.
+    #[serde(default)]
+    pub traits: Vec<TraitDef>,
+    /// Equipment kits by name. `default` is always given; an option's kit
+    /// (or the kit named after it) is added for the chosen options.
+    #[serde(default)]
+    pub kits: HashMap<String, Vec<KitItem>>,
+    /// Derived stats, evaluated in order.
+    #[serde(default)]
+    pub derived: Vec<DerivedStat>,
+}
+
+/// Source for random character names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameStyle {
+    #[default]
+    Fantasy,
+    Modern,
+    #[serde(rename = "1920s")]
+    Period1920s,
+    Cyberpunk,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeDef {
+    pub name: String,
+    #[serde(default)]
+    pub min: Option<i32>,
+    #[serde(default)]
+    pub max: Option<i32>,
+}
+
+/// How starting attribute values are produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum GenerationMethod {
+    /// Roll dice notation per attribute.
+    Roll {
+        dice: String,
+        /// Different notation for some attributes.
+        #[serde(default)]
+        per_attribute: HashMap<String, String>,
+    },
+    /// Assign a shuffled array of values.
+    Array { values: Vec<i32> },
+    /// Start every attribute at `base` (plus option bonuses) and spend
+    /// `points` at random, raising by `step` up to `max`.
+    PointBuy {
+        points: u32,
+        #[serde(default)]
+        base: i32,
+        #[serde(default = "default_step")]
+        step: i32,
+        #[serde(default)]
+        max: Option<i32>,
+        /// Cost of each step above `base`; empty means every step costs 1.
+        #[serde(default)]
+        costs: Vec<u32>,
+    },
+    /// Fixed starting values.
+    Fixed {
+        #[serde(default)]
+        value: i32,
+        #[serde(default)]
+        per_attribute: HashMap<String, i32>,
+    },
+}
+
+fn default_step() -> i32 {
+    1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillDef {
+    pub name: String,
+    #[serde(default)]
+    pub base: i32,
+    /// Formula replacing `base`, e.g. `dex / 2`.
+    #[serde(default)]
+    pub formula: Option<String>,
+}
+
+/// A selectable race, class or background.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionDef {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Attribute bonuses.
+    #[serde(default)]
+    pub attributes: HashMap<String, i32>,
+    /// Skill bonuses.
+    #[serde(default)]
+    pub skills: HashMap<String, i32>,
+    /// Derived stat bonuses.
+    #[serde(default)]
+    pub derived: HashMap<String, i32>,
+    #[serde(default)]
+    pub traits: Vec<TraitDef>,
+    #[serde(default)]
+    pub kit: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraitDef {
+    pub name: String,
+    /// Trait type; defaults by where the trait is listed.
+    #[serde(default, rename = "type")]
+    pub trait_type: Option<TraitType>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub effect: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KitItem {
+    pub name: String,
+    #[serde(default = "default_category")]
+    pub category: EquipmentCategory,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub stats: HashMap<String, String>,
+}
+
+fn default_category() -> EquipmentCategory {
+    EquipmentCategory::Other
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedStat {
+    pub name: String,
+    pub formula: String,
+}
+
+// ============================================================================
+// Loading and Validation
+// ============================================================================
+
+fn invalid(id: &str, msg: impl std::fmt::Display) -> CharacterGenError {
+    CharacterGenError::InvalidDefinition(format!("{id}: {msg}"))
+}
+
+impl SystemDefinition {
+    /// Parse and validate a YAML definition.
+    pub fn from_yaml_str(yaml: &str) -> Result<Self> {
+        let definition: Self = serde_yaml::from_str(yaml)
+            .map_err(|e| CharacterGenError::InvalidDefinition(e.to_string()))?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// Parse and validate a TOML definition.
+    pub fn from_toml_str(source: &str) -> Result<Self> {
+        let definition: Self = toml::from_str(source)
+            .map_err(|e| CharacterGenError::InvalidDefinition(e.to_string()))?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// Load a `.yaml`/`.yml` or `.toml` definition file.
+    pub fn load_file(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path).map_err(|e| {
+            CharacterGenError::InvalidDefinition(format!("{}: {e}", path.display()))
+        })?;
+        let is_toml = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("toml"));
+        if is_toml {
+            Self::from_toml_str(&source)
+        } else {
+            Self::from_yaml_str(&source)
+        }
+    }
+
+    /// Load every definition file in `dir`, sorted by file name.
+    ///
+    /// Invalid files are logged and skipped; a missing directory yields none.
+    pub fn load_dir(dir: &Path) -> Vec<Self> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+                    ["yaml", "yml", "toml"]
+                        .iter()
+                        .any(|ext| e.eq_ignore_ascii_case(ext))
+                })
+            })
+            .collect();
+        paths.sort();
+
+        let mut definitions = Vec::new();
+        for path in paths {
+            match Self::load_file(&path) {
+                Ok(definition) => definitions.push(definition),
+                Err(e) => log::warn!("Skipping system definition {}: {e}", path.display()),
+            }
+        }
+        definitions
+    }
+
+    /// Check the definition is internally consistent.
+    ///
+    /// Every formula must parse and only use names available at its point of
+    /// evaluation; bonuses must name defined attributes, skills and derived
+    /// stats; kits referenced by options must exist.
+    pub fn validate(&self) -> Result<()> {
+        let id = self.id.as_str();
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(invalid(id, "id must be lowercase letters, digits or '_'"));
+        }
+        if self.name.trim().is_empty() {
+            return Err(invalid(id, "name is empty"));
+        }
+        if self.attributes.is_empty() {
+            return Err(invalid(id, "no attributes defined"));
+        }
+        if self.generation.is_empty() {
+            return Err(invalid(id, "no generation methods defined"));
+        }
+
+        let attributes = unique_keys(id, "attribute", self.attributes.iter().map(|a| &a.name))?;
+        let skills = unique_keys(id, "skill", self.skills.iter().map(|s| &s.name))?;
+        let derived = unique_keys(id, "derived stat", self.derived.iter().map(|d| &d.name))?;
+
+        if let Some(ref modifier) = self.attribute_modifier {
+            let known: HashSet<String> = ["value".to_string()].into();
+            check_formula(id, "attribute_modifier", modifier, &known)?;
+        }
+
+        for method in &self.generation {
+            match method {
+                GenerationMethod::Roll {
+                    dice,
+                    per_attribute,
+                } => {
+                    DiceNotation::parse(dice).map_err(|e| invalid(id, e))?;
+                    for (name, notation) in per_attribute {
+                        require(id, "attribute", name, &attributes)?;
+                        DiceNotation::parse(notation).map_err(|e| invalid(id, e))?;
+                    }
+                }
+                GenerationMethod::Array { values } => {
+                    if values.len() < self.attributes.len() {
+                        return Err(invalid(id, "array has fewer values than attributes"));
+                    }
+                }
+                GenerationMethod::PointBuy {
+                    base,
+                    step,
+                    max,
+                    costs,
+                    ..
+                } => {
+                    if *step <= 0 {
+                        return Err(invalid(id, "point buy step must be positive"));
+                    }
+                    if costs.contains(&0) {
+                        return Err(invalid(id, "point buy costs must be positive"));
+                    }
+                    if max.is_some_and(|max| max < *base) {
+                        return Err(invalid(id, "point buy max is below base"));
+                    }
+                }
+                GenerationMethod::Fixed { per_attribute, .. } => {
+                    for name in per_attribute.keys() {
+                        require(id, "attribute", name, &attributes)?;
+                    }
+                }
+            }
+        }
+
+        // Names visible to formulas grow as evaluation proceeds
+        let mut known: HashSet<String> = attributes
+            .iter()
+            .flat_map(|a| [a.clone(), format!("{a}_mod")])
+            .collect();
+        known.insert("level".to_string());
+        for skill in &self.skills {
+            if let Some(ref formula) = skill.formula {
+                check_formula(id, &skill.name, formula, &known)?;
+            }
+        }
+        known.extend(skills.iter().cloned());
+        for stat in &self.derived {
+            check_formula(id, &stat.name, &stat.formula, &known)?;
+            known.insert(formula_key(&stat.name));
+        }
+
+        for option in self
+            .races
+            .iter()
+            .chain(&self.classes)
+            .chain(&self.backgrounds)
+        {
+            for name in option.attributes.keys() {
+                require(id, "attribute", name, &attributes)?;
+            }
+            for name in option.skills.keys() {
+                require(id, "skill", name, &skills)?;
+            }
+            for name in option.derived.keys() {
+                require(id, "derived stat", name, &derived)?;
+            }
+            if let Some(ref kit) = option.kit {
+                if self.kit(kit).is_none() {
+                    return Err(invalid(
+                        id,
+                        format!("{} uses unknown kit '{kit}'", option.name),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Look up a kit by name (case-insensitive).
+    pub fn kit(&self, name: &str) -> Option<&[KitItem]> {
+        self.kits
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, items)| items.as_slice())
+    }
+}
+
+fn unique_keys<'a>(
+    id: &str,
+    what: &str,
+    names: impl Iterator<Item = &'a String>,
+) -> Result<HashSet<String>> {
+    let mut keys = HashSet::new();
+    for name in names {
+        let key = formula_key(name);
+        if key.is_empty() {
+            return Err(invalid(id, format!("{what} name '{name}' is empty")));
+        }
+        if !keys.insert(key) {
+            return Err(invalid(id, format!("duplicate {what} '{name}'")));
+        }
+    }
+    Ok(keys)
+}
+
+fn require(id: &str, what: &str, name: &str, keys: &HashSet<String>) -> Result<()> {
+    if keys.contains(&formula_key(name)) {
+        Ok(())
+    } else {
+        Err(invalid(id, format!("unknown {what} '{name}'")))
+    }
+}
+
+fn check_formula(id: &str, owner: &str, source: &str, known: &HashSet<String>) -> Result<()> {
+    let formula = Formula::parse(source).map_err(|e| invalid(id, format!("{owner}: {e}")))?;
+    if let Some(name) = formula.variables().into_iter().find(|v| !known.contains(v)) {
+        return Err(invalid(
+            id,
+            format!("{owner}: unknown name '{name}' in '{source}'"),
+        ));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Formulas
+// ============================================================================
+
+/// Normalize a name for use in formulas: lowercase, with runs of
+/// non-alphanumeric characters collapsed to `_` (`Fighting (Brawl)` is
+/// `fighting_brawl`).
+pub fn formula_key(name: &str) -> String {
+    let mut key = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            key.extend(c.to_lowercase());
+        } else if !key.is_empty() && !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    while key.ends_with('_') {
+        key.pop();
+    }
+    key
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Num(i32),
+    Dice { count: u32, sides: u32 },
+    Var(String),
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, char, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(i32),
+    Dice(u32, u32),
+    Ident(String),
+    Op(char),
+}
+
+/// A parsed integer formula.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formula {
+    expr: Expr,
+}
+
+impl Formula {
+    /// Parse a formula.
+    pub fn parse(source: &str) -> std::result::Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.expr()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(format!("unexpected {:?}", parser.tokens[parser.pos]));
+        }
+        Ok(Self { expr })
+    }
+
+    /// Names the formula refers to.
+    pub fn variables(&self) -> Vec<String> {
+        fn walk(expr: &Expr, out: &mut Vec<String>) {
+            match expr {
+                Expr::Var(name) => out.push(name.clone()),
+                Expr::Neg(inner) => walk(inner, out),
+                Expr::Binary(a, _, b) => {
+                    walk(a, out);
+                    walk(b, out);
+                }
+                Expr::Call(_, args) => args.iter().for_each(|a| walk(a, out)),
+                Expr::Num(_) | Expr::Dice { .. } => {}
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.expr, &mut out);
+        out
+    }
+
+    /// Evaluate with `vars` keyed by [`formula_key`], rolling any dice with `rng`.
+    pub fn eval(
+        &self,
+        vars: &HashMap<String, i32>,
+        rng: &mut impl Rng,
+    ) -> std::result::Result<i32, String> {
+        eval(&self.expr, vars, rng)
+    }
+}
+
+fn eval(
+    expr: &Expr,
+    vars: &HashMap<String, i32>,
+    rng: &mut impl Rng,
+) -> std::result::Result<i32, String> {
+    Ok(match expr {
+        Expr::Num(n) => *n,
+        Expr::Dice { count, sides } => (0..*count).map(|_| rng.gen_range(1..=*sides as i32)).sum(),
+        Expr::Var(name) => *vars
+            .get(name)
+            .ok_or_else(|| format!("unknown name '{name}'"))?,
+        Expr::Neg(inner) => eval(inner, vars, rng)?.saturating_neg(),
+        Expr::Binary(a, op, b) => {
+            let (a, b) = (eval(a, vars, rng)?, eval(b, vars, rng)?);
+            match op {
+                '+' => a.saturating_add(b),
+                '-' => a.saturating_sub(b),
+                '*' => a.saturating_mul(b),
+                _ => {
+                    if b == 0 {
+                        return Err("division by zero".to_string());
+                    }
+                    let overflow = || format!("{a} / {b} overflows");
+                    // Floor division, so (9 - 10) / 2 is -1
+                    let quotient = a.checked_div(b).ok_or_else(overflow)?;
+                    let remainder = a.checked_rem(b).ok_or_else(overflow)?;
+                    if remainder != 0 && (a < 0) != (b < 0) {
+                        quotient - 1
+                    } else {
+                        quotient
+                    }
+                }
+            }
+        }
+        Expr::Call(name, args) => {
+            let values = args
+                .iter()
+                .map(|a| eval(a, vars, rng))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            let folded = if name == "min" {
+                values.into_iter().min()
+            } else {
+                values.into_iter().max()
+            };
+            folded.unwrap_or(0)
+        }
+    })
+}
+
+fn tokenize(source: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if "+-*/(),".contains(c) {
+            tokens.push(Token::Op(c));
+            chars.next();
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' {
+                    word.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(classify_word(&word)?);
+        } else {
+            return Err(format!("unexpected character '{c}'"));
+        }
+    }
+    if tokens.is_empty() {
+        return Err("empty formula".to_string());
+    }
+    Ok(tokens)
+}
+
+fn classify_word(word: &str) -> std::result::Result<Token, String> {
+    if word.chars().all(|c| c.is_ascii_digit()) {
+        return word
+            .parse()
+            .map(Token::Num)
+            .map_err(|_| format!("number too large: {word}"));
+    }
+
+    let lower = word.to_lowercase();
+    if let Some((count, sides)) = lower.split_once('d') {
+        let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        if (count.is_empty() || is_digits(count)) && is_digits(sides) {
+            let count: u32 = if count.is_empty() {
+                1
+            } else {
+                count.parse().map_err(|_| format!("bad dice '{word}'"))?
+            };
+            let sides: u32 = sides.parse().map_err(|_| format!("bad dice '{word}'"))?;
+            if !(1..=100).contains(&count) || !(1..=1000).contains(&sides) {
+                return Err(format!("dice out of range: {word}"));
+            }
+            return Ok(Token::Dice(count, sides));
+        }
+    }
+
+    if word.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(format!("invalid name '{word}'"));
+    }
+    Ok(Token::Ident(formula_key(word)))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_op(&self) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(c)) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, op: char) -> std::result::Result<(), String> {
+        if self.peek_op() == Some(op) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected '{op}'"))
+        }
+    }
+
+    fn expr(&mut self) -> std::result::Result<Expr, String> {
+        let mut left = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek_op() {
+            self.pos += 1;
+            left = Expr::Binary(Box::new(left), op, Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> std::result::Result<Expr, String> {
+        let mut left = self.unary()?;
+        while let Some(op @ ('*' | '/')) = self.peek_op() {
+            self.pos += 1;
+            left = Expr::Binary(Box::new(left), op, Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> std::result::Result<Expr, String> {
+        if self.peek_op() == Some('-') {
+            self.pos += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> std::result::Result<Expr, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of formula".to_string())?;
+        self.pos += 1;
+        match token {
+            Token::Num(n) => Ok(Expr::Num(n)),
+            Token::Dice(count, sides) => Ok(Expr::Dice { count, sides }),
+            Token::Op('(') => {
+                let inner = self.expr()?;
+                self.expect(')')?;
+                Ok(inner)
+            }
+            Token::Ident(name) if self.peek_op() == Some('(') => {
+                if name != "min" && name != "max" {
+                    return Err(format!("unknown function '{name}'"));
+                }
+                self.pos += 1;
+                let mut args = vec![self.expr()?];
+                while self.peek_op() == Some(',') {
+                    self.pos += 1;
+                    args.push(self.expr()?);
+                }
+                self.expect(')')?;
+                Ok(Expr::Call(name, args))
+            }
+            Token::Ident(name) => Ok(Expr::Var(name)),
+            Token::Op(c) => Err(format!("unexpected '{c}'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn eval_str(source: &str, vars: &[(&str, i32)]) -> i32 {
+        let vars: HashMap<String, i32> = vars.iter().map(|(k, v)| (k.to_string(), *v)).collect();
+        Formula::parse(source)
+            .unwrap()
+            .eval(&vars, &mut StdRng::seed_from_u64(7))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_formula_evaluation() {
+        assert_eq!(
+            eval_str("(con + siz) / 10", &[("con", 50), ("siz", 65)]),
+            11
+        );
+        assert_eq!(eval_str("(value - 10) / 2", &[("value", 9)]), -1);
+        assert_eq!(eval_str("2 + -3 * 2", &[]), -4);
+        assert_eq!(eval_str("max(1, min(a, 4))", &[("a", 9)]), 4);
+
+        let vars = HashMap::from([("a".to_string(), i32::MIN)]);
+        let divide = |source: &str| {
+            Formula::parse(source)
+                .unwrap()
+                .eval(&vars, &mut StdRng::seed_from_u64(7))
+        };
+        assert_eq!(divide("a / 0").err(), Some("division by zero".to_string()));
+        assert!(divide("a / -1").is_err());
+
+        let rolled = eval_str("1d10 + 10", &[]);
+        assert!((11..=20).contains(&rolled));
+
+        assert_eq!(formula_key("Fighting (Brawl)"), "fighting_brawl");
+        assert_eq!(
+            Formula::parse("Fear Save + STR_mod").err(),
+            Some("unexpected Ident(\"save\")".to_string())
+        );
+        assert!(Formula::parse("floor(3)").is_err());
+        assert!(Formula::parse("3 +").is_err());
+        assert!(Formula::parse("0d6").is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_names() {
+        let yaml = r#"
+id: test
+name: Test
+attributes:
+  - name: Might
+generation:
+  - method: fixed
+    value: 10
+derived:
+  - name: Edge
+    formula: "speed / 2"
+"#;
+        let err = SystemDefinition::from_yaml_str(yaml)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("speed"), "{err}");
+
+        let yaml = yaml.replace("speed / 2", "might / 2");
+        assert!(SystemDefinition::from_yaml_str(&yaml).is_ok());
+    }
+}
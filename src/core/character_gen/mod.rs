@@ -10,18 +10,34 @@
 //! - Dungeon World (PbtA)
 //! - GURPS (Universal)
 //! - Warhammer Fantasy (Grimdark)
+//!
+//! Further systems (Blades in the Dark, Mothership, Traveller, Savage Worlds,
+//! Year Zero, Cypher) are described by YAML definitions in `assets/systems/`
+//! and generated by [`DataDrivenGenerator`]; see [`definition`].
+//!
+//! Generated characters can be progressed afterwards (level up, experience
+//! spends, milestones, skill development); see [`advancement`].
 
 pub mod systems;
+pub mod definition;
+pub mod advancement;
 pub mod backstory;
 pub mod prompts;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use rand::Rng;
 use thiserror::Error;
 
 // Re-export system generators
 pub use systems::*;
+pub use definition::SystemDefinition;
+pub use advancement::{
+    Advancement, AdvancementChoice, AdvancementKind, AdvancementPreview, AdvancementRecord,
+    AdvancementRequest, Milestone,
+};
 
 // ============================================================================
 // Error Types
@@ -43,6 +59,12 @@ pub enum CharacterGenError {
 
     #[error("Backstory generation failed: {0}")]
     BackstoryError(String),
+
+    #[error("Invalid system definition: {0}")]
+    InvalidDefinition(String),
+
+    #[error("Advancement failed: {0}")]
+    AdvancementError(String),
 }
 
 pub type Result<T> = std::result::Result<T, CharacterGenError>;
@@ -68,6 +90,12 @@ pub struct Character {
     pub backstory: Option<String>,
     pub notes: String,
     pub portrait_prompt: Option<String>,
+    /// Unspent experience (systems that buy advances with experience)
+    #[serde(default)]
+    pub experience: u32,
+    /// Advancements applied since creation, oldest first
+    #[serde(default)]
+    pub advancement_history: Vec<AdvancementRecord>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -296,6 +324,21 @@ pub trait SystemGenerator: Send + Sync {
     /// Returns the game system this generator supports
     fn system(&self) -> GameSystem;
 
+    /// Human-readable system name
+    fn display_name(&self) -> String {
+        self.system().display_name().to_string()
+    }
+
+    /// Short description of the system
+    fn description(&self) -> String {
+        SystemInfo::system_description(&self.system())
+    }
+
+    /// Highest character level, or `None` for systems without levels
+    fn max_level(&self) -> Option<u32> {
+        SystemInfo::system_max_level(&self.system())
+    }
+
     /// Generate a character with the given options
     fn generate(&self, options: &GenerationOptions) -> Result<Character>;
 
@@ -319,6 +362,11 @@ pub trait SystemGenerator: Send + Sync {
         // Default implementation accepts all options
         Ok(())
     }
+
+    /// Advancement rules for this system, if characters can be progressed
+    fn advancement(&self) -> Option<&dyn Advancement> {
+        None
+    }
 }
 
 // ============================================================================
@@ -343,14 +391,14 @@ impl SystemInfo {
         let system = generator.system();
         Self {
             id: system.id().to_string(),
-            name: system.display_name().to_string(),
-            description: Self::system_description(&system),
+            name: generator.display_name(),
+            description: generator.description(),
             races: generator.available_races(),
             classes: generator.available_classes(),
             backgrounds: generator.available_backgrounds(),
             attributes: generator.attribute_names(),
-            has_levels: Self::system_has_levels(&system),
-            max_level: Self::system_max_level(&system),
+            has_levels: generator.max_level().is_some(),
+            max_level: generator.max_level(),
         }
     }
 
@@ -370,15 +418,6 @@ impl SystemInfo {
         }
     }
 
-    fn system_has_levels(system: &GameSystem) -> bool {
-        matches!(system,
-            GameSystem::DnD5e |
-            GameSystem::Pathfinder2e |
-            GameSystem::DungeonWorld |
-            GameSystem::Warhammer
-        )
-    }
-
     fn system_max_level(system: &GameSystem) -> Option<u32> {
         match system {
             GameSystem::DnD5e => Some(20),
@@ -397,12 +436,15 @@ impl SystemInfo {
 /// Registry for all system generators
 pub struct GeneratorRegistry {
     generators: HashMap<GameSystem, Box<dyn SystemGenerator>>,
+    /// Lowercased names and aliases of data-driven systems
+    aliases: HashMap<String, GameSystem>,
 }
 
 impl GeneratorRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             generators: HashMap::new(),
+            aliases: HashMap::new(),
         };
 
         // Register all built-in generators
@@ -417,6 +459,13 @@ impl GeneratorRegistry {
         registry.register(Box::new(systems::gurps::GURPSGenerator::new()));
         registry.register(Box::new(systems::warhammer::WarhammerGenerator::new()));
 
+        // Bundled definitions; one with a built-in ID replaces the built-in
+        for definition in crate::core::assets::AssetLoader::load_system_definitions() {
+            if let Err(e) = registry.register_definition(definition) {
+                log::warn!("Skipping bundled system definition: {e}");
+            }
+        }
+
         registry
     }
 
@@ -424,13 +473,45 @@ impl GeneratorRegistry {
         self.generators.insert(generator.system(), generator);
     }
 
+    /// Register a data-driven system, replacing any generator with the same ID.
+    pub fn register_definition(&mut self, definition: SystemDefinition) -> Result<()> {
+        let generator = DataDrivenGenerator::new(definition)?;
+        let system = generator.system();
+        let definition = generator.definition();
+        for name in definition.aliases.iter().chain([&definition.name, &definition.id]) {
+            self.aliases.insert(name.to_lowercase(), system.clone());
+        }
+        self.register(Box::new(generator));
+        Ok(())
+    }
+
+    /// Register every definition in `dir`, returning how many were added.
+    pub fn load_dir(&mut self, dir: &Path) -> usize {
+        let mut count = 0;
+        for definition in SystemDefinition::load_dir(dir) {
+            match self.register_definition(definition) {
+                Ok(()) => count += 1,
+                Err(e) => log::warn!("Skipping system definition in {}: {e}", dir.display()),
+            }
+        }
+        count
+    }
+
+    /// Resolve a system name, including data-driven names and aliases.
+    pub fn resolve(&self, name: &str) -> GameSystem {
+        self.aliases
+            .get(&name.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| GameSystem::from_str(name))
+    }
+
     pub fn get(&self, system: &GameSystem) -> Option<&dyn SystemGenerator> {
         self.generators.get(system).map(|g| g.as_ref())
     }
 
     pub fn generate(&self, options: &GenerationOptions) -> Result<Character> {
         let system = options.system.as_deref()
-            .map(GameSystem::from_str)
+            .map(|name| self.resolve(name))
             .unwrap_or(GameSystem::DnD5e);
 
         let generator = self.get(&system)
@@ -461,14 +542,30 @@ impl Default for GeneratorRegistry {
 // Character Generator Facade (for backward compatibility with commands.rs)
 // ============================================================================
 
+/// Directory of user system definitions, set once at startup
+static USER_SYSTEMS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
 /// Static character generator that uses the internal registry
 pub struct CharacterGenerator;
 
 impl CharacterGenerator {
+    /// Load user system definitions from `dir` (read again on every call, so
+    /// edits apply without restarting). Only the first call has any effect.
+    pub fn set_user_systems_dir(dir: PathBuf) {
+        let _ = USER_SYSTEMS_DIR.set(dir);
+    }
+
+    fn registry() -> GeneratorRegistry {
+        let mut registry = GeneratorRegistry::new();
+        if let Some(dir) = USER_SYSTEMS_DIR.get() {
+            registry.load_dir(dir);
+        }
+        registry
+    }
+
     /// Generate a character using the registry
     pub fn generate(options: &GenerationOptions) -> Result<Character> {
-        let registry = GeneratorRegistry::new();
-        registry.generate(options)
+        Self::registry().generate(options)
     }
 
     /// Get list of supported system names
@@ -481,16 +578,49 @@ impl CharacterGenerator {
 
     /// Get detailed info for all systems
     pub fn list_system_info() -> Vec<SystemInfo> {
-        let registry = GeneratorRegistry::new();
-        registry.list_systems()
+        Self::registry().list_systems()
     }
 
     /// Get info for a specific system
     pub fn get_system_info(system: &str) -> Option<SystemInfo> {
-        let registry = GeneratorRegistry::new();
-        let game_system = GameSystem::from_str(system);
+        let registry = Self::registry();
+        let game_system = registry.resolve(system);
         registry.get_system_info(&game_system)
     }
+
+    /// Preview the next advancement for a character: new features, hit
+    /// points and the choices the player has to make
+    pub fn preview_advancement(
+        character: &Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementPreview> {
+        let registry = Self::registry();
+        Self::advancement_for(&registry, character)?.preview_advancement(character, request)
+    }
+
+    /// Apply an advancement, recording it in the character's history
+    pub fn advance(
+        character: &mut Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementRecord> {
+        let registry = Self::registry();
+        Self::advancement_for(&registry, character)?.advance(character, request)
+    }
+
+    fn advancement_for<'a>(
+        registry: &'a GeneratorRegistry,
+        character: &Character,
+    ) -> Result<&'a dyn Advancement> {
+        registry
+            .get(&character.system)
+            .and_then(|generator| generator.advancement())
+            .ok_or_else(|| {
+                CharacterGenError::AdvancementError(format!(
+                    "{} characters cannot be advanced",
+                    character.system.display_name()
+                ))
+            })
+    }
 }
 
 // ============================================================================
@@ -555,6 +555,8 @@ mod tests {
             backstory: None,
             notes: String::new(),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         }
     }
 
@@ -7,6 +7,11 @@ use crate::core::character_gen::{
     AttributeValue, CharacterTrait, TraitType, Equipment, EquipmentCategory,
     CharacterBackground, Result, random_1920s_name,
 };
+use crate::core::character_gen::advancement::{
+    apply_advancement, advancement_error, check_system, find_key, match_choices, note_value, raise_skill,
+    set_note_value, sorted_keys, Advancement, AdvancementChoice, AdvancementKind, AdvancementPreview,
+    AdvancementRecord, AdvancementRequest, ChoiceKind, ChoiceSlot,
+};
 use rand::Rng;
 use uuid::Uuid;
 use std::collections::HashMap;
@@ -150,6 +155,8 @@ impl SystemGenerator for CallOfCthulhuGenerator {
             backstory: None,
             notes: format!("HP: {}\nSanity: {}\nMagic Points: {}", hp, san, mp),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         })
     }
 
@@ -287,4 +294,118 @@ impl SystemGenerator for CallOfCthulhuGenerator {
 
         equipment
     }
+
+    fn advancement(&self) -> Option<&dyn Advancement> {
+        Some(self)
+    }
+}
+
+// ============================================================================
+// Advancement
+// ============================================================================
+
+/// Skills that can't be improved through experience checks
+const UNDEVELOPED_SKILLS: &[&str] = &["Credit Rating", "Cthulhu Mythos"];
+
+const MAX_SKILL: i32 = 99;
+
+/// Improvement checks per development phase offered by the preview
+const DEVELOPMENT_SLOTS: usize = 6;
+
+impl Advancement for CallOfCthulhuGenerator {
+    fn advancement_kind(&self) -> AdvancementKind {
+        AdvancementKind::Development
+    }
+
+    fn preview_advancement(
+        &self,
+        character: &Character,
+        _request: &AdvancementRequest,
+    ) -> Result<AdvancementPreview> {
+        check_system(character, &GameSystem::CallOfCthulhu)?;
+
+        let skills: Vec<String> = sorted_keys(&character.skills)
+            .into_iter()
+            .filter(|skill| {
+                !UNDEVELOPED_SKILLS.contains(&skill.as_str()) && character.skills[skill] < MAX_SKILL
+            })
+            .collect();
+
+        let mut preview = AdvancementPreview::new(AdvancementKind::Development);
+        for _ in 0..DEVELOPMENT_SLOTS {
+            preview.slots.push(ChoiceSlot::new(
+                "Improvement check",
+                ChoiceKind::Skill { amount: 0 },
+                skills.clone(),
+            ));
+        }
+        preview.notes.push(
+            "Pick skills ticked during play; each improves by 1d10 if 1d100 rolls over it"
+                .to_string(),
+        );
+        preview
+            .notes
+            .push("Reaching 90% in a skill restores 2d6 Sanity".to_string());
+        Ok(preview)
+    }
+
+    fn advance(
+        &self,
+        character: &mut Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementRecord> {
+        let preview = self.preview_advancement(character, request)?;
+        match_choices(&preview, &request.choices)?;
+
+        let mut checked: Vec<&str> = vec![];
+        for choice in &request.choices {
+            if let AdvancementChoice::IncreaseSkill { name, .. } = choice {
+                if checked.iter().any(|c| c.eq_ignore_ascii_case(name)) {
+                    return Err(advancement_error(format!(
+                        "{name} can only be checked once"
+                    )));
+                }
+                checked.push(name);
+            }
+        }
+
+        let mut rng = rand::thread_rng();
+        apply_advancement(character, |character| {
+            let mut record =
+                AdvancementRecord::new(AdvancementKind::Development, character, request);
+
+            for name in checked {
+                let key = find_key(&character.skills, name)
+                    .ok_or_else(|| advancement_error(format!("Unknown skill: {name}")))?;
+                let current = character.skills[&key];
+                let roll = rng.gen_range(1..=100);
+                if roll <= current && roll < 96 {
+                    record
+                        .changes
+                        .push(format!("{key}: rolled {roll} vs {current}, no improvement"));
+                    continue;
+                }
+
+                let gain = rng.gen_range(1..=10).min(MAX_SKILL - current);
+                raise_skill(character, &key, gain, MAX_SKILL)?;
+                record.changes.push(format!(
+                    "{key}: rolled {roll} vs {current}, {current} → {}",
+                    current + gain
+                ));
+
+                if current < 90 && current + gain >= 90 {
+                    let sanity = note_value(&character.notes, "Sanity").unwrap_or(0);
+                    let mythos = character.skills.get("Cthulhu Mythos").copied().unwrap_or(0);
+                    let restored = rng.gen_range(1..=6) + rng.gen_range(1..=6);
+                    let raised = (sanity + restored).min(MAX_SKILL - mythos);
+                    set_note_value(&mut character.notes, "Sanity", raised);
+                    record
+                        .changes
+                        .push(format!("Sanity {sanity} → {raised} for mastering {key}"));
+                }
+            }
+
+            Ok(record)
+        })
+    }
 }
@@ -142,6 +142,8 @@ impl SystemGenerator for CyberpunkGenerator {
             backstory: None,
             notes: "Humanity: 40\nEurodollars: 2550".to_string(),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         })
     }
 
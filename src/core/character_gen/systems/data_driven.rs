@@ -0,0 +1,483 @@
+//! Data-Driven Character Generator
+//!
+//! Generates characters for any system described by a [`SystemDefinition`].
+
+use crate::core::campaign::dice::{DiceNotation, DiceRoller};
+use crate::core::character_gen::definition::{
+    formula_key, Formula, GenerationMethod, KitItem, NameStyle, OptionDef, SystemDefinition,
+    TraitDef,
+};
+use crate::core::character_gen::{
+    random_1920s_name, random_cyberpunk_handle, random_fantasy_name, random_modern_name,
+    AttributeValue, Character, CharacterBackground, CharacterGenError, CharacterTrait, Equipment,
+    GameSystem, GenerationOptions, Result, SystemGenerator, TraitType,
+};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct DataDrivenGenerator {
+    definition: SystemDefinition,
+    modifier: Option<Formula>,
+    skill_formulas: Vec<Option<Formula>>,
+    derived: Vec<Formula>,
+}
+
+fn formula_error(e: String) -> CharacterGenError {
+    CharacterGenError::InvalidDefinition(e)
+}
+
+impl DataDrivenGenerator {
+    /// Build a generator, validating the definition first.
+    pub fn new(definition: SystemDefinition) -> Result<Self> {
+        definition.validate()?;
+        let modifier = definition
+            .attribute_modifier
+            .as_deref()
+            .map(Formula::parse)
+            .transpose()
+            .map_err(formula_error)?;
+        let skill_formulas: Vec<Option<Formula>> = definition
+            .skills
+            .iter()
+            .map(|s| s.formula.as_deref().map(Formula::parse).transpose())
+            .collect::<std::result::Result<_, _>>()
+            .map_err(formula_error)?;
+        let derived: Vec<Formula> = definition
+            .derived
+            .iter()
+            .map(|d| Formula::parse(&d.formula))
+            .collect::<std::result::Result<_, _>>()
+            .map_err(formula_error)?;
+
+        Ok(Self {
+            definition,
+            modifier,
+            skill_formulas,
+            derived,
+        })
+    }
+
+    pub fn definition(&self) -> &SystemDefinition {
+        &self.definition
+    }
+
+    fn random_name(&self, rng: &mut impl Rng) -> String {
+        if let Some(name) = self.definition.names.choose(rng) {
+            return name.clone();
+        }
+        match self.definition.name_style {
+            NameStyle::Fantasy => random_fantasy_name(rng),
+            NameStyle::Modern => random_modern_name(rng),
+            NameStyle::Period1920s => random_1920s_name(rng),
+            NameStyle::Cyberpunk => random_cyberpunk_handle(rng),
+        }
+    }
+
+    /// Resolve a requested option, or pick one at random when none was asked for.
+    fn pick<'a>(
+        choices: &'a [OptionDef],
+        requested: Option<&str>,
+        rng: &mut impl Rng,
+    ) -> (Option<String>, Option<&'a OptionDef>) {
+        match requested {
+            Some(name) => {
+                let option = find_option(choices, name);
+                (
+                    Some(option.map_or(name, |o| o.name.as_str()).to_string()),
+                    option,
+                )
+            }
+            None => {
+                let option = choices.choose(rng);
+                (option.map(|o| o.name.clone()), option)
+            }
+        }
+    }
+
+    /// Generation method for the options: point buy when a budget is given,
+    /// rolling when random stats are requested, otherwise the first listed.
+    fn method(&self, options: &GenerationOptions) -> &GenerationMethod {
+        let methods = &self.definition.generation;
+        let preferred = if options.point_buy.is_some() {
+            methods
+                .iter()
+                .find(|m| matches!(m, GenerationMethod::PointBuy { .. }))
+        } else if options.random_stats {
+            methods
+                .iter()
+                .find(|m| matches!(m, GenerationMethod::Roll { .. }))
+        } else {
+            None
+        };
+        preferred.unwrap_or(&methods[0])
+    }
+
+    fn base_attributes(
+        &self,
+        options: &GenerationOptions,
+        chosen: &[&OptionDef],
+        rng: &mut impl Rng,
+    ) -> Result<Vec<i32>> {
+        let attributes = &self.definition.attributes;
+        let bonuses: Vec<i32> = attributes
+            .iter()
+            .map(|a| option_bonus(chosen, |o| &o.attributes, &a.name))
+            .collect();
+
+        let mut values = match self.method(options) {
+            GenerationMethod::Roll {
+                dice,
+                per_attribute,
+            } => {
+                let roller = DiceRoller::new();
+                let mut values = Vec::with_capacity(attributes.len());
+                for attr in attributes {
+                    let notation = lookup(per_attribute, &attr.name).unwrap_or(dice);
+                    let notation = DiceNotation::parse(notation)
+                        .map_err(|e| CharacterGenError::InvalidDefinition(e.to_string()))?;
+                    values.push(roller.roll_with_rng(&notation, rng).total);
+                }
+                values
+            }
+            GenerationMethod::Array { values } => {
+                let mut values = values.clone();
+                values.shuffle(rng);
+                values.truncate(attributes.len());
+                values
+            }
+            GenerationMethod::Fixed {
+                value,
+                per_attribute,
+            } => attributes
+                .iter()
+                .map(|a| *lookup(per_attribute, &a.name).unwrap_or(value))
+                .collect(),
+            GenerationMethod::PointBuy {
+                points,
+                base,
+                step,
+                max,
+                costs,
+            } => {
+                // Bonuses raise the starting point; points are spent on top
+                let mut values: Vec<i32> = bonuses.iter().map(|b| base + b).collect();
+                let mut steps = vec![0usize; attributes.len()];
+                let mut remaining = options.point_buy.unwrap_or(*points);
+                loop {
+                    let affordable: Vec<(usize, u32)> = (0..attributes.len())
+                        .filter_map(|i| {
+                            let cap = max.or(attributes[i].max).unwrap_or(i32::MAX);
+                            let cost = if costs.is_empty() {
+                                1
+                            } else {
+                                *costs.get(steps[i])?
+                            };
+                            (values[i] + step <= cap && cost <= remaining).then_some((i, cost))
+                        })
+                        .collect();
+                    let Some(&(i, cost)) = affordable.choose(rng) else {
+                        break;
+                    };
+                    values[i] += step;
+                    steps[i] += 1;
+                    remaining -= cost;
+                }
+                return Ok(self.clamp(values));
+            }
+        };
+
+        for (value, bonus) in values.iter_mut().zip(&bonuses) {
+            *value += bonus;
+        }
+        Ok(self.clamp(values))
+    }
+
+    fn clamp(&self, mut values: Vec<i32>) -> Vec<i32> {
+        for (value, attr) in values.iter_mut().zip(&self.definition.attributes) {
+            if let Some(min) = attr.min {
+                *value = (*value).max(min);
+            }
+            if let Some(max) = attr.max {
+                *value = (*value).min(max);
+            }
+        }
+        values
+    }
+
+    fn kit_for(&self, option: &OptionDef) -> &[KitItem] {
+        option
+            .kit
+            .as_deref()
+            .and_then(|k| self.definition.kit(k))
+            .or_else(|| self.definition.kit(&option.name))
+            .unwrap_or(&[])
+    }
+}
+
+fn find_option<'a>(choices: &'a [OptionDef], name: &str) -> Option<&'a OptionDef> {
+    choices.iter().find(|o| o.name.eq_ignore_ascii_case(name))
+}
+
+/// Look up a map entry by normalized name.
+fn lookup<'a, V>(map: &'a HashMap<String, V>, name: &str) -> Option<&'a V> {
+    let key = formula_key(name);
+    map.iter()
+        .find(|(k, _)| formula_key(k) == key)
+        .map(|(_, v)| v)
+}
+
+fn option_bonus(
+    chosen: &[&OptionDef],
+    field: fn(&OptionDef) -> &HashMap<String, i32>,
+    name: &str,
+) -> i32 {
+    chosen.iter().filter_map(|o| lookup(field(o), name)).sum()
+}
+
+fn to_trait(def: &TraitDef, default_type: TraitType) -> CharacterTrait {
+    CharacterTrait {
+        name: def.name.clone(),
+        trait_type: def.trait_type.clone().unwrap_or(default_type),
+        description: def.description.clone(),
+        mechanical_effect: def.effect.clone(),
+    }
+}
+
+fn to_equipment(item: &KitItem) -> Equipment {
+    Equipment {
+        name: item.name.clone(),
+        category: item.category.clone(),
+        description: item.description.clone(),
+        stats: item.stats.clone(),
+    }
+}
+
+impl SystemGenerator for DataDrivenGenerator {
+    fn system(&self) -> GameSystem {
+        GameSystem::from_str(&self.definition.id)
+    }
+
+    fn display_name(&self) -> String {
+        self.definition.name.clone()
+    }
+
+    fn description(&self) -> String {
+        self.definition.description.clone()
+    }
+
+    fn max_level(&self) -> Option<u32> {
+        self.definition.max_level
+    }
+
+    fn generate(&self, options: &GenerationOptions) -> Result<Character> {
+        let mut rng = rand::thread_rng();
+        let def = &self.definition;
+
+        let name = options
+            .name
+            .clone()
+            .unwrap_or_else(|| self.random_name(&mut rng));
+        let (race, race_def) = Self::pick(&def.races, options.race.as_deref(), &mut rng);
+        let (class, class_def) = Self::pick(&def.classes, options.class.as_deref(), &mut rng);
+        let (background_name, background_def) =
+            Self::pick(&def.backgrounds, options.background.as_deref(), &mut rng);
+        let chosen: Vec<&OptionDef> = [race_def, class_def, background_def]
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let level = match def.max_level {
+            Some(max) => options.level.unwrap_or(1).clamp(1, max.max(1)),
+            None => 1,
+        };
+
+        // Attributes, with every value visible to later formulas
+        let mut vars: HashMap<String, i32> = HashMap::new();
+        vars.insert("level".to_string(), level as i32);
+        let mut attributes = HashMap::new();
+        let values = self.base_attributes(options, &chosen, &mut rng)?;
+        for (attr, value) in def.attributes.iter().zip(values) {
+            let modifier = match self.modifier {
+                Some(ref formula) => {
+                    let scope = HashMap::from([("value".to_string(), value)]);
+                    formula.eval(&scope, &mut rng).map_err(formula_error)?
+                }
+                None => 0,
+            };
+            let key = formula_key(&attr.name);
+            vars.insert(key.clone(), value);
+            vars.insert(format!("{key}_mod"), modifier);
+            attributes.insert(
+                attr.name.clone(),
+                AttributeValue {
+                    base: value,
+                    modifier,
+                    temp_bonus: 0,
+                },
+            );
+        }
+
+        // Skills are evaluated against attributes only, then exposed to derived stats
+        let mut skill_values = Vec::with_capacity(def.skills.len());
+        for (skill, formula) in def.skills.iter().zip(&self.skill_formulas) {
+            let base = match formula {
+                Some(formula) => formula.eval(&vars, &mut rng).map_err(formula_error)?,
+                None => skill.base,
+            };
+            skill_values.push(base + option_bonus(&chosen, |o| &o.skills, &skill.name));
+        }
+        let mut skills = HashMap::new();
+        for (skill, value) in def.skills.iter().zip(skill_values) {
+            vars.insert(formula_key(&skill.name), value);
+            skills.insert(skill.name.clone(), value);
+        }
+
+        let mut notes = Vec::with_capacity(def.derived.len());
+        for (stat, formula) in def.derived.iter().zip(&self.derived) {
+            let value = formula.eval(&vars, &mut rng).map_err(formula_error)?
+                + option_bonus(&chosen, |o| &o.derived, &stat.name);
+            vars.insert(formula_key(&stat.name), value);
+            notes.push(format!("{}: {}", stat.name, value));
+        }
+
+        let mut traits: Vec<CharacterTrait> = def
+            .traits
+            .iter()
+            .map(|t| to_trait(t, TraitType::Background))
+            .collect();
+        for (option, trait_type) in [
+            (race_def, TraitType::Racial),
+            (class_def, TraitType::Class),
+            (background_def, TraitType::Background),
+        ] {
+            let Some(option) = option else { continue };
+            traits.push(CharacterTrait {
+                name: option.name.clone(),
+                trait_type: trait_type.clone(),
+                description: option.description.clone(),
+                mechanical_effect: None,
+            });
+            traits.extend(
+                option
+                    .traits
+                    .iter()
+                    .map(|t| to_trait(t, trait_type.clone())),
+            );
+        }
+
+        let equipment = if options.include_equipment {
+            let mut equipment = self.starting_equipment(class.as_deref());
+            for option in [race_def, background_def].into_iter().flatten() {
+                equipment.extend(self.kit_for(option).iter().map(to_equipment));
+            }
+            equipment
+        } else {
+            vec![]
+        };
+
+        let background = CharacterBackground {
+            origin: background_name
+                .clone()
+                .or_else(|| race.clone())
+                .unwrap_or_default(),
+            occupation: class.clone(),
+            motivation: String::new(),
+            connections: vec![],
+            secrets: vec![],
+            history: background_def
+                .map(|b| b.description.clone())
+                .unwrap_or_default(),
+        };
+
+        Ok(Character {
+            id: Uuid::new_v4().to_string(),
+            name,
+            system: self.system(),
+            concept: options
+                .concept
+                .clone()
+                .or_else(|| class.clone())
+                .unwrap_or_else(|| def.name.clone()),
+            race,
+            class,
+            level,
+            attributes,
+            skills,
+            traits,
+            equipment,
+            background,
+            backstory: None,
+            notes: notes.join("\n"),
+            portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
+        })
+    }
+
+    fn available_races(&self) -> Vec<String> {
+        self.definition
+            .races
+            .iter()
+            .map(|o| o.name.clone())
+            .collect()
+    }
+
+    fn available_classes(&self) -> Vec<String> {
+        self.definition
+            .classes
+            .iter()
+            .map(|o| o.name.clone())
+            .collect()
+    }
+
+    fn available_backgrounds(&self) -> Vec<String> {
+        self.definition
+            .backgrounds
+            .iter()
+            .map(|o| o.name.clone())
+            .collect()
+    }
+
+    fn attribute_names(&self) -> Vec<String> {
+        self.definition
+            .attributes
+            .iter()
+            .map(|a| a.name.clone())
+            .collect()
+    }
+
+    fn starting_equipment(&self, class: Option<&str>) -> Vec<Equipment> {
+        let mut items: Vec<Equipment> = self
+            .definition
+            .kit("default")
+            .unwrap_or(&[])
+            .iter()
+            .map(to_equipment)
+            .collect();
+        if let Some(option) = class.and_then(|c| find_option(&self.definition.classes, c)) {
+            items.extend(self.kit_for(option).iter().map(to_equipment));
+        }
+        items
+    }
+
+    fn validate_options(&self, options: &GenerationOptions) -> Result<()> {
+        let def = &self.definition;
+        for (what, choices, requested) in [
+            ("race", &def.races, &options.race),
+            ("class", &def.classes, &options.class),
+            ("background", &def.backgrounds, &options.background),
+        ] {
+            if let Some(name) = requested {
+                if !choices.is_empty() && find_option(choices, name).is_none() {
+                    return Err(CharacterGenError::InvalidOption(format!(
+                        "Unknown {what} '{name}' for {}",
+                        def.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -7,6 +7,11 @@ use crate::core::character_gen::{
     AttributeValue, CharacterTrait, TraitType, Equipment, EquipmentCategory,
     CharacterBackground, Result, random_fantasy_name,
 };
+use crate::core::character_gen::advancement::{
+    apply_advancement, advancement_error, check_system, find_key, gain_trait, has_trait, merge_features,
+    new_features, note_value, raise_attribute, set_note_value, Advancement, AdvancementChoice,
+    AdvancementKind, AdvancementPreview, AdvancementRecord, AdvancementRequest, ChoiceKind, ChoiceSlot,
+};
 use rand::Rng;
 use uuid::Uuid;
 use std::collections::HashMap;
@@ -542,6 +547,8 @@ impl SystemGenerator for DnD5eGenerator {
             backstory: None,
             notes: String::new(),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         })
     }
 
@@ -802,6 +809,296 @@ impl SystemGenerator for DnD5eGenerator {
 
         equipment
     }
+
+    fn advancement(&self) -> Option<&dyn Advancement> {
+        Some(self)
+    }
+}
+
+// ============================================================================
+// Advancement
+// ============================================================================
+
+/// Feats offered in place of an ability score improvement
+const FEATS: &[(&str, &str)] = &[
+    (
+        "Alert",
+        "+5 to initiative and you can't be surprised while conscious",
+    ),
+    (
+        "Great Weapon Master",
+        "Take -5 to hit with a heavy weapon for +10 damage",
+    ),
+    ("Lucky", "Three luck points per long rest to reroll d20s"),
+    (
+        "Mobile",
+        "+10 ft. speed and no opportunity attacks from creatures you attack",
+    ),
+    ("Observant", "+5 to passive Perception and Investigation"),
+    (
+        "Sentinel",
+        "Creatures hit by your opportunity attacks stop moving",
+    ),
+    (
+        "Sharpshooter",
+        "Take -5 to hit with a ranged weapon for +10 damage, ignore cover",
+    ),
+    ("Tough", "Hit point maximum increases by 2 per level"),
+    (
+        "War Caster",
+        "Advantage on concentration saves, cast spells as opportunity attacks",
+    ),
+];
+
+const MAX_ABILITY_SCORE: i32 = 20;
+
+impl DnD5eGenerator {
+    fn hit_die(class: &str) -> i32 {
+        match class.to_lowercase().as_str() {
+            "barbarian" => 12,
+            "fighter" | "paladin" | "ranger" => 10,
+            "sorcerer" | "wizard" => 6,
+            _ => 8,
+        }
+    }
+
+    fn proficiency_bonus(level: u32) -> i32 {
+        2 + (level.max(1) as i32 - 1) / 4
+    }
+
+    fn has_ability_score_improvement(class: &str, level: u32) -> bool {
+        matches!(level, 4 | 8 | 12 | 16 | 19)
+            || match class.to_lowercase().as_str() {
+                "fighter" => matches!(level, 6 | 14),
+                "rogue" => level == 10,
+                _ => false,
+            }
+    }
+
+    fn con_modifier(character: &Character) -> i32 {
+        find_key(&character.attributes, "Constitution")
+            .map(|key| character.attributes[&key].total_modifier())
+            .unwrap_or(0)
+    }
+
+    /// Hit point maximum from the notes, or the fixed-average total for the
+    /// character's level if it was never recorded
+    fn hit_point_maximum(character: &Character, class: &str) -> i32 {
+        note_value(&character.notes, "HP").unwrap_or_else(|| {
+            let die = Self::hit_die(class);
+            let con = Self::con_modifier(character);
+            let later_levels = character.level.saturating_sub(1) as i32;
+            let tough = if has_trait(character, "Tough") {
+                2 * character.level as i32
+            } else {
+                0
+            };
+            (die + con).max(1) + later_levels * (die / 2 + 1 + con).max(1) + tough
+        })
+    }
+
+    fn class_name(character: &Character) -> String {
+        character
+            .class
+            .clone()
+            .unwrap_or_else(|| "Fighter".to_string())
+    }
+}
+
+impl Advancement for DnD5eGenerator {
+    fn advancement_kind(&self) -> AdvancementKind {
+        AdvancementKind::LevelUp
+    }
+
+    fn preview_advancement(
+        &self,
+        character: &Character,
+        _request: &AdvancementRequest,
+    ) -> Result<AdvancementPreview> {
+        check_system(character, &GameSystem::DnD5e)?;
+        let max_level = self.max_level().unwrap_or(20);
+        if character.level >= max_level {
+            return Err(advancement_error(format!(
+                "{} is already level {}",
+                character.name, max_level
+            )));
+        }
+
+        let class = Self::class_name(character);
+        let next = character.level + 1;
+        let die = Self::hit_die(&class);
+        let con = Self::con_modifier(character);
+
+        let mut preview = AdvancementPreview::new(AdvancementKind::LevelUp);
+        preview.next_level = Some(next);
+        preview.features = new_features(character, Self::get_class_traits(&class, next));
+        preview.hit_points = Some(format!(
+            "d{} {:+} (average {})",
+            die,
+            con,
+            (die / 2 + 1 + con).max(1)
+        ));
+        preview.hit_die = Some(die);
+
+        if Self::proficiency_bonus(next) > Self::proficiency_bonus(character.level) {
+            preview.notes.push(format!(
+                "Proficiency bonus rises to +{}",
+                Self::proficiency_bonus(next)
+            ));
+        }
+
+        if Self::has_ability_score_improvement(&class, next) {
+            let abilities = self.attribute_names();
+            preview.slots.push(ChoiceSlot::new(
+                "Ability +1",
+                ChoiceKind::Attribute { amount: 1 },
+                abilities.clone(),
+            ));
+            preview.slots.push(ChoiceSlot::new(
+                "Ability +1",
+                ChoiceKind::Attribute { amount: 1 },
+                abilities,
+            ));
+
+            let feats: Vec<&(&str, &str)> = FEATS
+                .iter()
+                .filter(|(name, _)| !has_trait(character, name))
+                .collect();
+            preview.slots.push(
+                ChoiceSlot::new(
+                    "Feat",
+                    ChoiceKind::Trait {
+                        trait_type: TraitType::Feat,
+                    },
+                    feats.iter().map(|(name, _)| name.to_string()).collect(),
+                )
+                .with_details(
+                    feats
+                        .iter()
+                        .map(|(_, description)| description.to_string())
+                        .collect(),
+                ),
+            );
+            preview
+                .notes
+                .push("Ability Score Improvement: two ability increases or one feat".to_string());
+        }
+
+        Ok(preview)
+    }
+
+    fn advance(
+        &self,
+        character: &mut Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementRecord> {
+        let preview = self.preview_advancement(character, request)?;
+        let next = character.level + 1;
+        let class = Self::class_name(character);
+        let improvement = !preview.slots.is_empty();
+
+        let mut points = 0;
+        let mut feats = 0;
+        for choice in &request.choices {
+            match choice {
+                AdvancementChoice::IncreaseAttribute { amount, .. }
+                    if improvement && *amount > 0 =>
+                {
+                    points += amount
+                }
+                AdvancementChoice::GainTrait {
+                    name,
+                    trait_type: TraitType::Feat,
+                    ..
+                } if improvement => {
+                    if !FEATS
+                        .iter()
+                        .any(|(feat, _)| feat.eq_ignore_ascii_case(name))
+                    {
+                        return Err(advancement_error(format!("Unknown feat: {name}")));
+                    }
+                    feats += 1;
+                }
+                other => {
+                    return Err(advancement_error(format!(
+                        "{} is not available at level {}",
+                        other.describe(),
+                        next
+                    )));
+                }
+            }
+        }
+        if improvement && !matches!((points, feats), (2, 0) | (0, 1)) {
+            return Err(advancement_error(
+                "An ability score improvement takes two ability increases or one feat",
+            ));
+        }
+
+        let rolled = if request.roll_hit_points {
+            Some(rand::thread_rng().gen_range(1..=Self::hit_die(&class)))
+        } else {
+            None
+        };
+
+        apply_advancement(character, |character| {
+            let mut record = AdvancementRecord::new(AdvancementKind::LevelUp, character, request);
+            let hit_points = Self::hit_point_maximum(character, &class);
+            let con_before = Self::con_modifier(character);
+            let tough_before = has_trait(character, "Tough");
+
+            // Improvements apply first so a Constitution increase counts this level
+            for choice in &request.choices {
+                let change = match choice {
+                    AdvancementChoice::IncreaseAttribute { name, amount } => raise_attribute(
+                        character,
+                        name,
+                        *amount,
+                        MAX_ABILITY_SCORE,
+                        AttributeValue::new,
+                    )?,
+                    AdvancementChoice::GainTrait {
+                        name, description, ..
+                    } => {
+                        let (feat, text) = FEATS
+                            .iter()
+                            .find(|(feat, _)| feat.eq_ignore_ascii_case(name))
+                            .copied()
+                            .unwrap_or((name.as_str(), description.as_str()));
+                        gain_trait(character, feat, TraitType::Feat, text, None)?
+                    }
+                    _ => continue,
+                };
+                record.changes.push(change);
+            }
+
+            let die = Self::hit_die(&class);
+            let con = Self::con_modifier(character);
+            let mut gained = (rolled.unwrap_or(die / 2 + 1) + con).max(1);
+            // Constitution modifier increases apply to earlier levels too
+            gained += (con - con_before) * character.level as i32;
+            if has_trait(character, "Tough") {
+                gained += if tough_before { 2 } else { 2 * next as i32 };
+            }
+
+            character.level = next;
+            set_note_value(&mut character.notes, "HP", hit_points + gained);
+            set_note_value(
+                &mut character.notes,
+                "Proficiency Bonus",
+                format!("+{}", Self::proficiency_bonus(next)),
+            );
+            record
+                .changes
+                .push(format!("HP {} → {}", hit_points, hit_points + gained));
+            record.changes.extend(merge_features(
+                character,
+                Self::get_class_traits(&class, next),
+            ));
+            record.to_level = next;
+            record.hit_points_gained = Some(gained);
+            Ok(record)
+        })
+    }
 }
 
 #[cfg(test)]
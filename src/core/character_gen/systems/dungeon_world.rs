@@ -304,6 +304,8 @@ impl SystemGenerator for DungeonWorldGenerator {
             backstory: None,
             notes: format!("HP: {}\nArmor: 0\nLoad: 9\nXP: 0/{}", hp, level + 7),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         })
     }
 
@@ -7,10 +7,26 @@ use crate::core::character_gen::{
     AttributeValue, CharacterTrait, TraitType, Equipment, EquipmentCategory,
     CharacterBackground, Result, random_fantasy_name, random_modern_name,
 };
+use crate::core::character_gen::advancement::{
+    apply_advancement, advancement_error, check_system, find_key, gain_trait, has_trait, match_choices,
+    note_value, raise_skill, set_note_value, sorted_keys, Advancement, AdvancementChoice, AdvancementKind,
+    AdvancementPreview, AdvancementRecord, AdvancementRequest, ChoiceKind, ChoiceSlot, Milestone,
+};
 use rand::Rng;
 use uuid::Uuid;
 use std::collections::HashMap;
 
+/// Stunts offered at creation and when advancing
+const STUNT_TEMPLATES: &[(&str, &str)] = &[
+    ("Combat Veteran", "Because I'm a Combat Veteran, I get +2 to Fight when defending against multiple opponents"),
+    ("Quick Reflexes", "Because of my Quick Reflexes, I get +2 to Quick when avoiding sudden danger"),
+    ("Silver Tongue", "Because I have a Silver Tongue, I get +2 to Deceive when first meeting someone"),
+    ("Academic Expert", "Because I'm an Academic Expert, I get +2 to Lore when researching occult topics"),
+    ("Street Smart", "Because I'm Street Smart, I get +2 to Contacts when seeking information in urban areas"),
+    ("Danger Sense", "Because of my Danger Sense, I can use Notice to defend against ambushes"),
+    ("Tough as Nails", "Because I'm Tough as Nails, once per session I can reduce a physical consequence by one severity"),
+];
+
 pub struct FateCoreGenerator;
 
 impl FateCoreGenerator {
@@ -119,25 +135,15 @@ impl FateCoreGenerator {
     }
 
     fn generate_stunts(rng: &mut impl Rng) -> Vec<CharacterTrait> {
-        let stunt_templates = [
-            ("Combat Veteran", "Because I'm a Combat Veteran, I get +2 to Fight when defending against multiple opponents"),
-            ("Quick Reflexes", "Because of my Quick Reflexes, I get +2 to Quick when avoiding sudden danger"),
-            ("Silver Tongue", "Because I have a Silver Tongue, I get +2 to Deceive when first meeting someone"),
-            ("Academic Expert", "Because I'm an Academic Expert, I get +2 to Lore when researching occult topics"),
-            ("Street Smart", "Because I'm Street Smart, I get +2 to Contacts when seeking information in urban areas"),
-            ("Danger Sense", "Because of my Danger Sense, I can use Notice to defend against ambushes"),
-            ("Tough as Nails", "Because I'm Tough as Nails, once per session I can reduce a physical consequence by one severity"),
-        ];
-
         // Pick 3 random stunts
-        let mut indices: Vec<usize> = (0..stunt_templates.len()).collect();
+        let mut indices: Vec<usize> = (0..STUNT_TEMPLATES.len()).collect();
         for i in (1..indices.len()).rev() {
             let j = rng.gen_range(0..=i);
             indices.swap(i, j);
         }
 
         indices.iter().take(3).map(|&i| {
-            let (name, desc) = stunt_templates[i];
+            let (name, desc) = STUNT_TEMPLATES[i];
             CharacterTrait {
                 name: name.to_string(),
                 trait_type: TraitType::Stunt,
@@ -207,6 +213,8 @@ impl SystemGenerator for FateCoreGenerator {
             backstory: None,
             notes: "Fate Points: 3\nRefresh: 3\nStress: [1][2][3]".to_string(),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         })
     }
 
@@ -262,4 +270,218 @@ impl SystemGenerator for FateCoreGenerator {
             },
         ]
     }
+
+    fn advancement(&self) -> Option<&dyn Advancement> {
+        Some(self)
+    }
+}
+
+// ============================================================================
+// Advancement
+// ============================================================================
+
+const MAX_SKILL_RATING: i32 = 5;
+
+impl FateCoreGenerator {
+    /// Whether raising `skill` by one keeps the skill pyramid intact: at
+    /// every rating above +1 there must be no more skills than at the one below
+    fn pyramid_allows(skills: &HashMap<String, i32>, skill: &str) -> bool {
+        let mut raised = skills.clone();
+        match raised.get_mut(skill) {
+            Some(rating) if *rating < MAX_SKILL_RATING => *rating += 1,
+            _ => return false,
+        }
+        let count = |rating: i32| raised.values().filter(|&&r| r == rating).count();
+        (2..=MAX_SKILL_RATING).all(|rating| count(rating) <= count(rating - 1))
+    }
+
+    fn stunts(character: &Character) -> Vec<String> {
+        character
+            .traits
+            .iter()
+            .filter(|t| t.trait_type == TraitType::Stunt)
+            .map(|t| t.name.clone())
+            .collect()
+    }
+}
+
+impl Advancement for FateCoreGenerator {
+    fn advancement_kind(&self) -> AdvancementKind {
+        AdvancementKind::Milestone
+    }
+
+    fn preview_advancement(
+        &self,
+        character: &Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementPreview> {
+        check_system(character, &GameSystem::FateCore)?;
+        let milestone = request.milestone.unwrap_or_default();
+
+        let skills = sorted_keys(&character.skills);
+        let new_stunts: Vec<&(&str, &str)> = STUNT_TEMPLATES
+            .iter()
+            .filter(|(name, _)| !has_trait(character, name))
+            .collect();
+
+        let mut preview = AdvancementPreview::new(AdvancementKind::Milestone);
+        preview.slots.push(ChoiceSlot::new(
+            "Swap skill",
+            ChoiceKind::SwapSkill,
+            skills.clone(),
+        ));
+        preview.slots.push(ChoiceSlot::new(
+            "…with skill",
+            ChoiceKind::SwapSkill,
+            skills.clone(),
+        ));
+        preview.slots.push(ChoiceSlot::new(
+            "Replace stunt",
+            ChoiceKind::ReplaceTrait,
+            Self::stunts(character),
+        ));
+        preview.slots.push(
+            ChoiceSlot::new(
+                "New stunt",
+                ChoiceKind::Trait {
+                    trait_type: TraitType::Stunt,
+                },
+                new_stunts
+                    .iter()
+                    .map(|(name, _)| name.to_string())
+                    .collect(),
+            )
+            .with_details(
+                new_stunts
+                    .iter()
+                    .map(|(_, description)| description.to_string())
+                    .collect(),
+            ),
+        );
+        preview
+            .notes
+            .push("A new stunt without a replacement costs 1 Refresh".to_string());
+
+        if milestone != Milestone::Minor {
+            let raisable: Vec<String> = skills
+                .into_iter()
+                .filter(|skill| Self::pyramid_allows(&character.skills, skill))
+                .collect();
+            preview.slots.push(
+                ChoiceSlot::new("Skill +1", ChoiceKind::Skill { amount: 1 }, raisable).required(),
+            );
+        }
+        if milestone == Milestone::Major {
+            preview.notes.push("Refresh increases by 1".to_string());
+        }
+
+        Ok(preview)
+    }
+
+    fn advance(
+        &self,
+        character: &mut Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementRecord> {
+        let preview = self.preview_advancement(character, request)?;
+        match_choices(&preview, &request.choices)?;
+        let milestone = request.milestone.unwrap_or_default();
+        if milestone == Milestone::Minor && request.choices.is_empty() {
+            return Err(advancement_error(
+                "A minor milestone needs a skill swap or a stunt change",
+            ));
+        }
+
+        apply_advancement(character, |character| {
+            let mut record = AdvancementRecord::new(AdvancementKind::Milestone, character, request);
+            record.milestone = Some(milestone);
+            let mut refresh = note_value(&character.notes, "Refresh").unwrap_or(3);
+
+            if milestone == Milestone::Major {
+                refresh += 1;
+                record
+                    .changes
+                    .push(format!("Refresh {} → {}", refresh - 1, refresh));
+            }
+
+            for choice in &request.choices {
+                let change = match choice {
+                    AdvancementChoice::SwapSkills { first, second } => {
+                        let (Some(a), Some(b)) = (
+                            find_key(&character.skills, first),
+                            find_key(&character.skills, second),
+                        ) else {
+                            return Err(advancement_error(format!(
+                                "Unknown skills: {first}, {second}"
+                            )));
+                        };
+                        if a == b {
+                            return Err(advancement_error("Pick two different skills to swap"));
+                        }
+                        let (rating_a, rating_b) = (character.skills[&a], character.skills[&b]);
+                        character.skills.insert(a.clone(), rating_b);
+                        character.skills.insert(b.clone(), rating_a);
+                        format!("Swapped {a} ({rating_a:+}) and {b} ({rating_b:+})")
+                    }
+                    AdvancementChoice::IncreaseSkill { name, amount } => {
+                        if !Self::pyramid_allows(&character.skills, name) {
+                            return Err(advancement_error(format!(
+                                "Raising {name} would break the skill pyramid"
+                            )));
+                        }
+                        raise_skill(character, name, *amount, MAX_SKILL_RATING)?
+                    }
+                    AdvancementChoice::ReplaceTrait { old, name, .. } => {
+                        let description = STUNT_TEMPLATES
+                            .iter()
+                            .find(|(stunt, _)| stunt.eq_ignore_ascii_case(name))
+                            .map(|(_, description)| description.to_string())
+                            .ok_or_else(|| advancement_error(format!("Unknown stunt: {name}")))?;
+                        character
+                            .traits
+                            .retain(|t| !t.name.eq_ignore_ascii_case(old));
+                        gain_trait(
+                            character,
+                            name,
+                            TraitType::Stunt,
+                            &description,
+                            Some("+2 bonus or special effect".to_string()),
+                        )?;
+                        format!("Replaced {old} with {name}")
+                    }
+                    AdvancementChoice::GainTrait { name, .. } => {
+                        if refresh <= 1 {
+                            return Err(advancement_error(
+                                "Not enough Refresh to buy another stunt",
+                            ));
+                        }
+                        let description = STUNT_TEMPLATES
+                            .iter()
+                            .find(|(stunt, _)| stunt.eq_ignore_ascii_case(name))
+                            .map(|(_, description)| description.to_string())
+                            .ok_or_else(|| advancement_error(format!("Unknown stunt: {name}")))?;
+                        refresh -= 1;
+                        gain_trait(
+                            character,
+                            name,
+                            TraitType::Stunt,
+                            &description,
+                            Some("+2 bonus or special effect".to_string()),
+                        )?;
+                        format!("Bought {name} for 1 Refresh")
+                    }
+                    other => {
+                        return Err(advancement_error(format!(
+                            "{} is not available",
+                            other.describe()
+                        )))
+                    }
+                };
+                record.changes.push(change);
+            }
+
+            set_note_value(&mut character.notes, "Refresh", refresh);
+            Ok(record)
+        })
+    }
 }
@@ -227,6 +227,8 @@ impl SystemGenerator for GURPSGenerator {
             backstory: None,
             notes: format!("HP: {}\nFP: {}\nPoint Value: {}", hp, fp, options.point_buy.unwrap_or(100)),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         })
     }
 
@@ -1,6 +1,7 @@
 //! System-Specific Character Generators
 //!
 //! Each module implements the SystemGenerator trait for a specific TTRPG system.
+//! `data_driven` implements it for any system loaded from a definition file.
 
 pub mod dnd5e;
 pub mod pf2e;
@@ -12,6 +13,7 @@ pub mod wod;
 pub mod dungeon_world;
 pub mod gurps;
 pub mod warhammer;
+pub mod data_driven;
 
 // Re-exports for convenience
 pub use dnd5e::DnD5eGenerator;
@@ -24,3 +26,4 @@ pub use wod::WorldOfDarknessGenerator;
 pub use dungeon_world::DungeonWorldGenerator;
 pub use gurps::GURPSGenerator;
 pub use warhammer::WarhammerGenerator;
+pub use data_driven::DataDrivenGenerator;
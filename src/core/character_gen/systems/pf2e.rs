@@ -8,6 +8,12 @@ use crate::core::character_gen::{
     AttributeValue, CharacterTrait, TraitType, Equipment, EquipmentCategory,
     CharacterBackground, Result, random_fantasy_name,
 };
+use crate::core::character_gen::advancement::{
+    apply_advancement, advancement_error, check_system, find_key, gain_trait, has_trait, match_choices,
+    merge_features, new_features, note_value, raise_attribute, raise_skill, set_note_value, sorted_keys,
+    Advancement, AdvancementChoice, AdvancementKind, AdvancementPreview, AdvancementRecord,
+    AdvancementRequest, ChoiceKind, ChoiceSlot,
+};
 use rand::Rng;
 use uuid::Uuid;
 use std::collections::HashMap;
@@ -420,6 +426,8 @@ impl SystemGenerator for Pathfinder2eGenerator {
             backstory: None,
             notes: String::new(),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         })
     }
 
@@ -650,6 +658,434 @@ impl SystemGenerator for Pathfinder2eGenerator {
 
         equipment
     }
+
+    fn advancement(&self) -> Option<&dyn Advancement> {
+        Some(self)
+    }
+}
+
+// ============================================================================
+// Advancement
+// ============================================================================
+
+const GENERAL_FEATS: &[&str] = &[
+    "Adopted Ancestry",
+    "Armor Proficiency",
+    "Canny Acumen",
+    "Diehard",
+    "Feather Step",
+    "Fleet",
+    "Incredible Initiative",
+    "Ride",
+    "Toughness",
+];
+
+const SKILL_FEATS: &[&str] = &[
+    "Assurance",
+    "Bargain Hunter",
+    "Battle Medicine",
+    "Cat Fall",
+    "Courtly Graces",
+    "Experienced Tracker",
+    "Intimidating Glare",
+    "Quick Jump",
+    "Recognize Spell",
+    "Sign Language",
+    "Titan Wrestler",
+    "Trick Magic Item",
+];
+
+/// Multiclass dedications, available to every class as class feats
+const DEDICATION_FEATS: &[&str] = &[
+    "Bard Dedication",
+    "Champion Dedication",
+    "Cleric Dedication",
+    "Fighter Dedication",
+    "Rogue Dedication",
+    "Wizard Dedication",
+];
+
+const MAX_ATTRIBUTE_SCORE: i32 = 24;
+
+impl Pathfinder2eGenerator {
+    /// Hit points per level before the Constitution modifier
+    fn class_hit_points(class: &str) -> i32 {
+        match class.to_lowercase().as_str() {
+            "barbarian" => 12,
+            "champion" | "fighter" | "gunslinger" | "monk" | "ranger" | "summoner"
+            | "swashbuckler" => 10,
+            "psychic" | "sorcerer" | "witch" | "wizard" => 6,
+            _ => 8,
+        }
+    }
+
+    fn ancestry_hit_points(ancestry: &str) -> i32 {
+        match ancestry.to_lowercase().as_str() {
+            "dwarf" | "orc" | "automaton" => 10,
+            "elf" | "goblin" | "halfling" | "sprite" | "kobold" => 6,
+            _ => 8,
+        }
+    }
+
+    fn class_feats(class: &str) -> Vec<&'static str> {
+        let mut feats = match class.to_lowercase().as_str() {
+            "fighter" => vec![
+                "Aggressive Block",
+                "Brutish Shove",
+                "Combat Grab",
+                "Dueling Parry",
+                "Intimidating Strike",
+                "Lunge",
+            ],
+            "wizard" => vec![
+                "Cantrip Expansion",
+                "Conceal Spell",
+                "Enhanced Familiar",
+                "Linked Focus",
+                "Silent Spell",
+            ],
+            "rogue" => vec![
+                "Brutal Beating",
+                "Distracting Feint",
+                "Minor Magic",
+                "Mobility",
+                "Quick Draw",
+                "Unbalancing Blow",
+            ],
+            "cleric" => vec![
+                "Cantrip Expansion",
+                "Communal Healing",
+                "Emblazon Armament",
+                "Sap Life",
+                "Turn Undead",
+            ],
+            "champion" => vec![
+                "Divine Grace",
+                "Dragonslayer Oath",
+                "Fiendsbane Oath",
+                "Shining Oath",
+                "Vengeful Oath",
+            ],
+            "barbarian" => vec![
+                "Acute Vision",
+                "Raging Intimidation",
+                "Raging Thrower",
+                "Second Wind",
+                "Shake It Off",
+            ],
+            "ranger" => vec![
+                "Favored Terrain",
+                "Hunter's Aim",
+                "Monster Warden",
+                "Quick Draw",
+                "Wild Empathy",
+            ],
+            "bard" => vec![
+                "Cantrip Expansion",
+                "Esoteric Polymath",
+                "Inspire Competence",
+                "Loremaster's Etude",
+                "Multifarious Muse",
+            ],
+            _ => vec![],
+        };
+        let own = format!("{} dedication", class.to_lowercase());
+        feats.extend(
+            DEDICATION_FEATS
+                .iter()
+                .filter(|feat| feat.to_lowercase() != own),
+        );
+        feats
+    }
+
+    fn ancestry_feats(ancestry: &str) -> &'static [&'static str] {
+        match ancestry.to_lowercase().as_str() {
+            "human" => &[
+                "Adaptive Adept",
+                "Clever Improviser",
+                "Cooperative Soul",
+                "General Training",
+                "Natural Skill",
+            ],
+            "elf" => &[
+                "Ageless Patience",
+                "Elf Step",
+                "Elven Instincts",
+                "Otherworldly Acumen",
+                "Tree Climber",
+            ],
+            "dwarf" => &[
+                "Boulder Roll",
+                "Dwarven Lore",
+                "Mountain's Stoutness",
+                "Stonewalker",
+                "Unburdened Iron",
+            ],
+            "gnome" => &[
+                "Animal Elocutionist",
+                "Energized Font",
+                "Gnome Obsession",
+                "Intuitive Illusions",
+            ],
+            "goblin" => &[
+                "Burn It!",
+                "Goblin Scuttle",
+                "Junk Tinker",
+                "Roll With It",
+                "Very Sneaky",
+            ],
+            "halfling" => &[
+                "Ceaseless Shadows",
+                "Cultural Adaptability",
+                "Halfling Luck",
+                "Shared Luck",
+            ],
+            "orc" => &[
+                "Orc Ferocity",
+                "Orc Weapon Carnage",
+                "Pervasive Superstition",
+                "Undying Ferocity",
+            ],
+            _ => &[
+                "Cultural Adaptability",
+                "General Training",
+                "Natural Ambition",
+            ],
+        }
+    }
+
+    /// Highest skill proficiency rank (0 untrained to 4 legendary) at a level
+    fn max_skill_rank(level: u32) -> i32 {
+        match level {
+            0..=6 => 2,
+            7..=14 => 3,
+            _ => 4,
+        }
+    }
+
+    fn con_modifier(character: &Character) -> i32 {
+        find_key(&character.attributes, "Constitution")
+            .map(|key| character.attributes[&key].total_modifier())
+            .unwrap_or(0)
+    }
+
+    /// Hit point maximum from the notes, or ancestry plus class hit points per
+    /// level if it was never recorded
+    fn hit_point_maximum(character: &Character, class: &str) -> i32 {
+        note_value(&character.notes, "HP").unwrap_or_else(|| {
+            let ancestry = character.race.as_deref().unwrap_or("Human");
+            let per_level = (Self::class_hit_points(class) + Self::con_modifier(character)).max(1);
+            let toughness = if has_trait(character, "Toughness") {
+                character.level as i32
+            } else {
+                0
+            };
+            Self::ancestry_hit_points(ancestry)
+                + per_level * character.level.max(1) as i32
+                + toughness
+        })
+    }
+
+    fn feat_slot(character: &Character, label: &str, feats: &[&str]) -> ChoiceSlot {
+        let options: Vec<String> = feats
+            .iter()
+            .filter(|feat| !has_trait(character, feat))
+            .map(|feat| feat.to_string())
+            .collect();
+        ChoiceSlot::new(
+            label,
+            ChoiceKind::Trait {
+                trait_type: TraitType::Feat,
+            },
+            options,
+        )
+        .required()
+    }
+}
+
+impl Advancement for Pathfinder2eGenerator {
+    fn advancement_kind(&self) -> AdvancementKind {
+        AdvancementKind::LevelUp
+    }
+
+    fn preview_advancement(
+        &self,
+        character: &Character,
+        _request: &AdvancementRequest,
+    ) -> Result<AdvancementPreview> {
+        check_system(character, &GameSystem::Pathfinder2e)?;
+        let max_level = self.max_level().unwrap_or(20);
+        if character.level >= max_level {
+            return Err(advancement_error(format!(
+                "{} is already level {}",
+                character.name, max_level
+            )));
+        }
+
+        let class = character
+            .class
+            .clone()
+            .unwrap_or_else(|| "Fighter".to_string());
+        let ancestry = character
+            .race
+            .clone()
+            .unwrap_or_else(|| "Human".to_string());
+        let next = character.level + 1;
+        let con = Self::con_modifier(character);
+
+        let mut preview = AdvancementPreview::new(AdvancementKind::LevelUp);
+        preview.next_level = Some(next);
+        preview.features = new_features(character, Self::get_class_features(&class, next));
+        preview.hit_points = Some(format!(
+            "{} {:+} (fixed)",
+            Self::class_hit_points(&class),
+            con
+        ));
+
+        if next % 2 == 0 {
+            preview.slots.push(Self::feat_slot(
+                character,
+                "Class feat",
+                &Self::class_feats(&class),
+            ));
+            preview
+                .slots
+                .push(Self::feat_slot(character, "Skill feat", SKILL_FEATS));
+        }
+        if matches!(next, 3 | 7 | 11 | 15 | 19) {
+            preview
+                .slots
+                .push(Self::feat_slot(character, "General feat", GENERAL_FEATS));
+        }
+        if matches!(next, 5 | 9 | 13 | 17) {
+            preview.slots.push(Self::feat_slot(
+                character,
+                "Ancestry feat",
+                Self::ancestry_feats(&ancestry),
+            ));
+        }
+        if next >= 3 && next % 2 == 1 {
+            let cap = Self::max_skill_rank(next);
+            let skills: Vec<String> = sorted_keys(&character.skills)
+                .into_iter()
+                .filter(|skill| character.skills[skill] < cap)
+                .collect();
+            preview.slots.push(
+                ChoiceSlot::new("Skill increase", ChoiceKind::Skill { amount: 1 }, skills)
+                    .required(),
+            );
+        }
+        if next % 5 == 0 {
+            let attributes = self.attribute_names();
+            for _ in 0..4 {
+                preview.slots.push(
+                    ChoiceSlot::new(
+                        "Attribute boost",
+                        ChoiceKind::Attribute { amount: 2 },
+                        attributes.clone(),
+                    )
+                    .required(),
+                );
+            }
+            preview.notes.push(
+                "Boost four different attributes: +2, or +1 if already 18 or higher".to_string(),
+            );
+        }
+
+        Ok(preview)
+    }
+
+    fn advance(
+        &self,
+        character: &mut Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementRecord> {
+        let preview = self.preview_advancement(character, request)?;
+        let slots = match_choices(&preview, &request.choices)?;
+        let next = character.level + 1;
+        let class = character
+            .class
+            .clone()
+            .unwrap_or_else(|| "Fighter".to_string());
+
+        let mut boosted: Vec<String> = vec![];
+        for choice in &request.choices {
+            if let AdvancementChoice::IncreaseAttribute { name, .. } = choice {
+                if boosted.iter().any(|b| b.eq_ignore_ascii_case(name)) {
+                    return Err(advancement_error(format!(
+                        "{name} can only be boosted once per level"
+                    )));
+                }
+                boosted.push(name.clone());
+            }
+        }
+
+        apply_advancement(character, |character| {
+            let mut record = AdvancementRecord::new(AdvancementKind::LevelUp, character, request);
+            let hit_points = Self::hit_point_maximum(character, &class);
+            let con_before = Self::con_modifier(character);
+            let toughness_before = has_trait(character, "Toughness");
+
+            for (choice, &slot) in request.choices.iter().zip(&slots) {
+                let change = match choice {
+                    AdvancementChoice::IncreaseAttribute { name, .. } => {
+                        let base = find_key(&character.attributes, name)
+                            .map(|key| character.attributes[&key].base)
+                            .unwrap_or(0);
+                        let amount = if base >= 18 { 1 } else { 2 };
+                        raise_attribute(
+                            character,
+                            name,
+                            amount,
+                            MAX_ATTRIBUTE_SCORE,
+                            AttributeValue::new,
+                        )?
+                    }
+                    AdvancementChoice::IncreaseSkill { name, amount } => {
+                        raise_skill(character, name, *amount, Self::max_skill_rank(next))?
+                    }
+                    AdvancementChoice::GainTrait { name, .. } => {
+                        let label = &preview.slots[slot].label;
+                        gain_trait(
+                            character,
+                            name,
+                            TraitType::Feat,
+                            &format!("{label} (level {next})"),
+                            None,
+                        )?
+                    }
+                    other => {
+                        return Err(advancement_error(format!(
+                            "{} is not available",
+                            other.describe()
+                        )))
+                    }
+                };
+                record.changes.push(change);
+            }
+
+            let con = Self::con_modifier(character);
+            let mut gained = (Self::class_hit_points(&class) + con).max(1);
+            // Constitution modifier increases apply to earlier levels too
+            gained += (con - con_before) * character.level as i32;
+            if has_trait(character, "Toughness") {
+                gained += if toughness_before { 1 } else { next as i32 };
+            }
+
+            character.level = next;
+            set_note_value(&mut character.notes, "HP", hit_points + gained);
+            record
+                .changes
+                .push(format!("HP {} → {}", hit_points, hit_points + gained));
+            record.changes.extend(merge_features(
+                character,
+                Self::get_class_features(&class, next),
+            ));
+            record.to_level = next;
+            record.hit_points_gained = Some(gained);
+            Ok(record)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -230,6 +230,8 @@ impl SystemGenerator for ShadowrunGenerator {
             backstory: None,
             notes: format!("Essence: {}\nNuyen: 6000", essence),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         })
     }
 
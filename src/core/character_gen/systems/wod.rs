@@ -7,6 +7,11 @@ use crate::core::character_gen::{
     AttributeValue, CharacterTrait, TraitType, Equipment, EquipmentCategory,
     CharacterBackground, Result, random_modern_name,
 };
+use crate::core::character_gen::advancement::{
+    apply_advancement, advancement_error, check_system, gain_trait, has_trait, match_choices,
+    raise_attribute, raise_skill, set_note_value, sorted_keys, Advancement, AdvancementChoice,
+    AdvancementKind, AdvancementPreview, AdvancementRecord, AdvancementRequest, ChoiceKind, ChoiceSlot,
+};
 use rand::Rng;
 use uuid::Uuid;
 use std::collections::HashMap;
@@ -255,6 +260,8 @@ impl SystemGenerator for WorldOfDarknessGenerator {
             backstory: None,
             notes: format!("Health: {}\nWillpower: {}\nVirtue: {}\nVice: {}", health, willpower, virtue, vice),
             portrait_prompt: None,
+            experience: 0,
+            advancement_history: vec![],
         })
     }
 
@@ -341,4 +348,199 @@ impl SystemGenerator for WorldOfDarknessGenerator {
 
         equipment
     }
+
+    fn advancement(&self) -> Option<&dyn Advancement> {
+        Some(self)
+    }
+}
+
+// ============================================================================
+// Advancement
+// ============================================================================
+
+/// Experience costs per dot (Chronicles of Darkness 2e)
+const ATTRIBUTE_DOT_COST: u32 = 4;
+const SKILL_DOT_COST: u32 = 2;
+const MERIT_DOT_COST: u32 = 1;
+
+const MAX_DOTS: i32 = 5;
+
+const MERITS: &[(&str, &str)] = &[
+    (
+        "Common Sense",
+        "Ask the Storyteller for advice once per chapter",
+    ),
+    ("Danger Sense", "+2 to detect ambushes"),
+    ("Direction Sense", "Never get lost, always know north"),
+    (
+        "Eidetic Memory",
+        "Recall details without a roll, +2 to memory rolls",
+    ),
+    ("Fast Reflexes", "+1 Initiative"),
+    ("Iron Stamina", "Ignore one wound penalty"),
+    ("Language", "Fluency in another language"),
+    ("Resources", "Disposable income and assets"),
+    (
+        "Striking Looks",
+        "+1 to Presence or Manipulation rolls based on appearance",
+    ),
+    ("Trained Observer", "9-again on Perception rolls"),
+];
+
+impl WorldOfDarknessGenerator {
+    /// Refresh Health and Willpower after a trait change
+    fn update_derived(character: &mut Character) {
+        let dots = |name: &str| character.attributes.get(name).map(|a| a.base).unwrap_or(2);
+        let size = 5;
+        let health = dots("Stamina") + size;
+        let willpower = dots("Resolve") + dots("Composure");
+        set_note_value(&mut character.notes, "Health", health);
+        set_note_value(&mut character.notes, "Willpower", willpower);
+    }
+}
+
+impl Advancement for WorldOfDarknessGenerator {
+    fn advancement_kind(&self) -> AdvancementKind {
+        AdvancementKind::ExperienceSpend
+    }
+
+    fn preview_advancement(
+        &self,
+        character: &Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementPreview> {
+        check_system(character, &GameSystem::WorldOfDarkness)?;
+
+        let experience = character.experience + request.experience_award;
+        let mut preview = AdvancementPreview::new(AdvancementKind::ExperienceSpend);
+        preview.experience = experience;
+
+        let attributes: Vec<String> = sorted_keys(&character.attributes)
+            .into_iter()
+            .filter(|name| character.attributes[name].base < MAX_DOTS)
+            .collect();
+        let skills: Vec<String> = sorted_keys(&character.skills)
+            .into_iter()
+            .filter(|name| character.skills[name] < MAX_DOTS)
+            .collect();
+        let merits: Vec<&(&str, &str)> = MERITS
+            .iter()
+            .filter(|(name, _)| !has_trait(character, name))
+            .collect();
+
+        let attribute_slots = (experience / ATTRIBUTE_DOT_COST).min(2);
+        let skill_slots = (experience / SKILL_DOT_COST).min(3);
+        for _ in 0..attribute_slots {
+            preview.slots.push(
+                ChoiceSlot::new(
+                    "Attribute dot",
+                    ChoiceKind::Attribute { amount: 1 },
+                    attributes.clone(),
+                )
+                .with_cost(ATTRIBUTE_DOT_COST),
+            );
+        }
+        for _ in 0..skill_slots {
+            preview.slots.push(
+                ChoiceSlot::new("Skill dot", ChoiceKind::Skill { amount: 1 }, skills.clone())
+                    .with_cost(SKILL_DOT_COST),
+            );
+        }
+        if experience >= MERIT_DOT_COST {
+            preview.slots.push(
+                ChoiceSlot::new(
+                    "Merit",
+                    ChoiceKind::Trait {
+                        trait_type: TraitType::Merit,
+                    },
+                    merits.iter().map(|(name, _)| name.to_string()).collect(),
+                )
+                .with_details(
+                    merits
+                        .iter()
+                        .map(|(_, description)| description.to_string())
+                        .collect(),
+                )
+                .with_cost(MERIT_DOT_COST),
+            );
+        }
+
+        preview.notes.push(format!(
+            "Attribute dot {ATTRIBUTE_DOT_COST} XP, Skill dot {SKILL_DOT_COST} XP, Merit dot {MERIT_DOT_COST} XP"
+        ));
+        Ok(preview)
+    }
+
+    fn advance(
+        &self,
+        character: &mut Character,
+        request: &AdvancementRequest,
+    ) -> Result<AdvancementRecord> {
+        if request.experience_award == 0 && request.choices.is_empty() {
+            return Err(advancement_error(
+                "Award experience or choose something to buy",
+            ));
+        }
+        let preview = self.preview_advancement(character, request)?;
+        let slots = match_choices(&preview, &request.choices)?;
+        let cost: u32 = slots.iter().map(|&slot| preview.slots[slot].cost).sum();
+        if cost > preview.experience {
+            return Err(advancement_error(format!(
+                "These purchases cost {cost} XP but only {} XP is available",
+                preview.experience
+            )));
+        }
+
+        apply_advancement(character, |character| {
+            let mut record =
+                AdvancementRecord::new(AdvancementKind::ExperienceSpend, character, request);
+
+            for choice in &request.choices {
+                let change = match choice {
+                    AdvancementChoice::IncreaseAttribute { name, amount } => raise_attribute(
+                        character,
+                        name,
+                        *amount,
+                        MAX_DOTS,
+                        AttributeValue::new_raw,
+                    )?,
+                    AdvancementChoice::IncreaseSkill { name, amount } => {
+                        raise_skill(character, name, *amount, MAX_DOTS)?
+                    }
+                    AdvancementChoice::GainTrait { name, .. } => {
+                        let (merit, description) = MERITS
+                            .iter()
+                            .find(|(merit, _)| merit.eq_ignore_ascii_case(name))
+                            .copied()
+                            .ok_or_else(|| advancement_error(format!("Unknown merit: {name}")))?;
+                        gain_trait(
+                            character,
+                            merit,
+                            TraitType::Merit,
+                            description,
+                            Some("1 dot".to_string()),
+                        )?
+                    }
+                    other => {
+                        return Err(advancement_error(format!(
+                            "{} is not available",
+                            other.describe()
+                        )))
+                    }
+                };
+                record.changes.push(change);
+            }
+
+            if request.experience_award > 0 {
+                record
+                    .changes
+                    .insert(0, format!("Awarded {} XP", request.experience_award));
+            }
+            character.experience = preview.experience - cost;
+            Self::update_derived(character);
+            record.experience_awarded = request.experience_award;
+            record.experience_spent = cost;
+            Ok(record)
+        })
+    }
 }
@@ -1,12 +1,17 @@
 //! Secure Credential Storage
 //!
 //! Uses the system keychain (Keyring) for secure storage of API keys
-//! and other sensitive credentials.
+//! and other sensitive credentials, or the encrypted file vault
+//! ([`CredentialVault`]) where no keyring is available.
+
+use std::sync::Arc;
 
 use keyring::Entry;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::core::vault::{CredentialVault, VaultError};
+
 const SERVICE_NAME: &str = "ttrpg-assistant";
 
 // ============================================================================
@@ -26,6 +31,12 @@ pub enum CredentialError {
 
     #[error("Invalid credential format")]
     InvalidFormat,
+
+    #[error("Credential vault is locked")]
+    Locked,
+
+    #[error("Vault error: {0}")]
+    VaultError(#[from] VaultError),
 }
 
 pub type Result<T> = std::result::Result<T, CredentialError>;
@@ -57,8 +68,11 @@ pub struct VoiceCredential {
 // Credential Manager
 // ============================================================================
 
+#[derive(Clone)]
 pub struct CredentialManager {
     service: String,
+    /// When set, secrets live in the vault instead of the keyring.
+    vault: Option<Arc<CredentialVault>>,
 }
 
 impl Default for CredentialManager {
@@ -71,12 +85,36 @@ impl CredentialManager {
     pub fn new() -> Self {
         Self {
             service: SERVICE_NAME.to_string(),
+            vault: None,
         }
     }
 
     pub fn with_service(service: impl Into<String>) -> Self {
         Self {
             service: service.into(),
+            vault: None,
+        }
+    }
+
+    /// Store secrets in an encrypted vault file instead of the keyring.
+    pub fn with_vault(vault: Arc<CredentialVault>) -> Self {
+        Self {
+            service: SERVICE_NAME.to_string(),
+            vault: Some(vault),
+        }
+    }
+
+    /// The vault backing this manager, if any.
+    pub fn vault(&self) -> Option<&Arc<CredentialVault>> {
+        self.vault.as_ref()
+    }
+
+    /// Name of the active backend ("vault" or "keyring").
+    pub fn backend_name(&self) -> &'static str {
+        if self.vault.is_some() {
+            "vault"
+        } else {
+            "keyring"
         }
     }
 
@@ -86,6 +124,11 @@ impl CredentialManager {
 
     /// Store a raw string secret
     pub fn store_secret(&self, key: &str, value: &str) -> Result<()> {
+        if let Some(vault) = &self.vault {
+            vault.set(key, value).map_err(vault_error)?;
+            log::info!("Stored secret in vault for key: {}", key);
+            return Ok(());
+        }
         let entry = Entry::new(&self.service, key)?;
         entry.set_password(value)?;
         log::info!("Stored secret for key: {}", key);
@@ -94,6 +137,12 @@ impl CredentialManager {
 
     /// Retrieve a raw string secret
     pub fn get_secret(&self, key: &str) -> Result<String> {
+        if let Some(vault) = &self.vault {
+            return vault
+                .get(key)
+                .map_err(vault_error)?
+                .ok_or_else(|| CredentialError::NotFound(key.to_string()));
+        }
         let entry = Entry::new(&self.service, key)?;
         match entry.get_password() {
             Ok(value) => Ok(value),
@@ -104,6 +153,9 @@ impl CredentialManager {
 
     /// Delete a secret
     pub fn delete_secret(&self, key: &str) -> Result<()> {
+        if let Some(vault) = &self.vault {
+            return vault.remove(key).map_err(vault_error);
+        }
         let entry = Entry::new(&self.service, key)?;
         match entry.delete_password() {
             Ok(()) => {
@@ -117,6 +169,9 @@ impl CredentialManager {
 
     /// Check if a secret exists
     pub fn has_secret(&self, key: &str) -> bool {
+        if let Some(vault) = &self.vault {
+            return vault.contains(key);
+        }
         self.get_secret(key).is_ok()
     }
 
@@ -267,6 +322,49 @@ impl CredentialManager {
         Ok(serde_json::to_string_pretty(&export)?)
     }
 
+    /// Every keyring key this application writes.
+    pub fn known_secret_keys() -> Vec<String> {
+        use crate::core::llm::providers::PROVIDERS;
+
+        let mut keys: Vec<String> = PROVIDERS.iter().map(|p| format!("llm_{}", p.id)).collect();
+        keys.extend(
+            ["elevenlabs", "fishaudio", "ollama_tts"]
+                .iter()
+                .map(|p| format!("voice_{p}")),
+        );
+        keys.push(crate::core::backup::PASSPHRASE_SECRET_KEY.to_string());
+        keys
+    }
+
+    /// Copy secrets from the system keyring into the vault.
+    ///
+    /// Entries already in the vault are left alone, and keyring entries are
+    /// not deleted. Returns how many secrets were copied.
+    pub fn migrate_from_keyring(&self) -> Result<usize> {
+        let Some(vault) = &self.vault else {
+            return Ok(0);
+        };
+        if !vault.is_unlocked() {
+            return Err(CredentialError::Locked);
+        }
+
+        let keyring = Self::with_service(self.service.clone());
+        let mut copied = 0;
+        for key in Self::known_secret_keys() {
+            if vault.contains(&key) {
+                continue;
+            }
+            if let Ok(value) = keyring.get_secret(&key) {
+                vault.set(&key, &value).map_err(vault_error)?;
+                copied += 1;
+            }
+        }
+        if copied > 0 {
+            log::info!("Migrated {copied} secrets from keyring to vault");
+        }
+        Ok(copied)
+    }
+
     /// Import credentials from JSON
     pub fn import_credentials(&self, json: &str) -> Result<()> {
         let data: serde_json::Value = serde_json::from_str(json)?;
@@ -288,6 +386,13 @@ impl CredentialManager {
 // Helper Functions
 // ============================================================================
 
+fn vault_error(e: VaultError) -> CredentialError {
+    match e {
+        VaultError::Locked | VaultError::NotInitialized => CredentialError::Locked,
+        other => CredentialError::VaultError(other),
+    }
+}
+
 /// Mask an API key for display (show first 4 and last 4 chars)
 pub fn mask_api_key(key: &str) -> String {
     if key.len() <= 8 {
@@ -331,6 +436,29 @@ mod tests {
         assert!(!validate_api_key("copilot", "anything"));
     }
 
+    #[test]
+    fn test_vault_backend() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let vault = Arc::new(CredentialVault::open(dir.path().join("credentials.vault")).unwrap());
+        let manager = CredentialManager::with_vault(vault.clone());
+        assert_eq!(manager.backend_name(), "vault");
+        assert!(matches!(
+            manager.store_provider_secret("openai", "sk-test"),
+            Err(CredentialError::Locked)
+        ));
+
+        vault.initialize("pw").unwrap();
+        manager.store_provider_secret("openai", "sk-test").unwrap();
+        assert!(manager.has_secret("llm_openai"));
+        assert_eq!(manager.get_provider_secret("openai").unwrap(), "sk-test");
+
+        manager.delete_provider_secret("openai").unwrap();
+        assert!(matches!(
+            manager.get_provider_secret("openai"),
+            Err(CredentialError::NotFound(_))
+        ));
+    }
+
     #[test]
     fn test_get_provider_secret_migrates_old_json() {
         // Simulate the old JSON format that get_provider_secret should handle
@@ -41,6 +41,7 @@ use crate::oauth::claude::{
 use crate::oauth::claude::models::ContentDelta;
 #[cfg(feature = "keyring")]
 use crate::oauth::claude::KeyringTokenStorage;
+use crate::oauth::claude::VaultTokenStorage;
 
 use crate::core::llm::cost::{ProviderPricing, TokenUsage};
 use crate::core::llm::router::{
@@ -80,7 +81,9 @@ pub enum StorageBackend {
     Keyring,
     /// In-memory storage (tokens lost on restart)
     Memory,
-    /// Automatic selection (keyring if available, else file)
+    /// Encrypted credential vault (`[credentials] backend = "vault"`)
+    Vault,
+    /// Automatic selection (vault if enabled, else keyring if available, else file)
     #[default]
     Auto,
 }
@@ -94,6 +97,7 @@ impl StorageBackend {
             #[cfg(feature = "keyring")]
             Self::Keyring => "keyring",
             Self::Memory => "memory",
+            Self::Vault => "vault",
             Self::Auto => "auto",
         }
     }
@@ -361,6 +365,97 @@ impl ClaudeClientTrait for KeyringStorageClient {
     }
 }
 
+/// Wrapper for ClaudeClient with VaultTokenStorage
+struct VaultStorageClient {
+    client: ClaudeClient<VaultTokenStorage>,
+}
+
+#[async_trait]
+impl ClaudeClientTrait for VaultStorageClient {
+    async fn is_authenticated(&self) -> crate::oauth::claude::Result<bool> {
+        self.client.is_authenticated().await
+    }
+
+    async fn start_oauth_flow(&self) -> crate::oauth::claude::Result<String> {
+        self.client.start_oauth_flow().await
+    }
+
+    async fn complete_oauth_flow(
+        &self,
+        code: &str,
+        state: Option<&str>,
+    ) -> crate::oauth::claude::Result<crate::oauth::claude::TokenInfo> {
+        self.client.complete_oauth_flow(code, state).await
+    }
+
+    async fn logout(&self) -> crate::oauth::claude::Result<()> {
+        self.client.logout().await
+    }
+
+    async fn get_token_info(&self) -> crate::oauth::claude::Result<Option<crate::oauth::claude::TokenInfo>> {
+        self.client.get_token_info().await
+    }
+
+    async fn send_message(
+        &self,
+        model: &str,
+        max_tokens: u32,
+        messages: Vec<crate::oauth::claude::Message>,
+        system: Option<String>,
+        temperature: Option<f32>,
+    ) -> crate::oauth::claude::Result<MessagesResponse> {
+        let mut builder = self.client.messages()
+            .model(model)
+            .max_tokens(max_tokens)
+            .messages(messages);
+
+        if let Some(sys) = system {
+            builder = builder.system(sys);
+        }
+        if let Some(temp) = temperature {
+            builder = builder.temperature(temp);
+        }
+
+        builder.send().await
+    }
+
+    async fn stream_message(
+        &self,
+        model: &str,
+        max_tokens: u32,
+        messages: Vec<crate::oauth::claude::Message>,
+        system: Option<String>,
+        temperature: Option<f32>,
+    ) -> crate::oauth::claude::Result<mpsc::Receiver<crate::oauth::claude::Result<StreamEvent>>> {
+        let mut builder = self.client.messages()
+            .model(model)
+            .max_tokens(max_tokens)
+            .messages(messages)
+            .stream();
+
+        if let Some(sys) = system {
+            builder = builder.system(sys);
+        }
+        if let Some(temp) = temperature {
+            builder = builder.temperature(temp);
+        }
+
+        let stream = builder.send_stream().await?;
+        let (tx, rx) = mpsc::channel(100);
+
+        tokio::spawn(async move {
+            futures::pin_mut!(stream);
+            while let Some(event) = stream.next().await {
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
 /// Wrapper for ClaudeClient with MemoryTokenStorage
 struct MemoryStorageClient {
     client: ClaudeClient<MemoryTokenStorage>,
@@ -483,6 +578,12 @@ impl ClaudeProvider {
 
     /// Create a new provider with specified storage backend.
     pub fn with_storage(backend: StorageBackend, model: String, max_tokens: u32) -> Result<Self> {
+        // The vault, when enabled, takes precedence over keyring and file
+        let backend = match backend {
+            StorageBackend::Auto if VaultTokenStorage::shared().is_some() => StorageBackend::Vault,
+            other => other,
+        };
+
         let (client, storage_name): (Arc<dyn ClaudeClientTrait>, String) = match backend {
             StorageBackend::File => {
                 let storage = FileTokenStorage::app_data_path()
@@ -502,6 +603,16 @@ impl ClaudeProvider {
                     .map_err(|e| LLMError::NotConfigured(format!("Failed to create client: {}", e)))?;
                 (Arc::new(KeyringStorageClient { client: claude_client }), "keyring".to_string())
             }
+            StorageBackend::Vault => {
+                let storage = VaultTokenStorage::shared().ok_or_else(|| {
+                    LLMError::NotConfigured("Credential vault is not enabled".to_string())
+                })?;
+                let claude_client = ClaudeClient::builder()
+                    .with_storage(storage)
+                    .build()
+                    .map_err(|e| LLMError::NotConfigured(format!("Failed to create client: {}", e)))?;
+                (Arc::new(VaultStorageClient { client: claude_client }), "vault".to_string())
+            }
             StorageBackend::Memory => {
                 let storage = MemoryTokenStorage::new();
                 let claude_client = ClaudeClient::builder()
@@ -554,19 +665,20 @@ impl ClaudeProvider {
 
     /// Create a provider from a storage backend name string.
     ///
-    /// Accepts: "file", "keyring", "memory", "auto"
+    /// Accepts: "file", "keyring", "memory", "vault", "auto"
     pub fn from_storage_name(name: &str, model: String, max_tokens: u32) -> Result<Self> {
         let backend = match name.to_lowercase().as_str() {
             "file" => StorageBackend::File,
             #[cfg(feature = "keyring")]
             "keyring" => StorageBackend::Keyring,
             "memory" => StorageBackend::Memory,
+            "vault" => StorageBackend::Vault,
             "auto" => StorageBackend::Auto,
             _ => {
                 #[cfg(feature = "keyring")]
-                let valid_options = "file, keyring, memory, auto";
+                let valid_options = "file, keyring, memory, vault, auto";
                 #[cfg(not(feature = "keyring"))]
-                let valid_options = "file, memory, auto";
+                let valid_options = "file, memory, vault, auto";
                 return Err(LLMError::NotConfigured(format!(
                     "Unknown storage backend: {}. Valid options: {}",
                     name, valid_options
@@ -906,6 +1018,7 @@ impl LLMProvider for ClaudeProvider {
                                             finish_reason: None,
                                             usage: None,
                                             index: chunk_index,
+                                            tool_calls: None,
                                         };
                                         if tx.send(Ok(chunk)).await.is_err() {
                                             return;
@@ -929,6 +1042,7 @@ impl LLMProvider for ClaudeProvider {
                                     finish_reason: Some("stop".to_string()),
                                     usage: final_usage.clone(),
                                     index: chunk_index + 1,
+                                    tool_calls: None,
                                 };
                                 let _ = tx.send(Ok(final_chunk)).await;
                                 return;
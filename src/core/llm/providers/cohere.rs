@@ -285,6 +285,7 @@ impl LLMProvider for CohereProvider {
                                                             finish_reason: None,
                                                             usage: None,
                                                             index: chunk_index,
+                                                            tool_calls: None,
                                                         };
                                                         if tx.send(Ok(chunk)).await.is_err() {
                                                             return;
@@ -325,6 +326,7 @@ impl LLMProvider for CohereProvider {
                                                         .map(|s| s.to_string()),
                                                     usage: final_usage.clone(),
                                                     index: chunk_index + 1,
+                                                    tool_calls: None,
                                                 };
                                                 let _ = tx.send(Ok(final_chunk)).await;
                                                 return;
@@ -45,6 +45,7 @@ use crate::oauth::copilot::storage::{GateStorageAdapter, MemoryTokenStorage};
 use crate::oauth::storage::FileTokenStorage;
 #[cfg(feature = "keyring")]
 use crate::oauth::storage::KeyringTokenStorage;
+use crate::oauth::storage::VaultTokenStorage;
 
 use crate::core::llm::cost::{ProviderPricing, TokenUsage};
 use crate::core::llm::router::{
@@ -87,7 +88,9 @@ pub enum CopilotStorageBackend {
     Keyring,
     /// In-memory storage (tokens lost on restart)
     Memory,
-    /// Automatic selection (keyring if available, else file)
+    /// Encrypted credential vault (`[credentials] backend = "vault"`)
+    Vault,
+    /// Automatic selection (vault if enabled, else keyring if available, else file)
     #[default]
     Auto,
 }
@@ -101,6 +104,7 @@ impl CopilotStorageBackend {
             #[cfg(feature = "keyring")]
             Self::Keyring => "keyring",
             Self::Memory => "memory",
+            Self::Vault => "vault",
             Self::Auto => "auto",
         }
     }
@@ -357,6 +361,92 @@ impl CopilotClientTrait for KeyringStorageClient {
     }
 }
 
+/// Wrapper for CopilotClient with VaultTokenStorage
+struct VaultStorageClient {
+    client: CopilotClient<GateStorageAdapter<VaultTokenStorage>>,
+}
+
+#[async_trait]
+impl CopilotClientTrait for VaultStorageClient {
+    async fn is_authenticated(&self) -> bool {
+        self.client.is_authenticated().await
+    }
+
+    async fn start_device_flow(&self) -> crate::oauth::copilot::Result<DeviceFlowPending> {
+        self.client.start_device_flow().await
+    }
+
+    async fn poll_for_token(&self, pending: &DeviceFlowPending) -> crate::oauth::copilot::Result<PollResult> {
+        self.client.poll_for_token(pending).await
+    }
+
+    async fn complete_auth(&self, github_token: String) -> crate::oauth::copilot::Result<()> {
+        self.client.complete_auth(github_token).await
+    }
+
+    async fn sign_out(&self) -> crate::oauth::copilot::Result<()> {
+        self.client.sign_out().await
+    }
+
+    async fn send_message(
+        &self,
+        model: &str,
+        max_tokens: u32,
+        messages: Vec<CopilotMessage>,
+        system: Option<String>,
+        temperature: Option<f32>,
+    ) -> crate::oauth::copilot::Result<CopilotChatResponse> {
+        let mut builder = self.client.chat()
+            .model(model)
+            .max_tokens(max_tokens);
+
+        if let Some(sys) = system {
+            builder = builder.system(sys);
+        }
+        if let Some(temp) = temperature {
+            builder = builder.temperature(temp);
+        }
+
+        for msg in messages {
+            builder = builder.message(msg);
+        }
+
+        builder.send().await
+    }
+
+    async fn stream_message(
+        &self,
+        model: &str,
+        max_tokens: u32,
+        messages: Vec<CopilotMessage>,
+        system: Option<String>,
+        temperature: Option<f32>,
+    ) -> crate::oauth::copilot::Result<
+        std::pin::Pin<Box<dyn futures_util::Stream<Item = crate::oauth::copilot::Result<StreamChunk>> + Send>>,
+    > {
+        let mut builder = self.client.chat()
+            .model(model)
+            .max_tokens(max_tokens);
+
+        if let Some(sys) = system {
+            builder = builder.system(sys);
+        }
+        if let Some(temp) = temperature {
+            builder = builder.temperature(temp);
+        }
+
+        for msg in messages {
+            builder = builder.message(msg);
+        }
+
+        builder.send_stream().await
+    }
+
+    async fn embeddings(&self, text: &str) -> crate::oauth::copilot::Result<EmbeddingResponse> {
+        self.client.embeddings().input(text).send().await
+    }
+}
+
 /// Wrapper for CopilotClient with MemoryTokenStorage
 struct MemoryStorageClient {
     client: CopilotClient<MemoryTokenStorage>,
@@ -494,6 +584,12 @@ impl CopilotLLMProvider {
         model: String,
         max_tokens: u32,
     ) -> Result<Self> {
+        // The vault, when enabled, takes precedence over keyring and file
+        let backend = match backend {
+            CopilotStorageBackend::Auto if VaultTokenStorage::shared().is_some() => CopilotStorageBackend::Vault,
+            other => other,
+        };
+
         let (client, storage_name): (Arc<dyn CopilotClientTrait>, String) = match backend {
             CopilotStorageBackend::File => {
                 let storage = FileTokenStorage::app_data_path().map_err(|e| {
@@ -522,6 +618,20 @@ impl CopilotLLMProvider {
                     "keyring".to_string(),
                 )
             }
+            CopilotStorageBackend::Vault => {
+                let storage = VaultTokenStorage::shared().ok_or_else(|| {
+                    LLMError::NotConfigured("Credential vault is not enabled".to_string())
+                })?;
+                let adapter = GateStorageAdapter::new(storage);
+                let copilot_client = CopilotClient::builder()
+                    .with_storage(adapter)
+                    .build()
+                    .map_err(|e| LLMError::NotConfigured(format!("Failed to create client: {}", e)))?;
+                (
+                    Arc::new(VaultStorageClient { client: copilot_client }),
+                    "vault".to_string(),
+                )
+            }
             CopilotStorageBackend::Memory => {
                 let storage = MemoryTokenStorage::new();
                 let copilot_client = CopilotClient::builder()
@@ -591,19 +701,20 @@ impl CopilotLLMProvider {
 
     /// Create a provider from a storage backend name string.
     ///
-    /// Accepts: "file", "keyring", "memory", "auto"
+    /// Accepts: "file", "keyring", "memory", "vault", "auto"
     pub fn from_storage_name(name: &str, model: String, max_tokens: u32) -> Result<Self> {
         let backend = match name.to_lowercase().as_str() {
             "file" => CopilotStorageBackend::File,
             #[cfg(feature = "keyring")]
             "keyring" => CopilotStorageBackend::Keyring,
             "memory" => CopilotStorageBackend::Memory,
+            "vault" => CopilotStorageBackend::Vault,
             "auto" => CopilotStorageBackend::Auto,
             _ => {
                 #[cfg(feature = "keyring")]
-                let valid_options = "file, keyring, memory, auto";
+                let valid_options = "file, keyring, memory, vault, auto";
                 #[cfg(not(feature = "keyring"))]
-                let valid_options = "file, memory, auto";
+                let valid_options = "file, memory, vault, auto";
                 return Err(LLMError::NotConfigured(format!(
                     "Unknown storage backend: {}. Valid options: {}",
                     name, valid_options
@@ -940,6 +1051,7 @@ impl LLMProvider for CopilotLLMProvider {
                                         finish_reason: None,
                                         usage: None,
                                         index: chunk_index,
+                                        tool_calls: None,
                                     };
                                     if tx.send(Ok(chat_chunk)).await.is_err() {
                                         return;
@@ -956,6 +1068,7 @@ impl LLMProvider for CopilotLLMProvider {
                                     finish_reason: Some(reason),
                                     usage: final_usage.clone(),
                                     index: chunk_index + 1,
+                                    tool_calls: None,
                                 };
                                 let _ = tx.send(Ok(final_chunk)).await;
                                 return;
@@ -977,6 +1090,7 @@ impl LLMProvider for CopilotLLMProvider {
                                     finish_reason: Some("stop".to_string()),
                                     usage: final_usage.clone(),
                                     index: chunk_index + 1,
+                                    tool_calls: None,
                                 };
                                 let _ = tx.send(Ok(final_chunk)).await;
                                 return;
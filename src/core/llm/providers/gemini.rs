@@ -40,6 +40,7 @@ use crate::oauth::gemini::{
 };
 #[cfg(feature = "keyring")]
 use crate::oauth::gemini::KeyringTokenStorage;
+use crate::oauth::gemini::VaultTokenStorage;
 
 use crate::core::llm::cost::{ProviderPricing, TokenUsage};
 use crate::core::llm::router::{
@@ -79,7 +80,9 @@ pub enum GeminiStorageBackend {
     Keyring,
     /// In-memory storage (tokens lost on restart)
     Memory,
-    /// Automatic selection (keyring if available, else file)
+    /// Encrypted credential vault (`[credentials] backend = "vault"`)
+    Vault,
+    /// Automatic selection (vault if enabled, else keyring if available, else file)
     #[default]
     Auto,
 }
@@ -93,6 +96,7 @@ impl GeminiStorageBackend {
             #[cfg(feature = "keyring")]
             Self::Keyring => "keyring",
             Self::Memory => "memory",
+            Self::Vault => "vault",
             Self::Auto => "auto",
         }
     }
@@ -387,6 +391,107 @@ impl GeminiClientTrait for KeyringStorageClient {
     }
 }
 
+/// Wrapper for CloudCodeClient with VaultTokenStorage
+struct VaultStorageClient {
+    client: Arc<CloudCodeClient<VaultTokenStorage>>,
+}
+
+#[async_trait]
+impl GeminiClientTrait for VaultStorageClient {
+    async fn is_authenticated(&self) -> crate::oauth::gemini::Result<bool> {
+        self.client.is_authenticated().await
+    }
+
+    async fn start_oauth_flow(
+        &self,
+    ) -> crate::oauth::gemini::Result<(String, crate::oauth::gemini::OAuthFlowState)> {
+        self.client.start_oauth_flow().await
+    }
+
+    async fn complete_oauth_flow(
+        &self,
+        code: &str,
+        state: Option<&str>,
+    ) -> crate::oauth::gemini::Result<TokenInfo> {
+        self.client.complete_oauth_flow(code, state).await
+    }
+
+    async fn logout(&self) -> crate::oauth::gemini::Result<()> {
+        self.client.logout().await
+    }
+
+    async fn get_token_info(&self) -> crate::oauth::gemini::Result<Option<TokenInfo>> {
+        self.client.get_token_info().await
+    }
+
+    async fn send_message(
+        &self,
+        model: &str,
+        max_tokens: u32,
+        messages: Vec<crate::oauth::gemini::Message>,
+        system: Option<String>,
+        temperature: Option<f32>,
+    ) -> crate::oauth::gemini::Result<MessagesResponse> {
+        let mut builder = Arc::clone(&self.client)
+            .messages()
+            .model(model)
+            .max_tokens(max_tokens);
+
+        for msg in messages {
+            builder = builder.message(msg);
+        }
+
+        if let Some(sys) = system {
+            builder = builder.system(sys);
+        }
+        if let Some(temp) = temperature {
+            builder = builder.temperature(temp);
+        }
+
+        builder.send().await
+    }
+
+    async fn stream_message(
+        &self,
+        model: &str,
+        max_tokens: u32,
+        messages: Vec<crate::oauth::gemini::Message>,
+        system: Option<String>,
+        temperature: Option<f32>,
+    ) -> crate::oauth::gemini::Result<mpsc::Receiver<crate::oauth::gemini::Result<StreamEvent>>>
+    {
+        let mut builder = Arc::clone(&self.client)
+            .messages()
+            .model(model)
+            .max_tokens(max_tokens);
+
+        for msg in messages {
+            builder = builder.message(msg);
+        }
+
+        if let Some(sys) = system {
+            builder = builder.system(sys);
+        }
+        if let Some(temp) = temperature {
+            builder = builder.temperature(temp);
+        }
+
+        let stream = builder.send_stream().await?;
+        let (tx, rx) = mpsc::channel(100);
+
+        tokio::spawn(async move {
+            futures::pin_mut!(stream);
+            while let Some(event) = stream.next().await {
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
 /// Wrapper for CloudCodeClient with MemoryTokenStorage
 struct MemoryStorageClient {
     client: Arc<CloudCodeClient<MemoryTokenStorage>>,
@@ -579,6 +684,12 @@ impl GeminiProvider {
         model: String,
         max_tokens: u32,
     ) -> Result<Self> {
+        // The vault, when enabled, takes precedence over keyring and file
+        let backend = match backend {
+            GeminiStorageBackend::Auto if VaultTokenStorage::shared().is_some() => GeminiStorageBackend::Vault,
+            other => other,
+        };
+
         let (client, storage_name): (Arc<dyn GeminiClientTrait>, String) = match backend {
             GeminiStorageBackend::File => {
                 let storage = FileTokenStorage::app_data_path().map_err(|e| {
@@ -603,6 +714,18 @@ impl GeminiProvider {
                     "keyring".to_string(),
                 )
             }
+            GeminiStorageBackend::Vault => {
+                let storage = VaultTokenStorage::shared().ok_or_else(|| {
+                    LLMError::NotConfigured("Credential vault is not enabled".to_string())
+                })?;
+                let gemini_client = CloudCodeClient::new(storage);
+                (
+                    Arc::new(VaultStorageClient {
+                        client: Arc::new(gemini_client),
+                    }),
+                    "vault".to_string(),
+                )
+            }
             GeminiStorageBackend::Memory => {
                 let storage = MemoryTokenStorage::new();
                 let gemini_client = CloudCodeClient::new(storage);
@@ -705,19 +828,20 @@ impl GeminiProvider {
 
     /// Create a provider from a storage backend name string.
     ///
-    /// Accepts: "file", "keyring", "memory", "auto"
+    /// Accepts: "file", "keyring", "memory", "vault", "auto"
     pub fn from_storage_name(name: &str, model: String, max_tokens: u32) -> Result<Self> {
         let backend = match name.to_lowercase().as_str() {
             "file" => GeminiStorageBackend::File,
             #[cfg(feature = "keyring")]
             "keyring" => GeminiStorageBackend::Keyring,
             "memory" => GeminiStorageBackend::Memory,
+            "vault" => GeminiStorageBackend::Vault,
             "auto" => GeminiStorageBackend::Auto,
             _ => {
                 #[cfg(feature = "keyring")]
-                let valid_options = "file, keyring, memory, auto";
+                let valid_options = "file, keyring, memory, vault, auto";
                 #[cfg(not(feature = "keyring"))]
-                let valid_options = "file, memory, auto";
+                let valid_options = "file, memory, vault, auto";
                 return Err(LLMError::NotConfigured(format!(
                     "Unknown storage backend: {}. Valid options: {}",
                     name, valid_options
@@ -1059,6 +1183,7 @@ impl LLMProvider for GeminiProvider {
                                             finish_reason: None,
                                             usage: None,
                                             index: chunk_index,
+                                            tool_calls: None,
                                         };
                                         if tx.send(Ok(chunk)).await.is_err() {
                                             return;
@@ -1084,6 +1209,7 @@ impl LLMProvider for GeminiProvider {
                                     finish_reason: Some("stop".to_string()),
                                     usage: final_usage.clone(),
                                     index: chunk_index + 1,
+                                    tool_calls: None,
                                 };
                                 let _ = tx.send(Ok(final_chunk)).await;
                                 return;
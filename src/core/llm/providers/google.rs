@@ -302,6 +302,7 @@ impl LLMProvider for GoogleProvider {
                                                 finish_reason: None,
                                                 usage: None,
                                                 index: chunk_index,
+                                                tool_calls: None,
                                             };
                                             if tx.send(Ok(chunk)).await.is_err() {
                                                 return;
@@ -335,6 +336,7 @@ impl LLMProvider for GoogleProvider {
                                                 finish_reason: Some(reason.to_string()),
                                                 usage: final_usage.clone(),
                                                 index: chunk_index + 1,
+                                                tool_calls: None,
                                             };
                                             let _ = tx.send(Ok(final_chunk)).await;
                                             return;
@@ -368,6 +370,7 @@ impl LLMProvider for GoogleProvider {
                                     finish_reason: None,
                                     usage: None,
                                     index: chunk_index,
+                                    tool_calls: None,
                                 };
                                 let _ = tx.send(Ok(chunk)).await;
                             }
@@ -386,6 +389,7 @@ impl LLMProvider for GoogleProvider {
                 finish_reason: Some("stop".to_string()),
                 usage: final_usage,
                 index: chunk_index + 1,
+                tool_calls: None,
             };
             let _ = tx.send(Ok(final_chunk)).await;
         });
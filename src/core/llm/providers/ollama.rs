@@ -194,6 +194,7 @@ impl LLMProvider for OllamaProvider {
                                                 finish_reason: None,
                                                 usage: None,
                                                 index: chunk_index,
+                                                tool_calls: None,
                                             };
                                             if tx.send(Ok(chunk)).await.is_err() {
                                                 log::error!("Failed to send chunk to channel");
@@ -220,6 +221,7 @@ impl LLMProvider for OllamaProvider {
                                                 output_tokens,
                                             }),
                                             index: chunk_index + 1,
+                                            tool_calls: None,
                                         };
                                         let _ = tx.send(Ok(final_chunk)).await;
                                         return;
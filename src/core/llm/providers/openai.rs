@@ -66,11 +66,17 @@ impl OpenAIProvider {
         for msg in &request.messages {
             let mut message_obj = serde_json::Map::new();
 
-            message_obj.insert("role".to_string(), serde_json::json!(match msg.role {
-                MessageRole::System => "system",
-                MessageRole::User => "user",
-                MessageRole::Assistant => "assistant",
-            }));
+            // Tool results carry the id of the call they answer
+            let role = if msg.tool_call_id.is_some() {
+                "tool"
+            } else {
+                match msg.role {
+                    MessageRole::System => "system",
+                    MessageRole::User => "user",
+                    MessageRole::Assistant => "assistant",
+                }
+            };
+            message_obj.insert("role".to_string(), serde_json::json!(role));
 
             // Handle content (text or multi-modal)
             if let Some(images) = &msg.images {
@@ -123,6 +129,34 @@ impl OpenAIProvider {
     }
 }
 
+/// Fold streamed tool call fragments into complete calls.
+///
+/// Each delta carries the `index` of the call it belongs to; the first
+/// fragment has the id and name, later ones append to the JSON arguments.
+fn merge_tool_call_deltas(calls: &mut Vec<serde_json::Value>, deltas: &[serde_json::Value]) {
+    for delta in deltas {
+        let index = delta["index"].as_u64().unwrap_or(calls.len() as u64) as usize;
+        while calls.len() <= index {
+            calls.push(serde_json::json!({
+                "id": "",
+                "type": "function",
+                "function": { "name": "", "arguments": "" }
+            }));
+        }
+
+        let call = &mut calls[index];
+        if let Some(id) = delta["id"].as_str() {
+            call["id"] = serde_json::json!(id);
+        }
+        for field in ["name", "arguments"] {
+            if let Some(part) = delta["function"][field].as_str() {
+                let joined = format!("{}{part}", call["function"][field].as_str().unwrap_or(""));
+                call["function"][field] = serde_json::json!(joined);
+            }
+        }
+    }
+}
+
 #[async_trait]
 impl LLMProvider for OpenAIProvider {
     fn id(&self) -> &str {
@@ -284,6 +318,14 @@ impl LLMProvider for OpenAIProvider {
             body["temperature"] = serde_json::json!(temp);
         }
 
+        if let Some(tools) = &request.tools {
+            body["tools"] = serde_json::json!(tools);
+        }
+
+        if let Some(tool_choice) = &request.tool_choice {
+            body["tool_choice"] = tool_choice.clone();
+        }
+
         let mut req_builder = self
             .client
             .post(&url)
@@ -308,6 +350,7 @@ impl LLMProvider for OpenAIProvider {
             let mut stream = response.bytes_stream();
             let mut chunk_index = 0u32;
             let mut final_usage: Option<TokenUsage> = None;
+            let mut tool_calls: Vec<serde_json::Value> = Vec::new();
 
             while let Some(item) = stream.next().await {
                 match item {
@@ -323,15 +366,25 @@ impl LLMProvider for OpenAIProvider {
                                         provider: provider_id.clone(),
                                         model: model.clone(),
                                         is_final: true,
-                                        finish_reason: Some("stop".to_string()),
+                                        finish_reason: Some(
+                                            if tool_calls.is_empty() { "stop" } else { "tool_calls" }
+                                                .to_string(),
+                                        ),
                                         usage: final_usage.clone(),
                                         index: chunk_index + 1,
+                                        tool_calls: (!tool_calls.is_empty())
+                                            .then(|| std::mem::take(&mut tool_calls)),
                                     };
                                     let _ = tx.send(Ok(final_chunk)).await;
                                     return;
                                 }
 
                                 if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
+                                    if let Some(deltas) =
+                                        json["choices"][0]["delta"]["tool_calls"].as_array()
+                                    {
+                                        merge_tool_call_deltas(&mut tool_calls, deltas);
+                                    }
                                     if let Some(delta) =
                                         json["choices"][0]["delta"]["content"].as_str()
                                     {
@@ -346,6 +399,7 @@ impl LLMProvider for OpenAIProvider {
                                                 finish_reason: None,
                                                 usage: None,
                                                 index: chunk_index,
+                                                tool_calls: None,
                                             };
                                             if tx.send(Ok(chunk)).await.is_err() {
                                                 return;
@@ -673,6 +727,7 @@ impl LLMProvider for OpenAICompatibleProvider {
                                         finish_reason: Some("stop".to_string()),
                                         usage: final_usage.clone(),
                                         index: chunk_index + 1,
+                                        tool_calls: None,
                                     };
                                     let _ = tx.send(Ok(final_chunk)).await;
                                     return;
@@ -693,6 +748,7 @@ impl LLMProvider for OpenAICompatibleProvider {
                                                 finish_reason: None,
                                                 usage: None,
                                                 index: chunk_index,
+                                                tool_calls: None,
                                             };
                                             if tx.send(Ok(chunk)).await.is_err() {
                                                 return;
@@ -862,4 +918,35 @@ mod tests {
         assert!(messages[0].get("tool_calls").is_some(), "tool_calls missing in: {:?}", messages[0]);
         assert_eq!(messages[0]["tool_calls"][0]["id"], "call_123");
     }
+
+    #[test]
+    fn test_build_messages_with_tool_result() {
+        let provider = make_provider();
+        let request = ChatRequest::new(vec![ChatMessage::tool_result("call_123", "17")]);
+
+        let messages = provider.build_messages(&request);
+        assert_eq!(messages[0]["role"], "tool");
+        assert_eq!(messages[0]["tool_call_id"], "call_123");
+        assert_eq!(messages[0]["content"], "17");
+    }
+
+    #[test]
+    fn test_merge_streamed_tool_call_deltas() {
+        let mut calls = Vec::new();
+        merge_tool_call_deltas(&mut calls, &[json!({
+            "index": 0, "id": "call_1", "type": "function",
+            "function": { "name": "roll_dice", "arguments": "" }
+        })]);
+        merge_tool_call_deltas(&mut calls, &[json!({ "index": 0, "function": { "arguments": "{\"notation\":" } })]);
+        merge_tool_call_deltas(&mut calls, &[
+            json!({ "index": 0, "function": { "arguments": "\"2d6\"}" } }),
+            json!({ "index": 1, "id": "call_2", "function": { "name": "search_rules", "arguments": "{}" } }),
+        ]);
+
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0]["id"], "call_1");
+        assert_eq!(calls[0]["function"]["name"], "roll_dice");
+        assert_eq!(calls[0]["function"]["arguments"], "{\"notation\":\"2d6\"}");
+        assert_eq!(calls[1]["function"]["name"], "search_rules");
+    }
 }
@@ -147,6 +147,7 @@ impl LLMProvider for MeilisearchProvider {
                                 finish_reason: Some("stop".to_string()),
                                 usage: None,
                                 index: chunk_index + 1,
+                                tool_calls: None,
                             };
                             let _ = tx.send(Ok(final_chunk)).await;
                             return;
@@ -162,6 +163,7 @@ impl LLMProvider for MeilisearchProvider {
                             finish_reason: None,
                             usage: None,
                             index: chunk_index,
+                            tool_calls: None,
                         };
                         if tx.send(Ok(chunk)).await.is_err() {
                             return;
@@ -184,6 +186,7 @@ impl LLMProvider for MeilisearchProvider {
                 finish_reason: Some("stream_terminated".to_string()),
                 usage: None,
                 index: chunk_index + 1,
+                tool_calls: None,
             };
             let _ = tx.send(Ok(final_chunk)).await;
         });
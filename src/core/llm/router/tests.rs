@@ -195,6 +195,7 @@ impl LLMProvider for MockProvider {
                     finish_reason: None,
                     usage: None,
                     index: i as u32,
+                    tool_calls: None,
                 };
                 if tx.send(Ok(chunk)).await.is_err() {
                     break;
@@ -210,6 +211,7 @@ impl LLMProvider for MockProvider {
                 finish_reason: Some("stop".to_string()),
                 usage,
                 index: words.len() as u32,
+                tool_calls: None,
             };
             let _ = tx.send(Ok(final_chunk)).await;
         });
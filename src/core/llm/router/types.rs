@@ -87,6 +87,28 @@ impl ChatMessage {
             tool_call_id: None,
         }
     }
+
+    /// Assistant turn that requests tool calls
+    pub fn assistant_with_tool_calls(
+        content: impl Into<String>,
+        tool_calls: Vec<serde_json::Value>,
+    ) -> Self {
+        Self {
+            tool_calls: Some(tool_calls),
+            ..Self::assistant(content)
+        }
+    }
+
+    /// Result of a tool call, answering the call with `tool_call_id`.
+    ///
+    /// Sent with the `tool` role by providers that support tool calling;
+    /// others see it as a user message.
+    pub fn tool_result(tool_call_id: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            tool_call_id: Some(tool_call_id.into()),
+            ..Self::user(content)
+        }
+    }
 }
 
 // ============================================================================
@@ -146,6 +168,11 @@ impl ChatRequest {
         self.provider = Some(provider.into());
         self
     }
+
+    pub fn with_tools(mut self, tools: Vec<serde_json::Value>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
 }
 
 /// Response from a chat completion
@@ -173,4 +200,8 @@ pub struct ChatChunk {
     pub finish_reason: Option<String>,
     pub usage: Option<TokenUsage>,
     pub index: u32,
+    /// Tool calls requested by the model, set on the final chunk by
+    /// providers that stream them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<serde_json::Value>>,
 }
@@ -8,12 +8,15 @@ pub mod llm;
 pub mod campaign_manager;
 pub mod campaign;
 pub mod credentials;
+pub mod vault;
+pub mod backup;
 pub mod personality_base;
 pub mod personality;
 pub mod archetype;
 pub mod session_manager;
 pub mod character_gen;
 pub mod npc_gen;
+pub mod npc_memory;
 pub mod audio;
 pub mod theme;
 pub mod location_gen;
@@ -37,6 +40,7 @@ pub mod streaming;
 pub mod budget;
 pub mod alerts;
 pub mod query_expansion;
+pub mod query_rewrite;
 pub mod input_validator;
 pub mod audit;
 pub mod cost_predictor;
@@ -65,3 +69,6 @@ pub mod preprocess;
 
 // Embedded Meilisearch Core (Wilysearch)
 pub mod wilysearch;
+
+// Tool-calling GM assistant for chat
+pub mod assistant;
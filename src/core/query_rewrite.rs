@@ -0,0 +1,369 @@
+//! Conversational Query Rewriting
+//!
+//! Follow-up questions in chat ("what about at 5th level?") make poor search
+//! queries on their own. Before RAG retrieval the latest message is turned
+//! into a standalone query using the recent conversation: by the LLM when one
+//! answers, otherwise by a heuristic that carries the topic words of the
+//! question being followed up. Messages that already stand alone are searched
+//! as typed.
+
+use crate::core::llm::router::{ChatMessage, ChatRequest, LLMRouter, MessageRole};
+
+/// Most recent messages shown to the LLM when rewriting.
+const MAX_HISTORY_MESSAGES: usize = 6;
+
+/// Longest excerpt of a single message shown to the LLM.
+const MAX_TURN_CHARS: usize = 600;
+
+/// Longest rewritten query accepted from the LLM.
+const MAX_QUERY_CHARS: usize = 300;
+
+/// Follow-ups are short; longer messages carry their own context.
+const MAX_FOLLOW_UP_WORDS: usize = 10;
+
+const REWRITE_PROMPT: &str = "You turn follow-up messages from a tabletop RPG chat \
+into standalone search queries for a rulebook index. Reply with only the query: one \
+line, no quotes, no explanation. Keep the game terms, names and numbers from the \
+conversation that the latest message refers to.";
+
+/// Lead-ins that mark a message as continuing the previous question.
+const FOLLOW_UP_PREFIXES: &[&str] = &[
+    "what about",
+    "how about",
+    "what if",
+    "and",
+    "also",
+    "same for",
+    "same but",
+    "but",
+    "then",
+    "ok and",
+    "okay and",
+];
+
+/// Pronouns that refer back to something said earlier. Demonstratives and
+/// "there"/"one" are left out: "is there a rule for flanking?" stands alone.
+const REFERRING_WORDS: &[&str] = &[
+    "it", "its", "it's", "they", "them", "their", "he", "she", "him", "her",
+];
+
+/// Words dropped when carrying a question's topic over to a follow-up.
+const STOPWORDS: &[&str] = &[
+    "a", "about", "an", "and", "are", "as", "at", "be", "but", "by", "can", "could", "did", "do",
+    "does", "for", "from", "get", "has", "have", "how", "i", "if", "in", "is", "me", "much", "my",
+    "of", "on", "or", "so", "the", "to", "was", "we", "what", "when", "where", "which", "who",
+    "why", "will", "with", "would", "you", "your", "also", "same", "then", "ok", "okay", "many",
+    "should", "tell", "explain", "work", "works", "that", "this", "those", "these", "there", "one",
+    "ones",
+];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// How a search query was derived from the chat message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewriteMethod {
+    /// The message stands alone and is searched as typed
+    Unchanged,
+    /// Topic words from the earlier question were carried over
+    Heuristic,
+    /// The LLM rewrote the message using the conversation
+    Llm,
+}
+
+/// The query used for retrieval.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewrittenQuery {
+    pub query: String,
+    pub method: RewriteMethod,
+}
+
+impl RewrittenQuery {
+    /// Search for `message` as typed.
+    pub fn unchanged(message: &str) -> Self {
+        Self {
+            query: message.trim().to_string(),
+            method: RewriteMethod::Unchanged,
+        }
+    }
+
+    /// Whether the query differs from the message.
+    pub fn is_rewritten(&self) -> bool {
+        self.method != RewriteMethod::Unchanged
+    }
+}
+
+// ============================================================================
+// Rewriter
+// ============================================================================
+
+/// Rewrites follow-up chat messages into standalone search queries.
+pub struct QueryRewriter {
+    llm: Option<LLMRouter>,
+}
+
+impl QueryRewriter {
+    /// Without an LLM, follow-ups are rewritten with the heuristic only.
+    pub fn new(llm: Option<LLMRouter>) -> Self {
+        Self { llm }
+    }
+
+    /// Build the search query for `message`, given the conversation before it.
+    ///
+    /// Only follow-ups (see [`is_follow_up`]) to an earlier user message are
+    /// rewritten. If the LLM fails or gives an unusable answer the heuristic
+    /// is used instead.
+    pub async fn rewrite(&self, message: &str, history: &[ChatMessage]) -> RewrittenQuery {
+        let has_earlier_question = history.iter().any(|m| m.role == MessageRole::User);
+        if !has_earlier_question || !is_follow_up(message) {
+            return RewrittenQuery::unchanged(message);
+        }
+
+        if let Some(ref llm) = self.llm {
+            let request = ChatRequest::new(vec![ChatMessage::user(build_rewrite_prompt(
+                message, history,
+            ))])
+            .with_system(REWRITE_PROMPT)
+            .with_temperature(0.0)
+            .with_max_tokens(64);
+
+            match llm.chat(request).await {
+                Ok(response) => {
+                    if let Some(query) = parse_rewrite(&response.content) {
+                        return RewrittenQuery {
+                            query,
+                            method: RewriteMethod::Llm,
+                        };
+                    }
+                    log::debug!("Query rewrite: unusable LLM reply, using heuristic");
+                }
+                Err(e) => log::debug!("Query rewrite: LLM unavailable ({e}), using heuristic"),
+            }
+        }
+
+        match heuristic_rewrite(message, history) {
+            Some(query) => RewrittenQuery {
+                query,
+                method: RewriteMethod::Heuristic,
+            },
+            None => RewrittenQuery::unchanged(message),
+        }
+    }
+}
+
+// ============================================================================
+// Heuristics
+// ============================================================================
+
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Strip a follow-up lead-in ("what about", "and") from the start of a message.
+fn strip_follow_up_prefix(message: &str) -> &str {
+    let trimmed = message.trim();
+    let lower = trimmed.to_lowercase();
+    for prefix in FOLLOW_UP_PREFIXES {
+        if let Some(rest) = lower.strip_prefix(prefix) {
+            if !rest.starts_with(char::is_alphanumeric) && lower.len() == trimmed.len() {
+                return trimmed[prefix.len()..].trim_start_matches([',', ' ']);
+            }
+        }
+    }
+    trimmed
+}
+
+/// Whether `message` continues an earlier question rather than standing alone.
+///
+/// Short messages that open with a follow-up lead-in ("what about ...",
+/// "and ...") or refer back to something ("does it stack?") count.
+pub fn is_follow_up(message: &str) -> bool {
+    let words = words(message);
+    if words.is_empty() || words.len() > MAX_FOLLOW_UP_WORDS {
+        return false;
+    }
+    strip_follow_up_prefix(message).len() < message.trim().len()
+        || words.iter().any(|w| REFERRING_WORDS.contains(&w.as_str()))
+}
+
+/// Content words of `text`, in order and without repeats.
+fn keywords(text: &str) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    words(text)
+        .into_iter()
+        .filter(|w| !STOPWORDS.contains(&w.as_str()) && !REFERRING_WORDS.contains(&w.as_str()))
+        .filter(|w| seen.insert(w.clone()))
+        .collect()
+}
+
+/// Rewrite a follow-up by prefixing the topic words of the question it
+/// follows: the latest earlier user message that stands alone, or failing
+/// that the latest earlier user message.
+///
+/// Returns `None` when there is no earlier question to draw from.
+pub fn heuristic_rewrite(message: &str, history: &[ChatMessage]) -> Option<String> {
+    let mut earlier = history
+        .iter()
+        .rev()
+        .filter(|m| m.role == MessageRole::User)
+        .take(MAX_HISTORY_MESSAGES);
+    let latest = earlier.clone().next()?;
+    let topic_source = earlier
+        .find(|m| !is_follow_up(&m.content))
+        .unwrap_or(latest);
+
+    let own = keywords(strip_follow_up_prefix(message));
+    let topic: Vec<String> = keywords(&topic_source.content)
+        .into_iter()
+        .filter(|w| !own.contains(w))
+        .collect();
+    if topic.is_empty() {
+        return None;
+    }
+
+    Some(topic.into_iter().chain(own).collect::<Vec<_>>().join(" "))
+}
+
+// ============================================================================
+// LLM Prompt
+// ============================================================================
+
+fn excerpt(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > MAX_TURN_CHARS {
+        let cut: String = text.chars().take(MAX_TURN_CHARS).collect();
+        format!("{cut}...")
+    } else {
+        text
+    }
+}
+
+fn build_rewrite_prompt(message: &str, history: &[ChatMessage]) -> String {
+    let turns: Vec<&ChatMessage> = history
+        .iter()
+        .filter(|m| m.role != MessageRole::System && !m.content.trim().is_empty())
+        .collect();
+    let start = turns.len().saturating_sub(MAX_HISTORY_MESSAGES);
+
+    let mut prompt = String::from("Conversation:\n");
+    for turn in &turns[start..] {
+        let speaker = if turn.role == MessageRole::User {
+            "User"
+        } else {
+            "Assistant"
+        };
+        prompt.push_str(&format!("{speaker}: {}\n", excerpt(&turn.content)));
+    }
+    prompt.push_str(&format!(
+        "\nLatest message: {}\n\nStandalone search query:",
+        excerpt(message)
+    ));
+    prompt
+}
+
+/// Take the query from the LLM's reply: the first non-empty line, without a
+/// label or surrounding quotes.
+fn parse_rewrite(reply: &str) -> Option<String> {
+    let line = reply.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let line = ["Standalone search query:", "Search query:", "Query:"]
+        .iter()
+        .find_map(|label| line.strip_prefix(*label))
+        .unwrap_or(line);
+    let query = line
+        .trim()
+        .trim_matches(|c: char| c == '"' || c == '\'' || c == '`')
+        .trim();
+
+    (!query.is_empty() && query.chars().count() <= MAX_QUERY_CHARS).then(|| query.to_string())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::user("How much damage does Fireball do?"),
+            ChatMessage::assistant("Fireball deals 8d6 fire damage in a 20-foot radius."),
+        ]
+    }
+
+    #[test]
+    fn test_is_follow_up() {
+        assert!(is_follow_up("what about at 5th level?"));
+        assert!(is_follow_up("And for a rogue?"));
+        assert!(is_follow_up("does it stack with bless?"));
+        assert!(!is_follow_up("How does grappling work?"));
+        assert!(!is_follow_up("Android companions in Mothership"));
+        assert!(!is_follow_up("Is there a rule for flanking?"));
+        assert!(!is_follow_up("What does this feat do?"));
+        assert!(!is_follow_up("Which one is better, a longsword or a rapier?"));
+        assert!(!is_follow_up(""));
+    }
+
+    #[test]
+    fn test_heuristic_rewrite_carries_topic() {
+        let query = heuristic_rewrite("what about at 5th level?", &history()).unwrap();
+        assert_eq!(query, "damage fireball 5th level");
+
+        // A chain of follow-ups keeps the original topic
+        let mut chain = history();
+        chain.push(ChatMessage::user("what about at 5th level?"));
+        chain.push(ChatMessage::assistant("At 5th level it deals 10d6."));
+        let query = heuristic_rewrite("and at 7th?", &chain).unwrap();
+        assert_eq!(query, "damage fireball 7th");
+
+        assert!(heuristic_rewrite("what about it?", &[]).is_none());
+    }
+
+    #[test]
+    fn test_parse_rewrite() {
+        assert_eq!(
+            parse_rewrite("\n\"Fireball damage at 5th level\"\n").as_deref(),
+            Some("Fireball damage at 5th level")
+        );
+        assert_eq!(
+            parse_rewrite("Query: grapple rules").as_deref(),
+            Some("grapple rules")
+        );
+        assert!(parse_rewrite("   \n").is_none());
+        assert!(parse_rewrite(&"x".repeat(400)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_without_llm() {
+        let rewriter = QueryRewriter::new(None);
+
+        let standalone = rewriter
+            .rewrite("How does grappling work?", &history())
+            .await;
+        assert_eq!(
+            standalone,
+            RewrittenQuery::unchanged("How does grappling work?")
+        );
+        assert!(!standalone.is_rewritten());
+
+        let flanking = rewriter
+            .rewrite("Is there a rule for flanking?", &history())
+            .await;
+        assert_eq!(flanking.method, RewriteMethod::Unchanged);
+
+        let follow_up = rewriter
+            .rewrite("what about at 5th level?", &history())
+            .await;
+        assert_eq!(follow_up.method, RewriteMethod::Heuristic);
+        assert_eq!(follow_up.query, "damage fireball 5th level");
+
+        let first = rewriter.rewrite("what about it?", &[]).await;
+        assert_eq!(first.method, RewriteMethod::Unchanged);
+    }
+}
@@ -45,7 +45,7 @@ pub type Result<T> = std::result::Result<T, EmbeddingError>;
 /// Configuration for embedding providers
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingConfig {
-    /// Provider type: "ollama", "openai"
+    /// Provider type: "ollama", "openai", "candle"
     pub provider: String,
     /// Model name for embeddings
     pub model: String,
@@ -55,15 +55,26 @@ pub struct EmbeddingConfig {
     pub api_key: Option<String>,
     /// Embedding dimensions (for validation)
     pub dimensions: Option<usize>,
+    /// Local model directory (for candle)
+    #[serde(default)]
+    pub model_path: Option<PathBuf>,
     /// Batch size for processing
     #[serde(default = "default_batch_size")]
     pub batch_size: usize,
+    /// Provider calls per minute allowed to the background backfill of
+    /// chunks stored without vectors (0 = no limit)
+    #[serde(default = "default_backfill_requests_per_minute")]
+    pub backfill_requests_per_minute: u32,
 }
 
 fn default_batch_size() -> usize {
     32
 }
 
+fn default_backfill_requests_per_minute() -> u32 {
+    30
+}
+
 impl Default for EmbeddingConfig {
     fn default() -> Self {
         Self {
@@ -72,7 +83,9 @@ impl Default for EmbeddingConfig {
             endpoint: Some("http://localhost:11434".to_string()),
             api_key: None,
             dimensions: Some(768),
+            model_path: None,
             batch_size: 32,
+            backfill_requests_per_minute: default_backfill_requests_per_minute(),
         }
     }
 }
@@ -96,6 +109,13 @@ pub trait EmbeddingProvider: Send + Sync {
     /// Get provider name
     fn name(&self) -> &str;
 
+    /// Identifier of the model producing the vectors (e.g. "ollama/nomic-embed-text").
+    ///
+    /// Stored with each chunk so vectors from different models are never mixed.
+    fn model_id(&self) -> String {
+        self.name().to_string()
+    }
+
     /// Check if provider is healthy/available
     async fn health_check(&self) -> bool;
 }
@@ -326,6 +346,10 @@ impl EmbeddingProvider for CachedEmbeddingProvider {
         self.provider.name()
     }
 
+    fn model_id(&self) -> String {
+        self.provider.model_id()
+    }
+
     async fn health_check(&self) -> bool {
         self.provider.health_check().await
     }
@@ -23,6 +23,7 @@
 //! - Spell correction: ~2ms
 //! - Parallel searches: ~200-400ms
 //! - RRF fusion: ~1ms
+//! - Reranking (optional): bounded by `RerankConfig::latency_budget_ms`
 
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -30,6 +31,7 @@ use thiserror::Error;
 
 use super::embeddings::{EmbeddingError, EmbeddingProvider};
 use super::fusion::{FusedSearchResult, FusionStrategy, RRFConfig, RRFEngine};
+use super::rerank::{rerank, RerankCandidate, RerankConfig, Reranker};
 use super::synonyms::TTRPGSynonyms;
 use crate::core::query_expansion::QueryExpander;
 use crate::core::search::{SearchClient, SearchDocument};
@@ -106,6 +108,10 @@ pub struct HybridConfig {
     /// Options: "balanced", "keyword_heavy", "semantic_heavy", "vocabulary_optimized"
     #[serde(default)]
     pub fusion_strategy: Option<String>,
+
+    /// Rerank fused results (applied when the engine has a reranker)
+    #[serde(default)]
+    pub rerank: Option<RerankConfig>,
 }
 
 fn default_semantic_weight() -> f32 {
@@ -158,6 +164,7 @@ impl Default for HybridConfig {
             enable_vector_search: true,
             min_score: fusion_config::MIN_SCORE,
             fusion_strategy: Some("vocabulary_optimized".to_string()),
+            rerank: None,
         }
     }
 }
@@ -310,6 +317,20 @@ pub struct HybridSearchResult {
 
     /// Applied query (after expansion/correction)
     pub applied_query: Option<String>,
+
+    /// Reranker score (if the reranking stage rescored this result)
+    #[serde(default)]
+    pub rerank_score: Option<f32>,
+}
+
+impl RerankCandidate for HybridSearchResult {
+    fn rerank_text(&self) -> &str {
+        &self.document.content
+    }
+
+    fn set_rerank_score(&mut self, score: f32) {
+        self.rerank_score = Some(score);
+    }
 }
 
 /// Search response with metadata
@@ -350,6 +371,7 @@ pub struct HybridSearchEngine {
     spell_corrector: SpellCorrector,
     synonyms: TTRPGSynonyms,
     rrf_engine: RRFEngine,
+    reranker: Option<Arc<dyn Reranker>>,
 }
 
 impl HybridSearchEngine {
@@ -368,6 +390,7 @@ impl HybridSearchEngine {
             spell_corrector: SpellCorrector::new(),
             synonyms: TTRPGSynonyms::new(),
             rrf_engine,
+            reranker: None,
         }
     }
 
@@ -382,6 +405,12 @@ impl HybridSearchEngine {
         self
     }
 
+    /// Set reranker (used when `HybridConfig::rerank` is set)
+    pub fn with_reranker(mut self, reranker: Arc<dyn Reranker>) -> Self {
+        self.reranker = Some(reranker);
+        self
+    }
+
     /// Update configuration
     pub fn with_config(mut self, config: HybridConfig) -> Self {
         self.rrf_engine = RRFEngine::new(config.to_rrf_config());
@@ -481,10 +510,9 @@ impl HybridSearchEngine {
             semantic_weight,
         );
 
-        // Step 7: Convert to HybridSearchResult and apply limit
-        let results: Vec<HybridSearchResult> = fused
+        // Step 7: Convert to HybridSearchResult
+        let mut results: Vec<HybridSearchResult> = fused
             .into_iter()
-            .take(options.limit)
             .map(|r| HybridSearchResult {
                 document: r.document,
                 score: r.score,
@@ -492,9 +520,16 @@ impl HybridSearchEngine {
                 semantic_rank: r.semantic_rank,
                 index: r.index,
                 applied_query: expanded.clone(),
+                rerank_score: None,
             })
             .collect();
 
+        // Step 8: Optionally rerank the top candidates, then apply limit
+        if let (Some(reranker), Some(rerank_config)) = (&self.reranker, &self.config.rerank) {
+            results = rerank(reranker.as_ref(), &corrected_query, results, rerank_config).await;
+        }
+        results.truncate(options.limit);
+
         let processing_time = start.elapsed().as_millis() as u64;
 
         // Log performance warning if exceeds target
@@ -17,9 +17,10 @@
 //!
 //! ## Hybrid Search Engine
 //! - `embeddings`: Embedding provider trait and cache
-//! - `providers`: Concrete embedding providers (Ollama, OpenAI)
+//! - `providers`: Concrete embedding providers (Ollama, OpenAI, local candle)
 //! - `fusion`: Reciprocal Rank Fusion (RRF) algorithm for result merging
 //! - `hybrid`: Hybrid search engine combining keyword and semantic search
+//! - `rerank`: Optional reranking of fused results (cross-encoder or LLM judge)
 //! - `synonyms`: TTRPG synonym dictionary for query expansion
 //! - `query`: Unified query enhancement with correction, expansion, and suggestions
 //!
@@ -62,6 +63,7 @@ pub mod fusion;
 pub mod hybrid;
 pub mod providers;
 pub mod query;
+pub mod rerank;
 pub mod synonyms;
 
 // ============================================================================
@@ -115,6 +117,10 @@ pub use query::{
     enhance_query, get_query_hints, get_query_suggestions, CorrectionDetails, EnhancedQuery,
     ExpansionDetails, HintType, QueryEnhancer, SearchHint, TermExpansion, WordCorrection,
 };
+pub use rerank::{
+    create_reranker, LlmReranker, RerankCandidate, RerankConfig, RerankError, Reranker,
+    RerankerConfig,
+};
 pub use synonyms::{
     ClarificationPrompt, DiceNotation, ExpansionInfo, QueryExpansionResult, TTRPGSynonyms,
 };
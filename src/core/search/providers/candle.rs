@@ -0,0 +1,627 @@
+//! Candle Embeddings Provider
+//!
+//! Runs a sentence-transformer (BERT, MiniLM, BGE, E5, nomic-embed-text...)
+//! on the CPU with candle, so vector search works without Ollama or an API
+//! key. The same loader backs `CandleCrossEncoder`, a BERT cross-encoder
+//! reranker (e.g. `ms-marco-MiniLM-L-6-v2`).
+//!
+//! The model directory is a standard Hugging Face export:
+//! - `config.json` — model configuration
+//! - `tokenizer.json` — fast tokenizer
+//! - `model.safetensors` — weights
+//! - `1_Pooling/config.json` — optional sentence-transformers pooling config
+//!
+//! Two architectures are supported: classic BERT (`model_type = "bert"`) and
+//! nomic-bert (`model_type = "nomic_bert"`, e.g. `nomic-embed-text-v1.5`),
+//! which uses rotary position embeddings and a SwiGLU MLP. Cross-encoders
+//! must be BERT models.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use candle_core::{DType, Device, IndexOp, Tensor};
+use candle_nn::{linear, Linear, Module, VarBuilder};
+use candle_transformers::models::bert::{BertModel, Config as BertConfig, DTYPE};
+use candle_transformers::models::nomic_bert::{Config as NomicBertConfig, NomicBertModel};
+use serde::Deserialize;
+use tokenizers::{PaddingStrategy, Tokenizer, TruncationParams};
+
+use crate::core::search::embeddings::{EmbeddingError, EmbeddingProvider, Result};
+use crate::core::search::rerank::{RerankError, Reranker};
+
+/// Longest input (in tokens) fed to the model; BERT-family models top out at 512
+const MAX_SEQUENCE_LENGTH: usize = 512;
+
+/// Texts per forward pass; keeps peak memory bounded on laptops
+const BATCH_SIZE: usize = 16;
+
+// ============================================================================
+// Model Files
+// ============================================================================
+
+/// Subset of `config.json` read before the model is loaded. nomic-bert
+/// configs use GPT-style names for the same fields.
+#[derive(Debug, Deserialize)]
+struct ModelHeader {
+    #[serde(alias = "n_embd")]
+    hidden_size: usize,
+    #[serde(default, alias = "n_positions")]
+    max_position_embeddings: Option<usize>,
+    /// Classification labels (cross-encoders have one, or two with the last = relevant)
+    #[serde(default)]
+    id2label: Option<HashMap<String, String>>,
+}
+
+/// sentence-transformers `1_Pooling/config.json`
+#[derive(Debug, Default, Deserialize)]
+struct PoolingConfig {
+    #[serde(default)]
+    pooling_mode_cls_token: bool,
+}
+
+/// How token embeddings are reduced to one vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pooling {
+    /// Attention-masked mean over all tokens (sentence-transformers default)
+    Mean,
+    /// The `[CLS]` token's hidden state (BGE-style)
+    Cls,
+}
+
+fn model_error(e: impl std::fmt::Display) -> EmbeddingError {
+    EmbeddingError::ApiError(format!("Candle model error: {e}"))
+}
+
+/// Encoder architectures the loader can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Architecture {
+    Bert,
+    NomicBert,
+}
+
+/// Read the architecture from `config.json`, failing early with a useful
+/// message on one the loader cannot run.
+fn model_type(config_path: &Path) -> Result<Architecture> {
+    #[derive(Deserialize)]
+    struct Kind {
+        #[serde(default)]
+        model_type: Option<String>,
+    }
+
+    let kind: Kind = read_json(config_path)?;
+    match kind.model_type.as_deref() {
+        None | Some("bert") => Ok(Architecture::Bert),
+        Some("nomic_bert") => Ok(Architecture::NomicBert),
+        Some(other) => Err(EmbeddingError::NotConfigured(format!(
+            "Unsupported model architecture '{other}' (expected a BERT or nomic-bert model)"
+        ))),
+    }
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        EmbeddingError::NotConfigured(format!("Cannot read {}: {e}", path.display()))
+    })?;
+    serde_json::from_str(&text)
+        .map_err(|e| EmbeddingError::InvalidResponse(format!("{}: {e}", path.display())))
+}
+
+// ============================================================================
+// Candle Provider
+// ============================================================================
+
+/// A loaded sentence encoder of either supported architecture.
+enum Encoder {
+    Bert(BertModel),
+    NomicBert(NomicBertModel),
+}
+
+impl Encoder {
+    /// `(batch, seq, hidden)` token states for a padded batch.
+    fn forward(
+        &self,
+        ids: &Tensor,
+        type_ids: &Tensor,
+        mask: &Tensor,
+    ) -> candle_core::Result<Tensor> {
+        match self {
+            Self::Bert(model) => model.forward(ids, type_ids, Some(mask)),
+            Self::NomicBert(model) => model.forward(ids, Some(type_ids), Some(mask)),
+        }
+    }
+}
+
+struct CandleModel {
+    model: Encoder,
+    tokenizer: Tokenizer,
+    device: Device,
+    pooling: Pooling,
+}
+
+/// Local CPU embedding provider backed by candle
+pub struct CandleEmbeddings {
+    inner: Arc<CandleModel>,
+    model_name: String,
+    dimensions: usize,
+}
+
+impl CandleEmbeddings {
+    /// Load a sentence-transformer from a local model directory.
+    ///
+    /// # Arguments
+    /// * `model_dir` - Directory containing `config.json`, `tokenizer.json`
+    ///   and `model.safetensors`
+    pub fn load(model_dir: impl AsRef<Path>) -> Result<Self> {
+        let model_dir = model_dir.as_ref();
+        let bert = LoadedBert::load(model_dir)?;
+
+        let pooling_path = model_dir.join("1_Pooling").join("config.json");
+        let pooling = if pooling_path.exists() {
+            let pooling: PoolingConfig = read_json(&pooling_path)?;
+            if pooling.pooling_mode_cls_token {
+                Pooling::Cls
+            } else {
+                Pooling::Mean
+            }
+        } else {
+            Pooling::Mean
+        };
+
+        let model = match bert.architecture {
+            Architecture::Bert => Encoder::Bert(bert.load_bert()?),
+            Architecture::NomicBert => {
+                let config: NomicBertConfig = read_json(&bert.config_path)?;
+                Encoder::NomicBert(
+                    NomicBertModel::load(bert.vb.clone(), &config).map_err(model_error)?,
+                )
+            }
+        };
+
+        Ok(Self {
+            inner: Arc::new(CandleModel {
+                model,
+                tokenizer: bert.tokenizer,
+                device: bert.device,
+                pooling,
+            }),
+            model_name: bert.model_name,
+            dimensions: bert.header.hidden_size,
+        })
+    }
+
+    /// Override the pooling strategy detected from the model directory
+    pub fn with_pooling(mut self, pooling: Pooling) -> Self {
+        if let Some(inner) = Arc::get_mut(&mut self.inner) {
+            inner.pooling = pooling;
+        }
+        self
+    }
+
+    /// Name of the loaded model (its directory name)
+    pub fn model_name(&self) -> &str {
+        &self.model_name
+    }
+}
+
+/// Config, tokenizer and weights of a BERT-family model directory.
+struct LoadedBert {
+    architecture: Architecture,
+    config_path: PathBuf,
+    header: ModelHeader,
+    tokenizer: Tokenizer,
+    vb: VarBuilder<'static>,
+    device: Device,
+    model_name: String,
+}
+
+impl LoadedBert {
+    fn load(model_dir: &Path) -> Result<Self> {
+        let config_path = model_dir.join("config.json");
+        let architecture = model_type(&config_path)?;
+        let header: ModelHeader = read_json(&config_path)?;
+
+        let tokenizer = Self::load_tokenizer(
+            &model_dir.join("tokenizer.json"),
+            header
+                .max_position_embeddings
+                .unwrap_or(MAX_SEQUENCE_LENGTH)
+                .min(MAX_SEQUENCE_LENGTH),
+        )?;
+
+        let weights = Self::weights_path(model_dir)?;
+        let device = Device::Cpu;
+        // SAFETY: the safetensors file is memory-mapped read-only and must not
+        // be modified while the model is alive.
+        let vb = unsafe { VarBuilder::from_mmaped_safetensors(&[weights], DTYPE, &device) }
+            .map_err(model_error)?;
+
+        let model_name = model_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "candle".to_string());
+
+        Ok(Self {
+            architecture,
+            config_path,
+            header,
+            tokenizer,
+            vb,
+            device,
+            model_name,
+        })
+    }
+
+    /// Build the classic BERT encoder from these weights.
+    fn load_bert(&self) -> Result<BertModel> {
+        let config: BertConfig = read_json(&self.config_path)?;
+        BertModel::load(self.vb.clone(), &config).map_err(model_error)
+    }
+
+    fn load_tokenizer(path: &Path, max_length: usize) -> Result<Tokenizer> {
+        let mut tokenizer = Tokenizer::from_file(path).map_err(|e| {
+            EmbeddingError::NotConfigured(format!("Cannot load {}: {e}", path.display()))
+        })?;
+
+        let mut padding = tokenizer.get_padding().cloned().unwrap_or_default();
+        padding.strategy = PaddingStrategy::BatchLongest;
+        tokenizer.with_padding(Some(padding));
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length,
+                ..Default::default()
+            }))
+            .map_err(model_error)?;
+
+        Ok(tokenizer)
+    }
+
+    fn weights_path(model_dir: &Path) -> Result<PathBuf> {
+        let path = model_dir.join("model.safetensors");
+        if path.exists() {
+            Ok(path)
+        } else {
+            Err(EmbeddingError::NotConfigured(format!(
+                "No model.safetensors in {}",
+                model_dir.display()
+            )))
+        }
+    }
+}
+
+impl CandleModel {
+    /// Tokenize, run the encoder and pool one batch of texts.
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts.to_vec(), true)
+            .map_err(model_error)?;
+
+        let (ids, type_ids, mask) = batch_tensors(&encodings, &self.device).map_err(model_error)?;
+
+        let hidden = self
+            .model
+            .forward(&ids, &type_ids, &mask)
+            .map_err(model_error)?;
+        let pooled = pool(&hidden, &mask, self.pooling).map_err(model_error)?;
+
+        // Unit vectors, so cosine similarity in the HNSW index is a dot product
+        l2_normalize(&pooled)
+            .and_then(|t| t.to_vec2::<f32>())
+            .map_err(model_error)
+    }
+}
+
+/// Stack padded encodings into `(batch, seq)` id, type-id and mask tensors.
+fn batch_tensors(
+    encodings: &[tokenizers::Encoding],
+    device: &Device,
+) -> candle_core::Result<(Tensor, Tensor, Tensor)> {
+    let batch = encodings.len();
+    let seq_len = encodings.first().map(|e| e.len()).unwrap_or(0);
+    let mut ids = Vec::with_capacity(batch * seq_len);
+    let mut type_ids = Vec::with_capacity(batch * seq_len);
+    let mut mask = Vec::with_capacity(batch * seq_len);
+    for encoding in encodings {
+        ids.extend_from_slice(encoding.get_ids());
+        type_ids.extend_from_slice(encoding.get_type_ids());
+        mask.extend_from_slice(encoding.get_attention_mask());
+    }
+
+    let shape = (batch, seq_len);
+    Ok((
+        Tensor::from_vec(ids, shape, device)?,
+        Tensor::from_vec(type_ids, shape, device)?,
+        Tensor::from_vec(mask, shape, device)?,
+    ))
+}
+
+/// Reduce `(batch, seq, hidden)` token states to `(batch, hidden)`.
+fn pool(hidden: &Tensor, mask: &Tensor, pooling: Pooling) -> candle_core::Result<Tensor> {
+    match pooling {
+        Pooling::Cls => hidden.i((.., 0)),
+        Pooling::Mean => {
+            let mask = mask.to_dtype(DType::F32)?.unsqueeze(2)?;
+            let summed = hidden.broadcast_mul(&mask)?.sum(1)?;
+            let counts = mask.sum(1)?.clamp(1e-9, f64::MAX)?;
+            summed.broadcast_div(&counts)
+        }
+    }
+}
+
+fn l2_normalize(vectors: &Tensor) -> candle_core::Result<Tensor> {
+    let norms = vectors
+        .sqr()?
+        .sum_keepdim(1)?
+        .sqrt()?
+        .clamp(1e-12, f64::MAX)?;
+    vectors.broadcast_div(&norms)
+}
+
+#[async_trait]
+impl EmbeddingProvider for CandleEmbeddings {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.embed_batch(&[text])
+            .await?
+            .pop()
+            .ok_or_else(|| EmbeddingError::InvalidResponse("Empty embedding batch".to_string()))
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Inference is CPU-bound; keep it off the async runtime
+        let inner = self.inner.clone();
+        let texts: Vec<String> = texts.iter().map(|t| t.to_string()).collect();
+        tokio::task::spawn_blocking(move || {
+            let mut embeddings = Vec::with_capacity(texts.len());
+            for chunk in texts.chunks(BATCH_SIZE) {
+                embeddings.extend(inner.embed_batch(chunk)?);
+            }
+            Ok(embeddings)
+        })
+        .await
+        .map_err(|e| EmbeddingError::ApiError(format!("Embedding task failed: {e}")))?
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn name(&self) -> &str {
+        "candle"
+    }
+
+    fn model_id(&self) -> String {
+        format!("candle/{}", self.model_name)
+    }
+
+    async fn health_check(&self) -> bool {
+        // The model is loaded in-process; if construction succeeded it is available
+        true
+    }
+}
+
+// ============================================================================
+// Cross-encoder Reranker
+// ============================================================================
+
+struct CrossEncoderModel {
+    model: BertModel,
+    pooler: Linear,
+    classifier: Linear,
+    tokenizer: Tokenizer,
+    device: Device,
+    /// Logit column holding the relevance score
+    label: usize,
+}
+
+/// BERT cross-encoder reranker (e.g. `ms-marco-MiniLM-L-6-v2`) run on the CPU
+pub struct CandleCrossEncoder {
+    inner: Arc<CrossEncoderModel>,
+    model_name: String,
+}
+
+impl CandleCrossEncoder {
+    /// Load a cross-encoder from a local model directory.
+    ///
+    /// Expects a `BertForSequenceClassification` export: the encoder plus
+    /// `bert.pooler.dense` and `classifier` weights.
+    pub fn load(model_dir: impl AsRef<Path>) -> Result<Self> {
+        let bert = LoadedBert::load(model_dir.as_ref())?;
+        if bert.architecture != Architecture::Bert {
+            return Err(EmbeddingError::NotConfigured(
+                "Cross-encoder rerankers must be BERT models".to_string(),
+            ));
+        }
+        let hidden = bert.header.hidden_size;
+        let labels = bert
+            .header
+            .id2label
+            .as_ref()
+            .map(|labels| labels.len())
+            .unwrap_or(1)
+            .max(1);
+
+        let model = bert.load_bert()?;
+        let pooler =
+            linear(hidden, hidden, bert.vb.pp("bert.pooler.dense")).map_err(model_error)?;
+        let classifier = linear(hidden, labels, bert.vb.pp("classifier")).map_err(model_error)?;
+
+        Ok(Self {
+            inner: Arc::new(CrossEncoderModel {
+                model,
+                pooler,
+                classifier,
+                tokenizer: bert.tokenizer,
+                device: bert.device,
+                label: labels - 1,
+            }),
+            model_name: bert.model_name,
+        })
+    }
+
+    /// Name of the loaded model (its directory name)
+    pub fn model_name(&self) -> &str {
+        &self.model_name
+    }
+}
+
+impl CrossEncoderModel {
+    /// Relevance logits for `(query, passage)` pairs.
+    fn score_batch(&self, query: &str, passages: &[String]) -> Result<Vec<f32>> {
+        let pairs: Vec<(String, String)> = passages
+            .iter()
+            .map(|p| (query.to_string(), p.clone()))
+            .collect();
+        let encodings = self
+            .tokenizer
+            .encode_batch(pairs, true)
+            .map_err(model_error)?;
+        let (ids, type_ids, mask) = batch_tensors(&encodings, &self.device).map_err(model_error)?;
+
+        let hidden = self
+            .model
+            .forward(&ids, &type_ids, Some(&mask))
+            .map_err(model_error)?;
+        hidden
+            .i((.., 0))
+            .and_then(|cls| self.pooler.forward(&cls))
+            .and_then(|pooled| pooled.tanh())
+            .and_then(|pooled| self.classifier.forward(&pooled))
+            .and_then(|logits| logits.i((.., self.label)))
+            .and_then(|scores| scores.to_vec1::<f32>())
+            .map_err(model_error)
+    }
+}
+
+#[async_trait]
+impl Reranker for CandleCrossEncoder {
+    fn name(&self) -> &str {
+        "candle"
+    }
+
+    async fn score(
+        &self,
+        query: &str,
+        passages: &[&str],
+    ) -> std::result::Result<Vec<f32>, RerankError> {
+        if passages.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let inner = self.inner.clone();
+        let query = query.to_string();
+        let passages: Vec<String> = passages.iter().map(|p| p.to_string()).collect();
+        tokio::task::spawn_blocking(move || -> Result<Vec<f32>> {
+            let mut scores = Vec::with_capacity(passages.len());
+            for chunk in passages.chunks(BATCH_SIZE) {
+                scores.extend(inner.score_batch(&query, chunk)?);
+            }
+            Ok(scores)
+        })
+        .await
+        .map_err(|e| RerankError::Model(format!("Rerank task failed: {e}")))?
+        .map_err(|e| RerankError::Model(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mean_pooling_ignores_padding() {
+        let device = Device::Cpu;
+        // batch=1, seq=3, hidden=2; the last token is padding
+        let hidden = Tensor::new(&[[[1f32, 2.], [3., 4.], [100., 100.]]], &device).unwrap();
+        let mask = Tensor::new(&[[1u32, 1, 0]], &device).unwrap();
+
+        let pooled = pool(&hidden, &mask, Pooling::Mean).unwrap();
+        assert_eq!(pooled.to_vec2::<f32>().unwrap(), vec![vec![2., 3.]]);
+
+        let cls = pool(&hidden, &mask, Pooling::Cls).unwrap();
+        assert_eq!(cls.to_vec2::<f32>().unwrap(), vec![vec![1., 2.]]);
+    }
+
+    #[test]
+    fn test_l2_normalize() {
+        let device = Device::Cpu;
+        let vectors = Tensor::new(&[[3f32, 4.], [0., 0.]], &device).unwrap();
+        let normalized = l2_normalize(&vectors).unwrap().to_vec2::<f32>().unwrap();
+        assert!((normalized[0][0] - 0.6).abs() < 1e-6);
+        assert!((normalized[0][1] - 0.8).abs() < 1e-6);
+        assert_eq!(normalized[1], vec![0., 0.]);
+    }
+
+    #[test]
+    fn test_load_missing_directory() {
+        let err = CandleEmbeddings::load("/nonexistent/model").err().unwrap();
+        assert!(matches!(err, EmbeddingError::NotConfigured(_)));
+
+        let err = CandleCrossEncoder::load("/nonexistent/model").err().unwrap();
+        assert!(matches!(err, EmbeddingError::NotConfigured(_)));
+    }
+
+    #[test]
+    fn test_load_reads_nomic_bert_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_path = dir.path().join("config.json");
+        std::fs::write(
+            &config_path,
+            r#"{
+                "model_type": "nomic_bert",
+                "activation_function": "swiglu",
+                "layer_norm_epsilon": 1e-12,
+                "max_trained_positions": 2048,
+                "mlp_fc1_bias": false,
+                "mlp_fc2_bias": false,
+                "n_embd": 768,
+                "n_head": 12,
+                "n_inner": 3072,
+                "n_layer": 12,
+                "n_positions": 8192,
+                "prenorm": false,
+                "qkv_proj_bias": false,
+                "rotary_emb_base": 1000,
+                "rotary_emb_fraction": 1.0,
+                "rotary_emb_interleaved": false,
+                "rotary_scaling_factor": null,
+                "type_vocab_size": 2,
+                "vocab_size": 30528
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(model_type(&config_path).unwrap(), Architecture::NomicBert);
+        let header: ModelHeader = read_json(&config_path).unwrap();
+        assert_eq!(header.hidden_size, 768);
+        assert_eq!(header.max_position_embeddings, Some(8192));
+        let config: NomicBertConfig = read_json(&config_path).unwrap();
+        assert_eq!(config.n_layer, 12);
+
+        // Loading gets past the architecture check to the missing tokenizer
+        match CandleEmbeddings::load(dir.path()).err().unwrap() {
+            EmbeddingError::NotConfigured(message) => {
+                assert!(message.contains("tokenizer.json"), "{message}");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_architecture() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("config.json"), r#"{"model_type": "t5"}"#).unwrap();
+
+        match CandleEmbeddings::load(dir.path()).err().unwrap() {
+            EmbeddingError::NotConfigured(message) => {
+                assert!(message.contains("'t5'"), "{message}");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+}
@@ -2,9 +2,11 @@
 //!
 //! Concrete implementations of the EmbeddingProvider trait.
 
+pub mod candle;
 pub mod ollama;
 pub mod openai;
 
+pub use candle::{CandleCrossEncoder, CandleEmbeddings, Pooling};
 pub use ollama::OllamaEmbeddings;
 pub use openai::OpenAIEmbeddings;
 
@@ -36,6 +38,22 @@ pub fn create_provider(config: &EmbeddingConfig) -> Result<Arc<dyn EmbeddingProv
                 config.endpoint.clone(),
             )))
         }
+        "candle" => {
+            let model_path = config.model_path.as_ref().ok_or_else(|| {
+                EmbeddingError::NotConfigured("Candle model directory required".to_string())
+            })?;
+            let provider = CandleEmbeddings::load(model_path)?;
+            if let Some(expected) = config.dimensions {
+                if expected != provider.dimensions() {
+                    log::warn!(
+                        "Configured embedding dimensions ({}) differ from model ({}); using model",
+                        expected,
+                        provider.dimensions()
+                    );
+                }
+            }
+            Ok(Arc::new(provider))
+        }
         _ => Err(EmbeddingError::NotConfigured(format!(
             "Unknown embedding provider: {}",
             config.provider
@@ -148,6 +148,10 @@ impl EmbeddingProvider for OllamaEmbeddings {
         "ollama"
     }
 
+    fn model_id(&self) -> String {
+        format!("ollama/{}", self.model)
+    }
+
     async fn health_check(&self) -> bool {
         let url = format!("{}/api/tags", self.base_url);
         match self.client.get(&url).send().await {
@@ -175,6 +179,7 @@ mod tests {
             Some(768),
         );
         assert_eq!(provider.name(), "ollama");
+        assert_eq!(provider.model_id(), "ollama/nomic-embed-text");
         assert_eq!(provider.dimensions(), 768);
     }
 }
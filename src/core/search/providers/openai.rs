@@ -194,6 +194,10 @@ impl EmbeddingProvider for OpenAIEmbeddings {
         "openai"
     }
 
+    fn model_id(&self) -> String {
+        format!("openai/{}", self.model)
+    }
+
     async fn health_check(&self) -> bool {
         // Simple check - try to list models
         let url = format!("{}/models", self.base_url);
@@ -0,0 +1,390 @@
+//! Reranking Stage
+//!
+//! Fusion (RRF or weighted) only knows ranks and keyword/vector scores, so a
+//! chunk that merely mentions "grapple" can outrank the chunk that defines it.
+//! A reranker reads the query and each passage together and rescores the top
+//! fused candidates.
+//!
+//! Reranking is optional and bounded: it only touches the first `top_n`
+//! candidates, must finish within the preset's latency budget, and on error or
+//! timeout the fused order is returned unchanged.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::llm::router::{ChatMessage, ChatRequest, LLMRouter};
+use crate::core::search::providers::CandleCrossEncoder;
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Error, Debug)]
+pub enum RerankError {
+    #[error("Reranker model error: {0}")]
+    Model(String),
+
+    #[error("LLM error: {0}")]
+    Llm(String),
+
+    #[error("Invalid reranker response: {0}")]
+    InvalidResponse(String),
+}
+
+pub type Result<T> = std::result::Result<T, RerankError>;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Per-preset reranking settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RerankConfig {
+    /// Fused candidates passed to the reranker; the rest keep their order
+    #[serde(default = "default_top_n")]
+    pub top_n: usize,
+
+    /// Time allowed for reranking before falling back to the fused order
+    #[serde(default = "default_latency_budget_ms")]
+    pub latency_budget_ms: u64,
+}
+
+fn default_top_n() -> usize {
+    20
+}
+
+fn default_latency_budget_ms() -> u64 {
+    1500
+}
+
+impl Default for RerankConfig {
+    fn default() -> Self {
+        Self {
+            top_n: default_top_n(),
+            latency_budget_ms: default_latency_budget_ms(),
+        }
+    }
+}
+
+impl RerankConfig {
+    /// Rules lookups: rescore a wide candidate set, definitions often rank low after fusion
+    pub fn for_rules() -> Self {
+        Self {
+            top_n: 25,
+            latency_budget_ms: 2000,
+        }
+    }
+
+    /// Lore lookups: semantic fusion is already good, so rescore fewer candidates
+    pub fn for_lore() -> Self {
+        Self {
+            top_n: 12,
+            latency_budget_ms: 1200,
+        }
+    }
+
+    pub fn with_top_n(mut self, top_n: usize) -> Self {
+        self.top_n = top_n;
+        self
+    }
+
+    pub fn with_latency_budget_ms(mut self, budget_ms: u64) -> Self {
+        self.latency_budget_ms = budget_ms;
+        self
+    }
+
+    pub fn latency_budget(&self) -> Duration {
+        Duration::from_millis(self.latency_budget_ms)
+    }
+}
+
+/// Which reranker backs the reranking stage (`[rerank]` in config.toml).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RerankerConfig {
+    /// Backend: "none", "candle" (local cross-encoder) or "llm" (LLM-as-judge)
+    pub backend: String,
+    /// Cross-encoder model directory (for candle)
+    pub model_path: Option<PathBuf>,
+}
+
+impl Default for RerankerConfig {
+    fn default() -> Self {
+        Self {
+            backend: "none".to_string(),
+            model_path: None,
+        }
+    }
+}
+
+/// Create the configured reranker, or `None` when reranking is off.
+pub fn create_reranker(
+    config: &RerankerConfig,
+    router: &LLMRouter,
+) -> Result<Option<Arc<dyn Reranker>>> {
+    match config.backend.to_lowercase().as_str() {
+        "" | "none" => Ok(None),
+        "candle" => {
+            let model_path = config.model_path.as_ref().ok_or_else(|| {
+                RerankError::Model("Cross-encoder model directory required".to_string())
+            })?;
+            let reranker =
+                CandleCrossEncoder::load(model_path).map_err(|e| RerankError::Model(e.to_string()))?;
+            Ok(Some(Arc::new(reranker)))
+        }
+        "llm" => Ok(Some(Arc::new(LlmReranker::new(router.clone())))),
+        other => Err(RerankError::Model(format!("Unknown reranker backend: {other}"))),
+    }
+}
+
+// ============================================================================
+// Reranker Trait
+// ============================================================================
+
+/// Scores passages for relevance to a query (higher = more relevant).
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    /// Reranker name for logging
+    fn name(&self) -> &str;
+
+    /// One score per passage, in input order
+    async fn score(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>>;
+}
+
+/// A search result the reranking stage can read and rescore.
+pub trait RerankCandidate {
+    /// Text shown to the reranker
+    fn rerank_text(&self) -> &str;
+
+    /// Record the reranker's score on the result
+    fn set_rerank_score(&mut self, score: f32);
+}
+
+/// Rerank the first `config.top_n` candidates.
+///
+/// Returns the candidates reordered by reranker score, followed by the
+/// untouched tail. On error, timeout or a malformed response the fused
+/// order is returned as-is.
+pub async fn rerank<T: RerankCandidate>(
+    reranker: &dyn Reranker,
+    query: &str,
+    mut candidates: Vec<T>,
+    config: &RerankConfig,
+) -> Vec<T> {
+    let n = config.top_n.min(candidates.len());
+    if n < 2 {
+        return candidates;
+    }
+
+    let start = Instant::now();
+    let scores = {
+        let passages: Vec<&str> = candidates[..n].iter().map(|c| c.rerank_text()).collect();
+        tokio::time::timeout(config.latency_budget(), reranker.score(query, &passages)).await
+    };
+
+    let scores = match scores {
+        Ok(Ok(scores)) if scores.len() == n => scores,
+        Ok(Ok(scores)) => {
+            log::warn!(
+                "Reranker {} returned {} scores for {} passages; keeping fused order",
+                reranker.name(),
+                scores.len(),
+                n
+            );
+            return candidates;
+        }
+        Ok(Err(e)) => {
+            log::warn!("Reranker {} failed: {e}; keeping fused order", reranker.name());
+            return candidates;
+        }
+        Err(_) => {
+            log::warn!(
+                "Reranker {} exceeded {}ms budget; keeping fused order",
+                reranker.name(),
+                config.latency_budget_ms
+            );
+            return candidates;
+        }
+    };
+
+    let tail = candidates.split_off(n);
+    let mut head: Vec<(T, f32)> = candidates.into_iter().zip(scores).collect();
+    // Stable sort keeps the fused order among equal scores
+    head.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    log::debug!(
+        "Reranked {} candidates with {} in {}ms",
+        n,
+        reranker.name(),
+        start.elapsed().as_millis()
+    );
+
+    head.into_iter()
+        .map(|(mut candidate, score)| {
+            candidate.set_rerank_score(score);
+            candidate
+        })
+        .chain(tail)
+        .collect()
+}
+
+// ============================================================================
+// LLM-as-judge Reranker
+// ============================================================================
+
+/// Longest passage excerpt sent to the judge model
+const LLM_PASSAGE_CHARS: usize = 600;
+
+const LLM_RERANK_PROMPT: &str = "You rank passages from tabletop RPG books by how well they \
+answer a question. A passage that defines or states the rule ranks above one that only \
+mentions the term. Reply with only a JSON array of integer scores from 0 (irrelevant) to \
+10 (directly answers), one per passage, in order.";
+
+/// Reranker that asks a chat model to grade passages.
+pub struct LlmReranker {
+    router: LLMRouter,
+}
+
+impl LlmReranker {
+    pub fn new(router: LLMRouter) -> Self {
+        Self { router }
+    }
+
+    fn build_prompt(query: &str, passages: &[&str]) -> String {
+        let mut prompt = format!("Question: {query}\n\n");
+        for (i, passage) in passages.iter().enumerate() {
+            let excerpt: String = passage.chars().take(LLM_PASSAGE_CHARS).collect();
+            prompt.push_str(&format!("[{}] {}\n\n", i + 1, excerpt.trim()));
+        }
+        prompt.push_str(&format!("Scores for passages 1-{}:", passages.len()));
+        prompt
+    }
+
+    /// Pull the first JSON array of numbers out of the model's reply.
+    fn parse_scores(reply: &str, expected: usize) -> Result<Vec<f32>> {
+        let start = reply.find('[');
+        let end = reply.rfind(']');
+        let (Some(start), Some(end)) = (start, end) else {
+            return Err(RerankError::InvalidResponse(format!("no score array in '{reply}'")));
+        };
+        if end < start {
+            return Err(RerankError::InvalidResponse(format!("no score array in '{reply}'")));
+        }
+
+        let scores: Vec<f32> = serde_json::from_str(&reply[start..=end])
+            .map_err(|e| RerankError::InvalidResponse(e.to_string()))?;
+        if scores.len() != expected {
+            return Err(RerankError::InvalidResponse(format!(
+                "expected {expected} scores, got {}",
+                scores.len()
+            )));
+        }
+        Ok(scores)
+    }
+}
+
+#[async_trait]
+impl Reranker for LlmReranker {
+    fn name(&self) -> &str {
+        "llm"
+    }
+
+    async fn score(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>> {
+        let request = ChatRequest::new(vec![ChatMessage::user(Self::build_prompt(query, passages))])
+            .with_system(LLM_RERANK_PROMPT)
+            .with_temperature(0.0)
+            .with_max_tokens(16 + passages.len() as u32 * 4);
+
+        let response = self
+            .router
+            .chat(request)
+            .await
+            .map_err(|e| RerankError::Llm(e.to_string()))?;
+        Self::parse_scores(&response.content, passages.len())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Doc {
+        text: &'static str,
+        score: Option<f32>,
+    }
+
+    impl RerankCandidate for Doc {
+        fn rerank_text(&self) -> &str {
+            self.text
+        }
+
+        fn set_rerank_score(&mut self, score: f32) {
+            self.score = Some(score);
+        }
+    }
+
+    fn docs(texts: &[&'static str]) -> Vec<Doc> {
+        texts.iter().map(|text| Doc { text, score: None }).collect()
+    }
+
+    /// Scores passages by length; optionally sleeps to blow the budget.
+    struct LengthReranker {
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl Reranker for LengthReranker {
+        fn name(&self) -> &str {
+            "length"
+        }
+
+        async fn score(&self, _query: &str, passages: &[&str]) -> Result<Vec<f32>> {
+            tokio::time::sleep(self.delay).await;
+            Ok(passages.iter().map(|p| p.len() as f32).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rerank_reorders_top_n_only() {
+        let reranker = LengthReranker { delay: Duration::ZERO };
+        let config = RerankConfig::default().with_top_n(3);
+        let result = rerank(&reranker, "q", docs(&["a", "ccc", "bb", "dddd"]), &config).await;
+
+        let texts: Vec<&str> = result.iter().map(|d| d.text).collect();
+        assert_eq!(texts, vec!["ccc", "bb", "a", "dddd"]);
+        assert_eq!(result[0].score, Some(3.0));
+        assert_eq!(result[3].score, None);
+    }
+
+    #[tokio::test]
+    async fn test_rerank_falls_back_on_timeout() {
+        let reranker = LengthReranker { delay: Duration::from_millis(200) };
+        let config = RerankConfig::default().with_latency_budget_ms(10);
+        let result = rerank(&reranker, "q", docs(&["a", "ccc"]), &config).await;
+
+        assert_eq!(result, docs(&["a", "ccc"]));
+    }
+
+    #[test]
+    fn test_llm_parse_scores() {
+        let scores = LlmReranker::parse_scores("Scores: [2, 9, 0]", 3).unwrap();
+        assert_eq!(scores, vec![2.0, 9.0, 0.0]);
+        assert!(LlmReranker::parse_scores("[1, 2]", 3).is_err());
+        assert!(LlmReranker::parse_scores("no idea", 1).is_err());
+    }
+
+    #[test]
+    fn test_presets() {
+        assert!(RerankConfig::for_rules().top_n > RerankConfig::for_lore().top_n);
+    }
+}
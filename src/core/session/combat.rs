@@ -2,13 +2,17 @@
 //!
 //! Extracted from session_manager.rs to provide better cohesion.
 //! Contains combat state, combatant tracking, initiative management,
-//! HP tracking, and combat event logging.
+//! HP tracking, combat event logging, and undo/redo of combat commands.
 
 use chrono::{DateTime, Utc};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::conditions::ConditionTracker;
+use super::history::{CombatCommand, CombatHistory, CombatMemento, HistoryEntry};
+use crate::core::campaign::dice::{DiceNotation, DiceRoller};
+use crate::ingestion::ttrpg::{AbilityScores, StatBlockData};
 
 // ============================================================================
 // Combat Types
@@ -31,6 +35,16 @@ pub enum CombatantType {
     Environment,
 }
 
+/// How a stat block's hit points are set when it joins combat.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum HitPointMode {
+    /// Use the printed average
+    #[default]
+    Average,
+    /// Roll the hit-dice formula (falls back to the average if unparseable)
+    Roll,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CombatEventType {
     Attack,
@@ -44,6 +58,8 @@ pub enum CombatEventType {
     Reaction,
     Death,
     Stabilized,
+    Undo,
+    Redo,
     Other,
 }
 
@@ -172,6 +188,73 @@ impl Combatant {
         let target = condition_name.to_lowercase();
         self.condition_immunities.retain(|i| i.to_lowercase() != target);
     }
+
+    /// Create a monster combatant from a parsed stat block.
+    ///
+    /// Initiative is rolled as d20 + DEX modifier; HP follows `hp_mode`.
+    /// AC and condition immunities are copied from the block.
+    pub fn from_stat_block<R: Rng>(
+        name: impl Into<String>,
+        block: &StatBlockData,
+        hp_mode: HitPointMode,
+        rng: &mut R,
+    ) -> Self {
+        let dex_mod = block
+            .ability_scores
+            .dexterity
+            .map(AbilityScores::modifier)
+            .unwrap_or(0);
+        let initiative = rng.gen_range(1..=20) + dex_mod;
+
+        let mut combatant = Self::new(name, initiative, CombatantType::Monster);
+        combatant.initiative_modifier = dex_mod;
+        combatant.armor_class = block.armor_class.as_ref().map(|ac| ac.value);
+
+        if let Some(ref hp) = block.hit_points {
+            let rolled = match (hp_mode, hp.formula.as_deref()) {
+                (HitPointMode::Roll, Some(formula)) => DiceNotation::parse(&formula.replace(' ', ""))
+                    .ok()
+                    .map(|notation| DiceRoller::new().roll_with_rng(&notation, rng).total.max(1)),
+                _ => None,
+            };
+            let max_hp = rolled.unwrap_or(hp.average);
+            combatant.max_hp = Some(max_hp);
+            combatant.current_hp = Some(max_hp);
+        }
+
+        for immunity in &block.condition_immunities {
+            combatant.add_immunity(immunity.as_str());
+        }
+
+        combatant
+    }
+}
+
+/// Names for `count` new copies of `base`, numbered after any already present.
+///
+/// A single copy of a creature not yet in the fight keeps its plain name;
+/// otherwise copies are numbered ("Goblin 1".."Goblin 4"), continuing from the
+/// highest existing number (an unnumbered "Goblin" counts as 1).
+pub fn numbered_names(base: &str, count: usize, existing: &[Combatant]) -> Vec<String> {
+    let highest = existing
+        .iter()
+        .filter_map(|c| {
+            if c.name == base {
+                Some(1)
+            } else {
+                c.name
+                    .strip_prefix(base)
+                    .and_then(|rest| rest.strip_prefix(' '))
+                    .and_then(|n| n.parse::<usize>().ok())
+            }
+        })
+        .max();
+
+    match highest {
+        None if count == 1 => vec![base.to_string()],
+        None => (1..=count).map(|n| format!("{base} {n}")).collect(),
+        Some(h) => (h + 1..=h + count).map(|n| format!("{base} {n}")).collect(),
+    }
 }
 
 // ============================================================================
@@ -187,6 +270,9 @@ pub struct CombatState {
     pub started_at: DateTime<Utc>,
     pub status: CombatStatus,
     pub events: Vec<CombatEvent>,
+    /// Undo/redo stacks (kept for the running app only, not persisted)
+    #[serde(skip)]
+    history: CombatHistory,
 }
 
 /// Result of advancing a turn, containing the new current combatant
@@ -208,6 +294,7 @@ impl CombatState {
             started_at: Utc::now(),
             status: CombatStatus::Active,
             events: vec![],
+            history: CombatHistory::default(),
         }
     }
 
@@ -227,6 +314,29 @@ impl CombatState {
         self.sort_initiative();
     }
 
+    /// Add `count` copies of a stat block with auto-numbered names
+    /// Keeps the current turn on the same combatant when joining mid-fight
+    /// Returns the combatants that were added
+    pub fn add_from_stat_block<R: Rng>(
+        &mut self,
+        block: &StatBlockData,
+        count: usize,
+        hp_mode: HitPointMode,
+        rng: &mut R,
+    ) -> Vec<Combatant> {
+        let current_id = self.current_combatant().map(|c| c.id.clone());
+        let added: Vec<Combatant> = numbered_names(&block.name, count, &self.combatants)
+            .into_iter()
+            .map(|name| Combatant::from_stat_block(name, block, hp_mode, rng))
+            .collect();
+        self.combatants.extend(added.iter().cloned());
+        self.sort_initiative();
+        if let Some(pos) = current_id.and_then(|id| self.combatants.iter().position(|c| c.id == id)) {
+            self.current_turn = pos;
+        }
+        added
+    }
+
     /// Remove a combatant by ID
     /// Adjusts current_turn if needed
     /// Returns the removed combatant if found
@@ -409,6 +519,134 @@ impl CombatState {
         });
     }
 
+    // ========================================================================
+    // Undo / Redo
+    // ========================================================================
+
+    /// Run a reversible command, log it, and push it onto the undo stack
+    /// Returns false (recording nothing) if the target combatant is missing
+    pub fn execute(&mut self, command: CombatCommand) -> bool {
+        let touched = command.touched_ids(self);
+        let before = CombatMemento::capture(self, &touched);
+
+        let Some((actor, event_type, label)) = self.apply_command(command) else {
+            return false;
+        };
+        self.log_event(actor, event_type, label.clone());
+
+        let after = CombatMemento::capture(self, &touched);
+        self.history.record(HistoryEntry { label, before, after });
+        true
+    }
+
+    /// Undo the most recent command
+    /// Returns the label of the undone command
+    pub fn undo(&mut self) -> Option<String> {
+        let entry = self.history.pop_undo()?;
+        entry.before.restore(self);
+        self.log_event("GM", CombatEventType::Undo, format!("Undid: {}", entry.label));
+        let label = entry.label.clone();
+        self.history.push_redo(entry);
+        Some(label)
+    }
+
+    /// Redo the most recently undone command
+    /// Returns the label of the redone command
+    pub fn redo(&mut self) -> Option<String> {
+        let entry = self.history.pop_redo()?;
+        entry.after.restore(self);
+        self.log_event("GM", CombatEventType::Redo, format!("Redid: {}", entry.label));
+        let label = entry.label.clone();
+        self.history.push_undo(entry);
+        Some(label)
+    }
+
+    /// Undo/redo stacks for display
+    pub fn history(&self) -> &CombatHistory {
+        &self.history
+    }
+
+    /// Perform a command's mutation
+    /// Returns (actor, event type, description) for the log and history
+    fn apply_command(&mut self, command: CombatCommand) -> Option<(String, CombatEventType, String)> {
+        match command {
+            CombatCommand::Damage { combatant_id, amount } => {
+                let combatant = self.get_combatant_mut(&combatant_id)?;
+                combatant.apply_damage(amount);
+                let name = combatant.name.clone();
+                let label = format!("{} takes {} damage", name, amount);
+                Some((name, CombatEventType::Damage, label))
+            }
+            CombatCommand::Heal { combatant_id, amount } => {
+                let combatant = self.get_combatant_mut(&combatant_id)?;
+                combatant.heal(amount);
+                let name = combatant.name.clone();
+                let label = format!("{} heals {} HP", name, amount);
+                Some((name, CombatEventType::Healing, label))
+            }
+            CombatCommand::AddTempHp { combatant_id, amount } => {
+                let combatant = self.get_combatant_mut(&combatant_id)?;
+                combatant.add_temp_hp(amount);
+                let name = combatant.name.clone();
+                let label = format!("{} gains {} temp HP", name, amount);
+                Some((name, CombatEventType::Other, label))
+            }
+            CombatCommand::AddCondition { combatant_id, condition } => {
+                let combatant = self.get_combatant_mut(&combatant_id)?;
+                let name = combatant.name.clone();
+                if combatant.is_immune_to(&condition.name) {
+                    let label = format!("{} is immune to {}", name, condition.name);
+                    return Some((name, CombatEventType::ConditionApplied, label));
+                }
+                let condition_name = condition.name.clone();
+                match combatant.condition_tracker.add_condition(condition) {
+                    Ok(()) => {
+                        let label = format!("{} gains condition: {}", name, condition_name);
+                        Some((name, CombatEventType::ConditionApplied, label))
+                    }
+                    Err(msg) => {
+                        let label = format!("Condition not applied to {}: {}", name, msg);
+                        Some((name, CombatEventType::Other, label))
+                    }
+                }
+            }
+            CombatCommand::RemoveCondition { combatant_id, condition_id } => {
+                let combatant = self.get_combatant_mut(&combatant_id)?;
+                let removed = combatant.condition_tracker.remove_condition(&condition_id)?;
+                let name = combatant.name.clone();
+                let label = format!("{} loses condition: {}", name, removed.name);
+                Some((name, CombatEventType::ConditionRemoved, label))
+            }
+            CombatCommand::SetInitiative { combatant_id, initiative } => {
+                let current_id = self.current_combatant().map(|c| c.id.clone());
+                let combatant = self.get_combatant_mut(&combatant_id)?;
+                combatant.initiative = initiative;
+                let name = combatant.name.clone();
+                self.sort_initiative();
+                if let Some(pos) = current_id.and_then(|id| self.combatants.iter().position(|c| c.id == id)) {
+                    self.current_turn = pos;
+                }
+                let label = format!("{} initiative set to {}", name, initiative);
+                Some((name, CombatEventType::Other, label))
+            }
+            CombatCommand::RemoveCombatant { combatant_id } => {
+                let removed = self.remove_combatant(&combatant_id)?;
+                let label = format!("{} removed from combat", removed.name);
+                Some((removed.name, CombatEventType::Other, label))
+            }
+            CombatCommand::NextTurn => {
+                let current = self.next_turn().current_combatant?;
+                let label = format!("Round {}: {}'s turn", self.round, current.name);
+                Some((current.name, CombatEventType::Other, label))
+            }
+            CombatCommand::PreviousTurn => {
+                let current = self.previous_turn()?;
+                let label = format!("Back to round {}: {}'s turn", self.round, current.name);
+                Some((current.name, CombatEventType::Other, label))
+            }
+        }
+    }
+
     /// End the combat
     pub fn end(&mut self) {
         self.status = CombatStatus::Ended;
@@ -521,4 +759,134 @@ mod tests {
         assert_eq!(combat.current_turn, 0);
         assert_eq!(combat.current_combatant().unwrap().name, "Goblin");
     }
+
+    #[test]
+    fn test_undo_redo_damage_and_turn() {
+        let mut combat = CombatState::new();
+        let mut fighter = Combatant::new("Fighter", 18, CombatantType::Player);
+        fighter.current_hp = Some(30);
+        fighter.max_hp = Some(30);
+        let fighter_id = fighter.id.clone();
+        combat.add_combatant(fighter);
+        combat.add_combatant(Combatant::new("Goblin", 15, CombatantType::Monster));
+
+        assert!(combat.execute(CombatCommand::Damage { combatant_id: fighter_id.clone(), amount: 12 }));
+        assert!(combat.execute(CombatCommand::NextTurn));
+        assert_eq!(combat.current_combatant().unwrap().name, "Goblin");
+        assert_eq!(combat.history().undo_labels().count(), 2);
+
+        // Two levels of undo restore the turn and the HP
+        assert_eq!(combat.undo().as_deref(), Some("Round 1: Goblin's turn"));
+        assert_eq!(combat.current_combatant().unwrap().name, "Fighter");
+        combat.undo();
+        assert_eq!(combat.get_combatant(&fighter_id).unwrap().current_hp, Some(30));
+        assert!(combat.undo().is_none());
+
+        combat.redo();
+        assert_eq!(combat.get_combatant(&fighter_id).unwrap().current_hp, Some(18));
+        assert!(combat.history().can_redo());
+
+        // A new action discards the redo stack
+        combat.execute(CombatCommand::Heal { combatant_id: fighter_id.clone(), amount: 5 });
+        assert!(!combat.history().can_redo());
+        assert!(combat.events.iter().any(|e| matches!(e.event_type, CombatEventType::Undo)));
+    }
+
+    #[test]
+    fn test_undo_condition_expiry_and_removal() {
+        use crate::core::session::conditions::{AdvancedCondition, ConditionDuration};
+
+        let mut combat = CombatState::new();
+        let fighter = Combatant::new("Fighter", 18, CombatantType::Player);
+        let fighter_id = fighter.id.clone();
+        let goblin = Combatant::new("Goblin", 15, CombatantType::Monster);
+        let goblin_id = goblin.id.clone();
+        combat.add_combatant(fighter);
+        combat.add_combatant(goblin);
+
+        let blessed = AdvancedCondition::new("Blessed", "+1d4", ConditionDuration::Turns(1));
+        combat.execute(CombatCommand::AddCondition { combatant_id: fighter_id.clone(), condition: blessed });
+        combat.execute(CombatCommand::NextTurn);
+        assert!(combat.get_combatant(&fighter_id).unwrap().condition_tracker.conditions().is_empty());
+
+        // Undoing the turn brings back the condition that expired with it
+        combat.undo();
+        assert_eq!(combat.get_combatant(&fighter_id).unwrap().condition_tracker.conditions().len(), 1);
+
+        combat.execute(CombatCommand::RemoveCombatant { combatant_id: goblin_id.clone() });
+        assert!(combat.get_combatant(&goblin_id).is_none());
+        combat.undo();
+        assert_eq!(combat.combatants[1].id, goblin_id);
+    }
+
+    fn ghoul_block() -> StatBlockData {
+        crate::ingestion::ttrpg::StatBlockParser::new()
+            .parse(
+                "Ghoul\n\
+                 Medium undead, chaotic evil\n\
+                 Armor Class 12\n\
+                 Hit Points 22 (5d8)\n\
+                 STR 13 (+1) DEX 15 (+2) CON 10 (+0) INT 7 (-2) WIS 10 (+0) CHA 6 (-2)\n\
+                 Condition Immunities charmed, exhaustion, poisoned\n\
+                 Challenge 1 (200 XP)\n",
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_combatant_from_stat_block() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let block = ghoul_block();
+
+        let ghoul = Combatant::from_stat_block("Ghoul", &block, HitPointMode::Average, &mut rng);
+        assert_eq!(ghoul.combatant_type, CombatantType::Monster);
+        assert_eq!(ghoul.max_hp, Some(22));
+        assert_eq!(ghoul.current_hp, Some(22));
+        assert_eq!(ghoul.armor_class, Some(12));
+        assert_eq!(ghoul.initiative_modifier, 2);
+        assert!((3..=22).contains(&ghoul.initiative));
+        assert!(ghoul.is_immune_to("Poisoned"));
+        assert!(!ghoul.is_immune_to("Prone"));
+
+        for _ in 0..20 {
+            let rolled = Combatant::from_stat_block("Ghoul", &block, HitPointMode::Roll, &mut rng);
+            let hp = rolled.max_hp.unwrap();
+            assert!((5..=40).contains(&hp));
+        }
+    }
+
+    #[test]
+    fn test_numbered_names() {
+        assert_eq!(numbered_names("Goblin", 1, &[]), vec!["Goblin"]);
+        assert_eq!(
+            numbered_names("Goblin", 4, &[]),
+            vec!["Goblin 1", "Goblin 2", "Goblin 3", "Goblin 4"]
+        );
+
+        let existing = vec![
+            Combatant::new("Goblin", 10, CombatantType::Monster),
+            Combatant::new("Goblin Boss", 12, CombatantType::Monster),
+        ];
+        assert_eq!(numbered_names("Goblin", 2, &existing), vec!["Goblin 2", "Goblin 3"]);
+
+        let existing = vec![Combatant::new("Goblin 3", 10, CombatantType::Monster)];
+        assert_eq!(numbered_names("Goblin", 1, &existing), vec!["Goblin 4"]);
+    }
+
+    #[test]
+    fn test_add_from_stat_block() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant::new("Fighter", 30, CombatantType::Player));
+
+        let added = combat.add_from_stat_block(&ghoul_block(), 3, HitPointMode::Average, &mut rng);
+        assert_eq!(added.len(), 3);
+        assert_eq!(combat.combatants.len(), 4);
+        assert_eq!(combat.combatants[0].name, "Fighter"); // still sorted by initiative
+        let mut names: Vec<_> = added.iter().map(|c| c.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Ghoul 1", "Ghoul 2", "Ghoul 3"]);
+    }
 }
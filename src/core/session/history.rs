@@ -0,0 +1,193 @@
+//! Combat Undo/Redo
+//!
+//! Reversible combat actions (command pattern). A `CombatCommand` run through
+//! `CombatState::execute` captures the combatants it touches plus the round
+//! and turn position before and after, so undo/redo restore HP, conditions
+//! ticked off by a turn advance and initiative order exactly.
+
+use super::combat::{CombatState, Combatant};
+use super::conditions::AdvancedCondition;
+
+/// Commands kept on the undo stack before the oldest are dropped
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// A reversible combat action.
+#[derive(Debug, Clone)]
+pub enum CombatCommand {
+    Damage { combatant_id: String, amount: i32 },
+    Heal { combatant_id: String, amount: i32 },
+    AddTempHp { combatant_id: String, amount: i32 },
+    AddCondition { combatant_id: String, condition: AdvancedCondition },
+    RemoveCondition { combatant_id: String, condition_id: String },
+    SetInitiative { combatant_id: String, initiative: i32 },
+    RemoveCombatant { combatant_id: String },
+    NextTurn,
+    PreviousTurn,
+}
+
+impl CombatCommand {
+    /// Combatants whose state the command can change.
+    ///
+    /// Turn changes tick conditions on everyone, so they touch the whole roster.
+    pub(super) fn touched_ids(&self, combat: &CombatState) -> Vec<String> {
+        match self {
+            Self::Damage { combatant_id, .. }
+            | Self::Heal { combatant_id, .. }
+            | Self::AddTempHp { combatant_id, .. }
+            | Self::AddCondition { combatant_id, .. }
+            | Self::RemoveCondition { combatant_id, .. }
+            | Self::SetInitiative { combatant_id, .. }
+            | Self::RemoveCombatant { combatant_id } => vec![combatant_id.clone()],
+            Self::NextTurn | Self::PreviousTurn => {
+                combat.combatants.iter().map(|c| c.id.clone()).collect()
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Memento
+// ============================================================================
+
+/// The slice of combat state a command can change.
+#[derive(Debug, Clone)]
+pub(super) struct CombatMemento {
+    round: u32,
+    current_id: Option<String>,
+    current_turn: usize,
+    combatants: Vec<Combatant>,
+    /// Combatants that must not exist in this state (removed by the command)
+    absent: Vec<String>,
+}
+
+impl CombatMemento {
+    /// Capture the listed combatants (missing ones are recorded as absent).
+    pub(super) fn capture(combat: &CombatState, ids: &[String]) -> Self {
+        let mut combatants = Vec::new();
+        let mut absent = Vec::new();
+        for id in ids {
+            match combat.get_combatant(id) {
+                Some(c) => combatants.push(c.clone()),
+                None => absent.push(id.clone()),
+            }
+        }
+        Self {
+            round: combat.round,
+            current_id: combat.current_combatant().map(|c| c.id.clone()),
+            current_turn: combat.current_turn,
+            combatants,
+            absent,
+        }
+    }
+
+    /// Put the captured combatants and turn position back.
+    pub(super) fn restore(&self, combat: &mut CombatState) {
+        combat.combatants.retain(|c| !self.absent.contains(&c.id));
+        for saved in &self.combatants {
+            match combat.combatants.iter_mut().find(|c| c.id == saved.id) {
+                Some(existing) => *existing = saved.clone(),
+                None => combat.combatants.push(saved.clone()),
+            }
+        }
+        combat.sort_initiative();
+
+        combat.round = self.round;
+        combat.current_turn = self
+            .current_id
+            .as_ref()
+            .and_then(|id| combat.combatants.iter().position(|c| &c.id == id))
+            .unwrap_or_else(|| {
+                self.current_turn
+                    .min(combat.combatants.len().saturating_sub(1))
+            });
+    }
+}
+
+// ============================================================================
+// History
+// ============================================================================
+
+/// One executed command with the state on either side of it.
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    /// Human-readable description, e.g. "Goblin 2 takes 7 damage"
+    pub label: String,
+    pub(super) before: CombatMemento,
+    pub(super) after: CombatMemento,
+}
+
+/// Undo and redo stacks for one combat.
+#[derive(Debug, Clone)]
+pub struct CombatHistory {
+    undo: Vec<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    limit: usize,
+}
+
+impl Default for CombatHistory {
+    fn default() -> Self {
+        Self::with_limit(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl CombatHistory {
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit: limit.max(1),
+        }
+    }
+
+    /// Record a freshly executed command; a new action discards the redo stack
+    pub(super) fn record(&mut self, entry: HistoryEntry) {
+        self.redo.clear();
+        self.push_undo(entry);
+    }
+
+    pub(super) fn push_undo(&mut self, entry: HistoryEntry) {
+        self.undo.push(entry);
+        if self.undo.len() > self.limit {
+            self.undo.remove(0);
+        }
+    }
+
+    pub(super) fn pop_undo(&mut self) -> Option<HistoryEntry> {
+        self.undo.pop()
+    }
+
+    pub(super) fn push_redo(&mut self, entry: HistoryEntry) {
+        self.redo.push(entry);
+    }
+
+    pub(super) fn pop_redo(&mut self) -> Option<HistoryEntry> {
+        self.redo.pop()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Undoable actions, most recent first
+    pub fn undo_labels(&self) -> impl Iterator<Item = &str> {
+        self.undo.iter().rev().map(|e| e.label.as_str())
+    }
+
+    /// Redoable actions, next redo first
+    pub fn redo_labels(&self) -> impl Iterator<Item = &str> {
+        self.redo.iter().rev().map(|e| e.label.as_str())
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
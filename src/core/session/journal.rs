@@ -0,0 +1,392 @@
+//! Session Journal
+//!
+//! Append-only write-through log for live session state. Every mutation of a
+//! `GameSession` (including its `CombatState` and each combatant's
+//! `ConditionTracker`), `SessionTimeline` or `SessionNote` appends a full
+//! snapshot of the changed record as one JSON line; on startup the journal is
+//! replayed and the last snapshot of each record wins.
+//!
+//! - Lines are written with a single `write_all` so a crash leaves at most one
+//!   truncated trailing line, which replay skips
+//! - Deleted notes are recorded as a tombstone that drops the note on replay
+//! - The journal is compacted (one line per record, temp file + rename) on open
+//!   and whenever superseded lines pile up
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::notes::SessionNote;
+use super::timeline::SessionTimeline;
+use crate::core::session_manager::GameSession;
+
+/// Superseded lines tolerated before the journal is rewritten
+const COMPACT_THRESHOLD: usize = 1000;
+
+// ============================================================================
+// Records
+// ============================================================================
+
+/// One journal line.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+enum JournalRecord {
+    Session(GameSession),
+    Timeline(SessionTimeline),
+    Note(SessionNote),
+    /// Tombstone carrying the deleted note's ID
+    NoteDeleted(String),
+}
+
+impl JournalRecord {
+    fn key(&self) -> String {
+        match self {
+            Self::Session(s) => format!("session:{}", s.id),
+            Self::Timeline(t) => format!("timeline:{}", t.session_id),
+            Self::Note(n) => format!("note:{}", n.id),
+            Self::NoteDeleted(id) => format!("note:{id}"),
+        }
+    }
+}
+
+/// Borrowed form of [`JournalRecord`] so snapshots are written without cloning.
+#[derive(Serialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+enum JournalRecordRef<'a> {
+    Session(&'a GameSession),
+    Timeline(&'a SessionTimeline),
+    Note(&'a SessionNote),
+    NoteDeleted(&'a str),
+}
+
+impl JournalRecordRef<'_> {
+    fn key(&self) -> String {
+        match self {
+            Self::Session(s) => format!("session:{}", s.id),
+            Self::Timeline(t) => format!("timeline:{}", t.session_id),
+            Self::Note(n) => format!("note:{}", n.id),
+            Self::NoteDeleted(id) => format!("note:{id}"),
+        }
+    }
+}
+
+/// State recovered from the journal on open.
+#[derive(Debug, Default)]
+pub struct RestoredSessions {
+    pub sessions: Vec<GameSession>,
+    /// Timelines with their lookup index rebuilt
+    pub timelines: Vec<SessionTimeline>,
+    pub notes: Vec<SessionNote>,
+}
+
+// ============================================================================
+// Journal
+// ============================================================================
+
+struct JournalInner {
+    file: File,
+    /// Latest serialized line per record key, in first-seen order
+    latest: HashMap<String, (usize, String)>,
+    next_order: usize,
+    /// Lines in the file that a later line has replaced
+    superseded: usize,
+}
+
+/// Append-only JSONL journal of session snapshots.
+pub struct SessionJournal {
+    path: PathBuf,
+    inner: Mutex<JournalInner>,
+}
+
+impl SessionJournal {
+    /// Open (or create) the journal at `path` and replay it.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<(Self, RestoredSessions)> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut latest: HashMap<String, (usize, String)> = HashMap::new();
+        let mut sessions: HashMap<String, (usize, GameSession)> = HashMap::new();
+        let mut timelines: HashMap<String, (usize, SessionTimeline)> = HashMap::new();
+        let mut notes: HashMap<String, (usize, SessionNote)> = HashMap::new();
+        let mut next_order = 0;
+
+        if path.exists() {
+            let reader = BufReader::new(File::open(&path)?);
+            for (line_no, line) in reader.lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record = match serde_json::from_str::<JournalRecord>(&line) {
+                    Ok(record) => record,
+                    Err(e) => {
+                        log::warn!(
+                            "Skipping unreadable session journal line {} in {}: {e}",
+                            line_no + 1,
+                            path.display()
+                        );
+                        continue;
+                    }
+                };
+
+                let key = record.key();
+                if let JournalRecord::NoteDeleted(id) = &record {
+                    latest.remove(&key);
+                    notes.remove(id);
+                    continue;
+                }
+                let order = match latest.get(&key) {
+                    Some((order, _)) => *order,
+                    None => {
+                        next_order += 1;
+                        next_order
+                    }
+                };
+                latest.insert(key, (order, line));
+
+                match record {
+                    JournalRecord::Session(s) => {
+                        sessions.insert(s.id.clone(), (order, s));
+                    }
+                    JournalRecord::Timeline(t) => {
+                        timelines.insert(t.session_id.clone(), (order, t));
+                    }
+                    JournalRecord::Note(n) => {
+                        notes.insert(n.id.clone(), (order, n));
+                    }
+                    JournalRecord::NoteDeleted(_) => {}
+                }
+            }
+        }
+
+        let mut sessions: Vec<(usize, GameSession)> = sessions.into_values().collect();
+        sessions.sort_by_key(|(order, _)| *order);
+        let mut timelines: Vec<(usize, SessionTimeline)> = timelines.into_values().collect();
+        timelines.sort_by_key(|(order, _)| *order);
+        let mut notes: Vec<(usize, SessionNote)> = notes.into_values().collect();
+        notes.sort_by_key(|(order, _)| *order);
+
+        let restored = RestoredSessions {
+            sessions: sessions.into_iter().map(|(_, s)| s).collect(),
+            timelines: timelines
+                .into_iter()
+                .map(|(_, mut t)| {
+                    t.rebuild_index();
+                    t
+                })
+                .collect(),
+            notes: notes.into_iter().map(|(_, n)| n).collect(),
+        };
+
+        // Start from a compact file so replay cost stays proportional to state
+        let file = Self::rewrite(&path, &latest)?;
+        let journal = Self {
+            path,
+            inner: Mutex::new(JournalInner {
+                file,
+                latest,
+                next_order,
+                superseded: 0,
+            }),
+        };
+
+        Ok((journal, restored))
+    }
+
+    /// Path of the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a snapshot of a session.
+    pub fn record_session(&self, session: &GameSession) -> io::Result<()> {
+        self.append(JournalRecordRef::Session(session))
+    }
+
+    /// Append a snapshot of a session timeline.
+    pub fn record_timeline(&self, timeline: &SessionTimeline) -> io::Result<()> {
+        self.append(JournalRecordRef::Timeline(timeline))
+    }
+
+    /// Append a snapshot of a session note.
+    pub fn record_note(&self, note: &SessionNote) -> io::Result<()> {
+        self.append(JournalRecordRef::Note(note))
+    }
+
+    /// Append a tombstone so the note is dropped on replay.
+    pub fn record_note_deleted(&self, note_id: &str) -> io::Result<()> {
+        self.append(JournalRecordRef::NoteDeleted(note_id))
+    }
+
+    fn append(&self, record: JournalRecordRef<'_>) -> io::Result<()> {
+        let key = record.key();
+        let mut line = serde_json::to_string(&record)?;
+
+        let mut guard = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let inner = &mut *guard;
+        line.push('\n');
+        inner.file.write_all(line.as_bytes())?;
+        line.pop();
+
+        if let JournalRecordRef::NoteDeleted(_) = record {
+            // The tombstone and the snapshot it deletes both go at the next compaction
+            let had_note = inner.latest.remove(&key).is_some();
+            inner.superseded += 1 + usize::from(had_note);
+        } else {
+            let order = match inner.latest.get(&key) {
+                Some(&(order, _)) => {
+                    inner.superseded += 1;
+                    order
+                }
+                None => {
+                    inner.next_order += 1;
+                    inner.next_order
+                }
+            };
+            inner.latest.insert(key, (order, line));
+        }
+
+        if inner.superseded >= COMPACT_THRESHOLD {
+            inner.file = Self::rewrite(&self.path, &inner.latest)?;
+            inner.superseded = 0;
+        }
+        Ok(())
+    }
+
+    /// Write one line per record to a temp file, swap it in, and reopen for append.
+    fn rewrite(path: &Path, latest: &HashMap<String, (usize, String)>) -> io::Result<File> {
+        let mut lines: Vec<&(usize, String)> = latest.values().collect();
+        lines.sort_by_key(|(order, _)| *order);
+
+        let temp_path = path.with_extension("tmp");
+        {
+            let mut temp = File::create(&temp_path)?;
+            for (_, line) in lines {
+                temp.write_all(line.as_bytes())?;
+                temp.write_all(b"\n")?;
+            }
+            temp.sync_all()?;
+        }
+        fs::rename(&temp_path, path)?;
+
+        OpenOptions::new().append(true).open(path)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::session::timeline::{TimelineEvent, TimelineEventType};
+    use crate::core::session_manager::SessionStatus;
+    use chrono::Utc;
+    use tempfile::tempdir;
+
+    fn session(id: &str, number: u32) -> GameSession {
+        GameSession {
+            id: id.to_string(),
+            campaign_id: "camp".to_string(),
+            session_number: number,
+            started_at: Utc::now(),
+            ended_at: None,
+            status: SessionStatus::Active,
+            combat: None,
+            notes: vec![],
+            active_scene: None,
+            title: None,
+            order_index: 0,
+        }
+    }
+
+    #[test]
+    fn test_replay_keeps_latest_snapshot() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sessions.journal");
+
+        {
+            let (journal, restored) = SessionJournal::open(&path).unwrap();
+            assert!(restored.sessions.is_empty());
+
+            let mut s1 = session("s1", 1);
+            journal.record_session(&s1).unwrap();
+            journal.record_session(&session("s2", 2)).unwrap();
+            s1.status = SessionStatus::Paused;
+            journal.record_session(&s1).unwrap();
+
+            let mut timeline = SessionTimeline::new("s1");
+            timeline.add_event(TimelineEvent::new(
+                "s1",
+                TimelineEventType::SessionStart,
+                "Start",
+                "",
+            ));
+            journal.record_timeline(&timeline).unwrap();
+        }
+
+        let (_journal, restored) = SessionJournal::open(&path).unwrap();
+        let ids: Vec<&str> = restored.sessions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["s1", "s2"]);
+        assert_eq!(restored.sessions[0].status, SessionStatus::Paused);
+        assert_eq!(restored.timelines.len(), 1);
+        assert_eq!(
+            restored.timelines[0]
+                .events_by_type(&TimelineEventType::SessionStart)
+                .len(),
+            1
+        );
+
+        // Opening compacts to one line per record
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_replay_drops_deleted_notes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sessions.journal");
+
+        {
+            let (journal, _) = SessionJournal::open(&path).unwrap();
+            let kept = SessionNote::new("s1", "camp", "Kept", "The ferryman lies");
+            let deleted = SessionNote::new("s1", "camp", "Deleted", "Scratch that");
+            journal.record_note(&kept).unwrap();
+            journal.record_note(&deleted).unwrap();
+            journal.record_note_deleted(&deleted.id).unwrap();
+        }
+
+        let (_journal, restored) = SessionJournal::open(&path).unwrap();
+        assert_eq!(restored.notes.len(), 1);
+        assert_eq!(restored.notes[0].title, "Kept");
+
+        // Compaction drops both the tombstone and the deleted snapshot
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_replay_skips_truncated_tail() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sessions.journal");
+
+        {
+            let (journal, _) = SessionJournal::open(&path).unwrap();
+            journal.record_session(&session("s1", 1)).unwrap();
+        }
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"kind":"session","data":{"id":"s2","#)
+            .unwrap();
+
+        let (_journal, restored) = SessionJournal::open(&path).unwrap();
+        assert_eq!(restored.sessions.len(), 1);
+        assert_eq!(restored.sessions[0].id, "s1");
+    }
+}
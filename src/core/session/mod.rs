@@ -1,14 +1,17 @@
 //! Session Module
 //!
 //! Submodules for session management including timeline tracking,
-//! advanced conditions, combat state, session notes with AI categorization,
-//! and session planning with pacing templates.
+//! advanced conditions, combat state with undo/redo, session notes with AI
+//! categorization, session planning with pacing templates, and the
+//! write-through session journal.
 
 pub mod timeline;
 pub mod conditions;
 pub mod combat;
+pub mod history;
 pub mod notes;
 pub mod plan_types;
+pub mod journal;
 
 // Re-exports for convenience
 pub use timeline::{
@@ -37,5 +40,9 @@ pub use plan_types::{
 
 pub use combat::{
     CombatState, CombatStatus, Combatant, CombatantType,
-    CombatEvent, CombatEventType, TurnResult,
+    CombatEvent, CombatEventType, TurnResult, HitPointMode, numbered_names,
 };
+
+pub use history::{CombatCommand, CombatHistory, HistoryEntry};
+
+pub use journal::{SessionJournal, RestoredSessions};
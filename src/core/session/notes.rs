@@ -45,6 +45,22 @@ pub enum NoteCategory {
 }
 
 impl NoteCategory {
+    /// Built-in categories, in display order
+    pub const ALL: [NoteCategory; 12] = [
+        Self::General,
+        Self::Combat,
+        Self::Character,
+        Self::Location,
+        Self::Plot,
+        Self::Quest,
+        Self::Loot,
+        Self::Rules,
+        Self::Meta,
+        Self::Worldbuilding,
+        Self::Dialogue,
+        Self::Secret,
+    ];
+
     /// Convert to display string
     pub fn display(&self) -> String {
         match self {
@@ -118,6 +134,23 @@ pub enum EntityType {
     Custom(String),
 }
 
+impl EntityType {
+    /// Convert to display string
+    pub fn display(&self) -> String {
+        match self {
+            Self::NPC => "NPC".to_string(),
+            Self::Player => "Player".to_string(),
+            Self::Location => "Location".to_string(),
+            Self::Item => "Item".to_string(),
+            Self::Quest => "Quest".to_string(),
+            Self::Session => "Session".to_string(),
+            Self::Campaign => "Campaign".to_string(),
+            Self::Combat => "Combat".to_string(),
+            Self::Custom(s) => s.clone(),
+        }
+    }
+}
+
 /// A reference to an entity from a note
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntityLink {
@@ -703,6 +736,79 @@ pub struct NoteExport {
     pub notes: Vec<SessionNote>,
 }
 
+impl NoteExport {
+    /// Render as a Markdown document, one section per category.
+    ///
+    /// Notes are ordered pinned first, then by creation time.
+    pub fn to_markdown(&self, title: &str) -> String {
+        let mut out = format!(
+            "# {}\n\n_Exported {}_\n",
+            title,
+            self.exported_at.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        let mut categories: Vec<&NoteCategory> = Vec::new();
+        for note in &self.notes {
+            if !categories.contains(&&note.category) {
+                categories.push(&note.category);
+            }
+        }
+        let rank = |c: &NoteCategory| {
+            NoteCategory::ALL
+                .iter()
+                .position(|b| b == c)
+                .unwrap_or(NoteCategory::ALL.len())
+        };
+        categories.sort_by_key(|c| (rank(c), c.display()));
+
+        for category in categories {
+            out.push_str(&format!("\n## {}\n", category.display()));
+
+            let mut notes: Vec<&SessionNote> = self
+                .notes
+                .iter()
+                .filter(|n| &n.category == category)
+                .collect();
+            notes.sort_by(|a, b| {
+                b.is_pinned
+                    .cmp(&a.is_pinned)
+                    .then_with(|| a.created_at.cmp(&b.created_at))
+            });
+
+            for note in notes {
+                let mut heading = note.title.clone();
+                if note.is_pinned {
+                    heading.push_str(" (pinned)");
+                }
+                if note.is_private {
+                    heading.push_str(" (private)");
+                }
+                out.push_str(&format!("\n### {}\n\n", heading));
+
+                if !note.tags.is_empty() {
+                    let tags: Vec<String> = note.tags.iter().map(|t| format!("`{}`", t)).collect();
+                    out.push_str(&format!("**Tags:** {}\n", tags.join(", ")));
+                }
+                if !note.entity_links.is_empty() {
+                    let links: Vec<String> = note
+                        .entity_links
+                        .iter()
+                        .map(|l| format!("{} ({})", l.display_name, l.entity_type.display()))
+                        .collect();
+                    out.push_str(&format!("**Links:** {}\n", links.join(", ")));
+                }
+                if !note.tags.is_empty() || !note.entity_links.is_empty() {
+                    out.push('\n');
+                }
+                out.push_str(note.content.trim_end());
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+}
+
 impl NotesManager {
     /// Export all notes for a session
     pub fn export_session(&self, session_id: &str) -> NoteExport {
@@ -792,6 +898,31 @@ mod tests {
         assert_eq!(manager.search("dragon").len(), 0);
     }
 
+    #[test]
+    fn test_export_markdown() {
+        let mut manager = NotesManager::new();
+        manager.create_note(
+            SessionNote::new("session-1", "campaign-1", "Ambush", "Goblins on the road.")
+                .with_category(NoteCategory::Combat),
+        );
+        manager.create_note(
+            SessionNote::new("session-1", "campaign-1", "Ferryman", "Wants two coins.")
+                .with_category(NoteCategory::Character)
+                .with_tags(["npc"])
+                .with_entity_link(EntityType::NPC, "npc-1", "Charon")
+                .pinned(),
+        );
+        manager.create_note(SessionNote::new("session-2", "campaign-1", "Elsewhere", ""));
+
+        let md = manager.export_session("session-1").to_markdown("Session 1");
+        assert!(md.starts_with("# Session 1\n"));
+        assert!(md.find("## Combat").unwrap() < md.find("## Character").unwrap());
+        assert!(md.contains("### Ferryman (pinned)"));
+        assert!(md.contains("**Tags:** `npc`"));
+        assert!(md.contains("**Links:** Charon (NPC)"));
+        assert!(!md.contains("Elsewhere"));
+    }
+
     #[test]
     fn test_categorization_prompt() {
         let request = CategorizationRequest {
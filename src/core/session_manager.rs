@@ -9,10 +9,15 @@
 //! - Stacking rules
 //! - Condition immunity tracking
 //! - Custom condition builder support
+//!
+//! With a journal attached (`SessionManager::open`), every change to a
+//! session, its combat state or its timeline is written through to disk and
+//! restored on the next start.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::RwLock;
 use thiserror::Error;
 use uuid::Uuid;
@@ -26,17 +31,23 @@ use super::session::timeline::{
     EventSeverity, SessionTimeline, TimelineEvent, TimelineEventType, TimelineSummary,
 };
 
+use super::session::history::CombatCommand;
+use super::session::journal::SessionJournal;
+
 // TASK-017: Notes imports
 use super::session::notes::{
-    EntityType as NoteEntityType, NoteCategory, NotesManager, SessionNote,
+    EntityType as NoteEntityType, NoteCategory, NoteExport, NotesManager, SessionNote,
 };
 
+use crate::ingestion::ttrpg::StatBlockData;
+
 // ============================================================================
 // Re-exports for backward compatibility
 // ============================================================================
 
 pub use super::session::combat::{
     CombatEvent, CombatEventType, CombatState, CombatStatus, Combatant, CombatantType,
+    HitPointMode,
 };
 
 // ============================================================================
@@ -140,6 +151,10 @@ pub struct SessionManager {
     timelines: RwLock<HashMap<String, SessionTimeline>>,
     // TASK-017: Notes manager
     notes_manager: RwLock<NotesManager>,
+    /// Write-through persistence (None = in-memory only)
+    journal: Option<SessionJournal>,
+    /// Sessions restored as active, i.e. the app exited without pausing them
+    interrupted: Vec<String>,
 }
 
 impl Default for SessionManager {
@@ -155,6 +170,87 @@ impl SessionManager {
             campaign_sessions: RwLock::new(HashMap::new()),
             timelines: RwLock::new(HashMap::new()),
             notes_manager: RwLock::new(NotesManager::new()),
+            journal: None,
+            interrupted: Vec::new(),
+        }
+    }
+
+    /// Open a session manager backed by the journal at `path`.
+    ///
+    /// Sessions, timelines and notes recorded in the journal are restored;
+    /// sessions that were still active are reported by `interrupted_sessions`.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let (journal, restored) = SessionJournal::open(path)?;
+
+        let mut sessions = HashMap::new();
+        let mut campaign_sessions: HashMap<String, Vec<String>> = HashMap::new();
+        let mut interrupted = Vec::new();
+        for session in restored.sessions {
+            if session.status == SessionStatus::Active {
+                interrupted.push(session.id.clone());
+            }
+            campaign_sessions
+                .entry(session.campaign_id.clone())
+                .or_default()
+                .push(session.id.clone());
+            sessions.insert(session.id.clone(), session);
+        }
+
+        let timelines = restored
+            .timelines
+            .into_iter()
+            .map(|t| (t.session_id.clone(), t))
+            .collect();
+
+        let mut notes_manager = NotesManager::new();
+        for note in restored.notes {
+            notes_manager.create_note(note);
+        }
+
+        log::info!(
+            "Restored {} sessions ({} interrupted) from {}",
+            sessions.len(),
+            interrupted.len(),
+            journal.path().display()
+        );
+
+        Ok(Self {
+            sessions: RwLock::new(sessions),
+            campaign_sessions: RwLock::new(campaign_sessions),
+            timelines: RwLock::new(timelines),
+            notes_manager: RwLock::new(notes_manager),
+            journal: Some(journal),
+            interrupted,
+        })
+    }
+
+    /// Sessions that were active when the app last exited without pausing them.
+    ///
+    /// Only sessions that are still active are returned, most recent first.
+    pub fn interrupted_sessions(&self) -> Vec<GameSession> {
+        let sessions = self.sessions.read().unwrap();
+        let mut interrupted: Vec<GameSession> = self
+            .interrupted
+            .iter()
+            .filter_map(|id| sessions.get(id))
+            .filter(|s| s.status == SessionStatus::Active)
+            .cloned()
+            .collect();
+        interrupted.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        interrupted
+    }
+
+    /// Pause every active session (clean shutdown).
+    ///
+    /// Sessions left active in the journal are offered for resumption on the
+    /// next start, so a clean exit parks them first.
+    pub fn pause_active_sessions(&self) {
+        let mut sessions = self.sessions.write().unwrap();
+        for session in sessions.values_mut() {
+            if session.status == SessionStatus::Active {
+                session.status = SessionStatus::Paused;
+                self.persist_session(session);
+            }
         }
     }
 
@@ -162,6 +258,35 @@ impl SessionManager {
     // Private Helpers - Reduce Lock Boilerplate
     // ========================================================================
 
+    /// Write a session snapshot to the journal (if any).
+    ///
+    /// Called with the sessions lock held so journal order matches memory.
+    fn persist_session(&self, session: &GameSession) {
+        if let Some(ref journal) = self.journal {
+            if let Err(e) = journal.record_session(session) {
+                log::error!("Failed to journal session {}: {e}", session.id);
+            }
+        }
+    }
+
+    /// Write a timeline snapshot to the journal (if any).
+    fn persist_timeline(&self, timeline: &SessionTimeline) {
+        if let Some(ref journal) = self.journal {
+            if let Err(e) = journal.record_timeline(timeline) {
+                log::error!("Failed to journal timeline {}: {e}", timeline.session_id);
+            }
+        }
+    }
+
+    /// Write a note snapshot to the journal (if any).
+    fn persist_note(&self, note: &SessionNote) {
+        if let Some(ref journal) = self.journal {
+            if let Err(e) = journal.record_note(note) {
+                log::error!("Failed to journal note {}: {e}", note.id);
+            }
+        }
+    }
+
     /// Execute a closure with mutable access to a session
     fn with_session_mut<F, R>(&self, session_id: &str, f: F) -> Result<R>
     where
@@ -171,7 +296,9 @@ impl SessionManager {
         let session = sessions
             .get_mut(session_id)
             .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        Ok(f(session))
+        let result = f(session);
+        self.persist_session(session);
+        Ok(result)
     }
 
     /// Execute a closure with mutable access to a session's combat state
@@ -184,7 +311,27 @@ impl SessionManager {
             .get_mut(session_id)
             .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
         let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        Ok(f(combat))
+        let result = f(combat);
+        self.persist_session(session);
+        Ok(result)
+    }
+
+    /// Execute a closure with mutable access to a combatant, by index into
+    /// the session's combat state
+    fn with_combatant_mut<F, R>(&self, session_id: &str, combatant_id: &str, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut CombatState, usize) -> R,
+    {
+        let mut sessions = self.sessions.write().unwrap();
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
+        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
+        let idx = Self::find_combatant_index(combat, combatant_id)
+            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
+        let result = f(combat, idx);
+        self.persist_session(session);
+        Ok(result)
     }
 
     /// Find a combatant index in the combat state
@@ -212,10 +359,11 @@ impl SessionManager {
         };
 
         // Store session
-        self.sessions
-            .write()
-            .unwrap()
-            .insert(session.id.clone(), session.clone());
+        {
+            let mut sessions = self.sessions.write().unwrap();
+            self.persist_session(&session);
+            sessions.insert(session.id.clone(), session.clone());
+        }
 
         // Link to campaign
         self.campaign_sessions
@@ -275,10 +423,11 @@ impl SessionManager {
             order_index: session_number as i32,
         };
 
-        self.sessions
-            .write()
-            .unwrap()
-            .insert(session.id.clone(), session.clone());
+        {
+            let mut sessions = self.sessions.write().unwrap();
+            self.persist_session(&session);
+            sessions.insert(session.id.clone(), session.clone());
+        }
         self.campaign_sessions
             .write()
             .unwrap()
@@ -320,6 +469,17 @@ impl SessionManager {
         })
     }
 
+    /// Most recently started active session across all campaigns
+    pub fn latest_active_session(&self) -> Option<GameSession> {
+        self.sessions
+            .read()
+            .unwrap()
+            .values()
+            .filter(|s| s.status == SessionStatus::Active)
+            .max_by_key(|s| s.started_at)
+            .cloned()
+    }
+
     pub fn list_sessions(&self, campaign_id: &str) -> Vec<SessionSummary> {
         let sessions = self.sessions.read().unwrap();
         let campaign_sessions = self.campaign_sessions.read().unwrap();
@@ -404,6 +564,7 @@ impl SessionManager {
                 order_index: session.order_index,
             };
 
+            self.persist_session(session);
             (summary, session.session_number, session.id.clone())
         };
 
@@ -450,6 +611,7 @@ impl SessionManager {
         };
 
         session.notes.push(entry.clone());
+        self.persist_session(session);
         Some(entry)
     }
 
@@ -481,6 +643,7 @@ impl SessionManager {
 
             let combat = CombatState::new();
             session.combat = Some(combat.clone());
+            self.persist_session(session);
             combat
         };
 
@@ -515,6 +678,13 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Replace a session's combat state wholesale (e.g. from the TUI tracker).
+    pub fn set_combat(&self, session_id: &str, combat: CombatState) -> Result<()> {
+        self.with_session_mut(session_id, |session| {
+            session.combat = Some(combat);
+        })
+    }
+
     pub fn get_combat(&self, session_id: &str) -> Option<CombatState> {
         self.sessions
             .read()
@@ -545,15 +715,31 @@ impl SessionManager {
         Ok(combatant)
     }
 
-    pub fn remove_combatant(&self, session_id: &str, combatant_id: &str) -> Result<()> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
+    /// Add `count` copies of a stat block to the active combat.
+    ///
+    /// Each copy rolls its own initiative (d20 + DEX) and, with
+    /// `HitPointMode::Roll`, its own hit points. Copies are auto-numbered
+    /// ("Goblin 1".."Goblin 4").
+    pub fn add_combatants_from_stat_block(
+        &self,
+        session_id: &str,
+        block: &StatBlockData,
+        count: usize,
+        hp_mode: HitPointMode,
+    ) -> Result<Vec<Combatant>> {
+        self.with_combat_mut(session_id, |combat| {
+            combat.add_from_stat_block(block, count, hp_mode, &mut rand::thread_rng())
+        })
+    }
 
-        combat.remove_combatant(combatant_id)
-            .map(|_| ())
+    pub fn remove_combatant(&self, session_id: &str, combatant_id: &str) -> Result<()> {
+        let removed = self.with_combat_mut(session_id, |combat| {
+            combat.execute(CombatCommand::RemoveCombatant {
+                combatant_id: combatant_id.to_string(),
+            })
+        })?;
+        removed
+            .then_some(())
             .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))
     }
 
@@ -571,22 +757,36 @@ impl SessionManager {
 
     pub fn set_initiative(&self, session_id: &str, combatant_id: &str, initiative: i32) -> Result<()> {
         self.with_combat_mut(session_id, |combat| {
-            if let Some(combatant) = combat.get_combatant_mut(combatant_id) {
-                combatant.initiative = initiative;
-                combat.sort_initiative();
-            }
+            combat.execute(CombatCommand::SetInitiative {
+                combatant_id: combatant_id.to_string(),
+                initiative,
+            });
         })
     }
 
     pub fn next_turn(&self, session_id: &str) -> Result<Option<Combatant>> {
         self.with_combat_mut(session_id, |combat| {
-            let result = combat.next_turn();
-            result.current_combatant
+            combat.execute(CombatCommand::NextTurn);
+            combat.current_combatant().cloned()
         })
     }
 
     pub fn previous_turn(&self, session_id: &str) -> Result<Option<Combatant>> {
-        self.with_combat_mut(session_id, |combat| combat.previous_turn())
+        self.with_combat_mut(session_id, |combat| {
+            combat.execute(CombatCommand::PreviousTurn);
+            combat.current_combatant().cloned()
+        })
+    }
+
+    /// Undo the last combat command (damage, heal, condition, initiative, turn)
+    /// Returns the undone command's description, or None if nothing to undo
+    pub fn undo_combat(&self, session_id: &str) -> Result<Option<String>> {
+        self.with_combat_mut(session_id, |combat| combat.undo())
+    }
+
+    /// Redo the most recently undone combat command
+    pub fn redo_combat(&self, session_id: &str) -> Result<Option<String>> {
+        self.with_combat_mut(session_id, |combat| combat.redo())
     }
 
     pub fn get_current_combatant(&self, session_id: &str) -> Option<Combatant> {
@@ -603,50 +803,32 @@ impl SessionManager {
     // ========================================================================
 
     pub fn damage_combatant(&self, session_id: &str, combatant_id: &str, amount: i32) -> Result<i32> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
-
-        let combatant = &mut combat.combatants[idx];
-        let new_hp = combatant.apply_damage(amount);
-        let name = combatant.name.clone();
-
-        combat.log_event(&name, CombatEventType::Damage, format!("{} takes {} damage", name, amount));
-        Ok(new_hp)
+        self.with_combatant_mut(session_id, combatant_id, |combat, idx| {
+            combat.execute(CombatCommand::Damage {
+                combatant_id: combatant_id.to_string(),
+                amount,
+            });
+            combat.combatants[idx].current_hp.unwrap_or(0)
+        })
     }
 
     pub fn heal_combatant(&self, session_id: &str, combatant_id: &str, amount: i32) -> Result<i32> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
-
-        let combatant = &mut combat.combatants[idx];
-        let new_hp = combatant.heal(amount);
-        let name = combatant.name.clone();
-
-        combat.log_event(&name, CombatEventType::Healing, format!("{} heals {} HP", name, amount));
-        Ok(new_hp)
+        self.with_combatant_mut(session_id, combatant_id, |combat, idx| {
+            combat.execute(CombatCommand::Heal {
+                combatant_id: combatant_id.to_string(),
+                amount,
+            });
+            combat.combatants[idx].current_hp.unwrap_or(0)
+        })
     }
 
     pub fn add_temp_hp(&self, session_id: &str, combatant_id: &str, amount: i32) -> Result<()> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
-
-        combat.combatants[idx].add_temp_hp(amount);
-        Ok(())
+        self.with_combatant_mut(session_id, combatant_id, |combat, _| {
+            combat.execute(CombatCommand::AddTempHp {
+                combatant_id: combatant_id.to_string(),
+                amount,
+            });
+        })
     }
 
     // ========================================================================
@@ -660,48 +842,13 @@ impl SessionManager {
         combatant_id: &str,
         condition: AdvancedCondition,
     ) -> Result<()> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
-
-        let combatant = &mut combat.combatants[idx];
-
-        // Check for immunity
-        if combatant.is_immune_to(&condition.name) {
-            let name = combatant.name.clone();
-            let cond_name = condition.name.clone();
-            combat.log_event(
-                &name,
-                CombatEventType::ConditionApplied,
-                format!("{} is immune to {}", name, cond_name),
-            );
-            return Ok(());
-        }
-
-        let condition_name = condition.name.clone();
-        let combatant_name = combatant.name.clone();
-
-        match combatant.condition_tracker.add_condition(condition) {
-            Ok(()) => {
-                combat.log_event(
-                    combatant_name,
-                    CombatEventType::ConditionApplied,
-                    format!("Gained condition: {}", condition_name),
-                );
-            }
-            Err(msg) => {
-                combat.log_event(
-                    combatant_name,
-                    CombatEventType::Other,
-                    format!("Condition not applied: {}", msg),
-                );
-            }
-        }
-        Ok(())
+        // Immunity and stacking rules are applied (and logged) by the command
+        self.with_combatant_mut(session_id, combatant_id, |combat, _| {
+            combat.execute(CombatCommand::AddCondition {
+                combatant_id: combatant_id.to_string(),
+                condition,
+            });
+        })
     }
 
     /// Add a standard condition by name with optional duration
@@ -753,27 +900,19 @@ impl SessionManager {
         combatant_id: &str,
         condition_id: &str,
     ) -> Result<Option<AdvancedCondition>> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
-
-        let combatant = &mut combat.combatants[idx];
-        if let Some(removed) = combatant.condition_tracker.remove_condition(condition_id) {
-            let name = combatant.name.clone();
-            let cond_name = removed.name.clone();
-            combat.log_event(
-                &name,
-                CombatEventType::ConditionRemoved,
-                format!("{} loses condition: {}", name, cond_name),
-            );
-            Ok(Some(removed))
-        } else {
-            Ok(None)
-        }
+        self.with_combatant_mut(session_id, combatant_id, |combat, idx| {
+            let removed = combat.combatants[idx]
+                .condition_tracker
+                .conditions()
+                .iter()
+                .find(|c| c.id == condition_id)
+                .cloned()?;
+            combat.execute(CombatCommand::RemoveCondition {
+                combatant_id: combatant_id.to_string(),
+                condition_id: condition_id.to_string(),
+            });
+            Some(removed)
+        })
     }
 
     /// Remove all advanced conditions with a given name
@@ -783,26 +922,24 @@ impl SessionManager {
         combatant_id: &str,
         condition_name: &str,
     ) -> Result<Vec<AdvancedCondition>> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
-
-        let combatant = &mut combat.combatants[idx];
-        let removed = combatant.condition_tracker.remove_by_name(condition_name);
-        let name = combatant.name.clone();
+        self.with_combatant_mut(session_id, combatant_id, |combat, idx| {
+            let removed: Vec<AdvancedCondition> = combat.combatants[idx]
+                .condition_tracker
+                .conditions()
+                .iter()
+                .filter(|c| c.name == condition_name)
+                .cloned()
+                .collect();
 
-        for condition in &removed {
-            combat.log_event(
-                &name,
-                CombatEventType::ConditionRemoved,
-                format!("{} loses condition: {}", name, condition.name),
-            );
-        }
-        Ok(removed)
+            // One command per instance so each removal can be undone
+            for condition in &removed {
+                combat.execute(CombatCommand::RemoveCondition {
+                    combatant_id: combatant_id.to_string(),
+                    condition_id: condition.id.clone(),
+                });
+            }
+            removed
+        })
     }
 
     /// Get all advanced conditions for a combatant
@@ -830,16 +967,9 @@ impl SessionManager {
         combatant_id: &str,
         condition_name: &str,
     ) -> Result<()> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
-
-        combat.combatants[idx].add_immunity(condition_name);
-        Ok(())
+        self.with_combatant_mut(session_id, combatant_id, |combat, idx| {
+            combat.combatants[idx].add_immunity(condition_name);
+        })
     }
 
     /// Remove a condition immunity from a combatant
@@ -849,16 +979,9 @@ impl SessionManager {
         combatant_id: &str,
         condition_name: &str,
     ) -> Result<()> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
-
-        combat.combatants[idx].remove_immunity(condition_name);
-        Ok(())
+        self.with_combatant_mut(session_id, combatant_id, |combat, idx| {
+            combat.combatants[idx].remove_immunity(condition_name);
+        })
     }
 
     /// Attempt a saving throw against a condition
@@ -869,38 +992,32 @@ impl SessionManager {
         condition_id: &str,
         roll: i32,
     ) -> Result<bool> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
-
-        let combatant = &mut combat.combatants[idx];
-        if let Some(cond) = combatant.condition_tracker.get_mut(condition_id) {
-            let success = cond.attempt_save(roll);
-            let name = combatant.name.clone();
-            let cond_name = cond.name.clone();
-
-            if success {
-                combatant.condition_tracker.remove_condition(condition_id);
-                combat.log_event(
-                    &name,
-                    CombatEventType::ConditionRemoved,
-                    format!("{} saved against {} (roll: {})", name, cond_name, roll),
-                );
+        self.with_combatant_mut(session_id, combatant_id, |combat, idx| {
+            let combatant = &mut combat.combatants[idx];
+            if let Some(cond) = combatant.condition_tracker.get_mut(condition_id) {
+                let success = cond.attempt_save(roll);
+                let name = combatant.name.clone();
+                let cond_name = cond.name.clone();
+
+                if success {
+                    combatant.condition_tracker.remove_condition(condition_id);
+                    combat.log_event(
+                        &name,
+                        CombatEventType::ConditionRemoved,
+                        format!("{} saved against {} (roll: {})", name, cond_name, roll),
+                    );
+                } else {
+                    combat.log_event(
+                        &name,
+                        CombatEventType::Other,
+                        format!("{} failed save against {} (roll: {})", name, cond_name, roll),
+                    );
+                }
+                success
             } else {
-                combat.log_event(
-                    &name,
-                    CombatEventType::Other,
-                    format!("{} failed save against {} (roll: {})", name, cond_name, roll),
-                );
+                false
             }
-            Ok(success)
-        } else {
-            Ok(false)
-        }
+        })
     }
 
     /// Tick conditions at end of turn for a specific combatant
@@ -910,28 +1027,22 @@ impl SessionManager {
         session_id: &str,
         combatant_id: &str,
     ) -> Result<Vec<String>> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
+        self.with_combatant_mut(session_id, combatant_id, |combat, idx| {
+            let combatant = &mut combat.combatants[idx];
+            let expired = combatant.condition_tracker.tick_end_of_turn(true);
+            let expired_names: Vec<String> = expired.iter().map(|c| c.name.clone()).collect();
 
-        let combatant = &mut combat.combatants[idx];
-        let expired = combatant.condition_tracker.tick_end_of_turn(true);
-        let expired_names: Vec<String> = expired.iter().map(|c| c.name.clone()).collect();
-
-        let name = combatant.name.clone();
-        for cond in &expired {
-            combat.log_event(
-                &name,
-                CombatEventType::ConditionRemoved,
-                format!("{} expired on {}", cond.name, name),
-            );
-        }
+            let name = combatant.name.clone();
+            for cond in &expired {
+                combat.log_event(
+                    &name,
+                    CombatEventType::ConditionRemoved,
+                    format!("{} expired on {}", cond.name, name),
+                );
+            }
 
-        Ok(expired_names)
+            expired_names
+        })
     }
 
     /// Tick conditions at start of turn for a specific combatant
@@ -941,28 +1052,22 @@ impl SessionManager {
         session_id: &str,
         combatant_id: &str,
     ) -> Result<Vec<String>> {
-        let mut sessions = self.sessions.write().unwrap();
-        let session = sessions
-            .get_mut(session_id)
-            .ok_or_else(|| SessionError::SessionNotFound(session_id.to_string()))?;
-        let combat = session.combat.as_mut().ok_or(SessionError::NoCombatActive)?;
-        let idx = Self::find_combatant_index(combat, combatant_id)
-            .ok_or_else(|| SessionError::CombatantNotFound(combatant_id.to_string()))?;
+        self.with_combatant_mut(session_id, combatant_id, |combat, idx| {
+            let combatant = &mut combat.combatants[idx];
+            let expired = combatant.condition_tracker.tick_start_of_turn(true);
+            let expired_names: Vec<String> = expired.iter().map(|c| c.name.clone()).collect();
 
-        let combatant = &mut combat.combatants[idx];
-        let expired = combatant.condition_tracker.tick_start_of_turn(true);
-        let expired_names: Vec<String> = expired.iter().map(|c| c.name.clone()).collect();
-
-        let name = combatant.name.clone();
-        for cond in &expired {
-            combat.log_event(
-                &name,
-                CombatEventType::ConditionRemoved,
-                format!("{} expired on {} (start of turn)", cond.name, name),
-            );
-        }
+            let name = combatant.name.clone();
+            for cond in &expired {
+                combat.log_event(
+                    &name,
+                    CombatEventType::ConditionRemoved,
+                    format!("{} expired on {} (start of turn)", cond.name, name),
+                );
+            }
 
-        Ok(expired_names)
+            expired_names
+        })
     }
 
     /// Get list of available condition templates
@@ -1003,7 +1108,8 @@ impl SessionManager {
     /// Create a new session note
     pub fn create_note(&self, note: SessionNote) -> Result<()> {
         let mut manager = self.notes_manager.write().unwrap();
-        manager.create_note(note);
+        let note = manager.create_note(note);
+        self.persist_note(note);
         Ok(())
     }
 
@@ -1016,10 +1122,12 @@ impl SessionManager {
     /// Update an existing note
     pub fn update_note(&self, note: SessionNote) -> Result<SessionNote> {
         let mut manager = self.notes_manager.write().unwrap();
-        manager
-            .update_note(note.clone())
+        let note = manager
+            .update_note(note)
             .cloned()
-            .map_err(SessionError::SessionNotFound)
+            .map_err(SessionError::SessionNotFound)?;
+        self.persist_note(&note);
+        Ok(note)
     }
 
     /// Delete a note
@@ -1028,9 +1136,26 @@ impl SessionManager {
         manager
             .delete_note(note_id)
             .ok_or_else(|| SessionError::SessionNotFound(note_id.to_string()))?;
+        if let Some(ref journal) = self.journal {
+            if let Err(e) = journal.record_note_deleted(note_id) {
+                log::error!("Failed to journal deletion of note {note_id}: {e}");
+            }
+        }
         Ok(())
     }
 
+    /// List every note, pinned first, then most recently updated
+    pub fn list_notes(&self) -> Vec<SessionNote> {
+        let manager = self.notes_manager.read().unwrap();
+        let mut notes: Vec<SessionNote> = manager.all_notes().into_iter().cloned().collect();
+        notes.sort_by(|a, b| {
+            b.is_pinned
+                .cmp(&a.is_pinned)
+                .then_with(|| b.updated_at.cmp(&a.updated_at))
+        });
+        notes
+    }
+
     /// List all notes for a session
     pub fn list_notes_for_session(&self, session_id: &str) -> Vec<SessionNote> {
         let manager = self.notes_manager.read().unwrap();
@@ -1080,6 +1205,12 @@ impl SessionManager {
         manager.notes_with_tag(tag).into_iter().cloned().collect()
     }
 
+    /// Export a session's notes
+    pub fn export_session_notes(&self, session_id: &str) -> NoteExport {
+        let manager = self.notes_manager.read().unwrap();
+        manager.export_session(session_id)
+    }
+
     /// Link an entity to a note
     pub fn link_entity_to_note(
         &self,
@@ -1088,30 +1219,30 @@ impl SessionManager {
         entity_id: &str,
         entity_name: &str,
     ) -> Result<()> {
-        let mut manager = self.notes_manager.write().unwrap();
-        if let Some(note) = manager.get_note_mut(note_id) {
-            note.link_entity(entity_type, entity_id, entity_name);
-            Ok(())
-        } else {
-            Err(SessionError::SessionNotFound(format!(
-                "Note not found: {}",
-                note_id
-            )))
-        }
+        self.with_note_mut(note_id, |note| {
+            note.link_entity(entity_type, entity_id, entity_name)
+        })
     }
 
     /// Unlink an entity from a note
     pub fn unlink_entity_from_note(&self, note_id: &str, entity_id: &str) -> Result<()> {
+        self.with_note_mut(note_id, |note| note.unlink_entity(entity_id))
+    }
+
+    /// Modify a copy of a note and store it back through `update_note`, so
+    /// the entity index and journal stay in step
+    fn with_note_mut(&self, note_id: &str, f: impl FnOnce(&mut SessionNote)) -> Result<()> {
         let mut manager = self.notes_manager.write().unwrap();
-        if let Some(note) = manager.get_note_mut(note_id) {
-            note.unlink_entity(entity_id);
-            Ok(())
-        } else {
-            Err(SessionError::SessionNotFound(format!(
-                "Note not found: {}",
-                note_id
-            )))
-        }
+        let mut note = manager
+            .get_note(note_id)
+            .cloned()
+            .ok_or_else(|| SessionError::SessionNotFound(format!("Note not found: {}", note_id)))?;
+        f(&mut note);
+        let note = manager
+            .update_note(note)
+            .map_err(SessionError::SessionNotFound)?;
+        self.persist_note(note);
+        Ok(())
     }
 }
 
@@ -1136,6 +1267,7 @@ impl SessionManager {
             .or_insert_with(|| SessionTimeline::new(session_id));
 
         timeline.add_event(event);
+        self.persist_timeline(timeline);
         Ok(())
     }
 
@@ -1245,9 +1377,11 @@ impl SessionManager {
     /// Create a timeline for a new session (called automatically on session start)
     fn ensure_timeline_exists(&self, session_id: &str) {
         let mut timelines = self.timelines.write().unwrap();
-        timelines
-            .entry(session_id.to_string())
-            .or_insert_with(|| SessionTimeline::new(session_id));
+        if !timelines.contains_key(session_id) {
+            let timeline = SessionTimeline::new(session_id);
+            self.persist_timeline(&timeline);
+            timelines.insert(session_id.to_string(), timeline);
+        }
     }
 
     /// Get the raw timeline for direct manipulation (internal use)
@@ -1289,6 +1423,19 @@ mod tests {
         assert!(summary.ended_at.is_some());
     }
 
+    #[test]
+    fn test_latest_active_session() {
+        let manager = SessionManager::new();
+        assert!(manager.latest_active_session().is_none());
+
+        let first = manager.start_session("campaign-1", 1);
+        let second = manager.start_session("campaign-2", 1);
+        assert_eq!(manager.latest_active_session().unwrap().id, second.id);
+
+        manager.pause_session(&second.id).unwrap();
+        assert_eq!(manager.latest_active_session().unwrap().id, first.id);
+    }
+
     #[test]
     fn test_combat_initiative() {
         let manager = SessionManager::new();
@@ -1323,6 +1470,44 @@ mod tests {
         assert_eq!(current.name, "Goblin");
     }
 
+    #[test]
+    fn test_add_combatants_from_stat_block() {
+        let manager = SessionManager::new();
+        let session = manager.start_session("campaign-1", 1);
+        manager.start_combat(&session.id).unwrap();
+
+        let block = crate::ingestion::ttrpg::StatBlockParser::new()
+            .parse(
+                "Goblin\n\
+                 Small humanoid (goblinoid), neutral evil\n\
+                 Armor Class 15 (leather armor, shield)\n\
+                 Hit Points 7 (2d6)\n\
+                 STR 8 (-1) DEX 14 (+2) CON 10 (+0) INT 10 (+0) WIS 8 (-1) CHA 8 (-1)\n\
+                 Challenge 1/4 (50 XP)\n",
+            )
+            .unwrap();
+
+        let added = manager
+            .add_combatants_from_stat_block(&session.id, &block, 4, HitPointMode::Roll)
+            .unwrap();
+        assert_eq!(added.len(), 4);
+
+        let combat = manager.get_combat(&session.id).unwrap();
+        assert_eq!(combat.combatants.len(), 4);
+        for goblin in &combat.combatants {
+            assert!(goblin.name.starts_with("Goblin "));
+            assert_eq!(goblin.armor_class, Some(15));
+            assert_eq!(goblin.initiative_modifier, 2);
+            assert!((2..=12).contains(&goblin.max_hp.unwrap()));
+        }
+
+        // Without active combat the call fails like add_combatant
+        let other = manager.start_session("campaign-1", 2);
+        assert!(manager
+            .add_combatants_from_stat_block(&other.id, &block, 1, HitPointMode::Average)
+            .is_err());
+    }
+
     #[test]
     fn test_hp_tracking() {
         let manager = SessionManager::new();
@@ -1382,4 +1567,77 @@ mod tests {
             .unwrap();
         assert!(conditions.is_empty());
     }
+
+    #[test]
+    fn test_journal_restores_interrupted_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.journal");
+
+        let (session_id, fighter_id) = {
+            let manager = SessionManager::open(&path).unwrap();
+            let session = manager.start_session("campaign-1", 1);
+            manager.start_combat(&session.id).unwrap();
+            let mut fighter = Combatant::new("Fighter", 15, CombatantType::Player);
+            fighter.max_hp = Some(30);
+            fighter.current_hp = Some(30);
+            manager.add_combatant(&session.id, fighter.clone()).unwrap();
+            manager.damage_combatant(&session.id, &fighter.id, 12).unwrap();
+            manager
+                .add_condition_by_name(&session.id, &fighter.id, "Poisoned", None, None, None)
+                .unwrap();
+            (session.id, fighter.id)
+            // Dropped without pausing: simulates a crash
+        };
+
+        let manager = SessionManager::open(&path).unwrap();
+        let interrupted = manager.interrupted_sessions();
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].id, session_id);
+
+        let combat = manager.get_combat(&session_id).unwrap();
+        assert_eq!(combat.combatants[0].current_hp, Some(18));
+        let conditions = manager.get_combatant_conditions(&session_id, &fighter_id).unwrap();
+        assert_eq!(conditions[0].name, "Poisoned");
+        assert!(!manager
+            .get_timeline_events_by_type(&session_id, &TimelineEventType::CombatStart)
+            .is_empty());
+        assert_eq!(manager.list_sessions("campaign-1").len(), 1);
+
+        // A clean shutdown parks active sessions so they aren't offered again
+        manager.pause_active_sessions();
+        drop(manager);
+        let manager = SessionManager::open(&path).unwrap();
+        assert!(manager.interrupted_sessions().is_empty());
+        assert_eq!(
+            manager.get_session(&session_id).unwrap().status,
+            SessionStatus::Paused
+        );
+    }
+
+    #[test]
+    fn test_journal_restores_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.journal");
+
+        let note_id = {
+            let manager = SessionManager::open(&path).unwrap();
+            let note = SessionNote::new("s1", "campaign-1", "Ferryman", "Demands two coins");
+            let note_id = note.id.clone();
+            manager.create_note(note).unwrap();
+            manager
+                .link_entity_to_note(&note_id, NoteEntityType::NPC, "npc-1", "Charon")
+                .unwrap();
+            let scratch = SessionNote::new("s1", "campaign-1", "Scratch", "");
+            let scratch_id = scratch.id.clone();
+            manager.create_note(scratch).unwrap();
+            manager.delete_note(&scratch_id).unwrap();
+            note_id
+        };
+
+        let manager = SessionManager::open(&path).unwrap();
+        let notes = manager.list_notes_for_session("s1");
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].id, note_id);
+        assert_eq!(notes[0].entity_links[0].display_name, "Charon");
+    }
 }
@@ -0,0 +1,592 @@
+//! Bestiary storage: creature stat blocks harvested from ingested documents.
+//!
+//! During ingestion every page is scanned with `StatBlockParser`; each parsed
+//! block is stored as a `stat_block` record linked to its source library item.
+//! The flattened columns (CR, type, size, source) back the bestiary filters,
+//! while the full `StatBlockData` is kept in `data` for display and combat.
+
+use serde::{Deserialize, Serialize};
+use surrealdb::engine::local::Db;
+use surrealdb::Surreal;
+
+use super::error::StorageError;
+use crate::ingestion::ttrpg::{format_challenge_rating, StatBlockData, StatBlockParser};
+
+/// Creature types recognised as bare words in a bestiary query.
+const CREATURE_TYPES: &[&str] = &[
+    "aberration", "beast", "celestial", "construct", "dragon", "elemental", "fey",
+    "fiend", "giant", "humanoid", "monstrosity", "ooze", "plant", "undead",
+];
+
+/// Creature sizes recognised as bare words in a bestiary query.
+const SIZES: &[&str] = &["tiny", "small", "medium", "large", "huge", "gargantuan"];
+
+/// Longest plausible creature name; longer "names" are prose misdetections.
+const MAX_NAME_LEN: usize = 60;
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// A stored stat block with its source attribution.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatBlockRecord {
+    /// Record ID (without table prefix)
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Creature name
+    pub name: String,
+    /// Size category, lowercase (e.g. "small")
+    #[serde(default)]
+    pub size: Option<String>,
+    /// Creature type, lowercase (e.g. "humanoid (goblinoid)")
+    #[serde(default)]
+    pub creature_type: Option<String>,
+    /// Alignment text
+    #[serde(default)]
+    pub alignment: Option<String>,
+    /// Armor class value
+    #[serde(default)]
+    pub armor_class: Option<i32>,
+    /// Average hit points
+    #[serde(default)]
+    pub hit_points: Option<i32>,
+    /// Hit-dice formula (e.g. "2d6")
+    #[serde(default)]
+    pub hit_dice: Option<String>,
+    /// Challenge rating as a number (1/4 is stored as 0.25)
+    #[serde(default)]
+    pub challenge_rating: Option<f32>,
+    /// Experience points
+    #[serde(default)]
+    pub xp: Option<i32>,
+    /// Source library item ID (without table prefix)
+    #[serde(default)]
+    pub library_item: Option<String>,
+    /// Source title (the library item's title)
+    pub source: String,
+    /// Page the block was found on
+    #[serde(default)]
+    pub page_number: Option<i32>,
+    /// Full parsed stat block
+    pub data: StatBlockData,
+}
+
+impl StatBlockRecord {
+    /// Build a record from parsed stat block data.
+    pub fn from_parsed(data: StatBlockData, source: impl Into<String>, page_number: Option<i32>) -> Self {
+        Self {
+            id: None,
+            name: data.name.clone(),
+            size: data.size.clone(),
+            creature_type: data.creature_type.clone(),
+            alignment: data.alignment.clone(),
+            armor_class: data.armor_class.as_ref().map(|ac| ac.value),
+            hit_points: data.hit_points.as_ref().map(|hp| hp.average),
+            hit_dice: data.hit_points.as_ref().and_then(|hp| hp.formula.clone()),
+            challenge_rating: data.challenge_rating.as_ref().map(|cr| cr.value),
+            xp: data.challenge_rating.as_ref().and_then(|cr| cr.xp),
+            library_item: None,
+            source: source.into(),
+            page_number,
+            data,
+        }
+    }
+
+    /// Challenge rating formatted as printed ("1/4", "5").
+    pub fn cr_label(&self) -> Option<String> {
+        self.challenge_rating.map(format_challenge_rating)
+    }
+}
+
+/// Scan extracted pages for stat blocks and parse them into records.
+///
+/// The pages are scanned together, so a block that runs onto the next page is
+/// parsed whole and recorded on the page it starts on. Blocks missing a name,
+/// AC or HP are treated as misdetections and dropped. Creatures repeated
+/// within the same source keep their first occurrence.
+pub fn collect_stat_blocks(pages: &[(u32, String)], source: &str) -> Vec<StatBlockRecord> {
+    let parser = StatBlockParser::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut records = Vec::new();
+
+    for (page, block) in parser.find_blocks_in_pages(pages) {
+        let Ok(data) = parser.parse(&block) else {
+            continue;
+        };
+        if data.name.is_empty()
+            || data.name.len() > MAX_NAME_LEN
+            || data.armor_class.is_none()
+            || data.hit_points.is_none()
+        {
+            continue;
+        }
+        if !seen.insert(data.name.to_lowercase()) {
+            continue;
+        }
+        records.push(StatBlockRecord::from_parsed(data, source, Some(page as i32)));
+    }
+
+    records
+}
+
+// ============================================================================
+// Filter
+// ============================================================================
+
+/// Filter for bestiary queries. All set criteria must match.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BestiaryFilter {
+    /// Case-insensitive substring of the creature name
+    pub name: Option<String>,
+    /// Minimum challenge rating (inclusive)
+    pub cr_min: Option<f32>,
+    /// Maximum challenge rating (inclusive)
+    pub cr_max: Option<f32>,
+    /// Creature type prefix (e.g. "undead", "humanoid")
+    pub creature_type: Option<String>,
+    /// Exact size category
+    pub size: Option<String>,
+    /// Case-insensitive substring of the source title
+    pub source: Option<String>,
+    /// Restrict to one library item (ID without table prefix)
+    pub library_item: Option<String>,
+}
+
+impl BestiaryFilter {
+    /// Create an empty filter (matches everything).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Filter by name substring.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into().to_lowercase());
+        self
+    }
+
+    /// Filter by challenge rating range (inclusive on both ends).
+    pub fn cr_range(mut self, min: Option<f32>, max: Option<f32>) -> Self {
+        self.cr_min = min;
+        self.cr_max = max;
+        self
+    }
+
+    /// Filter by creature type prefix.
+    pub fn creature_type(mut self, creature_type: impl Into<String>) -> Self {
+        self.creature_type = Some(creature_type.into().to_lowercase());
+        self
+    }
+
+    /// Filter by size category.
+    pub fn size(mut self, size: impl Into<String>) -> Self {
+        self.size = Some(size.into().to_lowercase());
+        self
+    }
+
+    /// Filter by source title substring.
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into().to_lowercase());
+        self
+    }
+
+    /// Restrict to a single library item.
+    pub fn library_item(mut self, id: impl Into<String>) -> Self {
+        self.library_item = Some(id.into());
+        self
+    }
+
+    /// Parse a free-form bestiary query.
+    ///
+    /// Understands `cr:3-5` / `cr 3-5` (also `1/4`, `5+`), `type:undead`,
+    /// `size:large`, `src:phb` / `from <source...>`, and bare creature types
+    /// and sizes. Anything else becomes part of the name filter, so
+    /// `"undead CR 3-5 from Monster Manual"` does what it says.
+    pub fn parse(query: &str) -> Self {
+        let mut filter = Self::new();
+        let mut name_words: Vec<&str> = Vec::new();
+        let tokens: Vec<&str> = query.split_whitespace().collect();
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let token = tokens[i];
+            let lower = token.to_lowercase();
+
+            if let Some((key, value)) = lower.split_once(':') {
+                match key {
+                    "cr" => filter.apply_cr(value),
+                    "type" => filter.creature_type = Some(value.to_string()),
+                    "size" => filter.size = Some(value.to_string()),
+                    "src" | "source" => filter.source = Some(value.trim_matches('"').to_string()),
+                    _ => name_words.push(token),
+                }
+            } else if lower == "cr" && i + 1 < tokens.len() && parse_cr_range(tokens[i + 1]).is_some() {
+                filter.apply_cr(tokens[i + 1]);
+                i += 1;
+            } else if lower == "from" && i + 1 < tokens.len() {
+                filter.source = Some(tokens[i + 1..].join(" ").to_lowercase());
+                break;
+            } else if CREATURE_TYPES.contains(&lower.as_str()) && filter.creature_type.is_none() {
+                filter.creature_type = Some(lower);
+            } else if SIZES.contains(&lower.as_str()) && filter.size.is_none() {
+                filter.size = Some(lower);
+            } else {
+                name_words.push(token);
+            }
+            i += 1;
+        }
+
+        if !name_words.is_empty() {
+            filter.name = Some(name_words.join(" ").to_lowercase());
+        }
+        filter
+    }
+
+    fn apply_cr(&mut self, value: &str) {
+        if let Some((min, max)) = parse_cr_range(value) {
+            self.cr_min = min;
+            self.cr_max = max;
+        }
+    }
+
+    /// True if no criteria are set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Build the WHERE clause for this filter (parameters bound separately).
+    fn to_surql(&self) -> String {
+        let mut conditions = Vec::new();
+        if self.name.is_some() {
+            conditions.push("string::lowercase(name) CONTAINS $name");
+        }
+        if self.cr_min.is_some() {
+            conditions.push("challenge_rating >= $cr_min");
+        }
+        if self.cr_max.is_some() {
+            conditions.push("challenge_rating <= $cr_max");
+        }
+        if self.creature_type.is_some() {
+            conditions.push("string::starts_with(creature_type ?? '', $creature_type)");
+        }
+        if self.size.is_some() {
+            conditions.push("size = $size");
+        }
+        if self.source.is_some() {
+            conditions.push("string::lowercase(source) CONTAINS $source");
+        }
+        if self.library_item.is_some() {
+            conditions.push("library_item = type::thing('library_item', $library_item)");
+        }
+
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        }
+    }
+}
+
+/// Parse a single CR value ("3", "1/4", "0.5").
+fn parse_cr(value: &str) -> Option<f32> {
+    match value.split_once('/') {
+        Some((num, den)) => {
+            let num: f32 = num.parse().ok()?;
+            let den: f32 = den.parse().ok()?;
+            (den != 0.0).then_some(num / den)
+        }
+        None => value.parse().ok(),
+    }
+}
+
+/// Parse a CR range: "3-5", "5+", or a single value (exact match).
+fn parse_cr_range(value: &str) -> Option<(Option<f32>, Option<f32>)> {
+    if let Some(min) = value.strip_suffix('+') {
+        return Some((Some(parse_cr(min)?), None));
+    }
+    if let Some((min, max)) = value.split_once('-') {
+        return Some((Some(parse_cr(min)?), Some(parse_cr(max)?)));
+    }
+    let cr = parse_cr(value)?;
+    Some((Some(cr), Some(cr)))
+}
+
+// ============================================================================
+// CRUD Operations
+// ============================================================================
+
+/// Replace all stat blocks for a library item.
+///
+/// Existing blocks for the item are deleted first so re-ingestion doesn't
+/// duplicate the bestiary. Returns the number of blocks stored.
+pub async fn store_stat_blocks(
+    db: &Surreal<Db>,
+    library_item_id: &str,
+    records: Vec<StatBlockRecord>,
+) -> Result<usize, StorageError> {
+    delete_library_stat_blocks(db, library_item_id).await?;
+
+    let mut stored = 0;
+    for (i, record) in records.into_iter().enumerate() {
+        let data = serde_json::to_value(&record.data)
+            .map_err(|e| StorageError::Query(format!("Failed to serialize stat block: {}", e)))?;
+
+        db.query(
+            r#"
+            CREATE type::thing('stat_block', $id) CONTENT {
+                name: $name,
+                size: $size,
+                creature_type: $creature_type,
+                alignment: $alignment,
+                armor_class: $armor_class,
+                hit_points: $hit_points,
+                hit_dice: $hit_dice,
+                challenge_rating: $challenge_rating,
+                xp: $xp,
+                library_item: type::thing('library_item', $library_id),
+                source: $source,
+                page_number: $page_number,
+                data: $data
+            };
+        "#,
+        )
+        .bind(("id", format!("{}-{}", library_item_id, i)))
+        .bind(("name", record.name))
+        .bind(("size", record.size))
+        .bind(("creature_type", record.creature_type))
+        .bind(("alignment", record.alignment))
+        .bind(("armor_class", record.armor_class))
+        .bind(("hit_points", record.hit_points))
+        .bind(("hit_dice", record.hit_dice))
+        .bind(("challenge_rating", record.challenge_rating))
+        .bind(("xp", record.xp))
+        .bind(("library_id", library_item_id.to_string()))
+        .bind(("source", record.source))
+        .bind(("page_number", record.page_number))
+        .bind(("data", data))
+        .await
+        .map_err(|e| StorageError::Query(format!("Failed to insert stat block: {}", e)))?;
+
+        stored += 1;
+    }
+
+    tracing::info!(
+        library_item_id = %library_item_id,
+        stat_blocks = stored,
+        "Stored bestiary entries"
+    );
+
+    Ok(stored)
+}
+
+/// Delete all stat blocks harvested from a library item.
+pub async fn delete_library_stat_blocks(
+    db: &Surreal<Db>,
+    library_item_id: &str,
+) -> Result<(), StorageError> {
+    db.query("DELETE stat_block WHERE library_item = type::thing('library_item', $id)")
+        .bind(("id", library_item_id.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+    Ok(())
+}
+
+/// Get a stat block by ID (without table prefix).
+pub async fn get_stat_block(
+    db: &Surreal<Db>,
+    id: &str,
+) -> Result<Option<StatBlockRecord>, StorageError> {
+    let result: Option<StatBlockRecord> = db
+        .query(
+            "SELECT *, meta::id(id) as id, meta::id(library_item) as library_item \
+             FROM type::thing('stat_block', $id)",
+        )
+        .bind(("id", id.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+
+    Ok(result)
+}
+
+/// Search the bestiary, ordered by challenge rating then name.
+pub async fn search_stat_blocks(
+    db: &Surreal<Db>,
+    filter: &BestiaryFilter,
+    limit: usize,
+) -> Result<Vec<StatBlockRecord>, StorageError> {
+    let query = format!(
+        r#"
+        SELECT *, meta::id(id) as id, meta::id(library_item) as library_item
+        FROM stat_block
+        {}
+        ORDER BY challenge_rating, name
+        LIMIT {limit};
+    "#,
+        filter.to_surql()
+    );
+
+    let results: Vec<StatBlockRecord> = db
+        .query(&query)
+        .bind(("name", filter.name.clone()))
+        .bind(("cr_min", filter.cr_min))
+        .bind(("cr_max", filter.cr_max))
+        .bind(("creature_type", filter.creature_type.clone()))
+        .bind(("size", filter.size.clone()))
+        .bind(("source", filter.source.clone()))
+        .bind(("library_item", filter.library_item.clone()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::storage::models::{create_library_item, LibraryItem};
+    use crate::core::storage::SurrealStorage;
+    use tempfile::TempDir;
+
+    const PAGE: &str = r#"
+        Ghoul
+        Medium undead, chaotic evil
+        Armor Class 12
+        Hit Points 22 (5d8)
+        Speed 30 ft.
+        STR 13 (+1) DEX 15 (+2) CON 10 (+0) INT 7 (-2) WIS 10 (+0) CHA 6 (-2)
+        Condition Immunities charmed, exhaustion, poisoned
+        Challenge 1 (200 XP)
+
+        Wight
+        Medium undead, neutral evil
+        Armor Class 14 (studded leather)
+        Hit Points 45 (6d8 + 18)
+        Speed 30 ft.
+        Challenge 3 (700 XP)
+
+        Goblin
+        Small humanoid (goblinoid), neutral evil
+        Armor Class 15 (leather armor, shield)
+        Hit Points 7 (2d6)
+        Speed 30 ft.
+        Challenge 1/4 (50 XP)
+    "#;
+
+    async fn setup_test_db() -> (SurrealStorage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage = SurrealStorage::new(temp_dir.path().to_path_buf())
+            .await
+            .expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_collect_stat_blocks() {
+        let records = collect_stat_blocks(&[(12, PAGE.to_string())], "Monster Manual");
+        assert_eq!(records.len(), 3);
+
+        let wight = &records[1];
+        assert_eq!(wight.name, "Wight");
+        assert_eq!(wight.creature_type.as_deref(), Some("undead"));
+        assert_eq!(wight.hit_dice.as_deref(), Some("6d8 + 18"));
+        assert_eq!(wight.page_number, Some(12));
+        assert_eq!(wight.source, "Monster Manual");
+        assert_eq!(records[2].cr_label().as_deref(), Some("1/4"));
+    }
+
+    #[test]
+    fn test_collect_stat_blocks_dedupes_by_name() {
+        let pages = vec![(1, PAGE.to_string()), (2, PAGE.to_string())];
+        assert_eq!(collect_stat_blocks(&pages, "MM").len(), 3);
+    }
+
+    #[test]
+    fn test_collect_stat_blocks_across_page_break() {
+        let split = PAGE.find("Hit Points 7").unwrap();
+        let pages = vec![
+            (12, PAGE[..split].to_string()),
+            (13, PAGE[split..].to_string()),
+        ];
+        let records = collect_stat_blocks(&pages, "Monster Manual");
+        assert_eq!(records.len(), 3);
+
+        let goblin = &records[2];
+        assert_eq!(goblin.name, "Goblin");
+        assert_eq!(goblin.page_number, Some(12));
+        assert_eq!(goblin.hit_points, Some(7));
+        assert_eq!(goblin.cr_label().as_deref(), Some("1/4"));
+    }
+
+    #[test]
+    fn test_filter_parse_natural_query() {
+        let filter = BestiaryFilter::parse("undead CR 3-5 from Monster Manual");
+        assert_eq!(filter.creature_type.as_deref(), Some("undead"));
+        assert_eq!(filter.cr_min, Some(3.0));
+        assert_eq!(filter.cr_max, Some(5.0));
+        assert_eq!(filter.source.as_deref(), Some("monster manual"));
+        assert!(filter.name.is_none());
+    }
+
+    #[test]
+    fn test_filter_parse_keyed_terms() {
+        let filter = BestiaryFilter::parse("dragon size:huge cr:10+ red");
+        assert_eq!(filter.creature_type.as_deref(), Some("dragon"));
+        assert_eq!(filter.size.as_deref(), Some("huge"));
+        assert_eq!(filter.cr_min, Some(10.0));
+        assert_eq!(filter.cr_max, None);
+        assert_eq!(filter.name.as_deref(), Some("red"));
+
+        let filter = BestiaryFilter::parse("cr:1/4");
+        assert_eq!(filter.cr_min, Some(0.25));
+        assert_eq!(filter.cr_max, Some(0.25));
+
+        assert!(BestiaryFilter::parse("   ").is_empty());
+    }
+
+    #[test]
+    fn test_filter_to_surql() {
+        assert_eq!(BestiaryFilter::new().to_surql(), "");
+        let sql = BestiaryFilter::new().creature_type("Undead").cr_range(Some(3.0), None).to_surql();
+        assert!(sql.starts_with("WHERE "));
+        assert!(sql.contains("challenge_rating >= $cr_min"));
+        assert!(sql.contains("$creature_type"));
+        assert!(!sql.contains("$cr_max"));
+    }
+
+    #[tokio::test]
+    async fn test_store_and_search_stat_blocks() {
+        let (storage, _temp_dir) = setup_test_db().await;
+        let db = storage.db();
+
+        let item = LibraryItem::new("monster-manual".to_string(), "Monster Manual".to_string());
+        let item_id = create_library_item(db, &item).await.unwrap();
+
+        let records = collect_stat_blocks(&[(12, PAGE.to_string())], &item.title);
+        let stored = store_stat_blocks(db, &item_id, records.clone()).await.unwrap();
+        assert_eq!(stored, 3);
+
+        // Re-storing replaces rather than duplicates
+        store_stat_blocks(db, &item_id, records).await.unwrap();
+        let all = search_stat_blocks(db, &BestiaryFilter::new(), 100).await.unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].name, "Goblin");
+
+        let undead = search_stat_blocks(db, &BestiaryFilter::parse("undead cr 2-5 from monster"), 100)
+            .await
+            .unwrap();
+        assert_eq!(undead.len(), 1);
+        assert_eq!(undead[0].name, "Wight");
+        assert_eq!(undead[0].library_item.as_deref(), Some(item_id.as_str()));
+
+        let ghoul = search_stat_blocks(db, &BestiaryFilter::new().name("ghou"), 10).await.unwrap();
+        let fetched = get_stat_block(db, ghoul[0].id.as_deref().unwrap()).await.unwrap().unwrap();
+        assert!(fetched.data.condition_immunities.contains(&"charmed".to_string()));
+
+        delete_library_stat_blocks(db, &item_id).await.unwrap();
+        assert!(search_stat_blocks(db, &BestiaryFilter::new(), 100).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,847 @@
+//! Embedding index management: keeps the `chunk` HNSW index in step with the
+//! active embedding model.
+//!
+//! `SCHEMA_V1` creates the vector index at 768 dimensions (nomic-embed-text).
+//! At startup `ensure_embedding_index()` compares the active provider's model
+//! and dimensions against the `embedding_index:active` record:
+//!
+//! - Fresh library (no vectors yet): the index is redefined at the provider's
+//!   dimensions straight away.
+//! - Same model: nothing to do.
+//! - Different model: a `ReembedJob` embeds every chunk into the staging
+//!   `embedding_next` field while the old index keeps serving searches, then
+//!   swaps the staged vectors in and rebuilds the index at the new dimension.
+//!
+//! Progress lives in the chunks themselves (`embedding_next_model`), so an
+//! interrupted job resumes where it stopped the next time it runs.
+//!
+//! Once the index serves the active model, a `BackfillJob` embeds the chunks
+//! that still lack a vector from it: chunks stored while the provider was
+//! down (or before one was configured) and stray vectors from another model.
+//! It writes straight into the live index, with rate limiting and retries,
+//! and likewise resumes from the chunks themselves.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::engine::local::Db;
+use surrealdb::Surreal;
+
+use super::error::StorageError;
+use crate::core::search::EmbeddingProvider;
+
+/// Chunks embedded per provider call during re-embedding.
+pub const DEFAULT_REEMBED_BATCH: usize = 32;
+
+/// Attempts per batch after the first during a backfill.
+pub const DEFAULT_BACKFILL_RETRIES: u32 = 3;
+
+/// Delay before the first retry of a failed batch; doubles on each retry.
+const BACKFILL_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Batches in a row that may fail before a backfill gives up until its next
+/// run (the provider is most likely down).
+const BACKFILL_MAX_FAILED_BATCHES: usize = 3;
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Lifecycle of the vector index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexStatus {
+    /// Index matches `model` / `dimensions`
+    Ready,
+    /// A re-embed towards `target_model` is in progress
+    Reembedding,
+}
+
+/// The `embedding_index:active` record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingIndexState {
+    /// Model whose vectors the live index holds (e.g. "ollama/nomic-embed-text")
+    pub model: String,
+    /// Dimension of the live index
+    pub dimensions: usize,
+    pub status: IndexStatus,
+    /// Model being re-embedded towards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_dimensions: Option<usize>,
+}
+
+impl EmbeddingIndexState {
+    /// State for an index that serves `model` at `dimensions`.
+    pub fn ready(model: impl Into<String>, dimensions: usize) -> Self {
+        Self {
+            model: model.into(),
+            dimensions,
+            status: IndexStatus::Ready,
+            target_model: None,
+            target_dimensions: None,
+        }
+    }
+
+    /// Whether vectors from `model` can be written to and searched in the live index.
+    pub fn serves(&self, model: &str, dimensions: usize) -> bool {
+        self.model == model && self.dimensions == dimensions
+    }
+}
+
+/// Result of `ensure_embedding_index()`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexCheck {
+    /// The index serves the active model
+    Ready,
+    /// Stored vectors came from another model; run a `ReembedJob`
+    NeedsReembed {
+        from: Option<String>,
+        to: String,
+        chunks: usize,
+    },
+}
+
+/// Progress reported by `ReembedJob::run()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReembedProgress {
+    /// Chunks re-embedded so far (including earlier, interrupted runs)
+    pub processed: usize,
+    pub total: usize,
+}
+
+/// Progress reported by `BackfillJob::run()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackfillProgress {
+    /// Chunks embedded in this run
+    pub embedded: usize,
+    /// Chunks whose batch kept failing; retried on the next run
+    pub failed: usize,
+    /// Chunks lacking a vector when the run started
+    pub total: usize,
+}
+
+// ============================================================================
+// Index State
+// ============================================================================
+
+/// SurrealQL defining the chunk vector index at `dimensions`.
+pub fn vector_index_definition(dimensions: usize) -> String {
+    format!(
+        "DEFINE INDEX chunk_embedding ON chunk FIELDS embedding \
+         HNSW DIMENSION {dimensions} DIST COSINE EFC 150 M 12;"
+    )
+}
+
+/// Load the `embedding_index:active` record, if any.
+pub async fn get_index_state(db: &Surreal<Db>) -> Result<Option<EmbeddingIndexState>, StorageError> {
+    let state: Option<EmbeddingIndexState> = db
+        .query("SELECT * FROM embedding_index:active")
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(format!("Failed to read index state: {}", e)))?;
+    Ok(state)
+}
+
+/// Persist the `embedding_index:active` record.
+pub async fn save_index_state(db: &Surreal<Db>, state: &EmbeddingIndexState) -> Result<(), StorageError> {
+    db.query("UPSERT embedding_index:active CONTENT $state")
+        .bind(("state", state.clone()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .check()
+        .map_err(|e| StorageError::Query(format!("Failed to save index state: {}", e)))?;
+
+    tracing::debug!(model = %state.model, status = ?state.status, "Saved embedding index state");
+    Ok(())
+}
+
+/// Drop and recreate the chunk vector index at `dimensions`.
+pub async fn rebuild_vector_index(db: &Surreal<Db>, dimensions: usize) -> Result<(), StorageError> {
+    let sql = format!(
+        "REMOVE INDEX IF EXISTS chunk_embedding ON chunk; {}",
+        vector_index_definition(dimensions)
+    );
+    db.query(sql)
+        .await
+        .map_err(|e| StorageError::Index(e.to_string()))?
+        .check()
+        .map_err(|e| StorageError::Index(format!("Failed to rebuild vector index: {}", e)))?;
+
+    tracing::info!(dimensions, "Rebuilt chunk vector index");
+    Ok(())
+}
+
+async fn count_chunks(db: &Surreal<Db>, condition: &str, model: &str) -> Result<usize, StorageError> {
+    #[derive(Debug, Deserialize)]
+    struct CountResult {
+        count: i64,
+    }
+
+    let sql = format!("SELECT count() AS count FROM chunk WHERE {condition} GROUP ALL");
+    let result: Option<CountResult> = db
+        .query(sql)
+        .bind(("model", model.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(format!("Failed to count chunks: {}", e)))?;
+    Ok(result.map(|r| r.count as usize).unwrap_or(0))
+}
+
+/// Distinct `embedding_model` labels on chunks that carry a vector.
+async fn stored_models(db: &Surreal<Db>) -> Result<Vec<String>, StorageError> {
+    #[derive(Debug, Deserialize)]
+    struct ModelRow {
+        embedding_model: Option<String>,
+    }
+
+    let rows: Vec<ModelRow> = db
+        .query("SELECT embedding_model FROM chunk WHERE embedding IS NOT NONE GROUP BY embedding_model")
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(format!("Failed to list embedding models: {}", e)))?;
+    Ok(rows.into_iter().map(|r| r.embedding_model.unwrap_or_default()).collect())
+}
+
+/// Whether a chunk label written before model ids were recorded (a bare
+/// provider or model name) refers to `model_id`.
+fn is_legacy_label(label: &str, model_id: &str) -> bool {
+    !label.is_empty() && (label == model_id || model_id.split('/').any(|part| part == label))
+}
+
+/// Make sure the vector index serves the active embedding model.
+///
+/// Returns `IndexCheck::NeedsReembed` when stored vectors came from a
+/// different model; searches keep using the old index until the
+/// `ReembedJob` swaps it.
+pub async fn ensure_embedding_index(
+    db: &Surreal<Db>,
+    model_id: &str,
+    dimensions: usize,
+) -> Result<IndexCheck, StorageError> {
+    let state = get_index_state(db).await?;
+    if let Some(ref state) = state {
+        if state.status == IndexStatus::Ready && state.serves(model_id, dimensions) {
+            return Ok(IndexCheck::Ready);
+        }
+    }
+
+    let embedded = count_chunks(db, "embedding IS NOT NONE", model_id).await?;
+    if embedded == 0 {
+        // Nothing to migrate: size the index for this model now
+        rebuild_vector_index(db, dimensions).await?;
+        save_index_state(db, &EmbeddingIndexState::ready(model_id, dimensions)).await?;
+        return Ok(IndexCheck::Ready);
+    }
+
+    // Libraries embedded before the index state was tracked: adopt the vectors
+    // if they were produced by this model at the schema's default dimension.
+    if state.is_none() && dimensions == 768 {
+        let models = stored_models(db).await?;
+        if models.iter().all(|label| is_legacy_label(label, model_id)) {
+            db.query("UPDATE chunk SET embedding_model = $model WHERE embedding IS NOT NONE")
+                .bind(("model", model_id.to_string()))
+                .await
+                .map_err(|e| StorageError::Query(e.to_string()))?;
+            save_index_state(db, &EmbeddingIndexState::ready(model_id, dimensions)).await?;
+            return Ok(IndexCheck::Ready);
+        }
+    }
+
+    let chunks = count_chunks(db, "true", model_id).await?;
+    Ok(IndexCheck::NeedsReembed {
+        from: state.map(|s| s.model),
+        to: model_id.to_string(),
+        chunks,
+    })
+}
+
+// ============================================================================
+// Re-embedding Job
+// ============================================================================
+
+/// Chunks still lacking a vector from the target model.
+const PENDING_CONDITION: &str = "embedding_model != $model AND embedding_next_model != $model";
+
+/// Re-embeds every chunk with a new provider, then swaps the vector index.
+pub struct ReembedJob {
+    db: Arc<Surreal<Db>>,
+    provider: Arc<dyn EmbeddingProvider>,
+    batch_size: usize,
+}
+
+impl ReembedJob {
+    pub fn new(db: Arc<Surreal<Db>>, provider: Arc<dyn EmbeddingProvider>) -> Self {
+        Self {
+            db,
+            provider,
+            batch_size: DEFAULT_REEMBED_BATCH,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Run (or resume) the job to completion.
+    ///
+    /// `on_progress` is called after each batch.
+    pub async fn run(
+        &self,
+        mut on_progress: impl FnMut(ReembedProgress) + Send,
+    ) -> Result<ReembedProgress, StorageError> {
+        let db = self.db.as_ref();
+        let model = self.provider.model_id();
+        let dimensions = self.provider.dimensions();
+
+        let mut state = get_index_state(db)
+            .await?
+            .unwrap_or_else(|| EmbeddingIndexState::ready("", 768));
+        state.status = IndexStatus::Reembedding;
+        state.target_model = Some(model.clone());
+        state.target_dimensions = Some(dimensions);
+        save_index_state(db, &state).await?;
+
+        let total = count_chunks(db, "true", &model).await?;
+        let pending = count_chunks(db, PENDING_CONDITION, &model).await?;
+        let mut progress = ReembedProgress {
+            processed: total.saturating_sub(pending),
+            total,
+        };
+        tracing::info!(model = %model, dimensions, pending, total, "Re-embedding library");
+
+        loop {
+            let batch = self.next_batch(&model).await?;
+            if batch.is_empty() {
+                break;
+            }
+
+            let texts: Vec<&str> = batch.iter().map(|c| c.content.as_str()).collect();
+            let embeddings = self
+                .provider
+                .embed_batch(&texts)
+                .await
+                .map_err(|e| StorageError::Embedding(e.to_string()))?;
+            if embeddings.len() != batch.len() {
+                return Err(StorageError::Embedding(format!(
+                    "Provider returned {} embeddings for {} chunks",
+                    embeddings.len(),
+                    batch.len()
+                )));
+            }
+
+            for (chunk, embedding) in batch.into_iter().zip(embeddings) {
+                if embedding.len() != dimensions {
+                    return Err(StorageError::Embedding(format!(
+                        "Expected {} dimensions from {}, got {}",
+                        dimensions,
+                        model,
+                        embedding.len()
+                    )));
+                }
+                db.query(
+                    "UPDATE type::thing('chunk', $id) SET \
+                     embedding_next = $embedding, embedding_next_model = $model",
+                )
+                .bind(("id", chunk.id))
+                .bind(("embedding", embedding))
+                .bind(("model", model.clone()))
+                .await
+                .map_err(|e| StorageError::Query(format!("Failed to stage embedding: {}", e)))?;
+                progress.processed += 1;
+            }
+            on_progress(progress);
+        }
+
+        self.swap(&model, dimensions).await?;
+        Ok(progress)
+    }
+
+    async fn next_batch(&self, model: &str) -> Result<Vec<PendingChunk>, StorageError> {
+        let sql = format!(
+            "SELECT meta::id(id) AS id, content FROM chunk WHERE {PENDING_CONDITION} LIMIT $limit"
+        );
+        self.db
+            .query(sql)
+            .bind(("model", model.to_string()))
+            .bind(("limit", self.batch_size))
+            .await
+            .map_err(|e| StorageError::Query(e.to_string()))?
+            .take(0)
+            .map_err(|e| StorageError::Query(format!("Failed to read pending chunks: {}", e)))
+    }
+
+    /// Move staged vectors into `embedding` and rebuild the index at the new dimension.
+    async fn swap(&self, model: &str, dimensions: usize) -> Result<(), StorageError> {
+        let db = self.db.as_ref();
+        db.query(
+            "REMOVE INDEX IF EXISTS chunk_embedding ON chunk; \
+             UPDATE chunk SET embedding = embedding_next, embedding_model = embedding_next_model \
+                 WHERE embedding_next_model = $model; \
+             UPDATE chunk SET embedding_next = NONE, embedding_next_model = NONE \
+                 WHERE embedding_next_model IS NOT NONE;",
+        )
+        .bind(("model", model.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .check()
+        .map_err(|e| StorageError::Query(format!("Failed to swap embeddings: {}", e)))?;
+
+        rebuild_vector_index(db, dimensions).await?;
+        save_index_state(db, &EmbeddingIndexState::ready(model, dimensions)).await?;
+        tracing::info!(model = %model, dimensions, "Re-embedding complete");
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PendingChunk {
+    id: String,
+    content: String,
+}
+
+// ============================================================================
+// Backfill Job
+// ============================================================================
+
+/// Chunks without a vector from the live index's model.
+const MISSING_CONDITION: &str = "embedding IS NONE OR embedding_model != $model";
+
+/// Embeds chunks stored without a vector (or with one from another model)
+/// into the live index.
+pub struct BackfillJob {
+    db: Arc<Surreal<Db>>,
+    provider: Arc<dyn EmbeddingProvider>,
+    batch_size: usize,
+    /// Minimum time between provider calls
+    min_interval: Duration,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+impl BackfillJob {
+    pub fn new(db: Arc<Surreal<Db>>, provider: Arc<dyn EmbeddingProvider>) -> Self {
+        Self {
+            db,
+            provider,
+            batch_size: DEFAULT_REEMBED_BATCH,
+            min_interval: Duration::ZERO,
+            max_retries: DEFAULT_BACKFILL_RETRIES,
+            retry_delay: BACKFILL_RETRY_DELAY,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Limit provider calls to `per_minute` (0 = no limit).
+    pub fn with_requests_per_minute(mut self, per_minute: u32) -> Self {
+        self.min_interval = match per_minute {
+            0 => Duration::ZERO,
+            n => Duration::from_secs(60) / n,
+        };
+        self
+    }
+
+    pub fn with_retries(mut self, max_retries: u32, retry_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Whether the live index serves the provider's model, so its vectors
+    /// can be written there. While a `ReembedJob` is pending or running the
+    /// backfill has to wait.
+    pub async fn is_ready(&self) -> Result<bool, StorageError> {
+        let state = get_index_state(self.db.as_ref()).await?;
+        Ok(state.is_some_and(|state| {
+            state.status == IndexStatus::Ready
+                && state.serves(&self.provider.model_id(), self.provider.dimensions())
+        }))
+    }
+
+    /// Number of chunks lacking a vector from the provider's model.
+    pub async fn pending(&self) -> Result<usize, StorageError> {
+        count_chunks(self.db.as_ref(), MISSING_CONDITION, &self.provider.model_id()).await
+    }
+
+    /// Embed every chunk that lacks a vector, calling `on_progress` after
+    /// each batch.
+    ///
+    /// A batch that still fails after its retries is skipped for the rest of
+    /// the run; if several fail in a row the run stops with the last error.
+    /// Either way the chunks keep lacking a vector, so the next run picks
+    /// them up again.
+    pub async fn run(
+        &self,
+        mut on_progress: impl FnMut(BackfillProgress) + Send,
+    ) -> Result<BackfillProgress, StorageError> {
+        let model = self.provider.model_id();
+        let dimensions = self.provider.dimensions();
+        if !self.is_ready().await? {
+            return Err(StorageError::Embedding(format!(
+                "Vector index does not serve {model} yet; re-embed the library first"
+            )));
+        }
+
+        let mut progress = BackfillProgress {
+            total: self.pending().await?,
+            ..Default::default()
+        };
+        tracing::info!(model = %model, pending = progress.total, "Backfilling embeddings");
+
+        let mut skipped: Vec<String> = Vec::new();
+        let mut failed_batches = 0;
+        let mut last_call: Option<tokio::time::Instant> = None;
+
+        loop {
+            let batch = self.next_batch(&model, &skipped).await?;
+            if batch.is_empty() {
+                break;
+            }
+
+            if let Some(last) = last_call {
+                tokio::time::sleep_until(last + self.min_interval).await;
+            }
+            last_call = Some(tokio::time::Instant::now());
+
+            let texts: Vec<&str> = batch.iter().map(|c| c.content.as_str()).collect();
+            let embeddings = match self.embed_with_retry(&texts).await {
+                Ok(embeddings) => embeddings,
+                Err(e) => {
+                    failed_batches += 1;
+                    progress.failed += batch.len();
+                    skipped.extend(batch.into_iter().map(|c| c.id));
+                    on_progress(progress);
+                    if failed_batches >= BACKFILL_MAX_FAILED_BATCHES {
+                        return Err(e);
+                    }
+                    tracing::warn!(model = %model, "Skipping backfill batch: {}", e);
+                    continue;
+                }
+            };
+            failed_batches = 0;
+
+            for (chunk, embedding) in batch.into_iter().zip(embeddings) {
+                if embedding.len() != dimensions {
+                    tracing::warn!(
+                        chunk = %chunk.id,
+                        "Expected {} dimensions from {}, got {}",
+                        dimensions,
+                        model,
+                        embedding.len()
+                    );
+                    progress.failed += 1;
+                    skipped.push(chunk.id);
+                    continue;
+                }
+                self.db
+                    .query(
+                        "UPDATE type::thing('chunk', $id) SET \
+                         embedding = $embedding, embedding_model = $model",
+                    )
+                    .bind(("id", chunk.id))
+                    .bind(("embedding", embedding))
+                    .bind(("model", model.clone()))
+                    .await
+                    .map_err(|e| StorageError::Query(format!("Failed to store embedding: {}", e)))?;
+                progress.embedded += 1;
+            }
+            on_progress(progress);
+        }
+
+        tracing::info!(
+            model = %model,
+            embedded = progress.embedded,
+            failed = progress.failed,
+            "Embedding backfill complete"
+        );
+        Ok(progress)
+    }
+
+    /// Call the provider, retrying with exponential backoff.
+    async fn embed_with_retry(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, StorageError> {
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        loop {
+            let error = match self.provider.embed_batch(texts).await {
+                Ok(embeddings) if embeddings.len() == texts.len() => return Ok(embeddings),
+                Ok(embeddings) => format!(
+                    "Provider returned {} embeddings for {} chunks",
+                    embeddings.len(),
+                    texts.len()
+                ),
+                Err(e) => e.to_string(),
+            };
+            if attempt >= self.max_retries {
+                return Err(StorageError::Embedding(error));
+            }
+            attempt += 1;
+            tracing::debug!(attempt, "Embedding batch failed, retrying: {}", error);
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+
+    async fn next_batch(
+        &self,
+        model: &str,
+        skipped: &[String],
+    ) -> Result<Vec<PendingChunk>, StorageError> {
+        let sql = format!(
+            "SELECT meta::id(id) AS id, content FROM chunk \
+             WHERE ({MISSING_CONDITION}) AND meta::id(id) NOTINSIDE $skipped LIMIT $limit"
+        );
+        self.db
+            .query(sql)
+            .bind(("model", model.to_string()))
+            .bind(("skipped", skipped.to_vec()))
+            .bind(("limit", self.batch_size))
+            .await
+            .map_err(|e| StorageError::Query(e.to_string()))?
+            .take(0)
+            .map_err(|e| StorageError::Query(format!("Failed to read chunks to backfill: {}", e)))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::search::embeddings::Result as EmbeddingResult;
+    use crate::core::storage::SurrealStorage;
+    use async_trait::async_trait;
+    use tempfile::TempDir;
+
+    /// Deterministic provider producing `dims`-length vectors.
+    struct FakeProvider {
+        model: &'static str,
+        dims: usize,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for FakeProvider {
+        async fn embed(&self, text: &str) -> EmbeddingResult<Vec<f32>> {
+            let mut v = vec![0.1; self.dims];
+            v[0] = text.len() as f32;
+            Ok(v)
+        }
+
+        async fn embed_batch(&self, texts: &[&str]) -> EmbeddingResult<Vec<Vec<f32>>> {
+            let mut out = Vec::new();
+            for text in texts {
+                out.push(self.embed(text).await?);
+            }
+            Ok(out)
+        }
+
+        fn dimensions(&self) -> usize {
+            self.dims
+        }
+
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        fn model_id(&self) -> String {
+            self.model.to_string()
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+    }
+
+    async fn setup_test_db() -> (SurrealStorage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage = SurrealStorage::new(temp_dir.path().to_path_buf())
+            .await
+            .expect("Failed to create storage");
+        storage
+            .db()
+            .query("CREATE library_item:book SET slug = 'book', title = 'Book', file_path = '/tmp/book.pdf'")
+            .await
+            .expect("Failed to create library item");
+        (storage, temp_dir)
+    }
+
+    /// Provider that fails its first call and every batch containing "poison".
+    struct FlakyProvider {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for FlakyProvider {
+        async fn embed(&self, _text: &str) -> EmbeddingResult<Vec<f32>> {
+            Ok(vec![0.3; 384])
+        }
+
+        async fn embed_batch(&self, texts: &[&str]) -> EmbeddingResult<Vec<Vec<f32>>> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if call == 0 || texts.iter().any(|t| t.contains("poison")) {
+                return Err(crate::core::search::embeddings::EmbeddingError::ApiError(
+                    "unavailable".to_string(),
+                ));
+            }
+            Ok(texts.iter().map(|_| vec![0.3; 384]).collect())
+        }
+
+        fn dimensions(&self) -> usize {
+            384
+        }
+
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn model_id(&self) -> String {
+            "candle/minilm".to_string()
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+    }
+
+    async fn add_chunk_without_vector(db: &Surreal<Db>, content: &str) {
+        db.query(
+            "CREATE chunk SET content = $content, library_item = library_item:book, \
+             content_type = 'rules'",
+        )
+        .bind(("content", content.to_string()))
+        .await
+        .expect("Failed to create chunk");
+    }
+
+    async fn add_chunk(db: &Surreal<Db>, content: &str, embedding: Vec<f32>, model: &str) {
+        db.query(
+            "CREATE chunk SET content = $content, library_item = library_item:book, \
+             content_type = 'rules', embedding = $embedding, embedding_model = $model",
+        )
+        .bind(("content", content.to_string()))
+        .bind(("embedding", embedding))
+        .bind(("model", model.to_string()))
+        .await
+        .expect("Failed to create chunk");
+    }
+
+    #[test]
+    fn test_vector_index_definition() {
+        let sql = vector_index_definition(384);
+        assert!(sql.contains("HNSW DIMENSION 384"));
+        assert!(sql.contains("DIST COSINE"));
+    }
+
+    #[test]
+    fn test_legacy_label() {
+        assert!(is_legacy_label("ollama", "ollama/nomic-embed-text"));
+        assert!(is_legacy_label("nomic-embed-text", "ollama/nomic-embed-text"));
+        assert!(!is_legacy_label("openai", "ollama/nomic-embed-text"));
+        assert!(!is_legacy_label("", "ollama/nomic-embed-text"));
+    }
+
+    #[tokio::test]
+    async fn test_fresh_library_resizes_index() {
+        let (storage, _temp) = setup_test_db().await;
+        let check = ensure_embedding_index(storage.db(), "candle/minilm", 384).await.unwrap();
+        assert_eq!(check, IndexCheck::Ready);
+
+        let state = get_index_state(storage.db()).await.unwrap().unwrap();
+        assert_eq!(state, EmbeddingIndexState::ready("candle/minilm", 384));
+    }
+
+    #[tokio::test]
+    async fn test_model_change_reembeds_and_swaps() {
+        let (storage, _temp) = setup_test_db().await;
+        let db = storage.db();
+        add_chunk(db, "Fireball deals fire damage", vec![0.5; 768], "ollama").await;
+        add_chunk(db, "Grapple rules", vec![0.2; 768], "ollama").await;
+
+        // Legacy vectors from the same model are adopted without re-embedding
+        let check = ensure_embedding_index(db, "ollama/nomic-embed-text", 768).await.unwrap();
+        assert_eq!(check, IndexCheck::Ready);
+
+        let check = ensure_embedding_index(db, "candle/minilm", 384).await.unwrap();
+        assert!(matches!(check, IndexCheck::NeedsReembed { chunks: 2, .. }));
+
+        let provider = Arc::new(FakeProvider { model: "candle/minilm", dims: 384 });
+        let mut reports = Vec::new();
+        let progress = ReembedJob::new(storage.clone_db(), provider)
+            .with_batch_size(1)
+            .run(|p| reports.push(p))
+            .await
+            .unwrap();
+        assert_eq!(progress, ReembedProgress { processed: 2, total: 2 });
+        assert_eq!(reports.len(), 2);
+
+        let state = get_index_state(db).await.unwrap().unwrap();
+        assert_eq!(state, EmbeddingIndexState::ready("candle/minilm", 384));
+        assert_eq!(count_chunks(db, "embedding_model = $model", "candle/minilm").await.unwrap(), 2);
+        assert_eq!(count_chunks(db, "embedding_next IS NOT NONE", "").await.unwrap(), 0);
+        assert_eq!(
+            ensure_embedding_index(db, "candle/minilm", 384).await.unwrap(),
+            IndexCheck::Ready
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backfill_embeds_missing_and_stale_chunks() {
+        let (storage, _temp) = setup_test_db().await;
+        let db = storage.db();
+        ensure_embedding_index(db, "candle/minilm", 384).await.unwrap();
+        add_chunk_without_vector(db, "Stored while the provider was down").await;
+        add_chunk(db, "Embedded by another model", vec![0.2; 384], "other/model").await;
+        add_chunk(db, "Already embedded", vec![0.4; 384], "candle/minilm").await;
+
+        let provider = Arc::new(FakeProvider { model: "candle/minilm", dims: 384 });
+        let job = BackfillJob::new(storage.clone_db(), provider).with_batch_size(1);
+        assert!(job.is_ready().await.unwrap());
+        assert_eq!(job.pending().await.unwrap(), 2);
+
+        let mut reports = Vec::new();
+        let progress = job.run(|p| reports.push(p)).await.unwrap();
+        assert_eq!(progress, BackfillProgress { embedded: 2, failed: 0, total: 2 });
+        assert_eq!(reports.len(), 2);
+        assert_eq!(job.pending().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_retries_then_skips_failing_batches() {
+        let (storage, _temp) = setup_test_db().await;
+        let db = storage.db();
+        ensure_embedding_index(db, "candle/minilm", 384).await.unwrap();
+        add_chunk_without_vector(db, "Grapple rules").await;
+        add_chunk_without_vector(db, "poison rules").await;
+
+        let provider = Arc::new(FlakyProvider { calls: Default::default() });
+        let job = BackfillJob::new(storage.clone_db(), provider)
+            .with_batch_size(1)
+            .with_retries(2, Duration::ZERO);
+        let progress = job.run(|_| {}).await.unwrap();
+
+        assert_eq!(progress, BackfillProgress { embedded: 1, failed: 1, total: 2 });
+        assert_eq!(job.pending().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_waits_for_reembed() {
+        let (storage, _temp) = setup_test_db().await;
+        ensure_embedding_index(storage.db(), "ollama/nomic-embed-text", 768).await.unwrap();
+
+        let provider = Arc::new(FakeProvider { model: "candle/minilm", dims: 384 });
+        let job = BackfillJob::new(storage.clone_db(), provider);
+        assert!(!job.is_ready().await.unwrap());
+        assert!(job.run(|_| {}).await.is_err());
+    }
+}
@@ -9,6 +9,7 @@
 //! - **3.1.1**: `ingest_chunks()` - Bulk insert document chunks (FR-2.1, FR-6.2)
 //! - **3.1.2**: `delete_library_chunks()` - Remove all chunks for a library item (FR-8.2)
 //! - **3.1.3**: `ingest_chunks_with_embeddings()` - Batch ingestion with pre-computed embeddings
+//! - `apply_chunk_changes()` - Incremental re-ingestion that keeps unchanged chunks
 //!
 //! # Example
 //!
@@ -57,8 +58,11 @@ use super::error::StorageError;
 /// - `chapter_title`/`section_title` - Document structure context
 /// - `chunk_type` - Semantic type: "table", "stat_block", "spell", "narrative"
 /// - `semantic_keywords` - Extracted keywords for hybrid search boosting
-/// - `embedding` - 768-dimension vector for semantic search (nomic-embed-text)
-/// - `embedding_model` - Model identifier (e.g., "nomic-embed-text-v1.5")
+/// - `content_hash` - Hash of the content, used to match chunks on re-ingestion
+/// - `game_system` - Detected game system ID (e.g., "dnd5e") for filtering
+/// - `challenge_rating`/`spell_level`/`damage_types`/`creature_types` - Filterable TTRPG attributes
+/// - `embedding` - Vector for semantic search (dimension set by the embedding model)
+/// - `embedding_model` - Model identifier (e.g., "ollama/nomic-embed-text")
 /// - `metadata` - Arbitrary JSON for custom fields
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ChunkData {
@@ -100,11 +104,35 @@ pub struct ChunkData {
     #[serde(default)]
     pub semantic_keywords: Option<Vec<String>>,
 
-    /// 768-dimension embedding vector for semantic search.
+    /// Hash of the content, used to match chunks when a document is re-ingested.
+    #[serde(default)]
+    pub content_hash: Option<String>,
+
+    /// Game system ID detected for the document (e.g., "dnd5e", "pf2e").
+    #[serde(default)]
+    pub game_system: Option<String>,
+
+    /// Challenge rating found in the chunk (fractions as decimals, 1/4 = 0.25).
+    #[serde(default)]
+    pub challenge_rating: Option<f32>,
+
+    /// Spell level found in the chunk (0 for cantrips).
+    #[serde(default)]
+    pub spell_level: Option<i32>,
+
+    /// Damage types mentioned in the chunk.
+    #[serde(default)]
+    pub damage_types: Option<Vec<String>>,
+
+    /// Creature types mentioned in the chunk.
+    #[serde(default)]
+    pub creature_types: Option<Vec<String>>,
+
+    /// Embedding vector for semantic search; must match the index dimension.
     #[serde(default)]
     pub embedding: Option<Vec<f32>>,
 
-    /// Embedding model identifier (e.g., "nomic-embed-text-v1.5").
+    /// Embedding model identifier (e.g., "ollama/nomic-embed-text").
     #[serde(default)]
     pub embedding_model: Option<String>,
 
@@ -176,46 +204,7 @@ pub async fn ingest_chunks(
 
     for (i, chunk) in chunks.into_iter().enumerate() {
         let chunk_id = format!("{}-{}", library_item_id, i);
-
-        let query = r#"
-            CREATE type::thing('chunk', $chunk_id) CONTENT {
-                content: $content,
-                library_item: type::thing('library_item', $library_id),
-                content_type: $content_type,
-                page_number: $page_number,
-                page_start: $page_start,
-                page_end: $page_end,
-                chunk_index: $chunk_index,
-                section_path: $section_path,
-                chapter_title: $chapter_title,
-                section_title: $section_title,
-                chunk_type: $chunk_type,
-                semantic_keywords: $keywords,
-                embedding: $embedding,
-                embedding_model: $embedding_model,
-                metadata: $metadata
-            };
-        "#;
-
-        let result = db
-            .query(query)
-            .bind(("chunk_id", chunk_id))
-            .bind(("content", chunk.content))
-            .bind(("library_id", library_id_owned.clone()))
-            .bind(("content_type", chunk.content_type))
-            .bind(("page_number", chunk.page_number))
-            .bind(("page_start", chunk.page_start))
-            .bind(("page_end", chunk.page_end))
-            .bind(("chunk_index", i as i32))
-            .bind(("section_path", chunk.section_path))
-            .bind(("chapter_title", chunk.chapter_title))
-            .bind(("section_title", chunk.section_title))
-            .bind(("chunk_type", chunk.chunk_type))
-            .bind(("keywords", chunk.semantic_keywords))
-            .bind(("embedding", chunk.embedding))
-            .bind(("embedding_model", chunk.embedding_model))
-            .bind(("metadata", chunk.metadata))
-            .await;
+        let result = create_chunk(db, chunk_id, &library_id_owned, i, chunk).await;
 
         if let Err(e) = result {
             // Rollback on error
@@ -266,6 +255,374 @@ pub async fn ingest_chunks(
     Ok(inserted)
 }
 
+/// Create one chunk record linked to a library item.
+async fn create_chunk(
+    db: &Surreal<Db>,
+    chunk_id: String,
+    library_item_id: &str,
+    chunk_index: usize,
+    chunk: ChunkData,
+) -> Result<(), surrealdb::Error> {
+    let query = r#"
+        CREATE type::thing('chunk', $chunk_id) CONTENT {
+            content: $content,
+            library_item: type::thing('library_item', $library_id),
+            content_type: $content_type,
+            page_number: $page_number,
+            page_start: $page_start,
+            page_end: $page_end,
+            chunk_index: $chunk_index,
+            section_path: $section_path,
+            chapter_title: $chapter_title,
+            section_title: $section_title,
+            chunk_type: $chunk_type,
+            semantic_keywords: $keywords,
+            content_hash: $content_hash,
+            game_system: $game_system,
+            challenge_rating: $challenge_rating,
+            spell_level: $spell_level,
+            damage_types: $damage_types,
+            creature_types: $creature_types,
+            embedding: $embedding,
+            embedding_model: $embedding_model,
+            metadata: $metadata
+        };
+    "#;
+
+    db.query(query)
+        .bind(("chunk_id", chunk_id))
+        .bind(("content", chunk.content))
+        .bind(("library_id", library_item_id.to_string()))
+        .bind(("content_type", chunk.content_type))
+        .bind(("page_number", chunk.page_number))
+        .bind(("page_start", chunk.page_start))
+        .bind(("page_end", chunk.page_end))
+        .bind(("chunk_index", chunk_index as i32))
+        .bind(("section_path", chunk.section_path))
+        .bind(("chapter_title", chunk.chapter_title))
+        .bind(("section_title", chunk.section_title))
+        .bind(("chunk_type", chunk.chunk_type))
+        .bind(("keywords", chunk.semantic_keywords))
+        .bind(("content_hash", chunk.content_hash))
+        .bind(("game_system", chunk.game_system))
+        .bind(("challenge_rating", chunk.challenge_rating))
+        .bind(("spell_level", chunk.spell_level))
+        .bind(("damage_types", chunk.damage_types))
+        .bind(("creature_types", chunk.creature_types))
+        .bind(("embedding", chunk.embedding))
+        .bind(("embedding_model", chunk.embedding_model))
+        .bind(("metadata", chunk.metadata))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// A stored chunk's ID, content hash and pages, for diffing a re-ingested
+/// document.
+#[derive(Clone, Debug, Deserialize)]
+pub struct StoredChunkHash {
+    /// Chunk record ID (without table prefix)
+    pub id: String,
+    /// Content hash; `None` for chunks stored before hashes were recorded
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// Page the chunk came from; `None` for documents without pages
+    #[serde(default)]
+    pub page_number: Option<i32>,
+    /// First page of the chunk's text, when recorded
+    #[serde(default)]
+    pub page_start: Option<i32>,
+    /// Last page of the chunk's text, when recorded
+    #[serde(default)]
+    pub page_end: Option<i32>,
+}
+
+/// What to do with one chunk of a re-ingested document, in document order.
+#[derive(Clone, Debug)]
+pub enum ChunkChange {
+    /// Keep a stored chunk whose pages are all unchanged as it is, moving
+    /// it to its new pages (pages before it may have been inserted or
+    /// removed).
+    Keep {
+        id: String,
+        page_number: Option<i32>,
+        page_start: Option<i32>,
+        page_end: Option<i32>,
+    },
+    /// Reuse a stored chunk whose text reappears on a changed page: its
+    /// content and embedding are kept and its metadata replaced.
+    Reuse { id: String, chunk: ChunkData },
+    /// Create a new chunk.
+    Create(ChunkData),
+}
+
+/// Get the ID, content hash and pages of every chunk of a library item.
+///
+/// # Arguments
+///
+/// * `db` - SurrealDB connection
+/// * `library_item_id` - ID of the library item
+pub async fn get_chunk_hashes(
+    db: &Surreal<Db>,
+    library_item_id: &str,
+) -> Result<Vec<StoredChunkHash>, StorageError> {
+    db.query(
+        "SELECT meta::id(id) as id, content_hash, page_number, page_start, page_end FROM chunk \
+         WHERE library_item = type::thing('library_item', $id) ORDER BY chunk_index",
+    )
+    .bind(("id", library_item_id.to_string()))
+    .await
+    .map_err(|e| StorageError::Query(format!("Failed to read chunk hashes: {}", e)))?
+    .take(0)
+    .map_err(|e| StorageError::Query(format!("Failed to extract chunk hashes: {}", e)))
+}
+
+/// Replace a library item's chunks after re-ingestion, keeping unchanged ones.
+///
+/// `chunks` is the new chunk list in document order (see [`ChunkChange`]);
+/// every entry is given its position as chunk index. Chunks in `removed` are
+/// deleted. Runs in a transaction and sets the library item status to
+/// "ready".
+///
+/// # Returns
+///
+/// Number of chunks the library item has afterwards.
+///
+/// # Errors
+///
+/// Returns `StorageError::Transaction` if the transaction fails, or
+/// `StorageError::Query` if a statement fails (the transaction is cancelled).
+pub async fn apply_chunk_changes(
+    db: &Surreal<Db>,
+    library_item_id: &str,
+    chunks: Vec<ChunkChange>,
+    removed: Vec<String>,
+) -> Result<usize, StorageError> {
+    db.query("BEGIN TRANSACTION")
+        .await
+        .map_err(|e| StorageError::Transaction(format!("Failed to begin transaction: {}", e)))?;
+
+    let total = chunks.len();
+    let kept = chunks
+        .iter()
+        .filter(|change| !matches!(change, ChunkChange::Create(_)))
+        .count();
+    let deleted = removed.len();
+
+    let result = async {
+        for chunk_id in removed {
+            db.query("DELETE type::thing('chunk', $id)")
+                .bind(("id", chunk_id))
+                .await?
+                .check()?;
+        }
+
+        for (i, change) in chunks.into_iter().enumerate() {
+            match change {
+                ChunkChange::Keep {
+                    id,
+                    page_number,
+                    page_start,
+                    page_end,
+                } => {
+                    db.query(
+                        "UPDATE type::thing('chunk', $chunk_id) SET \
+                         page_number = $page_number, page_start = $page_start, \
+                         page_end = $page_end, chunk_index = $chunk_index",
+                    )
+                    .bind(("chunk_id", id))
+                    .bind(("page_number", page_number))
+                    .bind(("page_start", page_start))
+                    .bind(("page_end", page_end))
+                    .bind(("chunk_index", i as i32))
+                    .await?
+                    .check()?;
+                }
+                ChunkChange::Reuse {
+                    id: chunk_id,
+                    chunk,
+                } => {
+                    db.query(
+                        r#"
+                        UPDATE type::thing('chunk', $chunk_id) SET
+                            content_type = $content_type,
+                            page_number = $page_number,
+                            page_start = $page_start,
+                            page_end = $page_end,
+                            chunk_index = $chunk_index,
+                            section_path = $section_path,
+                            chapter_title = $chapter_title,
+                            section_title = $section_title,
+                            chunk_type = $chunk_type,
+                            semantic_keywords = $keywords,
+                            game_system = $game_system,
+                            challenge_rating = $challenge_rating,
+                            spell_level = $spell_level,
+                            damage_types = $damage_types,
+                            creature_types = $creature_types,
+                            metadata = $metadata;
+                    "#,
+                    )
+                    .bind(("chunk_id", chunk_id))
+                    .bind(("content_type", chunk.content_type))
+                    .bind(("page_number", chunk.page_number))
+                    .bind(("page_start", chunk.page_start))
+                    .bind(("page_end", chunk.page_end))
+                    .bind(("chunk_index", i as i32))
+                    .bind(("section_path", chunk.section_path))
+                    .bind(("chapter_title", chunk.chapter_title))
+                    .bind(("section_title", chunk.section_title))
+                    .bind(("chunk_type", chunk.chunk_type))
+                    .bind(("keywords", chunk.semantic_keywords))
+                    .bind(("game_system", chunk.game_system))
+                    .bind(("challenge_rating", chunk.challenge_rating))
+                    .bind(("spell_level", chunk.spell_level))
+                    .bind(("damage_types", chunk.damage_types))
+                    .bind(("creature_types", chunk.creature_types))
+                    .bind(("metadata", chunk.metadata))
+                    .await?
+                    .check()?;
+                }
+                ChunkChange::Create(chunk) => {
+                    // Positional IDs could collide with kept chunks
+                    let chunk_id =
+                        format!("{}-{}", library_item_id, uuid::Uuid::new_v4().simple());
+                    create_chunk(db, chunk_id, library_item_id, i, chunk).await?;
+                }
+            }
+        }
+
+        db.query(
+            "UPDATE type::thing('library_item', $id) SET status = 'ready', updated_at = time::now()",
+        )
+        .bind(("id", library_item_id.to_string()))
+        .await?
+        .check()?;
+
+        Ok::<(), surrealdb::Error>(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        let _ = db.query("CANCEL TRANSACTION").await;
+        return Err(StorageError::Query(format!(
+            "Failed to apply chunk changes: {}",
+            e
+        )));
+    }
+
+    db.query("COMMIT TRANSACTION")
+        .await
+        .map_err(|e| StorageError::Transaction(format!("Failed to commit transaction: {}", e)))?;
+
+    tracing::info!(
+        library_item_id = %library_item_id,
+        kept,
+        added = total - kept,
+        removed = deleted,
+        "Applied incremental chunk changes"
+    );
+
+    Ok(total)
+}
+
+/// Get the `(page number, text hash)` pairs recorded when a library item was
+/// last ingested, in page order.
+///
+/// Returns `None` if the item has never been ingested with page hashes.
+/// Items ingested before page numbers were recorded are assumed to be
+/// numbered from 1.
+pub async fn get_page_hashes(
+    db: &Surreal<Db>,
+    library_item_id: &str,
+) -> Result<Option<Vec<(u32, String)>>, StorageError> {
+    #[derive(Deserialize)]
+    struct Row {
+        page_hashes: Option<Vec<String>>,
+        page_numbers: Option<Vec<u32>>,
+    }
+
+    let row: Option<Row> = db
+        .query("SELECT page_hashes, page_numbers FROM ONLY type::thing('library_item', $id)")
+        .bind(("id", library_item_id.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(format!("Failed to read page hashes: {}", e)))?
+        .take(0)
+        .map_err(|e| StorageError::Query(format!("Failed to extract page hashes: {}", e)))?;
+
+    Ok(row.and_then(|row| {
+        let hashes = row.page_hashes?;
+        let numbers = row
+            .page_numbers
+            .filter(|numbers| numbers.len() == hashes.len())
+            .unwrap_or_else(|| (1..=hashes.len() as u32).collect());
+        Some(numbers.into_iter().zip(hashes).collect())
+    }))
+}
+
+/// Find the library items whose recorded pages include any of `page_hashes`.
+///
+/// Returns `(library item ID, number of the pages it shares)`, most shared
+/// first. Items whose file is missing are not considered.
+pub async fn find_items_sharing_pages(
+    db: &Surreal<Db>,
+    page_hashes: Vec<String>,
+) -> Result<Vec<(String, usize)>, StorageError> {
+    #[derive(Deserialize)]
+    struct Row {
+        id: String,
+        shared: usize,
+    }
+
+    let rows: Vec<Row> = db
+        .query(
+            "SELECT meta::id(id) AS id, \
+             array::len(array::intersect(page_hashes, $hashes)) AS shared \
+             FROM library_item \
+             WHERE page_hashes CONTAINSANY $hashes AND status != 'missing' \
+             ORDER BY shared DESC",
+        )
+        .bind(("hashes", page_hashes))
+        .await
+        .map_err(|e| StorageError::Query(format!("Failed to find shared pages: {}", e)))?
+        .take(0)
+        .map_err(|e| StorageError::Query(format!("Failed to extract shared pages: {}", e)))?;
+
+    Ok(rows.into_iter().map(|row| (row.id, row.shared)).collect())
+}
+
+/// Record the file hash and the `(page number, text hash)` pairs of an
+/// ingested library item.
+///
+/// The file hash identifies duplicates on import; the page hashes let the
+/// next ingestion of a changed file find the pages that changed.
+pub async fn set_document_hashes(
+    db: &Surreal<Db>,
+    library_item_id: &str,
+    content_hash: &str,
+    pages: Vec<(u32, String)>,
+) -> Result<(), StorageError> {
+    let (page_numbers, page_hashes): (Vec<u32>, Vec<String>) = pages.into_iter().unzip();
+    db.query(
+        r#"
+        UPDATE type::thing('library_item', $id) SET
+            content_hash = $content_hash,
+            page_hashes = $page_hashes,
+            page_numbers = $page_numbers,
+            updated_at = time::now();
+    "#,
+    )
+    .bind(("id", library_item_id.to_string()))
+    .bind(("content_hash", content_hash.to_string()))
+    .bind(("page_hashes", page_hashes))
+    .bind(("page_numbers", page_numbers))
+    .await
+    .map_err(|e| StorageError::Query(format!("Failed to store document hashes: {}", e)))?;
+
+    Ok(())
+}
+
 /// Delete all chunks associated with a library item.
 ///
 /// Removes all chunks linked to the specified library item ID.
@@ -533,6 +890,9 @@ mod tests {
             DEFINE FIELD title ON library_item TYPE string;
             DEFINE FIELD status ON library_item TYPE string DEFAULT "pending";
             DEFINE FIELD updated_at ON library_item TYPE option<datetime>;
+            DEFINE FIELD content_hash ON library_item TYPE option<string>;
+            DEFINE FIELD page_hashes ON library_item TYPE option<array<string>>;
+            DEFINE FIELD page_numbers ON library_item TYPE option<array<int>>;
             DEFINE INDEX library_slug ON library_item FIELDS slug UNIQUE;
 
             DEFINE TABLE chunk SCHEMAFULL;
@@ -548,6 +908,12 @@ mod tests {
             DEFINE FIELD section_title ON chunk TYPE option<string>;
             DEFINE FIELD chunk_type ON chunk TYPE option<string>;
             DEFINE FIELD semantic_keywords ON chunk TYPE option<array<string>>;
+            DEFINE FIELD content_hash ON chunk TYPE option<string>;
+            DEFINE FIELD game_system ON chunk TYPE option<string>;
+            DEFINE FIELD challenge_rating ON chunk TYPE option<float>;
+            DEFINE FIELD spell_level ON chunk TYPE option<int>;
+            DEFINE FIELD damage_types ON chunk TYPE option<array<string>>;
+            DEFINE FIELD creature_types ON chunk TYPE option<array<string>>;
             DEFINE FIELD embedding ON chunk TYPE option<array<float>>;
             DEFINE FIELD embedding_model ON chunk TYPE option<string>;
             DEFINE FIELD metadata ON chunk TYPE option<object>;
@@ -640,6 +1006,12 @@ mod tests {
                 "opportunity".to_string(),
                 "reaction".to_string(),
             ]),
+            content_hash: Some("abc123".to_string()),
+            game_system: Some("dnd5e".to_string()),
+            challenge_rating: Some(0.25),
+            spell_level: Some(3),
+            damage_types: Some(vec!["fire".to_string()]),
+            creature_types: Some(vec!["humanoid".to_string()]),
             embedding: Some(vec![0.1; 768]),
             embedding_model: Some("nomic-embed-text-v1.5".to_string()),
             metadata: Some(serde_json::json!({"source": "phb"})),
@@ -828,6 +1200,205 @@ mod tests {
         assert_eq!(updated, 1);
     }
 
+    #[tokio::test]
+    async fn test_apply_chunk_changes_keeps_reused_chunks() {
+        let (db, _temp) = setup_test_db().await;
+        create_test_library_item(&db, "errata", "Errata Test").await;
+
+        let chunk = |content: &str, embedding: Option<Vec<f32>>| ChunkData {
+            content: content.to_string(),
+            content_type: "rules".to_string(),
+            content_hash: Some(format!("hash-{content}")),
+            embedding,
+            ..Default::default()
+        };
+        ingest_chunks(
+            &db,
+            "errata",
+            vec![
+                chunk("Unchanged", Some(vec![0.5; 4])),
+                chunk("Old wording", Some(vec![0.1; 4])),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let stored = get_chunk_hashes(&db, "errata").await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].content_hash.as_deref(), Some("hash-Unchanged"));
+
+        let mut kept = chunk("Unchanged", None);
+        kept.section_path = Some("Errata".to_string());
+        let total = apply_chunk_changes(
+            &db,
+            "errata",
+            vec![
+                ChunkChange::Create(chunk("New wording", None)),
+                ChunkChange::Reuse {
+                    id: stored[0].id.clone(),
+                    chunk: kept,
+                },
+            ],
+            vec![stored[1].id.clone()],
+        )
+        .await
+        .unwrap();
+        assert_eq!(total, 2);
+
+        #[derive(Debug, Deserialize)]
+        struct Row {
+            id: String,
+            content: String,
+            chunk_index: i32,
+            section_path: Option<String>,
+            embedding: Option<Vec<f32>>,
+        }
+        let rows: Vec<Row> = db
+            .query(
+                "SELECT meta::id(id) as id, content, chunk_index, section_path, embedding \
+                 FROM chunk ORDER BY chunk_index",
+            )
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].content, "New wording");
+        assert!(rows[0].embedding.is_none());
+        assert_eq!(rows[1].id, stored[0].id);
+        assert_eq!(rows[1].chunk_index, 1);
+        assert_eq!(rows[1].section_path.as_deref(), Some("Errata"));
+        assert_eq!(rows[1].embedding, Some(vec![0.5; 4]));
+    }
+
+    #[tokio::test]
+    async fn test_apply_chunk_changes_moves_kept_chunks() {
+        let (db, _temp) = setup_test_db().await;
+        create_test_library_item(&db, "moved", "Moved").await;
+
+        let mut chunk = ChunkData {
+            content: "Unchanged page".to_string(),
+            content_type: "rules".to_string(),
+            page_number: Some(3),
+            page_start: Some(3),
+            page_end: Some(4),
+            section_path: Some("Monsters".to_string()),
+            embedding: Some(vec![0.5; 4]),
+            ..Default::default()
+        };
+        ingest_chunks(&db, "moved", vec![chunk.clone()])
+            .await
+            .unwrap();
+        let stored = get_chunk_hashes(&db, "moved").await.unwrap();
+        assert_eq!(stored[0].page_number, Some(3));
+        assert_eq!((stored[0].page_start, stored[0].page_end), (Some(3), Some(4)));
+
+        // A page inserted before it pushes the unchanged pages back by one
+        chunk.content = "Inserted page".to_string();
+        chunk.embedding = None;
+        apply_chunk_changes(
+            &db,
+            "moved",
+            vec![
+                ChunkChange::Create(chunk),
+                ChunkChange::Keep {
+                    id: stored[0].id.clone(),
+                    page_number: Some(4),
+                    page_start: Some(4),
+                    page_end: Some(5),
+                },
+            ],
+            Vec::new(),
+        )
+        .await
+        .unwrap();
+
+        #[derive(Debug, Deserialize)]
+        struct Row {
+            id: String,
+            page_number: Option<i32>,
+            page_end: Option<i32>,
+            chunk_index: i32,
+            section_path: Option<String>,
+            embedding: Option<Vec<f32>>,
+        }
+        let rows: Vec<Row> = db
+            .query(
+                "SELECT meta::id(id) as id, page_number, page_end, chunk_index, section_path, \
+                 embedding FROM chunk ORDER BY chunk_index",
+            )
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].id, stored[0].id);
+        assert_eq!(rows[1].page_number, Some(4));
+        assert_eq!(rows[1].page_end, Some(5));
+        assert_eq!(rows[1].chunk_index, 1);
+        assert_eq!(rows[1].section_path.as_deref(), Some("Monsters"));
+        assert_eq!(rows[1].embedding, Some(vec![0.5; 4]));
+    }
+
+    #[tokio::test]
+    async fn test_document_hashes_roundtrip() {
+        let (db, _temp) = setup_test_db().await;
+        create_test_library_item(&db, "hashed", "Hashed").await;
+
+        assert_eq!(get_page_hashes(&db, "hashed").await.unwrap(), None);
+
+        let pages = vec![(2, "p2".to_string()), (4, "p4".to_string())];
+        set_document_hashes(&db, "hashed", "file-hash", pages.clone())
+            .await
+            .unwrap();
+        assert_eq!(get_page_hashes(&db, "hashed").await.unwrap(), Some(pages));
+    }
+
+    #[tokio::test]
+    async fn test_find_items_sharing_pages() {
+        let (db, _temp) = setup_test_db().await;
+        create_test_library_item(&db, "phb", "Player's Handbook").await;
+        create_test_library_item(&db, "dmg", "Dungeon Master's Guide").await;
+        let pages = |hashes: &[&str]| {
+            hashes
+                .iter()
+                .enumerate()
+                .map(|(i, h)| (i as u32 + 1, h.to_string()))
+                .collect::<Vec<_>>()
+        };
+        set_document_hashes(&db, "phb", "phb-hash", pages(&["a", "b", "c"]))
+            .await
+            .unwrap();
+        set_document_hashes(&db, "dmg", "dmg-hash", pages(&["c", "x"]))
+            .await
+            .unwrap();
+
+        let shared = find_items_sharing_pages(&db, vec!["a".into(), "b".into(), "c".into()])
+            .await
+            .unwrap();
+        assert_eq!(shared, vec![("phb".to_string(), 3), ("dmg".to_string(), 1)]);
+        assert!(find_items_sharing_pages(&db, vec!["zzz".into()])
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_page_hashes_without_numbers_count_from_one() {
+        let (db, _temp) = setup_test_db().await;
+        create_test_library_item(&db, "legacy", "Legacy").await;
+
+        db.query("UPDATE library_item:legacy SET page_hashes = ['p1', 'p2']")
+            .await
+            .unwrap();
+        assert_eq!(
+            get_page_hashes(&db, "legacy").await.unwrap(),
+            Some(vec![(1, "p1".to_string()), (2, "p2".to_string())])
+        );
+    }
+
     #[tokio::test]
     async fn test_chunk_data_serialization() {
         let chunk = ChunkData {
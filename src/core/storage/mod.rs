@@ -21,6 +21,9 @@
 //! - `ingestion` - Document ingestion and chunking
 //! - `migration` - SQLite/Meilisearch to SurrealDB migration utilities
 //! - `models` - Data models for storage operations
+//! - `bestiary` - Creature stat blocks harvested during ingestion
+//! - `embedding_index` - Vector index sizing and re-embedding on model change
+//! - `npc_memory` - Long-term NPC memories recalled into NPC chat
 
 pub mod surrealdb;
 pub mod error;
@@ -30,6 +33,9 @@ pub mod rag;
 pub mod ingestion;
 pub mod migration;
 pub mod models;
+pub mod bestiary;
+pub mod embedding_index;
+pub mod npc_memory;
 
 pub use error::StorageError;
 pub use surrealdb::SurrealStorage;
@@ -54,7 +60,9 @@ pub use search::{
     fulltext_search,
     fulltext_search_with_highlights,
     hybrid_search,
+    hybrid_search_reranked,
     hybrid_search_with_preprocessing,
+    keyword_search_reranked,
 };
 
 pub use ingestion::{
@@ -64,15 +72,42 @@ pub use ingestion::{
     delete_library_chunks,
     get_chunk_count,
     update_chunk_embeddings,
+    StoredChunkHash,
+    ChunkChange,
+    get_chunk_hashes,
+    apply_chunk_changes,
+    get_page_hashes,
+    find_items_sharing_pages,
+    set_document_hashes,
 };
 
 // Library item models and CRUD (Task 3.2.1, 3.2.2)
 pub use models::{
-    count_library_items, create_library_item, delete_library_item, get_library_item,
-    get_library_item_by_slug, get_library_items, update_library_item, update_library_item_status,
+    count_library_items, create_library_item, delete_library_item, find_library_item_by_hash,
+    find_library_item_by_path, find_library_item_by_title, get_library_item,
+    get_library_item_by_slug, get_library_items, list_library_items_under_path, update_library_item, update_library_item_status,
     LibraryItem, LibraryItemBuilder, LibraryItemWithCount,
 };
 
+// Bestiary (stat blocks harvested during ingestion)
+pub use bestiary::{
+    BestiaryFilter, StatBlockRecord, collect_stat_blocks, delete_library_stat_blocks,
+    get_stat_block, search_stat_blocks, store_stat_blocks,
+};
+
+// Embedding index (vector dimension tracks the active embedding model)
+pub use embedding_index::{
+    BackfillJob, BackfillProgress, EmbeddingIndexState, IndexCheck, IndexStatus, ReembedJob,
+    ReembedProgress, ensure_embedding_index, get_index_state,
+};
+
+// NPC memory (facts and impressions recalled into NPC chat)
+pub use npc_memory::{
+    NpcMemoryKind, NpcMemoryRecord, QueryVector, RecalledMemory, create_npc_memory,
+    delete_npc_memories, delete_npc_memory, list_npc_memories, rank_memories,
+    set_npc_memory_pinned, touch_npc_memories, update_npc_memory,
+};
+
 // RAG pipeline types and functions (Task 4.1, 4.2)
 pub use rag::{
     RagConfig, RagSource, RagResponse, RagContext, FormattedContext,
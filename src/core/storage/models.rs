@@ -61,6 +61,9 @@ pub struct LibraryItem {
     /// Error message if status is "error"
     #[serde(default)]
     pub error_message: Option<String>,
+    /// BLAKE3 hash of the source file, set once ingestion succeeds
+    #[serde(default)]
+    pub content_hash: Option<String>,
     /// Creation timestamp (ISO 8601)
     #[serde(default)]
     pub created_at: Option<String>,
@@ -89,6 +92,7 @@ impl LibraryItem {
             publisher: None,
             status: "pending".to_string(),
             error_message: None,
+            content_hash: None,
             created_at: None,
             updated_at: None,
             metadata: None,
@@ -169,6 +173,12 @@ impl LibraryItemBuilder {
         self
     }
 
+    /// Set the source file's content hash.
+    pub fn content_hash(mut self, hash: impl Into<String>) -> Self {
+        self.item.content_hash = Some(hash.into());
+        self
+    }
+
     /// Set additional metadata.
     pub fn metadata(mut self, metadata: serde_json::Value) -> Self {
         self.item.metadata = Some(metadata);
@@ -240,6 +250,7 @@ pub async fn create_library_item(
             publisher: $publisher,
             status: $status,
             error_message: $error_message,
+            content_hash: $content_hash,
             metadata: $metadata
         };
     "#;
@@ -258,6 +269,7 @@ pub async fn create_library_item(
         .bind(("publisher", item.publisher.clone()))
         .bind(("status", item.status.clone()))
         .bind(("error_message", item.error_message.clone()))
+        .bind(("content_hash", item.content_hash.clone()))
         .bind(("metadata", item.metadata.clone()))
         .await
         .map_err(|e| StorageError::Query(e.to_string()))?;
@@ -321,6 +333,107 @@ pub async fn get_library_item_by_slug(
     Ok(result)
 }
 
+/// Find the library item ingested from a file with the given content hash.
+///
+/// Used on import to detect a file that is already in the library, even
+/// under another name.
+///
+/// # Arguments
+///
+/// * `db` - SurrealDB connection
+/// * `hash` - BLAKE3 hash of the file (see `ingestion::hash::hash_file`)
+pub async fn find_library_item_by_hash(
+    db: &Surreal<Db>,
+    hash: &str,
+) -> Result<Option<LibraryItem>, StorageError> {
+    let result: Option<LibraryItem> = db
+        .query("SELECT *, meta::id(id) as id FROM library_item WHERE content_hash = $hash LIMIT 1")
+        .bind(("hash", hash.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+
+    Ok(result)
+}
+
+/// Find a library item by title, ignoring case.
+///
+/// Used on import to spot a new version of a document saved under another
+/// file name. Items whose file is missing are not considered.
+///
+/// # Arguments
+///
+/// * `db` - SurrealDB connection
+/// * `title` - Title to look for
+pub async fn find_library_item_by_title(
+    db: &Surreal<Db>,
+    title: &str,
+) -> Result<Option<LibraryItem>, StorageError> {
+    let result: Option<LibraryItem> = db
+        .query(
+            "SELECT *, meta::id(id) as id FROM library_item \
+             WHERE string::lowercase(title) = string::lowercase($title) \
+             AND status != 'missing' LIMIT 1",
+        )
+        .bind(("title", title.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+
+    Ok(result)
+}
+
+/// Find the library item ingested from a file path.
+///
+/// # Arguments
+///
+/// * `db` - SurrealDB connection
+/// * `path` - Source file path as stored on the item
+pub async fn find_library_item_by_path(
+    db: &Surreal<Db>,
+    path: &str,
+) -> Result<Option<LibraryItem>, StorageError> {
+    let result: Option<LibraryItem> = db
+        .query("SELECT *, meta::id(id) as id FROM library_item WHERE file_path = $path LIMIT 1")
+        .bind(("path", path.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+
+    Ok(result)
+}
+
+/// List library items whose source file path starts with `prefix`.
+///
+/// Used to find the items that came from a watched folder. The match is on
+/// the stored path string, so callers should check path components when a
+/// folder name can be a prefix of another.
+///
+/// # Arguments
+///
+/// * `db` - SurrealDB connection
+/// * `prefix` - Leading part of the file path (typically a folder)
+pub async fn list_library_items_under_path(
+    db: &Surreal<Db>,
+    prefix: &str,
+) -> Result<Vec<LibraryItem>, StorageError> {
+    let results: Vec<LibraryItem> = db
+        .query(
+            "SELECT *, meta::id(id) as id FROM library_item \
+             WHERE string::starts_with(file_path ?? '', $prefix)",
+        )
+        .bind(("prefix", prefix.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+
+    Ok(results)
+}
+
 /// Update a library item.
 ///
 /// Updates all mutable fields of a library item. The slug field is not updated
@@ -350,6 +463,7 @@ pub async fn update_library_item(
             publisher: $publisher,
             status: $status,
             error_message: $error_message,
+            content_hash: $content_hash,
             metadata: $metadata,
             updated_at: time::now()
         };
@@ -367,6 +481,7 @@ pub async fn update_library_item(
     .bind(("publisher", item.publisher.clone()))
     .bind(("status", item.status.clone()))
     .bind(("error_message", item.error_message.clone()))
+    .bind(("content_hash", item.content_hash.clone()))
     .bind(("metadata", item.metadata.clone()))
     .await
     .map_err(|e| StorageError::Query(e.to_string()))?;
@@ -374,9 +489,9 @@ pub async fn update_library_item(
     Ok(())
 }
 
-/// Delete a library item (cascades to chunks).
+/// Delete a library item (cascades to chunks and stat blocks).
 ///
-/// Deletes the library item and all associated chunks. This is a destructive
+/// Deletes the library item with all associated chunks and bestiary entries. This is a destructive
 /// operation that cannot be undone.
 ///
 /// # Arguments
@@ -392,6 +507,12 @@ pub async fn delete_library_item(db: &Surreal<Db>, id: &str) -> Result<(), Stora
         .await
         .map_err(|e| StorageError::Query(e.to_string()))?;
 
+    // Delete harvested stat blocks
+    db.query("DELETE stat_block WHERE library_item = type::thing('library_item', $id)")
+        .bind(("id", id_owned.clone()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+
     // Delete library item
     db.query("DELETE type::thing('library_item', $id)")
         .bind(("id", id_owned))
@@ -913,6 +1034,46 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_find_library_item_by_hash_and_path() {
+        let (storage, _temp_dir) = setup_test_db().await;
+        let db = storage.db();
+
+        let item = LibraryItem::builder("hashed".to_string(), "Hashed".to_string())
+            .file_path("/books/hashed.pdf")
+            .content_hash("abc123")
+            .build();
+        let id = create_library_item(db, &item)
+            .await
+            .expect("Failed to create");
+
+        let by_hash = find_library_item_by_hash(db, "abc123").await.unwrap();
+        assert_eq!(by_hash.and_then(|i| i.id), Some(id.clone()));
+        assert!(find_library_item_by_hash(db, "other").await.unwrap().is_none());
+
+        let by_path = find_library_item_by_path(db, "/books/hashed.pdf").await.unwrap();
+        assert_eq!(by_path.and_then(|i| i.content_hash), Some("abc123".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_list_library_items_under_path() {
+        let (storage, _temp_dir) = setup_test_db().await;
+        let db = storage.db();
+
+        for (slug, path) in [("core", "/books/core.pdf"), ("other", "/elsewhere/other.pdf")] {
+            let item = LibraryItem::builder(slug.to_string(), slug.to_string())
+                .file_path(path)
+                .build();
+            create_library_item(db, &item).await.expect("Failed to create");
+        }
+        let pathless = LibraryItem::builder("pasted".to_string(), "Pasted".to_string()).build();
+        create_library_item(db, &pathless).await.expect("Failed to create");
+
+        let items = list_library_items_under_path(db, "/books/").await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].slug, "core");
+    }
+
     #[tokio::test]
     async fn test_library_item_builder() {
         let item = LibraryItem::builder("builder-test".to_string(), "Builder Test".to_string())
@@ -0,0 +1,573 @@
+//! NPC memory storage: long-term facts and impressions an NPC keeps between
+//! conversations.
+//!
+//! Memories are extracted from NPC chats by the LLM (see `core::npc_memory`)
+//! and stored as `npc_memory` records keyed by the SQLite NPC id. Each has an
+//! importance that fades with a half-life from the last time it was recalled;
+//! pinned memories never fade and are always recalled first.
+//!
+//! Vectors are optional and labelled with their embedding model. Recall ranks
+//! an NPC's memories in process (an NPC holds tens of memories, not
+//! thousands), so memories embedded by an older model still rank by keyword
+//! overlap instead of needing a vector index rebuild.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::engine::local::Db;
+use surrealdb::Surreal;
+
+use super::error::StorageError;
+
+/// Strength below which a memory is treated as forgotten during recall.
+pub const FORGOTTEN_STRENGTH: f32 = 0.05;
+
+/// Words shorter than this are ignored by the keyword fallback.
+const MIN_KEYWORD_LEN: usize = 4;
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// What a memory records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NpcMemoryKind {
+    /// Something the NPC learned ("The party is looking for the Duke's ring")
+    Fact,
+    /// How the NPC feels about someone or something ("Distrusts the dwarf")
+    Impression,
+}
+
+impl NpcMemoryKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Fact => "fact",
+            Self::Impression => "impression",
+        }
+    }
+
+    /// Parse a kind label, defaulting to `Fact`.
+    pub fn from_label(label: &str) -> Self {
+        match label.trim().to_lowercase().as_str() {
+            "impression" | "feeling" | "opinion" => Self::Impression,
+            _ => Self::Fact,
+        }
+    }
+}
+
+/// A stored NPC memory.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NpcMemoryRecord {
+    /// Record ID (without table prefix)
+    #[serde(default)]
+    pub id: Option<String>,
+    /// SQLite NPC id
+    pub npc_id: String,
+    #[serde(default)]
+    pub campaign_id: Option<String>,
+    pub kind: NpcMemoryKind,
+    pub content: String,
+    /// Base importance, 0.0-1.0
+    pub importance: f32,
+    /// Pinned by the GM: never fades, always recalled
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+    /// Model that produced `embedding`
+    #[serde(default)]
+    pub embedding_model: Option<String>,
+    /// NPC conversation the memory was extracted from
+    #[serde(default)]
+    pub source_conversation: Option<String>,
+    /// Creation timestamp (RFC 3339)
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Last time the memory was injected into a prompt (RFC 3339)
+    #[serde(default)]
+    pub last_recalled_at: Option<String>,
+}
+
+impl NpcMemoryRecord {
+    /// Create an unsaved memory.
+    pub fn new(
+        npc_id: impl Into<String>,
+        kind: NpcMemoryKind,
+        content: impl Into<String>,
+        importance: f32,
+    ) -> Self {
+        Self {
+            id: None,
+            npc_id: npc_id.into(),
+            campaign_id: None,
+            kind,
+            content: content.into(),
+            importance: importance.clamp(0.0, 1.0),
+            pinned: false,
+            embedding: None,
+            embedding_model: None,
+            source_conversation: None,
+            created_at: None,
+            last_recalled_at: None,
+        }
+    }
+
+    /// Current strength: importance faded by time since the memory was last
+    /// recalled (or created). Pinned memories keep their full importance.
+    pub fn strength(&self, now: DateTime<Utc>, half_life_days: f32) -> f32 {
+        if self.pinned || half_life_days <= 0.0 {
+            return self.importance;
+        }
+        let reference = self
+            .last_recalled_at
+            .as_deref()
+            .or(self.created_at.as_deref())
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok());
+        let Some(reference) = reference else {
+            return self.importance;
+        };
+        let age_days = (now - reference.with_timezone(&Utc)).num_seconds().max(0) as f32 / 86_400.0;
+        self.importance * 0.5_f32.powf(age_days / half_life_days)
+    }
+
+    /// Whether the memory has faded below recall.
+    pub fn is_forgotten(&self, now: DateTime<Utc>, half_life_days: f32) -> bool {
+        self.strength(now, half_life_days) < FORGOTTEN_STRENGTH
+    }
+}
+
+/// A memory chosen for a prompt, with its ranking score.
+#[derive(Clone, Debug)]
+pub struct RecalledMemory {
+    pub memory: NpcMemoryRecord,
+    pub score: f32,
+}
+
+// ============================================================================
+// Ranking
+// ============================================================================
+
+/// Query embedding and the model that produced it.
+#[derive(Clone, Copy, Debug)]
+pub struct QueryVector<'a> {
+    pub embedding: &'a [f32],
+    pub model: &'a str,
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() || a.is_empty() {
+        return None;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    Some(dot / (norm_a * norm_b))
+}
+
+fn keywords(text: &str) -> std::collections::HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= MIN_KEYWORD_LEN)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Fraction of the query's keywords found in `content`.
+fn keyword_overlap(query: &str, content: &str) -> f32 {
+    let query_words = keywords(query);
+    if query_words.is_empty() {
+        return 0.0;
+    }
+    let content_words = keywords(content);
+    let shared = query_words.intersection(&content_words).count();
+    shared as f32 / query_words.len() as f32
+}
+
+/// Pick the memories to inject for `query`.
+///
+/// Pinned memories come first. The rest are scored by relevance (cosine
+/// similarity when the memory was embedded by the query's model, keyword
+/// overlap otherwise) weighted by strength, so a strong memory still
+/// surfaces when nothing in the query matches it. Forgotten memories are
+/// skipped.
+pub fn rank_memories(
+    memories: Vec<NpcMemoryRecord>,
+    query: &str,
+    query_vector: Option<QueryVector<'_>>,
+    now: DateTime<Utc>,
+    half_life_days: f32,
+    limit: usize,
+) -> Vec<RecalledMemory> {
+    let mut ranked: Vec<RecalledMemory> = memories
+        .into_iter()
+        .filter(|m| !m.is_forgotten(now, half_life_days))
+        .map(|memory| {
+            let vector_relevance = query_vector.and_then(|q| {
+                let embedding = memory.embedding.as_deref()?;
+                if memory.embedding_model.as_deref() != Some(q.model) {
+                    return None;
+                }
+                cosine_similarity(q.embedding, embedding)
+            });
+            let relevance = vector_relevance
+                .unwrap_or_else(|| keyword_overlap(query, &memory.content))
+                .clamp(0.0, 1.0);
+            let strength = memory.strength(now, half_life_days);
+            let score = (0.3 + 0.7 * relevance) * strength;
+            RecalledMemory { memory, score }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        b.memory
+            .pinned
+            .cmp(&a.memory.pinned)
+            .then(b.score.total_cmp(&a.score))
+    });
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Normalized form of memory text for duplicate detection.
+pub fn normalize_memory_text(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ============================================================================
+// CRUD Operations
+// ============================================================================
+
+const SELECT_FIELDS: &str = "*, meta::id(id) as id, <string> created_at as created_at, \
+     IF last_recalled_at != NONE THEN <string> last_recalled_at END as last_recalled_at";
+
+/// Store a new memory. Returns its record ID.
+pub async fn create_npc_memory(
+    db: &Surreal<Db>,
+    memory: &NpcMemoryRecord,
+) -> Result<String, StorageError> {
+    let id = uuid::Uuid::new_v4().to_string();
+    db.query(
+        r#"
+        CREATE type::thing('npc_memory', $id) CONTENT {
+            npc_id: $npc_id,
+            campaign_id: $campaign_id,
+            kind: $kind,
+            content: $content,
+            importance: $importance,
+            pinned: $pinned,
+            embedding: $embedding,
+            embedding_model: $embedding_model,
+            source_conversation: $source_conversation
+        };
+    "#,
+    )
+    .bind(("id", id.clone()))
+    .bind(("npc_id", memory.npc_id.clone()))
+    .bind(("campaign_id", memory.campaign_id.clone()))
+    .bind(("kind", memory.kind.label()))
+    .bind(("content", memory.content.clone()))
+    .bind(("importance", memory.importance))
+    .bind(("pinned", memory.pinned))
+    .bind(("embedding", memory.embedding.clone()))
+    .bind(("embedding_model", memory.embedding_model.clone()))
+    .bind(("source_conversation", memory.source_conversation.clone()))
+    .await
+    .map_err(|e| StorageError::Query(format!("Failed to insert NPC memory: {}", e)))?
+    .check()
+    .map_err(|e| StorageError::Query(format!("Failed to insert NPC memory: {}", e)))?;
+
+    Ok(id)
+}
+
+/// List an NPC's memories, pinned first, then most important.
+pub async fn list_npc_memories(
+    db: &Surreal<Db>,
+    npc_id: &str,
+) -> Result<Vec<NpcMemoryRecord>, StorageError> {
+    let query = format!(
+        "SELECT {SELECT_FIELDS} FROM npc_memory WHERE npc_id = $npc_id \
+         ORDER BY pinned DESC, importance DESC, created_at DESC"
+    );
+    let results: Vec<NpcMemoryRecord> = db
+        .query(query)
+        .bind(("npc_id", npc_id.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+
+    Ok(results)
+}
+
+/// Update a memory's text, kind and importance.
+///
+/// Changing the text drops the stored vector; pass the new one (or `None`).
+pub async fn update_npc_memory(
+    db: &Surreal<Db>,
+    id: &str,
+    memory: &NpcMemoryRecord,
+) -> Result<(), StorageError> {
+    db.query(
+        r#"
+        UPDATE type::thing('npc_memory', $id) MERGE {
+            kind: $kind,
+            content: $content,
+            importance: $importance,
+            pinned: $pinned,
+            embedding: $embedding,
+            embedding_model: $embedding_model
+        };
+    "#,
+    )
+    .bind(("id", id.to_string()))
+    .bind(("kind", memory.kind.label()))
+    .bind(("content", memory.content.clone()))
+    .bind(("importance", memory.importance.clamp(0.0, 1.0)))
+    .bind(("pinned", memory.pinned))
+    .bind(("embedding", memory.embedding.clone()))
+    .bind(("embedding_model", memory.embedding_model.clone()))
+    .await
+    .map_err(|e| StorageError::Query(format!("Failed to update NPC memory: {}", e)))?;
+
+    Ok(())
+}
+
+/// Pin or unpin a memory.
+pub async fn set_npc_memory_pinned(
+    db: &Surreal<Db>,
+    id: &str,
+    pinned: bool,
+) -> Result<(), StorageError> {
+    db.query("UPDATE type::thing('npc_memory', $id) SET pinned = $pinned")
+        .bind(("id", id.to_string()))
+        .bind(("pinned", pinned))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+    Ok(())
+}
+
+/// Mark memories as recalled now, restarting their fade.
+pub async fn touch_npc_memories(db: &Surreal<Db>, ids: Vec<String>) -> Result<(), StorageError> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    db.query(
+        "UPDATE npc_memory SET last_recalled_at = time::now() \
+         WHERE meta::id(id) IN $ids",
+    )
+    .bind(("ids", ids))
+    .await
+    .map_err(|e| StorageError::Query(e.to_string()))?;
+    Ok(())
+}
+
+/// Delete a memory.
+pub async fn delete_npc_memory(db: &Surreal<Db>, id: &str) -> Result<(), StorageError> {
+    db.query("DELETE type::thing('npc_memory', $id)")
+        .bind(("id", id.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+    Ok(())
+}
+
+/// Delete all of an NPC's memories.
+pub async fn delete_npc_memories(db: &Surreal<Db>, npc_id: &str) -> Result<(), StorageError> {
+    db.query("DELETE npc_memory WHERE npc_id = $npc_id")
+        .bind(("npc_id", npc_id.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?;
+    Ok(())
+}
+
+/// Number of messages of an NPC conversation already read for memories.
+///
+/// Zero for a conversation that has never been through extraction.
+pub async fn get_extraction_watermark(
+    db: &Surreal<Db>,
+    conversation_id: &str,
+) -> Result<usize, StorageError> {
+    let remembered: Option<i64> = db
+        .query("SELECT VALUE remembered FROM ONLY type::thing('npc_memory_watermark', $id)")
+        .bind(("id", conversation_id.to_string()))
+        .await
+        .map_err(|e| StorageError::Query(e.to_string()))?
+        .take(0)
+        .map_err(|e| StorageError::Query(format!("Failed to read memory watermark: {}", e)))?;
+
+    Ok(remembered.unwrap_or(0).max(0) as usize)
+}
+
+/// Record that the first `remembered` messages of a conversation have been
+/// read for memories.
+pub async fn set_extraction_watermark(
+    db: &Surreal<Db>,
+    conversation_id: &str,
+    remembered: usize,
+) -> Result<(), StorageError> {
+    db.query(
+        "UPSERT type::thing('npc_memory_watermark', $id) \
+         SET remembered = $remembered, updated_at = time::now()",
+    )
+    .bind(("id", conversation_id.to_string()))
+    .bind(("remembered", remembered as i64))
+    .await
+    .map_err(|e| StorageError::Query(e.to_string()))?
+    .check()
+    .map_err(|e| StorageError::Query(format!("Failed to save memory watermark: {}", e)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::storage::SurrealStorage;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    async fn setup_test_db() -> (SurrealStorage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage = SurrealStorage::new(temp_dir.path().to_path_buf())
+            .await
+            .expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn aged(content: &str, importance: f32, days: i64) -> NpcMemoryRecord {
+        let mut memory = NpcMemoryRecord::new("npc-1", NpcMemoryKind::Fact, content, importance);
+        memory.created_at = Some((Utc::now() - Duration::days(days)).to_rfc3339());
+        memory
+    }
+
+    #[test]
+    fn test_strength_decays_unless_pinned() {
+        let now = Utc::now();
+        let memory = aged("The party owes me 10 gold", 0.8, 30);
+        assert!((memory.strength(now, 30.0) - 0.4).abs() < 0.01);
+        assert!(memory.is_forgotten(now + Duration::days(150), 30.0));
+
+        let mut pinned = memory.clone();
+        pinned.pinned = true;
+        assert_eq!(pinned.strength(now + Duration::days(365), 30.0), 0.8);
+    }
+
+    #[test]
+    fn test_rank_memories_by_relevance_and_pin() {
+        let now = Utc::now();
+        let mut pinned = aged("Sworn enemy of the Red Hand", 0.2, 0);
+        pinned.pinned = true;
+        let memories = vec![
+            aged("Likes apples and fresh bread", 0.6, 0),
+            aged("The party is searching for the stolen crown", 0.6, 0),
+            aged("Forgotten rumour about the crown", 0.9, 365),
+            pinned,
+        ];
+
+        let ranked = rank_memories(
+            memories,
+            "Have you heard about the crown?",
+            None,
+            now,
+            30.0,
+            3,
+        );
+        assert_eq!(ranked.len(), 3);
+        assert!(ranked[0].memory.pinned);
+        assert!(ranked[1].memory.content.contains("stolen crown"));
+        assert!(ranked
+            .iter()
+            .all(|r| !r.memory.content.starts_with("Forgotten")));
+    }
+
+    #[test]
+    fn test_rank_memories_uses_matching_model_vectors() {
+        let now = Utc::now();
+        let mut near = aged("alpha", 0.5, 0);
+        near.embedding = Some(vec![1.0, 0.0]);
+        near.embedding_model = Some("m1".into());
+        let mut stale = aged("beta", 0.5, 0);
+        stale.embedding = Some(vec![1.0, 0.0]);
+        stale.embedding_model = Some("m0".into());
+
+        let query = QueryVector {
+            embedding: &[1.0, 0.0],
+            model: "m1",
+        };
+        let ranked = rank_memories(vec![stale, near], "unrelated", Some(query), now, 30.0, 2);
+        assert_eq!(ranked[0].memory.content, "alpha");
+        assert!(ranked[0].score > ranked[1].score);
+    }
+
+    #[test]
+    fn test_normalize_memory_text() {
+        assert_eq!(
+            normalize_memory_text("The party  owes me 10 gold!"),
+            normalize_memory_text("the party owes me 10 gold")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_npc_memory_crud() {
+        let (storage, _temp_dir) = setup_test_db().await;
+        let db = storage.db();
+
+        let mut memory = NpcMemoryRecord::new(
+            "npc-1",
+            NpcMemoryKind::Impression,
+            "Distrusts the dwarf",
+            0.7,
+        );
+        memory.embedding = Some(vec![0.1, 0.2, 0.3]);
+        memory.embedding_model = Some("test/model".into());
+        let id = create_npc_memory(db, &memory).await.unwrap();
+        create_npc_memory(
+            db,
+            &NpcMemoryRecord::new("npc-2", NpcMemoryKind::Fact, "Other", 0.5),
+        )
+        .await
+        .unwrap();
+
+        let listed = list_npc_memories(db, "npc-1").await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id.as_deref(), Some(id.as_str()));
+        assert_eq!(listed[0].kind, NpcMemoryKind::Impression);
+        assert!(listed[0].created_at.is_some());
+        assert!(listed[0].last_recalled_at.is_none());
+
+        set_npc_memory_pinned(db, &id, true).await.unwrap();
+        touch_npc_memories(db, vec![id.clone()]).await.unwrap();
+        let mut edited = list_npc_memories(db, "npc-1").await.unwrap().remove(0);
+        assert!(edited.pinned);
+        assert!(edited.last_recalled_at.is_some());
+
+        edited.content = "Trusts the dwarf now".into();
+        edited.embedding = None;
+        edited.embedding_model = None;
+        update_npc_memory(db, &id, &edited).await.unwrap();
+        let updated = list_npc_memories(db, "npc-1").await.unwrap().remove(0);
+        assert_eq!(updated.content, "Trusts the dwarf now");
+        assert!(updated.embedding.is_none());
+
+        delete_npc_memory(db, &id).await.unwrap();
+        assert!(list_npc_memories(db, "npc-1").await.unwrap().is_empty());
+        delete_npc_memories(db, "npc-2").await.unwrap();
+        assert!(list_npc_memories(db, "npc-2").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_extraction_watermark() {
+        let (storage, _temp_dir) = setup_test_db().await;
+        let db = storage.db();
+
+        assert_eq!(get_extraction_watermark(db, "conv-1").await.unwrap(), 0);
+        set_extraction_watermark(db, "conv-1", 4).await.unwrap();
+        set_extraction_watermark(db, "conv-1", 7).await.unwrap();
+        assert_eq!(get_extraction_watermark(db, "conv-1").await.unwrap(), 7);
+        assert_eq!(get_extraction_watermark(db, "conv-2").await.unwrap(), 0);
+    }
+}
@@ -232,6 +232,7 @@ pub struct FormattedContext {
 ///         content: "Flanking gives advantage on attack rolls.".to_string(),
 ///         score: 0.95,
 ///         linear_score: None,
+///         rerank_score: None,
 ///         source: "phb-2024".to_string(),
 ///         page_number: Some(251),
 ///         section_path: Some("Combat/Flanking".to_string()),
@@ -630,6 +631,7 @@ mod tests {
             content: content.to_string(),
             score: 0.85,
             linear_score: None,
+            rerank_score: None,
             source: source.to_string(),
             page_number: page,
             section_path: None,
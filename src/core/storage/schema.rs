@@ -18,18 +18,23 @@
 //! ### Document Tables (Task 1.2.3)
 //! - `library_item` - Document metadata (FR-2.1)
 //! - `chunk` - Document chunks with BM25 + HNSW indexes (FR-2.3, FR-3.2)
+//! - `embedding_index` - Active embedding model and re-embedding progress
 //!
 //! ### Graph Relations (Task 1.2.4)
 //! - `npc_relation` - NPC-to-NPC relationships (FR-5.1, FR-5.2)
 //! - `chunk_reference` - Cross-references between chunks
 //! - `faction` - Campaign factions
 //! - `location` - Campaign locations with parent hierarchy
+//!
+//! ### Bestiary
+//! - `stat_block` - Creature stat blocks harvested during ingestion, per source
 
 /// Schema version 1 - Foundation schema.
 ///
 /// Defines the core tables and indexes for:
 /// - Document storage and chunking
-/// - Vector embeddings for semantic search (HNSW, 768 dimensions, COSINE)
+/// - Vector embeddings for semantic search (HNSW, COSINE; 768 dimensions until
+///   resized for the active embedding model)
 /// - Full-text search with custom analyzers (BM25 with highlights)
 /// - Campaign and entity graph relationships
 ///
@@ -168,6 +173,11 @@ DEFINE FIELD IF NOT EXISTS content_category ON library_item TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS publisher ON library_item TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS status ON library_item TYPE string DEFAULT "pending";
 DEFINE FIELD IF NOT EXISTS error_message ON library_item TYPE option<string>;
+-- BLAKE3 of the source file, and of each page's text (with the page numbers
+-- alongside) for incremental re-ingestion
+DEFINE FIELD IF NOT EXISTS content_hash ON library_item TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS page_hashes ON library_item TYPE option<array<string>>;
+DEFINE FIELD IF NOT EXISTS page_numbers ON library_item TYPE option<array<int>>;
 DEFINE FIELD IF NOT EXISTS created_at ON library_item TYPE datetime DEFAULT time::now();
 DEFINE FIELD IF NOT EXISTS updated_at ON library_item TYPE datetime DEFAULT time::now();
 DEFINE FIELD IF NOT EXISTS metadata ON library_item TYPE option<object>;
@@ -175,6 +185,7 @@ DEFINE FIELD IF NOT EXISTS metadata ON library_item TYPE option<object>;
 DEFINE INDEX IF NOT EXISTS library_slug ON library_item FIELDS slug UNIQUE;
 DEFINE INDEX IF NOT EXISTS library_status ON library_item FIELDS status;
 DEFINE INDEX IF NOT EXISTS library_game ON library_item FIELDS game_system_id;
+DEFINE INDEX IF NOT EXISTS library_content_hash ON library_item FIELDS content_hash;
 
 -- ============================================================================
 -- CHUNK TABLE (document chunks with vectors) - Task 1.2.3, FR-2.1, FR-2.3, FR-3.2
@@ -193,15 +204,27 @@ DEFINE FIELD IF NOT EXISTS chapter_title ON chunk TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS section_title ON chunk TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS chunk_type ON chunk TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS semantic_keywords ON chunk TYPE option<array<string>>;
+-- BLAKE3 of the content, so re-ingestion can keep unchanged chunks
+DEFINE FIELD IF NOT EXISTS content_hash ON chunk TYPE option<string>;
+-- TTRPG attributes from the ingestion classifier, for filtering
+DEFINE FIELD IF NOT EXISTS game_system ON chunk TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS challenge_rating ON chunk TYPE option<float>;
+DEFINE FIELD IF NOT EXISTS spell_level ON chunk TYPE option<int>;
+DEFINE FIELD IF NOT EXISTS damage_types ON chunk TYPE option<array<string>>;
+DEFINE FIELD IF NOT EXISTS creature_types ON chunk TYPE option<array<string>>;
 DEFINE FIELD IF NOT EXISTS embedding ON chunk TYPE option<array<float>>;
 DEFINE FIELD IF NOT EXISTS embedding_model ON chunk TYPE option<string>;
+-- Staging vector while the library is re-embedded for a new model (see embedding_index.rs)
+DEFINE FIELD IF NOT EXISTS embedding_next ON chunk TYPE option<array<float>>;
+DEFINE FIELD IF NOT EXISTS embedding_next_model ON chunk TYPE option<string>;
 DEFINE FIELD IF NOT EXISTS created_at ON chunk TYPE datetime DEFAULT time::now();
 DEFINE FIELD IF NOT EXISTS metadata ON chunk TYPE option<object>;
 
 -- Full-text index on content with BM25 and highlights (FR-3.2)
 DEFINE INDEX IF NOT EXISTS chunk_content ON chunk FIELDS content SEARCH ANALYZER ttrpg_analyzer BM25 HIGHLIGHTS;
 
--- Vector index (HNSW) - 768 dimensions for nomic-embed-text (FR-2.3)
+-- Vector index (HNSW) - created at 768 dimensions for nomic-embed-text (FR-2.3), then
+-- redefined for the active embedding model's dimensions by ensure_embedding_index()
 -- Parameters: EFC 150 (search quality), M 12 (graph connectivity)
 DEFINE INDEX IF NOT EXISTS chunk_embedding ON chunk FIELDS embedding HNSW DIMENSION 768 DIST COSINE EFC 150 M 12;
 
@@ -209,6 +232,18 @@ DEFINE INDEX IF NOT EXISTS chunk_embedding ON chunk FIELDS embedding HNSW DIMENS
 DEFINE INDEX IF NOT EXISTS chunk_library ON chunk FIELDS library_item;
 DEFINE INDEX IF NOT EXISTS chunk_type ON chunk FIELDS content_type;
 DEFINE INDEX IF NOT EXISTS chunk_page ON chunk FIELDS page_number;
+DEFINE INDEX IF NOT EXISTS chunk_embedding_model ON chunk FIELDS embedding_model;
+DEFINE INDEX IF NOT EXISTS chunk_game_system ON chunk FIELDS game_system;
+DEFINE INDEX IF NOT EXISTS chunk_element_type ON chunk FIELDS chunk_type;
+
+-- Active embedding model and re-embedding progress (single record: embedding_index:active)
+DEFINE TABLE IF NOT EXISTS embedding_index SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS model ON embedding_index TYPE string;
+DEFINE FIELD IF NOT EXISTS dimensions ON embedding_index TYPE int;
+DEFINE FIELD IF NOT EXISTS status ON embedding_index TYPE string;
+DEFINE FIELD IF NOT EXISTS target_model ON embedding_index TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS target_dimensions ON embedding_index TYPE option<int>;
+DEFINE FIELD IF NOT EXISTS updated_at ON embedding_index TYPE datetime DEFAULT time::now();
 
 -- ============================================================================
 -- CHUNK RELATIONS (for cross-references) - Task 1.2.4, FR-5.1
@@ -246,6 +281,57 @@ DEFINE FIELD IF NOT EXISTS created_at ON location TYPE datetime DEFAULT time::no
 
 DEFINE INDEX IF NOT EXISTS location_campaign ON location FIELDS campaign;
 DEFINE INDEX IF NOT EXISTS location_parent ON location FIELDS parent_location;
+
+-- ============================================================================
+-- STAT BLOCK TABLE (bestiary harvested from ingested documents)
+-- ============================================================================
+
+DEFINE TABLE IF NOT EXISTS stat_block SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS name ON stat_block TYPE string;
+DEFINE FIELD IF NOT EXISTS size ON stat_block TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS creature_type ON stat_block TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS alignment ON stat_block TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS armor_class ON stat_block TYPE option<int>;
+DEFINE FIELD IF NOT EXISTS hit_points ON stat_block TYPE option<int>;
+DEFINE FIELD IF NOT EXISTS hit_dice ON stat_block TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS challenge_rating ON stat_block TYPE option<float>;
+DEFINE FIELD IF NOT EXISTS xp ON stat_block TYPE option<int>;
+DEFINE FIELD IF NOT EXISTS library_item ON stat_block TYPE record<library_item>;
+DEFINE FIELD IF NOT EXISTS source ON stat_block TYPE string;
+DEFINE FIELD IF NOT EXISTS page_number ON stat_block TYPE option<int>;
+DEFINE FIELD IF NOT EXISTS data ON stat_block FLEXIBLE TYPE object;
+DEFINE FIELD IF NOT EXISTS created_at ON stat_block TYPE datetime DEFAULT time::now();
+
+DEFINE INDEX IF NOT EXISTS stat_block_name ON stat_block FIELDS name SEARCH ANALYZER exact_analyzer BM25;
+DEFINE INDEX IF NOT EXISTS stat_block_cr ON stat_block FIELDS challenge_rating;
+DEFINE INDEX IF NOT EXISTS stat_block_type ON stat_block FIELDS creature_type;
+DEFINE INDEX IF NOT EXISTS stat_block_size ON stat_block FIELDS size;
+DEFINE INDEX IF NOT EXISTS stat_block_library ON stat_block FIELDS library_item;
+
+-- ============================================================================
+-- NPC MEMORY TABLE (long-term facts and impressions from NPC conversations)
+-- ============================================================================
+
+-- npc_id is the SQLite NPC id; vectors are ranked in process (see npc_memory.rs)
+DEFINE TABLE IF NOT EXISTS npc_memory SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS npc_id ON npc_memory TYPE string;
+DEFINE FIELD IF NOT EXISTS campaign_id ON npc_memory TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS kind ON npc_memory TYPE string;
+DEFINE FIELD IF NOT EXISTS content ON npc_memory TYPE string;
+DEFINE FIELD IF NOT EXISTS importance ON npc_memory TYPE float;
+DEFINE FIELD IF NOT EXISTS pinned ON npc_memory TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS embedding ON npc_memory TYPE option<array<float>>;
+DEFINE FIELD IF NOT EXISTS embedding_model ON npc_memory TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS source_conversation ON npc_memory TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS created_at ON npc_memory TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS last_recalled_at ON npc_memory TYPE option<datetime>;
+
+DEFINE INDEX IF NOT EXISTS npc_memory_npc ON npc_memory FIELDS npc_id;
+
+-- Messages of each NPC conversation already read for memories (id = conversation id)
+DEFINE TABLE IF NOT EXISTS npc_memory_watermark SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS remembered ON npc_memory_watermark TYPE int;
+DEFINE FIELD IF NOT EXISTS updated_at ON npc_memory_watermark TYPE datetime DEFAULT time::now();
 "#;
 
 /// Schema version for migration tracking
@@ -318,6 +404,35 @@ mod tests {
         assert!(SCHEMA_V1.contains("TYPE record<chunk>"));
     }
 
+    #[test]
+    fn test_schema_contains_bestiary_table() {
+        assert!(SCHEMA_V1.contains("DEFINE TABLE IF NOT EXISTS stat_block SCHEMAFULL"));
+        assert!(SCHEMA_V1.contains("library_item ON stat_block TYPE record<library_item>"));
+        assert!(SCHEMA_V1.contains("stat_block_cr ON stat_block FIELDS challenge_rating"));
+    }
+
+    #[test]
+    fn test_schema_contains_npc_memory_table() {
+        assert!(SCHEMA_V1.contains("DEFINE TABLE IF NOT EXISTS npc_memory SCHEMAFULL"));
+        assert!(SCHEMA_V1.contains("npc_memory_npc ON npc_memory FIELDS npc_id"));
+    }
+
+    #[test]
+    fn test_schema_contains_chunk_ttrpg_fields() {
+        assert!(SCHEMA_V1.contains("game_system ON chunk TYPE option<string>"));
+        assert!(SCHEMA_V1.contains("challenge_rating ON chunk TYPE option<float>"));
+        assert!(SCHEMA_V1.contains("chunk_game_system ON chunk FIELDS game_system"));
+    }
+
+    #[test]
+    fn test_schema_contains_content_hashes() {
+        assert!(SCHEMA_V1.contains("content_hash ON library_item TYPE option<string>"));
+        assert!(SCHEMA_V1.contains("page_hashes ON library_item TYPE option<array<string>>"));
+        assert!(SCHEMA_V1.contains("page_numbers ON library_item TYPE option<array<int>>"));
+        assert!(SCHEMA_V1.contains("library_content_hash ON library_item FIELDS content_hash"));
+        assert!(SCHEMA_V1.contains("content_hash ON chunk TYPE option<string>"));
+    }
+
     #[test]
     fn test_schema_uses_if_not_exists() {
         // All definitions should be idempotent
@@ -37,6 +37,7 @@ use surrealdb::Surreal;
 
 use super::error::StorageError;
 use crate::core::preprocess::{Correction, ProcessedQuery, QueryPipeline};
+use crate::core::search::rerank::{rerank, RerankCandidate, RerankConfig, Reranker};
 
 // ============================================================================
 // TYPES
@@ -62,6 +63,9 @@ pub struct SearchResult {
     /// Linear score from hybrid search fusion (populated by search::linear())
     #[serde(default, alias = "linear_score")]
     pub linear_score: Option<f32>,
+    /// Reranker score, when the reranking stage rescored this result
+    #[serde(default)]
+    pub rerank_score: Option<f32>,
     /// Source document slug (from library_item)
     #[serde(default)]
     pub source: String,
@@ -76,6 +80,16 @@ pub struct SearchResult {
     pub highlights: Option<String>,
 }
 
+impl RerankCandidate for SearchResult {
+    fn rerank_text(&self) -> &str {
+        &self.content
+    }
+
+    fn set_rerank_score(&mut self, score: f32) {
+        self.rerank_score = Some(score);
+    }
+}
+
 /// Configuration for hybrid search operations.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HybridSearchConfig {
@@ -89,6 +103,13 @@ pub struct HybridSearchConfig {
     pub min_score: f32,
     /// Score normalization method
     pub normalization: ScoreNormalization,
+    /// Reranking after fusion, applied when a reranker is supplied (`None` = fused order only)
+    #[serde(default)]
+    pub rerank: Option<RerankConfig>,
+    /// Model that embedded the query vector. When the live index holds
+    /// another model's vectors the search is keyword-only (`None` = not checked)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_model: Option<String>,
 }
 
 /// Score normalization method for hybrid search.
@@ -111,6 +132,8 @@ impl Default for HybridSearchConfig {
             limit: 10,
             min_score: 0.1,
             normalization: ScoreNormalization::MinMax,
+            rerank: Some(RerankConfig::default()),
+            query_model: None,
         }
     }
 }
@@ -158,9 +181,22 @@ impl HybridSearchConfig {
         self
     }
 
+    /// Create config with reranking settings (`None` disables reranking).
+    pub fn with_rerank(mut self, rerank: Option<RerankConfig>) -> Self {
+        self.rerank = rerank;
+        self
+    }
+
+    /// Create config for a query vector embedded by `model` (a provider's `model_id()`).
+    pub fn with_query_model(mut self, model: impl Into<String>) -> Self {
+        self.query_model = Some(model.into());
+        self
+    }
+
     /// Create a config optimized for TTRPG rules content.
     ///
-    /// Uses lower semantic weight since rules queries often contain exact terms.
+    /// Uses lower semantic weight since rules queries often contain exact terms,
+    /// and reranks so defining passages beat passing mentions.
     pub fn for_rules() -> Self {
         Self {
             semantic_weight: 0.4,
@@ -168,6 +204,8 @@ impl HybridSearchConfig {
             limit: 15,
             min_score: 0.15,
             normalization: ScoreNormalization::MinMax,
+            rerank: Some(RerankConfig::for_rules()),
+            query_model: None,
         }
     }
 
@@ -181,6 +219,8 @@ impl HybridSearchConfig {
             limit: 10,
             min_score: 0.1,
             normalization: ScoreNormalization::MinMax,
+            rerank: Some(RerankConfig::for_lore()),
+            query_model: None,
         }
     }
 
@@ -194,6 +234,8 @@ impl HybridSearchConfig {
             limit: 20,
             min_score: 0.05,
             normalization: ScoreNormalization::MinMax,
+            rerank: None,
+            query_model: None,
         }
     }
 }
@@ -211,6 +253,18 @@ pub struct SearchFilter {
     pub page_min: Option<i32>,
     /// Filter by maximum page number
     pub page_max: Option<i32>,
+    /// Filter by detected game system ID (dnd5e, pf2e, coc, ...)
+    pub game_system: Option<String>,
+    /// Filter by chunk element type (stat_block, random_table, spell, ...)
+    pub chunk_type: Option<String>,
+    /// Filter by minimum challenge rating
+    pub cr_min: Option<f32>,
+    /// Filter by maximum challenge rating
+    pub cr_max: Option<f32>,
+    /// Filter by spell level (0 for cantrips)
+    pub spell_level: Option<i32>,
+    /// Filter by a damage type mentioned in the chunk
+    pub damage_type: Option<String>,
 }
 
 impl SearchFilter {
@@ -238,6 +292,37 @@ impl SearchFilter {
         self
     }
 
+    /// Filter by game system ID.
+    pub fn game_system(mut self, system: impl Into<String>) -> Self {
+        self.game_system = Some(system.into());
+        self
+    }
+
+    /// Filter by chunk element type.
+    pub fn chunk_type(mut self, chunk_type: impl Into<String>) -> Self {
+        self.chunk_type = Some(chunk_type.into());
+        self
+    }
+
+    /// Filter by challenge rating range.
+    pub fn challenge_rating(mut self, min: Option<f32>, max: Option<f32>) -> Self {
+        self.cr_min = min;
+        self.cr_max = max;
+        self
+    }
+
+    /// Filter by spell level.
+    pub fn spell_level(mut self, level: i32) -> Self {
+        self.spell_level = Some(level);
+        self
+    }
+
+    /// Filter by damage type.
+    pub fn damage_type(mut self, damage_type: impl Into<String>) -> Self {
+        self.damage_type = Some(damage_type.into().to_lowercase());
+        self
+    }
+
     /// Convert to SurrealQL WHERE clause fragment.
     ///
     /// Returns None if no filters are set.
@@ -260,6 +345,30 @@ impl SearchFilter {
             conditions.push(format!("page_number <= {}", max));
         }
 
+        if let Some(ref gs) = self.game_system {
+            conditions.push(format!("game_system = '{}'", gs));
+        }
+
+        if let Some(ref ct) = self.chunk_type {
+            conditions.push(format!("chunk_type = '{}'", ct));
+        }
+
+        if let Some(min) = self.cr_min {
+            conditions.push(format!("challenge_rating >= {}", min));
+        }
+
+        if let Some(max) = self.cr_max {
+            conditions.push(format!("challenge_rating <= {}", max));
+        }
+
+        if let Some(level) = self.spell_level {
+            conditions.push(format!("spell_level = {}", level));
+        }
+
+        if let Some(ref dt) = self.damage_type {
+            conditions.push(format!("damage_types CONTAINS '{}'", dt));
+        }
+
         if conditions.is_empty() {
             None
         } else {
@@ -268,6 +377,10 @@ impl SearchFilter {
     }
 }
 
+/// Condition every search applies: chunks of library items whose file went
+/// missing from a watched folder are left out until the file is found again.
+const AVAILABLE_CHUNKS: &str = "library_item.status != 'missing'";
+
 // ============================================================================
 // VECTOR SEARCH (Task 2.1.1, Task 2.1.2)
 // ============================================================================
@@ -349,12 +462,13 @@ pub async fn vector_search(
             content_type,
             vector::distance::knn() as score
         FROM chunk
-        WHERE {filter_clause} embedding <|{limit},{efc}|> $embedding
+        WHERE {filter_clause} {available} AND embedding <|{limit},{efc}|> $embedding
         ORDER BY score ASC;
     "#,
         limit = limit,
         efc = efc,
-        filter_clause = filter_clause
+        filter_clause = filter_clause,
+        available = AVAILABLE_CHUNKS
     );
 
     let mut response = db
@@ -500,10 +614,12 @@ pub async fn fulltext_search_with_highlights(
             search::highlight($highlight_start, $highlight_end, 1) as highlights
         FROM chunk
         WHERE content @1@ $query
+        AND {available}
         {filter_clause}
         ORDER BY score DESC
         LIMIT {limit};
     "#,
+        available = AVAILABLE_CHUNKS,
         filter_clause = filter_clause,
         limit = limit
     );
@@ -532,6 +648,47 @@ pub async fn fulltext_search_with_highlights(
 // HYBRID SEARCH (Task 2.3 - for future implementation)
 // ============================================================================
 
+/// Vector half of a hybrid search.
+///
+/// The query vector is only comparable with the live index when both come
+/// from the same model. While the library is being re-embedded, when the
+/// index records a different model than `query_model`, or when the
+/// dimensions differ, the search degrades to keyword-only instead of
+/// returning meaningless neighbours or failing. Any other error is returned.
+async fn vector_search_for_fusion(
+    db: &Surreal<Db>,
+    query_embedding: Vec<f32>,
+    query_model: Option<&str>,
+    limit: usize,
+    filters: Option<&str>,
+) -> Result<Vec<SearchResult>, StorageError> {
+    use super::embedding_index::{get_index_state, IndexStatus};
+
+    let dimensions = query_embedding.len();
+    if let Some(state) = get_index_state(db).await? {
+        let reason = if state.status == IndexStatus::Reembedding {
+            Some("the library is being re-embedded")
+        } else if query_model.is_some_and(|model| model != state.model) {
+            Some("the index holds another model's vectors")
+        } else if state.dimensions != dimensions {
+            Some("the query vector does not fit the index")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            tracing::warn!(
+                query_model,
+                dimensions,
+                index_model = %state.model,
+                index_dimensions = state.dimensions,
+                "Using keyword results only: {reason}"
+            );
+            return Ok(Vec::new());
+        }
+    }
+    vector_search(db, query_embedding, limit, filters).await
+}
+
 /// Perform hybrid search combining vector and full-text results.
 ///
 /// Uses SurrealDB's `search::linear()` function to fuse results from both
@@ -581,13 +738,45 @@ pub async fn hybrid_search(
     query_embedding: Vec<f32>,
     config: &HybridSearchConfig,
     filters: Option<&str>,
+) -> Result<Vec<SearchResult>, StorageError> {
+    hybrid_search_reranked(db, query, query_embedding, config, filters, None).await
+}
+
+/// Hybrid search followed by the optional reranking stage.
+///
+/// When both `reranker` and `config.rerank` are set, the top fused candidates
+/// are rescored before the limit is applied. If the reranker fails or runs
+/// past the preset's latency budget, the fused order is kept.
+///
+/// # Arguments
+///
+/// * `db` - SurrealDB database reference
+/// * `query` - Search query string (for full-text component and reranking)
+/// * `query_embedding` - Query embedding vector (for vector component)
+/// * `config` - Hybrid search configuration (weights, limits, reranking)
+/// * `filters` - Optional WHERE clause conditions
+/// * `reranker` - Reranker to apply, if one is configured
+pub async fn hybrid_search_reranked(
+    db: &Surreal<Db>,
+    query: &str,
+    query_embedding: Vec<f32>,
+    config: &HybridSearchConfig,
+    filters: Option<&str>,
+    reranker: Option<&dyn Reranker>,
 ) -> Result<Vec<SearchResult>, StorageError> {
     // Fetch more results than needed for fusion quality
     let fetch_limit = config.limit * 3;
 
     // Execute vector and fulltext searches
     // Note: search::linear() requires SurrealDB 3.x, so we perform manual fusion
-    let vec_results = vector_search(db, query_embedding, fetch_limit, filters).await?;
+    let vec_results = vector_search_for_fusion(
+        db,
+        query_embedding,
+        config.query_model.as_deref(),
+        fetch_limit,
+        filters,
+    )
+    .await?;
     let ft_results = fulltext_search(db, query, fetch_limit, filters).await?;
 
     // Perform score fusion in Rust
@@ -599,13 +788,48 @@ pub async fn hybrid_search(
         &config.normalization,
     );
 
-    // Apply minimum score threshold and limit
-    let filtered: Vec<SearchResult> = fused
+    // Apply minimum score threshold
+    let mut filtered: Vec<SearchResult> = fused
+        .into_iter()
+        .filter(|r| r.score >= config.min_score)
+        .collect();
+
+    // Rescore the top candidates before cutting to the limit
+    if let (Some(reranker), Some(rerank_config)) = (reranker, config.rerank.as_ref()) {
+        filtered = rerank(reranker, query, filtered, rerank_config).await;
+    }
+    filtered.truncate(config.limit);
+
+    Ok(filtered)
+}
+
+/// Keyword-only (BM25) counterpart of [`hybrid_search_reranked`], for when no
+/// query embedding is available (no embedding provider, or embedding the
+/// query failed).
+///
+/// BM25 scores are normalized as in the keyword half of a hybrid search, so
+/// `config.min_score` and the reranking stage apply unchanged; the fusion
+/// weights are ignored.
+pub async fn keyword_search_reranked(
+    db: &Surreal<Db>,
+    query: &str,
+    config: &HybridSearchConfig,
+    filters: Option<&str>,
+    reranker: Option<&dyn Reranker>,
+) -> Result<Vec<SearchResult>, StorageError> {
+    let ft_results = fulltext_search(db, query, config.limit * 3, filters).await?;
+    let normalized = fuse_search_results(Vec::new(), ft_results, 0.0, 1.0, &config.normalization);
+
+    let mut filtered: Vec<SearchResult> = normalized
         .into_iter()
         .filter(|r| r.score >= config.min_score)
-        .take(config.limit)
         .collect();
 
+    if let (Some(reranker), Some(rerank_config)) = (reranker, config.rerank.as_ref()) {
+        filtered = rerank(reranker, query, filtered, rerank_config).await;
+    }
+    filtered.truncate(config.limit);
+
     Ok(filtered)
 }
 
@@ -735,7 +959,14 @@ pub async fn hybrid_search_with_preprocessing(
     let fetch_limit = config.limit * 3;
 
     // Execute vector search using embedding (derived from corrected text)
-    let vec_results = vector_search(db, query_embedding, fetch_limit, filter_str.as_deref()).await?;
+    let vec_results = vector_search_for_fusion(
+        db,
+        query_embedding,
+        config.query_model.as_deref(),
+        fetch_limit,
+        filter_str.as_deref(),
+    )
+    .await?;
 
     // Execute full-text search using the synonym-expanded query
     // The expanded query produces OR clauses like: (content @@ 'hp' OR content @@ 'hit points')
@@ -817,11 +1048,13 @@ async fn fulltext_search_expanded(
             content_type,
             1.0 as score
         FROM chunk
-        WHERE {expanded_query}
+        WHERE ({expanded_query})
+        AND {available}
         {filter_clause}
         LIMIT {limit};
     "#,
         expanded_query = expanded_query,
+        available = AVAILABLE_CHUNKS,
         filter_clause = filter_clause,
         limit = limit
     );
@@ -989,6 +1222,7 @@ mod tests {
             DEFINE TABLE IF NOT EXISTS library_item SCHEMAFULL;
             DEFINE FIELD IF NOT EXISTS slug ON library_item TYPE string;
             DEFINE FIELD IF NOT EXISTS title ON library_item TYPE string;
+            DEFINE FIELD IF NOT EXISTS status ON library_item TYPE string DEFAULT 'pending';
             DEFINE INDEX IF NOT EXISTS library_slug ON library_item FIELDS slug UNIQUE;
 
             DEFINE TABLE IF NOT EXISTS chunk SCHEMAFULL;
@@ -1305,6 +1539,31 @@ mod tests {
         assert_eq!(results[0].content_type, "fiction");
     }
 
+    #[tokio::test]
+    async fn test_search_skips_chunks_of_missing_items() {
+        let (_dir, db) = setup_test_db().await;
+        insert_library_item(&db, "phb-2024", "Player's Handbook 2024").await;
+        insert_library_item(&db, "phb-old", "Player's Handbook (old path)").await;
+
+        insert_chunk(&db, "Dragon combat rules", "phb-2024", "rules", Some(100), make_embedding(0.0)).await;
+        insert_chunk(&db, "Dragon combat rules", "phb-old", "rules", Some(100), make_embedding(0.0)).await;
+        db.query("UPDATE library_item SET status = 'missing' WHERE slug = 'phb-old'")
+            .await
+            .expect("Failed to mark item missing");
+
+        let results = fulltext_search(&db, "dragon", 10, None)
+            .await
+            .expect("Fulltext search failed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source, "phb-2024");
+
+        let results = vector_search(&db, make_embedding(0.0), 10, None)
+            .await
+            .expect("Vector search failed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source, "phb-2024");
+    }
+
     // ========================================================================
     // Task 2.2.2: Highlighting tests
     // ========================================================================
@@ -1402,6 +1661,25 @@ mod tests {
         assert!(surql.contains("library_item = library_item:phb-2024"));
     }
 
+    #[test]
+    fn test_search_filter_ttrpg_attributes() {
+        let filter = SearchFilter::new()
+            .game_system("dnd5e")
+            .chunk_type("stat_block")
+            .challenge_rating(Some(0.25), Some(5.0))
+            .damage_type("Fire");
+
+        let surql = filter.to_surql().expect("Should have filter");
+        assert!(surql.contains("game_system = 'dnd5e'"));
+        assert!(surql.contains("chunk_type = 'stat_block'"));
+        assert!(surql.contains("challenge_rating >= 0.25"));
+        assert!(surql.contains("challenge_rating <= 5"));
+        assert!(surql.contains("damage_types CONTAINS 'fire'"));
+
+        let surql = SearchFilter::new().spell_level(0).to_surql().unwrap();
+        assert_eq!(surql, "spell_level = 0");
+    }
+
     // ========================================================================
     // HybridSearchConfig tests
     // ========================================================================
@@ -1472,6 +1750,14 @@ mod tests {
         assert!(config.semantic_weight > config.keyword_weight);
     }
 
+    #[test]
+    fn test_hybrid_config_rerank_presets() {
+        assert!(HybridSearchConfig::for_session_notes().rerank.is_none());
+        assert!(HybridSearchConfig::for_rules().rerank.is_some());
+        assert!(HybridSearchConfig::for_lore().rerank.is_some());
+        assert!(HybridSearchConfig::for_rules().with_rerank(None).rerank.is_none());
+    }
+
     #[test]
     fn test_hybrid_config_preset_for_session_notes() {
         let config = HybridSearchConfig::for_session_notes();
@@ -1553,6 +1839,178 @@ mod tests {
         // We mainly verify that results are returned and contain expected chunks.
     }
 
+    /// Prefers passages containing "tactical".
+    struct KeywordReranker;
+
+    #[async_trait::async_trait]
+    impl Reranker for KeywordReranker {
+        fn name(&self) -> &str {
+            "keyword"
+        }
+
+        async fn score(
+            &self,
+            _query: &str,
+            passages: &[&str],
+        ) -> crate::core::search::rerank::Result<Vec<f32>> {
+            Ok(passages
+                .iter()
+                .map(|p| if p.contains("tactical") { 1.0 } else { 0.0 })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_reranked_reorders_results() {
+        let (_dir, db) = setup_test_db().await;
+        insert_library_item(&db, "phb-2024", "Player's Handbook 2024").await;
+
+        let embedding = make_embedding(0.0);
+        insert_chunk(
+            &db,
+            "Flanking gives advantage",
+            "phb-2024",
+            "rules",
+            Some(251),
+            embedding.clone(),
+        )
+        .await;
+        insert_chunk(
+            &db,
+            "Flanking is a tactical maneuver",
+            "phb-2024",
+            "rules",
+            Some(252),
+            make_embedding(5.0),
+        )
+        .await;
+
+        let config = HybridSearchConfig::from_semantic_ratio(0.5)
+            .with_min_score(0.0)
+            .with_rerank(Some(RerankConfig::default()));
+        let results = hybrid_search_reranked(
+            &db,
+            "flanking",
+            embedding,
+            &config,
+            None,
+            Some(&KeywordReranker),
+        )
+        .await
+        .expect("Hybrid search failed");
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].content.contains("tactical"));
+        assert_eq!(results[0].rerank_score, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_keyword_search_reranked_normalizes_scores() {
+        let (_dir, db) = setup_test_db().await;
+        insert_library_item(&db, "phb-2024", "Player's Handbook 2024").await;
+
+        insert_chunk(
+            &db,
+            "Flanking gives advantage. When flanking, both allies gain advantage.",
+            "phb-2024",
+            "rules",
+            Some(251),
+            make_embedding(0.0),
+        )
+        .await;
+        insert_chunk(
+            &db,
+            "Movement on a flanking maneuver requires positioning.",
+            "phb-2024",
+            "rules",
+            Some(252),
+            make_embedding(0.1),
+        )
+        .await;
+        insert_chunk(
+            &db,
+            "Cover provides bonuses to AC.",
+            "phb-2024",
+            "rules",
+            Some(198),
+            make_embedding(0.2),
+        )
+        .await;
+
+        let config = HybridSearchConfig::default().with_min_score(0.0);
+        let results = keyword_search_reranked(&db, "flanking advantage", &config, None, None)
+            .await
+            .expect("Keyword search failed");
+
+        assert!(!results.is_empty());
+        assert!(results[0].content.contains("advantage"));
+        assert!(results.iter().all(|r| !r.content.contains("Cover")));
+        assert_eq!(results[0].score, 1.0);
+        assert!(results.iter().all(|r| (0.0..=1.0).contains(&r.score)));
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_vector_errors() {
+        use crate::core::storage::embedding_index::{
+            save_index_state, EmbeddingIndexState, IndexStatus,
+        };
+
+        let (_dir, db) = setup_test_db().await;
+        insert_library_item(&db, "phb-2024", "Player's Handbook 2024").await;
+        insert_chunk(
+            &db,
+            "Flanking gives advantage on melee attack rolls.",
+            "phb-2024",
+            "rules",
+            Some(251),
+            make_embedding(0.0),
+        )
+        .await;
+        let config = HybridSearchConfig::default().with_min_score(0.0);
+
+        // Mid re-embed: the live index still serves 768d vectors, so a
+        // query vector from the new model degrades to keyword results
+        save_index_state(&db, &EmbeddingIndexState::ready("test/new-model", 768))
+            .await
+            .unwrap();
+        let results = hybrid_search(&db, "flanking", vec![0.5; 384], &config, None)
+            .await
+            .expect("Dimension mismatch should degrade to keyword search");
+        assert_eq!(results.len(), 1);
+
+        // A query vector from another model at the same dimension is not
+        // comparable either, even though the search itself would succeed
+        save_index_state(&db, &EmbeddingIndexState::ready("test/old-model", 768))
+            .await
+            .unwrap();
+        let other_model = config.clone().with_query_model("test/new-model");
+        let results = hybrid_search(&db, "weather", make_embedding(0.0), &other_model, None)
+            .await
+            .expect("Model mismatch should degrade to keyword search");
+        assert!(results.is_empty());
+        let same_model = config.clone().with_query_model("test/old-model");
+        let results = hybrid_search(&db, "weather", make_embedding(0.0), &same_model, None)
+            .await
+            .expect("Hybrid search failed");
+        assert_eq!(results.len(), 1);
+
+        // Nor is anything searched while a re-embed is underway
+        let mut reembedding = EmbeddingIndexState::ready("test/old-model", 768);
+        reembedding.status = IndexStatus::Reembedding;
+        save_index_state(&db, &reembedding).await.unwrap();
+        let results = hybrid_search(&db, "weather", make_embedding(0.0), &same_model, None)
+            .await
+            .expect("Re-embedding should degrade to keyword search");
+        assert!(results.is_empty());
+
+        // Any other vector search failure is reported
+        save_index_state(&db, &EmbeddingIndexState::ready("test/new-model", 384))
+            .await
+            .unwrap();
+        let result = hybrid_search(&db, "flanking", vec![0.5; 384], &config, None).await;
+        assert!(matches!(result, Err(StorageError::Query(_))));
+    }
+
     #[tokio::test]
     async fn test_hybrid_search_min_score_filtering() {
         let (_dir, db) = setup_test_db().await;
@@ -1767,6 +2225,7 @@ mod benchmark_tests {
             DEFINE TABLE IF NOT EXISTS library_item SCHEMAFULL;
             DEFINE FIELD IF NOT EXISTS slug ON library_item TYPE string;
             DEFINE FIELD IF NOT EXISTS title ON library_item TYPE string;
+            DEFINE FIELD IF NOT EXISTS status ON library_item TYPE string DEFAULT 'pending';
             DEFINE INDEX IF NOT EXISTS library_slug ON library_item FIELDS slug UNIQUE;
 
             DEFINE TABLE IF NOT EXISTS chunk SCHEMAFULL;
@@ -0,0 +1,411 @@
+//! Encrypted Credential Vault
+//!
+//! A passphrase-protected file for secrets on machines without a system
+//! keyring (headless Linux, containers). Used by [`CredentialManager`] when
+//! `[credentials] backend = "vault"` and by OAuth token storage.
+//!
+//! The key is derived once per unlock with Argon2id; every entry is sealed
+//! separately with AES-256-GCM under its own random nonce, with the entry name
+//! as associated data so ciphertexts cannot be swapped between keys. A sealed
+//! check value lets `unlock` reject a wrong passphrase up front.
+//!
+//! [`CredentialManager`]: crate::core::credentials::CredentialManager
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Environment variable used to unlock the vault non-interactively.
+pub const VAULT_PASSPHRASE_ENV: &str = "TTTTRPS_VAULT_PASSPHRASE";
+
+const VAULT_VERSION: u32 = 1;
+const KDF_NAME: &str = "argon2id";
+const CHECK_KEY: &str = "__vault_check__";
+const CHECK_VALUE: &str = "ttttrps-vault";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Vault shared with backends that can't take it as a parameter (OAuth
+/// provider construction). Installed once at startup.
+static SHARED: OnceLock<Arc<CredentialVault>> = OnceLock::new();
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Error, Debug)]
+pub enum VaultError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Vault is locked")]
+    Locked,
+
+    #[error("Vault has no passphrase yet")]
+    NotInitialized,
+
+    #[error("Vault is already initialized")]
+    AlreadyInitialized,
+
+    #[error("Wrong passphrase")]
+    WrongPassphrase,
+
+    #[error("Vault entry is corrupt: {0}")]
+    Corrupt(String),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+pub type Result<T> = std::result::Result<T, VaultError>;
+
+// ============================================================================
+// On-disk Format
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    kdf: String,
+    /// Base64 Argon2 salt; `None` until a passphrase is set.
+    salt: Option<String>,
+    check: Option<SealedEntry>,
+    entries: BTreeMap<String, SealedEntry>,
+}
+
+impl Default for VaultFile {
+    fn default() -> Self {
+        Self {
+            version: VAULT_VERSION,
+            kdf: KDF_NAME.to_string(),
+            salt: None,
+            check: None,
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SealedEntry {
+    nonce: String,
+    ciphertext: String,
+}
+
+struct VaultState {
+    file: VaultFile,
+    cipher: Option<Aes256Gcm>,
+}
+
+// ============================================================================
+// Credential Vault
+// ============================================================================
+
+/// Encrypted key/value store for secrets, persisted as JSON.
+pub struct CredentialVault {
+    path: PathBuf,
+    state: RwLock<VaultState>,
+}
+
+impl std::fmt::Debug for CredentialVault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialVault")
+            .field("path", &self.path)
+            .field("unlocked", &self.is_unlocked())
+            .finish()
+    }
+}
+
+impl CredentialVault {
+    /// Open the vault at `path`; a missing file is an empty, uninitialized vault.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let file = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => VaultFile::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path,
+            state: RwLock::new(VaultState { file, cipher: None }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether a passphrase has been set.
+    pub fn is_initialized(&self) -> bool {
+        self.read().file.salt.is_some()
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.read().cipher.is_some()
+    }
+
+    /// Set the passphrase of a new vault and leave it unlocked.
+    pub fn initialize(&self, passphrase: &str) -> Result<()> {
+        let mut state = self.write();
+        if state.file.salt.is_some() {
+            return Err(VaultError::AlreadyInitialized);
+        }
+
+        let mut salt = [0u8; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let cipher = derive_cipher(passphrase, &salt)?;
+
+        state.file.salt = Some(BASE64.encode(salt));
+        state.file.check = Some(seal_entry(&cipher, CHECK_KEY, CHECK_VALUE)?);
+        state.cipher = Some(cipher);
+        self.persist(&state.file)?;
+
+        log::info!("Initialized credential vault at {}", self.path.display());
+        Ok(())
+    }
+
+    /// Derive the key and verify it against the stored check value.
+    pub fn unlock(&self, passphrase: &str) -> Result<()> {
+        let mut state = self.write();
+        let salt = state
+            .file
+            .salt
+            .as_deref()
+            .ok_or(VaultError::NotInitialized)?;
+        let salt = BASE64
+            .decode(salt)
+            .map_err(|e| VaultError::Corrupt(format!("salt: {e}")))?;
+        let cipher = derive_cipher(passphrase, &salt)?;
+
+        let check = state
+            .file
+            .check
+            .as_ref()
+            .ok_or_else(|| VaultError::Corrupt("missing check value".to_string()))?;
+        match open_entry(&cipher, CHECK_KEY, check) {
+            Ok(value) if value == CHECK_VALUE => {}
+            _ => return Err(VaultError::WrongPassphrase),
+        }
+
+        state.cipher = Some(cipher);
+        log::info!("Credential vault unlocked");
+        Ok(())
+    }
+
+    /// Forget the derived key.
+    pub fn lock(&self) {
+        self.write().cipher = None;
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let state = self.read();
+        let cipher = state.cipher.as_ref().ok_or(VaultError::Locked)?;
+        state
+            .file
+            .entries
+            .get(key)
+            .map(|entry| open_entry(cipher, key, entry))
+            .transpose()
+    }
+
+    pub fn set(&self, key: &str, value: &str) -> Result<()> {
+        let mut state = self.write();
+        let cipher = state.cipher.as_ref().ok_or(VaultError::Locked)?;
+        let sealed = seal_entry(cipher, key, value)?;
+        state.file.entries.insert(key.to_string(), sealed);
+        self.persist(&state.file)
+    }
+
+    /// Remove an entry. Works while locked — deleting needs no key.
+    pub fn remove(&self, key: &str) -> Result<()> {
+        let mut state = self.write();
+        if state.file.entries.remove(key).is_some() {
+            self.persist(&state.file)?;
+        }
+        Ok(())
+    }
+
+    /// Whether an entry exists, without decrypting it.
+    pub fn contains(&self, key: &str) -> bool {
+        self.read().file.entries.contains_key(key)
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        self.read().file.entries.keys().cloned().collect()
+    }
+
+    /// Write atomically via temp file + rename, owner-only on Unix.
+    fn persist(&self, file: &VaultFile) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp = self.path.with_extension("tmp");
+        std::fs::write(&temp, serde_json::to_string_pretty(file)?)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&temp, std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::fs::rename(&temp, &self.path)?;
+        Ok(())
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, VaultState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, VaultState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ============================================================================
+// Shared Instance
+// ============================================================================
+
+/// Make `vault` available to [`shared`]. Later calls are ignored.
+pub fn install_shared(vault: Arc<CredentialVault>) {
+    if SHARED.set(vault).is_err() {
+        log::warn!("Credential vault already installed; ignoring second instance");
+    }
+}
+
+/// The vault installed at startup, if the vault backend is in use.
+pub fn shared() -> Option<Arc<CredentialVault>> {
+    SHARED.get().cloned()
+}
+
+// ============================================================================
+// Crypto Helpers
+// ============================================================================
+
+fn derive_cipher(passphrase: &str, salt: &[u8]) -> Result<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| VaultError::Crypto(e.to_string()))?;
+    Aes256Gcm::new_from_slice(&key).map_err(|e| VaultError::Crypto(e.to_string()))
+}
+
+fn seal_entry(cipher: &Aes256Gcm, key: &str, value: &str) -> Result<SealedEntry> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let payload = Payload {
+        msg: value.as_bytes(),
+        aad: key.as_bytes(),
+    };
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), payload)
+        .map_err(|_| VaultError::Crypto(format!("failed to seal '{key}'")))?;
+    Ok(SealedEntry {
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn open_entry(cipher: &Aes256Gcm, key: &str, entry: &SealedEntry) -> Result<String> {
+    let nonce = BASE64
+        .decode(&entry.nonce)
+        .map_err(|e| VaultError::Corrupt(format!("{key}: {e}")))?;
+    if nonce.len() != NONCE_LEN {
+        return Err(VaultError::Corrupt(format!("{key}: bad nonce length")));
+    }
+    let ciphertext = BASE64
+        .decode(&entry.ciphertext)
+        .map_err(|e| VaultError::Corrupt(format!("{key}: {e}")))?;
+    let payload = Payload {
+        msg: &ciphertext,
+        aad: key.as_bytes(),
+    };
+    let plain = cipher
+        .decrypt(Nonce::from_slice(&nonce), payload)
+        .map_err(|_| VaultError::Corrupt(format!("{key}: authentication failed")))?;
+    String::from_utf8(plain).map_err(|e| VaultError::Corrupt(format!("{key}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_initialize_set_get() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("credentials.vault");
+        let vault = CredentialVault::open(&path).unwrap();
+        assert!(!vault.is_initialized());
+        assert!(matches!(vault.set("k", "v"), Err(VaultError::Locked)));
+
+        vault.initialize("correct horse").unwrap();
+        vault.set("llm_openai", "sk-test").unwrap();
+        assert_eq!(vault.get("llm_openai").unwrap().as_deref(), Some("sk-test"));
+        assert_eq!(vault.get("missing").unwrap(), None);
+
+        // Secrets never hit the disk in plaintext
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("sk-test"));
+        assert!(raw.contains("llm_openai"));
+    }
+
+    #[test]
+    fn test_reopen_and_unlock() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("credentials.vault");
+        {
+            let vault = CredentialVault::open(&path).unwrap();
+            vault.initialize("correct horse").unwrap();
+            vault
+                .set("voice_elevenlabs", "{\"api_key\":\"x\"}")
+                .unwrap();
+        }
+
+        let vault = CredentialVault::open(&path).unwrap();
+        assert!(vault.is_initialized());
+        assert!(!vault.is_unlocked());
+        assert!(vault.contains("voice_elevenlabs"));
+        assert!(matches!(
+            vault.get("voice_elevenlabs"),
+            Err(VaultError::Locked)
+        ));
+        assert!(matches!(
+            vault.unlock("wrong"),
+            Err(VaultError::WrongPassphrase)
+        ));
+
+        vault.unlock("correct horse").unwrap();
+        assert_eq!(
+            vault.get("voice_elevenlabs").unwrap().as_deref(),
+            Some("{\"api_key\":\"x\"}")
+        );
+
+        vault.remove("voice_elevenlabs").unwrap();
+        vault.lock();
+        assert!(!vault.contains("voice_elevenlabs"));
+    }
+
+    #[test]
+    fn test_entries_bound_to_their_key() {
+        let dir = TempDir::new().unwrap();
+        let vault = CredentialVault::open(dir.path().join("v")).unwrap();
+        vault.initialize("pw").unwrap();
+        vault.set("a", "alpha").unwrap();
+
+        // Moving a ciphertext under another name must fail authentication
+        {
+            let mut state = vault.write();
+            let entry = state.file.entries["a"].clone();
+            state.file.entries.insert("b".to_string(), entry);
+        }
+        assert!(matches!(vault.get("b"), Err(VaultError::Corrupt(_))));
+    }
+}
@@ -0,0 +1,101 @@
+//! Campaign export/import
+//!
+//! Bundles a campaign with its sessions, notes, NPCs, locations and
+//! relationships into one serializable document, for moving campaigns
+//! between installs or keeping them in version control.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::campaigns::CampaignOps;
+use super::locations::LocationOps;
+use super::models::{
+    CampaignRecord, EntityRelationshipRecord, LocationRecord, NpcRecord, SessionNoteRecord,
+    SessionRecord,
+};
+use super::npcs::NpcOps;
+use super::relationships::RelationshipOps;
+use super::sessions::SessionOps;
+use super::Database;
+
+/// Current bundle format version.
+pub const CAMPAIGN_BUNDLE_VERSION: u32 = 1;
+
+/// A campaign and everything that belongs to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignBundle {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub campaign: CampaignRecord,
+    #[serde(default)]
+    pub sessions: Vec<SessionRecord>,
+    #[serde(default)]
+    pub notes: Vec<SessionNoteRecord>,
+    #[serde(default)]
+    pub npcs: Vec<NpcRecord>,
+    #[serde(default)]
+    pub locations: Vec<LocationRecord>,
+    #[serde(default)]
+    pub relationships: Vec<EntityRelationshipRecord>,
+}
+
+/// Extension trait for campaign export/import
+pub trait CampaignExportOps {
+    /// Collect a campaign and its records, or `None` if it does not exist.
+    fn export_campaign_bundle(
+        &self,
+        campaign_id: &str,
+    ) -> impl std::future::Future<Output = Result<Option<CampaignBundle>, sqlx::Error>> + Send;
+    /// Insert every record in `bundle`, keeping their IDs.
+    ///
+    /// Fails if a campaign with the same ID already exists.
+    fn import_campaign_bundle(
+        &self,
+        bundle: &CampaignBundle,
+    ) -> impl std::future::Future<Output = Result<(), sqlx::Error>> + Send;
+}
+
+impl CampaignExportOps for Database {
+    async fn export_campaign_bundle(
+        &self,
+        campaign_id: &str,
+    ) -> Result<Option<CampaignBundle>, sqlx::Error> {
+        let Some(campaign) = self.get_campaign(campaign_id).await? else {
+            return Ok(None);
+        };
+
+        let mut sessions = self.list_sessions(campaign_id).await?;
+        sessions.sort_by_key(|s| s.session_number);
+
+        Ok(Some(CampaignBundle {
+            format_version: CAMPAIGN_BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            campaign,
+            sessions,
+            notes: self.list_campaign_notes(campaign_id).await?,
+            npcs: self.list_npcs(Some(campaign_id)).await?,
+            locations: self.list_locations(campaign_id).await?,
+            relationships: self.list_campaign_relationships(campaign_id).await?,
+        }))
+    }
+
+    async fn import_campaign_bundle(&self, bundle: &CampaignBundle) -> Result<(), sqlx::Error> {
+        self.create_campaign(&bundle.campaign).await?;
+        for session in &bundle.sessions {
+            self.create_session(session).await?;
+        }
+        for note in &bundle.notes {
+            self.save_session_note(note).await?;
+        }
+        for location in &bundle.locations {
+            self.save_location(location).await?;
+        }
+        for npc in &bundle.npcs {
+            self.save_npc(npc).await?;
+        }
+        for relationship in &bundle.relationships {
+            self.save_entity_relationship(relationship).await?;
+        }
+        Ok(())
+    }
+}
@@ -22,6 +22,7 @@ mod backup;
 
 // Domain-specific operation modules
 mod analytics;
+mod campaign_export;
 mod campaigns;
 mod characters;
 mod chat;
@@ -38,12 +39,13 @@ mod ttrpg;
 mod voice_profiles;
 
 // Re-export existing public items
-pub use migrations::run_migrations;
+pub use migrations::{run_migrations, SCHEMA_VERSION};
 pub use models::*;
 pub use backup::{create_backup, restore_backup, list_backups, BackupInfo};
 
 // Re-export operation traits for ergonomic imports
 pub use analytics::UsageOps;
+pub use campaign_export::{CampaignBundle, CampaignExportOps, CAMPAIGN_BUNDLE_VERSION};
 pub use campaigns::CampaignOps;
 pub use characters::CharacterOps;
 pub use chat::ChatOps;
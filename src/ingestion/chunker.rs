@@ -119,6 +119,10 @@ pub struct ContentChunk {
     pub content: String,
     /// Page number (if applicable)
     pub page_number: Option<u32>,
+    /// First and last page the chunk's text comes from, counting the overlap
+    /// carried over from the previous chunk (if known)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_range: Option<(u32, u32)>,
     /// Section/chapter (if detected) - current deepest section
     pub section: Option<String>,
     /// Chunk type (text, table, header, stat_block, spell, monster, rule, narrative)
@@ -485,6 +489,7 @@ impl SemanticChunker {
             source_id: source_id.to_string(),
             content: content.trim().to_string(),
             page_number,
+            page_range: None,
             section,
             chunk_type: "text".to_string(),
             chunk_index,
@@ -691,6 +696,10 @@ impl TTRPGChunker {
         let mut hierarchy = SectionHierarchy::new();
         let mut buffer = String::new();
         let mut buffer_page: Option<u32> = None;
+        // First and last page of the text in the buffer, overlap included
+        let mut buffer_range: Option<(u32, u32)> = None;
+        // Whether the buffer holds text not yet emitted (beyond the overlap)
+        let mut pending = false;
 
         for element in elements {
             // Update hierarchy for section headers
@@ -701,16 +710,20 @@ impl TTRPGChunker {
                         &buffer,
                         source_id,
                         buffer_page,
+                        buffer_range,
                         &hierarchy,
                         "text",
                         &mut chunk_index,
                     ));
                     buffer = self.get_overlap(&buffer);
                     buffer_page = Some(element.page_number);
+                    buffer_range = overlap_range(buffer_range);
+                    pending = false;
                 }
 
                 let level = Self::detect_header_level(&element.content);
-                hierarchy.update(&element.content, level);
+                let title = element.content.trim().trim_start_matches('#').trim();
+                hierarchy.update(title, level);
                 continue;
             }
 
@@ -722,11 +735,14 @@ impl TTRPGChunker {
                         &buffer,
                         source_id,
                         buffer_page,
+                        buffer_range,
                         &hierarchy,
                         "text",
                         &mut chunk_index,
                     ));
                     buffer.clear();
+                    buffer_range = None;
+                    pending = false;
                 }
 
                 // Emit atomic element as single chunk (or split if too large)
@@ -735,6 +751,7 @@ impl TTRPGChunker {
                         &element.content,
                         source_id,
                         Some(element.page_number),
+                        Some((element.page_number, element.page_number)),
                         &hierarchy,
                         element.element_type.as_str(),
                         &mut chunk_index,
@@ -767,11 +784,13 @@ impl TTRPGChunker {
                         &buffer,
                         source_id,
                         buffer_page,
+                        buffer_range,
                         &hierarchy,
                         "text",
                         &mut chunk_index,
                     ));
                     buffer = self.get_overlap(&buffer);
+                    buffer_range = overlap_range(buffer_range);
                 }
             }
 
@@ -780,6 +799,11 @@ impl TTRPGChunker {
                 buffer.push_str("\n\n");
             }
             buffer.push_str(&element.content);
+            buffer_range = Some(match buffer_range {
+                Some((first, _)) => (first, element.page_number),
+                None => (element.page_number, element.page_number),
+            });
+            pending = true;
 
             // Flush if we've reached target size
             if buffer.len() >= self.config.base.target_size {
@@ -787,21 +811,25 @@ impl TTRPGChunker {
                     &buffer,
                     source_id,
                     buffer_page,
+                    buffer_range,
                     &hierarchy,
                     "text",
                     &mut chunk_index,
                 ));
                 buffer = self.get_overlap(&buffer);
                 buffer_page = Some(element.page_number);
+                buffer_range = overlap_range(buffer_range);
+                pending = false;
             }
         }
 
-        // Flush remaining buffer
-        if !buffer.is_empty() && buffer.len() >= self.config.base.min_size {
+        // Flush remaining buffer, however short, so trailing text is not lost
+        if pending && !buffer.trim().is_empty() {
             chunks.push(self.create_chunk_with_hierarchy(
                 &buffer,
                 source_id,
                 buffer_page,
+                buffer_range,
                 &hierarchy,
                 "text",
                 &mut chunk_index,
@@ -817,6 +845,7 @@ impl TTRPGChunker {
         content: &str,
         source_id: &str,
         page_number: Option<u32>,
+        page_range: Option<(u32, u32)>,
         hierarchy: &SectionHierarchy,
         chunk_type: &str,
         chunk_index: &mut usize,
@@ -860,6 +889,7 @@ impl TTRPGChunker {
             source_id: source_id.to_string(),
             content: content_trimmed.to_string(),
             page_number,
+            page_range,
             section: hierarchy.current().map(|s| s.to_string()),
             chunk_type: chunk_type.to_string(),
             chunk_index: idx,
@@ -893,6 +923,12 @@ impl TTRPGChunker {
     /// Detect header level from text patterns.
     /// Uses the vocabulary module's `detect_header_level` function with fallback logic.
     fn detect_header_level(text: &str) -> usize {
+        // Markdown headings carry their own level
+        let hashes = text.trim().chars().take_while(|&c| c == '#').count();
+        if hashes > 0 {
+            return hashes;
+        }
+
         // Use the vocabulary module's comprehensive header detection
         if let Some(level) = vocabulary_detect_header_level(text) {
             return level as usize;
@@ -921,7 +957,13 @@ impl TTRPGChunker {
         3
     }
 
-    /// Split an oversized element into multiple chunks
+    /// Split an oversized element into multiple chunks.
+    ///
+    /// Atomic elements are only split between entries (a table row, a stat
+    /// block trait or action), never inside one. A line starts a new entry
+    /// unless it continues a wrapped line: the previous line has no closing
+    /// punctuation and this one starts in lowercase. Continuation chunks
+    /// repeat the element's first line so they stay identifiable.
     fn split_oversized_element(
         &self,
         element: &ClassifiedElement,
@@ -929,47 +971,51 @@ impl TTRPGChunker {
         hierarchy: &SectionHierarchy,
         chunk_index: &mut usize,
     ) -> Vec<ContentChunk> {
-        let mut chunks = Vec::new();
-        let sentences = self.base_chunker.split_into_sentences(&element.content);
-        let mut current = String::new();
-
-        for sentence in sentences {
-            if current.len() + sentence.len() > self.config.base.max_size
-                && current.len() >= self.config.base.min_size {
-                    chunks.push(self.create_chunk_with_hierarchy(
-                        &current,
-                        source_id,
-                        Some(element.page_number),
-                        hierarchy,
-                        element.element_type.as_str(),
-                        chunk_index,
-                    ));
-                    current = self.get_overlap(&current);
+        let mut entries: Vec<String> = Vec::new();
+        for line in element.content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let continues = entries.last().is_some_and(|prev| {
+                !prev.ends_with(['.', ':', ';', '!', '?', ')'])
+                    && line.starts_with(|c: char| c.is_lowercase())
+            });
+            match entries.last_mut() {
+                Some(prev) if continues => {
+                    prev.push(' ');
+                    prev.push_str(line);
                 }
-
-            if !current.is_empty() && !current.ends_with(' ') {
-                current.push(' ');
+                _ => entries.push(line.to_string()),
             }
-            current.push_str(&sentence);
+        }
 
-            if current.len() >= self.config.base.target_size {
+        let title = entries.first().cloned().unwrap_or_default();
+        let mut chunks = Vec::new();
+        let mut current = String::new();
+
+        for entry in entries {
+            if !current.is_empty() && current.len() + entry.len() + 1 > self.config.base.max_size {
                 chunks.push(self.create_chunk_with_hierarchy(
                     &current,
                     source_id,
                     Some(element.page_number),
+                    Some((element.page_number, element.page_number)),
                     hierarchy,
                     element.element_type.as_str(),
                     chunk_index,
                 ));
-                current = self.get_overlap(&current);
+                current = format!("{title} (continued)");
             }
+
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(&entry);
         }
 
-        if !current.is_empty() && current.len() >= self.config.base.min_size {
+        if !current.is_empty() {
             chunks.push(self.create_chunk_with_hierarchy(
                 &current,
                 source_id,
                 Some(element.page_number),
+                Some((element.page_number, element.page_number)),
                 hierarchy,
                 element.element_type.as_str(),
                 chunk_index,
@@ -991,6 +1037,12 @@ impl Default for TTRPGChunker {
     }
 }
 
+/// Page range of the overlap a flushed buffer is reset to: its tail comes
+/// from the last page the flushed text reached.
+fn overlap_range(range: Option<(u32, u32)>) -> Option<(u32, u32)> {
+    range.map(|(_, last)| (last, last))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1208,6 +1260,124 @@ mod tests {
         assert_eq!(TTRPGChunker::detect_header_level("Appendix B"), 2);
         assert_eq!(TTRPGChunker::detect_header_level("MONSTERS"), 2);
         assert_eq!(TTRPGChunker::detect_header_level("Regular Header"), 3);
+        assert_eq!(TTRPGChunker::detect_header_level("## Goblins"), 2);
+    }
+
+    #[test]
+    fn test_oversized_stat_block_splits_between_entries() {
+        let config = TTRPGChunkConfig {
+            base: ChunkConfig {
+                target_size: 60,
+                min_size: 10,
+                max_size: 100,
+                overlap_size: 0,
+                ..Default::default()
+            },
+            atomic_max_multiplier: 1.0,
+            ..Default::default()
+        };
+        let chunker = TTRPGChunker::with_config(config);
+
+        let stat_block = "Ogre\nLarge giant, chaotic evil\nArmor Class 11\nHit Points 59 (7d10 + 21)\n\
+            Greatclub. Melee Weapon Attack: +6 to hit, reach 5 ft., one\ntarget. Hit: 13 (2d8 + 4) bludgeoning damage.\n\
+            Javelin. Ranged Weapon Attack: +6 to hit, range 30/120 ft.";
+        let elements = vec![ClassifiedElement::new(
+            TTRPGElementType::StatBlock,
+            0.95,
+            stat_block.to_string(),
+            4,
+        )];
+
+        let chunks = chunker.chunk(&elements, "test");
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.chunk_type == "stat_block"));
+        assert!(chunks[1..].iter().all(|c| c.content.starts_with("Ogre (continued)")));
+
+        // The wrapped Greatclub line stays in one piece
+        let greatclub = chunks.iter().find(|c| c.content.contains("Greatclub")).unwrap();
+        assert!(greatclub.content.contains("one target. Hit: 13"));
+    }
+
+    #[test]
+    fn test_markdown_headers_build_hierarchy() {
+        let chunker = TTRPGChunker::new();
+        let elements = vec![
+            ClassifiedElement::new(
+                TTRPGElementType::SectionHeader,
+                0.9,
+                "# Bestiary".to_string(),
+                1,
+            ),
+            ClassifiedElement::new(
+                TTRPGElementType::SectionHeader,
+                0.9,
+                "## Goblins".to_string(),
+                1,
+            ),
+            ClassifiedElement::new(
+                TTRPGElementType::RandomTable,
+                0.9,
+                "d4 Goblin Mood\n1 Hungry\n2 Bored\n3 Angry\n4 Sleepy".to_string(),
+                1,
+            ),
+        ];
+
+        let chunks = chunker.chunk(&elements, "test");
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].metadata.get("section_path").map(String::as_str),
+            Some("Bestiary > Goblins")
+        );
+        assert_eq!(chunks[0].chapter_title.as_deref(), Some("Bestiary"));
+    }
+
+    #[test]
+    fn test_short_trailing_text_is_kept() {
+        let chunker = TTRPGChunker::new();
+        let elements = vec![
+            ClassifiedElement::new(
+                TTRPGElementType::RandomTable,
+                0.9,
+                "d4 Goblin Mood\n1 Hungry\n2 Bored\n3 Angry\n4 Sleepy".to_string(),
+                1,
+            ),
+            ClassifiedElement::new(
+                TTRPGElementType::GenericText,
+                0.5,
+                "The goblins flee at dawn.".to_string(),
+                2,
+            ),
+        ];
+
+        let chunks = chunker.chunk(&elements, "test");
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].content, "The goblins flee at dawn.");
+        assert_eq!(chunks[1].page_number, Some(2));
+    }
+
+    #[test]
+    fn test_chunks_span_pages_and_record_range() {
+        let chunker = TTRPGChunker::new();
+        let elements = vec![
+            ClassifiedElement::new(
+                TTRPGElementType::GenericText,
+                0.5,
+                "The goblins lair in the old mill, where the".to_string(),
+                1,
+            ),
+            ClassifiedElement::new(
+                TTRPGElementType::GenericText,
+                0.5,
+                "miller's ghost still turns the wheel.".to_string(),
+                2,
+            ),
+        ];
+
+        let chunks = chunker.chunk(&elements, "test");
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].content.contains("the\n\nmiller's ghost"));
+        assert_eq!(chunks[0].page_number, Some(1));
+        assert_eq!(chunks[0].page_range, Some((1, 2)));
     }
 
     // ========================================================================
@@ -0,0 +1,264 @@
+//! Incremental Re-Ingestion
+//!
+//! Helpers for re-ingesting a document whose file changed (for example an
+//! errata printing of a rulebook) without redoing the whole book:
+//!
+//! - **Page diff**: each page's text is hashed, so the pages that changed
+//!   since the last ingestion can be found without comparing text; chunks
+//!   never span pages, so those on unchanged pages are kept as they are
+//! - **Chunk matching**: chunks of changed pages are matched to the stored
+//!   chunks they replace by content hash, so text that survived an edit
+//!   keeps its ID and embedding
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::hash::hash_bytes;
+
+// ============================================================================
+// Hashing
+// ============================================================================
+
+/// Hash text for change detection.
+///
+/// Whitespace is normalized first, so re-extraction that only reflows lines
+/// does not count as a change.
+pub fn text_hash(text: &str) -> String {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    hash_bytes(normalized.as_bytes())
+}
+
+/// Hash every page of a document, in page order.
+pub fn page_hashes(pages: &[(u32, String)]) -> Vec<String> {
+    pages.iter().map(|(_, text)| text_hash(text)).collect()
+}
+
+// ============================================================================
+// Page Diff
+// ============================================================================
+
+/// Pages that differ between two ingestions of a document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageDiff {
+    /// Positions (in the new page order) of pages whose text is new
+    pub changed: Vec<usize>,
+    /// `(old position, new position)` of each page whose text is unchanged
+    pub kept: Vec<(usize, usize)>,
+    /// Number of old pages whose text no longer appears
+    pub removed: usize,
+    /// True when the page hashes are identical, in the same order
+    pub unchanged: bool,
+}
+
+impl PageDiff {
+    /// Compare the page hashes of the previous ingestion with the new ones.
+    ///
+    /// Pages are compared by content rather than position, so an inserted
+    /// page only marks itself as changed, not every page after it. Identical
+    /// pages that appear more than once are paired up in order.
+    pub fn compute(old: &[String], new: &[String]) -> Self {
+        let mut available: HashMap<&str, VecDeque<usize>> = HashMap::new();
+        for (i, hash) in old.iter().enumerate() {
+            available.entry(hash.as_str()).or_default().push_back(i);
+        }
+
+        let mut changed = Vec::new();
+        let mut kept = Vec::new();
+        for (i, hash) in new.iter().enumerate() {
+            match available
+                .get_mut(hash.as_str())
+                .and_then(VecDeque::pop_front)
+            {
+                Some(old_index) => kept.push((old_index, i)),
+                None => changed.push(i),
+            }
+        }
+
+        Self {
+            changed,
+            removed: old.len() - kept.len(),
+            kept,
+            unchanged: old == new,
+        }
+    }
+
+    /// New positions of the old pages `first..=last`, if every one of them
+    /// is unchanged and they still follow each other.
+    ///
+    /// A chunk drawing text from those pages can then be kept as it is;
+    /// otherwise a page it spans changed or a page was inserted inside it.
+    pub fn map_range(&self, first: usize, last: usize) -> Option<(usize, usize)> {
+        let new_position = |old: usize| {
+            self.kept
+                .iter()
+                .find(|&&(kept_old, _)| kept_old == old)
+                .map(|&(_, new)| new)
+        };
+        let start = new_position(first)?;
+        (first..=last)
+            .all(|old| new_position(old) == Some(start + (old - first)))
+            .then_some((start, start + (last - first)))
+    }
+
+    /// Whether any of the new pages `first..=last` changed.
+    pub fn touches_changed(&self, first: usize, last: usize) -> bool {
+        self.changed.iter().any(|i| (first..=last).contains(i))
+    }
+}
+
+// ============================================================================
+// Chunk Matching
+// ============================================================================
+
+/// Result of matching new chunks against the stored ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkMatch {
+    /// For each new chunk, the ID of the stored chunk it reuses
+    pub reused: Vec<Option<String>>,
+    /// IDs of stored chunks with no counterpart in the new document
+    pub removed: Vec<String>,
+}
+
+impl ChunkMatch {
+    /// Number of new chunks that reuse a stored chunk.
+    pub fn reused_count(&self) -> usize {
+        self.reused.iter().filter(|id| id.is_some()).count()
+    }
+}
+
+/// Match new chunks to stored chunks by content hash.
+///
+/// `stored` holds `(chunk ID, content hash)` pairs in stored order; chunks
+/// without a hash are never reused. Identical chunks that appear more than
+/// once are paired up in order, each stored chunk being reused at most once.
+pub fn match_chunks(stored: &[(String, Option<String>)], new_hashes: &[String]) -> ChunkMatch {
+    let mut available: HashMap<&str, VecDeque<&str>> = HashMap::new();
+    for (id, hash) in stored {
+        if let Some(hash) = hash {
+            available
+                .entry(hash.as_str())
+                .or_default()
+                .push_back(id.as_str());
+        }
+    }
+
+    let reused: Vec<Option<String>> = new_hashes
+        .iter()
+        .map(|hash| {
+            available
+                .get_mut(hash.as_str())
+                .and_then(VecDeque::pop_front)
+                .map(str::to_string)
+        })
+        .collect();
+
+    let used: HashSet<&str> = reused.iter().flatten().map(String::as_str).collect();
+    let removed = stored
+        .iter()
+        .filter(|(id, _)| !used.contains(id.as_str()))
+        .map(|(id, _)| id.clone())
+        .collect();
+
+    ChunkMatch { reused, removed }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashes(pages: &[&str]) -> Vec<String> {
+        pages.iter().map(|p| text_hash(p)).collect()
+    }
+
+    #[test]
+    fn test_text_hash_ignores_whitespace() {
+        assert_eq!(
+            text_hash("Fireball\n8d6  fire"),
+            text_hash("Fireball 8d6 fire")
+        );
+        assert_ne!(
+            text_hash("Fireball 8d6 fire"),
+            text_hash("Fireball 6d6 fire")
+        );
+    }
+
+    #[test]
+    fn test_page_diff_unchanged() {
+        let pages = hashes(&["one", "two"]);
+        let diff = PageDiff::compute(&pages, &pages);
+
+        assert!(diff.unchanged);
+        assert!(diff.changed.is_empty());
+        assert_eq!(diff.removed, 0);
+    }
+
+    #[test]
+    fn test_page_diff_errata_and_inserted_page() {
+        let old = hashes(&["one", "two", "three"]);
+        let new = hashes(&["one", "inserted", "two", "three (fixed)"]);
+        let diff = PageDiff::compute(&old, &new);
+
+        assert!(!diff.unchanged);
+        assert_eq!(diff.changed, vec![1, 3]);
+        assert_eq!(diff.kept, vec![(0, 0), (1, 2)]);
+        assert_eq!(diff.removed, 1);
+    }
+
+    #[test]
+    fn test_page_diff_pairs_repeated_pages_once() {
+        let old = hashes(&["blank", "one"]);
+        let new = hashes(&["blank", "one", "blank"]);
+        let diff = PageDiff::compute(&old, &new);
+
+        assert_eq!(diff.kept, vec![(0, 0), (1, 1)]);
+        assert_eq!(diff.changed, vec![2]);
+        assert_eq!(diff.removed, 0);
+    }
+
+    #[test]
+    fn test_page_diff_maps_ranges_of_unchanged_pages() {
+        let old = hashes(&["one", "two", "three", "four"]);
+        let new = hashes(&["inserted", "one", "two", "three (fixed)", "four"]);
+        let diff = PageDiff::compute(&old, &new);
+
+        // Pages one and two moved back together
+        assert_eq!(diff.map_range(0, 1), Some((1, 2)));
+        // A chunk running onto the fixed page is invalidated
+        assert_eq!(diff.map_range(1, 2), None);
+        assert_eq!(diff.map_range(3, 3), Some((4, 4)));
+
+        assert!(diff.touches_changed(2, 3));
+        assert!(!diff.touches_changed(1, 2));
+    }
+
+    #[test]
+    fn test_match_chunks_reuses_by_hash() {
+        let stored = vec![
+            ("a".to_string(), Some("h1".to_string())),
+            ("b".to_string(), Some("h2".to_string())),
+            ("c".to_string(), None),
+        ];
+        let new = vec!["h2".to_string(), "h3".to_string(), "h1".to_string()];
+
+        let matched = match_chunks(&stored, &new);
+        assert_eq!(
+            matched.reused,
+            vec![Some("b".to_string()), None, Some("a".to_string())]
+        );
+        assert_eq!(matched.removed, vec!["c".to_string()]);
+        assert_eq!(matched.reused_count(), 2);
+    }
+
+    #[test]
+    fn test_match_chunks_duplicates_used_once() {
+        let stored = vec![("a".to_string(), Some("h".to_string()))];
+        let new = vec!["h".to_string(), "h".to_string()];
+
+        let matched = match_chunks(&stored, &new);
+        assert_eq!(matched.reused, vec![Some("a".to_string()), None]);
+        assert!(matched.removed.is_empty());
+    }
+}
@@ -10,8 +10,11 @@ pub mod character_gen;
 pub mod rulebook_linker;
 pub mod chunker;
 pub mod hash;
+pub mod incremental;
 pub mod layout;
 pub mod ttrpg;
+pub mod ttrpg_pipeline;
+pub mod watch;
 
 // Pipeline-specific models and utilities (extracted from core/meilisearch_pipeline.rs)
 pub mod pipeline_models;
@@ -43,6 +46,7 @@ pub use chunker::{
     TTRPGChunker, TTRPGChunkConfig, SectionHierarchy,
 };
 pub use hash::{hash_file, hash_bytes, hash_file_with_size, get_file_size};
+pub use incremental::{text_hash, page_hashes, match_chunks, ChunkMatch, PageDiff};
 pub use layout::{
     ColumnDetector, ColumnBoundary, TextBlock,
     RegionDetector, DetectedRegion, RegionType, RegionBounds,
@@ -56,6 +60,8 @@ pub use ttrpg::{
     GameVocabulary, DnD5eVocabulary, Pf2eVocabulary,
     detect_game_system, detect_game_system_with_confidence, GameSystem, DetectionResult,
 };
+pub use ttrpg_pipeline::{TTRPGPipeline, TTRPGChunk, ProcessedDocument};
+pub use watch::{FolderConfig, FolderScanner, IngestedFile, WatchedFile, FOLDER_CONFIG_FILE};
 
 // Pipeline models and utilities (extracted from core/meilisearch_pipeline.rs)
 pub use pipeline_models::{
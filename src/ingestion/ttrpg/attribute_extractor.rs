@@ -92,6 +92,9 @@ pub struct TTRPGAttributes {
     pub spell_schools: Vec<AttributeMatch>,
     /// Challenge Rating or Level
     pub cr_level: Option<AttributeMatch>,
+    /// Spell level (0 for cantrips)
+    #[serde(default)]
+    pub spell_level: Option<AttributeMatch>,
     /// Named entities (spell names, creature names, etc.)
     pub named_entities: Vec<AttributeMatch>,
 }
@@ -125,6 +128,7 @@ impl TTRPGAttributes {
             && self.sizes.is_empty()
             && self.spell_schools.is_empty()
             && self.cr_level.is_none()
+            && self.spell_level.is_none()
             && self.named_entities.is_empty()
     }
 
@@ -138,7 +142,13 @@ impl TTRPGAttributes {
             rarities: self.rarities.iter().map(|m| m.value.clone()).collect(),
             sizes: self.sizes.iter().map(|m| m.value.clone()).collect(),
             spell_schools: self.spell_schools.iter().map(|m| m.value.clone()).collect(),
-            challenge_rating: self.cr_level.as_ref().and_then(|m| m.value.parse().ok()),
+            // A bare "level N" fallback is not a challenge rating
+            challenge_rating: self
+                .cr_level
+                .as_ref()
+                .filter(|m| m.source == AttributeSource::ExactMatch)
+                .and_then(|m| parse_challenge_rating(&m.value)),
+            spell_level: self.spell_level.as_ref().and_then(|m| m.value.parse().ok()),
         }
     }
 }
@@ -154,6 +164,20 @@ pub struct FilterableFields {
     pub sizes: Vec<String>,
     pub spell_schools: Vec<String>,
     pub challenge_rating: Option<f32>,
+    #[serde(default)]
+    pub spell_level: Option<u8>,
+}
+
+/// Parse a challenge rating such as "5" or "1/4".
+fn parse_challenge_rating(value: &str) -> Option<f32> {
+    match value.split_once('/') {
+        Some((num, denom)) => {
+            let num: f32 = num.trim().parse().ok()?;
+            let denom: f32 = denom.trim().parse().ok()?;
+            (denom > 0.0).then(|| num / denom)
+        }
+        None => value.trim().parse().ok(),
+    }
 }
 
 // ============================================================================
@@ -165,6 +189,7 @@ pub struct AttributeExtractor {
     vocabulary: Box<dyn GameVocabulary>,
     cr_pattern: Regex,
     level_pattern: Regex,
+    spell_level_pattern: Regex,
 }
 
 impl Default for AttributeExtractor {
@@ -185,6 +210,10 @@ impl AttributeExtractor {
             vocabulary,
             cr_pattern: Regex::new(r"(?i)\bchallenge\s+(?:rating\s+)?(\d+(?:/\d+)?)\b").unwrap(),
             level_pattern: Regex::new(r"(?i)\blevel\s+(\d+)\b").unwrap(),
+            spell_level_pattern: Regex::new(
+                r"(?i)\b(?:(\d)(?:st|nd|rd|th)[- ]level\s+(?:abjuration|conjuration|divination|enchantment|evocation|illusion|necromancy|transmutation)|(?:abjuration|conjuration|divination|enchantment|evocation|illusion|necromancy|transmutation)\s+(cantrip))\b",
+            )
+            .unwrap(),
         }
     }
 
@@ -202,6 +231,7 @@ impl AttributeExtractor {
         attrs.sizes = self.extract_from_list(&text_lower, self.vocabulary.sizes());
         attrs.spell_schools = self.extract_from_list(&text_lower, self.vocabulary.spell_schools());
         attrs.cr_level = self.extract_cr_level(&text_lower);
+        attrs.spell_level = self.extract_spell_level(&text_lower);
 
         attrs
     }
@@ -282,6 +312,17 @@ impl AttributeExtractor {
         None
     }
 
+    /// Extract a spell level from a "3rd-level evocation" or "illusion
+    /// cantrip" line.
+    fn extract_spell_level(&self, text: &str) -> Option<AttributeMatch> {
+        let caps = self.spell_level_pattern.captures(text)?;
+        let level = match caps.get(1) {
+            Some(level) => level.as_str().to_string(),
+            None => "0".to_string(),
+        };
+        Some(AttributeMatch::pattern(level, 0.9))
+    }
+
     /// Get antonyms for extracted attributes.
     pub fn get_antonyms(&self, attrs: &TTRPGAttributes) -> HashMap<String, Vec<String>> {
         let mut antonyms = HashMap::new();
@@ -412,5 +453,24 @@ mod tests {
         assert!(fields.creature_types.contains(&"undead".to_string()));
         assert!(fields.sizes.contains(&"small".to_string()));
         assert!(fields.damage_types.contains(&"fire".to_string()));
+        assert_eq!(fields.challenge_rating, Some(3.0));
+    }
+
+    #[test]
+    fn test_filterable_fields_fractional_cr_and_spell_level() {
+        let extractor = AttributeExtractor::new();
+
+        let fields = extractor.extract("Goblin\nChallenge 1/4 (50 XP)").to_filterable_fields();
+        assert_eq!(fields.challenge_rating, Some(0.25));
+
+        // "level 5" alone is not a challenge rating
+        let fields = extractor.extract("When you reach level 5, choose a path.").to_filterable_fields();
+        assert_eq!(fields.challenge_rating, None);
+
+        let fields = extractor.extract("Fireball\n3rd-level evocation").to_filterable_fields();
+        assert_eq!(fields.spell_level, Some(3));
+
+        let fields = extractor.extract("Light\nEvocation cantrip").to_filterable_fields();
+        assert_eq!(fields.spell_level, Some(0));
     }
 }
@@ -174,8 +174,10 @@ impl TTRPGClassifier {
                 spell_pattern: Regex::new(
                     r"(?i)^(\w[\w\s]+)\n((\d+)(?:st|nd|rd|th)[- ]level|cantrip)\s+(abjuration|conjuration|divination|enchantment|evocation|illusion|necromancy|transmutation)"
                 ).unwrap(),
+                // The "Wondrous item, rare" line under an item's name; a bare
+                // "ring" or "armor" in running text is not enough
                 item_pattern: Regex::new(
-                    r"(?i)(wondrous item|armor|weapon|ring|rod|staff|wand|potion|scroll),?\s*(common|uncommon|rare|very rare|legendary|artifact)?"
+                    r"(?im)^(wondrous item|armor|weapon|ring|rod|staff|wand|potion|scroll)\b[^,\n]{0,40},\s*(common|uncommon|rare|very rare|legendary|artifact)\b"
                 ).unwrap(),
                 class_feature_pattern: Regex::new(
                     r"(?i)^(starting at|at \d+(st|nd|rd|th) level|beginning at|when you reach)"
@@ -538,6 +540,20 @@ mod tests {
         assert_eq!(result.element_type, TTRPGElementType::GenericText);
     }
 
+    #[test]
+    fn test_item_classification() {
+        let classifier = TTRPGClassifier::new();
+
+        let item = "Bag of Holding\nWondrous item, uncommon\nThis bag has an interior space considerably larger than its outside dimensions.";
+        let result = classifier.classify(item, 1);
+        assert_eq!(result.element_type, TTRPGElementType::ItemDescription);
+
+        // Item words in running text are not an item description
+        let text = "During the journey the party must bring armor and a spare weapon for every member.";
+        let result = classifier.classify(text, 1);
+        assert_eq!(result.element_type, TTRPGElementType::GenericText);
+    }
+
     #[test]
     fn test_element_type_is_atomic() {
         assert!(TTRPGElementType::StatBlock.is_atomic());
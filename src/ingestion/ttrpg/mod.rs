@@ -38,7 +38,9 @@ pub mod dice_extractor;
 
 pub use classifier::{TTRPGClassifier, TTRPGElementType, ClassifiedElement};
 pub use content_mode::{ContentMode, ContentModeClassifier, ContentModeResult};
-pub use stat_block::{StatBlockParser, StatBlockData, AbilityScores, Feature, Speed};
+pub use stat_block::{
+    StatBlockParser, StatBlockData, AbilityScores, Feature, Speed, format_challenge_rating,
+};
 pub use random_table::{RandomTableParser, RandomTableData, TableEntry};
 pub use attribute_extractor::{
     AttributeExtractor, TTRPGAttributes, AttributeMatch, AttributeSource,
@@ -3,6 +3,8 @@
 //! Parses random/roll tables with dice notation and probability distributions.
 //! Handles various formats including d4, d6, d8, d10, d12, d20, d100, and 2d6 tables.
 
+use std::ops::Range;
+
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
@@ -88,6 +90,8 @@ pub struct RandomTableParser {
     single_pattern: Regex,
     /// Pattern for d100 percentile ranges
     percentile_pattern: Regex,
+    /// Pattern for lines that begin with dice notation (table headers, not rows)
+    leading_dice_pattern: Regex,
 }
 
 impl Default for RandomTableParser {
@@ -104,6 +108,7 @@ impl RandomTableParser {
             range_pattern: Regex::new(r"^(\d+)[–\-−](\d+)\s*[:\|]?\s*(.+)").unwrap(),
             single_pattern: Regex::new(r"^(\d+)\s*[:\|]?\s*(.+)").unwrap(),
             percentile_pattern: Regex::new(r"^(0?\d{1,2})[–\-−](0?\d{1,2})\s*[:\|]?\s*(.+)").unwrap(),
+            leading_dice_pattern: Regex::new(r"(?i)^\d*d(?:\d+\b|%)").unwrap(),
         }
     }
 
@@ -129,6 +134,18 @@ impl RandomTableParser {
         // Parse entries
         table.entries = self.parse_entries(text);
 
+        // Percentile tables print 100 as "00"
+        if table.total_outcomes == 100 {
+            for entry in &mut table.entries {
+                if entry.roll_max == 0 {
+                    entry.roll_max = 100;
+                }
+                if entry.roll_min == 0 {
+                    entry.roll_min = 100;
+                }
+            }
+        }
+
         // Calculate probabilities
         let total_outcomes = table.total_outcomes;
         for entry in &mut table.entries {
@@ -251,6 +268,86 @@ impl RandomTableParser {
         entries
     }
 
+    /// Find every random table in a page of running text.
+    ///
+    /// A table starts at a line mentioning dice notation (e.g. `d8 Encounter`
+    /// or `Wandering Monsters (d6)`) followed by at least two roll rows. A short
+    /// line directly above the header is kept as a title candidate.
+    pub fn find_tables(&self, text: &str) -> Vec<RandomTableData> {
+        self.find_table_spans(text)
+            .into_iter()
+            .map(|(_, table)| table)
+            .collect()
+    }
+
+    /// Like [`find_tables`](Self::find_tables), but also returns the range of
+    /// lines (in `text.lines()`) each table occupies, title line included.
+    pub fn find_table_spans(&self, text: &str) -> Vec<(Range<usize>, RandomTableData)> {
+        let lines: Vec<&str> = text.lines().map(str::trim).collect();
+        let mut tables = Vec::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+            if !self.is_header_line(line) {
+                i += 1;
+                continue;
+            }
+
+            let mut start = i;
+            let mut chunk: Vec<&str> = Vec::new();
+            if let Some(prev) = i.checked_sub(1).map(|p| lines[p]) {
+                if !prev.is_empty()
+                    && prev.len() < 60
+                    && !prev.ends_with('.')
+                    && !self.is_row_line(prev)
+                {
+                    chunk.push(prev);
+                    start = i - 1;
+                }
+            }
+            chunk.push(line);
+
+            let mut end = i + 1;
+            let mut last_row = i;
+            let mut rows = 0;
+            while end < lines.len() {
+                let next = lines[end];
+                if next.is_empty() {
+                    end += 1;
+                    continue;
+                }
+                if !self.is_row_line(next) {
+                    break;
+                }
+                chunk.push(next);
+                rows += 1;
+                last_row = end;
+                end += 1;
+            }
+
+            if rows >= 2 {
+                if let Some(table) = self.parse(&chunk.join("\n")) {
+                    tables.push((start..last_row + 1, table));
+                }
+                i = end;
+            } else {
+                i += 1;
+            }
+        }
+
+        tables
+    }
+
+    fn is_header_line(&self, line: &str) -> bool {
+        line.len() < 80 && self.dice_pattern.is_match(line) && !self.is_row_line(line)
+    }
+
+    fn is_row_line(&self, line: &str) -> bool {
+        !self.leading_dice_pattern.is_match(line)
+            && (self.range_pattern.is_match(line) || self.single_pattern.is_match(line))
+    }
+
     /// Check if text likely contains a random table.
     pub fn is_random_table(&self, text: &str) -> bool {
         // Must have dice notation
@@ -324,6 +421,55 @@ mod tests {
         assert_eq!(table.entries[3].roll_max, 5);
     }
 
+    #[test]
+    fn test_find_tables_in_page() {
+        let parser = RandomTableParser::new();
+
+        let page = r#"
+            The forest road is rarely quiet. Roll once per watch.
+            Forest Encounters
+            d6 Encounter
+            1 Wolves
+            2-3 Bandits
+            4-5 Traveling merchant
+            6 Owlbear
+            Treasure is described in chapter 7.
+            d100 Trinket
+            01-50 A tin whistle
+            51-00 A glass eye
+        "#;
+
+        let tables = parser.find_tables(page);
+        assert_eq!(tables.len(), 2);
+
+        assert_eq!(tables[0].title.as_deref(), Some("Forest Encounters"));
+        assert_eq!(tables[0].dice_notation, "d6");
+        assert_eq!(tables[0].entries.len(), 4);
+        assert_eq!(tables[0].entries[3].result, "Owlbear");
+
+        assert_eq!(tables[1].dice_notation, "d100");
+        assert_eq!(tables[1].entries[1].roll_min, 51);
+        assert_eq!(tables[1].entries[1].roll_max, 100);
+    }
+
+    #[test]
+    fn test_find_table_spans_cover_title_and_rows() {
+        let parser = RandomTableParser::new();
+        let page = "Intro text.\nForest Encounters\nd6 Encounter\n1-3 Wolves\n4-6 Bandits\nAfter the table.";
+
+        let spans = parser.find_table_spans(page);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].0, 1..5);
+        assert_eq!(spans[0].1.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_find_tables_ignores_prose() {
+        let parser = RandomTableParser::new();
+        let page = "Roll a d20 and add your modifier.\nOn a 15 or higher you succeed.";
+        assert!(parser.find_tables(page).is_empty());
+    }
+
     #[test]
     fn test_table_entry_matches_roll() {
         let entry = TableEntry::new(4, 6, "Result".to_string());
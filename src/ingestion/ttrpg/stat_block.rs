@@ -6,6 +6,7 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::Range;
 
 // ============================================================================
 // Types
@@ -129,6 +130,23 @@ pub struct ChallengeRating {
     pub xp: Option<i32>,
 }
 
+impl ChallengeRating {
+    /// Format the rating the way books print it ("1/8", "1/4", "1/2", "5").
+    pub fn label(&self) -> String {
+        format_challenge_rating(self.value)
+    }
+}
+
+/// Format a numeric challenge rating using the fractional forms for values below 1.
+pub fn format_challenge_rating(value: f32) -> String {
+    if value > 0.0 && value < 1.0 {
+        let denom = (1.0 / value).round() as i32;
+        format!("1/{denom}")
+    } else {
+        format!("{}", value.round() as i32)
+    }
+}
+
 /// A creature feature (trait, action, etc.).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Feature {
@@ -165,12 +183,14 @@ impl Feature {
 pub struct StatBlockParser {
     // Compiled regex patterns
     size_type_alignment: Regex,
+    size_line: Regex,
     armor_class: Regex,
     hit_points: Regex,
     speed: Regex,
     ability_scores: Regex,
     challenge_rating: Regex,
     feature: Regex,
+    block_line: Regex,
 }
 
 impl Default for StatBlockParser {
@@ -186,6 +206,9 @@ impl StatBlockParser {
             size_type_alignment: Regex::new(
                 r"(?i)(tiny|small|medium|large|huge|gargantuan)\s+(\w+(?:\s+\([^)]+\))?),?\s*([\w\s]+)?"
             ).unwrap(),
+            size_line: Regex::new(
+                r"(?i)^(tiny|small|medium|large|huge|gargantuan)\b"
+            ).unwrap(),
             armor_class: Regex::new(
                 r"(?i)armor\s+class\s+(\d+)\s*(?:\(([^)]+)\))?"
             ).unwrap(),
@@ -205,6 +228,10 @@ impl StatBlockParser {
             feature: Regex::new(
                 r"^([A-Z][A-Za-z\s'-]+)\.\s*(.+)"
             ).unwrap(),
+            // Statistic lines, section headings and named trait/action entries
+            block_line: Regex::new(
+                r"^(?i:armor class|hit points|speed|(str|dex|con|int|wis|cha)\b|saving throws|skills|damage (vulnerabilities|resistances|immunities)|condition immunities|senses|languages|challenge|proficiency bonus|((bonus|legendary|lair|mythic) )?actions$|reactions$)|^[A-Z][A-Za-z' -]{0,40}(\s*\([^)]*\))?\.\s"
+            ).unwrap(),
         }
     }
 
@@ -330,6 +357,131 @@ impl StatBlockParser {
         Ok(data)
     }
 
+    /// Locate individual stat blocks within a larger body of text (e.g. a page).
+    ///
+    /// Each block is anchored on its "Armor Class" line and starts at the name
+    /// line above the size/type/alignment line. A block runs until the next
+    /// block's name line. Candidates without a "Hit Points" line are skipped.
+    pub fn find_blocks(&self, text: &str) -> Vec<String> {
+        let lines: Vec<&str> = text.lines().map(|l| l.trim()).collect();
+        let starts = self.block_starts(&lines);
+
+        let mut blocks = Vec::new();
+        for (n, &start) in starts.iter().enumerate() {
+            let end = starts
+                .iter()
+                .skip(n + 1)
+                .copied()
+                .find(|&s| s > start)
+                .unwrap_or(lines.len());
+            let block = lines[start..end].join("\n");
+            if block.to_lowercase().contains("hit points") {
+                blocks.push(block);
+            }
+        }
+
+        blocks
+    }
+
+    /// Locate the stat blocks of a document given as `(page_number, text)`
+    /// pairs, each with the page its name line is on.
+    ///
+    /// The pages are scanned as one text with a blank line between them, so a
+    /// block that continues on the next page comes out whole. Blocks end where
+    /// the running text resumes, as in [`find_block_spans`](Self::find_block_spans).
+    pub fn find_blocks_in_pages(&self, pages: &[(u32, String)]) -> Vec<(u32, String)> {
+        let mut lines = Vec::new();
+        let mut line_pages = Vec::new();
+        for (page, text) in pages {
+            if !lines.is_empty() {
+                lines.push("");
+                line_pages.push(*page);
+            }
+            for line in text.lines() {
+                lines.push(line.trim());
+                line_pages.push(*page);
+            }
+        }
+
+        self.find_block_spans(&lines.join("\n"))
+            .into_iter()
+            .map(|range| (line_pages[range.start], lines[range].join("\n")))
+            .collect()
+    }
+
+    /// Line ranges (in `text.lines()`) of the stat blocks in `text`.
+    ///
+    /// Unlike [`find_blocks`](Self::find_blocks), a block also ends where the
+    /// running text resumes: at a blank line followed by a line that is neither
+    /// a statistic, a section heading nor a named trait or action.
+    pub fn find_block_spans(&self, text: &str) -> Vec<Range<usize>> {
+        let lines: Vec<&str> = text.lines().map(|l| l.trim()).collect();
+        let starts = self.block_starts(&lines);
+
+        let mut spans = Vec::new();
+        for (n, &start) in starts.iter().enumerate() {
+            let limit = starts
+                .iter()
+                .skip(n + 1)
+                .copied()
+                .find(|&s| s > start)
+                .unwrap_or(lines.len());
+
+            let mut end = start + 1;
+            let mut after_blank = false;
+            let mut after_heading = false;
+            for (i, line) in lines.iter().enumerate().take(limit).skip(start + 1) {
+                if line.is_empty() {
+                    after_blank = true;
+                    continue;
+                }
+                let is_block_line = self.block_line.is_match(line);
+                if after_blank && !is_block_line && !after_heading {
+                    break;
+                }
+                after_blank = false;
+                after_heading = is_block_line
+                    && !line.contains('.')
+                    && line.to_lowercase().ends_with("actions");
+                end = i + 1;
+            }
+
+            let has_hit_points = lines[start..end]
+                .iter()
+                .any(|l| l.to_lowercase().starts_with("hit points"));
+            if has_hit_points {
+                spans.push(start..end);
+            }
+        }
+
+        spans
+    }
+
+    /// Index of the name line of each stat block, in order.
+    fn block_starts(&self, lines: &[&str]) -> Vec<usize> {
+        let anchors: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| {
+                line.to_lowercase().starts_with("armor class") && self.armor_class.is_match(line)
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        let previous_non_empty = |from: usize| (0..from).rev().find(|&j| !lines[j].is_empty());
+
+        anchors
+            .iter()
+            .filter_map(|&anchor| {
+                // The size line sits directly above AC (allowing for a blank line)
+                let size_idx = (anchor.saturating_sub(3)..anchor)
+                    .rev()
+                    .find(|&j| self.size_line.is_match(lines[j]));
+                previous_non_empty(size_idx.unwrap_or(anchor))
+            })
+            .collect()
+    }
+
     fn parse_speed(&self, text: &str) -> Speed {
         let mut speed = Speed::default();
         let number = Regex::new(r"(\d+)").unwrap();
@@ -539,6 +691,63 @@ mod tests {
         assert!((data.challenge_rating.as_ref().unwrap().value - 0.25).abs() < 0.01);
     }
 
+    #[test]
+    fn test_find_blocks_splits_page() {
+        let parser = StatBlockParser::new();
+        let page = r#"
+            The goblins lurk in the ruins, led by their boss.
+
+            Goblin
+            Small humanoid (goblinoid), neutral evil
+            Armor Class 15 (leather armor, shield)
+            Hit Points 7 (2d6)
+            Speed 30 ft.
+            Challenge 1/4 (50 XP)
+
+            Goblin Boss
+            Small humanoid (goblinoid), neutral evil
+            Armor Class 17 (chain shirt, shield)
+            Hit Points 21 (6d6)
+            Speed 30 ft.
+            Challenge 1 (200 XP)
+        "#;
+
+        let blocks = parser.find_blocks(page);
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0].starts_with("Goblin\n"));
+        assert!(!blocks[0].contains("Goblin Boss"));
+        assert!(blocks[1].starts_with("Goblin Boss"));
+
+        let boss = parser.parse(&blocks[1]).unwrap();
+        assert_eq!(boss.name, "Goblin Boss");
+        assert_eq!(boss.hit_points.map(|hp| hp.average), Some(21));
+    }
+
+    #[test]
+    fn test_find_block_spans_stop_at_running_text() {
+        let parser = StatBlockParser::new();
+        let page = "Goblin\nSmall humanoid (goblinoid), neutral evil\nArmor Class 15\nHit Points 7 (2d6)\nChallenge 1/4 (50 XP)\n\nNimble Escape. The goblin can take the Disengage action.\n\nActions\n\nScimitar. Melee Weapon Attack: +4 to hit.\n\nThe goblins flee when their boss falls.";
+
+        let spans = parser.find_block_spans(page);
+        assert_eq!(spans, vec![0..11]);
+    }
+
+    #[test]
+    fn test_find_blocks_ignores_prose() {
+        let parser = StatBlockParser::new();
+        let text = "Your Armor Class 12 is low.\nHit points are tracked per character.";
+        assert!(parser.find_blocks(text).is_empty());
+    }
+
+    #[test]
+    fn test_challenge_rating_label() {
+        assert_eq!(format_challenge_rating(0.125), "1/8");
+        assert_eq!(format_challenge_rating(0.25), "1/4");
+        assert_eq!(format_challenge_rating(0.5), "1/2");
+        assert_eq!(format_challenge_rating(0.0), "0");
+        assert_eq!(format_challenge_rating(13.0), "13");
+    }
+
     #[test]
     fn test_parse_speed() {
         let parser = StatBlockParser::new();
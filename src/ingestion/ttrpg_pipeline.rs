@@ -0,0 +1,614 @@
+//! TTRPG-Aware Chunking Pipeline
+//!
+//! Turns extracted pages into search-ready chunks using the TTRPG tooling in
+//! this module tree instead of plain semantic chunking:
+//!
+//! - **Column reflow**: two-column pages are put back into reading order with
+//!   the `ColumnDetector`
+//! - **Segmentation**: stat blocks and random tables are cut out whole, even
+//!   when they run onto the next page, and the remaining text is split into
+//!   headings and paragraphs
+//! - **Classification**: paragraphs are typed by the `TTRPGClassifier`
+//! - **Chunking**: the `TTRPGChunker` keeps stat blocks and tables atomic and
+//!   tracks the section hierarchy
+//! - **Attributes**: each chunk is tagged with the document's game system and
+//!   the filterable attributes found by the `AttributeExtractor`
+//!
+//! # Example
+//!
+//! ```ignore
+//! use crate::ingestion::ttrpg_pipeline::TTRPGPipeline;
+//!
+//! let document = TTRPGPipeline::new().process(&pages, "monster-manual");
+//! for chunk in &document.chunks {
+//!     println!("{} {:?}", chunk.chunk.chunk_type, chunk.attributes.challenge_rating);
+//! }
+//! ```
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use regex::Regex;
+
+use crate::ingestion::chunker::{ContentChunk, TTRPGChunkConfig, TTRPGChunker};
+use crate::ingestion::layout::{ColumnDetector, TextBlock};
+use crate::ingestion::ttrpg::{
+    detect_game_system, AttributeExtractor, ClassifiedElement, FilterableFields, GameSystem,
+    Pf2eVocabulary, RandomTableParser, StatBlockParser, TTRPGClassifier, TTRPGElementType,
+};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Width of one character when plain text is treated as positioned blocks
+const CHAR_WIDTH: f32 = 6.0;
+
+/// Height of one line when plain text is treated as positioned blocks
+const LINE_HEIGHT: f32 = 12.0;
+
+/// Minimum distance between column starts, in characters
+const MIN_COLUMN_GAP_CHARS: f32 = 10.0;
+
+/// Share of lines that must have text on both sides of the gutter before a
+/// page is reflowed (a lone table does not make a two-column layout)
+const MIN_TWO_COLUMN_SHARE: f32 = 0.5;
+
+/// Pages with fewer non-empty lines are never reflowed
+const MIN_COLUMN_LINES: usize = 6;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A chunk with the TTRPG metadata used for filtering.
+#[derive(Debug, Clone)]
+pub struct TTRPGChunk {
+    /// The chunk; `chunk_type` holds the element type (stat_block, spell, ...)
+    pub chunk: ContentChunk,
+    /// Full section path, e.g. "Chapter 3 > Combat > Actions"
+    pub section_path: Option<String>,
+    /// Damage types, creature types, challenge rating, spell level, ...
+    pub attributes: FilterableFields,
+}
+
+/// Result of running the pipeline over a document.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessedDocument {
+    /// Game system detected from the whole document
+    pub game_system: Option<GameSystem>,
+    /// Chunks in document order
+    pub chunks: Vec<TTRPGChunk>,
+}
+
+// ============================================================================
+// Pipeline
+// ============================================================================
+
+/// Classifies, chunks and tags extracted document text.
+pub struct TTRPGPipeline {
+    classifier: TTRPGClassifier,
+    chunker: TTRPGChunker,
+    columns: ColumnDetector,
+    stat_blocks: StatBlockParser,
+    tables: RandomTableParser,
+    /// Runs of spaces (or tabs) wide enough to separate two columns
+    gutter: Regex,
+    /// Paragraphs longer than this are split before classification
+    max_paragraph: usize,
+}
+
+impl Default for TTRPGPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TTRPGPipeline {
+    /// Create a pipeline with the default chunk configuration.
+    pub fn new() -> Self {
+        Self::with_config(TTRPGChunkConfig::default())
+    }
+
+    /// Create a pipeline with a custom chunk configuration.
+    pub fn with_config(config: TTRPGChunkConfig) -> Self {
+        let max_paragraph = config.base.max_size;
+        Self {
+            classifier: TTRPGClassifier::new(),
+            chunker: TTRPGChunker::with_config(config),
+            columns: ColumnDetector::with_thresholds(MIN_COLUMN_GAP_CHARS * CHAR_WIDTH, 0.0),
+            stat_blocks: StatBlockParser::new(),
+            tables: RandomTableParser::new(),
+            gutter: Regex::new(r" {3,}|\t+").unwrap(),
+            max_paragraph,
+        }
+    }
+
+    /// Process a paginated document given as `(page_number, text)` pairs.
+    pub fn process(&self, pages: &[(u32, String)], source_id: &str) -> ProcessedDocument {
+        let pages: Vec<(u32, String)> = pages
+            .iter()
+            .map(|(page, text)| (*page, self.reflow_columns(text)))
+            .collect();
+
+        let full_text = pages
+            .iter()
+            .map(|(_, text)| text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let game_system = detect_game_system(&full_text).filter(|s| *s != GameSystem::Other);
+
+        let elements = self.segment_pages(&pages);
+
+        let extractor = match game_system {
+            Some(GameSystem::Pathfinder2e) => {
+                AttributeExtractor::with_vocabulary(Box::new(Pf2eVocabulary))
+            }
+            _ => AttributeExtractor::new(),
+        };
+
+        let chunks = self
+            .chunker
+            .chunk(&elements, source_id)
+            .into_iter()
+            .map(|chunk| {
+                let attributes = extractor.extract(&chunk.content).to_filterable_fields();
+                let section_path = chunk
+                    .metadata
+                    .get("section_path")
+                    .cloned()
+                    .or_else(|| chunk.section.clone());
+                TTRPGChunk {
+                    chunk,
+                    section_path,
+                    attributes,
+                }
+            })
+            .collect();
+
+        ProcessedDocument {
+            game_system,
+            chunks,
+        }
+    }
+
+    /// Process a document without page information; chunks carry no page
+    /// number.
+    pub fn process_text(&self, text: &str, source_id: &str) -> ProcessedDocument {
+        let mut document = self.process(&[(0, text.to_string())], source_id);
+        for chunk in &mut document.chunks {
+            chunk.chunk.page_number = None;
+        }
+        document
+    }
+
+    /// Split one page into classified elements in reading order.
+    ///
+    /// Stat blocks and random tables are located first so they come out as
+    /// single elements; the text around them is split into headings and
+    /// paragraphs and classified one paragraph at a time.
+    pub fn segment_page(&self, page_number: u32, text: &str) -> Vec<ClassifiedElement> {
+        let lines: Vec<&str> = text.lines().map(str::trim).collect();
+        self.segment_lines(&lines, &vec![page_number; lines.len()])
+    }
+
+    /// Split a whole document into classified elements in reading order.
+    ///
+    /// The pages are segmented as one text with a blank line between them, so
+    /// a stat block or table that continues on the next page is still found
+    /// whole. Each element takes the page its first line is on.
+    fn segment_pages(&self, pages: &[(u32, String)]) -> Vec<ClassifiedElement> {
+        let mut lines = Vec::new();
+        let mut line_pages = Vec::new();
+        for (page, text) in pages {
+            if !lines.is_empty() {
+                lines.push("");
+                line_pages.push(*page);
+            }
+            for line in text.lines() {
+                lines.push(line.trim());
+                line_pages.push(*page);
+            }
+        }
+        self.segment_lines(&lines, &line_pages)
+    }
+
+    /// Segment trimmed `lines`; `pages` holds the page number of each line.
+    fn segment_lines(&self, lines: &[&str], pages: &[u32]) -> Vec<ClassifiedElement> {
+        let text = lines.join("\n");
+        let mut spans: Vec<(Range<usize>, ClassifiedElement)> = Vec::new();
+
+        for range in self.stat_blocks.find_block_spans(&text) {
+            let content = lines[range.clone()].join("\n");
+            let page_number = pages[range.start];
+            let mut element =
+                ClassifiedElement::new(TTRPGElementType::StatBlock, 1.0, content, page_number);
+            if let Ok(data) = self.stat_blocks.parse(&element.content) {
+                element.structured_data = serde_json::to_value(&data).ok();
+            }
+            spans.push((range, element));
+        }
+
+        for (range, table) in self.tables.find_table_spans(&text) {
+            // Dice in a stat block's actions are not a table
+            if spans
+                .iter()
+                .any(|(r, _)| r.start < range.end && range.start < r.end)
+            {
+                continue;
+            }
+            let content = lines[range.clone()].join("\n");
+            let page_number = pages[range.start];
+            let mut element =
+                ClassifiedElement::new(TTRPGElementType::RandomTable, 1.0, content, page_number);
+            element.structured_data = serde_json::to_value(&table).ok();
+            spans.push((range, element));
+        }
+
+        spans.sort_by_key(|(range, _)| range.start);
+
+        let mut elements = Vec::new();
+        let mut cursor = 0;
+        for (range, element) in spans {
+            let prose = cursor..range.start;
+            self.segment_prose(&lines[prose.clone()], &pages[prose], &mut elements);
+            elements.push(element);
+            cursor = range.end;
+        }
+        self.segment_prose(&lines[cursor..], &pages[cursor..], &mut elements);
+
+        elements
+    }
+
+    /// Put a two-column page back into reading order.
+    ///
+    /// Extracted text keeps both columns on one line, separated by a wide run
+    /// of spaces. Each run of text becomes a positioned block for the
+    /// `ColumnDetector`; when it finds exactly two columns that both run down
+    /// most of the page, the left column is emitted before the right one.
+    /// Anything else is returned unchanged.
+    pub fn reflow_columns(&self, text: &str) -> String {
+        let lines: Vec<&str> = text.lines().collect();
+        let non_empty = lines.iter().filter(|l| !l.trim().is_empty()).count();
+        if non_empty < MIN_COLUMN_LINES {
+            return text.to_string();
+        }
+
+        let mut segments: Vec<(usize, usize, &str)> = Vec::new();
+        for (row, line) in lines.iter().enumerate() {
+            for (col, segment) in self.line_segments(line) {
+                segments.push((row, col, segment));
+            }
+        }
+
+        let blocks: Vec<TextBlock> = segments
+            .iter()
+            .map(|&(row, col, segment)| {
+                TextBlock::new(
+                    segment.to_string(),
+                    col as f32 * CHAR_WIDTH,
+                    row as f32 * LINE_HEIGHT,
+                    segment.chars().count() as f32 * CHAR_WIDTH,
+                    LINE_HEIGHT,
+                )
+            })
+            .collect();
+        let page_width =
+            lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as f32 * CHAR_WIDTH;
+
+        let columns = self.columns.detect_column_boundaries(&blocks, page_width);
+        if columns.len() != 2 {
+            return text.to_string();
+        }
+
+        let gutter = columns[1].left;
+        let left_rows: HashSet<usize> = blocks
+            .iter()
+            .zip(&segments)
+            .filter(|(b, _)| b.x < gutter)
+            .map(|(_, s)| s.0)
+            .collect();
+        let two_column_rows = blocks
+            .iter()
+            .zip(&segments)
+            .filter(|(b, s)| b.x >= gutter && left_rows.contains(&s.0))
+            .map(|(_, s)| s.0)
+            .collect::<HashSet<_>>()
+            .len();
+        if (two_column_rows as f32) < non_empty as f32 * MIN_TWO_COLUMN_SHARE {
+            return text.to_string();
+        }
+
+        let mut out = String::new();
+        for column in &columns {
+            let mut last_row: Option<usize> = None;
+            for (block, &(row, _, _)) in blocks.iter().zip(&segments) {
+                if !column.contains_x(block.x) {
+                    continue;
+                }
+                match last_row {
+                    // Keep paragraph breaks within the column
+                    Some(last) if row > last + 1 => out.push_str("\n\n"),
+                    Some(_) => out.push('\n'),
+                    None if !out.is_empty() => out.push_str("\n\n"),
+                    None => {}
+                }
+                out.push_str(&block.text);
+                last_row = Some(row);
+            }
+        }
+
+        out
+    }
+
+    /// Runs of text on one line with their starting character column.
+    fn line_segments<'a>(&self, line: &'a str) -> Vec<(usize, &'a str)> {
+        let mut segments = Vec::new();
+        let mut push = |from: usize, to: usize| {
+            let segment = &line[from..to];
+            let trimmed = segment.trim_start();
+            if !trimmed.is_empty() {
+                let offset = from + (segment.len() - trimmed.len());
+                segments.push((line[..offset].chars().count(), trimmed.trim_end()));
+            }
+        };
+
+        let mut start = 0;
+        for gap in self.gutter.find_iter(line) {
+            push(start, gap.start());
+            start = gap.end();
+        }
+        push(start, line.len());
+
+        segments
+    }
+
+    /// Split running text into heading and paragraph elements; `pages` holds
+    /// the page number of each line.
+    fn segment_prose(&self, lines: &[&str], pages: &[u32], elements: &mut Vec<ClassifiedElement>) {
+        let mut start = 0;
+        while start < lines.len() {
+            let end = lines[start..]
+                .iter()
+                .position(|l| l.is_empty())
+                .map_or(lines.len(), |n| start + n);
+            let paragraph = &lines[start..end];
+            let page_number = pages[start];
+            start = end + 1;
+            if paragraph.is_empty() {
+                continue;
+            }
+
+            // Headings often sit directly above their text
+            let mut rest = paragraph;
+            while let Some((first, tail)) = rest.split_first() {
+                if !self.is_heading(first, page_number) {
+                    break;
+                }
+                elements.push(ClassifiedElement::new(
+                    TTRPGElementType::SectionHeader,
+                    1.0,
+                    first.to_string(),
+                    page_number,
+                ));
+                rest = tail;
+            }
+
+            for piece in self.split_paragraph(rest) {
+                elements.push(self.classifier.classify(&piece, page_number));
+            }
+        }
+    }
+
+    fn is_heading(&self, line: &str, page_number: u32) -> bool {
+        line.starts_with('#')
+            || self.classifier.classify(line, page_number).element_type
+                == TTRPGElementType::SectionHeader
+    }
+
+    /// Join a paragraph's lines, splitting it into pieces no longer than the
+    /// chunker's maximum at line (or, for a single huge line, sentence)
+    /// boundaries.
+    fn split_paragraph(&self, lines: &[&str]) -> Vec<String> {
+        let mut pieces = Vec::new();
+        let mut current = String::new();
+
+        for line in lines {
+            let parts: Vec<&str> = if line.len() > self.max_paragraph {
+                line.split_inclusive(". ").collect()
+            } else {
+                vec![line]
+            };
+
+            for part in parts {
+                if !current.is_empty() && current.len() + part.len() + 1 > self.max_paragraph {
+                    pieces.push(std::mem::take(&mut current));
+                }
+                if !current.is_empty() && !current.ends_with(' ') {
+                    current.push('\n');
+                }
+                current.push_str(part);
+            }
+        }
+
+        if !current.trim().is_empty() {
+            pieces.push(current);
+        }
+
+        pieces
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOBLIN_PAGE: &str = "CHAPTER 2: GOBLINS
+
+Goblins lair in caves and ruins, and raid travellers on the old road.
+
+Goblin
+Small humanoid (goblinoid), neutral evil
+Armor Class 15 (leather armor, shield)
+Hit Points 7 (2d6)
+Speed 30 ft.
+STR 8 (-1) DEX 14 (+2) CON 10 (+0) INT 10 (+0) WIS 8 (-1) CHA 8 (-1)
+Challenge 1/4 (50 XP)
+
+Nimble Escape. The goblin can take the Disengage or Hide action as a bonus action.
+
+Actions
+
+Scimitar. Melee Weapon Attack: +4 to hit, reach 5 ft., one target. Hit: 5 (1d6 + 2) slashing damage.
+
+The goblins flee when their boss falls.
+
+Goblin Loot
+d4 Item
+1 A rusty key
+2 A bag of teeth
+3 3 copper pieces
+4 A stolen boot";
+
+    #[test]
+    fn test_segment_page_keeps_stat_blocks_and_tables_whole() {
+        let pipeline = TTRPGPipeline::new();
+        let elements = pipeline.segment_page(12, GOBLIN_PAGE);
+
+        let types: Vec<TTRPGElementType> = elements.iter().map(|e| e.element_type).collect();
+        assert_eq!(types[0], TTRPGElementType::SectionHeader);
+
+        let stat_blocks: Vec<_> = elements
+            .iter()
+            .filter(|e| e.element_type == TTRPGElementType::StatBlock)
+            .collect();
+        assert_eq!(stat_blocks.len(), 1);
+        assert!(stat_blocks[0].content.starts_with("Goblin\n"));
+        assert!(stat_blocks[0].content.contains("Scimitar."));
+        assert!(!stat_blocks[0].content.contains("flee"));
+        assert!(stat_blocks[0].structured_data.is_some());
+
+        let tables: Vec<_> = elements
+            .iter()
+            .filter(|e| e.element_type == TTRPGElementType::RandomTable)
+            .collect();
+        assert_eq!(tables.len(), 1);
+        assert!(tables[0].content.starts_with("Goblin Loot\nd4 Item"));
+        assert!(tables[0].content.ends_with("A stolen boot"));
+        assert!(elements.iter().all(|e| e.page_number == 12));
+    }
+
+    #[test]
+    fn test_process_tags_chunks() {
+        let pipeline = TTRPGPipeline::new();
+        let pages = vec![(12, GOBLIN_PAGE.to_string())];
+        let document = pipeline.process(&pages, "monsters");
+
+        let goblin = document
+            .chunks
+            .iter()
+            .find(|c| c.chunk.chunk_type == "stat_block")
+            .expect("stat block chunk");
+        assert_eq!(goblin.chunk.page_number, Some(12));
+        assert_eq!(goblin.attributes.challenge_rating, Some(0.25));
+        assert!(goblin
+            .attributes
+            .creature_types
+            .contains(&"humanoid".to_string()));
+        assert!(goblin
+            .attributes
+            .damage_types
+            .contains(&"slashing".to_string()));
+        assert_eq!(goblin.section_path.as_deref(), Some("CHAPTER 2: GOBLINS"));
+
+        assert!(document
+            .chunks
+            .iter()
+            .any(|c| c.chunk.chunk_type == "random_table"));
+    }
+
+    #[test]
+    fn test_stat_block_spanning_pages_stays_whole() {
+        let pipeline = TTRPGPipeline::new();
+        let (first, second) = GOBLIN_PAGE.split_at(GOBLIN_PAGE.find("STR 8").unwrap());
+        let pages = vec![(12, first.to_string()), (13, second.to_string())];
+        let document = pipeline.process(&pages, "monsters");
+
+        let stat_blocks: Vec<_> = document
+            .chunks
+            .iter()
+            .filter(|c| c.chunk.chunk_type == "stat_block")
+            .collect();
+        assert_eq!(stat_blocks.len(), 1);
+        let goblin = stat_blocks[0];
+        assert!(goblin.chunk.content.contains("Hit Points 7"));
+        assert!(goblin.chunk.content.contains("Scimitar."));
+        assert_eq!(goblin.chunk.page_number, Some(12));
+        assert_eq!(goblin.attributes.challenge_rating, Some(0.25));
+
+        let table = document
+            .chunks
+            .iter()
+            .find(|c| c.chunk.chunk_type == "random_table")
+            .expect("random table chunk");
+        assert_eq!(table.chunk.page_number, Some(13));
+    }
+
+    #[test]
+    fn test_process_text_has_no_page_numbers() {
+        let pipeline = TTRPGPipeline::new();
+        let document = pipeline.process_text(GOBLIN_PAGE, "monsters");
+
+        assert!(!document.chunks.is_empty());
+        assert!(document
+            .chunks
+            .iter()
+            .all(|c| c.chunk.page_number.is_none()));
+    }
+
+    #[test]
+    fn test_reflow_two_column_page() {
+        let pipeline = TTRPGPipeline::new();
+        let page = [
+            "Left one                                Right one",
+            "Left two                                Right two",
+            "Left three                              Right three",
+            "                                        Right four",
+            "Left four                               Right five",
+            "Left five                               Right six",
+        ]
+        .join("\n");
+
+        let reflowed = pipeline.reflow_columns(&page);
+        assert_eq!(
+            reflowed,
+            "Left one\nLeft two\nLeft three\n\nLeft four\nLeft five\n\n\
+             Right one\nRight two\nRight three\nRight four\nRight five\nRight six"
+        );
+    }
+
+    #[test]
+    fn test_reflow_leaves_single_column_text() {
+        let pipeline = TTRPGPipeline::new();
+        let page = "One\nTwo\nThree\nFour\nFive\nd6   Result\nSeven";
+
+        assert_eq!(pipeline.reflow_columns(page), page);
+    }
+
+    #[test]
+    fn test_long_paragraph_split_for_chunker() {
+        let pipeline = TTRPGPipeline::new();
+        let sentence = "The road winds through the old forest and past the ruined tower. ";
+        let paragraph = sentence.repeat(100);
+
+        let elements = pipeline.segment_page(1, &paragraph);
+        assert!(elements.len() > 1);
+        assert!(elements
+            .iter()
+            .all(|e| e.content.len() <= TTRPGChunkConfig::default().base.max_size));
+    }
+}
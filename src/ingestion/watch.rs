@@ -0,0 +1,351 @@
+//! Watched Library Folders
+//!
+//! Scans configured folders for supported documents (see
+//! [`DocumentExtractor::is_supported`]) and reports the files that are new or
+//! changed since the last scan, so they can be queued for ingestion.
+//!
+//! Scanning is polling-based rather than event-based, so it works the same on
+//! local disks and shared network directories. A file is only reported once
+//! its size and modification time are the same on two consecutive scans,
+//! which keeps half-copied files out of the queue. The scanner can be seeded
+//! with the files the library already holds, so restarting the application
+//! does not report every file again.
+//!
+//! A `.ttttrps.toml` file in any folder sets the content type and game
+//! system for the documents below it:
+//!
+//! ```toml
+//! content_type = "adventure"
+//! game_system = "dnd5e"
+//! ```
+//!
+//! The nearest file wins, field by field, so a sub-folder can override just
+//! the content type and inherit the game system from its parent.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::Deserialize;
+use walkdir::{DirEntry, WalkDir};
+
+use super::kreuzberg_extractor::DocumentExtractor;
+
+/// Name of the per-folder config file.
+pub const FOLDER_CONFIG_FILE: &str = ".ttttrps.toml";
+
+// ============================================================================
+// Folder Config
+// ============================================================================
+
+/// Settings from a folder's `.ttttrps.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct FolderConfig {
+    /// Content type for documents in the folder (e.g. rules, lore, adventure)
+    pub content_type: Option<String>,
+    /// Game system, by ID (`dnd5e`, `pf2e`, ...) or name
+    pub game_system: Option<String>,
+}
+
+impl FolderConfig {
+    /// Read the config file in `dir`, if there is one.
+    ///
+    /// A file that cannot be parsed is logged and ignored.
+    pub fn load(dir: &Path) -> Option<Self> {
+        let path = dir.join(FOLDER_CONFIG_FILE);
+        let text = std::fs::read_to_string(&path).ok()?;
+        match toml::from_str(&text) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                log::warn!("Ignoring invalid folder config {}: {e}", path.display());
+                None
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Scanner
+// ============================================================================
+
+/// A document found in a watched folder, ready to be ingested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedFile {
+    pub path: PathBuf,
+    pub content_type: String,
+    pub game_system: Option<String>,
+}
+
+/// A document the library already holds, as recorded when it was ingested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestedFile {
+    pub path: PathBuf,
+    /// File size when it was ingested
+    pub len: u64,
+    /// When the library item was last updated
+    pub ingested_at: SystemTime,
+}
+
+/// Size and modification time, used to tell whether a file changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileSignature {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Finds new and changed documents in a set of watched folders.
+pub struct FolderScanner {
+    roots: Vec<PathBuf>,
+    default_content_type: String,
+    /// Signatures from the previous scan
+    seen: HashMap<PathBuf, FileSignature>,
+    /// Signatures of files as they were when last reported
+    reported: HashMap<PathBuf, FileSignature>,
+}
+
+impl FolderScanner {
+    pub fn new(roots: Vec<PathBuf>, default_content_type: impl Into<String>) -> Self {
+        Self {
+            roots,
+            default_content_type: default_content_type.into(),
+            seen: HashMap::new(),
+            reported: HashMap::new(),
+        }
+    }
+
+    /// The watched folders.
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Treat files the library already holds as reported, unless they changed
+    /// since they were ingested (different size, or modified afterwards).
+    pub fn seed(&mut self, files: impl IntoIterator<Item = IngestedFile>) {
+        for file in files {
+            let Ok(meta) = std::fs::metadata(&file.path) else {
+                continue;
+            };
+            let signature = FileSignature {
+                len: meta.len(),
+                modified: meta.modified().ok(),
+            };
+            let unchanged = signature.len == file.len
+                && signature
+                    .modified
+                    .is_some_and(|modified| modified <= file.ingested_at);
+            if unchanged {
+                self.seen.insert(file.path.clone(), signature);
+                self.reported.insert(file.path, signature);
+            }
+        }
+    }
+
+    /// Walk the watched folders and return the documents that are new or
+    /// changed since they were last reported and have settled since the
+    /// previous scan.
+    ///
+    /// A file that disappears and comes back is reported again. Folders that
+    /// cannot be read are skipped.
+    pub fn scan(&mut self) -> Vec<WatchedFile> {
+        let mut current = HashMap::new();
+        let mut folder_configs: HashMap<PathBuf, Option<FolderConfig>> = HashMap::new();
+        let mut ready = Vec::new();
+
+        for root in &self.roots {
+            let entries = WalkDir::new(root)
+                .into_iter()
+                .filter_entry(|entry| entry.depth() == 0 || !is_hidden(entry))
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file());
+
+            for entry in entries {
+                let path = entry.path();
+                if !DocumentExtractor::is_supported(path) {
+                    continue;
+                }
+                let Ok(meta) = entry.metadata() else {
+                    continue;
+                };
+                let signature = FileSignature {
+                    len: meta.len(),
+                    modified: meta.modified().ok(),
+                };
+
+                let settled = self.seen.get(path) == Some(&signature);
+                let reported = self.reported.get(path) == Some(&signature);
+                if settled && !reported {
+                    let (content_type, game_system) =
+                        self.resolve_config(root, path, &mut folder_configs);
+                    ready.push(WatchedFile {
+                        path: path.to_path_buf(),
+                        content_type,
+                        game_system,
+                    });
+                    self.reported.insert(path.to_path_buf(), signature);
+                }
+                current.insert(path.to_path_buf(), signature);
+            }
+        }
+
+        self.reported.retain(|path, _| current.contains_key(path));
+        self.seen = current;
+        ready
+    }
+
+    /// Content type and game system for `path`, from the nearest folder
+    /// configs between the file and its watched root.
+    fn resolve_config(
+        &self,
+        root: &Path,
+        path: &Path,
+        cache: &mut HashMap<PathBuf, Option<FolderConfig>>,
+    ) -> (String, Option<String>) {
+        let mut content_type = None;
+        let mut game_system = None;
+
+        for dir in path.ancestors().skip(1) {
+            let config = cache
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| FolderConfig::load(dir));
+            if let Some(config) = config {
+                content_type = content_type.or_else(|| config.content_type.clone());
+                game_system = game_system.or_else(|| config.game_system.clone());
+            }
+            if dir == root || (content_type.is_some() && game_system.is_some()) {
+                break;
+            }
+        }
+
+        let content_type = content_type
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| self.default_content_type.clone());
+        let game_system = game_system
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        (content_type, game_system)
+    }
+}
+
+fn is_hidden(entry: &DirEntry) -> bool {
+    entry
+        .file_name()
+        .to_str()
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_files_reported_once_settled() {
+        let dir = TempDir::new().unwrap();
+        let book = dir.path().join("core.pdf");
+        fs::write(&book, "first printing").unwrap();
+
+        let mut scanner = FolderScanner::new(vec![dir.path().to_path_buf()], "rules");
+        assert!(
+            scanner.scan().is_empty(),
+            "first sighting is not settled yet"
+        );
+
+        let ready = scanner.scan();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].path, book);
+        assert_eq!(ready[0].content_type, "rules");
+        assert!(
+            scanner.scan().is_empty(),
+            "unchanged file is not reported again"
+        );
+
+        fs::write(&book, "errata printing, longer").unwrap();
+        assert!(scanner.scan().is_empty());
+        assert_eq!(scanner.scan().len(), 1);
+    }
+
+    #[test]
+    fn test_seeded_files_not_reported_until_changed() {
+        let dir = TempDir::new().unwrap();
+        let book = dir.path().join("core.pdf");
+        let notes = dir.path().join("notes.pdf");
+        fs::write(&book, "first printing").unwrap();
+        fs::write(&notes, "session notes").unwrap();
+
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        let mut scanner = FolderScanner::new(vec![dir.path().to_path_buf()], "rules");
+        scanner.seed([
+            IngestedFile {
+                path: book.clone(),
+                len: "first printing".len() as u64,
+                ingested_at: later,
+            },
+            // Ingested before the file was last written
+            IngestedFile {
+                path: notes.clone(),
+                len: "session notes".len() as u64,
+                ingested_at: SystemTime::UNIX_EPOCH,
+            },
+        ]);
+
+        assert!(scanner.scan().is_empty());
+        let ready = scanner.scan();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].path, notes);
+        assert!(scanner.scan().is_empty());
+
+        fs::write(&book, "errata printing, longer").unwrap();
+        assert!(scanner.scan().is_empty());
+        assert_eq!(scanner.scan().len(), 1);
+    }
+
+    #[test]
+    fn test_skips_hidden_and_unsupported_files() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("setup.exe"), "binary").unwrap();
+        fs::write(dir.path().join(".draft.pdf"), "hidden").unwrap();
+        fs::create_dir(dir.path().join(".cache")).unwrap();
+        fs::write(dir.path().join(".cache").join("copy.pdf"), "hidden").unwrap();
+
+        let mut scanner = FolderScanner::new(vec![dir.path().to_path_buf()], "rules");
+        scanner.scan();
+        assert!(scanner.scan().is_empty());
+    }
+
+    #[test]
+    fn test_nearest_folder_config_wins_per_field() {
+        let dir = TempDir::new().unwrap();
+        let adventures = dir.path().join("adventures");
+        fs::create_dir(&adventures).unwrap();
+        fs::write(
+            dir.path().join(FOLDER_CONFIG_FILE),
+            "content_type = \"rules\"\ngame_system = \"pf2e\"\n",
+        )
+        .unwrap();
+        fs::write(
+            adventures.join(FOLDER_CONFIG_FILE),
+            "content_type = \"Adventure\"\n",
+        )
+        .unwrap();
+        fs::write(adventures.join("abomination_vaults.pdf"), "dungeon").unwrap();
+        fs::write(dir.path().join("core.pdf"), "rules").unwrap();
+
+        let mut scanner = FolderScanner::new(vec![dir.path().to_path_buf()], "lore");
+        scanner.scan();
+        let mut ready = scanner.scan();
+        ready.sort_by(|a, b| a.path.cmp(&b.path));
+
+        assert_eq!(ready.len(), 2);
+        assert_eq!(ready[0].content_type, "adventure");
+        assert_eq!(ready[0].game_system.as_deref(), Some("pf2e"));
+        assert_eq!(ready[1].content_type, "rules");
+        assert_eq!(ready[1].game_system.as_deref(), Some("pf2e"));
+    }
+}
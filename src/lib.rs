@@ -3,6 +3,7 @@
 /// Core library providing campaign management, LLM integration,
 /// document ingestion, and search for tabletop RPG game masters.
 
+pub mod cli;
 pub mod config;
 pub mod core;
 pub mod database;
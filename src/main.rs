@@ -1,6 +1,7 @@
 use std::io;
 use std::time::Duration;
 
+use clap::Parser;
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -8,6 +9,7 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 use tokio::sync::mpsc;
 
+use ttttrps::cli::Cli;
 use ttttrps::config::AppConfig;
 use ttttrps::tui::app::AppState;
 use ttttrps::tui::services::Services;
@@ -29,12 +31,23 @@ fn install_panic_hook() {
 
 #[tokio::main]
 async fn main() {
-    // Install panic hook BEFORE entering raw mode
-    install_panic_hook();
+    let cli = Cli::parse();
 
     // Load configuration
     let config = AppConfig::load();
 
+    // Headless subcommand: run it and exit without touching the terminal
+    if let Some(command) = cli.command {
+        let log_guard = ttttrps::core::logging::init_tui();
+        log::info!("TTTTRPS v{} running command", ttttrps::VERSION);
+        let code = ttttrps::cli::run(command, cli.json, &config).await;
+        drop(log_guard);
+        std::process::exit(code);
+    }
+
+    // Install panic hook BEFORE entering raw mode
+    install_panic_hook();
+
     // Initialize TUI-safe logging (file only, no stdout)
     let _log_guard = ttttrps::core::logging::init_tui();
     log::info!("TTTTRPS v{} starting", ttttrps::VERSION);
@@ -54,7 +54,9 @@ pub use models::{
     ApiModel, ContentBlock, DocumentSource, ImageSource, Message, MessagesResponse, ModelsResponse,
     Role, StopReason, StreamEvent, Tool, ToolChoice, TokenInfo, Usage,
 };
-pub use storage::{CallbackStorage, FileTokenStorage, MemoryTokenStorage, TokenStorage};
+pub use storage::{
+    CallbackStorage, FileTokenStorage, MemoryTokenStorage, TokenStorage, VaultTokenStorage,
+};
 
 #[cfg(feature = "keyring")]
 pub use storage::KeyringTokenStorage;
@@ -91,6 +91,7 @@ pub use auth::{
 // Re-export storage types at module root
 pub use storage::{
     CallbackStorage, EnvSource, FileSource, FileTokenStorage, MemoryTokenStorage, TokenStorage,
+    VaultTokenStorage,
 };
 
 #[cfg(feature = "keyring")]
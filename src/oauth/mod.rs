@@ -102,6 +102,7 @@ pub use client::GateClient;
 // Re-export storage types
 pub use storage::{
     CallbackStorage, EnvSource, FileSource, FileTokenStorage, MemoryTokenStorage, TokenStorage,
+    VaultTokenStorage,
 };
 
 // Re-export token type
@@ -6,6 +6,7 @@
 //! - [`MemoryTokenStorage`] - In-memory storage for testing
 //! - [`CallbackStorage`] - Custom storage via callbacks
 //! - [`KeyringTokenStorage`] - System keyring storage (requires `keyring` feature)
+//! - [`VaultTokenStorage`] - Encrypted credential vault (for machines without a keyring)
 //!
 //! # Provider-Aware Storage
 //!
@@ -39,6 +40,7 @@
 mod callback;
 mod file;
 mod memory;
+mod vault;
 
 #[cfg(feature = "keyring")]
 mod keyring;
@@ -48,6 +50,7 @@ use async_trait::async_trait;
 pub use callback::{CallbackStorage, EnvSource, FileSource};
 pub use file::FileTokenStorage;
 pub use memory::MemoryTokenStorage;
+pub use vault::{migrate_tokens, VaultTokenStorage};
 
 #[cfg(feature = "keyring")]
 pub use keyring::KeyringTokenStorage;
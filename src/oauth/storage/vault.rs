@@ -0,0 +1,167 @@
+//! Encrypted vault token storage.
+//!
+//! Stores each provider's token as a sealed entry (`oauth_{provider}`) in the
+//! application's [`CredentialVault`], for machines without a system keyring.
+//! Loads and saves fail while the vault is locked.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::TokenStorage;
+use crate::core::vault::{self, CredentialVault};
+use crate::oauth::token::TokenInfo;
+use crate::oauth::{Error, Result};
+
+/// Vault-backed token storage.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use crate::oauth::storage::VaultTokenStorage;
+///
+/// // Use the vault installed at startup, if any
+/// if let Some(storage) = VaultTokenStorage::shared() {
+///     // Use storage...
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct VaultTokenStorage {
+    vault: Arc<CredentialVault>,
+}
+
+impl VaultTokenStorage {
+    /// Create storage backed by the given vault.
+    pub fn new(vault: Arc<CredentialVault>) -> Self {
+        Self { vault }
+    }
+
+    /// Storage backed by the shared vault, if the vault backend is in use.
+    pub fn shared() -> Option<Self> {
+        vault::shared().map(Self::new)
+    }
+
+    fn key(provider: &str) -> String {
+        format!("oauth_{provider}")
+    }
+}
+
+#[async_trait]
+impl TokenStorage for VaultTokenStorage {
+    #[instrument(skip(self))]
+    async fn load(&self, provider: &str) -> Result<Option<TokenInfo>> {
+        let raw = self
+            .vault
+            .get(&Self::key(provider))
+            .map_err(|e| Error::storage(format!("Vault read failed: {e}")))?;
+        match raw {
+            Some(json) => {
+                let token = serde_json::from_str(&json).map_err(|e| {
+                    Error::storage(format!(
+                        "Failed to parse token for provider '{}': {}",
+                        provider, e
+                    ))
+                })?;
+                Ok(Some(token))
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self, token))]
+    async fn save(&self, provider: &str, token: &TokenInfo) -> Result<()> {
+        let json = serde_json::to_string(token)?;
+        self.vault
+            .set(&Self::key(provider), &json)
+            .map_err(|e| Error::storage(format!("Vault write failed: {e}")))
+    }
+
+    #[instrument(skip(self))]
+    async fn remove(&self, provider: &str) -> Result<()> {
+        self.vault
+            .remove(&Self::key(provider))
+            .map_err(|e| Error::storage(format!("Vault write failed: {e}")))
+    }
+
+    async fn exists(&self, provider: &str) -> Result<bool> {
+        Ok(self.vault.contains(&Self::key(provider)))
+    }
+
+    fn name(&self) -> &str {
+        "vault"
+    }
+}
+
+/// Move tokens for `providers` from `from` into the vault.
+///
+/// Tokens already in the vault are kept. When `remove_source` is set the
+/// copied tokens are deleted from `from` (used to retire plaintext files).
+/// Returns the number of tokens copied.
+pub async fn migrate_tokens<S: TokenStorage + ?Sized>(
+    from: &S,
+    to: &VaultTokenStorage,
+    providers: &[&str],
+    remove_source: bool,
+) -> Result<usize> {
+    let mut copied = 0;
+    for provider in providers {
+        if to.exists(provider).await? {
+            continue;
+        }
+        if let Some(token) = from.load(provider).await? {
+            to.save(provider, &token).await?;
+            if remove_source {
+                from.remove(provider).await?;
+            }
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oauth::storage::MemoryTokenStorage;
+    use tempfile::tempdir;
+
+    fn unlocked_vault() -> (tempfile::TempDir, Arc<CredentialVault>) {
+        let dir = tempdir().unwrap();
+        let vault = CredentialVault::open(dir.path().join("credentials.vault")).unwrap();
+        vault.initialize("pw").unwrap();
+        (dir, Arc::new(vault))
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load() {
+        let (_dir, vault) = unlocked_vault();
+        let storage = VaultTokenStorage::new(vault.clone());
+
+        assert!(storage.load("claude").await.unwrap().is_none());
+        let token = TokenInfo::new("access".into(), "refresh".into(), 3600);
+        storage.save("claude", &token).await.unwrap();
+
+        let loaded = storage.load("claude").await.unwrap().unwrap();
+        assert_eq!(loaded.access_token, "access");
+        assert!(storage.exists("claude").await.unwrap());
+
+        vault.lock();
+        assert!(storage.load("claude").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_tokens() {
+        let (_dir, vault) = unlocked_vault();
+        let storage = VaultTokenStorage::new(vault);
+        let source = MemoryTokenStorage::new();
+        let token = TokenInfo::new("access".into(), "refresh".into(), 3600);
+        source.save("gemini", &token).await.unwrap();
+
+        let copied = migrate_tokens(&source, &storage, &["claude", "gemini"], true)
+            .await
+            .unwrap();
+        assert_eq!(copied, 1);
+        assert!(storage.load("gemini").await.unwrap().is_some());
+        assert!(source.load("gemini").await.unwrap().is_none());
+    }
+}
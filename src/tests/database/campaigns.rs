@@ -3,8 +3,9 @@
 //! Tests for campaign CRUD operations, versioning, and relationships.
 
 use crate::database::{
-    CampaignOps, CampaignRecord, CampaignVersionRecord, EntityRelationshipRecord, EntityType,
-    NpcOps, NpcRecord, RelationshipOps,
+    CampaignExportOps, CampaignOps, CampaignRecord, CampaignVersionRecord,
+    EntityRelationshipRecord, EntityType, LocationOps, LocationRecord, NpcOps, NpcRecord,
+    RelationshipOps, SessionOps, SessionRecord,
 };
 use crate::tests::common::create_test_db;
 
@@ -399,6 +400,65 @@ async fn test_campaign_with_large_json() {
     assert_eq!(retrieved.world_state, Some(large_json));
 }
 
+// =============================================================================
+// Export / Import
+// =============================================================================
+
+#[tokio::test]
+async fn test_campaign_bundle_round_trip() {
+    let (source, _temp) = create_test_db().await;
+
+    let campaign = CampaignRecord::new(
+        "camp-export".to_string(),
+        "Exported".to_string(),
+        "D&D 5e".to_string(),
+    );
+    source.create_campaign(&campaign).await.expect("create campaign");
+    source
+        .create_session(&SessionRecord::new("sess-1".into(), "camp-export".into(), 1))
+        .await
+        .expect("create session");
+    let mut npc = NpcRecord::new("npc-1".into(), "Mira".into(), "Innkeeper".into());
+    npc.campaign_id = Some("camp-export".into());
+    source.save_npc(&npc).await.expect("save npc");
+    source
+        .save_location(&LocationRecord::new(
+            "loc-1".into(),
+            "camp-export".into(),
+            "The Rusty Flagon".into(),
+            "tavern".into(),
+        ))
+        .await
+        .expect("save location");
+
+    let bundle = source
+        .export_campaign_bundle("camp-export")
+        .await
+        .expect("export")
+        .expect("campaign exists");
+    assert_eq!(bundle.sessions.len(), 1);
+    assert_eq!(bundle.npcs.len(), 1);
+    assert_eq!(bundle.locations.len(), 1);
+
+    let json = serde_json::to_string(&bundle).expect("serialize");
+    let bundle = serde_json::from_str(&json).expect("deserialize");
+
+    let (target, _temp2) = create_test_db().await;
+    target.import_campaign_bundle(&bundle).await.expect("import");
+    assert!(target.get_campaign("camp-export").await.unwrap().is_some());
+    assert_eq!(target.list_sessions("camp-export").await.unwrap().len(), 1);
+    assert_eq!(target.list_npcs(Some("camp-export")).await.unwrap().len(), 1);
+
+    // Importing the same campaign twice is refused
+    assert!(target.import_campaign_bundle(&bundle).await.is_err());
+}
+
+#[tokio::test]
+async fn test_export_missing_campaign() {
+    let (db, _temp) = create_test_db().await;
+    assert!(db.export_campaign_bundle("nope").await.unwrap().is_none());
+}
+
 // =============================================================================
 // Edge Cases
 // =============================================================================
@@ -204,6 +204,7 @@ impl LLMProvider for MockProvider {
                     finish_reason: if is_final { Some("stop".to_string()) } else { None },
                     usage: if is_final { Some(usage.clone()) } else { None },
                     index: i as u32,
+                    tool_calls: None,
                 };
 
                 if tx.send(Ok(chunk)).await.is_err() {
@@ -0,0 +1,562 @@
+//! Character Advancement Unit Tests
+//!
+//! Tests for progressing generated characters including:
+//! - D&D 5e and Pathfinder 2e level up (hit points, features, feats, boosts)
+//! - World of Darkness experience spends
+//! - Fate Core milestones and the skill pyramid
+//! - Call of Cthulhu development checks
+//! - Advancement history, rejected requests and unsupported systems
+
+use crate::core::character_gen::{
+    advancement::ChoiceKind, AdvancementChoice, AdvancementKind, AdvancementRequest,
+    AttributeValue, Character, CharacterGenError, CharacterGenerator, GenerationOptions, Milestone,
+    TraitType,
+};
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+fn create_character(system: &str, class: Option<&str>, level: u32) -> Character {
+    CharacterGenerator::generate(&GenerationOptions {
+        system: Some(system.to_string()),
+        class: class.map(str::to_string),
+        race: Some("Human".to_string()),
+        level: Some(level),
+        random_stats: false,
+        ..Default::default()
+    })
+    .expect("character should generate")
+}
+
+fn note_value(character: &Character, name: &str) -> i32 {
+    character
+        .notes
+        .lines()
+        .find_map(|line| line.strip_prefix(&format!("{name}: ")))
+        .and_then(|v| v.trim_start_matches('+').parse().ok())
+        .unwrap_or_else(|| panic!("missing {name} in notes: {}", character.notes))
+}
+
+fn increase_attribute(name: &str, amount: i32) -> AdvancementChoice {
+    AdvancementChoice::IncreaseAttribute {
+        name: name.to_string(),
+        amount,
+    }
+}
+
+fn increase_skill(name: &str, amount: i32) -> AdvancementChoice {
+    AdvancementChoice::IncreaseSkill {
+        name: name.to_string(),
+        amount,
+    }
+}
+
+fn request_with(choices: Vec<AdvancementChoice>) -> AdvancementRequest {
+    AdvancementRequest {
+        choices,
+        ..Default::default()
+    }
+}
+
+// ============================================================================
+// D&D 5e Tests
+// ============================================================================
+
+#[cfg(test)]
+mod dnd5e {
+    use super::*;
+
+    #[test]
+    fn test_level_up_adds_hit_points_and_features() {
+        let mut character = create_character("dnd5e", Some("Fighter"), 1);
+        assert!(!character.traits.iter().any(|t| t.name == "Action Surge"));
+
+        let request = AdvancementRequest::default();
+        let preview = CharacterGenerator::preview_advancement(&character, &request).unwrap();
+        assert_eq!(preview.kind, AdvancementKind::LevelUp);
+        assert_eq!(preview.next_level, Some(2));
+        assert_eq!(preview.hit_die, Some(10));
+        assert!(preview.features.iter().any(|t| t.name == "Action Surge"));
+        assert!(preview.slots.is_empty());
+
+        let record = CharacterGenerator::advance(&mut character, &request).unwrap();
+
+        // Standard array Constitution 13 (+1): 11 at level 1, average 6 + 1 per level
+        assert_eq!(character.level, 2);
+        assert_eq!(note_value(&character, "HP"), 18);
+        assert_eq!(note_value(&character, "Proficiency Bonus"), 2);
+        assert_eq!(record.hit_points_gained, Some(7));
+        assert_eq!((record.from_level, record.to_level), (1, 2));
+        assert!(character.traits.iter().any(|t| t.name == "Action Surge"));
+        assert_eq!(character.advancement_history.len(), 1);
+        assert!(record.changes.iter().any(|c| c == "Gained Action Surge"));
+    }
+
+    #[test]
+    fn test_rolled_hit_points_within_die() {
+        for _ in 0..10 {
+            let mut character = create_character("dnd5e", Some("Wizard"), 1);
+            let request = AdvancementRequest {
+                roll_hit_points: true,
+                ..Default::default()
+            };
+            let record = CharacterGenerator::advance(&mut character, &request).unwrap();
+            let gained = record.hit_points_gained.unwrap();
+            assert!((2..=7).contains(&gained), "d6 + 1 out of range: {gained}");
+        }
+    }
+
+    #[test]
+    fn test_improved_features_are_updated() {
+        let mut character = create_character("dnd5e", Some("Rogue"), 2);
+        CharacterGenerator::advance(&mut character, &AdvancementRequest::default()).unwrap();
+
+        let sneak_attack: Vec<_> = character
+            .traits
+            .iter()
+            .filter(|t| t.name == "Sneak Attack")
+            .collect();
+        assert_eq!(sneak_attack.len(), 1);
+        assert_eq!(
+            sneak_attack[0].mechanical_effect.as_deref(),
+            Some("2d6 extra damage")
+        );
+    }
+
+    #[test]
+    fn test_ability_score_improvement_required() {
+        let mut character = create_character("dnd5e", Some("Fighter"), 3);
+
+        let result = CharacterGenerator::advance(&mut character, &AdvancementRequest::default());
+        assert!(matches!(
+            result,
+            Err(CharacterGenError::AdvancementError(_))
+        ));
+        // A rejected advancement leaves the character untouched
+        assert_eq!(character.level, 3);
+        assert!(character.advancement_history.is_empty());
+
+        let request = request_with(vec![
+            increase_attribute("Constitution", 1),
+            increase_attribute("Constitution", 1),
+        ]);
+        CharacterGenerator::advance(&mut character, &request).unwrap();
+
+        // Constitution 13 → 15 raises the modifier to +2 for all four levels:
+        // 25 at level 3, then 6 + 2 plus 1 retroactively for each earlier level
+        assert_eq!(character.attributes["Constitution"].base, 15);
+        assert_eq!(character.attributes["Constitution"].modifier, 2);
+        assert_eq!(note_value(&character, "HP"), 36);
+    }
+
+    #[test]
+    fn test_feat_instead_of_ability_increase() {
+        let mut character = create_character("dnd5e", Some("Fighter"), 3);
+        let preview =
+            CharacterGenerator::preview_advancement(&character, &AdvancementRequest::default())
+                .unwrap();
+        let feat_slot = preview
+            .slots
+            .iter()
+            .position(|slot| {
+                slot.kind
+                    == ChoiceKind::Trait {
+                        trait_type: TraitType::Feat,
+                    }
+            })
+            .unwrap();
+        let tough = preview.slots[feat_slot]
+            .options
+            .iter()
+            .position(|o| o == "Tough")
+            .unwrap();
+
+        let mut picks = vec![None; preview.slots.len()];
+        picks[feat_slot] = Some(tough);
+        CharacterGenerator::advance(&mut character, &request_with(preview.choices(&picks)))
+            .unwrap();
+
+        assert!(character
+            .traits
+            .iter()
+            .any(|t| t.name == "Tough" && t.trait_type == TraitType::Feat));
+        // 25 + 7 for the level, + 2 per level from Tough
+        assert_eq!(note_value(&character, "HP"), 40);
+
+        // Mixing a feat with ability increases is rejected
+        let mut character = create_character("dnd5e", Some("Fighter"), 3);
+        let mut choices = preview.choices(&picks);
+        choices.push(increase_attribute("Strength", 1));
+        assert!(CharacterGenerator::advance(&mut character, &request_with(choices)).is_err());
+    }
+
+    #[test]
+    fn test_no_choices_outside_improvement_levels() {
+        let mut character = create_character("dnd5e", Some("Fighter"), 1);
+        let request = request_with(vec![increase_attribute("Strength", 1)]);
+        assert!(CharacterGenerator::advance(&mut character, &request).is_err());
+    }
+
+    #[test]
+    fn test_max_level_reached() {
+        let character = create_character("dnd5e", Some("Fighter"), 20);
+        let result =
+            CharacterGenerator::preview_advancement(&character, &AdvancementRequest::default());
+        assert!(result.is_err());
+    }
+}
+
+// ============================================================================
+// Pathfinder 2e Tests
+// ============================================================================
+
+#[cfg(test)]
+mod pf2e {
+    use super::*;
+
+    #[test]
+    fn test_even_level_grants_class_and_skill_feats() {
+        let mut character = create_character("pf2e", Some("Fighter"), 1);
+        let preview =
+            CharacterGenerator::preview_advancement(&character, &AdvancementRequest::default())
+                .unwrap();
+        let labels: Vec<&str> = preview.slots.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["Class feat", "Skill feat"]);
+        assert!(preview.slots.iter().all(|s| s.required));
+
+        // Feats are required
+        assert!(
+            CharacterGenerator::advance(&mut character, &AdvancementRequest::default()).is_err()
+        );
+
+        let picks = vec![Some(0); preview.slots.len()];
+        let record =
+            CharacterGenerator::advance(&mut character, &request_with(preview.choices(&picks)))
+                .unwrap();
+
+        assert_eq!(character.level, 2);
+        let feats = character
+            .traits
+            .iter()
+            .filter(|t| t.trait_type == TraitType::Feat)
+            .count();
+        assert_eq!(feats, 2);
+        // Human 8 + (Fighter 10 + Constitution 2) at level 1, then 12 more
+        assert_eq!(note_value(&character, "HP"), 32);
+        assert_eq!(record.hit_points_gained, Some(12));
+    }
+
+    #[test]
+    fn test_attribute_boosts_at_level_five() {
+        let mut character = create_character("pf2e", Some("Fighter"), 4);
+        let preview =
+            CharacterGenerator::preview_advancement(&character, &AdvancementRequest::default())
+                .unwrap();
+        let boosts: Vec<usize> = preview
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| matches!(s.kind, ChoiceKind::Attribute { .. }))
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(boosts.len(), 4);
+
+        // The same attribute can't be boosted twice
+        let duplicate = vec![Some(0); preview.slots.len()];
+        assert!(CharacterGenerator::advance(
+            &mut character.clone(),
+            &request_with(preview.choices(&duplicate))
+        )
+        .is_err());
+
+        let mut picks = vec![Some(0); preview.slots.len()];
+        for (n, &slot) in boosts.iter().enumerate() {
+            picks[slot] = Some(n);
+        }
+        CharacterGenerator::advance(&mut character, &request_with(preview.choices(&picks)))
+            .unwrap();
+
+        assert_eq!(character.level, 5);
+        assert_eq!(character.attributes["Strength"].base, 18);
+        assert_eq!(character.attributes["Dexterity"].base, 16);
+        assert_eq!(character.attributes["Intelligence"].base, 14);
+        assert!(character.skills.values().any(|&rank| rank == 1));
+    }
+}
+
+// ============================================================================
+// World of Darkness Tests
+// ============================================================================
+
+#[cfg(test)]
+mod world_of_darkness {
+    use super::*;
+
+    #[test]
+    fn test_spend_experience_on_dots() {
+        let mut character = create_character("wod", None, 1);
+        character
+            .attributes
+            .insert("Stamina".to_string(), AttributeValue::new_raw(2));
+        character.skills.insert("Brawl".to_string(), 1);
+
+        let request = AdvancementRequest {
+            experience_award: 10,
+            choices: vec![increase_attribute("Stamina", 1), increase_skill("Brawl", 1)],
+            ..Default::default()
+        };
+        let record = CharacterGenerator::advance(&mut character, &request).unwrap();
+
+        assert_eq!(character.attributes["Stamina"].base, 3);
+        assert_eq!(character.skills["Brawl"], 2);
+        assert_eq!(character.experience, 4);
+        assert_eq!(note_value(&character, "Health"), 8);
+        assert_eq!(record.experience_awarded, 10);
+        assert_eq!(record.experience_spent, 6);
+    }
+
+    #[test]
+    fn test_banked_experience_carries_over() {
+        let mut character = create_character("wod", None, 1);
+        let request = AdvancementRequest {
+            experience_award: 3,
+            ..Default::default()
+        };
+        CharacterGenerator::advance(&mut character, &request).unwrap();
+        assert_eq!(character.experience, 3);
+
+        // 3 XP can't buy an attribute dot
+        let request = request_with(vec![increase_attribute("Strength", 1)]);
+        assert!(CharacterGenerator::advance(&mut character, &request).is_err());
+        assert_eq!(character.experience, 3);
+
+        let preview =
+            CharacterGenerator::preview_advancement(&character, &AdvancementRequest::default())
+                .unwrap();
+        assert!(preview.slots.iter().any(|s| s.label == "Skill dot"));
+        assert!(!preview.slots.iter().any(|s| s.label == "Attribute dot"));
+    }
+
+    #[test]
+    fn test_empty_spend_rejected() {
+        let mut character = create_character("wod", None, 1);
+        assert!(
+            CharacterGenerator::advance(&mut character, &AdvancementRequest::default()).is_err()
+        );
+    }
+}
+
+// ============================================================================
+// Fate Core Tests
+// ============================================================================
+
+#[cfg(test)]
+mod fate {
+    use super::*;
+
+    fn milestone(milestone: Milestone, choices: Vec<AdvancementChoice>) -> AdvancementRequest {
+        AdvancementRequest {
+            milestone: Some(milestone),
+            choices,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_minor_milestone_swaps_skills() {
+        let mut character = create_character("fate", None, 1);
+        character.skills.insert("Fight".to_string(), 1);
+
+        let swap = AdvancementChoice::SwapSkills {
+            first: "Fight".to_string(),
+            second: "Notice".to_string(),
+        };
+        CharacterGenerator::advance(&mut character, &milestone(Milestone::Minor, vec![swap]))
+            .unwrap();
+        assert_eq!(character.skills["Fight"], 0);
+        assert_eq!(character.skills["Notice"], 1);
+
+        // A minor milestone must change something
+        assert!(
+            CharacterGenerator::advance(&mut character, &milestone(Milestone::Minor, vec![]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_significant_milestone_respects_pyramid() {
+        let mut character = create_character("fate", None, 1);
+        character.skills.insert("Fight".to_string(), 1);
+
+        let preview = CharacterGenerator::preview_advancement(
+            &character,
+            &milestone(Milestone::Significant, vec![]),
+        )
+        .unwrap();
+        let raise = preview
+            .slots
+            .iter()
+            .find(|s| s.kind == ChoiceKind::Skill { amount: 1 })
+            .unwrap();
+        assert!(raise.required);
+        // Fight at +2 would have no +1 skill beneath it
+        assert!(!raise.options.contains(&"Fight".to_string()));
+        assert!(raise.options.contains(&"Notice".to_string()));
+
+        let request = milestone(Milestone::Significant, vec![increase_skill("Fight", 1)]);
+        assert!(CharacterGenerator::advance(&mut character, &request).is_err());
+
+        let request = milestone(Milestone::Significant, vec![increase_skill("Notice", 1)]);
+        CharacterGenerator::advance(&mut character, &request).unwrap();
+        assert_eq!(character.skills["Notice"], 1);
+    }
+
+    #[test]
+    fn test_major_milestone_raises_refresh() {
+        let mut character = create_character("fate", None, 1);
+        let request = milestone(Milestone::Major, vec![increase_skill("Will", 1)]);
+        let record = CharacterGenerator::advance(&mut character, &request).unwrap();
+
+        assert_eq!(note_value(&character, "Refresh"), 4);
+        assert_eq!(record.milestone, Some(Milestone::Major));
+        assert_eq!(record.summary(), "Major milestone");
+    }
+
+    #[test]
+    fn test_new_stunt_costs_refresh() {
+        let mut character = create_character("fate", None, 1);
+        let preview = CharacterGenerator::preview_advancement(
+            &character,
+            &milestone(Milestone::Minor, vec![]),
+        )
+        .unwrap();
+        let slot = preview
+            .slots
+            .iter()
+            .position(|s| {
+                s.kind
+                    == ChoiceKind::Trait {
+                        trait_type: TraitType::Stunt,
+                    }
+            })
+            .unwrap();
+        let mut picks = vec![None; preview.slots.len()];
+        picks[slot] = Some(0);
+
+        let stunts_before = character
+            .traits
+            .iter()
+            .filter(|t| t.trait_type == TraitType::Stunt)
+            .count();
+        CharacterGenerator::advance(
+            &mut character,
+            &milestone(Milestone::Minor, preview.choices(&picks)),
+        )
+        .unwrap();
+
+        let stunts_after = character
+            .traits
+            .iter()
+            .filter(|t| t.trait_type == TraitType::Stunt)
+            .count();
+        assert_eq!(stunts_after, stunts_before + 1);
+        assert_eq!(note_value(&character, "Refresh"), 2);
+    }
+}
+
+// ============================================================================
+// Call of Cthulhu Tests
+// ============================================================================
+
+#[cfg(test)]
+mod call_of_cthulhu {
+    use super::*;
+
+    #[test]
+    fn test_development_checks() {
+        let mut character = create_character("coc", None, 1);
+        let before = character.skills["Spot Hidden"];
+
+        let request = request_with(vec![increase_skill("Spot Hidden", 0)]);
+        let record = CharacterGenerator::advance(&mut character, &request).unwrap();
+
+        let after = character.skills["Spot Hidden"];
+        assert!(after == before || (before + 1..=before + 10).contains(&after));
+        assert_eq!(record.kind, AdvancementKind::Development);
+        assert_eq!(record.changes.len(), 1);
+        assert_eq!(character.level, 1);
+    }
+
+    #[test]
+    fn test_development_slots_exclude_undevelopable_skills() {
+        let character = create_character("coc", None, 1);
+        let preview =
+            CharacterGenerator::preview_advancement(&character, &AdvancementRequest::default())
+                .unwrap();
+        assert!(!preview.slots.is_empty());
+        for slot in &preview.slots {
+            assert!(!slot.required);
+            assert!(!slot.options.contains(&"Cthulhu Mythos".to_string()));
+            assert!(!slot.options.contains(&"Credit Rating".to_string()));
+        }
+
+        let mut character = character;
+        let request = request_with(vec![
+            increase_skill("Library Use", 0),
+            increase_skill("Library Use", 0),
+        ]);
+        assert!(CharacterGenerator::advance(&mut character, &request).is_err());
+    }
+}
+
+// ============================================================================
+// General Tests
+// ============================================================================
+
+#[cfg(test)]
+mod general {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_system() {
+        let mut character = create_character("gurps", None, 1);
+        let result = CharacterGenerator::advance(&mut character, &AdvancementRequest::default());
+        assert!(matches!(
+            result,
+            Err(CharacterGenError::AdvancementError(_))
+        ));
+    }
+
+    #[test]
+    fn test_history_accumulates_and_round_trips() {
+        let mut character = create_character("dnd5e", Some("Fighter"), 1);
+        CharacterGenerator::advance(&mut character, &AdvancementRequest::default()).unwrap();
+        CharacterGenerator::advance(&mut character, &AdvancementRequest::default()).unwrap();
+
+        assert_eq!(character.level, 3);
+        let summaries: Vec<String> = character
+            .advancement_history
+            .iter()
+            .map(|r| r.summary())
+            .collect();
+        assert_eq!(summaries, ["Level 1 → 2", "Level 2 → 3"]);
+
+        let json = serde_json::to_string(&character).unwrap();
+        let restored: Character = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.advancement_history.len(), 2);
+    }
+
+    #[test]
+    fn test_characters_saved_before_advancement_still_load() {
+        let character = create_character("dnd5e", Some("Fighter"), 1);
+        let mut value = serde_json::to_value(&character).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("experience");
+        object.remove("advancement_history");
+
+        let restored: Character = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.experience, 0);
+        assert!(restored.advancement_history.is_empty());
+    }
+}
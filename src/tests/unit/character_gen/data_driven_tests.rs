@@ -0,0 +1,353 @@
+//! Data-Driven Character Generator Unit Tests
+//!
+//! Tests for systems loaded from declarative definitions including:
+//! - Parsing YAML and TOML definitions
+//! - Point buy, rolled and fixed attribute generation
+//! - Option bonuses, traits and starting kits
+//! - Derived stat formulas
+//! - Registry aliases and user overrides of built-in systems
+
+use crate::core::character_gen::{
+    definition::SystemDefinition, systems::data_driven::DataDrivenGenerator, GameSystem,
+    GenerationOptions, GeneratorRegistry, SystemGenerator, TraitType,
+};
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+const TEST_SYSTEM: &str = r#"
+id: test_system
+name: Test System
+description: A small system for tests
+aliases: ["testy"]
+max_level: 5
+names: ["Vex"]
+attributes:
+  - { name: Brawn, min: 1, max: 6 }
+  - { name: Wits, min: 1, max: 6 }
+attribute_modifier: "value - 3"
+generation:
+  - method: fixed
+    value: 2
+  - method: point_buy
+    points: 4
+    base: 1
+    max: 5
+  - method: roll
+    dice: "1d6"
+skills:
+  - { name: Melee, formula: "brawn_mod + 1" }
+  - { name: Lore, base: 1 }
+classes:
+  - name: Bruiser
+    description: Hits things
+    attributes: { Brawn: 2 }
+    skills: { Melee: 2 }
+    derived: { Health: 3 }
+    traits:
+      - { name: Tough, description: "Shrugs off blows" }
+  - name: Sage
+    attributes: { Wits: 2 }
+    kit: books
+backgrounds:
+  - { name: Urchin, description: "Grew up on the streets" }
+kits:
+  default:
+    - { name: Rope, category: Tool }
+  bruiser:
+    - { name: Club, category: Weapon }
+  books:
+    - { name: Tome }
+derived:
+  - name: Health
+    formula: "brawn * 2 + level"
+  - name: Guard
+    formula: "health / 2 + melee"
+"#;
+
+fn create_test_definition() -> SystemDefinition {
+    SystemDefinition::from_yaml_str(TEST_SYSTEM).expect("test definition should be valid")
+}
+
+fn create_test_generator() -> DataDrivenGenerator {
+    DataDrivenGenerator::new(create_test_definition()).unwrap()
+}
+
+fn create_default_options() -> GenerationOptions {
+    GenerationOptions {
+        system: Some("test_system".to_string()),
+        include_equipment: true,
+        ..Default::default()
+    }
+}
+
+fn note_value(notes: &str, name: &str) -> i32 {
+    notes
+        .lines()
+        .find_map(|line| line.strip_prefix(&format!("{name}: ")))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| panic!("missing {name} in notes: {notes}"))
+}
+
+// ============================================================================
+// Definition Tests
+// ============================================================================
+
+#[cfg(test)]
+mod definitions {
+    use super::*;
+
+    #[test]
+    fn test_parse_toml_definition() {
+        let toml = r#"
+id = "tiny"
+name = "Tiny"
+
+[[attributes]]
+name = "Body"
+
+[[generation]]
+method = "array"
+values = [3]
+
+[[derived]]
+name = "Wounds"
+formula = "body + 1"
+"#;
+        let definition = SystemDefinition::from_toml_str(toml).unwrap();
+        let generator = DataDrivenGenerator::new(definition).unwrap();
+        let character = generator.generate(&GenerationOptions::default()).unwrap();
+
+        assert_eq!(character.attributes["Body"].base, 3);
+        assert_eq!(character.notes, "Wounds: 4");
+        assert_eq!(character.system, GameSystem::Custom("tiny".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_definitions_rejected() {
+        let unknown_kit = TEST_SYSTEM.replace("kit: books", "kit: scrolls");
+        assert!(SystemDefinition::from_yaml_str(&unknown_kit).is_err());
+
+        // Derived stats may only use stats listed before them
+        let out_of_order = TEST_SYSTEM.replace("brawn * 2 + level", "guard");
+        assert!(SystemDefinition::from_yaml_str(&out_of_order).is_err());
+
+        let bad_id = TEST_SYSTEM.replace("id: test_system", "id: Test System");
+        assert!(SystemDefinition::from_yaml_str(&bad_id).is_err());
+    }
+
+    #[test]
+    fn test_load_dir_skips_invalid_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("good.yaml"), TEST_SYSTEM).unwrap();
+        std::fs::write(dir.path().join("bad.yaml"), "id: [").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let definitions = SystemDefinition::load_dir(dir.path());
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].id, "test_system");
+    }
+}
+
+// ============================================================================
+// Generation Tests
+// ============================================================================
+
+#[cfg(test)]
+mod generation {
+    use super::*;
+
+    #[test]
+    fn test_fixed_attributes_with_class_bonus() {
+        let generator = create_test_generator();
+        let options = GenerationOptions {
+            class: Some("bruiser".to_string()),
+            level: Some(3),
+            ..create_default_options()
+        };
+
+        let character = generator.generate(&options).unwrap();
+        assert_eq!(character.class.as_deref(), Some("Bruiser"));
+        assert_eq!(character.name, "Vex");
+        assert_eq!(character.level, 3);
+
+        let brawn = &character.attributes["Brawn"];
+        assert_eq!(brawn.base, 4);
+        assert_eq!(brawn.modifier, 1);
+        assert_eq!(character.attributes["Wits"].base, 2);
+
+        // brawn_mod + 1 + class bonus 2
+        assert_eq!(character.skills["Melee"], 4);
+        assert_eq!(character.skills["Lore"], 1);
+
+        // 4 * 2 + 3 + class bonus 3, then 14 / 2 + 4
+        assert_eq!(note_value(&character.notes, "Health"), 14);
+        assert_eq!(note_value(&character.notes, "Guard"), 11);
+    }
+
+    #[test]
+    fn test_point_buy_spends_budget_within_caps() {
+        let generator = create_test_generator();
+        let options = GenerationOptions {
+            class: Some("Sage".to_string()),
+            point_buy: Some(3),
+            ..create_default_options()
+        };
+
+        for _ in 0..20 {
+            let character = generator.generate(&options).unwrap();
+            let brawn = character.attributes["Brawn"].base;
+            let wits = character.attributes["Wits"].base;
+            // Base 1, Sage +2 Wits, 3 points spent with a cap of 5
+            assert_eq!(brawn + wits, 7);
+            assert!((1..=5).contains(&brawn));
+            assert!((3..=5).contains(&wits));
+        }
+    }
+
+    #[test]
+    fn test_random_stats_rolls_within_bounds() {
+        let generator = create_test_generator();
+        let options = GenerationOptions {
+            random_stats: true,
+            ..create_default_options()
+        };
+
+        for _ in 0..20 {
+            let character = generator.generate(&options).unwrap();
+            for attr in character.attributes.values() {
+                assert!((1..=6).contains(&attr.base));
+            }
+        }
+    }
+
+    #[test]
+    fn test_level_clamped_to_max() {
+        let generator = create_test_generator();
+        let options = GenerationOptions {
+            level: Some(12),
+            ..create_default_options()
+        };
+
+        let character = generator.generate(&options).unwrap();
+        assert_eq!(character.level, 5);
+    }
+
+    #[test]
+    fn test_traits_and_equipment_from_options() {
+        let generator = create_test_generator();
+        let options = GenerationOptions {
+            class: Some("Bruiser".to_string()),
+            background: Some("Urchin".to_string()),
+            ..create_default_options()
+        };
+
+        let character = generator.generate(&options).unwrap();
+        assert!(character
+            .traits
+            .iter()
+            .any(|t| t.name == "Bruiser" && t.trait_type == TraitType::Class));
+        assert!(character
+            .traits
+            .iter()
+            .any(|t| t.name == "Tough" && t.trait_type == TraitType::Class));
+        assert!(character
+            .traits
+            .iter()
+            .any(|t| t.name == "Urchin" && t.trait_type == TraitType::Background));
+
+        let items: Vec<&str> = character
+            .equipment
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(items, ["Rope", "Club"]);
+
+        let sage_kit = generator.starting_equipment(Some("Sage"));
+        assert_eq!(sage_kit.len(), 2);
+        assert_eq!(sage_kit[1].name, "Tome");
+    }
+
+    #[test]
+    fn test_unknown_option_rejected() {
+        let generator = create_test_generator();
+        let options = GenerationOptions {
+            class: Some("Wizard".to_string()),
+            ..create_default_options()
+        };
+
+        assert!(generator.validate_options(&options).is_err());
+    }
+}
+
+// ============================================================================
+// Registry Tests
+// ============================================================================
+
+#[cfg(test)]
+mod registry {
+    use super::*;
+
+    #[test]
+    fn test_bundled_systems_registered() {
+        let registry = GeneratorRegistry::new();
+        for id in [
+            "bitd",
+            "mothership",
+            "traveller",
+            "savage_worlds",
+            "year_zero",
+            "cypher",
+        ] {
+            let system = registry.resolve(id);
+            let info = registry
+                .get_system_info(&system)
+                .unwrap_or_else(|| panic!("{id} should be registered"));
+            assert_eq!(info.id, id);
+
+            let character = registry
+                .generate(&GenerationOptions {
+                    system: Some(id.to_string()),
+                    include_equipment: true,
+                    ..Default::default()
+                })
+                .unwrap_or_else(|e| panic!("{id} failed to generate: {e}"));
+            assert!(!character.attributes.is_empty());
+            assert!(!character.notes.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_resolve_aliases() {
+        let registry = GeneratorRegistry::new();
+        assert_eq!(
+            registry.resolve("Blades in the Dark"),
+            GameSystem::Custom("bitd".to_string())
+        );
+        assert_eq!(
+            registry.resolve("SWADE"),
+            GameSystem::Custom("savage_worlds".to_string())
+        );
+        assert_eq!(registry.resolve("dnd5e"), GameSystem::DnD5e);
+    }
+
+    #[test]
+    fn test_user_definition_overrides_builtin() {
+        let dir = tempfile::tempdir().unwrap();
+        let override_coc = TEST_SYSTEM
+            .replace("id: test_system", "id: coc")
+            .replace("name: Test System", "name: House Cthulhu");
+        std::fs::write(dir.path().join("coc.yaml"), override_coc).unwrap();
+
+        let mut registry = GeneratorRegistry::new();
+        assert_eq!(registry.load_dir(dir.path()), 1);
+
+        let info = registry
+            .get_system_info(&GameSystem::CallOfCthulhu)
+            .unwrap();
+        assert_eq!(info.name, "House Cthulhu");
+        assert_eq!(info.classes, ["Bruiser", "Sage"]);
+        assert_eq!(registry.resolve("testy"), GameSystem::CallOfCthulhu);
+    }
+}
@@ -27,6 +27,18 @@
 //! - Derived statistics (HP, Sanity, Magic Points)
 //! - Backstory generation elements
 //!
+//! ### Data-Driven Systems (`data_driven_tests`)
+//! - YAML/TOML definition parsing and validation
+//! - Fixed, rolled and point-buy attributes with option bonuses
+//! - Derived stat formulas, traits and starting kits
+//! - Bundled systems, aliases and user overrides
+//!
+//! ### Advancement (`advancement_tests`)
+//! - D&D 5e / PF2e level up: hit points, features, ability increases and feats
+//! - World of Darkness experience spends
+//! - Fate milestones and Call of Cthulhu development checks
+//! - Advancement history and rejected requests
+//!
 //! ## Running Tests
 //!
 //! ```bash
@@ -41,8 +53,16 @@
 //!
 //! # Run Call of Cthulhu tests only
 //! cargo test tests::unit::character_gen::coc_tests
+//!
+//! # Run data-driven system tests only
+//! cargo test tests::unit::character_gen::data_driven_tests
+//!
+//! # Run advancement tests only
+//! cargo test tests::unit::character_gen::advancement_tests
 //! ```
 
 mod dnd5e_tests;
 mod pf2e_tests;
 mod coc_tests;
+mod data_driven_tests;
+mod advancement_tests;
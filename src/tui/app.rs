@@ -14,6 +14,7 @@ use ratatui::{
 use tokio::sync::mpsc;
 
 use super::events::{Action, AppEvent, AreaFocus, Focus, Notification, NotificationLevel};
+use crate::core::session_manager::GameSession;
 use super::layout::AppLayout;
 use super::services::Services;
 use super::sidebar::SidebarState;
@@ -25,13 +26,18 @@ use super::views::command_palette::{
     build_command_registry, CommandPaletteState, PaletteResult,
 };
 use super::views::dice_modal::DiceRollerState;
+use super::views::vault_unlock::{UnlockResult, VaultUnlockState};
 use super::views::generation::GenerationState;
 use super::views::library::LibraryState;
 use super::views::archetypes::ArchetypeViewState;
 use super::views::audit::AuditViewState;
+use super::views::bestiary::BestiaryViewState;
+use super::views::calendar::CalendarViewState;
 use super::views::locations::LocationViewState;
+use super::views::notes::NotesViewState;
 use super::views::npcs::NpcViewState;
 use super::views::personality::PersonalityState;
+use super::views::relationships::RelationshipsViewState;
 use super::views::settings::SettingsState;
 use super::views::usage::UsageViewState;
 use super::views::voice::VoiceViewState;
@@ -60,6 +66,12 @@ pub struct AppState {
     pub personality: PersonalityState,
     /// Combat tracker view state.
     pub combat: CombatViewState,
+    /// Session notes view state.
+    pub notes: NotesViewState,
+    /// In-game calendar view state.
+    pub calendar: CalendarViewState,
+    /// Relationship graph view state.
+    pub relationships: RelationshipsViewState,
     /// NPC management view state.
     pub npcs: NpcViewState,
     /// Usage dashboard view state.
@@ -72,6 +84,8 @@ pub struct AppState {
     pub voice: VoiceViewState,
     /// Archetype browser view state.
     pub archetypes: ArchetypeViewState,
+    /// Bestiary browser view state.
+    pub bestiary: BestiaryViewState,
     /// Active notifications (max 3 visible).
     pub notifications: Vec<Notification>,
     /// Monotonic counter for notification IDs.
@@ -82,6 +96,14 @@ pub struct AppState {
     pub dice_roller: Option<DiceRollerState>,
     /// Command palette state (Some when open).
     pub command_palette: Option<CommandPaletteState>,
+    /// Sessions left active by a crash, offered for resumption one at a time.
+    resume_prompt: Vec<GameSession>,
+    /// Credential vault unlock prompt (Some while the vault is locked at startup).
+    vault_prompt: Option<VaultUnlockState>,
+    /// Game session the combat tracker is mirrored into.
+    combat_session: Option<String>,
+    /// Combat tracker revision last written to the session (skips no-op syncs).
+    combat_synced: Option<u64>,
     /// Receiver for backend events.
     event_rx: mpsc::UnboundedReceiver<AppEvent>,
     /// Sender for pushing events from within the app.
@@ -109,17 +131,29 @@ impl AppState {
             generation: GenerationState::new(),
             personality: PersonalityState::new(),
             combat: CombatViewState::new(),
+            notes: NotesViewState::new(),
+            calendar: CalendarViewState::new(),
+            relationships: RelationshipsViewState::new(),
             npcs: NpcViewState::new(),
             usage: UsageViewState::new(),
             audit: AuditViewState::new(),
             locations: LocationViewState::new(),
             voice: VoiceViewState::new(),
             archetypes: ArchetypeViewState::new(),
+            bestiary: BestiaryViewState::new(),
             notifications: Vec::new(),
             notification_counter: 0,
             show_help: false,
             dice_roller: None,
             command_palette: None,
+            resume_prompt: services.session.interrupted_sessions(),
+            vault_prompt: services
+                .credentials
+                .vault()
+                .filter(|vault| !vault.is_unlocked())
+                .map(|vault| VaultUnlockState::new(vault.clone())),
+            combat_session: None,
+            combat_synced: None,
             event_rx,
             event_tx,
             services,
@@ -160,6 +194,9 @@ impl AppState {
             }
         }
 
+        // Clean exit: park live sessions so they aren't offered as crashed
+        self.services.session.pause_active_sessions();
+
         Ok(())
     }
 
@@ -168,6 +205,34 @@ impl AppState {
     fn handle_event(&mut self, event: AppEvent) {
         match event {
             AppEvent::Input(crossterm_event) => {
+                // Priority 0: Vault unlock, then crash-recovery prompt
+                if let Some(ref mut prompt) = self.vault_prompt {
+                    match prompt.handle_input(&crossterm_event) {
+                        UnlockResult::Consumed => {}
+                        UnlockResult::Unlocked => {
+                            self.vault_prompt = None;
+                            self.services.on_vault_unlocked();
+                            self.push_notification(
+                                "Credential vault unlocked".to_string(),
+                                NotificationLevel::Success,
+                            );
+                        }
+                        UnlockResult::Skipped => {
+                            self.vault_prompt = None;
+                            self.push_notification(
+                                "Vault locked: API keys and OAuth tokens unavailable".to_string(),
+                                NotificationLevel::Warning,
+                            );
+                        }
+                    }
+                    return;
+                }
+
+                if !self.resume_prompt.is_empty() {
+                    self.handle_resume_input(&crossterm_event);
+                    return;
+                }
+
                 // Priority 1: Command palette consumes all input when open
                 if let Some(ref mut palette) = self.command_palette {
                     match palette.handle_input(&crossterm_event) {
@@ -243,6 +308,31 @@ impl AppState {
             AppEvent::RagChunksRetrieved(chunks) => {
                 self.chat.set_rag_chunks(chunks);
             }
+            AppEvent::RagQuery(query) => {
+                self.chat.set_rag_query(query);
+            }
+            AppEvent::AssistantToolTrace(trace) => {
+                self.chat.append_tool_trace(&trace);
+            }
+            AppEvent::AssistantConfirm(summary) => {
+                self.chat.request_confirmation(summary);
+                if self.chat.awaiting_confirmation() {
+                    self.set_focus(Focus::Chat);
+                }
+            }
+            AppEvent::AddStatBlockToCombat {
+                block,
+                count,
+                hp_mode,
+            } => {
+                let names = self.combat.add_from_stat_block(&block, count, hp_mode);
+                self.sync_combat();
+                self.set_focus(Focus::Combat);
+                self.push_notification(
+                    format!("Added {} to combat", names.join(", ")),
+                    NotificationLevel::Success,
+                );
+            }
             AppEvent::AudioPlayback(ref event) => {
                 self.services.audio.update_state(event);
                 self.chat.on_audio_event(event);
@@ -254,6 +344,9 @@ impl AppState {
                 self.library
                     .handle_ingestion_event(ingest, &self.services);
             }
+            AppEvent::EmbeddingBackfill(progress) => {
+                self.library.handle_backfill_event(progress);
+            }
             ref oauth_event @ (AppEvent::OAuthFlowResult { .. }
             | AppEvent::DeviceFlowUpdate { .. }) => {
                 self.settings
@@ -286,15 +379,23 @@ impl AppState {
             Focus::Settings => self.settings.handle_input(event, &self.services),
             Focus::Generation => self.generation.handle_input(event, &self.services),
             Focus::Personality => self.personality.handle_input(event, &self.services),
-            Focus::Combat => self.combat.handle_input(event),
+            Focus::Combat => {
+                let consumed = self.combat.handle_input(event);
+                if consumed {
+                    self.sync_combat();
+                }
+                consumed
+            }
             Focus::Npcs => self.npcs.handle_input(event, &self.services),
             Focus::Usage => self.usage.handle_input(event, &self.services),
             Focus::Audit => self.audit.handle_input(event, &self.services),
             Focus::Locations => self.locations.handle_input(event, &self.services),
             Focus::Voice => self.voice.handle_input(event, &self.services),
             Focus::Archetypes => self.archetypes.handle_input(event, &self.services),
-            // Stub views
-            Focus::Notes => false,
+            Focus::Bestiary => self.bestiary.handle_input(event, &self.services),
+            Focus::Notes => self.notes.handle_input(event, &self.services),
+            Focus::Calendar => self.calendar.handle_input(event, &self.services),
+            Focus::Relationships => self.relationships.handle_input(event, &self.services),
         }
     }
 
@@ -424,7 +525,18 @@ impl AppState {
                 self.personality.load(&self.services);
             }
             Action::FocusCombat => self.set_focus(Focus::Combat),
-            Action::FocusNotes => self.set_focus(Focus::Notes),
+            Action::FocusNotes => {
+                self.set_focus(Focus::Notes);
+                self.notes.load(&self.services);
+            }
+            Action::FocusCalendar => {
+                self.set_focus(Focus::Calendar);
+                self.calendar.load(&self.services);
+            }
+            Action::FocusRelationships => {
+                self.set_focus(Focus::Relationships);
+                self.relationships.load(&self.services);
+            }
             Action::FocusNpcs => {
                 self.set_focus(Focus::Npcs);
                 self.npcs.load(&self.services);
@@ -437,6 +549,10 @@ impl AppState {
                 self.set_focus(Focus::Archetypes);
                 self.archetypes.load(&self.services);
             }
+            Action::FocusBestiary => {
+                self.set_focus(Focus::Bestiary);
+                self.bestiary.load(&self.services);
+            }
             Action::FocusVoice => {
                 self.set_focus(Focus::Voice);
                 self.voice.load(&self.services);
@@ -470,7 +586,9 @@ impl AppState {
             Action::ShowHelp => self.show_help = true,
             Action::CloseHelp => self.show_help = false,
             Action::OpenDiceRoller => {
-                self.dice_roller = Some(DiceRollerState::new());
+                let mut dice = DiceRollerState::new();
+                dice.load_tables(&self.services);
+                self.dice_roller = Some(dice);
             }
             Action::CloseDiceRoller => {
                 self.dice_roller = None;
@@ -524,6 +642,8 @@ impl AppState {
             Action::RefreshLocations => self.locations.load(&self.services),
             Action::RefreshVoice => self.voice.load(&self.services),
             Action::RefreshArchetypes => self.archetypes.load(&self.services),
+            Action::RefreshBestiary => self.bestiary.load(&self.services),
+            Action::CreateBackup => self.services.create_backup(),
             // Combat actions — handled internally by combat view keybindings
             Action::StartCombat | Action::EndCombat | Action::NextTurn => {}
         }
@@ -550,7 +670,11 @@ impl AppState {
             Focus::Locations => self.locations.load(&self.services),
             Focus::Voice => self.voice.load(&self.services),
             Focus::Archetypes => self.archetypes.load(&self.services),
-            Focus::Combat | Focus::Notes => {}
+            Focus::Bestiary => self.bestiary.load(&self.services),
+            Focus::Notes => self.notes.load(&self.services),
+            Focus::Calendar => self.calendar.load(&self.services),
+            Focus::Relationships => self.relationships.load(&self.services),
+            Focus::Combat => {}
         }
     }
 
@@ -575,6 +699,88 @@ impl AppState {
         }
     }
 
+    // ── Session recovery ────────────────────────────────────────────────
+
+    /// y/Enter resumes the offered session; n/Esc pauses it and moves on.
+    fn handle_resume_input(&mut self, event: &Event) {
+        let Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        else {
+            return;
+        };
+        let Some(session) = self.resume_prompt.first().cloned() else {
+            return;
+        };
+
+        match code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                // Only one session can drive the tracker; park the rest
+                for other in self.resume_prompt.drain(..).skip(1) {
+                    let _ = self.services.session.pause_session(&other.id);
+                }
+                self.combat_session = Some(session.id.clone());
+                if let Some(combat) = session.combat {
+                    self.combat.resume(combat);
+                    self.combat_synced = Some(self.combat.revision());
+                    self.set_focus(Focus::Combat);
+                }
+                self.push_notification(
+                    format!("Resumed session {}", session.session_number),
+                    NotificationLevel::Success,
+                );
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.resume_prompt.remove(0);
+                if let Err(e) = self.services.session.pause_session(&session.id) {
+                    log::warn!("Failed to pause session {}: {e}", session.id);
+                }
+                self.push_notification(
+                    format!("Session {} paused", session.session_number),
+                    NotificationLevel::Info,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Mirror the combat tracker into its game session so it is journaled.
+    ///
+    /// Attaches to the latest active session; while no session is running the
+    /// encounter is kept in memory only and written once one starts.
+    fn sync_combat(&mut self) {
+        let revision = self.combat.revision();
+        if self.combat_synced == Some(revision) {
+            return;
+        }
+        let Some(combat) = self.combat.combat_state() else {
+            return;
+        };
+
+        let sessions = &self.services.session;
+        let session_id = match self
+            .combat_session
+            .clone()
+            .filter(|id| sessions.get_session(id).is_some())
+            .or_else(|| sessions.latest_active_session().map(|s| s.id))
+        {
+            Some(id) => id,
+            None => {
+                log::debug!("No active session; combat not journaled");
+                return;
+            }
+        };
+
+        if let Err(e) = sessions.set_combat(&session_id, combat.clone()) {
+            log::warn!("Failed to save combat to session {session_id}: {e}");
+            return;
+        }
+        self.combat_session = Some(session_id);
+        self.combat_synced = Some(revision);
+    }
+
     /// Tick: decrement notification TTLs, dismiss expired, poll async data.
     fn on_tick(&mut self) {
         for n in &mut self.notifications {
@@ -594,6 +800,13 @@ impl AppState {
         self.locations.poll();
         self.voice.poll();
         self.archetypes.poll();
+        self.bestiary.poll();
+        self.notes.poll();
+        self.calendar.poll();
+        self.relationships.poll();
+        if let Some(ref mut dice) = self.dice_roller {
+            dice.poll();
+        }
     }
 
     // ── Rendering ───────────────────────────────────────────────────────
@@ -629,6 +842,14 @@ impl AppState {
         if let Some(ref palette) = self.command_palette {
             palette.render(frame, area);
         }
+
+        if let Some(session) = self.resume_prompt.first() {
+            self.render_resume_prompt(frame, area, session);
+        }
+
+        if let Some(ref prompt) = self.vault_prompt {
+            prompt.render(frame, area);
+        }
     }
 
     fn render_content(&self, frame: &mut Frame, area: Rect) {
@@ -646,47 +867,13 @@ impl AppState {
             Focus::Locations => self.locations.render(frame, area),
             Focus::Voice => self.voice.render(frame, area),
             Focus::Archetypes => self.archetypes.render(frame, area),
-            // Remaining stub views
-            other => self.render_stub_view(frame, area, other),
+            Focus::Bestiary => self.bestiary.render(frame, area),
+            Focus::Notes => self.notes.render(frame, area),
+            Focus::Calendar => self.calendar.render(frame, area),
+            Focus::Relationships => self.relationships.render(frame, area),
         }
     }
 
-    fn render_stub_view(&self, frame: &mut Frame, area: Rect, focus: Focus) {
-        let block = Block::default()
-            .title(format!(" {} ", focus.label()))
-            .title_alignment(Alignment::Center)
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::PRIMARY));
-
-        let inner = block.inner(area);
-        frame.render_widget(block, area);
-
-        let lines = vec![
-            Line::raw(""),
-            Line::from(Span::styled(
-                format!("{} {}", focus.icon(), focus.label()),
-                Style::default()
-                    .fg(theme::ACCENT)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::raw(""),
-            Line::from(Span::styled(
-                "Coming soon",
-                Style::default().fg(theme::TEXT_MUTED),
-            )),
-            Line::raw(""),
-            Line::from(Span::styled(
-                "This view is under construction.",
-                Style::default().fg(theme::TEXT_DIM),
-            )),
-        ];
-
-        frame.render_widget(
-            Paragraph::new(lines).alignment(Alignment::Center),
-            inner,
-        );
-    }
-
     fn render_status_bar(&self, frame: &mut Frame, area: Rect) {
         let llm_status = if self.chat.is_streaming() {
             Span::styled("streaming", Style::default().fg(theme::PRIMARY_LIGHT))
@@ -765,6 +952,49 @@ impl AppState {
         frame.render_widget(Paragraph::new(lines), notification_area);
     }
 
+    fn render_resume_prompt(&self, frame: &mut Frame, area: Rect, session: &GameSession) {
+        let modal = centered_rect(50, 30, area);
+
+        let detail = match session.combat {
+            Some(ref combat) => format!(
+                "  Combat in progress: round {}, {} combatants",
+                combat.round,
+                combat.combatants.len()
+            ),
+            None => "  No combat in progress".to_string(),
+        };
+
+        let lines = vec![
+            Line::raw(""),
+            Line::from(Span::styled(
+                format!(
+                    "  Session {} was still running when the app last closed.",
+                    session.session_number
+                ),
+                Style::default().fg(theme::TEXT),
+            )),
+            Line::raw(""),
+            Line::from(Span::styled(detail, Style::default().fg(theme::TEXT_MUTED))),
+            Line::raw(""),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled("y", Style::default().fg(theme::PRIMARY_LIGHT).bold()),
+                Span::raw(" resume   "),
+                Span::styled("n", Style::default().fg(theme::PRIMARY_LIGHT).bold()),
+                Span::raw(" pause it"),
+            ]),
+        ];
+
+        let block = Block::default()
+            .title(" Resume Session? ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::ACCENT));
+
+        frame.render_widget(Clear, modal);
+        frame.render_widget(Paragraph::new(lines).block(block), modal);
+    }
+
     fn render_help_modal(&self, frame: &mut Frame, area: Rect) {
         let modal = centered_rect(60, 80, area);
 
@@ -776,7 +1006,7 @@ impl AppState {
             ("1-6", "Jump to view by number"),
             ("Ctrl+P", "Open command palette"),
             ("Ctrl+B", "Toggle sidebar collapse/expand"),
-            ("Ctrl+D", "Open dice roller"),
+            ("Ctrl+D", "Open dice roller (@name rolls a table)"),
             ("Ctrl+C", "Force quit"),
             ("Esc", "Close modal / focus main"),
             ("", ""),
@@ -797,6 +1027,7 @@ impl AppState {
             ("/pause /resume /stop", "Playback controls"),
             ("/volume <0-100>", "Set volume"),
             ("/voices", "List voice providers"),
+            ("y / n", "Approve / decline an assistant action"),
             ("", ""),
             ("Library View:", ""),
             ("a", "Ingest document"),
@@ -836,8 +1067,41 @@ impl AppState {
             ("h", "Heal combatant"),
             ("D", "Damage combatant"),
             ("c", "Add condition"),
+            ("x", "Remove newest condition"),
+            ("i", "Set initiative"),
+            ("u", "Undo last action"),
+            ("Ctrl+R", "Redo"),
             ("e", "End combat"),
             ("", ""),
+            ("Notes View:", ""),
+            ("a", "Add note"),
+            ("e/Enter", "Edit note (Ctrl+S saves)"),
+            ("d", "Delete note"),
+            ("p", "Pin / unpin"),
+            ("l", "Link NPC / location"),
+            ("s/c/t", "Cycle session / category / tag filter"),
+            ("/", "Fuzzy search"),
+            ("x", "Export session to Markdown"),
+            ("", ""),
+            ("Calendar View:", ""),
+            ("h/l j/k", "Move by day / week"),
+            ("[/] {/}", "Previous / next month, year"),
+            ("t", "Jump to current date"),
+            (".", "Set current date to selection"),
+            ("+/-", "Advance current date one day"),
+            ("a", "Add event on selected day"),
+            ("d", "Delete selected event"),
+            ("c/s", "Cycle calendar / campaign"),
+            ("", ""),
+            ("Relationships View:", ""),
+            ("Enter/f", "Focus on entity / whole campaign"),
+            ("+/-", "Graph depth around focus"),
+            ("t", "Cycle relationship type filter"),
+            ("p", "Player view (hide secrets)"),
+            ("i", "Show ended relationships"),
+            ("Tab", "Switch entities / relationships"),
+            ("a/e/d", "Add / edit / delete relationship"),
+            ("", ""),
             ("NPC View:", ""),
             ("a", "Add NPC"),
             ("e", "Edit selected NPC"),
@@ -845,6 +1109,13 @@ impl AppState {
             ("Enter", "Toggle detail"),
             ("s", "Search NPCs"),
             ("", ""),
+            ("Bestiary View:", ""),
+            ("/", "Query (e.g. undead cr 3-5 from mm)"),
+            ("j/k", "Navigate list"),
+            ("PgUp/PgDn", "Scroll stat block"),
+            ("c", "Add to combat (Tab: avg/rolled HP)"),
+            ("r", "Refresh data"),
+            ("", ""),
             ("Voice View:", ""),
             ("Tab", "Switch panels"),
             ("j/k", "Navigate list"),
@@ -932,18 +1203,18 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_focus_next_cycles_14() {
+    fn test_focus_next_cycles_17() {
         let mut f = Focus::Chat;
-        for _ in 0..14 {
+        for _ in 0..17 {
             f = f.next();
         }
         assert_eq!(f, Focus::Chat); // Full cycle
     }
 
     #[test]
-    fn test_focus_prev_cycles_14() {
+    fn test_focus_prev_cycles_17() {
         let mut f = Focus::Chat;
-        for _ in 0..14 {
+        for _ in 0..17 {
             f = f.prev();
         }
         assert_eq!(f, Focus::Chat); // Full cycle
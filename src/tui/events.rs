@@ -1,3 +1,4 @@
+use crate::core::storage::embedding_index::BackfillProgress;
 use crate::database::{ChatMessageRecord, NpcConversation, NpcRecord};
 
 /// Events flowing through the Elm-architecture event loop.
@@ -41,6 +42,8 @@ pub enum AppEvent {
         library_item_id: String,
         phase: IngestionProgressKind,
     },
+    /// Progress of the background embedding backfill.
+    EmbeddingBackfill(BackfillProgressKind),
     /// NPC conversation loaded from database.
     NpcConversationLoaded {
         npc: NpcRecord,
@@ -48,6 +51,18 @@ pub enum AppEvent {
     },
     /// RAG context chunks retrieved for the chat pane.
     RagChunksRetrieved(Vec<RagChunkDisplay>),
+    /// Search query used for RAG retrieval, shown in the RAG pane.
+    RagQuery(RagQueryDisplay),
+    /// Add copies of a bestiary creature to the combat tracker.
+    AddStatBlockToCombat {
+        block: Box<crate::ingestion::ttrpg::StatBlockData>,
+        count: usize,
+        hp_mode: crate::core::session::combat::HitPointMode,
+    },
+    /// The GM assistant executed (or was refused) a tool call.
+    AssistantToolTrace(crate::core::assistant::ToolTrace),
+    /// The GM assistant wants to run a state-changing tool; awaiting y/n.
+    AssistantConfirm(String),
     /// Request to quit the application.
     Quit,
 }
@@ -65,6 +80,17 @@ pub struct RagChunkDisplay {
     pub preview: String,
 }
 
+/// The search query behind the chunks in the RAG pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RagQueryDisplay {
+    /// Query sent to the search index.
+    pub query: String,
+    /// Whether the query was rewritten from a follow-up message.
+    pub rewritten: bool,
+    /// Whether vectors were unavailable and only keyword search ran.
+    pub keyword_only: bool,
+}
+
 /// Progress phases during document ingestion.
 #[derive(Debug, Clone)]
 pub enum IngestionProgressKind {
@@ -78,10 +104,34 @@ pub enum IngestionProgressKind {
     Storing { stored: usize, total: usize },
     /// Ingestion completed successfully.
     Complete { chunk_count: usize },
+    /// The same file is already in the library under `title`.
+    Duplicate { title: String },
+    /// The file looks like a new version of library item `candidate_id`
+    /// (`reason` says why); nothing was ingested until the user chooses to
+    /// update that item or add a new one.
+    Similar {
+        candidate_id: String,
+        title: String,
+        reason: String,
+    },
     /// Ingestion failed.
     Error(String),
 }
 
+/// State of the background embedding backfill.
+#[derive(Debug, Clone)]
+pub enum BackfillProgressKind {
+    /// A batch was embedded (or failed and was skipped).
+    Running(BackfillProgress),
+    /// Every chunk lacking a vector was attempted.
+    Complete(BackfillProgress),
+    /// The run stopped early; it resumes on the next attempt.
+    Stopped {
+        progress: BackfillProgress,
+        error: String,
+    },
+}
+
 /// Updates from the background device-code polling loop.
 #[derive(Debug, Clone)]
 pub enum DeviceFlowUpdateKind {
@@ -113,9 +163,12 @@ pub enum Action {
     // Navigation — new views
     FocusCombat,
     FocusNotes,
+    FocusCalendar,
     FocusNpcs,
     FocusLocations,
+    FocusRelationships,
     FocusArchetypes,
+    FocusBestiary,
     FocusVoice,
     FocusUsage,
     FocusAudit,
@@ -158,6 +211,10 @@ pub enum Action {
     RefreshLocations,
     RefreshVoice,
     RefreshArchetypes,
+    RefreshBestiary,
+
+    // Data
+    CreateBackup,
 
     // Combat
     StartCombat,
@@ -178,9 +235,12 @@ pub enum Focus {
     Notes,
     // World group
     Campaign,
+    Calendar,
     Npcs,
     Locations,
+    Relationships,
     Archetypes,
+    Bestiary,
     // Tools group
     Generation,
     Voice,
@@ -224,9 +284,12 @@ impl SidebarGroup {
             SidebarGroup::Session => &[Focus::Chat, Focus::Combat, Focus::Notes],
             SidebarGroup::World => &[
                 Focus::Campaign,
+                Focus::Calendar,
                 Focus::Npcs,
                 Focus::Locations,
+                Focus::Relationships,
                 Focus::Archetypes,
+                Focus::Bestiary,
             ],
             SidebarGroup::Tools => &[Focus::Generation, Focus::Voice],
             SidebarGroup::System => &[
@@ -242,16 +305,19 @@ impl SidebarGroup {
 
 impl Focus {
     /// All focus variants in sidebar display order.
-    pub const ALL: [Focus; 14] = [
+    pub const ALL: [Focus; 17] = [
         // Session
         Focus::Chat,
         Focus::Combat,
         Focus::Notes,
         // World
         Focus::Campaign,
+        Focus::Calendar,
         Focus::Npcs,
         Focus::Locations,
+        Focus::Relationships,
         Focus::Archetypes,
+        Focus::Bestiary,
         // Tools
         Focus::Generation,
         Focus::Voice,
@@ -269,9 +335,12 @@ impl Focus {
             Focus::Combat => "Combat",
             Focus::Notes => "Notes",
             Focus::Campaign => "Campaign",
+            Focus::Calendar => "Calendar",
             Focus::Npcs => "NPCs",
             Focus::Locations => "Locations",
+            Focus::Relationships => "Relationships",
             Focus::Archetypes => "Archetypes",
+            Focus::Bestiary => "Bestiary",
             Focus::Generation => "Generation",
             Focus::Voice => "Voice",
             Focus::Settings => "Settings",
@@ -289,9 +358,12 @@ impl Focus {
             Focus::Combat => "⚔",
             Focus::Notes => "📝",
             Focus::Campaign => "🗺",
+            Focus::Calendar => "📅",
             Focus::Npcs => "👤",
             Focus::Locations => "🏰",
+            Focus::Relationships => "🕸",
             Focus::Archetypes => "📖",
+            Focus::Bestiary => "🐉",
             Focus::Generation => "🎲",
             Focus::Voice => "🔊",
             Focus::Settings => "⚙",
@@ -306,9 +378,13 @@ impl Focus {
     pub fn group(self) -> SidebarGroup {
         match self {
             Focus::Chat | Focus::Combat | Focus::Notes => SidebarGroup::Session,
-            Focus::Campaign | Focus::Npcs | Focus::Locations | Focus::Archetypes => {
-                SidebarGroup::World
-            }
+            Focus::Campaign
+            | Focus::Calendar
+            | Focus::Npcs
+            | Focus::Locations
+            | Focus::Relationships
+            | Focus::Archetypes
+            | Focus::Bestiary => SidebarGroup::World,
             Focus::Generation | Focus::Voice => SidebarGroup::Tools,
             Focus::Settings
             | Focus::Library
@@ -335,9 +411,12 @@ impl Focus {
             Focus::Combat => Action::FocusCombat,
             Focus::Notes => Action::FocusNotes,
             Focus::Campaign => Action::FocusCampaign,
+            Focus::Calendar => Action::FocusCalendar,
             Focus::Npcs => Action::FocusNpcs,
             Focus::Locations => Action::FocusLocations,
+            Focus::Relationships => Action::FocusRelationships,
             Focus::Archetypes => Action::FocusArchetypes,
+            Focus::Bestiary => Action::FocusBestiary,
             Focus::Generation => Action::FocusGeneration,
             Focus::Voice => Action::FocusVoice,
             Focus::Settings => Action::FocusSettings,
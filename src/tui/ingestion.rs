@@ -1,20 +1,51 @@
 //! Async ingestion pipeline orchestrator for TUI.
 //!
-//! Extracts text from a document, chunks it semantically, optionally
-//! generates embeddings, and stores the chunks in SurrealDB — sending
-//! progress events through the TUI event channel at each phase.
+//! Extracts text from a document, classifies and chunks it with the
+//! TTRPG-aware pipeline, optionally generates embeddings, and stores the
+//! chunks in SurrealDB — sending progress events through the TUI event
+//! channel at each phase. Chunks carry their element type, section path,
+//! game system and filterable attributes (challenge rating, spell level,
+//! damage and creature types). Stat blocks
+//! found in the extracted pages are stored in the bestiary along the way, and
+//! random tables are imported into the `RandomTableEngine`.
+//!
+//! Library items are keyed by the file's content hash: importing a file that
+//! is already in the library is reported as a duplicate, and re-importing a
+//! changed file (an errata printing, say) diffs its pages against the last
+//! ingestion so that only the chunks of changed pages are re-embedded and
+//! rewritten; chunks of unchanged pages keep their IDs and embeddings, and
+//! those of removed pages are deleted. A changed file saved under a new name
+//! is matched to its library item by title or by the pages they share, and
+//! the user chooses whether to update that item or add a new one.
 
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use surrealdb::engine::local::Db;
+use surrealdb::Surreal;
 use tokio::sync::mpsc;
 
+use crate::core::campaign::random_table::{collect_random_tables, RandomTableEngine};
 use crate::core::search::embeddings::EmbeddingProvider;
-use crate::core::storage::ingestion::{ingest_chunks, ChunkData};
-use crate::core::storage::models::update_library_item_status;
+use crate::core::storage::bestiary::{collect_stat_blocks, store_stat_blocks};
+use crate::core::storage::embedding_index::get_index_state;
+use crate::core::storage::ingestion::{
+    apply_chunk_changes, delete_library_chunks, find_items_sharing_pages, get_chunk_count,
+    get_chunk_hashes, get_page_hashes, ingest_chunks, set_document_hashes, ChunkChange, ChunkData,
+};
+use crate::core::storage::models::{
+    create_library_item, find_library_item_by_hash, find_library_item_by_path,
+    find_library_item_by_title, get_library_item, get_library_item_by_slug, update_library_item,
+    update_library_item_status, LibraryItem,
+};
 use crate::core::storage::surrealdb::SurrealStorage;
-use crate::ingestion::chunker::SemanticChunker;
+use crate::core::storage::StorageError;
+use crate::ingestion::hash::hash_file;
+use crate::ingestion::incremental::{match_chunks, page_hashes, text_hash, ChunkMatch, PageDiff};
 use crate::ingestion::kreuzberg_extractor::DocumentExtractor;
+use crate::ingestion::slugs::generate_source_slug;
+use crate::ingestion::ttrpg_pipeline::TTRPGPipeline;
 use crate::tui::events::{AppEvent, IngestionProgressKind};
 
 /// Batch size for embedding generation.
@@ -27,12 +58,18 @@ const EMBEDDING_BATCH_SIZE: usize = 32;
 ///
 /// If `embedding_provider` is `None`, chunks are stored without embeddings.
 ///
+/// If the library item was ingested before, its pages are diffed against the
+/// previous ingestion: an unchanged document is left as is, and otherwise only
+/// the chunks of changed pages are embedded and stored (see
+/// [`chunk_and_store`]).
+///
 /// Returns the number of chunks stored on success.
 pub async fn run_ingestion_pipeline(
     file_path: PathBuf,
     library_item_id: String,
     content_type: String,
     storage: SurrealStorage,
+    random_tables: Arc<RandomTableEngine>,
     embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
     event_tx: mpsc::UnboundedSender<AppEvent>,
 ) -> Result<usize, String> {
@@ -65,74 +102,429 @@ pub async fn run_ingestion_pipeline(
         .await
         .map_err(|e| format!("Extraction failed: {e}"))?;
 
-    // ── 2. Chunk ─────────────────────────────────────────────────────────
-    let chunker = SemanticChunker::new();
-    let source_id = &library_item_id;
-
-    let content_chunks = if let Some(ref pages) = extracted.pages {
-        let page_tuples: Vec<(u32, String)> = pages
+    let page_tuples: Option<Vec<(u32, String)>> = extracted.pages.as_ref().map(|pages| {
+        pages
             .iter()
             .map(|p| (p.page_number as u32, p.content.clone()))
-            .collect();
-        chunker.chunk_with_pages(&page_tuples, source_id)
-    } else {
-        chunker.chunk_text(&extracted.content, source_id)
+            .collect()
+    });
+
+    let stored_item = get_library_item(db, &library_item_id).await.ok().flatten();
+    let source_title = match stored_item {
+        Some(ref item) => item.title.clone(),
+        None => file_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+    let harvest_pages = match page_tuples {
+        Some(ref pages) => pages.clone(),
+        None => vec![(1, extracted.content.clone())],
+    };
+
+    // ── Diff against the previous ingestion ──────────────────────────────
+    let hash_path = file_path.clone();
+    let file_hash = tokio::task::spawn_blocking(move || hash_file(&hash_path))
+        .await
+        .map_err(|e| format!("Hashing failed: {e}"))?
+        .map_err(|e| format!("Hashing failed: {e}"))?;
+    let new_page_hashes: Vec<(u32, String)> = harvest_pages
+        .iter()
+        .map(|(page, _)| *page)
+        .zip(page_hashes(&harvest_pages))
+        .collect();
+
+    let previous = match get_page_hashes(db, &library_item_id).await {
+        Ok(hashes) => hashes.map(|pages| PreviousIngestion::new(pages, &new_page_hashes)),
+        Err(e) => {
+            log::warn!("Could not read page hashes for {source_title}: {e} — re-ingesting in full");
+            None
+        }
+    };
+
+    if let Some(ref previous) = previous {
+        if previous.diff.unchanged {
+            log::info!("No page of {source_title} changed — keeping the stored chunks");
+            set_document_hashes(db, &library_item_id, &file_hash, new_page_hashes)
+                .await
+                .map_err(|e| format!("Storage failed: {e}"))?;
+            update_library_item_status(db, &library_item_id, "ready", None)
+                .await
+                .map_err(|e| format!("Storage failed: {e}"))?;
+            let chunk_count = get_chunk_count(db, &library_item_id)
+                .await
+                .map_err(|e| format!("Storage failed: {e}"))?;
+            send_progress(IngestionProgressKind::Complete { chunk_count });
+            return Ok(chunk_count);
+        }
+        log::info!(
+            "{} of {} pages of {source_title} changed ({} removed)",
+            previous.diff.changed.len(),
+            new_page_hashes.len(),
+            previous.diff.removed
+        );
+    }
+
+    // ── 2. Harvest stat blocks (best-effort) ─────────────────────────────
+    let stat_blocks = collect_stat_blocks(&harvest_pages, &source_title);
+    if !stat_blocks.is_empty() {
+        match store_stat_blocks(db, &library_item_id, stat_blocks).await {
+            Ok(count) => log::info!("Added {count} stat blocks to the bestiary from {source_title}"),
+            Err(e) => log::warn!("Failed to store stat blocks for {source_title}: {e}"),
+        }
+    }
+
+    // ── 3. Import random tables (best-effort) ────────────────────────────
+    let tables = collect_random_tables(&harvest_pages);
+    match random_tables
+        .import_library_tables(&library_item_id, &source_title, tables)
+        .await
+    {
+        Ok(report) => {
+            if !report.imported.is_empty() || !report.skipped.is_empty() {
+                log::info!(
+                    "Imported {} random tables from {source_title} ({} skipped for incomplete coverage)",
+                    report.imported.len(),
+                    report.skipped.len()
+                );
+            }
+        }
+        Err(e) => log::warn!("Failed to import random tables for {source_title}: {e}"),
+    }
+
+    // ── 4-7. Chunk, embed and store ──────────────────────────────────────
+    let document = PagedDocument {
+        library_item_id: library_item_id.clone(),
+        source_title: source_title.clone(),
+        content_type,
+        pages: page_tuples,
+        text: extracted.content.clone(),
+        game_system_id: stored_item.and_then(|item| item.game_system_id),
     };
+    let inserted = chunk_and_store(
+        db,
+        document,
+        previous,
+        embedding_provider.as_ref(),
+        &send_progress,
+    )
+    .await?;
 
-    let chunk_count = content_chunks.len();
-    send_progress(IngestionProgressKind::Chunking { chunk_count });
+    if let Err(e) = set_document_hashes(db, &library_item_id, &file_hash, new_page_hashes).await {
+        log::warn!("Failed to record content hashes for {source_title}: {e}");
+    }
 
-    // ── 3. Convert to ChunkData ──────────────────────────────────────────
-    let mut chunk_data: Vec<ChunkData> = content_chunks
+    // Update page_count from extraction metadata
+    if extracted.page_count > 0 {
+        let _ = db
+            .query("UPDATE type::thing('library_item', $id) SET page_count = $pages, updated_at = time::now()")
+            .bind(("id", library_item_id.clone()))
+            .bind(("pages", extracted.page_count as i32))
+            .await;
+    }
+
+    send_progress(IngestionProgressKind::Complete {
+        chunk_count: inserted,
+    });
+
+    Ok(inserted)
+}
+
+/// A document's extracted text, ready to chunk and store.
+struct PagedDocument {
+    library_item_id: String,
+    source_title: String,
+    content_type: String,
+    /// Extracted pages; `None` for formats without pages
+    pages: Option<Vec<(u32, String)>>,
+    /// Full text, chunked when there are no pages
+    text: String,
+    /// Game system assigned to the library item, which wins over detection
+    game_system_id: Option<String>,
+}
+
+/// The pages recorded by the previous ingestion of a document, and how the
+/// new version differs from them.
+struct PreviousIngestion {
+    /// `(page number, text hash)` pairs, in page order
+    pages: Vec<(u32, String)>,
+    diff: PageDiff,
+}
+
+impl PreviousIngestion {
+    fn new(pages: Vec<(u32, String)>, new_pages: &[(u32, String)]) -> Self {
+        let old: Vec<String> = pages.iter().map(|(_, hash)| hash.clone()).collect();
+        let new: Vec<String> = new_pages.iter().map(|(_, hash)| hash.clone()).collect();
+        let diff = PageDiff::compute(&old, &new);
+        Self { pages, diff }
+    }
+}
+
+/// Chunk a document, embed the new chunks and store them.
+///
+/// On a first ingestion every chunk is new. Chunks may run across pages, so
+/// each records the range of pages its text comes from. On a re-ingestion a
+/// stored chunk is kept (with its ID and embedding, moved to its new pages)
+/// only if every page in its range is unchanged and the new document yields
+/// the same chunk there; any other stored chunk is deleted, unless its text
+/// reappears in a new chunk, which then reuses it rather than being
+/// embedded again.
+///
+/// Returns the number of chunks the library item has afterwards.
+async fn chunk_and_store(
+    db: &Surreal<Db>,
+    document: PagedDocument,
+    previous: Option<PreviousIngestion>,
+    embedding_provider: Option<&Arc<dyn EmbeddingProvider>>,
+    send_progress: &dyn Fn(IngestionProgressKind),
+) -> Result<usize, String> {
+    let PagedDocument {
+        library_item_id,
+        source_title,
+        content_type,
+        pages,
+        text,
+        game_system_id,
+    } = document;
+
+    // Documents without pages are diffed as a single page 1
+    let page_numbers: Vec<u32> = match pages {
+        Some(ref pages) => pages.iter().map(|(page, _)| *page).collect(),
+        None => vec![1],
+    };
+
+    // ── 4. Classify and chunk ────────────────────────────────────────────
+    // Column reflow and classification are CPU-bound; keep them off the
+    // async runtime. The whole document is classified so that chunks of
+    // changed pages get the same section paths as in a full ingestion.
+    let source_id = library_item_id.clone();
+    let processed = tokio::task::spawn_blocking(move || {
+        let pipeline = TTRPGPipeline::new();
+        match pages {
+            Some(pages) => pipeline.process(&pages, &source_id),
+            None => pipeline.process_text(&text, &source_id),
+        }
+    })
+    .await
+    .map_err(|e| format!("Chunking failed: {e}"))?;
+
+    let chunks = processed.chunks;
+    send_progress(IngestionProgressKind::Chunking {
+        chunk_count: chunks.len(),
+    });
+
+    // Record the detected system unless the user (or a watched folder's
+    // config) already set one; an assigned system also wins for the chunks
+    let game_system_id =
+        game_system_id.or_else(|| processed.game_system.map(|s| s.as_str().to_string()));
+    if let Some(system) = processed.game_system {
+        let result = db
+            .query(
+                "UPDATE type::thing('library_item', $id) \
+                 SET game_system = $name, game_system_id = $system_id \
+                 WHERE game_system_id = NONE",
+            )
+            .bind(("id", library_item_id.clone()))
+            .bind(("name", system.display_name().to_string()))
+            .bind(("system_id", system.as_str().to_string()))
+            .await;
+        if let Err(e) = result {
+            log::warn!("Failed to record game system for {source_title}: {e}");
+        }
+    }
+
+    // ── 5. Convert to ChunkData ──────────────────────────────────────────
+    let mut chunk_data: Vec<ChunkData> = chunks
         .into_iter()
-        .map(|cc| ChunkData {
-            content: cc.content,
-            content_type: content_type.clone(),
-            page_number: cc.page_number.map(|p| p as i32),
-            section_path: cc.section.clone(),
-            chapter_title: cc.chapter_title,
-            section_title: cc.subsection_title,
-            chunk_type: Some(cc.chunk_type),
-            semantic_keywords: if cc.semantic_keywords.is_empty() {
-                None
-            } else {
-                Some(cc.semantic_keywords)
-            },
-            ..Default::default()
+        .map(|tc| {
+            let cc = tc.chunk;
+            let attributes = tc.attributes;
+            let content_hash = text_hash(&cc.content);
+            ChunkData {
+                content: cc.content,
+                content_type: content_type.clone(),
+                page_number: cc.page_number.map(|p| p as i32),
+                page_start: cc.page_range.map(|(first, _)| first as i32),
+                page_end: cc.page_range.map(|(_, last)| last as i32),
+                section_path: tc.section_path,
+                chapter_title: cc.chapter_title,
+                section_title: cc.section,
+                chunk_type: Some(cc.chunk_type),
+                semantic_keywords: if cc.semantic_keywords.is_empty() {
+                    None
+                } else {
+                    Some(cc.semantic_keywords)
+                },
+                content_hash: Some(content_hash),
+                game_system: game_system_id.clone(),
+                challenge_rating: attributes.challenge_rating,
+                spell_level: attributes.spell_level.map(i32::from),
+                damage_types: if attributes.damage_types.is_empty() {
+                    None
+                } else {
+                    Some(attributes.damage_types)
+                },
+                creature_types: if attributes.creature_types.is_empty() {
+                    None
+                } else {
+                    Some(attributes.creature_types)
+                },
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    // Keep the stored chunks whose pages are all unchanged; replace those
+    // that draw text from a changed or removed page, reusing any whose text
+    // survived the edit. A first ingestion (or one from before page hashes
+    // were recorded) replaces any stored chunks.
+    let reuse: Option<(Vec<Option<String>>, ChunkMatch)> = match previous {
+        Some(previous) => {
+            let new_positions: HashMap<u32, usize> = page_numbers
+                .iter()
+                .enumerate()
+                .map(|(i, &page)| (page, i))
+                .collect();
+            let old_positions: HashMap<u32, usize> = previous
+                .pages
+                .iter()
+                .enumerate()
+                .map(|(i, (page, _))| (*page, i))
+                .collect();
+
+            // Stored chunks that can be kept, by content hash and the
+            // positions their pages moved to
+            let stored = get_chunk_hashes(db, &library_item_id)
+                .await
+                .map_err(|e| format!("Storage failed: {e}"))?;
+            let mut keepable: HashMap<(String, usize, usize), VecDeque<String>> = HashMap::new();
+            for chunk in &stored {
+                let range = chunk
+                    .page_start
+                    .or(chunk.page_number)
+                    .zip(chunk.page_end.or(chunk.page_number));
+                let moved = range.and_then(|(first, last)| {
+                    let first = old_positions.get(&u32::try_from(first).ok()?)?;
+                    let last = old_positions.get(&u32::try_from(last).ok()?)?;
+                    previous.diff.map_range(*first, *last)
+                });
+                if let (Some((first, last)), Some(hash)) = (moved, chunk.content_hash.clone()) {
+                    keepable
+                        .entry((hash, first, last))
+                        .or_default()
+                        .push_back(chunk.id.clone());
+                }
+            }
+
+            // A new chunk is kept only if none of its pages changed
+            let kept: Vec<Option<String>> = chunk_data
+                .iter()
+                .map(|chunk| {
+                    let range = chunk.page_start.zip(chunk.page_end).and_then(|(first, last)| {
+                        let first = *new_positions.get(&(first as u32))?;
+                        let last = *new_positions.get(&(last as u32))?;
+                        Some((first, last))
+                    })?;
+                    if previous.diff.touches_changed(range.0, range.1) {
+                        return None;
+                    }
+                    let hash = chunk.content_hash.clone()?;
+                    keepable
+                        .get_mut(&(hash, range.0, range.1))
+                        .and_then(VecDeque::pop_front)
+                })
+                .collect();
+
+            let kept_ids: HashSet<&str> = kept.iter().flatten().map(String::as_str).collect();
+            let stale: Vec<(String, Option<String>)> = stored
+                .iter()
+                .filter(|chunk| !kept_ids.contains(chunk.id.as_str()))
+                .map(|chunk| (chunk.id.clone(), chunk.content_hash.clone()))
+                .collect();
+            // Kept chunks get no hash, so they match nothing
+            let new_hashes: Vec<String> = chunk_data
+                .iter()
+                .zip(&kept)
+                .map(|(c, kept)| match kept {
+                    Some(_) => String::new(),
+                    None => c.content_hash.clone().unwrap_or_default(),
+                })
+                .collect();
+            let matched = match_chunks(&stale, &new_hashes);
+            let kept_count = kept_ids.len();
+            log::info!(
+                "Re-ingesting {source_title}: {} chunks kept, {} reused, {} new, {} removed",
+                kept_count,
+                matched.reused_count(),
+                chunk_data.len() - kept_count - matched.reused_count(),
+                matched.removed.len()
+            );
+            Some((kept, matched))
+        }
+        None => {
+            delete_library_chunks(db, &library_item_id)
+                .await
+                .map_err(|e| format!("Storage failed: {e}"))?;
+            None
+        }
+    };
+    let to_embed: Vec<usize> = (0..chunk_data.len())
+        .filter(|&i| {
+            reuse.as_ref().is_none_or(|(kept, matched)| {
+                kept[i].is_none() && matched.reused[i].is_none()
+            })
         })
         .collect();
 
-    // ── 4. Embed (optional) ──────────────────────────────────────────────
-    if let Some(ref provider) = embedding_provider {
-        let total = chunk_data.len();
+    // ── 6. Embed (optional) ──────────────────────────────────────────────
+    // While the library is re-embedded for a new model the live index still
+    // has the old dimension; the background job embeds these chunks instead.
+    let index_serves_provider = match embedding_provider {
+        Some(provider) => match get_index_state(db).await {
+            Ok(Some(state)) => state.serves(&provider.model_id(), provider.dimensions()),
+            Ok(None) => true,
+            Err(e) => {
+                log::warn!("Could not read embedding index state: {e}");
+                true
+            }
+        },
+        None => false,
+    };
+    if embedding_provider.is_some() && !index_serves_provider {
+        log::info!("Embedding index is being rebuilt — chunks will be embedded by the re-embed job");
+    }
+
+    if let Some(provider) = embedding_provider.filter(|_| index_serves_provider) {
+        let total = to_embed.len();
         send_progress(IngestionProgressKind::Embedding {
             processed: 0,
             total,
         });
 
-        let model_name = provider.name().to_string();
+        let model_name = provider.model_id();
         let mut processed = 0;
 
         for batch_start in (0..total).step_by(EMBEDDING_BATCH_SIZE) {
             let batch_end = (batch_start + EMBEDDING_BATCH_SIZE).min(total);
-            let texts: Vec<&str> = chunk_data[batch_start..batch_end]
+            let batch = &to_embed[batch_start..batch_end];
+            let texts: Vec<&str> = batch
                 .iter()
-                .map(|c| c.content.as_str())
+                .map(|&i| chunk_data[i].content.as_str())
                 .collect();
 
             match provider.embed_batch(&texts).await {
                 Ok(embeddings) => {
-                    for (i, embedding) in embeddings.into_iter().enumerate() {
-                        chunk_data[batch_start + i].embedding = Some(embedding);
-                        chunk_data[batch_start + i].embedding_model =
-                            Some(model_name.clone());
+                    for (&i, embedding) in batch.iter().zip(embeddings) {
+                        chunk_data[i].embedding = Some(embedding);
+                        chunk_data[i].embedding_model = Some(model_name.clone());
                     }
                 }
                 Err(e) => {
                     log::warn!(
                         "Embedding batch {batch_start}..{batch_end} failed: {e} — storing without vectors"
                     );
-                    // Continue without embeddings for this batch
+                    // Continue without embeddings; the backfill job embeds
+                    // these chunks later
                 }
             }
 
@@ -146,29 +538,33 @@ pub async fn run_ingestion_pipeline(
         );
     }
 
-    // ── 5. Store ─────────────────────────────────────────────────────────
+    // ── 7. Store ─────────────────────────────────────────────────────────
     let total = chunk_data.len();
     send_progress(IngestionProgressKind::Storing { stored: 0, total });
 
-    // ingest_chunks auto-sets library_item status to "ready" on success
-    let inserted = ingest_chunks(db, &library_item_id, chunk_data)
-        .await
-        .map_err(|e| format!("Storage failed: {e}"))?;
-
-    // Update page_count from extraction metadata
-    if extracted.page_count > 0 {
-        let _ = db
-            .query("UPDATE type::thing('library_item', $id) SET page_count = $pages, updated_at = time::now()")
-            .bind(("id", library_item_id.clone()))
-            .bind(("pages", extracted.page_count as i32))
-            .await;
+    // Both paths set the library_item status to "ready" on success
+    match reuse {
+        Some((kept, matched)) => {
+            let changes = kept
+                .into_iter()
+                .zip(matched.reused)
+                .zip(chunk_data)
+                .map(|((kept, reused), chunk)| match (kept, reused) {
+                    (Some(id), _) => ChunkChange::Keep {
+                        id,
+                        page_number: chunk.page_number,
+                        page_start: chunk.page_start,
+                        page_end: chunk.page_end,
+                    },
+                    (None, Some(id)) => ChunkChange::Reuse { id, chunk },
+                    (None, None) => ChunkChange::Create(chunk),
+                })
+                .collect();
+            apply_chunk_changes(db, &library_item_id, changes, matched.removed).await
+        }
+        None => ingest_chunks(db, &library_item_id, chunk_data).await,
     }
-
-    send_progress(IngestionProgressKind::Complete {
-        chunk_count: inserted,
-    });
-
-    Ok(inserted)
+    .map_err(|e| format!("Storage failed: {e}"))
 }
 
 /// Wrapper that runs the pipeline and handles errors by updating library item status.
@@ -177,6 +573,7 @@ pub async fn run_ingestion_with_error_handling(
     library_item_id: String,
     content_type: String,
     storage: SurrealStorage,
+    random_tables: Arc<RandomTableEngine>,
     embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
     event_tx: mpsc::UnboundedSender<AppEvent>,
 ) {
@@ -188,6 +585,7 @@ pub async fn run_ingestion_with_error_handling(
         library_item_id,
         content_type,
         storage,
+        random_tables,
         embedding_provider,
         event_tx.clone(),
     )
@@ -207,3 +605,593 @@ pub async fn run_ingestion_with_error_handling(
         }
     }
 }
+
+/// Build the `processing` library item for a document about to be ingested.
+///
+/// The title defaults to the file stem and the slug follows the title.
+pub fn new_library_item(
+    path: &Path,
+    title_override: Option<&str>,
+    content_type: &str,
+) -> LibraryItem {
+    let slug = generate_source_slug(path, title_override);
+    let title = title_override.map(|s| s.to_string()).unwrap_or_else(|| {
+        path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Untitled")
+            .to_string()
+    });
+    let file_ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let item = LibraryItem::builder(slug, title)
+        .file_path(path.to_string_lossy())
+        .file_type(file_ext)
+        .status("processing")
+        .content_category(content_type);
+
+    match std::fs::metadata(path) {
+        Ok(meta) => item.file_size(meta.len() as i64).build(),
+        Err(_) => item.build(),
+    }
+}
+
+/// Minimum length of a page's trimmed text for it to count when looking
+/// for library items that share pages with an import. Shorter pages (blank
+/// pages, "Notes" pages) are common to many books.
+const MIN_SIMILAR_PAGE_LEN: usize = 100;
+
+/// What an import should do with a library item that looks like an earlier
+/// version of the file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ImportTarget {
+    /// Re-ingest the item with the same file path; if there is none but an
+    /// item has the same title or shares most of its pages, stop with
+    /// [`ImportPlan::Similar`] so the user can choose.
+    #[default]
+    Auto,
+    /// Always create a new library item.
+    New,
+    /// Re-ingest the given library item from this file.
+    Update(String),
+}
+
+/// How an import was set up, given what is already in the library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportPlan {
+    /// The document is new; a library item was created for it.
+    New { library_item_id: String },
+    /// An earlier version of the file (same path, or the item the user
+    /// chose) is in the library and will be re-ingested incrementally.
+    Update { library_item_id: String },
+    /// A file with the same content is already in the library.
+    Duplicate { library_item_id: String, title: String },
+    /// The file of a library item was moved or renamed: the item now
+    /// points at the new path and keeps its chunks.
+    Relinked { library_item_id: String, title: String },
+    /// A library item from another file looks like an earlier version of
+    /// this one. Nothing was created; import again with
+    /// [`ImportTarget::Update`] or [`ImportTarget::New`].
+    Similar {
+        library_item_id: String,
+        title: String,
+        /// Why the item matched, e.g. "40 of 42 pages match"
+        reason: String,
+    },
+}
+
+impl ImportPlan {
+    /// ID of the library item the import uses (for `Similar`, the
+    /// candidate it might update).
+    pub fn library_item_id(&self) -> &str {
+        match self {
+            Self::New { library_item_id }
+            | Self::Update { library_item_id }
+            | Self::Duplicate {
+                library_item_id, ..
+            }
+            | Self::Relinked {
+                library_item_id, ..
+            }
+            | Self::Similar {
+                library_item_id, ..
+            } => library_item_id,
+        }
+    }
+}
+
+/// Find or create the library item for a document about to be ingested.
+///
+/// A file whose content hash matches an ingested item is a duplicate and is
+/// left alone, unless the item's own file is gone (it was moved or renamed):
+/// the item is then re-linked to the new path. Otherwise, depending on
+/// `target`, an existing item is reused
+/// (marked as processing) so that the pipeline re-ingests it incrementally,
+/// or a new item is created under a slug no other item uses.
+///
+/// With [`ImportTarget::Auto`] the item with the same file path is reused.
+/// A file saved under a new name (an errata PDF, say) is checked against the
+/// library by title and by the pages it shares with ingested items; a match
+/// is returned as [`ImportPlan::Similar`] without creating anything.
+pub async fn prepare_import(
+    db: &Surreal<Db>,
+    path: &Path,
+    title_override: Option<&str>,
+    content_type: &str,
+    target: ImportTarget,
+) -> Result<ImportPlan, String> {
+    let hash_path = path.to_path_buf();
+    let hash = tokio::task::spawn_blocking(move || hash_file(&hash_path))
+        .await
+        .map_err(|e| format!("Hashing failed: {e}"))?
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+
+    let storage_error = |e: StorageError| format!("Failed to look up library items: {e}");
+    let mut item = new_library_item(path, title_override, content_type);
+    if let Some(mut existing) = find_library_item_by_hash(db, &hash)
+        .await
+        .map_err(storage_error)?
+    {
+        let library_item_id = existing.id.clone().unwrap_or_default();
+        let moved = match existing.status.as_str() {
+            "missing" => true,
+            // The folder scan may not have noticed the old path is gone yet
+            "ready" => match existing.file_path {
+                Some(ref recorded) if Some(recorded) != item.file_path.as_ref() => {
+                    !tokio::fs::try_exists(recorded).await.unwrap_or(true)
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+        if moved {
+            existing.file_path = item.file_path;
+            existing.file_type = item.file_type;
+            existing.file_size = item.file_size;
+            existing.status = "ready".to_string();
+            existing.error_message = None;
+            update_library_item(db, &library_item_id, &existing)
+                .await
+                .map_err(|e| format!("Failed to update library item: {e}"))?;
+            return Ok(ImportPlan::Relinked {
+                library_item_id,
+                title: existing.title,
+            });
+        }
+        if existing.status == "ready" {
+            return Ok(ImportPlan::Duplicate {
+                library_item_id,
+                title: existing.title,
+            });
+        }
+    }
+
+    let previous = match target {
+        ImportTarget::New => None,
+        ImportTarget::Update(ref id) => Some(
+            get_library_item(db, id)
+                .await
+                .map_err(storage_error)?
+                .ok_or_else(|| format!("Library item not found: {id}"))?,
+        ),
+        ImportTarget::Auto => {
+            match find_library_item_by_path(db, item.file_path.as_deref().unwrap_or(""))
+                .await
+                .map_err(storage_error)?
+            {
+                Some(previous) => Some(previous),
+                None => {
+                    if let Some(similar) = find_similar_item(db, path, &item.title).await {
+                        return Ok(similar);
+                    }
+                    None
+                }
+            }
+        }
+    };
+
+    match previous {
+        Some(mut previous) => {
+            let library_item_id = previous
+                .id
+                .clone()
+                .ok_or_else(|| "Library item has no ID".to_string())?;
+            previous.file_path = item.file_path;
+            previous.file_type = item.file_type;
+            previous.file_size = item.file_size;
+            previous.content_category = item.content_category;
+            previous.status = item.status;
+            previous.error_message = None;
+            update_library_item(db, &library_item_id, &previous)
+                .await
+                .map_err(|e| format!("Failed to update library item: {e}"))?;
+            Ok(ImportPlan::Update { library_item_id })
+        }
+        None => {
+            item.slug = unique_slug(db, &item.slug).await.map_err(storage_error)?;
+            let item_id = create_library_item(db, &item)
+                .await
+                .map_err(|e| format!("Failed to create library item: {e}"))?;
+            let library_item_id = item_id
+                .strip_prefix("library_item:")
+                .unwrap_or(&item_id)
+                .to_string();
+            Ok(ImportPlan::New { library_item_id })
+        }
+    }
+}
+
+/// Look for a library item that `path` is probably a new version of.
+///
+/// An item sharing at least half of the file's pages wins; failing that, an
+/// item whose title matches `title` or the document's own title. Lookup is
+/// best-effort: if the file can't be extracted or the library can't be
+/// queried the import just goes ahead as a new item.
+async fn find_similar_item(db: &Surreal<Db>, path: &Path, title: &str) -> Option<ImportPlan> {
+    // OCR is too slow for a check; scanned books still match by title
+    let extracted = match DocumentExtractor::new()
+        .extract(path, None::<fn(f32, &str)>)
+        .await
+    {
+        Ok(extracted) => Some(extracted),
+        Err(e) => {
+            log::debug!(
+                "Could not extract {} to look for similar items: {e}",
+                path.display()
+            );
+            None
+        }
+    };
+
+    if let Some(ref extracted) = extracted {
+        let pages: Vec<(u32, String)> = match extracted.pages {
+            Some(ref pages) => pages
+                .iter()
+                .map(|p| (p.page_number as u32, p.content.clone()))
+                .collect(),
+            None => vec![(1, extracted.content.clone())],
+        };
+        let hashes: Vec<String> = pages
+            .iter()
+            .filter(|(_, text)| text.trim().len() >= MIN_SIMILAR_PAGE_LEN)
+            .map(|(_, text)| text_hash(text))
+            .collect();
+        let total = hashes.len();
+
+        if total > 0 {
+            match find_items_sharing_pages(db, hashes).await {
+                Ok(shared) => {
+                    let best = shared.into_iter().next().filter(|(_, n)| n * 2 >= total);
+                    if let Some((id, count)) = best {
+                        if let Ok(Some(item)) = get_library_item(db, &id).await {
+                            return Some(ImportPlan::Similar {
+                                library_item_id: id,
+                                title: item.title,
+                                reason: format!("{count} of {total} pages match"),
+                            });
+                        }
+                    }
+                }
+                Err(e) => log::warn!("Could not look for items sharing pages: {e}"),
+            }
+        }
+    }
+
+    let titles = std::iter::once(title.to_string())
+        .chain(extracted.and_then(|e| e.title))
+        .filter(|t| !t.trim().is_empty());
+    for candidate in titles {
+        match find_library_item_by_title(db, candidate.trim()).await {
+            Ok(Some(item)) => {
+                return Some(ImportPlan::Similar {
+                    library_item_id: item.id.clone().unwrap_or_default(),
+                    title: item.title,
+                    reason: "same title".to_string(),
+                });
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Could not look up library items by title: {e}"),
+        }
+    }
+    None
+}
+
+/// `slug`, or the first of `slug-2`, `slug-3`, ... that no library item uses.
+///
+/// Different books often share a file name, so a taken slug says nothing
+/// about whether the import is an update.
+async fn unique_slug(db: &Surreal<Db>, slug: &str) -> Result<String, StorageError> {
+    let mut candidate = slug.to_string();
+    let mut n = 2;
+    while get_library_item_by_slug(db, &candidate).await?.is_some() {
+        candidate = format!("{slug}-{n}");
+        n += 1;
+    }
+    Ok(candidate)
+}
+
+/// Create the library item for `path` and run the pipeline on it, without
+/// a UI. Returns how the import was set up and the number of chunks stored
+/// (for a duplicate or a re-linked item, the number the existing item has;
+/// for a similar item, nothing is ingested and the count is 0).
+///
+/// On failure the item is marked as errored, as in the TUI.
+pub async fn ingest_file(
+    path: &Path,
+    title_override: Option<&str>,
+    content_type: &str,
+    target: ImportTarget,
+    storage: SurrealStorage,
+    random_tables: Arc<RandomTableEngine>,
+    embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+) -> Result<(ImportPlan, usize), String> {
+    let plan = prepare_import(storage.db(), path, title_override, content_type, target).await?;
+    let item_id = plan.library_item_id().to_string();
+
+    match plan {
+        ImportPlan::Duplicate { .. } | ImportPlan::Relinked { .. } => {
+            let count = get_chunk_count(storage.db(), &item_id)
+                .await
+                .map_err(|e| format!("Failed to count chunks: {e}"))?;
+            return Ok((plan, count));
+        }
+        ImportPlan::Similar { .. } => return Ok((plan, 0)),
+        ImportPlan::New { .. } | ImportPlan::Update { .. } => {}
+    }
+
+    let storage_for_error = storage.clone();
+    match run_ingestion_pipeline(
+        path.to_path_buf(),
+        item_id.clone(),
+        content_type.to_string(),
+        storage,
+        random_tables,
+        embedding_provider,
+        event_tx,
+    )
+    .await
+    {
+        Ok(count) => Ok((plan, count)),
+        Err(error) => {
+            let db = storage_for_error.db();
+            let _ = update_library_item_status(db, &item_id, "error", Some(&error)).await;
+            Err(error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::search::embeddings::Result as EmbeddingResult;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Provider that records every text it is asked to embed.
+    #[derive(Default)]
+    struct RecordingProvider {
+        texts: Mutex<Vec<String>>,
+    }
+
+    impl RecordingProvider {
+        fn take(&self) -> Vec<String> {
+            std::mem::take(&mut *self.texts.lock().unwrap())
+        }
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for RecordingProvider {
+        async fn embed(&self, text: &str) -> EmbeddingResult<Vec<f32>> {
+            self.texts.lock().unwrap().push(text.to_string());
+            Ok(vec![0.1; 768])
+        }
+
+        async fn embed_batch(&self, texts: &[&str]) -> EmbeddingResult<Vec<Vec<f32>>> {
+            let mut out = Vec::new();
+            for text in texts {
+                out.push(self.embed(text).await?);
+            }
+            Ok(out)
+        }
+
+        fn dimensions(&self) -> usize {
+            768
+        }
+
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+    }
+
+    /// Ingest `pages` as the next version of `library_item:book`, as the
+    /// pipeline does after extraction.
+    async fn ingest(
+        db: &Surreal<Db>,
+        pages: &[(u32, &str)],
+        provider: &Arc<dyn EmbeddingProvider>,
+    ) {
+        let pages: Vec<(u32, String)> = pages.iter().map(|(n, t)| (*n, t.to_string())).collect();
+        let hashes: Vec<(u32, String)> = pages
+            .iter()
+            .map(|(page, _)| *page)
+            .zip(page_hashes(&pages))
+            .collect();
+        let previous = get_page_hashes(db, "book")
+            .await
+            .unwrap()
+            .map(|previous| PreviousIngestion::new(previous, &hashes));
+
+        let document = PagedDocument {
+            library_item_id: "book".to_string(),
+            source_title: "Book".to_string(),
+            content_type: "rules".to_string(),
+            text: String::new(),
+            pages: Some(pages),
+            game_system_id: None,
+        };
+        chunk_and_store(db, document, previous, Some(provider), &|_: IngestionProgressKind| {})
+            .await
+            .unwrap();
+        set_document_hashes(db, "book", "file-hash", hashes)
+            .await
+            .unwrap();
+    }
+
+    const QUARRY: &str = "Goblins lair in the old quarry beyond the river.";
+    const MILL: &str = "The mill still grinds grain for the village.";
+    const MILL_ERRATA: &str = "The mill burned down last winter and stands empty.";
+    const CHAPEL: &str = "A ghost haunts the chapel bell tower at night.";
+
+    /// A page long enough to fill a chunk of its own.
+    fn page(sentence: &str) -> String {
+        vec![sentence; 30].join(" ")
+    }
+
+    #[tokio::test]
+    async fn test_reingest_embeds_only_changed_pages() {
+        let temp = TempDir::new().unwrap();
+        let storage = SurrealStorage::new(temp.path().to_path_buf())
+            .await
+            .unwrap();
+        let db = storage.db();
+        db.query(
+            "CREATE library_item:book SET slug = 'book', title = 'Book', file_path = '/tmp/book.pdf'",
+        )
+        .await
+        .unwrap();
+        let recorder = Arc::new(RecordingProvider::default());
+        let provider: Arc<dyn EmbeddingProvider> = recorder.clone();
+        let (quarry, mill, errata, chapel) =
+            (page(QUARRY), page(MILL), page(MILL_ERRATA), page(CHAPEL));
+
+        let pages = [(1, quarry.as_str()), (2, mill.as_str()), (3, chapel.as_str())];
+        ingest(db, &pages, &provider).await;
+        let first = get_chunk_hashes(db, "book").await.unwrap();
+        assert_eq!(recorder.take().len(), first.len());
+        // Chunks carry the overlap of the page before them
+        assert!(first
+            .iter()
+            .any(|c| (c.page_start, c.page_end) == (Some(1), Some(2))));
+
+        // Errata on page 2: only chunks drawing text from it are embedded
+        // again, including the one that runs on from page 1
+        let pages = [(1, quarry.as_str()), (2, errata.as_str()), (3, chapel.as_str())];
+        ingest(db, &pages, &provider).await;
+        let embedded = recorder.take();
+        assert!(!embedded.is_empty());
+        assert!(embedded.iter().all(|text| text.contains("burned down")));
+        assert!(embedded
+            .iter()
+            .any(|text| text.contains("quarry") && text.contains("burned down")));
+        let second = get_chunk_hashes(db, "book").await.unwrap();
+        assert_eq!(second[0].id, first[0].id);
+
+        // A page inserted up front moves the others back; removing the
+        // chapel page deletes the chunks drawing text from it
+        let pages = [(1, mill.as_str()), (2, quarry.as_str()), (3, errata.as_str())];
+        ingest(db, &pages, &provider).await;
+        let embedded = recorder.take();
+        assert!(embedded.iter().all(|text| text.contains("still grinds")));
+
+        // The chunk running from the quarry page onto the errata page is
+        // kept, one page further back
+        let stored = get_chunk_hashes(db, "book").await.unwrap();
+        let kept: Vec<_> = stored
+            .iter()
+            .filter_map(|c| second.iter().find(|s| s.id == c.id).map(|s| (s, c)))
+            .collect();
+        assert!(!kept.is_empty());
+        for (before, after) in kept {
+            assert_eq!(after.page_start, before.page_start.map(|p| p + 1));
+            assert_eq!(after.page_end, before.page_end.map(|p| p + 1));
+        }
+        assert!(stored.iter().all(|c| c.page_end <= Some(3)));
+        assert!(stored
+            .windows(2)
+            .all(|w| w[0].page_start <= w[1].page_start));
+    }
+
+    #[tokio::test]
+    async fn test_moved_file_relinks_missing_item() {
+        let temp = TempDir::new().unwrap();
+        let storage = SurrealStorage::new(temp.path().join("db")).await.unwrap();
+        let db = storage.db();
+        let moved = temp.path().join("renamed.md");
+        std::fs::write(&moved, QUARRY).unwrap();
+        db.query(
+            "CREATE library_item:book SET slug = 'book', title = 'Book', \
+             file_path = '/tmp/gone/book.md', status = 'missing', content_hash = $hash",
+        )
+        .bind(("hash", hash_file(&moved).unwrap()))
+        .await
+        .unwrap();
+
+        let plan = prepare_import(db, &moved, None, "rules", ImportTarget::Auto)
+            .await
+            .unwrap();
+        assert_eq!(
+            plan,
+            ImportPlan::Relinked {
+                library_item_id: "book".to_string(),
+                title: "Book".to_string()
+            }
+        );
+        let item = get_library_item(db, "book").await.unwrap().unwrap();
+        assert_eq!(item.status, "ready");
+        assert_eq!(item.file_path.as_deref(), Some(&*moved.to_string_lossy()));
+    }
+
+    #[tokio::test]
+    async fn test_renamed_file_offers_to_update_item_with_same_title() {
+        let temp = TempDir::new().unwrap();
+        let storage = SurrealStorage::new(temp.path().join("db")).await.unwrap();
+        let db = storage.db();
+        db.query(
+            "CREATE library_item:book SET slug = 'book', title = 'Book', \
+             file_path = '/tmp/old/book.md', status = 'ready'",
+        )
+        .await
+        .unwrap();
+        let errata = temp.path().join("Book.md");
+        std::fs::write(&errata, MILL_ERRATA).unwrap();
+
+        let plan = prepare_import(db, &errata, None, "rules", ImportTarget::Auto)
+            .await
+            .unwrap();
+        assert!(matches!(
+            plan,
+            ImportPlan::Similar { ref library_item_id, .. } if library_item_id == "book"
+        ));
+        assert!(find_library_item_by_path(db, &errata.to_string_lossy())
+            .await
+            .unwrap()
+            .is_none());
+
+        let plan = prepare_import(
+            db,
+            &errata,
+            None,
+            "rules",
+            ImportTarget::Update("book".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            plan,
+            ImportPlan::Update {
+                library_item_id: "book".to_string()
+            }
+        );
+        let item = get_library_item(db, "book").await.unwrap().unwrap();
+        assert_eq!(item.file_path.as_deref(), Some(&*errata.to_string_lossy()));
+    }
+}
@@ -1,34 +1,57 @@
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::{mpsc, RwLock};
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, WatchConfig};
 use crate::core::archetype::InMemoryArchetypeRegistry;
+use crate::core::assistant::{ToolContext, ToolRegistry};
+use crate::core::backup::{self, ArchiveOptions, ArchiveSource};
 use crate::core::budget::BudgetEnforcer;
+use crate::core::campaign::random_table::RandomTableEngine;
+use crate::core::campaign::relationships::RelationshipManager;
 use crate::core::search::embeddings::EmbeddingProvider;
+use crate::core::search::rerank::{create_reranker, Reranker};
 use crate::core::campaign_manager::CampaignManager;
+use crate::core::character_gen::CharacterGenerator;
 use crate::core::cost_predictor::CostPredictor;
 use crate::core::credentials::CredentialManager;
 use crate::core::llm::providers::{AuthMethod, ProviderConfig};
 use crate::core::llm::router::LLMRouter;
 use crate::core::location_gen::LocationGenerator;
 use crate::core::npc_gen::{InMemoryNpcIndexes, NPCGenerator};
+use crate::core::npc_memory::NpcMemoryManager;
 use crate::core::personality::application::PersonalityApplicationManager;
 use crate::core::personality_base::PersonalityStore;
 use crate::core::plot_manager::PlotManager;
 use crate::core::preprocess::pipeline::QueryPipeline;
 use crate::core::session_manager::SessionManager;
 use crate::core::session_summary::SessionSummarizer;
+use crate::core::storage::embedding_index::{
+    ensure_embedding_index, BackfillJob, BackfillProgress, IndexCheck, ReembedJob,
+};
+use crate::core::storage::models::{
+    get_library_item, list_library_items_under_path, update_library_item,
+    update_library_item_status,
+};
 use crate::core::storage::surrealdb::SurrealStorage;
 use crate::core::transcription::TranscriptionManager;
+use crate::core::vault::{self, CredentialVault, VaultError, VAULT_PASSPHRASE_ENV};
 use crate::core::voice::manager::VoiceManager;
 use crate::core::voice::queue::events::QueueEventEmitter;
 use crate::core::voice::queue::SynthesisQueue;
 use crate::database::Database;
+use crate::ingestion::ttrpg::GameSystem;
+use crate::ingestion::watch::{FolderScanner, IngestedFile, WatchedFile};
+use crate::oauth::storage::{migrate_tokens, FileTokenStorage, VaultTokenStorage};
 
 use super::audio::AudioPlayer;
+use super::ingestion::{
+    prepare_import, run_ingestion_with_error_handling, ImportPlan, ImportTarget,
+};
 
-use super::events::AppEvent;
+use super::events::{AppEvent, BackfillProgressKind, Notification, NotificationLevel};
 
 /// Centralized handle to all backend services.
 ///
@@ -59,14 +82,27 @@ pub struct Services {
     pub npc_generator: Arc<NPCGenerator>,
     pub campaign_manager: Arc<CampaignManager>,
     pub plot_manager: Arc<PlotManager>,
+    /// Entity relationships, hydrated per campaign from SQLite by the graph view
+    pub relationship_manager: Arc<RelationshipManager>,
     pub location_generator: Arc<LocationGenerator>,
 
     // ---- Phase 4 additions ----
     pub embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
+    /// Search reranker (cross-encoder or LLM judge), if configured
+    pub reranker: Option<Arc<dyn Reranker>>,
 
     // ---- Phase 7 additions ----
     pub input_validator: Arc<crate::core::input_validator::InputValidator>,
     pub search_analytics: Arc<crate::core::search_analytics::SearchAnalytics>,
+
+    // ---- Random tables (SQLite-backed) ----
+    pub random_tables: Arc<RandomTableEngine>,
+
+    // ---- GM assistant tools ----
+    pub assistant_tools: Arc<ToolRegistry>,
+
+    // ---- Long-term NPC memory ----
+    pub npc_memory: Arc<NpcMemoryManager>,
 }
 
 impl Services {
@@ -76,6 +112,26 @@ impl Services {
     pub async fn init(
         config: &AppConfig,
         event_tx: mpsc::UnboundedSender<AppEvent>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::build(config, event_tx, true).await
+    }
+
+    /// Initialize services for a one-shot command-line run.
+    ///
+    /// Same as [`Services::init`], but without background work that would be
+    /// cut off when the command exits (scheduled backups, automatic
+    /// re-embedding).
+    pub async fn init_headless(
+        config: &AppConfig,
+        event_tx: mpsc::UnboundedSender<AppEvent>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::build(config, event_tx, false).await
+    }
+
+    async fn build(
+        config: &AppConfig,
+        event_tx: mpsc::UnboundedSender<AppEvent>,
+        background: bool,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         let data_dir = config.data_dir();
         log::info!("Initializing services with data dir: {}", data_dir.display());
@@ -93,9 +149,15 @@ impl Services {
         let database = Database::new(&data_dir).await?;
         log::info!("SQLite database initialized");
 
-        // Credential manager (keyring)
-        let credentials = CredentialManager::new();
-        log::info!("Credential manager initialized");
+        // Credential manager (keyring, or the encrypted vault if configured)
+        let credentials = open_credentials(config)?;
+        if credentials.vault().is_some_and(|vault| vault.is_unlocked()) {
+            migrate_into_vault(&credentials).await;
+        }
+        log::info!(
+            "Credential manager initialized ({})",
+            credentials.backend_name()
+        );
 
         // ================================================================
         // Asset-backed registries (Phase 1 + 2)
@@ -154,12 +216,22 @@ impl Services {
         // Core gameplay services
         // ================================================================
 
-        let session = Arc::new(SessionManager::new());
+        // Live session state is journaled so a crash doesn't lose combat
+        let session = match SessionManager::open(data_dir.join("sessions.journal")) {
+            Ok(manager) => Arc::new(manager),
+            Err(e) => {
+                log::error!("Session journal unavailable (sessions won't survive restart): {e}");
+                Arc::new(SessionManager::new())
+            }
+        };
         let session_summarizer = Arc::new(SessionSummarizer::new());
         let campaign_manager = Arc::new(CampaignManager::with_data_dir(&data_dir));
         let plot_manager = Arc::new(PlotManager::new());
         let npc_generator = Arc::new(NPCGenerator::new());
         let location_generator = Arc::new(LocationGenerator::new());
+        // User system definitions override bundled and built-in generators
+        CharacterGenerator::set_user_systems_dir(data_dir.join("systems"));
+        let random_tables = Arc::new(RandomTableEngine::new(Arc::new(database.pool().clone())));
 
         // ================================================================
         // Budget and cost tracking
@@ -190,22 +262,137 @@ impl Services {
         // Embedding provider (Phase 4)
         // ================================================================
 
-        let embedding_provider: Option<Arc<dyn EmbeddingProvider>> = {
-            use crate::core::search::providers::ollama::OllamaEmbeddings;
-            let ollama = OllamaEmbeddings::new(
-                "http://localhost:11434",
-                "nomic-embed-text",
-                Some(768),
-            );
-            if ollama.health_check().await {
-                log::info!("Embedding provider: Ollama (nomic-embed-text, 768d)");
-                Some(Arc::new(ollama))
-            } else {
-                log::warn!("Ollama not available — embeddings disabled (chunks stored without vectors)");
+        let embedding_provider: Option<Arc<dyn EmbeddingProvider>> =
+            match crate::core::search::providers::create_provider(&config.embedding) {
+                Ok(provider) if provider.health_check().await => {
+                    log::info!(
+                        "Embedding provider: {} ({}, {}d)",
+                        config.embedding.provider,
+                        provider.model_id(),
+                        provider.dimensions()
+                    );
+                    Some(provider)
+                }
+                Ok(_) => {
+                    log::warn!(
+                        "Embedding provider {} ({}) not reachable at {} — embeddings disabled (chunks stored without vectors)",
+                        config.embedding.provider,
+                        config.embedding.model,
+                        config.embedding.endpoint.as_deref().unwrap_or("default endpoint")
+                    );
+                    None
+                }
+                Err(e) => {
+                    log::warn!("Embeddings unavailable ({}): {e}", config.embedding.provider);
+                    None
+                }
+            };
+
+        // Size the vector index for the active model; re-embed if it changed
+        if let Some(ref provider) = embedding_provider {
+            match ensure_embedding_index(storage.db(), &provider.model_id(), provider.dimensions()).await {
+                Ok(IndexCheck::Ready) => {}
+                Ok(IndexCheck::NeedsReembed { from, to, chunks }) if background => {
+                    log::info!(
+                        "Embedding model changed ({} -> {to}); re-embedding {chunks} chunks",
+                        from.as_deref().unwrap_or("unknown")
+                    );
+                    spawn_reembed_job(storage.clone_db(), provider.clone(), event_tx.clone());
+                }
+                Ok(IndexCheck::NeedsReembed { .. }) => {
+                    log::info!("Embedding model changed; re-embedding deferred (run `reindex`)");
+                }
+                Err(e) => log::error!("Failed to check embedding index: {e}"),
+            }
+
+            // Chunks stored without vectors (provider outages, documents
+            // ingested before a provider was configured) are embedded later
+            if background {
+                spawn_backfill_job(
+                    storage.clone_db(),
+                    provider.clone(),
+                    config.embedding.batch_size,
+                    config.embedding.backfill_requests_per_minute,
+                    event_tx.clone(),
+                );
+            }
+        }
+
+        // ================================================================
+        // Search reranker (optional)
+        // ================================================================
+
+        let reranker = match create_reranker(&config.rerank, &llm) {
+            Ok(Some(reranker)) => {
+                log::info!("Search reranker: {}", reranker.name());
+                Some(reranker)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                log::warn!("Search reranker unavailable (using fused order): {e}");
                 None
             }
         };
 
+        // ================================================================
+        // NPC memory
+        // ================================================================
+
+        let npc_memory = Arc::new(NpcMemoryManager::new(
+            storage.clone_db(),
+            llm.clone(),
+            embedding_provider.clone(),
+            config.npc_memory.clone(),
+        ));
+
+        // ================================================================
+        // Scheduled backups (optional)
+        // ================================================================
+
+        if config.backup.enabled && background {
+            let passphrase = config.backup.encrypt.then(|| backup::resolve_passphrase(&credentials));
+            match passphrase {
+                Some(None) => log::warn!(
+                    "Encrypted backups enabled but no passphrase is set — scheduled backups disabled"
+                ),
+                passphrase => {
+                    backup::spawn_scheduler(
+                        storage.clone_db(),
+                        database.pool().clone(),
+                        ArchiveSource::defaults(&data_dir, &AppConfig::config_path()),
+                        config.backup_dir(),
+                        Duration::from_secs(config.backup.interval_hours.max(1) * 3600),
+                        config.backup.keep,
+                        passphrase.flatten(),
+                    );
+                    log::info!(
+                        "Scheduled backups every {}h to {}",
+                        config.backup.interval_hours.max(1),
+                        config.backup_dir().display()
+                    );
+                }
+            }
+        }
+
+        // ================================================================
+        // Watched library folders (optional)
+        // ================================================================
+
+        if !config.watch.folders.is_empty() && background {
+            spawn_library_watcher(
+                config.watch.clone(),
+                storage.clone(),
+                random_tables.clone(),
+                embedding_provider.clone(),
+                event_tx.clone(),
+            );
+            log::info!(
+                "Watching {} library folders every {}s",
+                config.watch.folders.len(),
+                config.watch.interval_secs.max(MIN_WATCH_INTERVAL_SECS)
+            );
+        }
+
         log::info!("All services initialized");
 
         Ok(Self {
@@ -229,13 +416,29 @@ impl Services {
             npc_generator,
             campaign_manager,
             plot_manager,
+            relationship_manager: Arc::new(RelationshipManager::default()),
             location_generator,
             embedding_provider,
+            reranker,
             input_validator: Arc::new(crate::core::input_validator::InputValidator::new()),
             search_analytics: Arc::new(crate::core::search_analytics::SearchAnalytics::new()),
+            random_tables,
+            assistant_tools: Arc::new(ToolRegistry::with_defaults()),
+            npc_memory,
         })
     }
 
+    /// Backend handles for the GM assistant's tools.
+    pub fn assistant_context(&self) -> ToolContext {
+        ToolContext {
+            session: self.session.clone(),
+            storage: self.storage.clone(),
+            embedding_provider: self.embedding_provider.clone(),
+            reranker: self.reranker.clone(),
+            random_tables: self.random_tables.clone(),
+        }
+    }
+
     // ========================================================================
     // Provider CRUD
     // ========================================================================
@@ -312,6 +515,87 @@ impl Services {
         log::info!("Saved OAuth provider config: {provider_id} ({model})");
         Ok(())
     }
+
+    /// Finish startup after the credential vault is unlocked from the TUI.
+    ///
+    /// Pulls existing keyring secrets and OAuth tokens into the vault, then
+    /// re-adds saved providers whose API keys were unreadable while locked.
+    pub fn on_vault_unlocked(&self) {
+        let credentials = self.credentials.clone();
+        let mut llm = self.llm.clone();
+        tokio::spawn(async move {
+            migrate_into_vault(&credentials).await;
+
+            let config = AppConfig::load();
+            for (id, provider_config) in &config.llm.providers {
+                let provider_config = restore_provider_config(id, provider_config, &credentials);
+                if provider_config.auth_method() == AuthMethod::ApiKey {
+                    llm.add_provider(provider_config.create_provider()).await;
+                }
+            }
+        });
+    }
+
+    /// Archive every store in the background, reporting the result as a
+    /// notification. Location, rotation and encryption follow `[backup]`.
+    pub fn create_backup(&self) {
+        let config = AppConfig::load();
+        let tx = self.event_tx.clone();
+        let notify = move |message: String, level: NotificationLevel| {
+            let _ = tx.send(AppEvent::Notification(Notification {
+                id: 0,
+                message,
+                level,
+                ttl_ticks: 120,
+            }));
+        };
+
+        let passphrase = if config.backup.encrypt {
+            match backup::resolve_passphrase(&self.credentials) {
+                Some(p) => Some(p),
+                None => {
+                    notify(
+                        "Encrypted backups need a passphrase (keyring or TTTTRPS_BACKUP_PASSPHRASE)"
+                            .to_string(),
+                        NotificationLevel::Warning,
+                    );
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+
+        notify("Creating backup…".to_string(), NotificationLevel::Info);
+        let db = self.storage.clone_db();
+        let sqlite = self.database.pool().clone();
+        tokio::spawn(async move {
+            let sources = ArchiveSource::defaults(&config.data_dir(), &AppConfig::config_path());
+            let options = ArchiveOptions {
+                description: Some("Manual backup".to_string()),
+                passphrase,
+            };
+            let result = backup::backup_live(
+                db,
+                sqlite,
+                sources,
+                config.backup_dir(),
+                config.backup.keep,
+                options,
+            )
+            .await;
+            match result {
+                Ok(info) => notify(
+                    format!("Backup saved: {}", info.filename),
+                    NotificationLevel::Success,
+                ),
+                Err(e) => {
+                    log::error!("Backup failed: {e}");
+                    notify(format!("Backup failed: {e}"), NotificationLevel::Error);
+                }
+            }
+        });
+    }
 }
 
 /// Restore a `ProviderConfig` by injecting the API key from keyring.
@@ -329,6 +613,466 @@ fn restore_provider_config(
     }
 }
 
+/// Credential manager for the configured backend.
+///
+/// With the vault backend, the vault is opened, unlocked from
+/// `TTTTRPS_VAULT_PASSPHRASE` when set, and installed as the shared vault
+/// for OAuth token storage.
+pub fn open_credentials(config: &AppConfig) -> Result<CredentialManager, VaultError> {
+    if !config.credentials.uses_vault() {
+        return Ok(CredentialManager::new());
+    }
+
+    let vault = Arc::new(CredentialVault::open(config.vault_path())?);
+    if let Ok(passphrase) = std::env::var(VAULT_PASSPHRASE_ENV) {
+        let unlocked = if vault.is_initialized() {
+            vault.unlock(&passphrase)
+        } else {
+            vault.initialize(&passphrase)
+        };
+        if let Err(e) = unlocked {
+            log::warn!("Could not unlock vault from {VAULT_PASSPHRASE_ENV}: {e}");
+        }
+    }
+    vault::install_shared(vault.clone());
+    Ok(CredentialManager::with_vault(vault))
+}
+
+/// OAuth providers whose tokens move into the vault.
+const VAULT_OAUTH_PROVIDERS: [&str; 3] = ["claude", "gemini", "copilot"];
+
+/// Copy keyring secrets and OAuth tokens into an unlocked vault.
+///
+/// Plaintext token files are removed once copied; keyring entries are kept
+/// so switching back to the keyring backend still works.
+async fn migrate_into_vault(credentials: &CredentialManager) {
+    if let Err(e) = credentials.migrate_from_keyring() {
+        log::warn!("Keyring migration skipped: {e}");
+    }
+
+    let Some(vault_storage) = VaultTokenStorage::shared() else {
+        return;
+    };
+    if let Ok(file) = FileTokenStorage::app_data_path() {
+        match migrate_tokens(&file, &vault_storage, &VAULT_OAUTH_PROVIDERS, true).await {
+            Ok(0) => {}
+            Ok(n) => log::info!("Moved {n} OAuth tokens from file into vault"),
+            Err(e) => log::warn!("OAuth token file migration failed: {e}"),
+        }
+    }
+    #[cfg(feature = "keyring")]
+    {
+        let keyring = crate::oauth::storage::KeyringTokenStorage::new();
+        match migrate_tokens(&keyring, &vault_storage, &VAULT_OAUTH_PROVIDERS, false).await {
+            Ok(0) => {}
+            Ok(n) => log::info!("Copied {n} OAuth tokens from keyring into vault"),
+            Err(e) => log::warn!("OAuth token keyring migration failed: {e}"),
+        }
+    }
+}
+
+/// Re-embed the library with `provider` in the background, reporting progress
+/// as notifications. An interrupted run resumes on the next start.
+fn spawn_reembed_job(
+    db: Arc<surrealdb::Surreal<surrealdb::engine::local::Db>>,
+    provider: Arc<dyn EmbeddingProvider>,
+    tx: mpsc::UnboundedSender<AppEvent>,
+) {
+    let notify = move |message: String, level: NotificationLevel| {
+        let _ = tx.send(AppEvent::Notification(Notification {
+            id: 0,
+            message,
+            level,
+            ttl_ticks: 120,
+        }));
+    };
+
+    tokio::spawn(async move {
+        notify(
+            format!("Re-embedding library for {}…", provider.model_id()),
+            NotificationLevel::Info,
+        );
+
+        let mut next_report = 25;
+        let job = ReembedJob::new(db, provider);
+        let result = job
+            .run(|progress| {
+                let percent = progress.processed * 100 / progress.total.max(1);
+                if percent >= next_report && progress.processed < progress.total {
+                    next_report = percent - percent % 25 + 25;
+                    notify(
+                        format!("Re-embedding: {}/{} chunks", progress.processed, progress.total),
+                        NotificationLevel::Info,
+                    );
+                }
+            })
+            .await;
+
+        match result {
+            Ok(progress) => notify(
+                format!("Re-embedding complete ({} chunks)", progress.total),
+                NotificationLevel::Success,
+            ),
+            Err(e) => {
+                log::error!("Re-embedding failed: {e}");
+                notify(
+                    format!("Re-embedding stopped: {e} (resumes on next start)"),
+                    NotificationLevel::Error,
+                );
+            }
+        }
+    });
+}
+
+/// How often the embedding backfill looks for chunks without vectors.
+const BACKFILL_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Embed chunks stored without a vector in the background, now and whenever
+/// later ingestions leave some behind. Progress goes to the Library view;
+/// the job waits while the library is re-embedded for a new model.
+fn spawn_backfill_job(
+    db: Arc<surrealdb::Surreal<surrealdb::engine::local::Db>>,
+    provider: Arc<dyn EmbeddingProvider>,
+    batch_size: usize,
+    requests_per_minute: u32,
+    tx: mpsc::UnboundedSender<AppEvent>,
+) {
+    let job = BackfillJob::new(db, provider)
+        .with_batch_size(batch_size)
+        .with_requests_per_minute(requests_per_minute);
+
+    tokio::spawn(async move {
+        loop {
+            let pending = match job.is_ready().await {
+                Ok(true) => job.pending().await,
+                Ok(false) => Ok(0),
+                Err(e) => Err(e),
+            };
+            match pending {
+                Ok(0) => {}
+                Ok(pending) => {
+                    log::info!("Backfilling embeddings for {pending} chunks");
+                    let mut last = BackfillProgress::default();
+                    let result = job
+                        .run(|progress| {
+                            last = progress;
+                            let _ = tx.send(AppEvent::EmbeddingBackfill(
+                                BackfillProgressKind::Running(progress),
+                            ));
+                        })
+                        .await;
+
+                    let event = match result {
+                        Ok(progress) => {
+                            if progress.embedded > 0 {
+                                let _ = tx.send(AppEvent::Notification(Notification {
+                                    id: 0,
+                                    message: format!(
+                                        "Embedded {} chunks that were stored without vectors",
+                                        progress.embedded
+                                    ),
+                                    level: NotificationLevel::Success,
+                                    ttl_ticks: 120,
+                                }));
+                            }
+                            BackfillProgressKind::Complete(progress)
+                        }
+                        Err(e) => {
+                            log::warn!("Embedding backfill stopped: {e}");
+                            BackfillProgressKind::Stopped {
+                                progress: last,
+                                error: e.to_string(),
+                            }
+                        }
+                    };
+                    let _ = tx.send(AppEvent::EmbeddingBackfill(event));
+                }
+                Err(e) => log::warn!("Failed to check for chunks without embeddings: {e}"),
+            }
+
+            tokio::time::sleep(BACKFILL_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Shortest time between scans of the watched library folders.
+const MIN_WATCH_INTERVAL_SECS: u64 = 5;
+
+/// Watch the configured library folders: each scan marks items whose file
+/// has gone as missing, then ingests the new and changed documents one at a
+/// time so a large drop of files doesn't compete for the embedding provider.
+fn spawn_library_watcher(
+    watch: WatchConfig,
+    storage: SurrealStorage,
+    random_tables: Arc<RandomTableEngine>,
+    embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
+    tx: mpsc::UnboundedSender<AppEvent>,
+) {
+    let interval = Duration::from_secs(watch.interval_secs.max(MIN_WATCH_INTERVAL_SECS));
+    let mut scanner = FolderScanner::new(watch.folders, watch.default_content_type);
+
+    tokio::spawn(async move {
+        // Documents the library already holds are not queued again on restart
+        let mut known = Some(ingested_files(storage.db(), scanner.roots()).await);
+
+        loop {
+            let missing = mark_missing_files(storage.db(), scanner.roots()).await;
+
+            // Walking a large or remote tree blocks; keep it off the runtime
+            let seed = known.take();
+            let scan = tokio::task::spawn_blocking(move || {
+                if let Some(seed) = seed {
+                    scanner.seed(seed);
+                }
+                let files = scanner.scan();
+                (scanner, files)
+            })
+            .await;
+            let files = match scan {
+                Ok((returned, files)) => {
+                    scanner = returned;
+                    files
+                }
+                Err(e) => {
+                    log::error!("Library folder scan failed, watching stopped: {e}");
+                    return;
+                }
+            };
+
+            let (mut ingested, mut relinked) = (0, 0);
+            for file in files {
+                match ingest_watched_file(file, &storage, &random_tables, &embedding_provider, &tx)
+                    .await
+                {
+                    WatchedOutcome::Ingested => ingested += 1,
+                    WatchedOutcome::Relinked => relinked += 1,
+                    WatchedOutcome::Skipped => {}
+                }
+            }
+            // A file renamed since the last scan was marked missing above and
+            // found again under its new name
+            let missing = missing.saturating_sub(relinked);
+
+            if ingested > 0 || relinked > 0 || missing > 0 {
+                let mut parts = Vec::new();
+                if ingested > 0 {
+                    parts.push(format!("{ingested} documents ingested"));
+                }
+                if relinked > 0 {
+                    parts.push(format!("{relinked} moved"));
+                }
+                if missing > 0 {
+                    parts.push(format!("{missing} marked missing"));
+                }
+                let _ = tx.send(AppEvent::Notification(Notification {
+                    id: 0,
+                    message: format!("Library folders: {}", parts.join(", ")),
+                    level: NotificationLevel::Info,
+                    ttl_ticks: 120,
+                }));
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    });
+}
+
+/// What became of a document found in a watched folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchedOutcome {
+    Ingested,
+    /// A moved or renamed file whose library item now points at it
+    Relinked,
+    /// Already in the library, or left for the user to decide about
+    Skipped,
+}
+
+/// Run one document from a watched folder through the ingestion pipeline.
+///
+/// A file is skipped when the library already has it (unchanged, or the
+/// same content under another path), or when it looks like a new version
+/// of another item — the user is told and can import it from the library
+/// view.
+async fn ingest_watched_file(
+    file: WatchedFile,
+    storage: &SurrealStorage,
+    random_tables: &Arc<RandomTableEngine>,
+    embedding_provider: &Option<Arc<dyn EmbeddingProvider>>,
+    tx: &mpsc::UnboundedSender<AppEvent>,
+) -> WatchedOutcome {
+    let db = storage.db();
+    let plan = match prepare_import(db, &file.path, None, &file.content_type, ImportTarget::Auto)
+        .await
+    {
+        Ok(plan) => plan,
+        Err(e) => {
+            log::warn!("Skipping watched file {}: {e}", file.path.display());
+            return WatchedOutcome::Skipped;
+        }
+    };
+    let library_item_id = match plan {
+        ImportPlan::Duplicate { title, .. } => {
+            log::debug!("{} is already in the library as {title}", file.path.display());
+            return WatchedOutcome::Skipped;
+        }
+        ImportPlan::Relinked { title, .. } => {
+            log::info!("{title} was moved to {}; re-linked", file.path.display());
+            return WatchedOutcome::Relinked;
+        }
+        ImportPlan::Similar { title, reason, .. } => {
+            let name = file
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let _ = tx.send(AppEvent::Notification(Notification {
+                id: 0,
+                message: format!(
+                    "{name} looks like a new version of \"{title}\" ({reason}) — import it from the library to update or add it"
+                ),
+                level: NotificationLevel::Warning,
+                ttl_ticks: 240,
+            }));
+            return WatchedOutcome::Skipped;
+        }
+        ImportPlan::New { library_item_id } | ImportPlan::Update { library_item_id } => {
+            library_item_id
+        }
+    };
+
+    if let Some(ref system) = file.game_system {
+        assign_game_system(db, &library_item_id, system).await;
+    }
+
+    log::info!("Ingesting watched file {}", file.path.display());
+    run_ingestion_with_error_handling(
+        file.path,
+        library_item_id,
+        file.content_type,
+        storage.clone(),
+        random_tables.clone(),
+        embedding_provider.clone(),
+        tx.clone(),
+    )
+    .await;
+    WatchedOutcome::Ingested
+}
+
+/// Set a library item's game system from a folder config value, which may
+/// be a known system ID or alias (`dnd5e`, `pf2e`) or any other name.
+async fn assign_game_system(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    library_item_id: &str,
+    system: &str,
+) {
+    let (name, system_id) = match GameSystem::from_str(system) {
+        Some(known) => (known.display_name().to_string(), known.as_str().to_string()),
+        None => (system.to_string(), system.to_lowercase()),
+    };
+
+    let result = match get_library_item(db, library_item_id).await {
+        Ok(Some(mut item)) => {
+            item.game_system = Some(name);
+            item.game_system_id = Some(system_id);
+            update_library_item(db, library_item_id, &item).await
+        }
+        Ok(None) => return,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        log::warn!("Failed to set game system for {library_item_id}: {e}");
+    }
+}
+
+/// The ready library items from the watched folders, with the file size and
+/// time recorded for them, for seeding the folder scanner.
+async fn ingested_files(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    roots: &[PathBuf],
+) -> Vec<IngestedFile> {
+    let mut files = Vec::new();
+    for root in roots {
+        let items = match list_library_items_under_path(db, &root.to_string_lossy()).await {
+            Ok(items) => items,
+            Err(e) => {
+                log::warn!("Failed to list library items in {}: {e}", root.display());
+                continue;
+            }
+        };
+
+        for item in items {
+            if item.status != "ready" {
+                continue;
+            }
+            let (Some(path), Some(len), Some(updated_at)) =
+                (item.file_path, item.file_size, item.updated_at)
+            else {
+                continue;
+            };
+            let Ok(updated_at) = chrono::DateTime::parse_from_rfc3339(&updated_at) else {
+                continue;
+            };
+            let path = PathBuf::from(path);
+            if path.starts_with(root) {
+                files.push(IngestedFile {
+                    path,
+                    len: len as u64,
+                    ingested_at: updated_at.into(),
+                });
+            }
+        }
+    }
+    files
+}
+
+/// Mark library items from the watched folders whose file no longer exists
+/// as missing, returning how many were marked.
+///
+/// Folders that can't be reached right now (an unmounted share, say) are
+/// skipped rather than having every item in them marked missing.
+async fn mark_missing_files(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    roots: &[PathBuf],
+) -> usize {
+    let mut marked = 0;
+    for root in roots {
+        if !tokio::fs::metadata(root).await.is_ok_and(|meta| meta.is_dir()) {
+            log::warn!("Watched folder {} is not reachable", root.display());
+            continue;
+        }
+
+        let items = match list_library_items_under_path(db, &root.to_string_lossy()).await {
+            Ok(items) => items,
+            Err(e) => {
+                log::warn!("Failed to list library items in {}: {e}", root.display());
+                continue;
+            }
+        };
+
+        for item in items {
+            if item.status == "missing" {
+                continue;
+            }
+            let (Some(id), Some(path)) = (item.id, item.file_path) else {
+                continue;
+            };
+            let path = PathBuf::from(path);
+            if !path.starts_with(root) || tokio::fs::try_exists(&path).await.unwrap_or(true) {
+                continue;
+            }
+            let reason = Some("File removed from watched folder");
+            match update_library_item_status(db, &id, "missing", reason).await {
+                Ok(()) => {
+                    log::info!("{} is gone; marked {} as missing", path.display(), item.title);
+                    marked += 1;
+                }
+                Err(e) => log::warn!("Failed to mark {} as missing: {e}", item.title),
+            }
+        }
+    }
+    marked
+}
+
 /// Voice queue event emitter that forwards events into the TUI event channel.
 pub struct TuiQueueEmitter {
     tx: mpsc::UnboundedSender<AppEvent>,
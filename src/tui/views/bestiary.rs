@@ -0,0 +1,731 @@
+//! Bestiary browser — stat blocks harvested from ingested documents.
+//!
+//! Master-detail layout: left side lists matching creatures (CR, type, source),
+//! right side shows the full stat block. Press `/` to query, e.g.
+//! `undead cr 3-5 from monster manual`, `size:large cr:10+`, or a name.
+//! Press `c` to add the selected creature to the combat tracker.
+//! Data loads asynchronously from the `stat_block` table via Services.
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+use crate::core::session::combat::HitPointMode;
+use crate::core::storage::bestiary::{search_stat_blocks, BestiaryFilter, StatBlockRecord};
+use crate::ingestion::ttrpg::{AbilityScores, Feature, StatBlockData};
+use crate::tui::events::AppEvent;
+use crate::tui::services::Services;
+use crate::tui::theme;
+use crate::tui::widgets::input_buffer::InputBuffer;
+
+/// Maximum entries fetched per query.
+const RESULT_LIMIT: usize = 500;
+
+// ── Internal async data events ─────────────────────────────────────────────
+
+enum BestiaryDataEvent {
+    Loaded(Vec<StatBlockRecord>),
+    LoadError(String),
+}
+
+// ── State ──────────────────────────────────────────────────────────────────
+
+/// Inline "add to combat" prompt for the selected creature.
+struct CombatPrompt {
+    count: InputBuffer,
+    hp_mode: HitPointMode,
+}
+
+impl CombatPrompt {
+    fn new() -> Self {
+        let mut count = InputBuffer::new();
+        count.insert_char('1');
+        Self {
+            count,
+            hp_mode: HitPointMode::Average,
+        }
+    }
+
+    fn hp_label(&self) -> &'static str {
+        match self.hp_mode {
+            HitPointMode::Average => "average",
+            HitPointMode::Roll => "rolled",
+        }
+    }
+}
+
+pub struct BestiaryViewState {
+    entries: Vec<StatBlockRecord>,
+    selected: usize,
+    detail_scroll: usize,
+    loading: bool,
+
+    // Query
+    query: InputBuffer,
+    query_active: bool,
+
+    // Add-to-combat prompt
+    combat_prompt: Option<CombatPrompt>,
+
+    error: Option<String>,
+
+    data_tx: mpsc::UnboundedSender<BestiaryDataEvent>,
+    data_rx: mpsc::UnboundedReceiver<BestiaryDataEvent>,
+}
+
+impl BestiaryViewState {
+    pub fn new() -> Self {
+        let (data_tx, data_rx) = mpsc::unbounded_channel();
+        Self {
+            entries: Vec::new(),
+            selected: 0,
+            detail_scroll: 0,
+            loading: false,
+            query: InputBuffer::new(),
+            query_active: false,
+            combat_prompt: None,
+            error: None,
+            data_tx,
+            data_rx,
+        }
+    }
+
+    /// Run the current query against the bestiary.
+    pub fn load(&mut self, services: &Services) {
+        self.loading = true;
+        let filter = BestiaryFilter::parse(self.query.text());
+        let storage = services.storage.clone();
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            match search_stat_blocks(storage.db(), &filter, RESULT_LIMIT).await {
+                Ok(entries) => {
+                    let _ = tx.send(BestiaryDataEvent::Loaded(entries));
+                }
+                Err(e) => {
+                    let _ = tx.send(BestiaryDataEvent::LoadError(format!("{e}")));
+                }
+            }
+        });
+    }
+
+    pub fn poll(&mut self) {
+        while let Ok(event) = self.data_rx.try_recv() {
+            match event {
+                BestiaryDataEvent::Loaded(entries) => {
+                    self.entries = entries;
+                    self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+                    self.detail_scroll = 0;
+                    self.loading = false;
+                    self.error = None;
+                }
+                BestiaryDataEvent::LoadError(msg) => {
+                    self.loading = false;
+                    self.error = Some(msg);
+                }
+            }
+        }
+    }
+
+    /// Currently selected stat block, if any.
+    pub fn selected_entry(&self) -> Option<&StatBlockRecord> {
+        self.entries.get(self.selected)
+    }
+
+    // ── Input handling ─────────────────────────────────────────────────────
+
+    pub fn handle_input(&mut self, event: &Event, services: &Services) -> bool {
+        let Event::Key(KeyEvent { code, modifiers, kind: KeyEventKind::Press, .. }) = event else {
+            return false;
+        };
+
+        if self.query_active {
+            return self.handle_query_input(*code, *modifiers, services);
+        }
+        if self.combat_prompt.is_some() {
+            return self.handle_combat_prompt_input(*code, services);
+        }
+
+        match (*modifiers, *code) {
+            (KeyModifiers::NONE, KeyCode::Char('/')) => {
+                self.query_active = true;
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('j') | KeyCode::Down) => {
+                if self.selected + 1 < self.entries.len() {
+                    self.selected += 1;
+                    self.detail_scroll = 0;
+                }
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('k') | KeyCode::Up) => {
+                if self.selected > 0 {
+                    self.selected -= 1;
+                    self.detail_scroll = 0;
+                }
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('g') | KeyCode::Home) => {
+                self.selected = 0;
+                self.detail_scroll = 0;
+                true
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('G')) | (KeyModifiers::NONE, KeyCode::End) => {
+                self.selected = self.entries.len().saturating_sub(1);
+                self.detail_scroll = 0;
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::PageDown) | (KeyModifiers::CONTROL, KeyCode::Char('d')) => {
+                self.detail_scroll = self.detail_scroll.saturating_add(5);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::PageUp) | (KeyModifiers::CONTROL, KeyCode::Char('u')) => {
+                self.detail_scroll = self.detail_scroll.saturating_sub(5);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Esc) if !self.query.is_empty() => {
+                self.query.clear();
+                self.selected = 0;
+                self.load(services);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('r')) => {
+                self.load(services);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('c')) if self.selected_entry().is_some() => {
+                self.combat_prompt = Some(CombatPrompt::new());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_combat_prompt_input(&mut self, code: KeyCode, services: &Services) -> bool {
+        let Some(prompt) = self.combat_prompt.as_mut() else {
+            return false;
+        };
+        match code {
+            KeyCode::Esc => {
+                self.combat_prompt = None;
+            }
+            KeyCode::Tab => {
+                prompt.hp_mode = match prompt.hp_mode {
+                    HitPointMode::Average => HitPointMode::Roll,
+                    HitPointMode::Roll => HitPointMode::Average,
+                };
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => prompt.count.insert_char(c),
+            KeyCode::Backspace => prompt.count.backspace(),
+            KeyCode::Enter => {
+                if let Some(event) = self.submit_combat_prompt() {
+                    let _ = services.event_tx.send(event);
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Close the add-to-combat prompt and build the event that adds the
+    /// selected creature. Returns `None` if the count is not a positive number.
+    fn submit_combat_prompt(&mut self) -> Option<AppEvent> {
+        let prompt = self.combat_prompt.take()?;
+        let count = prompt.count.text().trim().parse::<usize>().ok().filter(|&n| n > 0);
+        let Some(count) = count else {
+            self.error = Some("Count must be a positive number".into());
+            return None;
+        };
+        let entry = self.selected_entry()?;
+        Some(AppEvent::AddStatBlockToCombat {
+            block: Box::new(entry.data.clone()),
+            count,
+            hp_mode: prompt.hp_mode,
+        })
+    }
+
+    fn handle_query_input(&mut self, code: KeyCode, modifiers: KeyModifiers, services: &Services) -> bool {
+        match (modifiers, code) {
+            (KeyModifiers::NONE, KeyCode::Esc) => {
+                self.query_active = false;
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Enter) => {
+                self.query_active = false;
+                self.selected = 0;
+                self.load(services);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char(c)) | (KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+                self.query.insert_char(c);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Backspace) => {
+                self.query.backspace();
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Delete) => {
+                self.query.delete();
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Left) => {
+                self.query.move_left();
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Right) => {
+                self.query.move_right();
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Home) => {
+                self.query.move_home();
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::End) => {
+                self.query.move_end();
+                true
+            }
+            _ => true,
+        }
+    }
+
+    // ── Rendering ──────────────────────────────────────────────────────────
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::horizontal([
+            Constraint::Percentage(45),
+            Constraint::Percentage(55),
+        ])
+        .split(area);
+        self.render_list(frame, chunks[0]);
+        self.render_detail(frame, chunks[1]);
+    }
+
+    fn render_list(&self, frame: &mut Frame, area: Rect) {
+        let title = if self.loading {
+            " Bestiary (loading…) ".to_string()
+        } else {
+            format!(" Bestiary ({}) ", self.entries.len())
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::TEXT_MUTED));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines: Vec<Line<'static>> = Vec::new();
+
+        // Query bar
+        let query_text = self.query.text();
+        let query_style = if self.query_active {
+            Style::default().fg(theme::ACCENT)
+        } else {
+            Style::default().fg(theme::TEXT_MUTED)
+        };
+        let display = if self.query_active {
+            format!("{query_text}▎")
+        } else if query_text.is_empty() {
+            "undead cr 3-5 from monster manual".to_string()
+        } else {
+            query_text.to_string()
+        };
+        let display_style = if !self.query_active && query_text.is_empty() {
+            Style::default().fg(theme::TEXT_DIM)
+        } else {
+            Style::default().fg(theme::TEXT)
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  / ", query_style),
+            Span::styled(display, display_style),
+        ]));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", "─".repeat(inner.width.saturating_sub(4) as usize)),
+            Style::default().fg(theme::TEXT_DIM),
+        )));
+
+        if self.entries.is_empty() {
+            lines.push(Line::raw(""));
+            let msg = if query_text.is_empty() {
+                "No stat blocks yet. Ingest a bestiary or adventure in Library."
+            } else {
+                "No matches."
+            };
+            lines.push(Line::from(Span::styled(
+                format!("  {msg}"),
+                Style::default().fg(theme::TEXT_MUTED),
+            )));
+        } else {
+            let visible = (inner.height as usize).saturating_sub(6);
+            let start = self.selected.saturating_sub(visible.saturating_sub(1));
+            for (i, entry) in self.entries.iter().enumerate().skip(start).take(visible.max(1)) {
+                let is_selected = i == self.selected;
+                let cursor = if is_selected { "▸ " } else { "  " };
+                let name_style = if is_selected {
+                    Style::default().fg(theme::TEXT).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme::TEXT)
+                };
+                let cr = entry.cr_label().unwrap_or_else(|| "—".to_string());
+                lines.push(Line::from(vec![
+                    Span::styled(cursor.to_string(), Style::default().fg(theme::ACCENT)),
+                    Span::styled(format!("CR {cr:<4} "), Style::default().fg(theme::WARNING)),
+                    Span::styled(truncate(&entry.name, 22), name_style),
+                    Span::raw("  "),
+                    Span::styled(
+                        truncate(entry.creature_type.as_deref().unwrap_or(""), 14),
+                        Style::default().fg(theme::TEXT_MUTED),
+                    ),
+                    Span::raw("  "),
+                    Span::styled(truncate(&entry.source, 18), Style::default().fg(theme::TEXT_DIM)),
+                ]));
+            }
+        }
+
+        // Footer
+        lines.push(Line::raw(""));
+        if let Some(ref prompt) = self.combat_prompt {
+            lines.push(Line::from(vec![
+                Span::styled("  Add to combat: ", Style::default().fg(theme::ACCENT)),
+                Span::styled(format!("{}▎", prompt.count.text()), Style::default().fg(theme::TEXT)),
+                Span::styled(format!("  HP {}", prompt.hp_label()), Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw("  "),
+                Span::styled("Tab", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(":hp "),
+                Span::styled("Enter", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(":add "),
+                Span::styled("Esc", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(":cancel"),
+            ]));
+        } else {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled("/", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(":query "),
+                Span::styled("j/k", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(":select "),
+                Span::styled("PgUp/PgDn", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(":scroll "),
+                Span::styled("c", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(":combat "),
+                Span::styled("r", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(":refresh"),
+            ]));
+        }
+
+        if let Some(ref err) = self.error {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(format!("✗ {err}"), Style::default().fg(theme::ERROR)),
+            ]));
+        }
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_detail(&self, frame: &mut Frame, area: Rect) {
+        let Some(entry) = self.selected_entry() else {
+            let block = theme::block_default("Stat Block");
+            frame.render_widget(block, area);
+            return;
+        };
+
+        let block = Block::default()
+            .title(format!(" {} ", entry.name))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::PRIMARY_LIGHT));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let lines = stat_block_lines(entry);
+        frame.render_widget(
+            Paragraph::new(lines).scroll((self.detail_scroll as u16, 0)),
+            inner,
+        );
+    }
+}
+
+// ── Free helpers ───────────────────────────────────────────────────────────
+
+/// Render a stat block as styled lines, in printed stat block order.
+fn stat_block_lines(entry: &StatBlockRecord) -> Vec<Line<'static>> {
+    let data = &entry.data;
+    let mut lines: Vec<Line<'static>> = Vec::new();
+
+    lines.push(Line::raw(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {}", entry.name),
+        Style::default().fg(theme::ACCENT).add_modifier(Modifier::BOLD),
+    )));
+    let descriptor = [
+        entry.size.as_deref().map(capitalize),
+        entry.creature_type.clone(),
+        entry.alignment.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ");
+    if !descriptor.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  {descriptor}"),
+            Style::default().fg(theme::TEXT_MUTED).add_modifier(Modifier::ITALIC),
+        )));
+    }
+    let page = entry.page_number.map(|p| format!(", p. {p}")).unwrap_or_default();
+    lines.push(Line::from(Span::styled(
+        format!("  {}{page}", entry.source),
+        Style::default().fg(theme::TEXT_DIM),
+    )));
+
+    lines.push(Line::raw(""));
+    if let Some(ref ac) = data.armor_class {
+        let armor = ac.armor_type.as_ref().map(|t| format!(" ({t})")).unwrap_or_default();
+        lines.push(detail_line("Armor Class", &format!("{}{armor}", ac.value)));
+    }
+    if let Some(ref hp) = data.hit_points {
+        let formula = hp.formula.as_ref().map(|f| format!(" ({f})")).unwrap_or_default();
+        lines.push(detail_line("Hit Points", &format!("{}{formula}", hp.average)));
+    }
+    let speed = format_speed(data);
+    if !speed.is_empty() {
+        lines.push(detail_line("Speed", &speed));
+    }
+
+    // Ability scores
+    let scores = &data.ability_scores;
+    let abilities = [
+        ("STR", scores.strength),
+        ("DEX", scores.dexterity),
+        ("CON", scores.constitution),
+        ("INT", scores.intelligence),
+        ("WIS", scores.wisdom),
+        ("CHA", scores.charisma),
+    ];
+    if abilities.iter().any(|(_, s)| s.is_some()) {
+        lines.push(Line::raw(""));
+        let mut spans = vec![Span::raw("  ")];
+        for (label, score) in abilities {
+            let text = match score {
+                Some(s) => format!("{label} {s} ({:+})  ", AbilityScores::modifier(s)),
+                None => format!("{label} —  "),
+            };
+            spans.push(Span::styled(text, Style::default().fg(theme::TEXT)));
+        }
+        lines.push(Line::from(spans));
+        lines.push(Line::raw(""));
+    }
+
+    let list_line = |lines: &mut Vec<Line<'static>>, label: &str, items: &[String]| {
+        if !items.is_empty() {
+            lines.push(detail_line(label, &items.join(", ")));
+        }
+    };
+    let mut saves: Vec<String> = data
+        .saving_throws
+        .iter()
+        .map(|(k, v)| format!("{} {v:+}", capitalize(k)))
+        .collect();
+    saves.sort();
+    list_line(&mut lines, "Saves", &saves);
+    let mut skills: Vec<String> = data
+        .skills
+        .iter()
+        .map(|(k, v)| format!("{} {v:+}", capitalize(k)))
+        .collect();
+    skills.sort();
+    list_line(&mut lines, "Skills", &skills);
+    list_line(&mut lines, "Vulnerable", &data.damage_vulnerabilities);
+    list_line(&mut lines, "Resistant", &data.damage_resistances);
+    list_line(&mut lines, "Immune", &data.damage_immunities);
+    list_line(&mut lines, "Cond. Immune", &data.condition_immunities);
+    list_line(&mut lines, "Senses", &data.senses);
+    list_line(&mut lines, "Languages", &data.languages);
+    if let Some(cr) = entry.cr_label() {
+        let xp = entry.xp.map(|xp| format!(" ({xp} XP)")).unwrap_or_default();
+        lines.push(detail_line("Challenge", &format!("{cr}{xp}")));
+    }
+
+    feature_section(&mut lines, "TRAITS", &data.traits);
+    feature_section(&mut lines, "ACTIONS", &data.actions);
+    feature_section(&mut lines, "BONUS ACTIONS", &data.bonus_actions);
+    feature_section(&mut lines, "REACTIONS", &data.reactions);
+    feature_section(&mut lines, "LEGENDARY ACTIONS", &data.legendary_actions);
+
+    lines
+}
+
+fn feature_section(lines: &mut Vec<Line<'static>>, title: &str, features: &[Feature]) {
+    if features.is_empty() {
+        return;
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {title}"),
+        Style::default().fg(theme::PRIMARY).add_modifier(Modifier::BOLD),
+    )));
+    for feature in features {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("{}. ", feature.name),
+                Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(feature.description.clone(), Style::default().fg(theme::TEXT)),
+        ]));
+    }
+}
+
+fn format_speed(data: &StatBlockData) -> String {
+    let speed = &data.speed;
+    let mut parts = Vec::new();
+    if let Some(walk) = speed.walk {
+        parts.push(format!("{walk} ft."));
+    }
+    for (label, value) in [
+        ("burrow", speed.burrow),
+        ("climb", speed.climb),
+        ("fly", speed.fly),
+        ("swim", speed.swim),
+    ] {
+        if let Some(v) = value {
+            let hover = if label == "fly" && speed.hover { " (hover)" } else { "" };
+            parts.push(format!("{label} {v} ft.{hover}"));
+        }
+    }
+    parts.join(", ")
+}
+
+fn detail_line(label: &str, value: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("  {:<13}", label),
+            Style::default().fg(theme::TEXT_MUTED),
+        ),
+        Span::styled(value.to_string(), Style::default().fg(theme::TEXT)),
+    ])
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        let cut: String = s.chars().take(max.saturating_sub(1)).collect();
+        format!("{cut}…")
+    } else {
+        s.to_string()
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::storage::bestiary::collect_stat_blocks;
+
+    fn sample_entries() -> Vec<StatBlockRecord> {
+        let page = r#"
+            Goblin
+            Small humanoid (goblinoid), neutral evil
+            Armor Class 15 (leather armor, shield)
+            Hit Points 7 (2d6)
+            Speed 30 ft.
+            STR 8 (-1) DEX 14 (+2) CON 10 (+0) INT 10 (+0) WIS 8 (-1) CHA 8 (-1)
+            Challenge 1/4 (50 XP)
+
+            Wight
+            Medium undead, neutral evil
+            Armor Class 14 (studded leather)
+            Hit Points 45 (6d8 + 18)
+            Speed 30 ft.
+            Challenge 3 (700 XP)
+        "#;
+        collect_stat_blocks(&[(7, page.to_string())], "Monster Manual")
+    }
+
+    #[test]
+    fn test_bestiary_view_new() {
+        let state = BestiaryViewState::new();
+        assert!(state.entries.is_empty());
+        assert!(state.selected_entry().is_none());
+        assert!(!state.query_active);
+    }
+
+    #[test]
+    fn test_bestiary_poll_clamps_selection() {
+        let mut state = BestiaryViewState::new();
+        state.selected = 10;
+        state.data_tx.send(BestiaryDataEvent::Loaded(sample_entries())).unwrap();
+        state.poll();
+        assert_eq!(state.entries.len(), 2);
+        assert_eq!(state.selected, 1);
+        assert_eq!(state.selected_entry().map(|e| e.name.as_str()), Some("Wight"));
+    }
+
+    #[test]
+    fn test_stat_block_lines_include_core_stats() {
+        let entries = sample_entries();
+        let text: Vec<String> = stat_block_lines(&entries[0])
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        let joined = text.join("\n");
+        assert!(joined.contains("Armor Class  15 (leather armor, shield)"));
+        assert!(joined.contains("Hit Points   7 (2d6)"));
+        assert!(joined.contains("DEX 14 (+2)"));
+        assert!(joined.contains("1/4 (50 XP)"));
+        assert!(joined.contains("Monster Manual, p. 7"));
+    }
+
+    #[test]
+    fn test_combat_prompt_builds_event() {
+        let mut state = BestiaryViewState::new();
+        state.entries = sample_entries();
+        state.combat_prompt = Some(CombatPrompt::new());
+        if let Some(prompt) = state.combat_prompt.as_mut() {
+            prompt.count.clear();
+            prompt.count.insert_char('4');
+            prompt.hp_mode = HitPointMode::Roll;
+        }
+
+        match state.submit_combat_prompt() {
+            Some(AppEvent::AddStatBlockToCombat { block, count, hp_mode }) => {
+                assert_eq!(block.name, "Goblin");
+                assert_eq!(count, 4);
+                assert_eq!(hp_mode, HitPointMode::Roll);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert!(state.combat_prompt.is_none());
+    }
+
+    #[test]
+    fn test_combat_prompt_rejects_zero() {
+        let mut state = BestiaryViewState::new();
+        state.entries = sample_entries();
+        let mut prompt = CombatPrompt::new();
+        prompt.count.clear();
+        prompt.count.insert_char('0');
+        state.combat_prompt = Some(prompt);
+
+        assert!(state.submit_combat_prompt().is_none());
+        assert!(state.error.is_some());
+    }
+
+    #[test]
+    fn test_truncate_helper() {
+        assert_eq!(truncate("Goblin", 10), "Goblin");
+        assert_eq!(truncate("Adult Red Dragon", 6), "Adult…");
+    }
+}
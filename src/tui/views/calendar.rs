@@ -0,0 +1,1060 @@
+//! Calendar view — a campaign's in-game calendar with its world events.
+//!
+//! Month grid on the left (weekday columns, holidays, event markers, the
+//! current in-game date), details for the selected day on the right: weekday,
+//! season, moon phases, holidays and `WorldEvent`s. World state is stored as
+//! JSON in the campaign's `world_state` column.
+//! Move with h/j/k/l, `[`/`]` for months, `{`/`}` for years. Press `a` to add
+//! an event, `d` to delete one, `.` to make the selected day the current
+//! date, `+`/`-` to advance time, `c` to switch calendar, `s` to switch campaign.
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+use crate::config::AppConfig;
+use crate::core::campaign::calendar::Calendar;
+use crate::core::campaign::world_state::{InGameDate, WorldEvent, WorldEventType, WorldState};
+use crate::database::{CampaignOps, CampaignRecord};
+use crate::tui::app::centered_rect;
+use crate::tui::services::Services;
+use crate::tui::theme;
+use crate::tui::widgets::input_buffer::InputBuffer;
+
+// ── Internal async data events ─────────────────────────────────────────────
+
+enum CalendarDataEvent {
+    Loaded(Vec<CampaignRecord>),
+    Saved,
+    Error(String),
+}
+
+/// A campaign and its parsed world state.
+struct CampaignCalendar {
+    record: CampaignRecord,
+    world: WorldState,
+    /// `world_state` held JSON that isn't a `WorldState`; never overwrite it
+    read_only: bool,
+}
+
+impl CampaignCalendar {
+    fn from_record(record: CampaignRecord) -> Self {
+        let parsed = record
+            .world_state
+            .as_deref()
+            .map(serde_json::from_str::<WorldState>);
+        let (world, read_only) = match parsed {
+            None => (WorldState::new(&record.id), false),
+            Some(Ok(world)) => (world, false),
+            Some(Err(e)) => {
+                log::warn!(
+                    "World state of campaign {} isn't calendar data: {e}",
+                    record.id
+                );
+                (WorldState::new(&record.id), true)
+            }
+        };
+        Self {
+            record,
+            world,
+            read_only,
+        }
+    }
+}
+
+// ── Modal types ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CalendarModal {
+    AddEvent,
+    DeleteEvent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventField {
+    Title,
+    Type,
+    Description,
+}
+
+const EVENT_FIELDS: [EventField; 3] =
+    [EventField::Title, EventField::Type, EventField::Description];
+
+/// Event types offered in the add-event form.
+const EVENT_TYPES: [WorldEventType; 10] = [
+    WorldEventType::Session,
+    WorldEventType::Combat,
+    WorldEventType::Political,
+    WorldEventType::Natural,
+    WorldEventType::Economic,
+    WorldEventType::Religious,
+    WorldEventType::Magical,
+    WorldEventType::Social,
+    WorldEventType::Personal,
+    WorldEventType::Discovery,
+];
+
+// ── State ──────────────────────────────────────────────────────────────────
+
+pub struct CalendarViewState {
+    campaigns: Vec<CampaignCalendar>,
+    active: usize,
+    /// Presets plus user calendars from `<data_dir>/calendars`
+    calendars: Vec<Calendar>,
+
+    // Selected day and the highlighted event on it
+    cursor: InGameDate,
+    event_cursor: usize,
+
+    // Modal
+    modal: Option<CalendarModal>,
+    form_focus: usize,
+    form_title: InputBuffer,
+    form_type: usize,
+    form_description: InputBuffer,
+
+    loading: bool,
+    error: Option<String>,
+
+    // Async channel
+    data_tx: mpsc::UnboundedSender<CalendarDataEvent>,
+    data_rx: mpsc::UnboundedReceiver<CalendarDataEvent>,
+}
+
+impl CalendarViewState {
+    pub fn new() -> Self {
+        let (data_tx, data_rx) = mpsc::unbounded_channel();
+        Self {
+            campaigns: Vec::new(),
+            active: 0,
+            calendars: Vec::new(),
+            cursor: InGameDate::default(),
+            event_cursor: 0,
+            modal: None,
+            form_focus: 0,
+            form_title: InputBuffer::new(),
+            form_type: 0,
+            form_description: InputBuffer::new(),
+            loading: false,
+            error: None,
+            data_tx,
+            data_rx,
+        }
+    }
+
+    /// Reload calendars from disk and campaigns from the database.
+    pub fn load(&mut self, services: &Services) {
+        self.calendars = Calendar::available(&AppConfig::load().data_dir());
+
+        if self.loading {
+            return;
+        }
+        self.loading = true;
+
+        let db = services.database.clone();
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            let event = match db.list_campaigns().await {
+                Ok(records) => CalendarDataEvent::Loaded(records),
+                Err(e) => CalendarDataEvent::Error(format!("Failed to load campaigns: {e}")),
+            };
+            let _ = tx.send(event);
+        });
+    }
+
+    pub fn poll(&mut self) {
+        while let Ok(event) = self.data_rx.try_recv() {
+            match event {
+                CalendarDataEvent::Loaded(records) => {
+                    self.loading = false;
+                    let active_id = self.campaign().map(|c| c.record.id.clone());
+                    self.campaigns = records
+                        .into_iter()
+                        .filter(|r| r.archived_at.is_none())
+                        .map(CampaignCalendar::from_record)
+                        .collect();
+                    self.active = active_id
+                        .and_then(|id| self.campaigns.iter().position(|c| c.record.id == id))
+                        .unwrap_or(0);
+                    self.jump_to_today();
+                }
+                CalendarDataEvent::Saved => {}
+                CalendarDataEvent::Error(msg) => {
+                    self.loading = false;
+                    self.error = Some(msg);
+                }
+            }
+        }
+    }
+
+    // ── Accessors ──────────────────────────────────────────────────────────
+
+    fn campaign(&self) -> Option<&CampaignCalendar> {
+        self.campaigns.get(self.active)
+    }
+
+    fn calendar(&self) -> Option<&Calendar> {
+        self.campaign().map(|c| &c.world.calendar)
+    }
+
+    fn events_on_cursor(&self) -> Vec<&WorldEvent> {
+        self.campaign()
+            .map(|c| c.world.events_on(&self.cursor))
+            .unwrap_or_default()
+    }
+
+    fn jump_to_today(&mut self) {
+        if let Some(campaign) = self.campaign() {
+            self.cursor = campaign
+                .world
+                .calendar
+                .clamp_date(&campaign.world.current_date);
+        }
+        self.event_cursor = 0;
+    }
+
+    fn move_days(&mut self, days: i64) {
+        let Some(calendar) = self.calendar() else {
+            return;
+        };
+        if let Ok(date) = calendar.add_days(&self.cursor, days) {
+            self.cursor = date;
+            self.event_cursor = 0;
+        }
+    }
+
+    fn move_months(&mut self, delta: i32) {
+        let Some(calendar) = self.calendar() else {
+            return;
+        };
+        self.cursor = shift_month(calendar, &self.cursor, delta);
+        self.event_cursor = 0;
+    }
+
+    // ── Mutations ──────────────────────────────────────────────────────────
+
+    /// Apply a change to the active campaign's world state and save it.
+    fn update_world(
+        &mut self,
+        services: &Services,
+        f: impl FnOnce(&mut WorldState) -> Result<(), String>,
+    ) {
+        let Some(campaign) = self.campaigns.get_mut(self.active) else {
+            return;
+        };
+        if campaign.read_only {
+            self.error =
+                Some("This campaign's world state isn't calendar data; not editing it".to_string());
+            return;
+        }
+        if let Err(e) = f(&mut campaign.world) {
+            self.error = Some(e);
+            return;
+        }
+        self.error = None;
+
+        let json = match serde_json::to_string(&campaign.world) {
+            Ok(json) => json,
+            Err(e) => {
+                self.error = Some(format!("Failed to serialize world state: {e}"));
+                return;
+            }
+        };
+        campaign.record.world_state = Some(json);
+        campaign.record.current_in_game_date = Some(
+            campaign
+                .world
+                .calendar
+                .format_date(&campaign.world.current_date),
+        );
+        campaign.record.updated_at = chrono::Utc::now().to_rfc3339();
+
+        let record = campaign.record.clone();
+        let db = services.database.clone();
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            let event = match db.update_campaign(&record).await {
+                Ok(()) => CalendarDataEvent::Saved,
+                Err(e) => CalendarDataEvent::Error(format!("Failed to save world state: {e}")),
+            };
+            let _ = tx.send(event);
+        });
+    }
+
+    fn advance_current_date(&mut self, services: &Services, days: i64) {
+        self.update_world(services, |world| {
+            world
+                .advance_date(days)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        });
+        self.jump_to_today();
+    }
+
+    fn set_current_date(&mut self, services: &Services) {
+        let date = self.cursor.clone();
+        self.update_world(services, |world| {
+            world.set_current_date(date).map_err(|e| e.to_string())
+        });
+    }
+
+    /// Switch the active campaign to the next available calendar.
+    fn cycle_calendar(&mut self, services: &Services) {
+        let Some(current) = self.calendar() else {
+            return;
+        };
+        if self.calendars.is_empty() {
+            return;
+        }
+        let next = self
+            .calendars
+            .iter()
+            .position(|c| c.name == current.name)
+            .map(|i| (i + 1) % self.calendars.len())
+            .unwrap_or(0);
+        let calendar = self.calendars[next].clone();
+        self.update_world(services, |world| {
+            world.set_calendar(calendar);
+            Ok(())
+        });
+        self.jump_to_today();
+    }
+
+    fn cycle_campaign(&mut self) {
+        if !self.campaigns.is_empty() {
+            self.active = (self.active + 1) % self.campaigns.len();
+            self.error = None;
+            self.jump_to_today();
+        }
+    }
+
+    // ── Input handling ─────────────────────────────────────────────────────
+
+    pub fn handle_input(&mut self, event: &Event, services: &Services) -> bool {
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        else {
+            return false;
+        };
+
+        if let Some(modal) = self.modal {
+            match modal {
+                CalendarModal::AddEvent => self.handle_form_input(*code, *modifiers, services),
+                CalendarModal::DeleteEvent => self.handle_delete_input(*code, services),
+            }
+            return true;
+        }
+
+        let week = self
+            .calendar()
+            .map(|c| c.weekdays.len().max(1) as i64)
+            .unwrap_or(7);
+
+        match (*modifiers, *code) {
+            (KeyModifiers::NONE, KeyCode::Char('h') | KeyCode::Left) => self.move_days(-1),
+            (KeyModifiers::NONE, KeyCode::Char('l') | KeyCode::Right) => self.move_days(1),
+            (KeyModifiers::NONE, KeyCode::Char('k') | KeyCode::Up) => self.move_days(-week),
+            (KeyModifiers::NONE, KeyCode::Char('j') | KeyCode::Down) => self.move_days(week),
+            (_, KeyCode::Char('[')) => self.move_months(-1),
+            (_, KeyCode::Char(']')) => self.move_months(1),
+            (_, KeyCode::Char('{')) => {
+                let months = self.calendar().map(|c| c.months.len() as i32).unwrap_or(12);
+                self.move_months(-months);
+            }
+            (_, KeyCode::Char('}')) => {
+                let months = self.calendar().map(|c| c.months.len() as i32).unwrap_or(12);
+                self.move_months(months);
+            }
+            (KeyModifiers::NONE, KeyCode::Char('t')) => self.jump_to_today(),
+            (KeyModifiers::NONE, KeyCode::Char('.')) => self.set_current_date(services),
+            (_, KeyCode::Char('+')) => self.advance_current_date(services, 1),
+            (KeyModifiers::NONE, KeyCode::Char('-')) => self.advance_current_date(services, -1),
+            (KeyModifiers::NONE, KeyCode::Tab) => {
+                let count = self.events_on_cursor().len();
+                if count > 0 {
+                    self.event_cursor = (self.event_cursor + 1) % count;
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('a')) => {
+                if self.campaign().is_some() {
+                    self.open_form();
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('d')) => {
+                if !self.events_on_cursor().is_empty() {
+                    self.modal = Some(CalendarModal::DeleteEvent);
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('c')) => self.cycle_calendar(services),
+            (KeyModifiers::NONE, KeyCode::Char('s')) => self.cycle_campaign(),
+            (KeyModifiers::NONE, KeyCode::Char('r')) => self.load(services),
+            _ => return false,
+        }
+        true
+    }
+
+    fn open_form(&mut self) {
+        self.modal = Some(CalendarModal::AddEvent);
+        self.form_focus = 0;
+        self.form_title.clear();
+        self.form_type = 0;
+        self.form_description.clear();
+        self.error = None;
+    }
+
+    fn handle_form_input(&mut self, code: KeyCode, modifiers: KeyModifiers, services: &Services) {
+        match (modifiers, code) {
+            (KeyModifiers::NONE, KeyCode::Esc) => {
+                self.modal = None;
+                self.error = None;
+            }
+            (KeyModifiers::NONE, KeyCode::Enter) => self.save_form(services),
+            (KeyModifiers::NONE, KeyCode::Tab) => {
+                self.form_focus = (self.form_focus + 1) % EVENT_FIELDS.len();
+            }
+            (KeyModifiers::SHIFT, KeyCode::BackTab) => {
+                self.form_focus = (self.form_focus + EVENT_FIELDS.len() - 1) % EVENT_FIELDS.len();
+            }
+            _ => match EVENT_FIELDS[self.form_focus] {
+                EventField::Title => route_text_input(&mut self.form_title, code, modifiers),
+                EventField::Description => {
+                    route_text_input(&mut self.form_description, code, modifiers)
+                }
+                EventField::Type => match code {
+                    KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                        self.form_type = (self.form_type + 1) % EVENT_TYPES.len();
+                    }
+                    KeyCode::Left | KeyCode::Char('h') => {
+                        self.form_type =
+                            (self.form_type + EVENT_TYPES.len() - 1) % EVENT_TYPES.len();
+                    }
+                    _ => {}
+                },
+            },
+        }
+    }
+
+    fn save_form(&mut self, services: &Services) {
+        let title = self.form_title.text().trim().to_string();
+        if title.is_empty() {
+            self.error = Some("Title is required.".to_string());
+            return;
+        }
+        let Some(campaign_id) = self.campaign().map(|c| c.record.id.clone()) else {
+            return;
+        };
+        let event = WorldEvent::new(
+            &campaign_id,
+            &title,
+            self.form_description.text().trim(),
+            self.cursor.clone(),
+        )
+        .with_type(EVENT_TYPES[self.form_type].clone());
+
+        self.update_world(services, |world| {
+            world.events.push(event);
+            Ok(())
+        });
+        if self.error.is_none() {
+            self.modal = None;
+            self.event_cursor = self.events_on_cursor().len().saturating_sub(1);
+        }
+    }
+
+    fn handle_delete_input(&mut self, code: KeyCode, services: &Services) {
+        match code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                let selected = self
+                    .events_on_cursor()
+                    .get(self.event_cursor)
+                    .map(|e| e.id.clone());
+                if let Some(id) = selected {
+                    self.update_world(services, |world| {
+                        world.events.retain(|e| e.id != id);
+                        Ok(())
+                    });
+                    self.event_cursor = self.event_cursor.saturating_sub(1);
+                }
+                self.modal = None;
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.modal = None;
+            }
+            _ => {}
+        }
+    }
+
+    // ── Rendering ──────────────────────────────────────────────────────────
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let rows = Layout::vertical([
+            Constraint::Length(1), // Campaign / calendar bar
+            Constraint::Min(8),    // Month grid + day detail
+            Constraint::Length(1), // Key hints
+        ])
+        .split(area);
+
+        let Some(campaign) = self.campaign() else {
+            let block = theme::block_focused("Calendar");
+            let inner = block.inner(rows[1]);
+            frame.render_widget(block, rows[1]);
+            let msg = if self.loading {
+                " Loading campaigns…".to_string()
+            } else if let Some(ref err) = self.error {
+                format!(" ✗ {err}")
+            } else {
+                " No campaigns yet — create one with the campaign wizard".to_string()
+            };
+            frame.render_widget(
+                Paragraph::new(Span::styled(msg, Style::default().fg(theme::TEXT_DIM))),
+                inner,
+            );
+            return;
+        };
+
+        self.render_header(frame, rows[0], campaign);
+
+        let cols = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(rows[1]);
+        self.render_month(frame, cols[0], campaign);
+        self.render_day(frame, cols[1], campaign);
+
+        self.render_hints(frame, rows[2]);
+
+        if let Some(modal) = self.modal {
+            match modal {
+                CalendarModal::AddEvent => self.render_form(frame, area, campaign),
+                CalendarModal::DeleteEvent => self.render_delete_modal(frame, area),
+            }
+        }
+    }
+
+    fn render_header(&self, frame: &mut Frame, area: Rect, campaign: &CampaignCalendar) {
+        let label = |s: &'static str| Span::styled(s, Style::default().fg(theme::TEXT_DIM));
+        let value = |s: String| Span::styled(s, Style::default().fg(theme::ACCENT));
+        let world = &campaign.world;
+
+        let mut spans = vec![
+            label(" Campaign: "),
+            value(campaign.record.name.clone()),
+            label("  Calendar: "),
+            value(world.calendar.name.clone()),
+            label("  Today: "),
+            value(world.calendar.format_date(&world.current_date)),
+        ];
+        if campaign.read_only {
+            spans.push(Span::styled(
+                "  (read-only)",
+                Style::default().fg(theme::WARNING),
+            ));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn render_month(&self, frame: &mut Frame, area: Rect, campaign: &CampaignCalendar) {
+        let calendar = &campaign.world.calendar;
+        let title = format!(
+            "{} {}",
+            calendar.month_name(self.cursor.month),
+            self.cursor.year
+        );
+        let block = theme::block_focused(&title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let today = &campaign.world.current_date;
+        let has_events = |day: u8| {
+            let date = InGameDate {
+                day,
+                ..self.cursor.clone()
+            };
+            !campaign.world.events_on(&date).is_empty()
+        };
+        let is_holiday = |day: u8| {
+            let date = InGameDate {
+                day,
+                ..self.cursor.clone()
+            };
+            !calendar.holidays_on(&date).is_empty()
+        };
+
+        let columns = calendar.weekdays.len().max(1);
+        let cell_width = (inner.width as usize / columns).clamp(3, 12);
+        let mut lines: Vec<Line> = Vec::new();
+
+        let intercalary = calendar
+            .month(self.cursor.month)
+            .is_some_and(|m| m.intercalary);
+        if intercalary {
+            lines.push(Line::from(Span::styled(
+                " Festival days outside the week",
+                Style::default().fg(theme::TEXT_MUTED),
+            )));
+            lines.push(Line::raw(""));
+        } else if !calendar.weekdays.is_empty() {
+            let header: Vec<Span> = calendar
+                .weekdays
+                .iter()
+                .map(|d| {
+                    Span::styled(
+                        format!(
+                            "{:>width$}",
+                            truncate(d, cell_width - 1),
+                            width = cell_width
+                        ),
+                        Style::default().fg(theme::TEXT_MUTED),
+                    )
+                })
+                .collect();
+            lines.push(Line::from(header));
+        }
+
+        for week in month_grid(calendar, self.cursor.year, self.cursor.month) {
+            let spans: Vec<Span> = week
+                .into_iter()
+                .map(|cell| {
+                    let Some(day) = cell else {
+                        return Span::raw(" ".repeat(cell_width));
+                    };
+                    let marker = if has_events(day) { "•" } else { " " };
+                    let text = format!("{:>width$}{marker}", day, width = cell_width - 1);
+                    let is_today = today.same_day(&InGameDate {
+                        day,
+                        ..self.cursor.clone()
+                    });
+                    let mut style = if is_holiday(day) {
+                        Style::default().fg(theme::WARNING)
+                    } else {
+                        Style::default().fg(theme::TEXT)
+                    };
+                    if is_today {
+                        style = style.fg(theme::SUCCESS).add_modifier(Modifier::BOLD);
+                    }
+                    if day == self.cursor.day {
+                        style = style.bg(theme::PRIMARY_DARK).add_modifier(Modifier::BOLD);
+                    }
+                    Span::styled(text, style)
+                })
+                .collect();
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::from(vec![
+            Span::styled(" • ", Style::default().fg(theme::TEXT)),
+            Span::styled("events  ", Style::default().fg(theme::TEXT_MUTED)),
+            Span::styled("■ ", Style::default().fg(theme::WARNING)),
+            Span::styled("holiday  ", Style::default().fg(theme::TEXT_MUTED)),
+            Span::styled("■ ", Style::default().fg(theme::SUCCESS)),
+            Span::styled("today", Style::default().fg(theme::TEXT_MUTED)),
+        ]));
+
+        // Events elsewhere in this month
+        let mut month_events: Vec<&WorldEvent> = campaign
+            .world
+            .events
+            .iter()
+            .filter(|e| {
+                e.in_game_date.year == self.cursor.year && e.in_game_date.month == self.cursor.month
+            })
+            .collect();
+        month_events.sort_by_key(|e| e.in_game_date.day);
+        if !month_events.is_empty() {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(Span::styled(
+                " This month",
+                Style::default().fg(theme::PRIMARY_LIGHT),
+            )));
+            for event in month_events {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!(" {:>3}  ", event.in_game_date.day),
+                        Style::default().fg(theme::TEXT_MUTED),
+                    ),
+                    Span::styled(event.title.clone(), Style::default().fg(theme::TEXT)),
+                ]));
+            }
+        }
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_day(&self, frame: &mut Frame, area: Rect, campaign: &CampaignCalendar) {
+        let block = theme::block_default("Day");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let calendar = &campaign.world.calendar;
+        let muted = Style::default().fg(theme::TEXT_MUTED);
+        let date = &self.cursor;
+
+        let mut lines: Vec<Line> = vec![Line::from(Span::styled(
+            calendar.format_date(date),
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        ))];
+
+        let weekday = calendar
+            .weekday(date)
+            .map(str::to_string)
+            .unwrap_or_else(|| "Festival day".to_string());
+        let mut facts = vec![weekday];
+        if let Some(season) = calendar.season(date) {
+            facts.push(season.name.clone());
+        }
+        if date.same_day(&campaign.world.current_date) {
+            facts.push("today".to_string());
+        } else if let Ok(delta) = calendar.days_between(&campaign.world.current_date, date) {
+            facts.push(if delta > 0 {
+                format!("in {delta} days")
+            } else {
+                format!("{} days ago", -delta)
+            });
+        }
+        lines.push(Line::from(Span::styled(facts.join(" · "), muted)));
+
+        for (moon, phase) in calendar.moon_phases(date) {
+            lines.push(Line::from(vec![
+                Span::raw(format!("{} ", phase.symbol())),
+                Span::styled(format!("{}: ", moon.name), muted),
+                Span::styled(phase.label(), Style::default().fg(theme::INFO)),
+            ]));
+        }
+        for holiday in calendar.holidays_on(date) {
+            let mut spans = vec![Span::styled(
+                format!("★ {}", holiday.name),
+                Style::default().fg(theme::WARNING),
+            )];
+            if let Some(ref desc) = holiday.description {
+                spans.push(Span::styled(format!(" — {desc}"), muted));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(Span::styled(
+            "─".repeat(inner.width as usize),
+            Style::default().fg(theme::TEXT_DIM),
+        )));
+
+        let events = campaign.world.events_on(date);
+        if events.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No events — press a to add one",
+                Style::default().fg(theme::TEXT_DIM),
+            )));
+        }
+        for (i, event) in events.iter().enumerate() {
+            let selected = i == self.event_cursor;
+            let title_style = if selected {
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::TEXT)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    if selected { "▸ " } else { "  " },
+                    Style::default().fg(theme::ACCENT),
+                ),
+                Span::styled(event.title.clone(), title_style),
+                Span::styled(format!("  {:?}", event.event_type), muted),
+            ]));
+            if selected && !event.description.is_empty() {
+                lines.push(Line::raw(format!("  {}", event.description)));
+            }
+        }
+
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+
+    fn render_hints(&self, frame: &mut Frame, area: Rect) {
+        let hint = |key: &'static str, desc: &'static str| {
+            [
+                Span::styled(key, theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(desc, theme::key_hint()),
+            ]
+        };
+        let spans: Vec<Span> = [
+            hint(" hjkl", ":move "),
+            hint("[/]", ":month "),
+            hint("{/}", ":year "),
+            hint("t", ":today "),
+            hint(".", ":set today "),
+            hint("+/-", ":advance "),
+            hint("a", ":add "),
+            hint("d", ":delete "),
+            hint("c", ":calendar "),
+            hint("s", ":campaign"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let line = match (&self.error, self.modal) {
+            (Some(err), None) => Line::from(Span::styled(
+                format!(" ✗ {err}"),
+                Style::default().fg(theme::ERROR),
+            )),
+            _ => Line::from(spans),
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+
+    fn render_form(&self, frame: &mut Frame, area: Rect, campaign: &CampaignCalendar) {
+        let modal_area = centered_rect(60, 30, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(format!(
+                " New Event — {} ",
+                campaign.world.calendar.format_date(&self.cursor)
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::ACCENT));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let focused = EVENT_FIELDS[self.form_focus];
+        let field_line = |field: EventField, label: &str, value: String| {
+            let is_focused = field == focused;
+            let label_style = if is_focused {
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::TEXT_MUTED)
+            };
+            Line::from(vec![
+                Span::raw(if is_focused { " ▸ " } else { "   " }),
+                Span::styled(format!("{label:<13}"), label_style),
+                Span::styled(value, Style::default().fg(theme::TEXT)),
+            ])
+        };
+        let with_cursor = |field: EventField, buf: &InputBuffer| {
+            if field == focused {
+                format!("{}▎", buf.text())
+            } else {
+                buf.text().to_string()
+            }
+        };
+        let event_type = format!("{:?}", EVENT_TYPES[self.form_type]);
+        let event_type = if focused == EventField::Type {
+            format!("◂ {event_type} ▸")
+        } else {
+            event_type
+        };
+
+        let footer = match self.error {
+            Some(ref err) => Line::from(Span::styled(
+                format!(" ✗ {err}"),
+                Style::default().fg(theme::ERROR),
+            )),
+            None => Line::from(vec![
+                Span::styled(" Tab", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":field ", theme::key_hint()),
+                Span::styled("←/→", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":type ", theme::key_hint()),
+                Span::styled("Enter", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":save ", theme::key_hint()),
+                Span::styled("Esc", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":cancel", theme::key_hint()),
+            ]),
+        };
+
+        let lines = vec![
+            Line::raw(""),
+            field_line(
+                EventField::Title,
+                "Title:",
+                with_cursor(EventField::Title, &self.form_title),
+            ),
+            field_line(EventField::Type, "Type:", event_type),
+            field_line(
+                EventField::Description,
+                "Description:",
+                with_cursor(EventField::Description, &self.form_description),
+            ),
+            Line::raw(""),
+            footer,
+        ];
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+
+    fn render_delete_modal(&self, frame: &mut Frame, area: Rect) {
+        let modal_area = centered_rect(40, 20, area);
+        frame.render_widget(Clear, modal_area);
+
+        let events = self.events_on_cursor();
+        let title = events
+            .get(self.event_cursor)
+            .map(|e| e.title.as_str())
+            .unwrap_or("?");
+        let block = Block::default()
+            .title(" Delete Event ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::ERROR));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let lines = vec![
+            Line::raw(""),
+            Line::from(vec![
+                Span::raw("  Delete "),
+                Span::styled(
+                    title.to_string(),
+                    Style::default()
+                        .fg(theme::ACCENT)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("?"),
+            ]),
+            Line::raw(""),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled("y/Enter", Style::default().fg(theme::SUCCESS)),
+                Span::raw(" to confirm, "),
+                Span::styled("n/Esc", Style::default().fg(theme::ERROR)),
+                Span::raw(" to cancel"),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+// ── Free helpers ───────────────────────────────────────────────────────────
+
+/// Move a date by whole months, skipping months absent in that year (leap-only
+/// days) and clamping the day to the target month's length.
+fn shift_month(calendar: &Calendar, date: &InGameDate, delta: i32) -> InGameDate {
+    let months = calendar.months.len() as i64;
+    let mut index = date.year as i64 * months + date.month as i64 - 1;
+    let step = if delta < 0 { -1 } else { 1 };
+    let mut remaining = delta.unsigned_abs();
+    let mut year = date.year;
+    let mut month = date.month;
+    while remaining > 0 {
+        index += step;
+        year = index.div_euclid(months) as i32;
+        month = index.rem_euclid(months) as u8 + 1;
+        if calendar.month_length(year, month) > 0 {
+            remaining -= 1;
+        }
+    }
+    InGameDate {
+        year,
+        month,
+        day: date.day.clamp(1, calendar.month_length(year, month).max(1)),
+        ..date.clone()
+    }
+}
+
+/// Rows of day numbers for a month, aligned to weekday columns. Intercalary
+/// months and calendars without weeks fill rows from the first column.
+fn month_grid(calendar: &Calendar, year: i32, month: u8) -> Vec<Vec<Option<u8>>> {
+    let columns = calendar.weekdays.len().max(1);
+    let length = calendar.month_length(year, month);
+    let lead = InGameDate {
+        year,
+        month,
+        day: 1,
+        ..InGameDate::default()
+    };
+    let offset = calendar.weekday_index(&lead).unwrap_or(0);
+
+    let mut cells: Vec<Option<u8>> = vec![None; offset];
+    cells.extend((1..=length).map(Some));
+    cells
+        .chunks(columns)
+        .map(|row| {
+            let mut row = row.to_vec();
+            row.resize(columns, None);
+            row
+        })
+        .collect()
+}
+
+fn route_text_input(buf: &mut InputBuffer, code: KeyCode, modifiers: KeyModifiers) {
+    match (modifiers, code) {
+        (KeyModifiers::NONE, KeyCode::Char(c)) | (KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+            buf.insert_char(c);
+        }
+        (KeyModifiers::NONE, KeyCode::Backspace) => buf.backspace(),
+        (KeyModifiers::NONE, KeyCode::Delete) => buf.delete(),
+        (KeyModifiers::NONE, KeyCode::Left) => buf.move_left(),
+        (KeyModifiers::NONE, KeyCode::Right) => buf.move_right(),
+        (KeyModifiers::NONE, KeyCode::Home) => buf.move_home(),
+        (KeyModifiers::NONE, KeyCode::End) => buf.move_end(),
+        _ => {}
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    s.chars().take(max).collect()
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_grid_aligns_weekdays() {
+        let calendar = Calendar::preset("Gregorian").unwrap();
+        // 1 February 2024 was a Thursday
+        let grid = month_grid(&calendar, 2024, 2);
+        assert_eq!(grid[0][4], Some(1));
+        assert!(grid[0][..4].iter().all(Option::is_none));
+        let days: usize = grid.iter().flatten().flatten().count();
+        assert_eq!(days, 29);
+    }
+
+    #[test]
+    fn test_shift_month_skips_missing_leap_day() {
+        let calendar = Calendar::preset("Harptos").unwrap();
+        let midsummer = calendar.month_index("Midsummer").unwrap();
+        let eleasis = calendar.month_index("Eleasis").unwrap();
+
+        let common = calendar.date(1491, midsummer, 1).unwrap();
+        assert_eq!(shift_month(&calendar, &common, 1).month, eleasis);
+
+        let leap = calendar.date(1492, midsummer, 1).unwrap();
+        assert_eq!(shift_month(&calendar, &leap, 1).month, midsummer + 1);
+    }
+
+    #[test]
+    fn test_shift_month_clamps_and_wraps_years() {
+        let calendar = Calendar::preset("Gregorian").unwrap();
+        let date = calendar.date(2023, 12, 31).unwrap();
+        let next = shift_month(&calendar, &date, 2);
+        assert_eq!((next.year, next.month, next.day), (2024, 2, 29));
+        let back = shift_month(&calendar, &next, -14);
+        assert_eq!((back.year, back.month, back.day), (2022, 12, 29));
+    }
+
+    #[test]
+    fn test_unparseable_world_state_is_read_only() {
+        let mut record = CampaignRecord::new("c1".into(), "Test".into(), "dnd5e".into());
+        record.world_state = Some(r#"{"season": "winter"}"#.to_string());
+        assert!(CampaignCalendar::from_record(record).read_only);
+
+        let mut record = CampaignRecord::new("c2".into(), "Test".into(), "dnd5e".into());
+        let world = WorldState::new("c2");
+        record.world_state = Some(serde_json::to_string(&world).unwrap());
+        let campaign = CampaignCalendar::from_record(record);
+        assert!(!campaign.read_only);
+        assert_eq!(campaign.world.campaign_id, "c2");
+    }
+}
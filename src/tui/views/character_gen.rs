@@ -1,8 +1,10 @@
 //! Character Generation view — wizard-style UI for creating TTRPG characters.
 //!
-//! Phase state machine: SystemSelect → Options → Generating → Display.
+//! Phase state machine: SystemSelect → Options → Generating → Display → Advance.
 //! Press j/k to navigate, Enter to advance, Esc to go back.
-//! In Display: 'b' backstory, 's' save, 'r' regenerate, 'n' new character, 'l' saved list.
+//! In Display: 'b' backstory, 's' save, 'r' regenerate, 'n' new character,
+//! 'a' advance the character (level up, spend XP, milestone, development).
+//! In the saved list ('l'), Enter opens a saved character for advancing.
 
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
@@ -19,7 +21,8 @@ use crate::core::character_gen::prompts::{
     estimate_tokens, recommended_temperature, BackstoryPromptBuilder,
 };
 use crate::core::character_gen::{
-    BackstoryLength, Character, CharacterGenerator, GenerationOptions, SystemInfo,
+    AdvancementKind, AdvancementPreview, AdvancementRequest, BackstoryLength, Character,
+    CharacterGenerator, GenerationOptions, Milestone, SystemInfo,
 };
 use crate::core::llm::{ChatMessage, ChatRequest, MessageRole};
 use crate::database::{CharacterOps, CharacterRecord};
@@ -35,6 +38,7 @@ pub enum GenPhase {
     Options,
     Generating,
     Display,
+    Advance,
 }
 
 // ── Internal async event channel ────────────────────────────────────────────
@@ -124,6 +128,76 @@ impl GenForm {
     }
 }
 
+// ── Advancement form ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AdvanceRow {
+    Milestone,
+    Award,
+    RollHitPoints,
+    Slot(usize),
+}
+
+struct AdvanceForm {
+    preview: AdvancementPreview,
+    request: AdvancementRequest,
+    /// Picked option per preview slot
+    picks: Vec<Option<usize>>,
+    focus: usize,
+}
+
+impl AdvanceForm {
+    fn new(preview: AdvancementPreview, request: AdvancementRequest) -> Self {
+        let picks = default_picks(&preview);
+        Self {
+            preview,
+            request,
+            picks,
+            focus: 0,
+        }
+    }
+
+    fn rows(&self) -> Vec<AdvanceRow> {
+        let mut rows = vec![];
+        match self.preview.kind {
+            AdvancementKind::Milestone => rows.push(AdvanceRow::Milestone),
+            AdvancementKind::ExperienceSpend => rows.push(AdvanceRow::Award),
+            _ => {}
+        }
+        if self.preview.hit_die.is_some() {
+            rows.push(AdvanceRow::RollHitPoints);
+        }
+        rows.extend((0..self.preview.slots.len()).map(AdvanceRow::Slot));
+        rows
+    }
+
+    /// Request with the current picks filled in
+    fn to_request(&self) -> AdvancementRequest {
+        AdvancementRequest {
+            choices: self.preview.choices(&self.picks),
+            ..self.request.clone()
+        }
+    }
+}
+
+/// Required slots start on an option (spread across repeated slots), optional
+/// ones start empty
+fn default_picks(preview: &AdvancementPreview) -> Vec<Option<usize>> {
+    let mut seen: Vec<&str> = vec![];
+    preview
+        .slots
+        .iter()
+        .map(|slot| {
+            if !slot.required || slot.options.is_empty() {
+                return None;
+            }
+            let repeats = seen.iter().filter(|label| **label == slot.label).count();
+            seen.push(&slot.label);
+            Some(repeats % slot.options.len())
+        })
+        .collect()
+}
+
 // ── State ───────────────────────────────────────────────────────────────────
 
 pub struct CharacterGenState {
@@ -139,6 +213,8 @@ pub struct CharacterGenState {
     generated: Option<Character>,
     backstory: Option<String>,
     backstory_loading: bool,
+    // Advancement
+    advance: Option<AdvanceForm>,
     // Saved characters list
     saved_characters: Vec<CharacterRecord>,
     show_saved: bool,
@@ -166,6 +242,7 @@ impl CharacterGenState {
             generated: None,
             backstory: None,
             backstory_loading: false,
+            advance: None,
             saved_characters: Vec::new(),
             show_saved: false,
             saved_selected: 0,
@@ -247,6 +324,7 @@ impl CharacterGenState {
             GenPhase::Options => self.handle_options_input(*code, *modifiers),
             GenPhase::Generating => true, // absorb all input during generation
             GenPhase::Display => self.handle_display_input(*code, *modifiers, services),
+            GenPhase::Advance => self.handle_advance_input(*code, *modifiers),
         }
     }
 
@@ -400,6 +478,10 @@ impl CharacterGenState {
                 self.cmd_generate();
                 true
             }
+            (KeyModifiers::NONE, KeyCode::Char('a')) => {
+                self.cmd_open_advance();
+                true
+            }
             (KeyModifiers::NONE, KeyCode::Char('n')) => {
                 self.phase = GenPhase::SystemSelect;
                 self.generated = None;
@@ -413,6 +495,76 @@ impl CharacterGenState {
         }
     }
 
+    fn handle_advance_input(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        let Some(form) = self.advance.as_mut() else {
+            self.phase = GenPhase::Display;
+            return false;
+        };
+        let rows = form.rows();
+
+        match (modifiers, code) {
+            (KeyModifiers::NONE, KeyCode::Esc) => {
+                self.advance = None;
+                self.error = None;
+                self.phase = GenPhase::Display;
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Tab) | (KeyModifiers::NONE, KeyCode::Down) => {
+                if !rows.is_empty() {
+                    form.focus = (form.focus + 1) % rows.len();
+                }
+                true
+            }
+            (KeyModifiers::SHIFT, KeyCode::BackTab) | (KeyModifiers::NONE, KeyCode::Up) => {
+                if !rows.is_empty() {
+                    form.focus = if form.focus == 0 { rows.len() - 1 } else { form.focus - 1 };
+                }
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Enter) => {
+                self.cmd_advance();
+                true
+            }
+            _ => match rows.get(form.focus).copied() {
+                Some(AdvanceRow::Milestone) => {
+                    let milestone = form.request.milestone.unwrap_or_default();
+                    form.request.milestone = match code {
+                        KeyCode::Char('j') | KeyCode::Right => Some(milestone.next()),
+                        KeyCode::Char('k') | KeyCode::Left => Some(milestone.prev()),
+                        _ => return false,
+                    };
+                    self.refresh_advance_preview();
+                    true
+                }
+                Some(AdvanceRow::Award) => {
+                    let award = form.request.experience_award;
+                    form.request.experience_award = match code {
+                        KeyCode::Char('j') | KeyCode::Right => award + 1,
+                        KeyCode::Char('k') | KeyCode::Left => award.saturating_sub(1),
+                        KeyCode::PageUp => award + 10,
+                        KeyCode::PageDown => award.saturating_sub(10),
+                        _ => return false,
+                    };
+                    self.refresh_advance_preview();
+                    true
+                }
+                Some(AdvanceRow::RollHitPoints) => {
+                    toggle_field(code, &mut form.request.roll_hit_points)
+                }
+                Some(AdvanceRow::Slot(slot)) => {
+                    let slot_info = &form.preview.slots[slot];
+                    cycle_pick(
+                        code,
+                        slot_info.options.len(),
+                        slot_info.required,
+                        &mut form.picks[slot],
+                    )
+                }
+                None => false,
+            },
+        }
+    }
+
     fn handle_saved_list_input(
         &mut self,
         code: KeyCode,
@@ -435,6 +587,10 @@ impl CharacterGenState {
                 self.saved_selected = self.saved_selected.saturating_sub(1);
                 true
             }
+            (KeyModifiers::NONE, KeyCode::Enter) => {
+                self.cmd_open_saved();
+                true
+            }
             (KeyModifiers::NONE, KeyCode::Char('d')) => {
                 // Delete selected character
                 if let Some(record) = self.saved_characters.get(self.saved_selected) {
@@ -554,6 +710,85 @@ impl CharacterGenState {
         }
     }
 
+    fn cmd_open_saved(&mut self) {
+        let Some(record) = self.saved_characters.get(self.saved_selected) else {
+            return;
+        };
+        match serde_json::from_str::<Character>(&record.data_json) {
+            Ok(character) => {
+                if let Some(idx) = self.systems.iter().position(|s| s.id == record.system) {
+                    self.selected_system = idx;
+                    self.form.reset(&self.systems[idx]);
+                }
+                self.backstory = character.backstory.clone();
+                self.generated = Some(character);
+                self.backstory_loading = false;
+                self.show_saved = false;
+                self.scroll_offset = 0;
+                self.error = None;
+                self.phase = GenPhase::Display;
+            }
+            Err(e) => {
+                self.error = Some(format!("Could not open {}: {e}", record.name));
+            }
+        }
+    }
+
+    fn cmd_open_advance(&mut self) {
+        let Some(character) = self.generated.as_ref() else {
+            return;
+        };
+        let request = AdvancementRequest {
+            milestone: Some(Milestone::default()),
+            ..Default::default()
+        };
+        match CharacterGenerator::preview_advancement(character, &request) {
+            Ok(preview) => {
+                self.advance = Some(AdvanceForm::new(preview, request));
+                self.error = None;
+                self.phase = GenPhase::Advance;
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Rebuild the preview after the milestone or experience award changed
+    fn refresh_advance_preview(&mut self) {
+        let (Some(character), Some(form)) = (self.generated.as_ref(), self.advance.as_mut()) else {
+            return;
+        };
+        match CharacterGenerator::preview_advancement(character, &form.request) {
+            Ok(preview) => {
+                form.picks = default_picks(&preview);
+                form.preview = preview;
+                form.focus = form.focus.min(form.rows().len().saturating_sub(1));
+                self.error = None;
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    fn cmd_advance(&mut self) {
+        let (Some(character), Some(form)) = (self.generated.as_mut(), self.advance.as_ref()) else {
+            return;
+        };
+        match CharacterGenerator::advance(character, &form.to_request()) {
+            Ok(_) => {
+                self.advance = None;
+                self.error = None;
+                self.scroll_offset = 0;
+                self.phase = GenPhase::Display;
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
     fn cmd_backstory(&mut self, services: &Services) {
         let character = match self.generated.clone() {
             Some(ch) => ch,
@@ -677,6 +912,7 @@ impl CharacterGenState {
             GenPhase::Options => self.render_options(frame, area),
             GenPhase::Generating => self.render_generating(frame, area),
             GenPhase::Display => self.render_display(frame, area),
+            GenPhase::Advance => self.render_advance(frame, area),
         }
     }
 
@@ -997,6 +1233,12 @@ impl CharacterGenState {
                 Style::default().fg(theme::TEXT_MUTED),
             ));
         }
+        if character.experience > 0 {
+            info_parts.push(Span::styled(
+                format!(" {} XP", character.experience),
+                Style::default().fg(theme::TEXT_MUTED),
+            ));
+        }
         lines.push(Line::from(info_parts));
 
         if !character.concept.is_empty() {
@@ -1125,6 +1367,57 @@ impl CharacterGenState {
             }
         }
 
+        // ── Notes (derived stats) ──
+        if !character.notes.is_empty() {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(Span::styled(
+                "  NOTES",
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for note in character.notes.lines() {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(note.to_string(), Style::default().fg(theme::TEXT_MUTED)),
+                ]));
+            }
+        }
+
+        // ── Advancement history ──
+        if !character.advancement_history.is_empty() {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(Span::styled(
+                "  ADVANCEMENT",
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for record in &character.advancement_history {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled("• ", Style::default().fg(theme::PRIMARY_LIGHT)),
+                    Span::styled(
+                        record.summary(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!(" {}", record.advanced_at.format("%Y-%m-%d")),
+                        Style::default().fg(theme::TEXT_MUTED),
+                    ),
+                ]));
+                for change in &record.changes {
+                    lines.push(Line::from(vec![
+                        Span::raw("    "),
+                        Span::styled(
+                            truncate(change, 70),
+                            Style::default().fg(theme::TEXT_MUTED),
+                        ),
+                    ]));
+                }
+            }
+        }
+
         // ── Background ──
         let bg = &character.background;
         if !bg.origin.is_empty() || !bg.motivation.is_empty() {
@@ -1210,6 +1503,8 @@ impl CharacterGenState {
             Span::raw(":save "),
             Span::styled("r", Style::default().fg(theme::TEXT_MUTED)),
             Span::raw(":regenerate "),
+            Span::styled("a", Style::default().fg(theme::TEXT_MUTED)),
+            Span::raw(":advance "),
             Span::styled("n", Style::default().fg(theme::TEXT_MUTED)),
             Span::raw(":new "),
             Span::styled("j/k", Style::default().fg(theme::TEXT_MUTED)),
@@ -1222,6 +1517,179 @@ impl CharacterGenState {
         frame.render_widget(content, inner);
     }
 
+    fn render_advance(&self, frame: &mut Frame, area: Rect) {
+        let (Some(character), Some(form)) = (self.generated.as_ref(), self.advance.as_ref()) else {
+            return;
+        };
+        let preview = &form.preview;
+
+        let block = Block::default()
+            .title(format!(" {} — {} ", character.name, preview.kind.label()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::TEXT_MUTED));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        lines.push(Line::raw(""));
+
+        if let Some(next) = preview.next_level {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    format!("Level {} → {next}", character.level),
+                    Style::default()
+                        .fg(theme::ACCENT)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]));
+        }
+        if let Some(ref hp) = preview.hit_points {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled("Hit points: ", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(hp.clone()),
+            ]));
+        }
+        if preview.kind == AdvancementKind::ExperienceSpend {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled("Experience: ", Style::default().fg(theme::TEXT_MUTED)),
+                Span::raw(format!(
+                    "{} available, {} to spend",
+                    preview.experience,
+                    preview.cost(&form.picks)
+                )),
+            ]));
+        }
+        for feature in &preview.features {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled("+ ", Style::default().fg(theme::SUCCESS)),
+                Span::styled(
+                    feature.name.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        " {}",
+                        truncate(
+                            feature.mechanical_effect.as_deref().unwrap_or(&feature.description),
+                            60
+                        )
+                    ),
+                    Style::default().fg(theme::TEXT_MUTED),
+                ),
+            ]));
+        }
+        for note in &preview.notes {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(note.clone(), Style::default().fg(theme::TEXT_MUTED)),
+            ]));
+        }
+        lines.push(Line::raw(""));
+
+        for (i, row) in form.rows().into_iter().enumerate() {
+            let is_focused = i == form.focus;
+            let marker = if is_focused { "▸" } else { " " };
+            let label_style = if is_focused {
+                Style::default().fg(theme::ACCENT).bold()
+            } else {
+                Style::default().fg(theme::TEXT_MUTED)
+            };
+
+            let (label, value, detail) = match row {
+                AdvanceRow::Milestone => {
+                    let milestone = form.request.milestone.unwrap_or_default();
+                    ("Milestone".to_string(), format!("◀ {} ▶", milestone.label()), None)
+                }
+                AdvanceRow::Award => (
+                    "Award XP".to_string(),
+                    format!("◀ {} ▶", form.request.experience_award),
+                    None,
+                ),
+                AdvanceRow::RollHitPoints => {
+                    let val = if form.request.roll_hit_points {
+                        "[✓] Roll"
+                    } else {
+                        "[ ] Average"
+                    };
+                    ("Hit Points".to_string(), val.to_string(), None)
+                }
+                AdvanceRow::Slot(slot) => {
+                    let slot_info = &preview.slots[slot];
+                    let pick = form.picks[slot];
+                    let val = match pick.and_then(|p| slot_info.options.get(p)) {
+                        Some(option) => format!(
+                            "◀ {option} ▶  ({}/{})",
+                            pick.unwrap_or(0) + 1,
+                            slot_info.options.len()
+                        ),
+                        None if slot_info.options.is_empty() => "(nothing available)".to_string(),
+                        None => "◀ — ▶".to_string(),
+                    };
+                    let mut label = slot_info.label.clone();
+                    if slot_info.cost > 0 {
+                        label.push_str(&format!(" ({} XP)", slot_info.cost));
+                    }
+                    let detail = pick.and_then(|p| slot_info.details.get(p)).cloned();
+                    (label, val, detail)
+                }
+            };
+
+            let val_style = if is_focused {
+                Style::default().fg(theme::TEXT)
+            } else {
+                Style::default()
+            };
+
+            lines.push(Line::from(vec![
+                Span::raw(format!("  {marker} ")),
+                Span::styled(format!("{:<22}", format!("{label}:")), label_style),
+                Span::styled(value, val_style),
+            ]));
+            if let Some(detail) = detail.filter(|_| is_focused) {
+                lines.push(Line::from(vec![
+                    Span::raw("      "),
+                    Span::styled(truncate(&detail, 70), Style::default().fg(theme::TEXT_MUTED)),
+                ]));
+            }
+        }
+
+        // Footer
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {}",
+                "─".repeat(inner.width.saturating_sub(4) as usize)
+            ),
+            Style::default().fg(theme::TEXT_MUTED),
+        )));
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("Tab/↑↓", Style::default().fg(theme::TEXT_MUTED)),
+            Span::raw(":fields "),
+            Span::styled("j/k/◀▶", Style::default().fg(theme::TEXT_MUTED)),
+            Span::raw(":cycle "),
+            Span::styled("Enter", Style::default().fg(theme::TEXT_MUTED)),
+            Span::raw(":apply "),
+            Span::styled("Esc", Style::default().fg(theme::TEXT_MUTED)),
+            Span::raw(":back"),
+        ]));
+
+        if let Some(ref err) = self.error {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(format!("✗ {err}"), Style::default().fg(theme::ERROR)),
+            ]));
+        }
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
     fn render_saved_list(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
             .title(format!(" Saved Characters ({}) ", self.saved_characters.len()))
@@ -1282,6 +1750,8 @@ impl CharacterGenState {
             Span::raw("  "),
             Span::styled("j/k", Style::default().fg(theme::TEXT_MUTED)),
             Span::raw(":navigate "),
+            Span::styled("Enter", Style::default().fg(theme::TEXT_MUTED)),
+            Span::raw(":open "),
             Span::styled("l/Esc", Style::default().fg(theme::TEXT_MUTED)),
             Span::raw(":close"),
         ]));
@@ -1344,6 +1814,24 @@ fn cycle_select(code: KeyCode, len: usize, idx: &mut usize) -> bool {
     }
 }
 
+/// Cycle through a slot's options; optional slots also cycle through "none"
+fn cycle_pick(code: KeyCode, len: usize, required: bool, pick: &mut Option<usize>) -> bool {
+    if len == 0 {
+        return false;
+    }
+    // Position 0 is "none" for optional slots
+    let offset = usize::from(!required);
+    let positions = len + offset;
+    let current = pick.map_or(0, |p| p + offset);
+    let next = match code {
+        KeyCode::Char('j') | KeyCode::Right => (current + 1) % positions,
+        KeyCode::Char('k') | KeyCode::Left => (current + positions - 1) % positions,
+        _ => return false,
+    };
+    *pick = if next < offset { None } else { Some(next - offset) };
+    true
+}
+
 fn toggle_field(code: KeyCode, flag: &mut bool) -> bool {
     match code {
         KeyCode::Char(' ') | KeyCode::Char('j') | KeyCode::Char('k') | KeyCode::Right
@@ -1494,4 +1982,42 @@ mod tests {
             assert_eq!(state.field_count(info), FORM_FIELD_COUNT - 1);
         }
     }
+
+    #[test]
+    fn test_cycle_pick_optional_includes_none() {
+        let mut pick = None;
+        assert!(cycle_pick(KeyCode::Right, 2, false, &mut pick));
+        assert_eq!(pick, Some(0));
+        cycle_pick(KeyCode::Right, 2, false, &mut pick);
+        cycle_pick(KeyCode::Right, 2, false, &mut pick);
+        assert_eq!(pick, None);
+
+        // Required slots never go back to "none"
+        let mut pick = Some(0);
+        cycle_pick(KeyCode::Left, 2, true, &mut pick);
+        assert_eq!(pick, Some(1));
+    }
+
+    #[test]
+    fn test_advance_generated_character() {
+        let mut state = CharacterGenState::new();
+        let options = GenerationOptions {
+            system: Some("dnd5e".to_string()),
+            class: Some("Fighter".to_string()),
+            ..Default::default()
+        };
+        state.generated = Some(CharacterGenerator::generate(&options).unwrap());
+        state.phase = GenPhase::Display;
+
+        state.cmd_open_advance();
+        assert_eq!(state.phase, GenPhase::Advance);
+        let form = state.advance.as_ref().unwrap();
+        assert_eq!(form.rows()[0], AdvanceRow::RollHitPoints);
+
+        state.cmd_advance();
+        assert_eq!(state.phase, GenPhase::Display);
+        let character = state.generated.as_ref().unwrap();
+        assert_eq!(character.level, 2);
+        assert_eq!(character.advancement_history.len(), 1);
+    }
 }
@@ -11,15 +11,19 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
+use tokio::sync::{mpsc, Mutex};
 
 use super::super::theme;
 
+use crate::core::assistant::{AssistantAgent, ToolApproval, ToolCall, ToolOutcome, ToolTrace};
 use crate::core::campaign::dice::{DiceNotation, DiceRoller};
 use crate::core::llm::router::{ChatMessage, ChatRequest};
+use crate::core::npc_memory::format_memories_for_prompt;
+use crate::core::query_rewrite::QueryRewriter;
 use crate::core::voice::types::{SynthesisRequest, OutputFormat, VoiceProviderType};
 use crate::database::{ChatMessageRecord, ConversationMessage, MessageRole, NpcConversation, NpcRecord};
 use crate::tui::audio::{AudioEvent, PlaybackState};
-use crate::tui::events::{AppEvent, Notification, NotificationLevel, RagChunkDisplay};
+use crate::tui::events::{AppEvent, Notification, NotificationLevel, RagChunkDisplay, RagQueryDisplay};
 use crate::tui::services::Services;
 use crate::tui::widgets::input_buffer::InputBuffer;
 use crate::tui::widgets::markdown::markdown_to_lines;
@@ -293,7 +297,9 @@ fn render_chat_input(
 
 const DEFAULT_SYSTEM_PROMPT: &str =
     "You are a knowledgeable TTRPG Game Master assistant. Help the GM with rules, \
-     lore, encounter design, NPC roleplay, and session planning. Be concise and practical.";
+     lore, encounter design, NPC roleplay, and session planning. Be concise and practical. \
+     Use the available tools to roll dice, look up rules, roll on random tables, manage \
+     combat, and record session notes instead of inventing results.";
 
 fn build_about_mode_prompt(
     npc: &NpcRecord,
@@ -449,6 +455,14 @@ pub struct ChatState {
     rag_pane_open: bool,
     /// Retrieved context chunks shown in the RAG pane.
     rag_chunks: Vec<RagChunkDisplay>,
+    /// Search query behind the chunks in the RAG pane.
+    rag_query: Option<RagQueryDisplay>,
+    /// Background task producing the current response.
+    response_task: Option<tokio::task::JoinHandle<()>>,
+    /// Assistant tool call awaiting the GM's y/n.
+    pending_confirmation: Option<String>,
+    /// Answers to the assistant's confirmation requests.
+    approval_tx: Option<mpsc::UnboundedSender<bool>>,
 }
 
 impl ChatState {
@@ -470,6 +484,10 @@ impl ChatState {
             speaking_text: None,
             rag_pane_open: false,
             rag_chunks: Vec::new(),
+            rag_query: None,
+            response_task: None,
+            pending_confirmation: None,
+            approval_tx: None,
         }
     }
 
@@ -491,9 +509,20 @@ impl ChatState {
         self.rag_chunks = chunks;
     }
 
+    /// Show the search query used for the latest retrieval.
+    pub fn set_rag_query(&mut self, query: RagQueryDisplay) {
+        self.rag_query = Some(query);
+    }
+
     /// Clear the RAG context pane.
     pub fn clear_rag_chunks(&mut self) {
         self.rag_chunks.clear();
+        self.rag_query = None;
+    }
+
+    /// Whether the assistant is waiting for the GM to approve a tool call.
+    pub fn awaiting_confirmation(&self) -> bool {
+        self.pending_confirmation.is_some()
     }
 
     // ── Session loading ──────────────────────────────────────────────
@@ -555,12 +584,39 @@ impl ChatState {
             return false;
         };
 
+        if self.pending_confirmation.is_some() {
+            return self.handle_confirmation_input(*code, *modifiers);
+        }
+
         match self.input_mode {
             ChatInputMode::Insert => self.handle_insert_input(*code, *modifiers, services),
             ChatInputMode::Normal => self.handle_normal_input(*code, *modifiers),
         }
     }
 
+    fn handle_confirmation_input(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        if modifiers == KeyModifiers::CONTROL && code == KeyCode::Char('c') {
+            return false;
+        }
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                self.answer_confirmation(true)
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                self.answer_confirmation(false)
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn answer_confirmation(&mut self, approved: bool) {
+        self.pending_confirmation = None;
+        if let Some(ref tx) = self.approval_tx {
+            let _ = tx.send(approved);
+        }
+    }
+
     fn handle_insert_input(
         &mut self,
         code: KeyCode,
@@ -762,6 +818,7 @@ impl ChatState {
         self.streaming_buffer.clear();
         self.streaming_record_id = None;
         self.active_stream_id = None;
+        self.clear_assistant_state();
         self.session_loading = true;
 
         let db = services.database.clone();
@@ -816,7 +873,7 @@ impl ChatState {
     fn cmd_help(&self, services: &Services) {
         let msg = match self.context {
             ChatContext::General => {
-                "Commands: /clear /new /roll <dice> /npc <name> /npcs /speak <text> /pause /resume /stop /volume <0-100> /voices /help | Ctrl+R: RAG pane | [[2d6+3]]: inline roll"
+                "Commands: /clear /new /roll <dice> /npc <name> /npcs /speak <text> /pause /resume /stop /volume <0-100> /voices /help | Ctrl+R: RAG pane | [[2d6+3]]: inline roll | y/n: approve assistant actions"
             }
             ChatContext::Npc { .. } => {
                 "NPC Commands: /voice /about /exit /roll <dice> /speak [text] /pause /resume /stop /volume <0-100> /voices /clear /help | Ctrl+R: RAG pane"
@@ -908,6 +965,7 @@ impl ChatState {
             }));
             return;
         }
+        self.extract_npc_memories(services, 2);
         self.context = ChatContext::General;
         self.messages.clear();
         self.scroll_offset = 0;
@@ -1023,6 +1081,8 @@ impl ChatState {
         };
 
         self.scroll_to_bottom();
+        // Pick up whatever the last visit left unread
+        self.extract_npc_memories(services, 1);
 
         let _ = services.event_tx.send(AppEvent::Notification(Notification {
             id: 0,
@@ -1055,6 +1115,9 @@ impl ChatState {
             tokio::spawn(async move {
                 llm.cancel_stream(&sid).await;
             });
+            if let Some(task) = self.response_task.take() {
+                task.abort();
+            }
             self.finalize_response();
         }
 
@@ -1151,27 +1214,70 @@ impl ChatState {
 
         let base_system_prompt = self.build_system_prompt();
         let user_query = text.to_string();
+        // Conversation before this message, for rewriting follow-up questions
+        let rag_history: Vec<ChatMessage> = if is_npc {
+            Vec::new()
+        } else {
+            let earlier = chat_messages.len().saturating_sub(1);
+            chat_messages[..earlier].to_vec()
+        };
+        let npc_recall = match self.context {
+            ChatContext::Npc { ref npc, .. } if services.npc_memory.enabled() => {
+                Some((services.npc_memory.clone(), npc.id.clone(), npc.name.clone()))
+            }
+            _ => None,
+        };
 
-        // 4. Spawn streaming task (with optional RAG retrieval for General mode)
+        // 4. General mode runs the tool-calling assistant (streamed); NPC mode streams plain chat
+        let assistant = if is_npc {
+            None
+        } else {
+            let (approval_tx, approval_rx) = mpsc::unbounded_channel();
+            self.approval_tx = Some(approval_tx);
+            let agent = AssistantAgent::new(
+                services.llm.clone(),
+                services.assistant_tools.clone(),
+                services.assistant_context(),
+            );
+            let approval = ChannelApproval {
+                event_tx: services.event_tx.clone(),
+                answers: Mutex::new(approval_rx),
+            };
+            Some((agent, approval))
+        };
+
+        // 5. Spawn response task (with optional RAG retrieval for General mode)
         let llm = services.llm.clone();
         let tx = services.event_tx.clone();
         let storage = services.storage.clone();
         let embedding_provider = services.embedding_provider.clone();
+        let reranker = services.reranker.clone();
+        let rewriter = QueryRewriter::new(Some(services.llm.clone()));
 
-        tokio::spawn(async move {
-            // RAG: retrieve context if embeddings available and not NPC mode
+        let task = tokio::spawn(async move {
+            // RAG context in General mode; NPC mode recalls the NPC's memories instead
             let system_prompt = if !is_npc {
-                if let Some(ref provider) = embedding_provider {
-                    match try_rag_retrieval(
-                        &storage, provider.as_ref(), &user_query, &tx,
-                    ).await {
-                        Some(rag_prompt) => {
-                            format!("{base_system_prompt}\n\n{rag_prompt}")
-                        }
-                        None => base_system_prompt,
+                let search = rewriter.rewrite(&user_query, &rag_history).await;
+                match try_rag_retrieval(
+                    &storage, embedding_provider.as_deref(), reranker.as_deref(),
+                    &search.query, search.is_rewritten(), &tx,
+                ).await {
+                    Some(rag_prompt) => {
+                        format!("{base_system_prompt}\n\n{rag_prompt}")
+                    }
+                    None => base_system_prompt,
+                }
+            } else if let Some((memory, npc_id, npc_name)) = npc_recall {
+                match memory.recall(&npc_id, &user_query).await {
+                    Ok(recalled) if !recalled.is_empty() => {
+                        let section = format_memories_for_prompt(&npc_name, &recalled);
+                        format!("{base_system_prompt}\n\n{section}")
+                    }
+                    Ok(_) => base_system_prompt,
+                    Err(e) => {
+                        log::debug!("NPC memory recall failed (proceeding without): {e}");
+                        base_system_prompt
                     }
-                } else {
-                    base_system_prompt
                 }
             } else {
                 base_system_prompt
@@ -1179,6 +1285,26 @@ impl ChatState {
 
             let request = ChatRequest::new(chat_messages).with_system(&system_prompt);
 
+            if let Some((agent, approval)) = assistant {
+                let trace_tx = tx.clone();
+                let on_trace = move |trace: &ToolTrace| {
+                    let _ = trace_tx.send(AppEvent::AssistantToolTrace(trace.clone()));
+                };
+                let token_tx = tx.clone();
+                let on_token = move |token: &str| {
+                    let _ = token_tx.send(AppEvent::LlmToken(token.to_string()));
+                };
+                match agent.run_streaming(request, &approval, on_trace, on_token).await {
+                    Ok(_) => {
+                        let _ = tx.send(AppEvent::LlmDone);
+                    }
+                    Err(e) => {
+                        let _ = tx.send(AppEvent::LlmError(e.to_string()));
+                    }
+                }
+                return;
+            }
+
             match llm.stream_chat(request).await {
                 Ok(mut rx) => {
                     while let Some(chunk_result) = rx.recv().await {
@@ -1206,6 +1332,7 @@ impl ChatState {
             }
         });
 
+        self.response_task = Some(task);
         self.active_stream_id = self.streaming_record_id.clone();
     }
 
@@ -1290,6 +1417,7 @@ impl ChatState {
 
         self.streaming_buffer.clear();
         self.active_stream_id = None;
+        self.clear_assistant_state();
     }
 
     pub fn finalize_and_persist(&mut self, services: &Services) {
@@ -1362,6 +1490,59 @@ impl ChatState {
                 }
             });
         }
+
+        let extract_every = services.npc_memory.config().extract_every.max(1);
+        self.extract_npc_memories(services, extract_every);
+    }
+
+    /// Extract long-term memories from the NPC messages not yet read, if
+    /// there are at least `min_new` of them. How far the conversation has
+    /// been read is tracked by the memory manager, which also runs one
+    /// extraction per conversation at a time.
+    fn extract_npc_memories(&self, services: &Services, min_new: usize) {
+        if !services.npc_memory.enabled() {
+            return;
+        }
+        let ChatContext::Npc {
+            ref npc,
+            ref conversation_id,
+            ref npc_messages,
+            ..
+        } = self.context
+        else {
+            return;
+        };
+
+        let messages = npc_messages.clone();
+
+        let memory = services.npc_memory.clone();
+        let tx = services.event_tx.clone();
+        let npc = npc.clone();
+        let conversation_id = conversation_id.clone();
+        tokio::spawn(async move {
+            match memory
+                .remember_conversation(
+                    &npc.id,
+                    &npc.name,
+                    npc.campaign_id.as_deref(),
+                    &conversation_id,
+                    &messages,
+                    min_new,
+                )
+                .await
+            {
+                Ok(0) => {}
+                Ok(count) => {
+                    let _ = tx.send(AppEvent::Notification(Notification {
+                        id: 0,
+                        message: format!("{} will remember {count} new thing(s)", npc.name),
+                        level: NotificationLevel::Info,
+                        ttl_ticks: 60,
+                    }));
+                }
+                Err(e) => log::warn!("NPC memory extraction failed for {}: {e}", npc.name),
+            }
+        });
     }
 
     pub fn handle_stream_error(&mut self, error: &str, services: &Services) {
@@ -1406,6 +1587,53 @@ impl ChatState {
         }));
     }
 
+    // ── Assistant tool calls (called by AppState) ────────────────────
+
+    /// Show an assistant tool call in the transcript, above the pending reply.
+    pub fn append_tool_trace(&mut self, trace: &ToolTrace) {
+        let color = match trace.outcome {
+            ToolOutcome::Success(_) => theme::ACCENT,
+            ToolOutcome::Declined => theme::TEXT_MUTED,
+            ToolOutcome::Failed(_) | ToolOutcome::UnknownTool => theme::ERROR,
+        };
+        let text = trace.to_string();
+        let record = ChatMessageRecord::with_role(
+            self.session_id.clone().unwrap_or_default(),
+            MessageRole::System,
+            text.clone(),
+        );
+        let display = DisplayMessage {
+            id: record.id.clone(),
+            role: MessageRole::System,
+            raw_content: text.clone(),
+            rendered_lines: vec![Line::styled(format!("🔧 {text}"), Style::default().fg(color))],
+            created_at: record.created_at,
+            is_streaming: false,
+        };
+
+        let index = match self.messages.last() {
+            Some(last) if last.is_streaming => self.messages.len() - 1,
+            _ => self.messages.len(),
+        };
+        self.messages.insert(index, display);
+        if self.auto_scroll {
+            self.scroll_to_bottom();
+        }
+    }
+
+    /// Ask the GM to approve a state-changing tool call.
+    pub fn request_confirmation(&mut self, summary: String) {
+        if self.approval_tx.is_some() {
+            self.pending_confirmation = Some(summary);
+        }
+    }
+
+    fn clear_assistant_state(&mut self) {
+        self.response_task = None;
+        self.pending_confirmation = None;
+        self.approval_tx = None;
+    }
+
     // ── Audio event handling ──────────────────────────────────────────
 
     /// Update playback state from an audio event.
@@ -1613,7 +1841,7 @@ impl ChatState {
         if notation.is_empty() {
             let _ = services.event_tx.send(AppEvent::Notification(Notification {
                 id: 0,
-                message: "Usage: /roll <dice> (e.g. /roll 2d6+3)".to_string(),
+                message: "Usage: /roll <dice> (e.g. /roll 2d6+3, /roll 4d6kh3, /roll 8d10>=7, /roll 4dF)".to_string(),
                 level: NotificationLevel::Warning,
                 ttl_ticks: 80,
             }));
@@ -1868,7 +2096,35 @@ impl ChatState {
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
-        if self.rag_chunks.is_empty() {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+
+        if let Some(ref query) = self.rag_query {
+            lines.push(Line::from(vec![
+                Span::styled("Query: ", Style::default().fg(theme::TEXT_DIM)),
+                Span::styled(query.query.clone(), Style::default().fg(theme::TEXT)),
+            ]));
+            let mut tags = Vec::new();
+            if query.rewritten {
+                tags.push("rewritten");
+            }
+            if query.keyword_only {
+                tags.push("keyword only");
+            }
+            if !tags.is_empty() {
+                lines.push(Line::styled(
+                    format!("  ({})", tags.join(", ")),
+                    Style::default().fg(theme::TEXT_MUTED),
+                ));
+            }
+            lines.push(Line::raw(""));
+
+            if self.rag_chunks.is_empty() {
+                lines.push(Line::styled(
+                    "  No matching chunks.",
+                    Style::default().fg(theme::TEXT_MUTED),
+                ));
+            }
+        } else if self.rag_chunks.is_empty() {
             let empty = Paragraph::new(vec![
                 Line::raw(""),
                 Line::styled(
@@ -1901,8 +2157,6 @@ impl ChatState {
             return;
         }
 
-        let mut lines: Vec<Line<'static>> = Vec::new();
-
         for (i, chunk) in self.rag_chunks.iter().enumerate() {
             // Header: [N] source (p.X) — score: 0.XX
             let page_str = chunk
@@ -1943,7 +2197,7 @@ impl ChatState {
     }
 
     fn render_input(&self, frame: &mut Frame, area: Rect) {
-        let mode_line = match self.input_mode {
+        let mut mode_line = match self.input_mode {
             ChatInputMode::Insert => {
                 if self.is_streaming() {
                     Line::from(vec![
@@ -1980,6 +2234,21 @@ impl ChatState {
             }
         };
 
+        if let Some(ref summary) = self.pending_confirmation {
+            mode_line = Line::from(vec![
+                Span::styled(
+                    " CONFIRM ",
+                    Style::default().fg(theme::BG_BASE).bg(theme::WARNING),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("Assistant wants to {summary}"),
+                    Style::default().fg(theme::TEXT),
+                ),
+                Span::styled("  [y/n]", Style::default().fg(theme::TEXT_MUTED)),
+            ]);
+        }
+
         let chunks = Layout::vertical([
             Constraint::Length(1), // Mode indicator
             Constraint::Min(1),   // Input box
@@ -2000,47 +2269,108 @@ impl ChatState {
     }
 }
 
+// ── Assistant approval (runs inside spawned task) ───────────────────────
+
+/// Routes the assistant's approval requests through the chat view's y/n prompt.
+struct ChannelApproval {
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+    answers: Mutex<mpsc::UnboundedReceiver<bool>>,
+}
+
+#[async_trait::async_trait]
+impl ToolApproval for ChannelApproval {
+    async fn approve(&self, _call: &ToolCall, summary: &str) -> bool {
+        let mut answers = self.answers.lock().await;
+        if self
+            .event_tx
+            .send(AppEvent::AssistantConfirm(summary.to_string()))
+            .is_err()
+        {
+            return false;
+        }
+        // A dropped sender (response cancelled) counts as a refusal
+        answers.recv().await.unwrap_or(false)
+    }
+}
+
 // ── RAG retrieval helper (runs inside spawned task) ──────────────────────
 
-/// Attempt to retrieve RAG context for the user's query.
+/// Attempt to retrieve RAG context for the search query.
 ///
-/// Returns the formatted RAG system prompt section on success, or `None`
-/// if embedding or search fails (graceful degradation — chat proceeds without RAG).
+/// Uses hybrid search when the query can be embedded and BM25 keyword search
+/// when it cannot (no provider configured, or the provider failed). Returns
+/// the formatted RAG system prompt section on success, or `None` if nothing
+/// was found or search fails (graceful degradation — chat proceeds without RAG).
 async fn try_rag_retrieval(
     storage: &crate::core::storage::surrealdb::SurrealStorage,
-    embedding_provider: &dyn crate::core::search::embeddings::EmbeddingProvider,
+    embedding_provider: Option<&dyn crate::core::search::embeddings::EmbeddingProvider>,
+    reranker: Option<&dyn crate::core::search::rerank::Reranker>,
     query: &str,
+    rewritten: bool,
     tx: &tokio::sync::mpsc::UnboundedSender<AppEvent>,
 ) -> Option<String> {
     use crate::core::storage::rag::{RagConfig, format_context, build_system_prompt as build_rag_prompt};
-    use crate::core::storage::search::hybrid_search;
+    use crate::core::storage::search::{hybrid_search_reranked, keyword_search_reranked};
 
-    // 1. Embed the user query
-    let embedding = match embedding_provider.embed(query).await {
-        Ok(emb) => emb,
-        Err(e) => {
-            log::debug!("RAG: embedding failed (proceeding without context): {e}");
-            return None;
-        }
+    // 1. Embed the query, if there is a provider to do it
+    let embedding = match embedding_provider {
+        Some(provider) => match provider.embed(query).await {
+            Ok(emb) => Some(emb),
+            Err(e) => {
+                log::debug!("RAG: embedding failed (using keyword search): {e}");
+                None
+            }
+        },
+        None => None,
     };
 
-    // 2. Hybrid search (BM25 + vector)
+    // 2. Hybrid search (BM25 + vector), or BM25 alone when there is no query
+    //    vector or the vector side fails; reranked when a reranker is configured
     let rag_config = RagConfig::default();
-    let results = match hybrid_search(
-        storage.db(),
-        query,
-        embedding,
-        &rag_config.search_config,
-        None,
-    ).await {
+    let mut keyword_only = embedding.is_none();
+    let hybrid = match (embedding, embedding_provider) {
+        (Some(embedding), Some(provider)) => Some(hybrid_search_reranked(
+            storage.db(),
+            query,
+            embedding,
+            &rag_config.search_config.clone().with_query_model(provider.model_id()),
+            None,
+            reranker,
+        ).await),
+        _ => None,
+    };
+    let searched = match hybrid {
+        Some(Ok(r)) => Ok(r),
+        other => {
+            if let Some(Err(e)) = other {
+                log::debug!("RAG: hybrid search failed (using keyword search): {e}");
+                keyword_only = true;
+            }
+            keyword_search_reranked(
+                storage.db(),
+                query,
+                &rag_config.search_config,
+                None,
+                reranker,
+            ).await
+        }
+    };
+    let results = match searched {
         Ok(r) => r,
         Err(e) => {
-            log::debug!("RAG: hybrid search failed (proceeding without context): {e}");
+            log::debug!("RAG: search failed (proceeding without context): {e}");
             return None;
         }
     };
 
+    let _ = tx.send(AppEvent::RagQuery(RagQueryDisplay {
+        query: query.to_string(),
+        rewritten,
+        keyword_only,
+    }));
+
     if results.is_empty() {
+        let _ = tx.send(AppEvent::RagChunksRetrieved(Vec::new()));
         return None;
     }
 
@@ -2208,6 +2538,72 @@ mod tests {
         assert!(state.rag_chunks.is_empty());
     }
 
+    #[test]
+    fn test_set_rag_query_cleared_with_chunks() {
+        let mut state = ChatState::new();
+        assert!(state.rag_query.is_none());
+
+        state.set_rag_query(RagQueryDisplay {
+            query: "damage fireball 5th level".into(),
+            rewritten: true,
+            keyword_only: true,
+        });
+        assert_eq!(
+            state.rag_query.as_ref().map(|q| q.query.as_str()),
+            Some("damage fireball 5th level")
+        );
+
+        state.clear_rag_chunks();
+        assert!(state.rag_query.is_none());
+    }
+
+    // ── Assistant tool call tests ───────────────────────────────────
+
+    #[test]
+    fn test_tool_trace_inserted_above_streaming_reply() {
+        let mut state = ChatState::new();
+        let (user, _) = DisplayMessage::from_user_input("s1", "Goblin takes 5");
+        let (placeholder, _) = DisplayMessage::new_streaming("s1");
+        state.messages.push(user);
+        state.messages.push(placeholder);
+
+        state.append_tool_trace(&ToolTrace {
+            tool: "damage_combatant".into(),
+            summary: "deal 5 damage to Goblin".into(),
+            outcome: ToolOutcome::Success("Goblin is at 2/7 HP".into()),
+        });
+
+        assert_eq!(state.messages.len(), 3);
+        assert_eq!(state.messages[1].role, MessageRole::System);
+        assert!(state.messages[1].raw_content.contains("2/7 HP"));
+        assert!(state.messages[2].is_streaming);
+    }
+
+    #[test]
+    fn test_confirmation_prompt_answers() {
+        let mut state = ChatState::new();
+
+        // Ignored when no assistant run is waiting
+        state.request_confirmation("add Ogre to combat".into());
+        assert!(!state.awaiting_confirmation());
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        state.approval_tx = Some(tx);
+        state.request_confirmation("add Ogre to combat".into());
+        assert!(state.awaiting_confirmation());
+
+        assert!(state.handle_confirmation_input(KeyCode::Char('x'), KeyModifiers::NONE));
+        assert!(state.awaiting_confirmation());
+
+        state.handle_confirmation_input(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert!(!state.awaiting_confirmation());
+        assert_eq!(rx.try_recv().ok(), Some(false));
+
+        state.request_confirmation("create note \"Rumors\"".into());
+        state.handle_confirmation_input(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(rx.try_recv().ok(), Some(true));
+    }
+
     // ── Inline dice detection tests ─────────────────────────────────
 
     #[test]
@@ -2277,4 +2673,19 @@ mod tests {
         let result = roller.roll(&notation);
         assert!(result.total >= 5 && result.total <= 15);
     }
+
+    #[test]
+    fn test_inline_dice_expression_language() {
+        let text = "Stats [[4d6kh3]], pool [[8d10>=7]], fate [[4dF+1]], smite [[1d8+2d6+5]]";
+        let inline = detect_inline_dice(text);
+        assert_eq!(inline.len(), 4);
+
+        let roller = DiceRoller::new();
+        for (_, notation_str) in &inline {
+            let notation = DiceNotation::parse(notation_str).unwrap();
+            let result = roller.roll(&notation);
+            assert!(result.total >= notation.min_result());
+            assert!(result.total <= notation.max_result());
+        }
+    }
 }
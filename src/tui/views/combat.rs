@@ -2,6 +2,7 @@
 //!
 //! Phases: NoCombat → InitiativeEntry → Active → Ended.
 //! Uses backend `CombatState`, `Combatant`, `ConditionTemplates`.
+//! Active-phase actions go through `CombatCommand` so they can be undone.
 
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
@@ -13,8 +14,12 @@ use ratatui::{
 };
 
 use crate::core::campaign::dice::{DiceNotation, DiceRoller};
-use crate::core::session::combat::{Combatant, CombatantType, CombatState};
+use crate::core::session::combat::{
+    Combatant, CombatantType, CombatState, CombatStatus, HitPointMode,
+};
 use crate::core::session::conditions::ConditionTemplates;
+use crate::core::session::history::CombatCommand;
+use crate::ingestion::ttrpg::StatBlockData;
 use crate::tui::theme;
 use crate::tui::widgets::input_buffer::InputBuffer;
 
@@ -58,12 +63,13 @@ impl EntryField {
     }
 }
 
-/// Active-phase sub-mode for numeric inputs (damage/heal).
+/// Active-phase sub-mode for numeric inputs (damage/heal/initiative).
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum ActiveInput {
     None,
     Damage,
     Heal,
+    Initiative,
     Condition,
 }
 
@@ -90,6 +96,10 @@ pub struct CombatViewState {
     active_input: ActiveInput,
     input_buf: InputBuffer,
     condition_cursor: usize,
+
+    /// Bumped whenever the encounter changes, so callers can tell when it
+    /// needs saving without comparing whole states.
+    revision: u64,
 }
 
 impl CombatViewState {
@@ -109,6 +119,7 @@ impl CombatViewState {
             active_input: ActiveInput::None,
             input_buf: InputBuffer::new(),
             condition_cursor: 0,
+            revision: 0,
         }
     }
 
@@ -134,7 +145,7 @@ impl CombatViewState {
         {
             match code {
                 KeyCode::Enter | KeyCode::Char('n') => {
-                    self.combat = CombatState::new();
+                    self.mutate(|combat| *combat = CombatState::new());
                     self.phase = CombatPhase::InitiativeEntry;
                     self.reset_entry_form();
                     true
@@ -159,7 +170,7 @@ impl CombatViewState {
                 || *code == KeyCode::F(5)
             {
                 if self.combat.combatants.len() >= 2 {
-                    self.combat.sort_initiative();
+                    self.mutate(|combat| combat.sort_initiative());
                     self.phase = CombatPhase::Active;
                     self.selected_idx = 0;
                 } else {
@@ -174,7 +185,7 @@ impl CombatViewState {
                         self.phase = CombatPhase::NoCombat;
                     } else {
                         // Remove last added combatant
-                        self.combat.combatants.pop();
+                        self.mutate(|combat| combat.combatants.pop());
                     }
                     true
                 }
@@ -266,10 +277,38 @@ impl CombatViewState {
                 }
                 KeyCode::Char(' ') => {
                     // Next turn
-                    self.combat.next_turn();
+                    self.mutate(|combat| combat.execute(CombatCommand::NextTurn));
                     self.selected_idx = self.combat.current_turn;
                     true
                 }
+                KeyCode::Char('u') => {
+                    self.mutate(|combat| combat.undo());
+                    self.clamp_selection();
+                    true
+                }
+                KeyCode::Char('r') if *modifiers == KeyModifiers::CONTROL => {
+                    self.mutate(|combat| combat.redo());
+                    self.clamp_selection();
+                    true
+                }
+                KeyCode::Char('i') => {
+                    self.active_input = ActiveInput::Initiative;
+                    self.input_buf.clear();
+                    true
+                }
+                KeyCode::Char('x') => {
+                    // Remove the most recent condition from the selected combatant
+                    if let Some(c) = self.combat.combatants.get(self.selected_idx) {
+                        if let Some(cond) = c.condition_tracker.conditions().last() {
+                            let command = CombatCommand::RemoveCondition {
+                                combatant_id: c.id.clone(),
+                                condition_id: cond.id.clone(),
+                            };
+                            self.mutate(|combat| combat.execute(command));
+                        }
+                    }
+                    true
+                }
                 KeyCode::Char('D') => {
                     self.active_input = ActiveInput::Damage;
                     self.input_buf.clear();
@@ -288,13 +327,11 @@ impl CombatViewState {
                 KeyCode::Char('d') => {
                     // Remove selected combatant
                     if let Some(c) = self.combat.combatants.get(self.selected_idx) {
-                        let id = c.id.clone();
-                        self.combat.remove_combatant(&id);
-                        if self.selected_idx >= self.combat.combatants.len()
-                            && !self.combat.combatants.is_empty()
-                        {
-                            self.selected_idx = self.combat.combatants.len() - 1;
-                        }
+                        let combatant_id = c.id.clone();
+                        self.mutate(|combat| {
+                            combat.execute(CombatCommand::RemoveCombatant { combatant_id })
+                        });
+                        self.clamp_selection();
                     }
                     true
                 }
@@ -306,7 +343,7 @@ impl CombatViewState {
                 }
                 KeyCode::Char('e') => {
                     // End combat
-                    self.combat.end();
+                    self.mutate(|combat| combat.end());
                     self.phase = CombatPhase::Ended;
                     true
                 }
@@ -352,10 +389,14 @@ impl CombatViewState {
                     let names = ConditionTemplates::list_names();
                     if let Some(name) = names.get(self.condition_cursor) {
                         if let Some(condition) = ConditionTemplates::by_name(name) {
-                            if let Some(combatant) =
-                                self.combat.combatants.get_mut(self.selected_idx)
-                            {
-                                let _ = combatant.condition_tracker.add_condition(condition);
+                            if let Some(combatant) = self.combat.combatants.get(self.selected_idx) {
+                                let combatant_id = combatant.id.clone();
+                                self.mutate(|combat| {
+                                    combat.execute(CombatCommand::AddCondition {
+                                        combatant_id,
+                                        condition,
+                                    })
+                                });
                             }
                         }
                     }
@@ -365,7 +406,7 @@ impl CombatViewState {
                 _ => true,
             },
             _ => {
-                // Damage / Heal numeric input
+                // Damage / Heal / Initiative numeric input
                 match code {
                     KeyCode::Esc => {
                         self.active_input = ActiveInput::None;
@@ -374,17 +415,25 @@ impl CombatViewState {
                     KeyCode::Enter => {
                         let text = self.input_buf.text().trim().to_string();
                         if let Ok(amount) = text.parse::<i32>() {
-                            if let Some(combatant) =
-                                self.combat.combatants.get_mut(self.selected_idx)
-                            {
-                                match self.active_input {
-                                    ActiveInput::Damage => {
-                                        combatant.apply_damage(amount);
-                                    }
-                                    ActiveInput::Heal => {
-                                        combatant.heal(amount);
-                                    }
-                                    _ => {}
+                            if let Some(combatant) = self.combat.combatants.get(self.selected_idx) {
+                                let combatant_id = combatant.id.clone();
+                                let command = match self.active_input {
+                                    ActiveInput::Damage => Some(CombatCommand::Damage {
+                                        combatant_id,
+                                        amount,
+                                    }),
+                                    ActiveInput::Heal => Some(CombatCommand::Heal {
+                                        combatant_id,
+                                        amount,
+                                    }),
+                                    ActiveInput::Initiative => Some(CombatCommand::SetInitiative {
+                                        combatant_id,
+                                        initiative: amount,
+                                    }),
+                                    _ => None,
+                                };
+                                if let Some(command) = command {
+                                    self.mutate(|combat| combat.execute(command));
                                 }
                             }
                         }
@@ -486,10 +535,24 @@ impl CombatViewState {
         let mut combatant = Combatant::new(name, init, self.entry_type.clone());
         combatant.current_hp = hp;
         combatant.max_hp = hp;
-        self.combat.add_combatant(combatant);
+        self.mutate(|combat| combat.add_combatant(combatant));
         self.reset_entry_form();
     }
 
+    /// Apply a change to the encounter. Every write to `combat` goes through
+    /// here so `revision` can't miss one.
+    fn mutate<R>(&mut self, change: impl FnOnce(&mut CombatState) -> R) -> R {
+        self.revision += 1;
+        change(&mut self.combat)
+    }
+
+    /// Keep the selection inside the roster after undo/redo or removal.
+    fn clamp_selection(&mut self) {
+        self.selected_idx = self
+            .selected_idx
+            .min(self.combat.combatants.len().saturating_sub(1));
+    }
+
     fn reset_entry_form(&mut self) {
         self.entry_name.clear();
         self.entry_init.clear();
@@ -499,6 +562,56 @@ impl CombatViewState {
         self.entry_error = None;
     }
 
+    /// Add `count` copies of a stat block's creature to the encounter.
+    ///
+    /// Starts a new encounter in the initiative-entry phase when none is
+    /// running. Returns the names given to the new combatants.
+    pub fn add_from_stat_block(
+        &mut self,
+        block: &StatBlockData,
+        count: usize,
+        hp_mode: HitPointMode,
+    ) -> Vec<String> {
+        if matches!(self.phase, CombatPhase::NoCombat | CombatPhase::Ended) {
+            self.mutate(|combat| *combat = CombatState::new());
+            self.phase = CombatPhase::InitiativeEntry;
+            self.selected_idx = 0;
+            self.reset_entry_form();
+        }
+
+        self.mutate(|combat| {
+            combat.add_from_stat_block(block, count, hp_mode, &mut rand::thread_rng())
+        })
+        .into_iter()
+        .map(|c| c.name)
+        .collect()
+    }
+
+    /// The encounter being tracked, if any.
+    pub fn combat_state(&self) -> Option<&CombatState> {
+        (self.phase != CombatPhase::NoCombat).then_some(&self.combat)
+    }
+
+    /// Counter that changes whenever the encounter does.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Pick up an encounter restored from the session journal.
+    pub fn resume(&mut self, combat: CombatState) {
+        self.phase = if combat.status == CombatStatus::Ended {
+            CombatPhase::Ended
+        } else if combat.combatants.len() >= 2 {
+            CombatPhase::Active
+        } else {
+            CombatPhase::InitiativeEntry
+        };
+        self.mutate(|current| *current = combat);
+        self.selected_idx = 0;
+        self.active_input = ActiveInput::None;
+        self.reset_entry_form();
+    }
+
     // ────────────────────────────────────────────────────────────────────
     // Rendering
     // ────────────────────────────────────────────────────────────────────
@@ -728,25 +841,30 @@ impl CombatViewState {
 
         self.render_initiative_list(frame, h_chunks[0]);
 
-        // Right side: detail (top) + log (bottom)
+        // Right side: detail (top) + undo history + log (bottom)
         let v_chunks = Layout::vertical([
             Constraint::Length(2), // Round/turn header
             Constraint::Min(5),   // Detail
+            Constraint::Length(self.history_height()), // Undo/redo stack
             Constraint::Length(self.log_height(h_chunks[1].height)), // Log
         ])
         .split(h_chunks[1]);
 
         self.render_round_header(frame, v_chunks[0]);
         self.render_combatant_detail(frame, v_chunks[1]);
-        self.render_combat_log(frame, v_chunks[2]);
+        self.render_history(frame, v_chunks[2]);
+        self.render_combat_log(frame, v_chunks[3]);
 
         // Overlay: condition picker
         if self.active_input == ActiveInput::Condition {
             self.render_condition_picker(frame, inner);
         }
 
-        // Overlay: damage/heal input
-        if self.active_input == ActiveInput::Damage || self.active_input == ActiveInput::Heal {
+        // Overlay: damage/heal/initiative input
+        if matches!(
+            self.active_input,
+            ActiveInput::Damage | ActiveInput::Heal | ActiveInput::Initiative
+        ) {
             self.render_numeric_input(frame, inner);
         }
     }
@@ -789,6 +907,10 @@ impl CombatViewState {
             Span::styled(":heal ", Style::default().fg(theme::TEXT_DIM)),
             Span::styled("c", theme::key_hint()),
             Span::styled(":cond ", Style::default().fg(theme::TEXT_DIM)),
+            Span::styled("u", theme::key_hint()),
+            Span::styled(":undo ", Style::default().fg(theme::TEXT_DIM)),
+            Span::styled("^R", theme::key_hint()),
+            Span::styled(":redo ", Style::default().fg(theme::TEXT_DIM)),
             Span::styled("e", theme::key_hint()),
             Span::styled(":end", Style::default().fg(theme::TEXT_DIM)),
         ]);
@@ -950,6 +1072,17 @@ impl CombatViewState {
             ]));
         }
 
+        // Condition immunities
+        if !c.condition_immunities.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled(" Immune: ", Style::default().fg(theme::TEXT_MUTED)),
+                Span::styled(
+                    c.condition_immunities.join(", "),
+                    Style::default().fg(theme::TEXT),
+                ),
+            ]));
+        }
+
         // Init
         lines.push(Line::from(vec![
             Span::styled(" Init: ", Style::default().fg(theme::TEXT_MUTED)),
@@ -1057,6 +1190,61 @@ impl CombatViewState {
         }
     }
 
+    /// Rows for the undo/redo panel: up to 3 undo entries plus the next redo.
+    fn history_height(&self) -> u16 {
+        let history = self.combat.history();
+        let rows = history.undo_labels().take(3).count() + history.redo_labels().take(1).count();
+        rows.max(1) as u16 + 2
+    }
+
+    fn render_history(&self, frame: &mut Frame, area: Rect) {
+        let history = self.combat.history();
+        let undo_count = history.undo_labels().count();
+        let block = Block::default()
+            .title(format!(" History ({undo_count}) "))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::TEXT_DIM));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if !history.can_undo() && !history.can_redo() {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    " Nothing to undo",
+                    Style::default().fg(theme::TEXT_DIM),
+                )),
+                inner,
+            );
+            return;
+        }
+
+        // Next redo (dimmed) above the undo stack, newest first
+        let mut lines: Vec<Line> = history
+            .redo_labels()
+            .take(1)
+            .map(|label| {
+                Line::from(vec![
+                    Span::styled(" ↷ ", Style::default().fg(theme::TEXT_DIM)),
+                    Span::styled(label.to_string(), Style::default().fg(theme::TEXT_DIM)),
+                ])
+            })
+            .collect();
+        lines.extend(history.undo_labels().take(3).enumerate().map(|(i, label)| {
+            let (marker, style) = if i == 0 {
+                (" ▸ ", Style::default().fg(theme::TEXT))
+            } else {
+                ("   ", Style::default().fg(theme::TEXT_MUTED))
+            };
+            Line::from(vec![
+                Span::styled(marker, Style::default().fg(theme::ACCENT)),
+                Span::styled(label.to_string(), style),
+            ])
+        }));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
     fn render_condition_picker(&self, frame: &mut Frame, area: Rect) {
         let names = ConditionTemplates::list_names();
         let height = (names.len() as u16 + 3).min(area.height.saturating_sub(4));
@@ -1109,11 +1297,13 @@ impl CombatViewState {
         let label = match self.active_input {
             ActiveInput::Damage => "Damage Amount",
             ActiveInput::Heal => "Heal Amount",
+            ActiveInput::Initiative => "New Initiative",
             _ => return,
         };
         let color = match self.active_input {
             ActiveInput::Damage => theme::ERROR,
             ActiveInput::Heal => theme::SUCCESS,
+            ActiveInput::Initiative => theme::ACCENT,
             _ => theme::TEXT,
         };
 
@@ -1316,7 +1506,6 @@ fn truncate_name(name: &str, max: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::session::combat::CombatStatus;
 
     #[test]
     fn test_initial_phase() {
@@ -1325,6 +1514,52 @@ mod tests {
         assert!(state.combat.combatants.is_empty());
     }
 
+    #[test]
+    fn test_resume_restored_combat() {
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant::new("Fighter", 15, CombatantType::Player));
+        combat.add_combatant(Combatant::new("Goblin", 12, CombatantType::Monster));
+
+        let mut state = CombatViewState::new();
+        assert!(state.combat_state().is_none());
+        state.resume(combat);
+        assert_eq!(state.phase, CombatPhase::Active);
+        assert_eq!(state.combat_state().unwrap().combatants.len(), 2);
+    }
+
+    #[test]
+    fn test_undo_redo_keys() {
+        let mut goblin = Combatant::new("Goblin", 12, CombatantType::Monster);
+        goblin.current_hp = Some(10);
+        goblin.max_hp = Some(10);
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant::new("Fighter", 15, CombatantType::Player));
+        combat.add_combatant(goblin);
+
+        let mut state = CombatViewState::new();
+        state.resume(combat);
+        state.selected_idx = 1;
+
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        state.handle_input(&Event::Key(KeyEvent::new(
+            KeyCode::Char('D'),
+            KeyModifiers::SHIFT,
+        )));
+        state.handle_input(&key(KeyCode::Char('4')));
+        state.handle_input(&key(KeyCode::Enter));
+        assert_eq!(state.combat.combatants[1].current_hp, Some(6));
+        assert!(state.combat.history().can_undo());
+
+        state.handle_input(&key(KeyCode::Char('u')));
+        assert_eq!(state.combat.combatants[1].current_hp, Some(10));
+
+        state.handle_input(&Event::Key(KeyEvent::new(
+            KeyCode::Char('r'),
+            KeyModifiers::CONTROL,
+        )));
+        assert_eq!(state.combat.combatants[1].current_hp, Some(6));
+    }
+
     #[test]
     fn test_start_combat_transition() {
         let mut state = CombatViewState::new();
@@ -1417,6 +1652,28 @@ mod tests {
         assert_eq!(state.combat.current_turn, 1);
     }
 
+    #[test]
+    fn test_revision_tracks_encounter_changes() {
+        let mut state = setup_active_combat();
+        let key = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        let start = state.revision();
+
+        // Moving the selection or opening an input leaves the encounter alone
+        state.handle_input(&key('j'));
+        state.handle_input(&key('k'));
+        state.handle_input(&key('c'));
+        state.handle_input(&Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)));
+        assert_eq!(state.revision(), start);
+
+        state.handle_input(&key(' '));
+        assert_ne!(state.revision(), start);
+
+        // Undo changes the encounter too
+        let before_undo = state.revision();
+        state.handle_input(&key('u'));
+        assert_ne!(state.revision(), before_undo);
+    }
+
     #[test]
     fn test_end_combat() {
         let mut state = setup_active_combat();
@@ -1478,6 +1735,45 @@ mod tests {
         assert_eq!(truncate_name("AB", 2), "AB");
     }
 
+    #[test]
+    fn test_add_from_stat_block_starts_encounter() {
+        let block = goblin_block();
+        let mut state = CombatViewState::new();
+        let names = state.add_from_stat_block(&block, 3, HitPointMode::Average);
+        assert_eq!(names, vec!["Goblin 1", "Goblin 2", "Goblin 3"]);
+        assert_eq!(state.phase, CombatPhase::InitiativeEntry);
+        assert_eq!(state.combat.combatants.len(), 3);
+        assert!(state
+            .combat
+            .combatants
+            .iter()
+            .all(|c| c.armor_class == Some(15) && c.max_hp == Some(7)));
+    }
+
+    #[test]
+    fn test_add_from_stat_block_joins_active_combat() {
+        let mut state = setup_active_combat();
+        let block = goblin_block();
+
+        let names = state.add_from_stat_block(&block, 1, HitPointMode::Average);
+        assert_eq!(names, vec!["Goblin 2"]);
+        assert_eq!(state.phase, CombatPhase::Active);
+        assert_eq!(state.combat.combatants.len(), 4);
+    }
+
+    fn goblin_block() -> StatBlockData {
+        crate::ingestion::ttrpg::StatBlockParser::new()
+            .parse(
+                "Goblin\n\
+                 Small humanoid (goblinoid), neutral evil\n\
+                 Armor Class 15 (leather armor, shield)\n\
+                 Hit Points 7 (2d6)\n\
+                 STR 8 (-1) DEX 14 (+2) CON 10 (+0) INT 10 (+0) WIS 8 (-1) CHA 8 (-1)\n\
+                 Challenge 1/4 (50 XP)\n",
+            )
+            .unwrap()
+    }
+
     /// Helper to set up an active combat with 3 combatants.
     fn setup_active_combat() -> CombatViewState {
         let mut state = CombatViewState::new();
@@ -146,6 +146,13 @@ pub fn build_command_registry() -> Vec<Command> {
             keybinding: None,
             action: Action::FocusNotes,
         },
+        Command {
+            label: "Go to Calendar",
+            description: "Switch to the in-game Calendar",
+            category: CommandCategory::Navigation,
+            keybinding: None,
+            action: Action::FocusCalendar,
+        },
         Command {
             label: "Go to NPCs",
             description: "Switch to NPC Management",
@@ -160,6 +167,13 @@ pub fn build_command_registry() -> Vec<Command> {
             keybinding: None,
             action: Action::FocusLocations,
         },
+        Command {
+            label: "Go to Relationships",
+            description: "Switch to the entity relationship graph",
+            category: CommandCategory::Navigation,
+            keybinding: None,
+            action: Action::FocusRelationships,
+        },
         Command {
             label: "Go to Archetypes",
             description: "Switch to Archetype Browser",
@@ -167,6 +181,13 @@ pub fn build_command_registry() -> Vec<Command> {
             keybinding: None,
             action: Action::FocusArchetypes,
         },
+        Command {
+            label: "Go to Bestiary",
+            description: "Browse stat blocks from ingested books",
+            category: CommandCategory::Navigation,
+            keybinding: None,
+            action: Action::FocusBestiary,
+        },
         Command {
             label: "Go to Voice",
             description: "Switch to Voice Manager",
@@ -262,6 +283,13 @@ pub fn build_command_registry() -> Vec<Command> {
             keybinding: Some("r"),
             action: Action::RefreshSettings,
         },
+        Command {
+            label: "Create Backup",
+            description: "Archive all application data to the backup folder",
+            category: CommandCategory::System,
+            keybinding: None,
+            action: Action::CreateBackup,
+        },
         Command {
             label: "Show Help",
             description: "Open the keybindings help modal",
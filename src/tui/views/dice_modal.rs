@@ -2,6 +2,10 @@
 //!
 //! Global overlay activated by `Ctrl+D` or `Action::OpenDiceRoller`.
 //! Uses the backend `DiceNotation` / `DiceRoller` / `RollResult` types.
+//! Input starting with `@` rolls on a random table imported from the library
+//! (e.g. `@forest enc`), resolved through the `RandomTableEngine`.
+
+use std::sync::Arc;
 
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
@@ -11,8 +15,13 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
+use tokio::sync::mpsc;
 
-use crate::core::campaign::dice::{DiceNotation, DiceRoller, RollResult};
+use crate::core::campaign::dice::{DiceNotation, DiceRoller, RollResult, SingleRoll};
+use crate::core::campaign::random_table::{
+    match_tables_by_name, RandomTable, RandomTableEngine, TableRollResult,
+};
+use crate::tui::services::Services;
 use crate::tui::theme;
 use crate::tui::widgets::input_buffer::InputBuffer;
 
@@ -29,24 +38,90 @@ const QUICK_ROLLS: &[(char, &str)] = &[
     ('d', "d20"),
 ];
 
+/// Prefix that switches the input from dice notation to a table lookup.
+const TABLE_PREFIX: char = '@';
+
+/// A single roll in the history list.
+struct HistoryEntry {
+    /// Notation or table name shown as the label.
+    label: String,
+    result: RollResult,
+    /// Table result text, for rolls on a random table.
+    outcome: Option<String>,
+}
+
+enum DiceDataEvent {
+    TablesLoaded(Vec<RandomTable>),
+    TableRolled(TableRollResult),
+    Error(String),
+}
+
 /// State for the dice roller modal.
 pub struct DiceRollerState {
     input: InputBuffer,
-    history: Vec<(String, RollResult)>,
+    history: Vec<HistoryEntry>,
     error: Option<String>,
     roller: DiceRoller,
     /// Scroll offset for history (0 = most recent at bottom).
     history_scroll: usize,
+    /// Imported random tables available for `@` rolls.
+    tables: Vec<RandomTable>,
+    engine: Option<Arc<RandomTableEngine>>,
+    data_tx: mpsc::UnboundedSender<DiceDataEvent>,
+    data_rx: mpsc::UnboundedReceiver<DiceDataEvent>,
 }
 
 impl DiceRollerState {
     pub fn new() -> Self {
+        let (data_tx, data_rx) = mpsc::unbounded_channel();
         Self {
             input: InputBuffer::new(),
             history: Vec::new(),
             error: None,
             roller: DiceRoller::new(),
             history_scroll: 0,
+            tables: Vec::new(),
+            engine: None,
+            data_tx,
+            data_rx,
+        }
+    }
+
+    /// Load the random tables imported from the library.
+    pub fn load_tables(&mut self, services: &Services) {
+        let engine = services.random_tables.clone();
+        self.engine = Some(engine.clone());
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            match engine.list_imported_tables().await {
+                Ok(tables) => {
+                    let _ = tx.send(DiceDataEvent::TablesLoaded(tables));
+                }
+                Err(e) => {
+                    let _ = tx.send(DiceDataEvent::Error(format!("Failed to load tables: {e}")));
+                }
+            }
+        });
+    }
+
+    pub fn poll(&mut self) {
+        while let Ok(event) = self.data_rx.try_recv() {
+            match event {
+                DiceDataEvent::TablesLoaded(tables) => {
+                    self.tables = tables;
+                }
+                DiceDataEvent::TableRolled(rolled) => {
+                    self.push_history(HistoryEntry {
+                        label: rolled.table_name,
+                        result: rolled.roll,
+                        outcome: Some(rolled.final_text),
+                    });
+                    self.error = None;
+                }
+                DiceDataEvent::Error(msg) => {
+                    self.error = Some(msg);
+                }
+            }
         }
     }
 
@@ -116,7 +191,11 @@ impl DiceRollerState {
         if text.is_empty() {
             return;
         }
-        self.roll_notation(&text);
+        if let Some(query) = text.strip_prefix(TABLE_PREFIX) {
+            self.roll_table(query.trim());
+        } else {
+            self.roll_notation(&text);
+        }
         self.input.clear();
     }
 
@@ -124,11 +203,11 @@ impl DiceRollerState {
         match DiceNotation::parse(notation) {
             Ok(parsed) => {
                 let result = self.roller.roll(&parsed);
-                self.history.push((notation.to_string(), result));
-                if self.history.len() > MAX_HISTORY {
-                    self.history.remove(0);
-                }
-                self.history_scroll = 0;
+                self.push_history(HistoryEntry {
+                    label: notation.to_string(),
+                    result,
+                    outcome: None,
+                });
                 self.error = None;
             }
             Err(e) => {
@@ -137,6 +216,43 @@ impl DiceRollerState {
         }
     }
 
+    /// Roll on the best-matching imported table. The result arrives via `poll`.
+    fn roll_table(&mut self, query: &str) {
+        let Some(table_id) = self.matching_tables(query).first().map(|t| t.id.clone()) else {
+            self.error = Some(format!("No table matching '{query}'"));
+            return;
+        };
+        let Some(engine) = self.engine.clone() else {
+            self.error = Some("Random tables are not loaded".into());
+            return;
+        };
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            match engine.quick_roll(&table_id).await {
+                Ok(rolled) => {
+                    let _ = tx.send(DiceDataEvent::TableRolled(rolled));
+                }
+                Err(e) => {
+                    let _ = tx.send(DiceDataEvent::Error(format!("{e}")));
+                }
+            }
+        });
+    }
+
+    /// Tables whose name contains `query` (case-insensitive), exact and
+    /// prefix matches first.
+    fn matching_tables(&self, query: &str) -> Vec<&RandomTable> {
+        match_tables_by_name(&self.tables, query)
+    }
+
+    fn push_history(&mut self, entry: HistoryEntry) {
+        self.history.push(entry);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history_scroll = 0;
+    }
+
     /// Render the dice roller as a centered modal overlay.
     pub fn render(&self, frame: &mut Frame, area: Rect) {
         let modal = centered_modal(55, 50, area);
@@ -184,7 +300,7 @@ impl DiceRollerState {
         // Show placeholder if empty
         let line = if text.is_empty() {
             Line::from(Span::styled(
-                "Type notation (e.g. 2d6+3) or press a quick key...",
+                "Type notation (e.g. 2d6+3, 4d6kh3), @table, or press a quick key...",
                 Style::default().fg(theme::TEXT_DIM),
             ))
         } else {
@@ -210,6 +326,40 @@ impl DiceRollerState {
                 Style::default().fg(theme::ERROR),
             ));
             frame.render_widget(Paragraph::new(line), area);
+        } else if let Some(query) = self.input.text().strip_prefix(TABLE_PREFIX) {
+            // Preview which table Enter will roll on
+            let matches = self.matching_tables(query.trim());
+            let line = match matches.first() {
+                Some(table) => {
+                    let mut spans = vec![
+                        Span::styled(" → ", Style::default().fg(theme::TEXT_DIM)),
+                        Span::styled(table.name.clone(), Style::default().fg(theme::ACCENT)),
+                        Span::styled(
+                            format!(" ({})", table.dice_notation),
+                            Style::default().fg(theme::TEXT_MUTED),
+                        ),
+                    ];
+                    if let Some(source) = table.source() {
+                        let page = table.page_number().map(|p| format!(" p. {p}")).unwrap_or_default();
+                        spans.push(Span::styled(
+                            format!(" · {source}{page}"),
+                            Style::default().fg(theme::TEXT_DIM),
+                        ));
+                    }
+                    if matches.len() > 1 {
+                        spans.push(Span::styled(
+                            format!(" +{} more", matches.len() - 1),
+                            Style::default().fg(theme::TEXT_DIM),
+                        ));
+                    }
+                    Line::from(spans)
+                }
+                None => Line::from(Span::styled(
+                    format!(" No table matches ({} imported)", self.tables.len()),
+                    Style::default().fg(theme::TEXT_DIM),
+                )),
+            };
+            frame.render_widget(Paragraph::new(line), area);
         }
     }
 
@@ -234,7 +384,8 @@ impl DiceRollerState {
 
         // Build lines from history (newest at bottom)
         let mut lines: Vec<Line<'static>> = Vec::with_capacity(total * 2);
-        for (i, (notation, result)) in self.history.iter().enumerate() {
+        for (i, entry) in self.history.iter().enumerate() {
+            let (notation, result) = (&entry.label, &entry.result);
             let is_latest = i == total - 1;
 
             // Roll label
@@ -246,24 +397,31 @@ impl DiceRollerState {
                 Style::default().fg(theme::TEXT_MUTED)
             };
 
-            // Build result line
-            let rolls_str: String = result
-                .rolls
-                .iter()
-                .map(|r| r.value.to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-
-            let mut spans: Vec<Span<'static>> = vec![
-                Span::styled(format!(" {notation}: "), label_style),
-                Span::styled(format!("[{rolls_str}]"), Style::default().fg(theme::TEXT)),
-            ];
+            let mut spans: Vec<Span<'static>> =
+                vec![Span::styled(format!(" {notation}: "), label_style)];
 
-            if result.notation.modifier != 0 {
+            // One bracketed group per dice term, each die styled by outcome
+            for (t, term) in result.terms.iter().enumerate() {
+                if t > 0 {
+                    spans.push(Span::styled(" ", Style::default().fg(theme::TEXT_DIM)));
+                }
+                spans.push(Span::styled("[", Style::default().fg(theme::TEXT)));
+                for (d, die) in term.rolls.iter().enumerate() {
+                    if d > 0 {
+                        spans.push(Span::styled(", ", Style::default().fg(theme::TEXT)));
+                    }
+                    spans.push(Span::styled(die.to_string(), die_style(die)));
+                }
+                spans.push(Span::styled("]", Style::default().fg(theme::TEXT)));
+            }
+
+            if result.notation.modifier != 0 || result.terms.len() > 1 {
                 spans.push(Span::styled(
                     format!(" ({}) ", result.subtotal),
                     Style::default().fg(theme::TEXT_DIM),
                 ));
+            } else {
+                spans.push(Span::raw(" "));
             }
 
             // Total
@@ -283,7 +441,12 @@ impl DiceRollerState {
                 Style::default().fg(theme::TEXT)
             };
 
-            spans.push(Span::styled(format!("= {}", result.total), total_style));
+            let total_text = match result.successes {
+                Some(_) if result.total == 1 => "= 1 success".to_string(),
+                Some(_) => format!("= {} successes", result.total),
+                None => format!("= {}", result.total),
+            };
+            spans.push(Span::styled(total_text, total_style));
 
             // Critical/fumble indicator
             if result.is_critical() {
@@ -302,6 +465,14 @@ impl DiceRollerState {
                 ));
             }
 
+            if let Some(ref outcome) = entry.outcome {
+                spans.push(Span::styled(" → ", Style::default().fg(theme::TEXT_DIM)));
+                spans.push(Span::styled(
+                    outcome.clone(),
+                    Style::default().fg(theme::TEXT).add_modifier(Modifier::BOLD),
+                ));
+            }
+
             lines.push(Line::from(spans));
         }
 
@@ -363,6 +534,8 @@ impl DiceRollerState {
             Span::styled(":close ", Style::default().fg(theme::TEXT_DIM)),
             Span::styled("Enter", theme::key_hint()),
             Span::styled(":roll ", Style::default().fg(theme::TEXT_DIM)),
+            Span::styled("@name", theme::key_hint()),
+            Span::styled(":table ", Style::default().fg(theme::TEXT_DIM)),
             Span::styled("↑/↓", theme::key_hint()),
             Span::styled(":scroll", Style::default().fg(theme::TEXT_DIM)),
         ]);
@@ -370,6 +543,23 @@ impl DiceRollerState {
     }
 }
 
+/// Style for a single die in the history breakdown.
+fn die_style(die: &SingleRoll) -> Style {
+    if !die.kept {
+        Style::default()
+            .fg(theme::TEXT_DIM)
+            .add_modifier(Modifier::CROSSED_OUT)
+    } else if die.success {
+        Style::default().fg(theme::SUCCESS)
+    } else if die.failure {
+        Style::default().fg(theme::ERROR)
+    } else if die.exploded {
+        Style::default().fg(theme::ACCENT)
+    } else {
+        Style::default().fg(theme::TEXT)
+    }
+}
+
 /// Center a modal of given percentage within the area.
 fn centered_modal(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let v = Layout::vertical([
@@ -406,9 +596,24 @@ mod tests {
         state.roll_notation("2d6+3");
         assert_eq!(state.history.len(), 1);
         assert!(state.error.is_none());
-        let (notation, result) = &state.history[0];
-        assert_eq!(notation, "2d6+3");
-        assert!(result.total >= 5 && result.total <= 15);
+        let entry = &state.history[0];
+        assert_eq!(entry.label, "2d6+3");
+        assert!(entry.result.total >= 5 && entry.result.total <= 15);
+        assert!(entry.outcome.is_none());
+    }
+
+    #[test]
+    fn test_roll_expression_notation() {
+        let mut state = DiceRollerState::new();
+        state.roll_notation("4d6kh3");
+        state.roll_notation("8d10>=7");
+        assert_eq!(state.history.len(), 2);
+        assert!(state.error.is_none());
+
+        let stats = &state.history[0].result;
+        assert_eq!(stats.rolls.iter().filter(|r| !r.kept).count(), 1);
+        let pool = &state.history[1].result;
+        assert!(pool.successes.is_some());
     }
 
     #[test]
@@ -469,9 +674,9 @@ mod tests {
         let consumed = state.handle_input(&event);
         assert!(consumed);
         assert_eq!(state.history.len(), 1);
-        let (notation, result) = &state.history[0];
-        assert_eq!(notation, "d20");
-        assert!(result.total >= 1 && result.total <= 20);
+        let entry = &state.history[0];
+        assert_eq!(entry.label, "d20");
+        assert!(entry.result.total >= 1 && entry.result.total <= 20);
     }
 
     #[test]
@@ -505,6 +710,77 @@ mod tests {
         assert_eq!(state.history_scroll, 1);
     }
 
+    fn table(name: &str) -> RandomTable {
+        RandomTable {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            description: None,
+            dice_notation: "d6".to_string(),
+            table_type: Default::default(),
+            category: None,
+            tags: vec!["source:DMG".to_string(), "page:87".to_string()],
+            campaign_id: None,
+            entries: Vec::new(),
+            is_system: false,
+            is_nested: false,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_matching_tables_ranks_prefix_first() {
+        let mut state = DiceRollerState::new();
+        state.tables = vec![table("Urban Forest Encounters"), table("Forest Encounters")];
+        let names: Vec<&str> = state
+            .matching_tables("forest")
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Forest Encounters", "Urban Forest Encounters"]);
+        assert!(state.matching_tables("desert").is_empty());
+    }
+
+    #[test]
+    fn test_table_roll_without_match_errors() {
+        let mut state = DiceRollerState::new();
+        for c in "@desert".chars() {
+            state.input.insert_char(c);
+        }
+        state.submit();
+        assert!(state.history.is_empty());
+        assert!(state.error.as_deref().unwrap().contains("desert"));
+    }
+
+    #[test]
+    fn test_poll_records_table_roll() {
+        let mut state = DiceRollerState::new();
+        let notation = DiceNotation::parse("d6").unwrap();
+        let rolled = TableRollResult {
+            table_id: "forest".to_string(),
+            table_name: "Forest Encounters".to_string(),
+            roll: RollResult::forced(&notation, 6),
+            entry: crate::core::campaign::random_table::TableEntry {
+                id: "e6".to_string(),
+                range_start: 6,
+                range_end: 6,
+                result_text: "Owlbear".to_string(),
+                result_type: Default::default(),
+                nested_table_id: None,
+                weight: 1.0,
+            },
+            nested_results: Vec::new(),
+            final_text: "Owlbear".to_string(),
+            history_id: "h1".to_string(),
+        };
+        state.data_tx.send(DiceDataEvent::TableRolled(rolled)).unwrap();
+        state.poll();
+
+        assert_eq!(state.history.len(), 1);
+        assert_eq!(state.history[0].label, "Forest Encounters");
+        assert_eq!(state.history[0].outcome.as_deref(), Some("Owlbear"));
+    }
+
     #[test]
     fn test_scroll_resets_on_new_roll() {
         let mut state = DiceRollerState::new();
@@ -28,11 +28,13 @@ use tokio::sync::mpsc;
 
 use crate::core::preprocess::pipeline::QueryPipeline;
 use crate::core::preprocess::typo::TypoCorrector;
-use crate::core::storage::models::{create_library_item, LibraryItem};
+use crate::core::storage::get_chunk_count;
 use crate::ingestion::kreuzberg_extractor::DocumentExtractor;
-use crate::ingestion::slugs::generate_source_slug;
-use crate::tui::events::{AppEvent, IngestionProgressKind};
-use crate::tui::ingestion::run_ingestion_with_error_handling;
+use crate::tui::events::{AppEvent, BackfillProgressKind, IngestionProgressKind};
+use crate::tui::ingestion::{
+    new_library_item, prepare_import, run_ingestion_with_error_handling, ImportPlan,
+    ImportTarget,
+};
 use crate::tui::services::Services;
 use crate::tui::widgets::input_buffer::InputBuffer;
 
@@ -166,7 +168,7 @@ impl ContentFilter {
         let status_ok = match item.status.as_str() {
             "ready" => self.status_ready,
             "processing" | "pending" => self.status_processing,
-            "error" => self.status_error,
+            "error" | "missing" => self.status_error,
             _ => true,
         };
         category_ok && status_ok
@@ -217,9 +219,25 @@ enum IngestionPhase {
     Embedding { processed: usize, total: usize },
     Storing { stored: usize, total: usize },
     Done { chunk_count: usize },
+    Duplicate { title: String },
+    /// Waiting for the user to update `candidate_id` or add a new item.
+    Similar {
+        candidate_id: String,
+        title: String,
+        reason: String,
+    },
     Error(String),
 }
 
+/// A document the user asked to ingest, kept so the import can be restarted
+/// once they decide what to do with a similar library item.
+#[derive(Clone, Debug)]
+struct PendingImport {
+    path: PathBuf,
+    title_override: Option<String>,
+    content_type: String,
+}
+
 #[derive(Clone, Debug)]
 enum IngestionModal {
     InputForm {
@@ -231,6 +249,7 @@ enum IngestionModal {
         file_name: String,
         phase: IngestionPhase,
         library_item_id: Option<String>,
+        import: PendingImport,
     },
 }
 
@@ -305,6 +324,10 @@ pub struct LibraryState {
     search_pending: bool,
     /// Timestamp of the last search input edit.
     last_search_edit: Option<Instant>,
+
+    // ── Background jobs ─────────────────────────────────────────────
+    /// Latest state of the embedding backfill, if one has run.
+    backfill: Option<BackfillProgressKind>,
 }
 
 impl LibraryState {
@@ -331,6 +354,8 @@ impl LibraryState {
 
             search_pending: false,
             last_search_edit: None,
+
+            backfill: None,
         }
     }
 
@@ -496,6 +521,18 @@ impl LibraryState {
                             chunk_count: *chunk_count,
                         }
                     }
+                    IngestionProgressKind::Duplicate { title } => IngestionPhase::Duplicate {
+                        title: title.clone(),
+                    },
+                    IngestionProgressKind::Similar {
+                        candidate_id,
+                        title,
+                        reason,
+                    } => IngestionPhase::Similar {
+                        candidate_id: candidate_id.clone(),
+                        title: title.clone(),
+                        reason: reason.clone(),
+                    },
                     IngestionProgressKind::Error(msg) => IngestionPhase::Error(msg.clone()),
                 };
             }
@@ -509,6 +546,11 @@ impl LibraryState {
         }
     }
 
+    /// Handle a progress event from the embedding backfill job.
+    pub fn handle_backfill_event(&mut self, progress: BackfillProgressKind) {
+        self.backfill = Some(progress);
+    }
+
     // ── Search / filter helpers ──────────────────────────────────────
 
     /// Rebuild the display lines cache, applying search query and filters.
@@ -800,9 +842,11 @@ impl LibraryState {
                 content_type,
                 ..
             } => self.handle_form_input(code, modifiers, focused_field, content_type, services),
-            IngestionModal::Progress { ref phase, .. } => {
-                self.handle_progress_input(code, phase)
-            }
+            IngestionModal::Progress {
+                ref phase,
+                ref import,
+                ..
+            } => self.handle_progress_input(code, phase, import, services),
         }
     }
 
@@ -898,16 +942,39 @@ impl LibraryState {
         }
     }
 
-    fn handle_progress_input(&mut self, code: KeyCode, phase: &IngestionPhase) -> bool {
+    fn handle_progress_input(
+        &mut self,
+        code: KeyCode,
+        phase: &IngestionPhase,
+        import: &PendingImport,
+        services: &Services,
+    ) -> bool {
         match code {
             // Esc closes modal (task continues in background)
             KeyCode::Esc => {
                 self.modal = None;
                 true
             }
+            // A similar item was found: update it or add the file as new
+            KeyCode::Char('u') => {
+                if let IngestionPhase::Similar { candidate_id, .. } = phase {
+                    let target = ImportTarget::Update(candidate_id.clone());
+                    self.spawn_import(import.clone(), target, services);
+                }
+                true
+            }
+            KeyCode::Char('n') if matches!(phase, IngestionPhase::Similar { .. }) => {
+                self.spawn_import(import.clone(), ImportTarget::New, services);
+                true
+            }
             // Enter closes on Done or Error
             KeyCode::Enter
-                if matches!(phase, IngestionPhase::Done { .. } | IngestionPhase::Error(_)) =>
+                if matches!(
+                    phase,
+                    IngestionPhase::Done { .. }
+                        | IngestionPhase::Duplicate { .. }
+                        | IngestionPhase::Error(_)
+                ) =>
             {
                 self.modal = None;
                 true
@@ -945,55 +1012,39 @@ impl LibraryState {
             _ => return,
         };
 
-        // Generate slug and title
-        let title_override = if title_override_text.is_empty() {
-            None
-        } else {
-            Some(title_override_text.as_str())
+        let import = PendingImport {
+            path,
+            title_override: if title_override_text.is_empty() {
+                None
+            } else {
+                Some(title_override_text)
+            },
+            content_type: content_type.as_str().to_string(),
         };
-        let slug = generate_source_slug(&path, title_override);
-        let title = title_override
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| {
-                path.file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("Untitled")
-                    .to_string()
-            });
-
-        let file_ext = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("")
-            .to_lowercase();
-
-        let file_size = std::fs::metadata(&path).map(|m| m.len() as i64).ok();
-
-        let file_name = path
+        self.spawn_import(import, ImportTarget::Auto, services);
+    }
+
+    /// Show the progress modal and run the import in the background.
+    fn spawn_import(&mut self, import: PendingImport, target: ImportTarget, services: &Services) {
+        let file_name = import
+            .path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("document")
             .to_string();
 
-        // Create library item in SurrealDB (status: "processing")
-        let item = LibraryItem::builder(slug.clone(), title)
-            .file_path(file_path_str)
-            .file_type(file_ext)
-            .status("processing")
-            .content_category(content_type.as_str());
-
-        let item = if let Some(size) = file_size {
-            item.file_size(size)
-        } else {
-            item
-        };
-
-        let library_item = item.build();
+        // Slug identifies the progress modal until the library item exists
+        let slug = new_library_item(
+            &import.path,
+            import.title_override.as_deref(),
+            &import.content_type,
+        )
+        .slug;
         let storage = services.storage.clone();
         let event_tx = services.event_tx.clone();
         let embedding_provider = services.embedding_provider.clone();
-        let ct = content_type.as_str().to_string();
-        let file_path_for_spawn = PathBuf::from(library_item.file_path.as_deref().unwrap_or(""));
+        let random_tables = services.random_tables.clone();
+        let import_for_spawn = import.clone();
         let slug_for_spawn = slug.clone();
 
         // Switch to progress modal (use slug as temporary ID for event matching)
@@ -1004,17 +1055,31 @@ impl LibraryState {
                 status: "Starting...".to_string(),
             },
             library_item_id: Some(slug),
+            import,
         });
 
         // Spawn the create + ingest pipeline
         tokio::spawn(async move {
             let db = storage.db();
-
-            // Create library item first
-            let item_id = match create_library_item(db, &library_item).await {
-                Ok(id) => id,
-                Err(e) => {
-                    let error_msg = format!("Failed to create library item: {e}");
+            let PendingImport {
+                path,
+                title_override,
+                content_type,
+            } = import_for_spawn;
+
+            // Find (or create) the library item first; an existing item for
+            // an earlier version of the file is re-ingested incrementally
+            let plan = match prepare_import(
+                db,
+                &path,
+                title_override.as_deref(),
+                &content_type,
+                target,
+            )
+            .await
+            {
+                Ok(plan) => plan,
+                Err(error_msg) => {
                     log::error!("{error_msg}");
                     let _ = event_tx.send(AppEvent::IngestionProgress {
                         library_item_id: slug_for_spawn,
@@ -1024,17 +1089,49 @@ impl LibraryState {
                 }
             };
 
-            // Extract just the ID portion (SurrealDB returns "library_item:xxx")
-            let clean_id = item_id
-                .strip_prefix("library_item:")
-                .unwrap_or(&item_id)
-                .to_string();
+            let clean_id = match plan {
+                ImportPlan::Duplicate { title, .. } => {
+                    let _ = event_tx.send(AppEvent::IngestionProgress {
+                        library_item_id: slug_for_spawn,
+                        phase: IngestionProgressKind::Duplicate { title },
+                    });
+                    return;
+                }
+                ImportPlan::Relinked {
+                    library_item_id, ..
+                } => {
+                    // The moved file's item keeps its chunks
+                    let chunk_count = get_chunk_count(db, &library_item_id).await.unwrap_or(0);
+                    let _ = event_tx.send(AppEvent::IngestionProgress {
+                        library_item_id: slug_for_spawn,
+                        phase: IngestionProgressKind::Complete { chunk_count },
+                    });
+                    return;
+                }
+                ImportPlan::Similar {
+                    library_item_id,
+                    title,
+                    reason,
+                } => {
+                    let _ = event_tx.send(AppEvent::IngestionProgress {
+                        library_item_id: slug_for_spawn,
+                        phase: IngestionProgressKind::Similar {
+                            candidate_id: library_item_id,
+                            title,
+                            reason,
+                        },
+                    });
+                    return;
+                }
+                plan => plan.library_item_id().to_string(),
+            };
 
             run_ingestion_with_error_handling(
-                file_path_for_spawn,
+                path,
                 clean_id,
-                ct,
+                content_type,
                 storage,
+                random_tables,
                 embedding_provider,
                 event_tx,
             )
@@ -1199,14 +1296,20 @@ impl LibraryState {
         let suggestion_height = if self.suggestion.is_some() { 1 } else { 0 };
         let search_bar_height = 1 + suggestion_height;
 
+        let backfill_height = if self.backfill.is_some() { 1 } else { 0 };
+
         let v_chunks = Layout::vertical([
             Constraint::Length(search_bar_height),
             Constraint::Min(1),
+            Constraint::Length(backfill_height),
         ])
         .split(area);
 
         self.render_search_bar(frame, v_chunks[0]);
         self.render_item_list(frame, v_chunks[1]);
+        if let Some(ref progress) = self.backfill {
+            frame.render_widget(Paragraph::new(backfill_line(progress)), v_chunks[2]);
+        }
     }
 
     fn render_search_bar(&self, frame: &mut Frame, area: Rect) {
@@ -1466,6 +1569,7 @@ impl LibraryState {
 
         let border_color = match phase {
             IngestionPhase::Done { .. } => theme::SUCCESS,
+            IngestionPhase::Duplicate { .. } | IngestionPhase::Similar { .. } => theme::WARNING,
             IngestionPhase::Error(_) => theme::ERROR,
             _ => theme::ACCENT,
         };
@@ -1568,6 +1672,49 @@ impl LibraryState {
                     Span::styled("Press Enter or Esc to close", Style::default().fg(theme::TEXT_MUTED)),
                 ]));
             }
+            IngestionPhase::Duplicate { title } => {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled("Already in the library as:", Style::default().fg(theme::WARNING).bold()),
+                ]));
+                let title_display = if title.len() > 44 {
+                    format!("{}...", &title[..41])
+                } else {
+                    title.clone()
+                };
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::raw(title_display),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled("Press Enter or Esc to close", Style::default().fg(theme::TEXT_MUTED)),
+                ]));
+            }
+            IngestionPhase::Similar { title, reason, .. } => {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled("Looks like a new version of:", Style::default().fg(theme::WARNING).bold()),
+                ]));
+                let title_display = if title.chars().count() > 44 {
+                    format!("{}...", title.chars().take(41).collect::<String>())
+                } else {
+                    title.clone()
+                };
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::raw(title_display),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(reason.clone(), Style::default().fg(theme::TEXT_MUTED)),
+                ]));
+                lines.push(Line::raw(""));
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled("[u] Update it  [n] Add as new  [Esc] Cancel", Style::default().fg(theme::TEXT_MUTED)),
+                ]));
+            }
             IngestionPhase::Error(msg) => {
                 lines.push(Line::from(vec![
                     Span::raw("  "),
@@ -1622,6 +1769,44 @@ fn centered_fixed(width: u16, height: u16, area: Rect) -> Rect {
 // ── Line builders ────────────────────────────────────────────────────────────
 
 /// Build display lines from a filtered subset of items.
+/// Status line for the embedding backfill, shown under the document list.
+fn backfill_line(progress: &BackfillProgressKind) -> Line<'static> {
+    let (text, color) = match progress {
+        BackfillProgressKind::Running(p) => (
+            format!(
+                "Embedding backfill: {}/{} chunks",
+                p.embedded + p.failed,
+                p.total
+            ),
+            theme::ACCENT,
+        ),
+        BackfillProgressKind::Complete(p) if p.failed > 0 => (
+            format!(
+                "Embedding backfill: {} chunks embedded, {} failed (retried later)",
+                p.embedded, p.failed
+            ),
+            theme::WARNING,
+        ),
+        BackfillProgressKind::Complete(p) => (
+            format!("Embedding backfill: {} chunks embedded", p.embedded),
+            theme::SUCCESS,
+        ),
+        BackfillProgressKind::Stopped { progress: p, error } => (
+            format!(
+                "Embedding backfill paused at {}/{} chunks: {}",
+                p.embedded + p.failed,
+                p.total,
+                error
+            ),
+            theme::WARNING,
+        ),
+    };
+    Line::from(vec![
+        Span::raw(" "),
+        Span::styled(text, Style::default().fg(color)),
+    ])
+}
+
 fn build_lines_filtered(
     items: &[&ItemDisplay],
     data: &LibraryData,
@@ -1693,6 +1878,7 @@ fn build_lines_filtered(
                 "processing" => theme::ACCENT,
                 "pending" => theme::TEXT_MUTED,
                 "error" => theme::ERROR,
+                "missing" => theme::WARNING,
                 _ => theme::TEXT_MUTED,
             };
 
@@ -1785,6 +1971,32 @@ mod tests {
         assert!(state.suggestion.is_none());
     }
 
+    #[test]
+    fn test_backfill_line() {
+        use crate::core::storage::embedding_index::BackfillProgress;
+
+        let progress = BackfillProgress {
+            embedded: 30,
+            failed: 2,
+            total: 64,
+        };
+        let text = |kind: BackfillProgressKind| -> String {
+            backfill_line(&kind)
+                .spans
+                .iter()
+                .map(|s| s.content.as_ref())
+                .collect()
+        };
+
+        assert!(text(BackfillProgressKind::Running(progress)).contains("32/64 chunks"));
+        assert!(text(BackfillProgressKind::Complete(progress)).contains("2 failed"));
+        assert!(text(BackfillProgressKind::Stopped {
+            progress,
+            error: "connection refused".to_string(),
+        })
+        .contains("paused at 32/64 chunks: connection refused"));
+    }
+
     #[test]
     fn test_open_ingest_modal() {
         let mut state = LibraryState::new();
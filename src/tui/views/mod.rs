@@ -1,6 +1,8 @@
 pub mod archetypes;
 pub mod assets;
 pub mod audit;
+pub mod bestiary;
+pub mod calendar;
 pub mod campaign;
 pub mod campaign_wizard;
 pub mod character_gen;
@@ -12,10 +14,14 @@ pub mod generation;
 pub mod ingestion;
 pub mod library;
 pub mod locations;
+pub mod notes;
+pub mod npc_memory;
 pub mod npcs;
 pub mod personality;
 pub mod rag;
+pub mod relationships;
 pub mod settings;
 pub mod system;
 pub mod usage;
+pub mod vault_unlock;
 pub mod voice;
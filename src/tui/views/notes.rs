@@ -0,0 +1,1443 @@
+//! Notes workspace — session notes backed by `NotesManager`.
+//!
+//! Master-detail layout: filterable note list on the left, the selected note
+//! (content, tags, linked entities) on the right.
+//! Press `a` to add, `e`/`Enter` to edit, `d` to delete, `p` to pin.
+//! Press `s`/`c`/`t` to cycle the session/category/tag filters, `/` to fuzzy
+//! search, `l` to link NPCs and locations, `x` to export a session to Markdown.
+
+use std::collections::HashSet;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use nucleo::{
+    pattern::{Atom, AtomKind, CaseMatching, Normalization},
+    Matcher, Utf32Str,
+};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use ratatui_textarea::TextArea;
+use tokio::sync::mpsc;
+
+use crate::config::AppConfig;
+use crate::core::session::notes::{EntityType, NoteCategory, SessionNote};
+use crate::database::{LocationOps, NpcOps};
+use crate::tui::app::centered_rect;
+use crate::tui::events::{AppEvent, Notification, NotificationLevel};
+use crate::tui::services::Services;
+use crate::tui::theme;
+use crate::tui::widgets::input_buffer::InputBuffer;
+
+// ── Internal async data events ─────────────────────────────────────────────
+
+enum NotesDataEvent {
+    LinkTargetsLoaded(Vec<LinkTarget>),
+    LoadError(String),
+}
+
+/// An NPC or location a note can be linked to.
+#[derive(Debug, Clone)]
+struct LinkTarget {
+    entity_type: EntityType,
+    id: String,
+    name: String,
+    /// NPC role or location type
+    detail: String,
+}
+
+// ── Modal types ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotesModal {
+    Editor,
+    Delete,
+    Link,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditorField {
+    Title,
+    Category,
+    Tags,
+    Content,
+}
+
+const EDITOR_FIELDS: [EditorField; 4] = [
+    EditorField::Title,
+    EditorField::Category,
+    EditorField::Tags,
+    EditorField::Content,
+];
+
+// ── State ──────────────────────────────────────────────────────────────────
+
+pub struct NotesViewState {
+    // Notes, pinned first then most recently updated
+    notes: Vec<SessionNote>,
+    /// Indices into `notes` that pass the filters, in display order
+    visible: Vec<usize>,
+    selected: usize,
+    detail_scroll: u16,
+
+    // Sessions that own notes: (id, label), newest first
+    sessions: Vec<(String, String)>,
+    active_session: Option<String>,
+
+    // Filters
+    session_filter: Option<String>,
+    category_filter: Option<NoteCategory>,
+    tag_filter: Option<String>,
+    search: InputBuffer,
+    search_active: bool,
+    matcher: Matcher,
+
+    // Modal
+    modal: Option<NotesModal>,
+    editing_id: Option<String>,
+    form_focus: usize,
+    form_title: InputBuffer,
+    form_category: NoteCategory,
+    form_tags: InputBuffer,
+    form_content: TextArea<'static>,
+
+    // Entity link picker
+    link_targets: Vec<LinkTarget>,
+    link_filter: InputBuffer,
+    link_cursor: usize,
+    link_loading: bool,
+
+    // Error/status
+    error: Option<String>,
+
+    // Async channel
+    data_tx: mpsc::UnboundedSender<NotesDataEvent>,
+    data_rx: mpsc::UnboundedReceiver<NotesDataEvent>,
+}
+
+impl NotesViewState {
+    pub fn new() -> Self {
+        let (data_tx, data_rx) = mpsc::unbounded_channel();
+        Self {
+            notes: Vec::new(),
+            visible: Vec::new(),
+            selected: 0,
+            detail_scroll: 0,
+            sessions: Vec::new(),
+            active_session: None,
+            session_filter: None,
+            category_filter: None,
+            tag_filter: None,
+            search: InputBuffer::new(),
+            search_active: false,
+            matcher: Matcher::default(),
+            modal: None,
+            editing_id: None,
+            form_focus: 0,
+            form_title: InputBuffer::new(),
+            form_category: NoteCategory::General,
+            form_tags: InputBuffer::new(),
+            form_content: new_content_area(""),
+            link_targets: Vec::new(),
+            link_filter: InputBuffer::new(),
+            link_cursor: 0,
+            link_loading: false,
+            error: None,
+            data_tx,
+            data_rx,
+        }
+    }
+
+    /// Reload notes and session labels from the session manager.
+    pub fn load(&mut self, services: &Services) {
+        let selected_id = self.selected_note().map(|n| n.id.clone());
+
+        self.notes = services.session.list_notes();
+        self.active_session = services.session.latest_active_session().map(|s| s.id);
+
+        let mut ids: Vec<&str> = self.notes.iter().map(|n| n.session_id.as_str()).collect();
+        ids.extend(self.active_session.as_deref());
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+
+        let mut sessions: Vec<_> = ids
+            .into_iter()
+            .map(|id| {
+                let session = services.session.get_session(id);
+                let label = match &session {
+                    Some(s) => s
+                        .title
+                        .clone()
+                        .unwrap_or_else(|| format!("Session {}", s.session_number)),
+                    None if id.is_empty() => "Unfiled".to_string(),
+                    None => "Unknown session".to_string(),
+                };
+                (session.map(|s| s.started_at), id.to_string(), label)
+            })
+            .collect();
+        // Newest first; unfiled/unknown (no start time) last
+        sessions.sort_by(|a, b| b.0.cmp(&a.0));
+        self.sessions = sessions
+            .into_iter()
+            .map(|(_, id, label)| (id, label))
+            .collect();
+
+        // Drop filters that no longer match anything
+        if let Some(ref sid) = self.session_filter {
+            if !self.sessions.iter().any(|(id, _)| id == sid) {
+                self.session_filter = None;
+            }
+        }
+        if let Some(ref tag) = self.tag_filter {
+            if !self.notes.iter().any(|n| n.tags.contains(tag)) {
+                self.tag_filter = None;
+            }
+        }
+
+        self.refilter();
+        if let Some(id) = selected_id {
+            self.select_note(&id);
+        }
+    }
+
+    pub fn poll(&mut self) {
+        while let Ok(event) = self.data_rx.try_recv() {
+            match event {
+                NotesDataEvent::LinkTargetsLoaded(targets) => {
+                    self.link_targets = targets;
+                    self.link_cursor = 0;
+                    self.link_loading = false;
+                }
+                NotesDataEvent::LoadError(msg) => {
+                    self.link_loading = false;
+                    self.error = Some(msg);
+                }
+            }
+        }
+    }
+
+    // ── Filtering ──────────────────────────────────────────────────────────
+
+    /// Recompute `visible` from the filters and fuzzy search.
+    fn refilter(&mut self) {
+        let candidates = self.notes.iter().enumerate().filter(|(_, n)| {
+            self.session_filter
+                .as_ref()
+                .is_none_or(|s| &n.session_id == s)
+                && self
+                    .category_filter
+                    .as_ref()
+                    .is_none_or(|c| &n.category == c || n.additional_categories.contains(c))
+                && self.tag_filter.as_ref().is_none_or(|t| n.tags.contains(t))
+        });
+
+        let query = self.search.text().trim();
+        if query.is_empty() {
+            self.visible = candidates.map(|(i, _)| i).collect();
+        } else {
+            let atom = Atom::new(
+                query,
+                CaseMatching::Ignore,
+                Normalization::Smart,
+                AtomKind::Fuzzy,
+                false,
+            );
+            let mut buf = Vec::new();
+            let mut scored: Vec<(usize, u16)> = Vec::new();
+            for (i, note) in candidates {
+                let haystack = format!("{} {} {}", note.title, note.tags.join(" "), note.content);
+                if let Some(score) =
+                    atom.score(Utf32Str::new(&haystack, &mut buf), &mut self.matcher)
+                {
+                    scored.push((i, score));
+                }
+            }
+            // Stable sort keeps pinned/recent order among equal scores
+            scored.sort_by(|a, b| b.1.cmp(&a.1));
+            self.visible = scored.into_iter().map(|(i, _)| i).collect();
+        }
+
+        self.selected = self.selected.min(self.visible.len().saturating_sub(1));
+        self.detail_scroll = 0;
+    }
+
+    fn selected_note(&self) -> Option<&SessionNote> {
+        self.visible
+            .get(self.selected)
+            .and_then(|&i| self.notes.get(i))
+    }
+
+    fn select_note(&mut self, note_id: &str) {
+        if let Some(pos) = self
+            .visible
+            .iter()
+            .position(|&i| self.notes[i].id == note_id)
+        {
+            self.selected = pos;
+        }
+    }
+
+    fn session_label(&self, session_id: &str) -> &str {
+        self.sessions
+            .iter()
+            .find(|(id, _)| id == session_id)
+            .map(|(_, label)| label.as_str())
+            .unwrap_or("Unfiled")
+    }
+
+    /// Categories in use, in display order.
+    fn categories_in_use(&self) -> Vec<NoteCategory> {
+        let mut used: Vec<NoteCategory> = Vec::new();
+        for note in &self.notes {
+            for c in std::iter::once(&note.category).chain(&note.additional_categories) {
+                if !used.contains(c) {
+                    used.push(c.clone());
+                }
+            }
+        }
+        let rank = |c: &NoteCategory| {
+            NoteCategory::ALL
+                .iter()
+                .position(|b| b == c)
+                .unwrap_or(NoteCategory::ALL.len())
+        };
+        used.sort_by_key(|c| (rank(c), c.display()));
+        used
+    }
+
+    /// Tags in use, alphabetically.
+    fn tags_in_use(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .notes
+            .iter()
+            .flat_map(|n| n.tags.iter().cloned())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        tags.sort_by_key(|t| t.to_lowercase());
+        tags
+    }
+
+    // ── Input handling ─────────────────────────────────────────────────────
+
+    pub fn handle_input(&mut self, event: &Event, services: &Services) -> bool {
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        else {
+            return false;
+        };
+
+        if let Some(modal) = self.modal {
+            match modal {
+                NotesModal::Editor => self.handle_editor_input(event, *code, *modifiers, services),
+                NotesModal::Delete => self.handle_delete_input(*code, services),
+                NotesModal::Link => self.handle_link_input(*code, *modifiers, services),
+            }
+            return true;
+        }
+
+        if self.search_active {
+            return self.handle_search_input(*code, *modifiers);
+        }
+
+        self.handle_list_input(*code, *modifiers, services)
+    }
+
+    fn handle_list_input(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        services: &Services,
+    ) -> bool {
+        match (modifiers, code) {
+            (KeyModifiers::NONE, KeyCode::Char('j') | KeyCode::Down) => {
+                if self.selected + 1 < self.visible.len() {
+                    self.selected += 1;
+                    self.detail_scroll = 0;
+                }
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('k') | KeyCode::Up) => {
+                if self.selected > 0 {
+                    self.selected -= 1;
+                    self.detail_scroll = 0;
+                }
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::PageDown) => {
+                self.detail_scroll = self.detail_scroll.saturating_add(10);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::PageUp) => {
+                self.detail_scroll = self.detail_scroll.saturating_sub(10);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('/')) => {
+                self.search_active = true;
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Esc) => {
+                // Clear search and filters
+                let had_filter = !self.search.is_empty()
+                    || self.session_filter.is_some()
+                    || self.category_filter.is_some()
+                    || self.tag_filter.is_some();
+                self.search.clear();
+                self.session_filter = None;
+                self.category_filter = None;
+                self.tag_filter = None;
+                self.refilter();
+                had_filter
+            }
+            (KeyModifiers::NONE, KeyCode::Char('s')) => {
+                let ids: Vec<String> = self.sessions.iter().map(|(id, _)| id.clone()).collect();
+                self.session_filter = cycle(&self.session_filter, &ids);
+                self.refilter();
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('c')) => {
+                self.category_filter = cycle(&self.category_filter, &self.categories_in_use());
+                self.refilter();
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('t')) => {
+                self.tag_filter = cycle(&self.tag_filter, &self.tags_in_use());
+                self.refilter();
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('a')) => {
+                self.open_editor(None);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('e') | KeyCode::Enter) => {
+                if let Some(note) = self.selected_note().cloned() {
+                    self.open_editor(Some(&note));
+                }
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('d')) => {
+                if self.selected_note().is_some() {
+                    self.modal = Some(NotesModal::Delete);
+                }
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('p')) => {
+                if let Some(mut note) = self.selected_note().cloned() {
+                    note.is_pinned = !note.is_pinned;
+                    if let Err(e) = services.session.update_note(note) {
+                        self.error = Some(e.to_string());
+                    }
+                    self.load(services);
+                }
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('l')) => {
+                self.open_link_picker(services);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('x')) => {
+                self.export_markdown(services);
+                true
+            }
+            (KeyModifiers::NONE, KeyCode::Char('r')) => {
+                self.load(services);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_search_input(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        match (modifiers, code) {
+            (KeyModifiers::NONE, KeyCode::Esc) => {
+                self.search.clear();
+                self.search_active = false;
+                self.refilter();
+            }
+            (KeyModifiers::NONE, KeyCode::Enter) => {
+                self.search_active = false;
+            }
+            (KeyModifiers::NONE, KeyCode::Down) => {
+                if self.selected + 1 < self.visible.len() {
+                    self.selected += 1;
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Up) => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            _ => {
+                route_text_input(&mut self.search, code, modifiers);
+                self.selected = 0;
+                self.refilter();
+            }
+        }
+        true
+    }
+
+    fn handle_editor_input(
+        &mut self,
+        event: &Event,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        services: &Services,
+    ) {
+        match (modifiers, code) {
+            (KeyModifiers::NONE, KeyCode::Esc) => {
+                self.modal = None;
+                self.error = None;
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('s') | KeyCode::Enter) => {
+                self.save_editor(services);
+            }
+            (KeyModifiers::NONE, KeyCode::Tab) => {
+                self.form_focus = (self.form_focus + 1) % EDITOR_FIELDS.len();
+                self.update_content_block();
+            }
+            (KeyModifiers::SHIFT, KeyCode::BackTab) => {
+                self.form_focus = (self.form_focus + EDITOR_FIELDS.len() - 1) % EDITOR_FIELDS.len();
+                self.update_content_block();
+            }
+            _ => match EDITOR_FIELDS[self.form_focus] {
+                EditorField::Title => route_text_input(&mut self.form_title, code, modifiers),
+                EditorField::Tags => route_text_input(&mut self.form_tags, code, modifiers),
+                EditorField::Category => {
+                    let all = &NoteCategory::ALL;
+                    let pos = all
+                        .iter()
+                        .position(|c| *c == self.form_category)
+                        .unwrap_or(0);
+                    match code {
+                        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                            self.form_category = all[(pos + 1) % all.len()].clone();
+                        }
+                        KeyCode::Left | KeyCode::Char('h') => {
+                            self.form_category = all[(pos + all.len() - 1) % all.len()].clone();
+                        }
+                        _ => {}
+                    }
+                }
+                EditorField::Content => {
+                    self.form_content.input(event.clone());
+                }
+            },
+        }
+    }
+
+    fn handle_delete_input(&mut self, code: KeyCode, services: &Services) {
+        match code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(id) = self.selected_note().map(|n| n.id.clone()) {
+                    if let Err(e) = services.session.delete_note(&id) {
+                        self.error = Some(e.to_string());
+                    }
+                    self.load(services);
+                }
+                self.modal = None;
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.modal = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_link_input(&mut self, code: KeyCode, modifiers: KeyModifiers, services: &Services) {
+        let filtered = self.filtered_link_targets();
+        match (modifiers, code) {
+            (KeyModifiers::NONE, KeyCode::Esc) => {
+                self.modal = None;
+            }
+            (KeyModifiers::NONE, KeyCode::Down) => {
+                if self.link_cursor + 1 < filtered.len() {
+                    self.link_cursor += 1;
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Up) => {
+                self.link_cursor = self.link_cursor.saturating_sub(1);
+            }
+            (KeyModifiers::NONE, KeyCode::Enter) => {
+                let Some(target) = filtered
+                    .get(self.link_cursor)
+                    .map(|&i| &self.link_targets[i])
+                else {
+                    return;
+                };
+                let Some(note) = self.selected_note() else {
+                    return;
+                };
+                // Enter toggles the link
+                let result = if note.entity_links.iter().any(|l| l.entity_id == target.id) {
+                    services
+                        .session
+                        .unlink_entity_from_note(&note.id, &target.id)
+                } else {
+                    services.session.link_entity_to_note(
+                        &note.id,
+                        target.entity_type.clone(),
+                        &target.id,
+                        &target.name,
+                    )
+                };
+                if let Err(e) = result {
+                    self.error = Some(e.to_string());
+                }
+                self.load(services);
+            }
+            _ => {
+                route_text_input(&mut self.link_filter, code, modifiers);
+                self.link_cursor = 0;
+            }
+        }
+    }
+
+    // ── Editor helpers ─────────────────────────────────────────────────────
+
+    fn open_editor(&mut self, note: Option<&SessionNote>) {
+        self.modal = Some(NotesModal::Editor);
+        self.editing_id = note.map(|n| n.id.clone());
+        self.form_focus = 0;
+        self.error = None;
+        self.form_title
+            .set_text(note.map(|n| n.title.as_str()).unwrap_or(""));
+        self.form_category = note
+            .map(|n| n.category.clone())
+            .or_else(|| self.category_filter.clone())
+            .unwrap_or(NoteCategory::General);
+        self.form_tags
+            .set_text(&note.map(|n| n.tags.join(", ")).unwrap_or_default());
+        self.form_content = new_content_area(note.map(|n| n.content.as_str()).unwrap_or(""));
+        self.update_content_block();
+    }
+
+    /// Highlight the content border while it has focus.
+    fn update_content_block(&mut self) {
+        let block = if EDITOR_FIELDS[self.form_focus] == EditorField::Content {
+            theme::block_focused("Content")
+        } else {
+            theme::block_default("Content")
+        };
+        self.form_content.set_block(block);
+    }
+
+    fn save_editor(&mut self, services: &Services) {
+        let title = self.form_title.text().trim().to_string();
+        if title.is_empty() {
+            self.error = Some("Title is required.".to_string());
+            return;
+        }
+        let content = self.form_content.lines().join("\n");
+        let tags = parse_tags(self.form_tags.text());
+
+        let existing = self
+            .editing_id
+            .as_deref()
+            .and_then(|id| services.session.get_note(id));
+        let result = match existing {
+            Some(mut note) => {
+                note.update_title(title);
+                note.update_content(content);
+                note.category = self.form_category.clone();
+                note.tags = tags;
+                services.session.update_note(note).map(|n| n.id)
+            }
+            None => {
+                // New notes go to the filtered session, else the running one
+                let session_id = self
+                    .session_filter
+                    .clone()
+                    .or_else(|| self.active_session.clone())
+                    .unwrap_or_default();
+                let campaign_id = services
+                    .session
+                    .get_session(&session_id)
+                    .map(|s| s.campaign_id)
+                    .unwrap_or_default();
+                let note = SessionNote::new(session_id, campaign_id, title, content)
+                    .with_category(self.form_category.clone())
+                    .with_tags(tags);
+                let id = note.id.clone();
+                services.session.create_note(note).map(|_| id)
+            }
+        };
+
+        match result {
+            Ok(id) => {
+                self.modal = None;
+                self.error = None;
+                self.load(services);
+                self.select_note(&id);
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    // ── Entity links ───────────────────────────────────────────────────────
+
+    fn open_link_picker(&mut self, services: &Services) {
+        let Some(note) = self.selected_note() else {
+            return;
+        };
+        let campaign_id = note.campaign_id.clone();
+
+        self.modal = Some(NotesModal::Link);
+        self.link_filter.clear();
+        self.link_cursor = 0;
+        self.link_loading = true;
+        self.error = None;
+
+        let db = services.database.clone();
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            let npcs = match db.list_npcs(None).await {
+                Ok(npcs) => npcs,
+                Err(e) => {
+                    let _ = tx.send(NotesDataEvent::LoadError(format!("{e}")));
+                    return;
+                }
+            };
+            let locations = if campaign_id.is_empty() {
+                Vec::new()
+            } else {
+                db.list_locations(&campaign_id).await.unwrap_or_default()
+            };
+
+            let mut targets: Vec<LinkTarget> = npcs
+                .into_iter()
+                .map(|npc| LinkTarget {
+                    entity_type: EntityType::NPC,
+                    id: npc.id,
+                    name: npc.name,
+                    detail: npc.role,
+                })
+                .collect();
+            targets.extend(locations.into_iter().map(|loc| LinkTarget {
+                entity_type: EntityType::Location,
+                id: loc.id,
+                name: loc.name,
+                detail: loc.location_type,
+            }));
+            let _ = tx.send(NotesDataEvent::LinkTargetsLoaded(targets));
+        });
+    }
+
+    /// Indices into `link_targets` matching the picker filter.
+    fn filtered_link_targets(&self) -> Vec<usize> {
+        let query = self.link_filter.text().trim().to_lowercase();
+        self.link_targets
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| query.is_empty() || t.name.to_lowercase().contains(&query))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    // ── Export ─────────────────────────────────────────────────────────────
+
+    /// Write the filtered (or selected note's) session to `<data>/exports/`.
+    fn export_markdown(&mut self, services: &Services) {
+        let Some(session_id) = self
+            .session_filter
+            .clone()
+            .or_else(|| self.selected_note().map(|n| n.session_id.clone()))
+        else {
+            notify(services, "No session to export", NotificationLevel::Warning);
+            return;
+        };
+
+        let export = services.session.export_session_notes(&session_id);
+        let label = self.session_label(&session_id).to_string();
+        let markdown = export.to_markdown(&format!("{label} Notes"));
+        let campaign_id = services
+            .session
+            .get_session(&session_id)
+            .map(|s| s.campaign_id)
+            .or_else(|| {
+                self.notes
+                    .iter()
+                    .find(|n| n.session_id == session_id)
+                    .map(|n| n.campaign_id.clone())
+            })
+            .unwrap_or_default();
+
+        let dir = AppConfig::load().data_dir().join("exports");
+        let path = dir.join(export_file_name(&campaign_id, &session_id, &label));
+        match std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, markdown)) {
+            Ok(()) => notify(
+                services,
+                &format!(
+                    "Exported {} notes to {}",
+                    export.notes.len(),
+                    path.display()
+                ),
+                NotificationLevel::Success,
+            ),
+            Err(e) => notify(
+                services,
+                &format!("Export failed: {e}"),
+                NotificationLevel::Error,
+            ),
+        }
+    }
+
+    // ── Rendering ──────────────────────────────────────────────────────────
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let rows = Layout::vertical([
+            Constraint::Length(1), // Filter bar
+            Constraint::Min(5),    // List + detail
+            Constraint::Length(1), // Key hints
+        ])
+        .split(area);
+
+        self.render_filter_bar(frame, rows[0]);
+
+        let cols = Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(rows[1]);
+        self.render_list(frame, cols[0]);
+        self.render_detail(frame, cols[1]);
+
+        self.render_hints(frame, rows[2]);
+
+        if let Some(modal) = self.modal {
+            match modal {
+                NotesModal::Editor => self.render_editor(frame, area),
+                NotesModal::Delete => self.render_delete_modal(frame, area),
+                NotesModal::Link => self.render_link_picker(frame, area),
+            }
+        }
+    }
+
+    fn render_filter_bar(&self, frame: &mut Frame, area: Rect) {
+        let value = |v: Option<String>| match v {
+            Some(v) => Span::styled(v, Style::default().fg(theme::ACCENT)),
+            None => Span::styled("All", Style::default().fg(theme::TEXT_MUTED)),
+        };
+        let label = |s: &'static str| Span::styled(s, Style::default().fg(theme::TEXT_DIM));
+
+        let mut spans = vec![
+            label(" Session: "),
+            value(
+                self.session_filter
+                    .as_deref()
+                    .map(|s| self.session_label(s).to_string()),
+            ),
+            label("  Category: "),
+            value(self.category_filter.as_ref().map(|c| c.display())),
+            label("  Tag: "),
+            value(self.tag_filter.as_ref().map(|t| format!("#{t}"))),
+        ];
+
+        if self.search_active || !self.search.is_empty() {
+            spans.push(label("  Search: "));
+            let cursor = if self.search_active { "▎" } else { "" };
+            spans.push(Span::styled(
+                format!("{}{cursor}", self.search.text()),
+                Style::default().fg(theme::TEXT),
+            ));
+        }
+
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn render_list(&self, frame: &mut Frame, area: Rect) {
+        let title = if self.visible.len() == self.notes.len() {
+            format!(" Notes ({}) ", self.notes.len())
+        } else {
+            format!(" Notes ({}/{}) ", self.visible.len(), self.notes.len())
+        };
+        let block = theme::block_focused(title.trim());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.visible.is_empty() {
+            let msg = if self.notes.is_empty() {
+                " No notes yet — press a to add one"
+            } else {
+                " No notes match the filters"
+            };
+            frame.render_widget(
+                Paragraph::new(Span::styled(msg, Style::default().fg(theme::TEXT_DIM))),
+                inner,
+            );
+            return;
+        }
+
+        // Keep the selection in view
+        let height = inner.height as usize;
+        let offset = self.selected.saturating_sub(height.saturating_sub(1));
+        let width = inner.width as usize;
+
+        let lines: Vec<Line> = self
+            .visible
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .map(|(pos, &i)| {
+                let note = &self.notes[i];
+                let is_selected = pos == self.selected;
+                let marker = if is_selected { "▸ " } else { "  " };
+                let pin = if note.is_pinned { "● " } else { "" };
+                let category = format!("{:<9}", truncate(&note.category.display(), 9));
+                let title_width = width.saturating_sub(marker.len() + pin.len() + 10);
+                let title_style = if is_selected {
+                    Style::default()
+                        .fg(theme::ACCENT)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme::TEXT)
+                };
+                Line::from(vec![
+                    Span::styled(marker, Style::default().fg(theme::ACCENT)),
+                    Span::styled(pin, Style::default().fg(theme::WARNING)),
+                    Span::styled(
+                        format!("{category} "),
+                        Style::default().fg(theme::TEXT_MUTED),
+                    ),
+                    Span::styled(truncate(&note.title, title_width), title_style),
+                ])
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_detail(&self, frame: &mut Frame, area: Rect) {
+        let block = theme::block_default("Note");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let Some(note) = self.selected_note() else {
+            return;
+        };
+
+        let muted = Style::default().fg(theme::TEXT_MUTED);
+        let mut lines: Vec<Line> = vec![
+            Line::from(Span::styled(
+                note.title.clone(),
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(vec![
+                Span::styled(
+                    note.category.display(),
+                    Style::default().fg(theme::PRIMARY_LIGHT),
+                ),
+                Span::styled(
+                    format!(
+                        " · {} · {} words · updated {}",
+                        self.session_label(&note.session_id),
+                        note.word_count(),
+                        note.updated_at.format("%Y-%m-%d %H:%M")
+                    ),
+                    muted,
+                ),
+            ]),
+        ];
+
+        if !note.tags.is_empty() {
+            let tags: Vec<String> = note.tags.iter().map(|t| format!("#{t}")).collect();
+            lines.push(Line::from(vec![
+                Span::styled("Tags: ", muted),
+                Span::styled(tags.join(" "), Style::default().fg(theme::INFO)),
+            ]));
+        }
+        if !note.entity_links.is_empty() {
+            let links: Vec<String> = note
+                .entity_links
+                .iter()
+                .map(|l| format!("{} ({})", l.display_name, l.entity_type.display()))
+                .collect();
+            lines.push(Line::from(vec![
+                Span::styled("Links: ", muted),
+                Span::styled(links.join(", "), Style::default().fg(theme::NPC)),
+            ]));
+        }
+
+        lines.push(Line::from(Span::styled(
+            "─".repeat(inner.width as usize),
+            Style::default().fg(theme::TEXT_DIM),
+        )));
+        lines.extend(note.content.lines().map(|l| Line::raw(l.to_string())));
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .scroll((self.detail_scroll, 0)),
+            inner,
+        );
+    }
+
+    fn render_hints(&self, frame: &mut Frame, area: Rect) {
+        let hint = |key: &'static str, desc: &'static str| {
+            [
+                Span::styled(key, theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(desc, theme::key_hint()),
+            ]
+        };
+        let spans: Vec<Span> = [
+            hint(" a", ":add "),
+            hint("e", ":edit "),
+            hint("d", ":delete "),
+            hint("p", ":pin "),
+            hint("l", ":link "),
+            hint("s/c/t", ":filter "),
+            hint("/", ":search "),
+            hint("x", ":export"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let mut line = Line::from(spans);
+        if let Some(ref err) = self.error {
+            if self.modal.is_none() {
+                line = Line::from(Span::styled(
+                    format!(" ✗ {err}"),
+                    Style::default().fg(theme::ERROR),
+                ));
+            }
+        }
+        frame.render_widget(Paragraph::new(line), area);
+    }
+
+    fn render_editor(&self, frame: &mut Frame, area: Rect) {
+        let modal_area = centered_rect(70, 80, area);
+        frame.render_widget(Clear, modal_area);
+
+        let title = if self.editing_id.is_some() {
+            " Edit Note "
+        } else {
+            " New Note "
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::ACCENT));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let rows = Layout::vertical([
+            Constraint::Length(1), // Title
+            Constraint::Length(1), // Category
+            Constraint::Length(1), // Tags
+            Constraint::Min(3),    // Content
+            Constraint::Length(1), // Hints / error
+        ])
+        .split(inner);
+
+        let focused = EDITOR_FIELDS[self.form_focus];
+        let field_line = |field: EditorField, label: &str, value: String| {
+            let is_focused = field == focused;
+            let label_style = if is_focused {
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::TEXT_MUTED)
+            };
+            Line::from(vec![
+                Span::raw(if is_focused { " ▸ " } else { "   " }),
+                Span::styled(format!("{label:<10}"), label_style),
+                Span::styled(value, Style::default().fg(theme::TEXT)),
+            ])
+        };
+        let with_cursor = |field: EditorField, buf: &InputBuffer| {
+            if field == focused {
+                format!("{}▎", buf.text())
+            } else {
+                buf.text().to_string()
+            }
+        };
+
+        frame.render_widget(
+            Paragraph::new(field_line(
+                EditorField::Title,
+                "Title:",
+                with_cursor(EditorField::Title, &self.form_title),
+            )),
+            rows[0],
+        );
+        let category = if focused == EditorField::Category {
+            format!("◂ {} ▸", self.form_category.display())
+        } else {
+            self.form_category.display()
+        };
+        frame.render_widget(
+            Paragraph::new(field_line(EditorField::Category, "Category:", category)),
+            rows[1],
+        );
+        frame.render_widget(
+            Paragraph::new(field_line(
+                EditorField::Tags,
+                "Tags:",
+                with_cursor(EditorField::Tags, &self.form_tags),
+            )),
+            rows[2],
+        );
+
+        frame.render_widget(&self.form_content, rows[3]);
+
+        let footer = match self.error {
+            Some(ref err) => Line::from(Span::styled(
+                format!(" ✗ {err}"),
+                Style::default().fg(theme::ERROR),
+            )),
+            None => Line::from(vec![
+                Span::styled(" Tab", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":field ", theme::key_hint()),
+                Span::styled("←/→", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":category ", theme::key_hint()),
+                Span::styled("Ctrl+S", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":save ", theme::key_hint()),
+                Span::styled("Esc", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":cancel", theme::key_hint()),
+            ]),
+        };
+        frame.render_widget(Paragraph::new(footer), rows[4]);
+    }
+
+    fn render_delete_modal(&self, frame: &mut Frame, area: Rect) {
+        let modal_area = centered_rect(40, 20, area);
+        frame.render_widget(Clear, modal_area);
+
+        let title = self
+            .selected_note()
+            .map(|n| n.title.as_str())
+            .unwrap_or("?");
+        let block = Block::default()
+            .title(" Delete Note ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::ERROR));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let lines = vec![
+            Line::raw(""),
+            Line::from(vec![
+                Span::raw("  Delete "),
+                Span::styled(
+                    title.to_string(),
+                    Style::default()
+                        .fg(theme::ACCENT)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("?"),
+            ]),
+            Line::raw(""),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled("y/Enter", Style::default().fg(theme::SUCCESS)),
+                Span::raw(" to confirm, "),
+                Span::styled("n/Esc", Style::default().fg(theme::ERROR)),
+                Span::raw(" to cancel"),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_link_picker(&self, frame: &mut Frame, area: Rect) {
+        let modal_area = centered_rect(50, 60, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(" Link NPC / Location ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::ACCENT));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let rows = Layout::vertical([
+            Constraint::Length(1), // Filter
+            Constraint::Min(1),    // Targets
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(" Filter: ", Style::default().fg(theme::TEXT_DIM)),
+                Span::styled(
+                    format!("{}▎", self.link_filter.text()),
+                    Style::default().fg(theme::TEXT),
+                ),
+            ])),
+            rows[0],
+        );
+
+        let linked: HashSet<&str> = self
+            .selected_note()
+            .map(|n| {
+                n.entity_links
+                    .iter()
+                    .map(|l| l.entity_id.as_str())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let filtered = self.filtered_link_targets();
+
+        let body: Vec<Line> = if self.link_loading {
+            vec![Line::from(Span::styled(
+                " Loading…",
+                Style::default().fg(theme::TEXT_DIM),
+            ))]
+        } else if let Some(ref err) = self.error {
+            vec![Line::from(Span::styled(
+                format!(" ✗ {err}"),
+                Style::default().fg(theme::ERROR),
+            ))]
+        } else if filtered.is_empty() {
+            vec![Line::from(Span::styled(
+                " No NPCs or locations found",
+                Style::default().fg(theme::TEXT_DIM),
+            ))]
+        } else {
+            let height = rows[1].height as usize;
+            let offset = self.link_cursor.saturating_sub(height.saturating_sub(1));
+            filtered
+                .iter()
+                .enumerate()
+                .skip(offset)
+                .take(height)
+                .map(|(pos, &i)| {
+                    let target = &self.link_targets[i];
+                    let is_selected = pos == self.link_cursor;
+                    let check = if linked.contains(target.id.as_str()) {
+                        "[✓] "
+                    } else {
+                        "[ ] "
+                    };
+                    let name_style = if is_selected {
+                        Style::default()
+                            .fg(theme::ACCENT)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme::TEXT)
+                    };
+                    Line::from(vec![
+                        Span::raw(if is_selected { " ▸ " } else { "   " }),
+                        Span::styled(check, Style::default().fg(theme::SUCCESS)),
+                        Span::styled(target.name.clone(), name_style),
+                        Span::styled(
+                            format!("  {} · {}", target.entity_type.display(), target.detail),
+                            Style::default().fg(theme::TEXT_MUTED),
+                        ),
+                    ])
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(body), rows[1]);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(" ↑/↓", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":move ", theme::key_hint()),
+                Span::styled("Enter", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":link/unlink ", theme::key_hint()),
+                Span::styled("Esc", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":close", theme::key_hint()),
+            ])),
+            rows[2],
+        );
+    }
+}
+
+// ── Free helpers ───────────────────────────────────────────────────────────
+
+fn new_content_area(text: &str) -> TextArea<'static> {
+    let mut ta = TextArea::default();
+    ta.set_cursor_line_style(Style::default());
+    ta.insert_str(text);
+    ta
+}
+
+/// Advance an optional filter through `options`, wrapping back to "all".
+fn cycle<T: PartialEq + Clone>(current: &Option<T>, options: &[T]) -> Option<T> {
+    match current
+        .as_ref()
+        .and_then(|c| options.iter().position(|o| o == c))
+    {
+        Some(i) => options.get(i + 1).cloned(),
+        None => options.first().cloned(),
+    }
+}
+
+/// Split a comma-separated tag list, dropping blanks and duplicates.
+fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(|t| t.trim().trim_start_matches('#')) {
+        if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// File name for a session's exported notes.
+///
+/// Session labels repeat across campaigns ("Session 1"), so the campaign and
+/// session ids are part of the name to keep one export from replacing another.
+fn export_file_name(campaign_id: &str, session_id: &str, label: &str) -> String {
+    let parts = [slugify(campaign_id), slugify(label), slugify(session_id)];
+    let stem: Vec<&str> = parts
+        .iter()
+        .map(String::as_str)
+        .filter(|p| !p.is_empty())
+        .collect();
+    format!("{}-notes.md", stem.join("-"))
+}
+
+fn slugify(s: &str) -> String {
+    let slug: String = s
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    slug.split('-')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn notify(services: &Services, message: &str, level: NotificationLevel) {
+    let _ = services.event_tx.send(AppEvent::Notification(Notification {
+        id: 0,
+        message: message.to_string(),
+        level,
+        ttl_ticks: 120,
+    }));
+}
+
+fn route_text_input(buf: &mut InputBuffer, code: KeyCode, modifiers: KeyModifiers) {
+    match (modifiers, code) {
+        (KeyModifiers::NONE, KeyCode::Char(c)) | (KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+            buf.insert_char(c);
+        }
+        (KeyModifiers::NONE, KeyCode::Backspace) => buf.backspace(),
+        (KeyModifiers::NONE, KeyCode::Delete) => buf.delete(),
+        (KeyModifiers::NONE, KeyCode::Left) => buf.move_left(),
+        (KeyModifiers::NONE, KeyCode::Right) => buf.move_right(),
+        (KeyModifiers::NONE, KeyCode::Home) => buf.move_home(),
+        (KeyModifiers::NONE, KeyCode::End) => buf.move_end(),
+        _ => {}
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        let mut out: String = s.chars().take(max.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    } else {
+        s.to_string()
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_notes() -> NotesViewState {
+        let mut state = NotesViewState::new();
+        state.notes = vec![
+            SessionNote::new("s1", "c", "Goblin ambush", "They hit us at the ford")
+                .with_category(NoteCategory::Combat)
+                .with_tags(["goblins"]),
+            SessionNote::new("s1", "c", "Ferryman", "Wants two silver coins")
+                .with_category(NoteCategory::Character)
+                .with_tags(["npc", "river"]),
+            SessionNote::new("s2", "c", "Map of the marsh", "Hidden shrine to the east")
+                .with_category(NoteCategory::Location)
+                .with_tags(["river"]),
+        ];
+        state.sessions = vec![
+            ("s2".to_string(), "Session 2".to_string()),
+            ("s1".to_string(), "Session 1".to_string()),
+        ];
+        state.refilter();
+        state
+    }
+
+    #[test]
+    fn test_filters_combine() {
+        let mut state = state_with_notes();
+        assert_eq!(state.visible.len(), 3);
+
+        state.tag_filter = Some("river".to_string());
+        state.refilter();
+        assert_eq!(state.visible.len(), 2);
+
+        state.session_filter = Some("s1".to_string());
+        state.refilter();
+        assert_eq!(state.visible, vec![1]);
+
+        state.category_filter = Some(NoteCategory::Combat);
+        state.refilter();
+        assert!(state.visible.is_empty());
+        assert!(state.selected_note().is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_search() {
+        let mut state = state_with_notes();
+        for c in "frymn".chars() {
+            state.search.insert_char(c);
+        }
+        state.refilter();
+        assert_eq!(state.selected_note().unwrap().title, "Ferryman");
+    }
+
+    #[test]
+    fn test_cycle_filter_wraps_to_all() {
+        let options = vec!["a".to_string(), "b".to_string()];
+        let first = cycle(&None, &options);
+        assert_eq!(first.as_deref(), Some("a"));
+        let second = cycle(&first, &options);
+        assert_eq!(second.as_deref(), Some("b"));
+        assert_eq!(cycle(&second, &options), None);
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(
+            parse_tags(" npc, #River ,, river , plot"),
+            vec!["npc".to_string(), "River".to_string(), "plot".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Session 3: The Marsh"), "session-3-the-marsh");
+    }
+
+    #[test]
+    fn test_export_file_name_is_unique_per_session() {
+        assert_eq!(
+            export_file_name("camp-1", "s-1", "Session 1"),
+            "camp-1-session-1-s-1-notes.md"
+        );
+        assert_ne!(
+            export_file_name("camp-1", "s-1", "Session 1"),
+            export_file_name("camp-2", "s-2", "Session 1")
+        );
+        assert_eq!(
+            export_file_name("", "s-1", "Session 1"),
+            "session-1-s-1-notes.md"
+        );
+    }
+}
@@ -0,0 +1,565 @@
+//! NPC memory panel — inspect and edit what an NPC remembers.
+//!
+//! Opened from the NPC view with `m`. Lists the NPC's long-term memories
+//! with their current strength. `p` pins, `f` fades, `e` edits, `a` adds,
+//! `t` toggles fact/impression, `d` deletes, `x` extracts memories from the
+//! stored conversation, `Esc` closes.
+
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+use crate::core::storage::npc_memory::{
+    delete_npc_memory, list_npc_memories, set_npc_memory_pinned, update_npc_memory, NpcMemoryKind,
+    NpcMemoryRecord,
+};
+use crate::database::{ConversationMessage, NpcOps, NpcRecord};
+use crate::tui::services::Services;
+use crate::tui::theme;
+use crate::tui::widgets::input_buffer::InputBuffer;
+
+// ── Internal async data events ─────────────────────────────────────────────
+
+enum MemoryDataEvent {
+    Loaded(Vec<NpcMemoryRecord>),
+    Extracted(usize),
+    Error(String),
+}
+
+/// Memory text being written or edited.
+struct MemoryEditor {
+    /// Memory being edited; `None` when adding
+    id: Option<String>,
+    input: InputBuffer,
+}
+
+// ── State ──────────────────────────────────────────────────────────────────
+
+pub struct NpcMemoryPanel {
+    npc: NpcRecord,
+    memories: Vec<NpcMemoryRecord>,
+    selected: usize,
+    editor: Option<MemoryEditor>,
+    confirm_delete: bool,
+    /// Fade half-life used to display strength
+    half_life_days: f32,
+    loading: bool,
+    status: Option<String>,
+    error: Option<String>,
+    data_tx: mpsc::UnboundedSender<MemoryDataEvent>,
+    data_rx: mpsc::UnboundedReceiver<MemoryDataEvent>,
+}
+
+impl NpcMemoryPanel {
+    pub fn new(npc: NpcRecord, half_life_days: f32) -> Self {
+        let (data_tx, data_rx) = mpsc::unbounded_channel();
+        Self {
+            npc,
+            memories: Vec::new(),
+            selected: 0,
+            editor: None,
+            confirm_delete: false,
+            half_life_days,
+            loading: true,
+            status: None,
+            error: None,
+            data_tx,
+            data_rx,
+        }
+    }
+
+    pub fn load(&self, services: &Services) {
+        let db = services.storage.clone_db();
+        let npc_id = self.npc.id.clone();
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            match list_npc_memories(&db, &npc_id).await {
+                Ok(memories) => {
+                    let _ = tx.send(MemoryDataEvent::Loaded(memories));
+                }
+                Err(e) => {
+                    let _ = tx.send(MemoryDataEvent::Error(e.to_string()));
+                }
+            }
+        });
+    }
+
+    pub fn poll(&mut self) {
+        while let Ok(event) = self.data_rx.try_recv() {
+            match event {
+                MemoryDataEvent::Loaded(memories) => {
+                    self.memories = memories;
+                    self.selected = self.selected.min(self.memories.len().saturating_sub(1));
+                    self.loading = false;
+                    self.error = None;
+                }
+                MemoryDataEvent::Extracted(count) => {
+                    self.status = Some(format!("Extracted {count} new memories"));
+                }
+                MemoryDataEvent::Error(msg) => {
+                    self.loading = false;
+                    self.error = Some(msg);
+                }
+            }
+        }
+    }
+
+    fn current(&self) -> Option<&NpcMemoryRecord> {
+        self.memories.get(self.selected)
+    }
+
+    // ── Input handling ─────────────────────────────────────────────────────
+
+    /// Handle a key press. Returns false when the panel should close.
+    pub fn handle_input(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        services: &Services,
+    ) -> bool {
+        if self.editor.is_some() {
+            self.handle_editor_input(code, modifiers, services);
+            return true;
+        }
+
+        if self.confirm_delete {
+            if matches!(code, KeyCode::Char('y') | KeyCode::Enter) {
+                self.delete_selected(services);
+            }
+            self.confirm_delete = false;
+            return true;
+        }
+
+        self.status = None;
+        match (modifiers, code) {
+            (KeyModifiers::NONE, KeyCode::Esc | KeyCode::Char('q')) => return false,
+            (KeyModifiers::NONE, KeyCode::Char('j') | KeyCode::Down) => {
+                if self.selected + 1 < self.memories.len() {
+                    self.selected += 1;
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('k') | KeyCode::Up) => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            (KeyModifiers::NONE, KeyCode::Char('p')) => self.toggle_pin(services),
+            (KeyModifiers::NONE, KeyCode::Char('f')) => {
+                if let Some(memory) = self.current().cloned() {
+                    let mut faded = memory;
+                    faded.importance /= 2.0;
+                    self.save(faded, services);
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('t')) => {
+                if let Some(memory) = self.current().cloned() {
+                    let mut toggled = memory;
+                    toggled.kind = match toggled.kind {
+                        NpcMemoryKind::Fact => NpcMemoryKind::Impression,
+                        NpcMemoryKind::Impression => NpcMemoryKind::Fact,
+                    };
+                    self.save(toggled, services);
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('e')) => {
+                if let Some(memory) = self.current() {
+                    let mut input = InputBuffer::new();
+                    for c in memory.content.chars() {
+                        input.insert_char(c);
+                    }
+                    self.editor = Some(MemoryEditor {
+                        id: memory.id.clone(),
+                        input,
+                    });
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('a')) => {
+                self.editor = Some(MemoryEditor {
+                    id: None,
+                    input: InputBuffer::new(),
+                });
+            }
+            (KeyModifiers::NONE, KeyCode::Char('d')) => {
+                self.confirm_delete = self.current().is_some();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('x')) => self.extract_from_history(services),
+            (KeyModifiers::NONE, KeyCode::Char('r')) => self.load(services),
+            _ => {}
+        }
+        true
+    }
+
+    fn handle_editor_input(&mut self, code: KeyCode, modifiers: KeyModifiers, services: &Services) {
+        let Some(editor) = self.editor.as_mut() else {
+            return;
+        };
+        match (modifiers, code) {
+            (_, KeyCode::Esc) => self.editor = None,
+            (_, KeyCode::Enter) => {
+                let Some(editor) = self.editor.take() else {
+                    return;
+                };
+                let text = editor.input.text().trim().to_string();
+                if text.is_empty() {
+                    return;
+                }
+                match editor.id {
+                    Some(id) => {
+                        if let Some(mut memory) = self
+                            .memories
+                            .iter()
+                            .find(|m| m.id.as_ref() == Some(&id))
+                            .cloned()
+                        {
+                            memory.content = text;
+                            self.save(memory, services);
+                        }
+                    }
+                    None => self.add(text, services),
+                }
+            }
+            (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+                editor.input.insert_char(c)
+            }
+            (_, KeyCode::Backspace) => editor.input.backspace(),
+            (_, KeyCode::Delete) => editor.input.delete(),
+            (_, KeyCode::Left) => editor.input.move_left(),
+            (_, KeyCode::Right) => editor.input.move_right(),
+            (_, KeyCode::Home) => editor.input.move_home(),
+            (_, KeyCode::End) => editor.input.move_end(),
+            _ => {}
+        }
+    }
+
+    // ── Mutations ──────────────────────────────────────────────────────────
+
+    /// Spawn `op`, then reload the list.
+    fn mutate<F>(&self, services: &Services, op: F)
+    where
+        F: std::future::Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let db = services.storage.clone_db();
+        let npc_id = self.npc.id.clone();
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = op.await {
+                let _ = tx.send(MemoryDataEvent::Error(e));
+            }
+            match list_npc_memories(&db, &npc_id).await {
+                Ok(memories) => {
+                    let _ = tx.send(MemoryDataEvent::Loaded(memories));
+                }
+                Err(e) => {
+                    let _ = tx.send(MemoryDataEvent::Error(e.to_string()));
+                }
+            }
+        });
+    }
+
+    fn toggle_pin(&mut self, services: &Services) {
+        let Some(memory) = self.current() else {
+            return;
+        };
+        let Some(id) = memory.id.clone() else {
+            return;
+        };
+        let pinned = !memory.pinned;
+        let db = services.storage.clone_db();
+        self.mutate(services, async move {
+            set_npc_memory_pinned(&db, &id, pinned)
+                .await
+                .map_err(|e| e.to_string())
+        });
+    }
+
+    /// Save importance/kind changes in place, or re-embed edited text.
+    fn save(&mut self, memory: NpcMemoryRecord, services: &Services) {
+        let Some(id) = memory.id.clone() else {
+            return;
+        };
+        let text_changed = self
+            .memories
+            .iter()
+            .find(|m| m.id.as_ref() == Some(&id))
+            .is_some_and(|m| m.content != memory.content);
+
+        if text_changed {
+            let manager = services.npc_memory.clone();
+            self.mutate(services, async move {
+                manager.edit(&id, memory).await.map_err(|e| e.to_string())
+            });
+        } else {
+            let db = services.storage.clone_db();
+            self.mutate(services, async move {
+                update_npc_memory(&db, &id, &memory)
+                    .await
+                    .map_err(|e| e.to_string())
+            });
+        }
+    }
+
+    fn add(&mut self, content: String, services: &Services) {
+        let mut memory =
+            NpcMemoryRecord::new(self.npc.id.clone(), NpcMemoryKind::Fact, content, 0.7);
+        memory.campaign_id = self.npc.campaign_id.clone();
+        let manager = services.npc_memory.clone();
+        self.mutate(services, async move {
+            manager
+                .add(memory)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        });
+    }
+
+    fn delete_selected(&mut self, services: &Services) {
+        let Some(id) = self.current().and_then(|m| m.id.clone()) else {
+            return;
+        };
+        let db = services.storage.clone_db();
+        self.mutate(services, async move {
+            delete_npc_memory(&db, &id).await.map_err(|e| e.to_string())
+        });
+    }
+
+    /// Run extraction over the NPC's whole stored conversation.
+    fn extract_from_history(&mut self, services: &Services) {
+        self.status = Some("Extracting memories from conversation...".to_string());
+        let database = services.database.clone();
+        let manager = services.npc_memory.clone();
+        let npc = self.npc.clone();
+        let tx = self.data_tx.clone();
+        self.mutate(services, async move {
+            let conversation = database
+                .get_npc_conversation(&npc.id)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("{} has no conversation yet", npc.name))?;
+            let transcript: Vec<ConversationMessage> =
+                serde_json::from_str(&conversation.messages_json).unwrap_or_default();
+            let count = manager
+                .remember(
+                    &npc.id,
+                    &npc.name,
+                    npc.campaign_id.as_deref(),
+                    Some(&conversation.id),
+                    &transcript,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+            let _ = tx.send(MemoryDataEvent::Extracted(count));
+            Ok(())
+        });
+    }
+
+    // ── Rendering ──────────────────────────────────────────────────────────
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title(format!(
+                " Memories — {} ({}) ",
+                self.npc.name,
+                self.memories.len()
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::NPC));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(1), // Editor / status
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+        self.render_list(frame, chunks[0]);
+        self.render_status(frame, chunks[1]);
+
+        let hints = if self.editor.is_some() {
+            vec![
+                Span::styled(" Enter", theme::key_hint()),
+                Span::styled(":save ", Style::default().fg(theme::TEXT_DIM)),
+                Span::styled("Esc", theme::key_hint()),
+                Span::styled(":cancel", Style::default().fg(theme::TEXT_DIM)),
+            ]
+        } else {
+            let mut spans = Vec::new();
+            for (key, label) in [
+                ("p", "pin"),
+                ("f", "fade"),
+                ("e", "edit"),
+                ("a", "add"),
+                ("t", "type"),
+                ("d", "delete"),
+                ("x", "extract"),
+                ("Esc", "close"),
+            ] {
+                spans.push(Span::styled(format!(" {key}"), theme::key_hint()));
+                spans.push(Span::styled(
+                    format!(":{label}"),
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+            spans
+        };
+        frame.render_widget(Paragraph::new(Line::from(hints)), chunks[2]);
+    }
+
+    fn render_list(&self, frame: &mut Frame, area: Rect) {
+        let half_life_days = self.half_life_days;
+        if self.memories.is_empty() {
+            let message = if self.loading {
+                "  Loading..."
+            } else {
+                "  No memories yet. Talk to this NPC in chat, or press x to extract from past conversations."
+            };
+            frame.render_widget(
+                Paragraph::new(Line::styled(
+                    message,
+                    Style::default().fg(theme::TEXT_MUTED),
+                )),
+                area,
+            );
+            return;
+        }
+
+        let now = Utc::now();
+        let visible = area.height as usize;
+        let start = self.selected.saturating_sub(visible.saturating_sub(1));
+        let width = area.width as usize;
+
+        let lines: Vec<Line> = self
+            .memories
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible)
+            .map(|(i, memory)| {
+                let strength = memory.strength(now, half_life_days);
+                let (strength_label, strength_color) = if memory.pinned {
+                    ("pinned".to_string(), theme::ACCENT)
+                } else if memory.is_forgotten(now, half_life_days) {
+                    ("faded".to_string(), theme::TEXT_DIM)
+                } else if strength >= 0.5 {
+                    (format!("{:>3.0}%", strength * 100.0), theme::SUCCESS)
+                } else {
+                    (format!("{:>3.0}%", strength * 100.0), theme::WARNING)
+                };
+                let kind_color = match memory.kind {
+                    NpcMemoryKind::Fact => theme::INFO,
+                    NpcMemoryKind::Impression => theme::NPC,
+                };
+                let marker = if i == self.selected { "▸ " } else { "  " };
+                let prefix_len = 2 + 7 + 12;
+                let content: String = memory
+                    .content
+                    .chars()
+                    .take(width.saturating_sub(prefix_len))
+                    .collect();
+
+                let mut line = Line::from(vec![
+                    Span::styled(marker, Style::default().fg(theme::ACCENT)),
+                    Span::styled(
+                        format!("{strength_label:<7}"),
+                        Style::default().fg(strength_color),
+                    ),
+                    Span::styled(
+                        format!("{:<12}", memory.kind.label()),
+                        Style::default().fg(kind_color),
+                    ),
+                    Span::styled(content, Style::default().fg(theme::TEXT)),
+                ]);
+                if i == self.selected {
+                    line = line.style(Style::default().add_modifier(Modifier::BOLD));
+                }
+                line
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn render_status(&self, frame: &mut Frame, area: Rect) {
+        let line = if let Some(ref editor) = self.editor {
+            let label = if editor.id.is_some() {
+                " Edit: "
+            } else {
+                " New: "
+            };
+            Line::from(vec![
+                Span::styled(label, Style::default().fg(theme::ACCENT)),
+                Span::styled(
+                    editor.input.text().to_string(),
+                    Style::default().fg(theme::TEXT),
+                ),
+            ])
+        } else if self.confirm_delete {
+            Line::styled(
+                " Delete this memory? (y/n)",
+                Style::default().fg(theme::WARNING),
+            )
+        } else if let Some(ref err) = self.error {
+            Line::styled(format!(" {err}"), Style::default().fg(theme::ERROR))
+        } else if let Some(ref status) = self.status {
+            Line::styled(format!(" {status}"), Style::default().fg(theme::TEXT_MUTED))
+        } else {
+            Line::raw("")
+        };
+        frame.render_widget(Paragraph::new(line), area);
+
+        if let Some(ref editor) = self.editor {
+            let label_len = if editor.id.is_some() { 7 } else { 6 };
+            frame.set_cursor_position((
+                area.x + label_len + editor.input.cursor_position() as u16,
+                area.y,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn panel_with(memories: Vec<NpcMemoryRecord>) -> NpcMemoryPanel {
+        let mut panel = NpcMemoryPanel::new(
+            NpcRecord::new("npc-1".into(), "Mira".into(), "Innkeeper".into()),
+            30.0,
+        );
+        panel
+            .data_tx
+            .send(MemoryDataEvent::Loaded(memories))
+            .unwrap();
+        panel.poll();
+        panel
+    }
+
+    #[test]
+    fn test_poll_loads_and_clamps_selection() {
+        let mut panel = panel_with(vec![
+            NpcMemoryRecord::new("npc-1", NpcMemoryKind::Fact, "a", 0.5),
+            NpcMemoryRecord::new("npc-1", NpcMemoryKind::Fact, "b", 0.5),
+        ]);
+        assert!(!panel.loading);
+        panel.selected = 1;
+
+        panel
+            .data_tx
+            .send(MemoryDataEvent::Loaded(vec![NpcMemoryRecord::new(
+                "npc-1",
+                NpcMemoryKind::Fact,
+                "a",
+                0.5,
+            )]))
+            .unwrap();
+        panel.poll();
+        assert_eq!(panel.selected, 0);
+        assert_eq!(panel.current().map(|m| m.content.as_str()), Some("a"));
+    }
+}
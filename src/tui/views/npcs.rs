@@ -4,6 +4,7 @@
 //! Press `a` to add, `e` to edit, `d` to delete, `Enter` toggles detail panel.
 //! Press `/` to filter by name, `Ctrl+G` to generate a random name (in form).
 //! Press `v` for voice mode, `t` for talk-about mode.
+//! Press `m` to inspect and edit what the selected NPC remembers.
 
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
@@ -22,6 +23,8 @@ use crate::tui::services::Services;
 use crate::tui::theme;
 use crate::tui::widgets::input_buffer::InputBuffer;
 
+use super::npc_memory::NpcMemoryPanel;
+
 // ── Internal async data events ─────────────────────────────────────────────
 
 enum NpcDataEvent {
@@ -72,6 +75,9 @@ pub struct NpcViewState {
     // Name generation
     name_gen: NameGenerator,
 
+    // Long-term memory panel for the selected NPC
+    memory_panel: Option<NpcMemoryPanel>,
+
     // Error/status
     error: Option<String>,
 
@@ -96,6 +102,7 @@ impl NpcViewState {
             form_notes: InputBuffer::new(),
             editing_id: None,
             name_gen: NameGenerator::new(),
+            memory_panel: None,
             error: None,
             data_tx,
             data_rx,
@@ -114,6 +121,9 @@ impl NpcViewState {
     }
 
     pub fn poll(&mut self) {
+        if let Some(ref mut panel) = self.memory_panel {
+            panel.poll();
+        }
         while let Ok(event) = self.data_rx.try_recv() {
             match event {
                 NpcDataEvent::NpcsLoaded(npcs) => {
@@ -164,6 +174,13 @@ impl NpcViewState {
             return self.handle_modal_input(modal, *code, *modifiers, services);
         }
 
+        if let Some(ref mut panel) = self.memory_panel {
+            if !panel.handle_input(*code, *modifiers, services) {
+                self.memory_panel = None;
+            }
+            return true;
+        }
+
         // When filter is active, route input to filter first
         if self.filter_active {
             return self.handle_filter_input(*code, *modifiers, services);
@@ -218,6 +235,17 @@ impl NpcViewState {
                 self.load(services);
                 true
             }
+            (KeyModifiers::NONE, KeyCode::Char('m')) => {
+                if let Some(npc) = self.npcs.get(self.selected) {
+                    let panel = NpcMemoryPanel::new(
+                        npc.clone(),
+                        services.npc_memory.config().half_life_days,
+                    );
+                    panel.load(services);
+                    self.memory_panel = Some(panel);
+                }
+                true
+            }
             _ => false,
         }
     }
@@ -433,7 +461,14 @@ impl NpcViewState {
     // ── Rendering ──────────────────────────────────────────────────────────
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
-        if self.show_detail && !self.npcs.is_empty() {
+        if let Some(ref panel) = self.memory_panel {
+            let chunks = Layout::horizontal([
+                Constraint::Percentage(30),
+                Constraint::Percentage(70),
+            ]).split(area);
+            self.render_list(frame, chunks[0]);
+            panel.render(frame, chunks[1]);
+        } else if self.show_detail && !self.npcs.is_empty() {
             let chunks = Layout::horizontal([
                 Constraint::Percentage(40),
                 Constraint::Percentage(60),
@@ -0,0 +1,1426 @@
+//! Relationship graph view — NPC, faction and location relationships of a campaign.
+//!
+//! Entities on the left, the graph (via `AsciiGraph`) on the right, and the
+//! selected entity's relationships underneath. Relationships live in the
+//! `entity_relationships` table and are mirrored into the shared
+//! `RelationshipManager`, which builds the graphs.
+//! Enter makes the selected entity the focal point (tree around it, `+`/`-`
+//! change the depth), `f` clears the focus. `t` filters by relationship type,
+//! `p` toggles the player view (secret relationships hidden), `i` shows ended
+//! ones. Tab switches to the relationship list: `a` add, `e` edit, `d` delete.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+use crate::core::campaign::relationships::{
+    EntityGraph, EntityRelationship, EntityType, GraphFilter, RelationshipManager,
+    RelationshipStrength, RelationshipType,
+};
+use crate::database::{CampaignOps, CampaignRecord, LocationOps, NpcOps, RelationshipOps};
+use crate::tui::app::centered_rect;
+use crate::tui::services::Services;
+use crate::tui::theme;
+use crate::tui::widgets::ascii_graph::{self, AsciiGraph, GraphLayout, NodeType};
+use crate::tui::widgets::input_buffer::InputBuffer;
+
+const MAX_DEPTH: usize = 5;
+
+const STRENGTHS: [RelationshipStrength; 4] = [
+    RelationshipStrength::Weak,
+    RelationshipStrength::Moderate,
+    RelationshipStrength::Strong,
+    RelationshipStrength::Unbreakable,
+];
+
+// ── Internal async data events ─────────────────────────────────────────────
+
+enum GraphDataEvent {
+    Loaded {
+        campaigns: Vec<CampaignRecord>,
+        campaign_id: Option<String>,
+        entities: Vec<Entity>,
+    },
+    Saved,
+    Error(String),
+}
+
+/// Something that can take part in a relationship.
+#[derive(Debug, Clone)]
+struct Entity {
+    id: String,
+    name: String,
+    entity_type: EntityType,
+}
+
+// ── Modal / pane types ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Entities,
+    Relationships,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum GraphModal {
+    /// Add a relationship from the selected entity
+    Add,
+    /// Edit the relationship with this ID
+    Edit(String),
+    /// Confirm deletion of the relationship with this ID
+    Delete(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormField {
+    Target,
+    TargetType,
+    Type,
+    Strength,
+    Secret,
+    Active,
+    Description,
+}
+
+/// Fields shown when adding; the endpoints of an existing relationship are fixed.
+const ADD_FIELDS: [FormField; 6] = [
+    FormField::Target,
+    FormField::TargetType,
+    FormField::Type,
+    FormField::Strength,
+    FormField::Secret,
+    FormField::Description,
+];
+const EDIT_FIELDS: [FormField; 5] = [
+    FormField::Type,
+    FormField::Strength,
+    FormField::Secret,
+    FormField::Active,
+    FormField::Description,
+];
+
+// ── State ──────────────────────────────────────────────────────────────────
+
+pub struct RelationshipsViewState {
+    campaigns: Vec<CampaignRecord>,
+    campaign_id: Option<String>,
+    /// Shared manager; replaced by the one in `Services` on first load
+    manager: Arc<RelationshipManager>,
+
+    // Entities (NPCs, locations and anything already in a relationship)
+    entities: Vec<Entity>,
+    entity_cursor: usize,
+    pane: Pane,
+
+    // Graph options
+    focus: Option<String>,
+    depth: usize,
+    type_filter: Option<RelationshipType>,
+    player_view: bool,
+    include_inactive: bool,
+
+    // Derived from the manager by `refresh`
+    graph: EntityGraph,
+    entity_relationships: Vec<EntityRelationship>,
+    rel_cursor: usize,
+    graph_scroll: usize,
+
+    // Modal
+    modal: Option<GraphModal>,
+    form_focus: usize,
+    form_target: InputBuffer,
+    form_suggestion: usize,
+    form_target_type: usize,
+    form_type: usize,
+    form_strength: usize,
+    form_secret: bool,
+    form_active: bool,
+    form_description: InputBuffer,
+
+    loading: bool,
+    error: Option<String>,
+
+    // Async channel
+    data_tx: mpsc::UnboundedSender<GraphDataEvent>,
+    data_rx: mpsc::UnboundedReceiver<GraphDataEvent>,
+}
+
+impl RelationshipsViewState {
+    pub fn new() -> Self {
+        let (data_tx, data_rx) = mpsc::unbounded_channel();
+        Self {
+            campaigns: Vec::new(),
+            campaign_id: None,
+            manager: Arc::default(),
+            entities: Vec::new(),
+            entity_cursor: 0,
+            pane: Pane::Entities,
+            focus: None,
+            depth: 2,
+            type_filter: None,
+            player_view: false,
+            include_inactive: false,
+            graph: EntityGraph {
+                nodes: vec![],
+                edges: vec![],
+                stats: Default::default(),
+            },
+            entity_relationships: Vec::new(),
+            rel_cursor: 0,
+            graph_scroll: 0,
+            modal: None,
+            form_focus: 0,
+            form_target: InputBuffer::new(),
+            form_suggestion: 0,
+            // Faction: the common entity without its own table
+            form_target_type: 3,
+            form_type: 0,
+            form_strength: 1,
+            form_secret: false,
+            form_active: true,
+            form_description: InputBuffer::new(),
+            loading: false,
+            error: None,
+            data_tx,
+            data_rx,
+        }
+    }
+
+    /// Load campaigns, then the selected campaign's entities and relationships.
+    pub fn load(&mut self, services: &Services) {
+        self.manager = services.relationship_manager.clone();
+        if self.loading {
+            return;
+        }
+        self.loading = true;
+
+        let db = services.database.clone();
+        let manager = self.manager.clone();
+        let preferred = self.campaign_id.clone();
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            let campaigns: Vec<CampaignRecord> = match db.list_campaigns().await {
+                Ok(records) => records
+                    .into_iter()
+                    .filter(|r| r.archived_at.is_none())
+                    .collect(),
+                Err(e) => {
+                    let _ = tx.send(GraphDataEvent::Error(format!(
+                        "Failed to load campaigns: {e}"
+                    )));
+                    return;
+                }
+            };
+            let campaign_id = preferred
+                .filter(|id| campaigns.iter().any(|c| &c.id == id))
+                .or_else(|| campaigns.first().map(|c| c.id.clone()));
+
+            let mut entities = Vec::new();
+            if let Some(ref campaign_id) = campaign_id {
+                let records = match db.list_campaign_relationships(campaign_id).await {
+                    Ok(records) => records,
+                    Err(e) => {
+                        let _ = tx.send(GraphDataEvent::Error(format!(
+                            "Failed to load relationships: {e}"
+                        )));
+                        return;
+                    }
+                };
+                manager.replace_relationships(
+                    campaign_id,
+                    records
+                        .iter()
+                        .map(EntityRelationship::from_record)
+                        .collect(),
+                );
+
+                let npcs = db
+                    .list_npcs(Some(campaign_id.as_str()))
+                    .await
+                    .unwrap_or_default();
+                let locations = db.list_locations(campaign_id).await.unwrap_or_default();
+                entities.extend(npcs.into_iter().map(|npc| Entity {
+                    id: npc.id,
+                    name: npc.name,
+                    entity_type: EntityType::NPC,
+                }));
+                entities.extend(locations.into_iter().map(|loc| Entity {
+                    id: loc.id,
+                    name: loc.name,
+                    entity_type: EntityType::Location,
+                }));
+            }
+
+            let _ = tx.send(GraphDataEvent::Loaded {
+                campaigns,
+                campaign_id,
+                entities,
+            });
+        });
+    }
+
+    pub fn poll(&mut self) {
+        while let Ok(event) = self.data_rx.try_recv() {
+            match event {
+                GraphDataEvent::Loaded {
+                    campaigns,
+                    campaign_id,
+                    entities,
+                } => {
+                    self.loading = false;
+                    if campaign_id != self.campaign_id {
+                        self.focus = None;
+                        self.type_filter = None;
+                        self.entity_cursor = 0;
+                    }
+                    self.campaigns = campaigns;
+                    self.campaign_id = campaign_id;
+                    self.entities = entities;
+                    self.merge_graph_entities();
+                    self.refresh();
+                }
+                GraphDataEvent::Saved => {}
+                GraphDataEvent::Error(msg) => {
+                    self.loading = false;
+                    self.error = Some(msg);
+                }
+            }
+        }
+    }
+
+    // ── Derived data ───────────────────────────────────────────────────────
+
+    fn campaign_name(&self) -> &str {
+        self.campaign_id
+            .as_ref()
+            .and_then(|id| self.campaigns.iter().find(|c| &c.id == id))
+            .map(|c| c.name.as_str())
+            .unwrap_or("—")
+    }
+
+    fn selected_entity(&self) -> Option<&Entity> {
+        self.entities.get(self.entity_cursor)
+    }
+
+    fn entity_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.entities
+            .iter()
+            .find(|e| e.id == id)
+            .map(|e| e.name.as_str())
+            .unwrap_or(id)
+    }
+
+    fn filter(&self) -> GraphFilter {
+        GraphFilter {
+            focus: self.focus.clone(),
+            depth: self.depth,
+            relationship_types: self.type_filter.iter().cloned().collect(),
+            hide_secret: self.player_view,
+            include_inactive: self.include_inactive,
+        }
+    }
+
+    /// Add entities that only exist in relationships (factions, items, ...)
+    /// and keep the list sorted by name.
+    fn merge_graph_entities(&mut self) {
+        let Some(ref campaign_id) = self.campaign_id else {
+            return;
+        };
+        let selected = self.selected_entity().map(|e| e.id.clone());
+        let known: HashSet<String> = self.entities.iter().map(|e| e.id.clone()).collect();
+        let full = self.manager.get_entity_graph(campaign_id, true);
+        self.entities.extend(
+            full.nodes
+                .into_iter()
+                .filter(|n| !known.contains(&n.id))
+                .map(|n| Entity {
+                    id: n.id,
+                    name: n.name,
+                    entity_type: n.entity_type,
+                }),
+        );
+        self.entities
+            .sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        if let Some(id) = selected {
+            if let Some(pos) = self.entities.iter().position(|e| e.id == id) {
+                self.entity_cursor = pos;
+            }
+        }
+        self.entity_cursor = self
+            .entity_cursor
+            .min(self.entities.len().saturating_sub(1));
+    }
+
+    /// Rebuild the graph and the selected entity's relationship list.
+    fn refresh(&mut self) {
+        let Some(ref campaign_id) = self.campaign_id else {
+            return;
+        };
+        let filter = self.filter();
+        self.graph = self.manager.get_filtered_graph(campaign_id, &filter);
+        self.entity_relationships = match self.selected_entity() {
+            Some(entity) => self
+                .manager
+                .get_entity_relationships(campaign_id, &entity.id)
+                .into_iter()
+                .filter(|r| filter.matches(r))
+                .collect(),
+            None => Vec::new(),
+        };
+        self.rel_cursor = self
+            .rel_cursor
+            .min(self.entity_relationships.len().saturating_sub(1));
+    }
+
+    /// Relationship types present in the campaign, for the type filter.
+    fn present_types(&self) -> Vec<RelationshipType> {
+        let Some(ref campaign_id) = self.campaign_id else {
+            return Vec::new();
+        };
+        let mut types: Vec<RelationshipType> = Vec::new();
+        for summary in self.manager.list_relationships(campaign_id) {
+            if !types.contains(&summary.relationship_type) {
+                types.push(summary.relationship_type);
+            }
+        }
+        types.sort_by_key(|t| t.to_string());
+        types
+    }
+
+    // ── Mutations ──────────────────────────────────────────────────────────
+
+    fn persist(&self, services: &Services, relationship: &EntityRelationship) {
+        let record = relationship.to_record();
+        let db = services.database.clone();
+        let tx = self.data_tx.clone();
+        tokio::spawn(async move {
+            let event = match db.save_entity_relationship(&record).await {
+                Ok(()) => GraphDataEvent::Saved,
+                Err(e) => GraphDataEvent::Error(format!("Failed to save relationship: {e}")),
+            };
+            let _ = tx.send(event);
+        });
+    }
+
+    fn delete_relationship(&mut self, services: &Services, id: &str) {
+        let Some(campaign_id) = self.campaign_id.clone() else {
+            return;
+        };
+        if let Err(e) = self.manager.delete_relationship(&campaign_id, id) {
+            self.error = Some(e.to_string());
+            return;
+        }
+        let db = services.database.clone();
+        let tx = self.data_tx.clone();
+        let id = id.to_string();
+        tokio::spawn(async move {
+            let event = match db.delete_entity_relationship(&id).await {
+                Ok(()) => GraphDataEvent::Saved,
+                Err(e) => GraphDataEvent::Error(format!("Failed to delete relationship: {e}")),
+            };
+            let _ = tx.send(event);
+        });
+        self.refresh();
+    }
+
+    // ── Input handling ─────────────────────────────────────────────────────
+
+    pub fn handle_input(&mut self, event: &Event, services: &Services) -> bool {
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        else {
+            return false;
+        };
+
+        if let Some(modal) = self.modal.clone() {
+            match modal {
+                GraphModal::Add | GraphModal::Edit(_) => {
+                    self.handle_form_input(*code, *modifiers, services)
+                }
+                GraphModal::Delete(id) => match code {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        self.delete_relationship(services, &id);
+                        self.modal = None;
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => self.modal = None,
+                    _ => {}
+                },
+            }
+            return true;
+        }
+
+        match (*modifiers, *code) {
+            (KeyModifiers::NONE, KeyCode::Tab) => {
+                self.pane = match self.pane {
+                    Pane::Entities => Pane::Relationships,
+                    Pane::Relationships => Pane::Entities,
+                };
+            }
+            (KeyModifiers::NONE, KeyCode::Char('j') | KeyCode::Down) => match self.pane {
+                Pane::Entities => {
+                    if self.entity_cursor + 1 < self.entities.len() {
+                        self.entity_cursor += 1;
+                        self.rel_cursor = 0;
+                        self.refresh();
+                    }
+                }
+                Pane::Relationships => {
+                    if self.rel_cursor + 1 < self.entity_relationships.len() {
+                        self.rel_cursor += 1;
+                    }
+                }
+            },
+            (KeyModifiers::NONE, KeyCode::Char('k') | KeyCode::Up) => match self.pane {
+                Pane::Entities => {
+                    if self.entity_cursor > 0 {
+                        self.entity_cursor -= 1;
+                        self.rel_cursor = 0;
+                        self.refresh();
+                    }
+                }
+                Pane::Relationships => self.rel_cursor = self.rel_cursor.saturating_sub(1),
+            },
+            (KeyModifiers::NONE, KeyCode::PageDown) => self.graph_scroll += 10,
+            (KeyModifiers::NONE, KeyCode::PageUp) => {
+                self.graph_scroll = self.graph_scroll.saturating_sub(10)
+            }
+            (KeyModifiers::NONE, KeyCode::Enter) => {
+                if let Some(entity) = self.selected_entity() {
+                    self.focus = Some(entity.id.clone());
+                    self.graph_scroll = 0;
+                    self.refresh();
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('f')) => {
+                self.focus = None;
+                self.graph_scroll = 0;
+                self.refresh();
+            }
+            (_, KeyCode::Char('+')) => {
+                self.depth = (self.depth + 1).min(MAX_DEPTH);
+                self.refresh();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('-')) => {
+                self.depth = self.depth.saturating_sub(1).max(1);
+                self.refresh();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('t')) => {
+                let types = self.present_types();
+                self.type_filter = match self.type_filter {
+                    None => types.first().cloned(),
+                    Some(ref current) => types
+                        .iter()
+                        .position(|t| t == current)
+                        .and_then(|i| types.get(i + 1))
+                        .cloned(),
+                };
+                self.refresh();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('p')) => {
+                self.player_view = !self.player_view;
+                self.refresh();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('i')) => {
+                self.include_inactive = !self.include_inactive;
+                self.refresh();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('a')) => {
+                if self.selected_entity().is_some() {
+                    self.open_add_form();
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('e')) => {
+                if let Some(rel) = self.entity_relationships.get(self.rel_cursor).cloned() {
+                    self.open_edit_form(&rel);
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('d')) => {
+                if let Some(rel) = self.entity_relationships.get(self.rel_cursor) {
+                    self.modal = Some(GraphModal::Delete(rel.id.clone()));
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('s')) => self.cycle_campaign(services),
+            (KeyModifiers::NONE, KeyCode::Char('r')) => self.load(services),
+            _ => return false,
+        }
+        true
+    }
+
+    fn cycle_campaign(&mut self, services: &Services) {
+        if self.campaigns.len() < 2 {
+            return;
+        }
+        let current = self
+            .campaign_id
+            .as_ref()
+            .and_then(|id| self.campaigns.iter().position(|c| &c.id == id))
+            .unwrap_or(0);
+        self.campaign_id = Some(
+            self.campaigns[(current + 1) % self.campaigns.len()]
+                .id
+                .clone(),
+        );
+        self.error = None;
+        self.load(services);
+    }
+
+    // ── Form ───────────────────────────────────────────────────────────────
+
+    fn form_fields(&self) -> &'static [FormField] {
+        match self.modal {
+            Some(GraphModal::Edit(_)) => &EDIT_FIELDS,
+            _ => &ADD_FIELDS,
+        }
+    }
+
+    fn open_add_form(&mut self) {
+        self.modal = Some(GraphModal::Add);
+        self.form_focus = 0;
+        self.form_target.clear();
+        self.form_suggestion = 0;
+        self.form_type = 0;
+        self.form_strength = 1;
+        self.form_secret = false;
+        self.form_active = true;
+        self.form_description.clear();
+        self.error = None;
+    }
+
+    fn open_edit_form(&mut self, rel: &EntityRelationship) {
+        self.modal = Some(GraphModal::Edit(rel.id.clone()));
+        self.form_focus = 0;
+        self.form_type = RelationshipType::ALL
+            .iter()
+            .position(|t| *t == rel.relationship_type)
+            .unwrap_or(0);
+        self.form_strength = STRENGTHS
+            .iter()
+            .position(|s| s.value() >= rel.strength.value())
+            .unwrap_or(STRENGTHS.len() - 1);
+        self.form_secret = !rel.is_known;
+        self.form_active = rel.is_active;
+        self.form_description.set_text(&rel.description);
+        self.error = None;
+    }
+
+    /// Existing entities whose name contains the target text.
+    fn target_suggestions(&self) -> Vec<&Entity> {
+        let query = self.form_target.text().trim().to_lowercase();
+        let source = self.selected_entity().map(|e| e.id.as_str());
+        self.entities
+            .iter()
+            .filter(|e| Some(e.id.as_str()) != source)
+            .filter(|e| query.is_empty() || e.name.to_lowercase().contains(&query))
+            .take(5)
+            .collect()
+    }
+
+    /// The target's entity if the name matches one exactly, otherwise a new
+    /// entity of the chosen type.
+    fn resolve_target(&self) -> Option<Entity> {
+        let name = self.form_target.text().trim();
+        if name.is_empty() {
+            return None;
+        }
+        let existing = self
+            .entities
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(name))
+            .cloned();
+        existing.or_else(|| {
+            let entity_type = EntityType::ALL[self.form_target_type].clone();
+            Some(Entity {
+                id: format!("{}-{}", entity_type.key(), uuid::Uuid::new_v4()),
+                name: name.to_string(),
+                entity_type,
+            })
+        })
+    }
+
+    fn handle_form_input(&mut self, code: KeyCode, modifiers: KeyModifiers, services: &Services) {
+        let fields = self.form_fields();
+        match (modifiers, code) {
+            (KeyModifiers::NONE, KeyCode::Esc) => {
+                self.modal = None;
+                self.error = None;
+                return;
+            }
+            (KeyModifiers::NONE, KeyCode::Enter) => {
+                self.save_form(services);
+                return;
+            }
+            (KeyModifiers::NONE, KeyCode::Tab) => {
+                self.form_focus = (self.form_focus + 1) % fields.len();
+                return;
+            }
+            (KeyModifiers::SHIFT, KeyCode::BackTab) => {
+                self.form_focus = (self.form_focus + fields.len() - 1) % fields.len();
+                return;
+            }
+            _ => {}
+        }
+
+        let cycle = |index: &mut usize, len: usize| match code {
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => *index = (*index + 1) % len,
+            KeyCode::Left | KeyCode::Char('h') => *index = (*index + len - 1) % len,
+            _ => {}
+        };
+        match fields[self.form_focus] {
+            FormField::Target => match code {
+                // Pick a suggestion; it fills in the exact name
+                KeyCode::Up | KeyCode::Down => {
+                    let names: Vec<String> = self
+                        .target_suggestions()
+                        .iter()
+                        .map(|e| e.name.clone())
+                        .collect();
+                    if !names.is_empty() {
+                        self.form_suggestion = if code == KeyCode::Down {
+                            (self.form_suggestion + 1) % names.len()
+                        } else {
+                            (self.form_suggestion + names.len() - 1) % names.len()
+                        };
+                        self.form_target.set_text(&names[self.form_suggestion]);
+                    }
+                }
+                _ => {
+                    route_text_input(&mut self.form_target, code, modifiers);
+                    self.form_suggestion = 0;
+                }
+            },
+            FormField::TargetType => cycle(&mut self.form_target_type, EntityType::ALL.len()),
+            FormField::Type => cycle(&mut self.form_type, RelationshipType::ALL.len()),
+            FormField::Strength => cycle(&mut self.form_strength, STRENGTHS.len()),
+            FormField::Secret => {
+                if matches!(code, KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right) {
+                    self.form_secret = !self.form_secret;
+                }
+            }
+            FormField::Active => {
+                if matches!(code, KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right) {
+                    self.form_active = !self.form_active;
+                }
+            }
+            FormField::Description => route_text_input(&mut self.form_description, code, modifiers),
+        }
+    }
+
+    fn save_form(&mut self, services: &Services) {
+        let Some(campaign_id) = self.campaign_id.clone() else {
+            return;
+        };
+        let relationship_type = RelationshipType::ALL[self.form_type].clone();
+        let strength = STRENGTHS[self.form_strength].clone();
+        let description = self.form_description.text().trim().to_string();
+
+        let saved = match self.modal.clone() {
+            Some(GraphModal::Edit(id)) => {
+                let Some(mut rel) = self.manager.get_relationship(&campaign_id, &id) else {
+                    self.error = Some("Relationship no longer exists.".to_string());
+                    return;
+                };
+                rel.relationship_type = relationship_type;
+                rel.strength = strength;
+                rel.is_known = !self.form_secret;
+                rel.is_active = self.form_active;
+                rel.description = description;
+                rel.updated_at = chrono::Utc::now();
+                self.manager.update_relationship(rel.clone()).map(|_| rel)
+            }
+            _ => {
+                let Some(source) = self.selected_entity().cloned() else {
+                    return;
+                };
+                let Some(target) = self.resolve_target() else {
+                    self.error = Some("Target is required.".to_string());
+                    return;
+                };
+                let mut rel = EntityRelationship::new(
+                    &campaign_id,
+                    &source.id,
+                    source.entity_type,
+                    &source.name,
+                    &target.id,
+                    target.entity_type,
+                    &target.name,
+                    relationship_type,
+                )
+                .with_strength(strength)
+                .with_description(&description);
+                rel.is_known = !self.form_secret;
+                self.manager.create_relationship(rel)
+            }
+        };
+
+        match saved {
+            Ok(rel) => {
+                self.persist(services, &rel);
+                self.modal = None;
+                self.error = None;
+                self.merge_graph_entities();
+                self.refresh();
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    // ── Rendering ──────────────────────────────────────────────────────────
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let rows = Layout::vertical([
+            Constraint::Length(1), // Campaign / filter bar
+            Constraint::Min(8),    // Entities + graph
+            Constraint::Length(1), // Key hints
+        ])
+        .split(area);
+
+        self.render_header(frame, rows[0]);
+
+        let cols = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+            .split(rows[1]);
+        self.render_entities(frame, cols[0]);
+
+        let right = Layout::vertical([Constraint::Min(6), Constraint::Length(10)]).split(cols[1]);
+        self.render_graph(frame, right[0]);
+        self.render_relationships(frame, right[1]);
+
+        self.render_hints(frame, rows[2]);
+
+        match self.modal {
+            Some(GraphModal::Add) | Some(GraphModal::Edit(_)) => self.render_form(frame, area),
+            Some(GraphModal::Delete(ref id)) => self.render_delete_modal(frame, area, id),
+            None => {}
+        }
+    }
+
+    fn render_header(&self, frame: &mut Frame, area: Rect) {
+        let label = |s: &'static str| Span::styled(s, Style::default().fg(theme::TEXT_DIM));
+        let value = |s: String| Span::styled(s, Style::default().fg(theme::ACCENT));
+
+        let focus = match self.focus {
+            Some(ref id) => format!("{} (depth {})", self.entity_name(id), self.depth),
+            None => "whole campaign".to_string(),
+        };
+        let types = self
+            .type_filter
+            .as_ref()
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| "all".to_string());
+
+        let mut spans = vec![
+            label(" Campaign: "),
+            value(self.campaign_name().to_string()),
+            label("  Focus: "),
+            value(focus),
+            label("  Type: "),
+            value(types),
+        ];
+        if self.include_inactive {
+            spans.push(label("  +ended"));
+        }
+        spans.push(if self.player_view {
+            Span::styled("  PLAYER VIEW", Style::default().fg(theme::WARNING))
+        } else {
+            Span::styled("  GM view", Style::default().fg(theme::TEXT_MUTED))
+        });
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn render_entities(&self, frame: &mut Frame, area: Rect) {
+        let title = format!("Entities ({})", self.entities.len());
+        let block = if self.pane == Pane::Entities {
+            theme::block_focused(&title)
+        } else {
+            theme::block_default(&title)
+        };
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.entities.is_empty() {
+            let msg = if self.loading {
+                " Loading…"
+            } else if self.campaign_id.is_none() {
+                " No campaigns yet"
+            } else {
+                " No NPCs or locations yet"
+            };
+            frame.render_widget(
+                Paragraph::new(Span::styled(msg, Style::default().fg(theme::TEXT_DIM))),
+                inner,
+            );
+            return;
+        }
+
+        let visible = inner.height as usize;
+        let start = self.entity_cursor.saturating_sub(visible.saturating_sub(1));
+        let lines: Vec<Line> = self
+            .entities
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible)
+            .map(|(i, entity)| {
+                let selected = i == self.entity_cursor;
+                let node_type = node_type(&entity.entity_type);
+                let is_focus = self.focus.as_deref() == Some(entity.id.as_str());
+                let name_style = if selected {
+                    Style::default()
+                        .fg(theme::ACCENT)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme::TEXT)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        if selected { "▸ " } else { "  " },
+                        Style::default().fg(theme::ACCENT),
+                    ),
+                    Span::styled(
+                        format!("{:<4}", entity_tag(&entity.entity_type)),
+                        Style::default().fg(node_type.color()),
+                    ),
+                    Span::styled(entity.name.clone(), name_style),
+                    Span::styled(
+                        if is_focus { " ◎" } else { "" },
+                        Style::default().fg(theme::SUCCESS),
+                    ),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_graph(&self, frame: &mut Frame, area: Rect) {
+        let title = format!(
+            "Graph — {} entities, {} relationships",
+            self.graph.nodes.len(),
+            self.graph.edges.len()
+        );
+        let block = theme::block_default(&title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.graph.edges.is_empty() {
+            let msg = if self.focus.is_some() {
+                " No relationships within range — try +, f or t"
+            } else {
+                " No relationships — select an entity and press a"
+            };
+            frame.render_widget(
+                Paragraph::new(Span::styled(msg, Style::default().fg(theme::TEXT_DIM))),
+                inner,
+            );
+            return;
+        }
+
+        let (nodes, edges) = widget_graph(&self.graph, self.focus.as_deref());
+        let layout = if self.focus.is_some() {
+            GraphLayout::Tree
+        } else {
+            GraphLayout::Flat
+        };
+        let widget = AsciiGraph::new(&nodes, &edges)
+            .layout(layout)
+            .selected(self.selected_entity().map(|e| e.id.as_str()))
+            .scroll(self.graph_scroll);
+        frame.render_widget(widget, inner);
+    }
+
+    fn render_relationships(&self, frame: &mut Frame, area: Rect) {
+        let title = match self.selected_entity() {
+            Some(entity) => format!("Relationships of {}", entity.name),
+            None => "Relationships".to_string(),
+        };
+        let block = if self.pane == Pane::Relationships {
+            theme::block_focused(&title)
+        } else {
+            theme::block_default(&title)
+        };
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let muted = Style::default().fg(theme::TEXT_MUTED);
+        if self.entity_relationships.is_empty() {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    " None — press a to add one",
+                    Style::default().fg(theme::TEXT_DIM),
+                )),
+                inner,
+            );
+            return;
+        }
+
+        let selected_id = self.selected_entity().map(|e| e.id.as_str());
+        let mut lines: Vec<Line> = Vec::new();
+        for (i, rel) in self.entity_relationships.iter().enumerate() {
+            let selected = self.pane == Pane::Relationships && i == self.rel_cursor;
+            let (arrow, other) = if Some(rel.source_id.as_str()) == selected_id {
+                ("→", rel.target_name.as_str())
+            } else {
+                ("←", rel.source_name.as_str())
+            };
+            let mut spans = vec![
+                Span::styled(
+                    if selected { "▸ " } else { "  " },
+                    Style::default().fg(theme::ACCENT),
+                ),
+                Span::styled(
+                    format!("{} ", rel.relationship_type),
+                    Style::default().fg(theme::PRIMARY_LIGHT),
+                ),
+                Span::styled(format!("{arrow} "), muted),
+                Span::styled(
+                    other.to_string(),
+                    if selected {
+                        Style::default()
+                            .fg(theme::ACCENT)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme::TEXT)
+                    },
+                ),
+                Span::styled(format!("  {:?}", rel.strength), muted),
+            ];
+            if !rel.is_known {
+                spans.push(Span::styled(
+                    "  secret",
+                    Style::default().fg(theme::WARNING),
+                ));
+            }
+            if !rel.is_active {
+                spans.push(Span::styled(
+                    "  ended",
+                    Style::default().fg(theme::TEXT_DIM),
+                ));
+            }
+            lines.push(Line::from(spans));
+            if selected && !rel.description.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", rel.description),
+                    muted,
+                )));
+            }
+        }
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_hints(&self, frame: &mut Frame, area: Rect) {
+        let hint = |key: &'static str, desc: &'static str| {
+            [
+                Span::styled(key, theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(desc, theme::key_hint()),
+            ]
+        };
+        let spans: Vec<Span> = [
+            hint(" Enter", ":focus "),
+            hint("f", ":unfocus "),
+            hint("+/-", ":depth "),
+            hint("t", ":type "),
+            hint("p", ":player view "),
+            hint("i", ":ended "),
+            hint("Tab", ":pane "),
+            hint("a/e/d", ":add/edit/del "),
+            hint("s", ":campaign"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let line = match (&self.error, &self.modal) {
+            (Some(err), None) => Line::from(Span::styled(
+                format!(" ✗ {err}"),
+                Style::default().fg(theme::ERROR),
+            )),
+            _ => Line::from(spans),
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+
+    fn render_form(&self, frame: &mut Frame, area: Rect) {
+        let modal_area = centered_rect(60, 50, area);
+        frame.render_widget(Clear, modal_area);
+
+        let source = self
+            .selected_entity()
+            .map(|e| e.name.as_str())
+            .unwrap_or("?");
+        let title = match self.modal {
+            Some(GraphModal::Edit(ref id)) => {
+                let rel = self.entity_relationships.iter().find(|r| &r.id == id);
+                match rel {
+                    Some(rel) => format!(" Edit: {} → {} ", rel.source_name, rel.target_name),
+                    None => " Edit Relationship ".to_string(),
+                }
+            }
+            _ => format!(" New Relationship from {source} "),
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::ACCENT));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let fields = self.form_fields();
+        let focused = fields[self.form_focus];
+        let field_line = |field: FormField, label: &str, value: String| {
+            let is_focused = field == focused;
+            let label_style = if is_focused {
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::TEXT_MUTED)
+            };
+            Line::from(vec![
+                Span::raw(if is_focused { " ▸ " } else { "   " }),
+                Span::styled(format!("{label:<13}"), label_style),
+                Span::styled(value, Style::default().fg(theme::TEXT)),
+            ])
+        };
+        let choice = |field: FormField, value: String| {
+            if field == focused {
+                format!("◂ {value} ▸")
+            } else {
+                value
+            }
+        };
+        let check = |on: bool| (if on { "[x]" } else { "[ ]" }).to_string();
+
+        let mut lines = vec![Line::raw("")];
+        for &field in fields {
+            match field {
+                FormField::Target => {
+                    let text = self.form_target.text();
+                    let value = if field == focused {
+                        format!("{text}▎")
+                    } else {
+                        text.to_string()
+                    };
+                    lines.push(field_line(field, "Target:", value));
+                    if field == focused {
+                        for entity in self.target_suggestions() {
+                            lines.push(Line::from(vec![
+                                Span::raw("                  "),
+                                Span::styled(
+                                    format!("{:<4}", entity_tag(&entity.entity_type)),
+                                    Style::default().fg(node_type(&entity.entity_type).color()),
+                                ),
+                                Span::styled(
+                                    entity.name.clone(),
+                                    Style::default().fg(theme::TEXT_MUTED),
+                                ),
+                            ]));
+                        }
+                    }
+                }
+                FormField::TargetType => {
+                    let existing = self
+                        .resolve_target()
+                        .is_some_and(|t| self.entities.iter().any(|e| e.id == t.id));
+                    let value = if existing {
+                        "existing entity".to_string()
+                    } else {
+                        choice(field, EntityType::ALL[self.form_target_type].to_string())
+                    };
+                    lines.push(field_line(field, "New as:", value));
+                }
+                FormField::Type => lines.push(field_line(
+                    field,
+                    "Type:",
+                    choice(field, RelationshipType::ALL[self.form_type].to_string()),
+                )),
+                FormField::Strength => lines.push(field_line(
+                    field,
+                    "Strength:",
+                    choice(field, format!("{:?}", STRENGTHS[self.form_strength])),
+                )),
+                FormField::Secret => lines.push(field_line(
+                    field,
+                    "Secret:",
+                    format!("{} hidden in player view", check(self.form_secret)),
+                )),
+                FormField::Active => {
+                    lines.push(field_line(field, "Active:", check(self.form_active)))
+                }
+                FormField::Description => {
+                    let text = self.form_description.text();
+                    let value = if field == focused {
+                        format!("{text}▎")
+                    } else {
+                        text.to_string()
+                    };
+                    lines.push(field_line(field, "Description:", value));
+                }
+            }
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(match self.error {
+            Some(ref err) => Line::from(Span::styled(
+                format!(" ✗ {err}"),
+                Style::default().fg(theme::ERROR),
+            )),
+            None => Line::from(vec![
+                Span::styled(" Tab", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":field ", theme::key_hint()),
+                Span::styled("↑/↓", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":pick ", theme::key_hint()),
+                Span::styled("←/→", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":change ", theme::key_hint()),
+                Span::styled("Enter", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":save ", theme::key_hint()),
+                Span::styled("Esc", theme::key_hint().add_modifier(Modifier::BOLD)),
+                Span::styled(":cancel", theme::key_hint()),
+            ]),
+        });
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+
+    fn render_delete_modal(&self, frame: &mut Frame, area: Rect, id: &str) {
+        let modal_area = centered_rect(40, 20, area);
+        frame.render_widget(Clear, modal_area);
+
+        let description = self
+            .entity_relationships
+            .iter()
+            .find(|r| r.id == id)
+            .map(|r| {
+                format!(
+                    "{} {} {}",
+                    r.source_name, r.relationship_type, r.target_name
+                )
+            })
+            .unwrap_or_else(|| "?".to_string());
+        let block = Block::default()
+            .title(" Delete Relationship ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::ERROR));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let lines = vec![
+            Line::raw(""),
+            Line::from(vec![
+                Span::raw("  Delete "),
+                Span::styled(
+                    description,
+                    Style::default()
+                        .fg(theme::ACCENT)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("?"),
+            ]),
+            Line::raw(""),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled("y/Enter", Style::default().fg(theme::SUCCESS)),
+                Span::raw(" to confirm, "),
+                Span::styled("n/Esc", Style::default().fg(theme::ERROR)),
+                Span::raw(" to cancel"),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+}
+
+// ── Free helpers ───────────────────────────────────────────────────────────
+
+fn node_type(entity_type: &EntityType) -> NodeType {
+    match entity_type {
+        EntityType::PC | EntityType::NPC => NodeType::Npc,
+        EntityType::Location => NodeType::Location,
+        EntityType::Faction => NodeType::Faction,
+        EntityType::Item => NodeType::Item,
+        _ => NodeType::Custom,
+    }
+}
+
+fn entity_tag(entity_type: &EntityType) -> &'static str {
+    match entity_type {
+        EntityType::PC => "PC",
+        EntityType::NPC => "NPC",
+        EntityType::Location => "LOC",
+        EntityType::Faction => "FAC",
+        EntityType::Item => "ITM",
+        EntityType::Event => "EVT",
+        EntityType::Quest => "QST",
+        EntityType::Deity => "GOD",
+        EntityType::Creature => "CRE",
+        EntityType::Custom(_) => "---",
+    }
+}
+
+/// Convert an `EntityGraph` into the `AsciiGraph` widget's types. With a focal
+/// entity the nodes form a breadth-first tree rooted at it; otherwise they
+/// are a flat, name-sorted list.
+fn widget_graph(
+    graph: &EntityGraph,
+    focus: Option<&str>,
+) -> (Vec<ascii_graph::GraphNode>, Vec<ascii_graph::GraphEdge>) {
+    let edges: Vec<ascii_graph::GraphEdge> = graph
+        .edges
+        .iter()
+        .map(|e| {
+            let mut label = e.label.clone();
+            if !e.is_known {
+                label.push_str(", secret");
+            }
+            if !e.is_active {
+                label.push_str(", ended");
+            }
+            ascii_graph::GraphEdge {
+                source: e.source.clone(),
+                target: e.target.clone(),
+                label,
+            }
+        })
+        .collect();
+
+    let mut children: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut order: Vec<&str> = Vec::new();
+    if let Some(root) = focus.filter(|id| graph.nodes.iter().any(|n| n.id == *id)) {
+        let mut seen: HashSet<&str> = HashSet::from([root]);
+        let mut queue = VecDeque::from([root]);
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for edge in &graph.edges {
+                let next = if edge.source == id {
+                    edge.target.as_str()
+                } else if edge.target == id {
+                    edge.source.as_str()
+                } else {
+                    continue;
+                };
+                if seen.insert(next) {
+                    children.entry(id).or_default().push(next.to_string());
+                    queue.push_back(next);
+                }
+            }
+        }
+    } else {
+        let mut sorted: Vec<_> = graph.nodes.iter().collect();
+        sorted.sort_by_key(|n| n.name.to_lowercase());
+        order = sorted.into_iter().map(|n| n.id.as_str()).collect();
+    }
+
+    let nodes = order
+        .into_iter()
+        .filter_map(|id| graph.nodes.iter().find(|n| n.id == id))
+        .map(|n| ascii_graph::GraphNode {
+            id: n.id.clone(),
+            label: n.name.clone(),
+            node_type: node_type(&n.entity_type),
+            children: children.remove(n.id.as_str()).unwrap_or_default(),
+        })
+        .collect();
+    (nodes, edges)
+}
+
+fn route_text_input(buf: &mut InputBuffer, code: KeyCode, modifiers: KeyModifiers) {
+    match (modifiers, code) {
+        (KeyModifiers::NONE, KeyCode::Char(c)) | (KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+            buf.insert_char(c);
+        }
+        (KeyModifiers::NONE, KeyCode::Backspace) => buf.backspace(),
+        (KeyModifiers::NONE, KeyCode::Delete) => buf.delete(),
+        (KeyModifiers::NONE, KeyCode::Left) => buf.move_left(),
+        (KeyModifiers::NONE, KeyCode::Right) => buf.move_right(),
+        (KeyModifiers::NONE, KeyCode::Home) => buf.move_home(),
+        (KeyModifiers::NONE, KeyCode::End) => buf.move_end(),
+        _ => {}
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager_with_graph() -> RelationshipManager {
+        let manager = RelationshipManager::default();
+        let rel = |source: (&str, EntityType), target: (&str, EntityType), t| {
+            EntityRelationship::new(
+                "c1", source.0, source.1, source.0, target.0, target.1, target.0, t,
+            )
+        };
+        manager
+            .create_relationship(rel(
+                ("Alice", EntityType::NPC),
+                ("Zhentarim", EntityType::Faction),
+                RelationshipType::MemberOf,
+            ))
+            .unwrap();
+        manager
+            .create_relationship(rel(
+                ("Bob", EntityType::NPC),
+                ("Zhentarim", EntityType::Faction),
+                RelationshipType::LeaderOf,
+            ))
+            .unwrap();
+        manager
+            .create_relationship(
+                rel(
+                    ("Alice", EntityType::NPC),
+                    ("Bob", EntityType::NPC),
+                    RelationshipType::Enemy,
+                )
+                .as_secret(),
+            )
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_widget_graph_builds_tree_from_focus() {
+        let graph = manager_with_graph().get_entity_graph("c1", false);
+        let (nodes, edges) = widget_graph(&graph, Some("Alice"));
+
+        assert_eq!(nodes[0].id, "Alice");
+        let mut roots_children = nodes[0].children.clone();
+        roots_children.sort();
+        assert_eq!(roots_children, vec!["Bob", "Zhentarim"]);
+        // Each node appears once, even though the graph has a cycle
+        assert_eq!(nodes.len(), 3);
+        assert!(nodes[1..].iter().all(|n| n.children.is_empty()));
+        assert!(edges.iter().any(|e| e.label == "Enemy, secret"));
+    }
+
+    #[test]
+    fn test_widget_graph_flat_without_focus() {
+        let graph = manager_with_graph().get_entity_graph("c1", false);
+        let (nodes, _) = widget_graph(&graph, None);
+        let names: Vec<&str> = nodes.iter().map(|n| n.label.as_str()).collect();
+        assert_eq!(names, vec!["Alice", "Bob", "Zhentarim"]);
+        assert!(nodes.iter().all(|n| n.children.is_empty()));
+        assert_eq!(nodes[2].node_type, NodeType::Faction);
+    }
+
+    #[test]
+    fn test_player_view_hides_secret_edges() {
+        let manager = manager_with_graph();
+        let filter = GraphFilter {
+            hide_secret: true,
+            ..Default::default()
+        };
+        let graph = manager.get_filtered_graph("c1", &filter);
+        let (_, edges) = widget_graph(&graph, None);
+        assert_eq!(edges.len(), 2);
+        assert!(edges.iter().all(|e| !e.label.contains("secret")));
+    }
+}
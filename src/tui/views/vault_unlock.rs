@@ -0,0 +1,344 @@
+//! Credential vault unlock modal.
+//!
+//! Shown at startup when `[credentials] backend = "vault"` and the vault is
+//! still locked. A vault without a passphrase yet is created here, with the
+//! passphrase entered twice. Esc skips: API keys and OAuth tokens stay
+//! unavailable until the next start.
+
+use std::sync::Arc;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::core::vault::{CredentialVault, VaultError};
+use crate::tui::app::centered_rect;
+use crate::tui::theme;
+use crate::tui::widgets::input_buffer::InputBuffer;
+
+/// Outcome of a key press in the unlock modal.
+#[derive(Debug, PartialEq, Eq)]
+pub enum UnlockResult {
+    /// Key handled; keep the modal open.
+    Consumed,
+    /// The vault is now unlocked.
+    Unlocked,
+    /// The user skipped unlocking.
+    Skipped,
+}
+
+/// State for the vault unlock modal.
+pub struct VaultUnlockState {
+    vault: Arc<CredentialVault>,
+    /// True when the vault has no passphrase yet and one is being chosen.
+    creating: bool,
+    passphrase: InputBuffer,
+    confirm: InputBuffer,
+    /// Whether the confirmation field has focus (create mode only).
+    confirming: bool,
+    error: Option<String>,
+}
+
+impl VaultUnlockState {
+    pub fn new(vault: Arc<CredentialVault>) -> Self {
+        let creating = !vault.is_initialized();
+        Self {
+            vault,
+            creating,
+            passphrase: InputBuffer::new(),
+            confirm: InputBuffer::new(),
+            confirming: false,
+            error: None,
+        }
+    }
+
+    /// Handle input events while the modal is open.
+    pub fn handle_input(&mut self, event: &Event) -> UnlockResult {
+        let Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        else {
+            return UnlockResult::Consumed;
+        };
+
+        match code {
+            KeyCode::Esc => return UnlockResult::Skipped,
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Tab | KeyCode::BackTab if self.creating => {
+                self.confirming = !self.confirming;
+            }
+            KeyCode::Backspace => {
+                self.field_mut().backspace();
+                self.error = None;
+            }
+            KeyCode::Delete => {
+                self.field_mut().delete();
+                self.error = None;
+            }
+            KeyCode::Left => self.field_mut().move_left(),
+            KeyCode::Right => self.field_mut().move_right(),
+            KeyCode::Home => self.field_mut().move_home(),
+            KeyCode::End => self.field_mut().move_end(),
+            KeyCode::Char(c) => {
+                self.field_mut().insert_char(*c);
+                self.error = None;
+            }
+            _ => {}
+        }
+        UnlockResult::Consumed
+    }
+
+    fn field_mut(&mut self) -> &mut InputBuffer {
+        if self.confirming {
+            &mut self.confirm
+        } else {
+            &mut self.passphrase
+        }
+    }
+
+    fn submit(&mut self) -> UnlockResult {
+        if self.passphrase.is_empty() {
+            self.error = Some("Enter a passphrase".to_string());
+            return UnlockResult::Consumed;
+        }
+
+        if self.creating && !self.confirming {
+            self.confirming = true;
+            return UnlockResult::Consumed;
+        }
+
+        let result = if self.creating {
+            if self.passphrase.text() != self.confirm.text() {
+                self.error = Some("Passphrases do not match".to_string());
+                self.confirm.clear();
+                return UnlockResult::Consumed;
+            }
+            self.vault.initialize(self.passphrase.text())
+        } else {
+            self.vault.unlock(self.passphrase.text())
+        };
+
+        match result {
+            Ok(()) => {
+                self.passphrase.clear();
+                self.confirm.clear();
+                UnlockResult::Unlocked
+            }
+            Err(VaultError::WrongPassphrase) => {
+                self.error = Some("Wrong passphrase".to_string());
+                self.passphrase.clear();
+                UnlockResult::Consumed
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                UnlockResult::Consumed
+            }
+        }
+    }
+
+    // ── Rendering ──────────────────────────────────────────────────────
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let modal = centered_rect(50, 40, area);
+        frame.render_widget(Clear, modal);
+
+        let title = if self.creating {
+            " Create Credential Vault "
+        } else {
+            " Unlock Credential Vault "
+        };
+        let block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::ACCENT))
+            .style(Style::default().bg(theme::BG_BASE));
+
+        let inner = block.inner(modal);
+        frame.render_widget(block, modal);
+
+        let confirm_height = if self.creating { 3 } else { 0 };
+        let chunks = Layout::vertical([
+            Constraint::Length(2),              // Explanation
+            Constraint::Length(3),              // Passphrase
+            Constraint::Length(confirm_height), // Confirm
+            Constraint::Length(1),              // Error
+            Constraint::Min(0),
+            Constraint::Length(1), // Hint
+        ])
+        .split(inner);
+
+        let intro = if self.creating {
+            " Choose a passphrase to encrypt API keys and OAuth tokens."
+        } else {
+            " API keys and OAuth tokens are locked."
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                intro,
+                Style::default().fg(theme::TEXT),
+            ))),
+            chunks[0],
+        );
+
+        self.render_field(
+            frame,
+            chunks[1],
+            " Passphrase ",
+            &self.passphrase,
+            !self.confirming,
+        );
+        if self.creating {
+            self.render_field(
+                frame,
+                chunks[2],
+                " Confirm ",
+                &self.confirm,
+                self.confirming,
+            );
+        }
+
+        if let Some(ref err) = self.error {
+            frame.render_widget(
+                Paragraph::new(Line::from(Span::styled(
+                    format!(" {err}"),
+                    Style::default().fg(theme::ERROR),
+                ))),
+                chunks[3],
+            );
+        }
+
+        let mut hint = vec![
+            Span::styled(" Enter", theme::key_hint()),
+            Span::styled(":submit ", Style::default().fg(theme::TEXT_DIM)),
+        ];
+        if self.creating {
+            hint.push(Span::styled("Tab", theme::key_hint()));
+            hint.push(Span::styled(
+                ":switch field ",
+                Style::default().fg(theme::TEXT_DIM),
+            ));
+        }
+        hint.push(Span::styled("Esc", theme::key_hint()));
+        hint.push(Span::styled(":skip", Style::default().fg(theme::TEXT_DIM)));
+        frame.render_widget(Paragraph::new(Line::from(hint)), chunks[5]);
+    }
+
+    fn render_field(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        input: &InputBuffer,
+        focused: bool,
+    ) {
+        let border = if focused {
+            theme::PRIMARY_LIGHT
+        } else {
+            theme::TEXT_DIM
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        // Never draw the passphrase itself
+        let masked = "•".repeat(input.text().chars().count());
+        frame.render_widget(
+            Paragraph::new(Span::styled(masked, Style::default().fg(theme::TEXT))),
+            inner,
+        );
+
+        if focused {
+            frame.set_cursor_position((inner.x + input.cursor_position() as u16, inner.y));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use tempfile::tempdir;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn type_text(state: &mut VaultUnlockState, text: &str) {
+        for c in text.chars() {
+            state.handle_input(&key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_create_requires_matching_confirmation() {
+        let dir = tempdir().unwrap();
+        let vault = Arc::new(CredentialVault::open(dir.path().join("v.vault")).unwrap());
+        let mut state = VaultUnlockState::new(vault.clone());
+        assert!(state.creating);
+
+        type_text(&mut state, "secret");
+        assert_eq!(
+            state.handle_input(&key(KeyCode::Enter)),
+            UnlockResult::Consumed
+        );
+        assert!(state.confirming);
+
+        type_text(&mut state, "other");
+        assert_eq!(
+            state.handle_input(&key(KeyCode::Enter)),
+            UnlockResult::Consumed
+        );
+        assert!(state.error.is_some());
+        assert!(!vault.is_unlocked());
+
+        type_text(&mut state, "secret");
+        assert_eq!(
+            state.handle_input(&key(KeyCode::Enter)),
+            UnlockResult::Unlocked
+        );
+        assert!(vault.is_unlocked());
+    }
+
+    #[test]
+    fn test_unlock_wrong_passphrase() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("v.vault");
+        CredentialVault::open(&path)
+            .unwrap()
+            .initialize("secret")
+            .unwrap();
+
+        let vault = Arc::new(CredentialVault::open(&path).unwrap());
+        let mut state = VaultUnlockState::new(vault.clone());
+        assert!(!state.creating);
+
+        type_text(&mut state, "nope");
+        assert_eq!(
+            state.handle_input(&key(KeyCode::Enter)),
+            UnlockResult::Consumed
+        );
+        assert_eq!(state.error.as_deref(), Some("Wrong passphrase"));
+        assert!(state.passphrase.is_empty());
+
+        type_text(&mut state, "secret");
+        assert_eq!(
+            state.handle_input(&key(KeyCode::Enter)),
+            UnlockResult::Unlocked
+        );
+        assert_eq!(
+            state.handle_input(&key(KeyCode::Esc)),
+            UnlockResult::Skipped
+        );
+    }
+}
@@ -28,7 +28,7 @@ pub enum NodeType {
 
 impl NodeType {
     /// Return the theme color for this node type.
-    fn color(self) -> ratatui::style::Color {
+    pub fn color(self) -> ratatui::style::Color {
         match self {
             NodeType::Npc => theme::PRIMARY_LIGHT,
             NodeType::Location => theme::WARNING,
@@ -211,6 +211,10 @@ impl<'a> AsciiGraph<'a> {
         // Edges outgoing from this node (label annotations on the branch).
         for edge in self.edges.iter().filter(|e| e.source == node_id) {
             if !edge.label.is_empty() {
+                let target_label = self
+                    .find_node(&edge.target)
+                    .map(|n| n.label.as_str())
+                    .unwrap_or(edge.target.as_str());
                 let edge_line = Line::from(vec![
                     Span::raw(format!("{continuation}  ")),
                     Span::styled(
@@ -218,7 +222,7 @@ impl<'a> AsciiGraph<'a> {
                         Style::default().fg(theme::TEXT_DIM),
                     ),
                     Span::styled(
-                        edge.target.clone(),
+                        target_label.to_string(),
                         Style::default().fg(theme::TEXT_MUTED),
                     ),
                 ]);